@@ -19,6 +19,10 @@
 //! output_rust = "src/generated.rs"
 //! output_ts = "app/src/generated.ts"
 //! ```
+//!
+//! Commands run through the `lumos` CLI, so a schema that imports other files
+//! or declares modules is resolved as one project, and `cargo lumos watch`
+//! regenerates when any file of it changes.
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
@@ -29,8 +29,8 @@ pub enum Commands {
 
         /// Target languages (comma-separated: rust,typescript,python,go,ruby)
         ///
-        /// Supported: rust (rs), typescript (ts)
-        /// Planned: python (py), go, ruby (rb)
+        /// Supported: rust (rs), typescript (ts), python (py), go, ruby (rb),
+        /// seahorse, jsonschema (JSON Schema, written to generated.json)
         ///
         /// Default: rust,typescript
         #[arg(short = 'l', long, default_value = "rust,typescript")]
@@ -62,6 +62,21 @@ pub enum Commands {
         /// Show diff and ask for confirmation before writing
         #[arg(short = 'd', long)]
         show_diff: bool,
+
+        /// Write one file per schema module, mirroring the module tree
+        ///
+        /// Rust gets a mod.rs hierarchy and TypeScript a folder per module
+        /// with index.ts barrels, under OUTPUT/generated/. Types in different
+        /// modules may then share a name. Supported for rust and typescript.
+        #[arg(long)]
+        module_tree: bool,
+
+        /// Fail instead of updating lumos.lock
+        ///
+        /// Imported packages must resolve to the versions and content hashes
+        /// already recorded in lumos.lock, for reproducible builds.
+        #[arg(long)]
+        locked: bool,
     },
 
     /// Validate schema syntax without generating code
@@ -70,6 +85,20 @@ pub enum Commands {
         schema: PathBuf,
     },
 
+    /// Publish a schema package to the local registry
+    ///
+    /// Copies the schema and every file it loads into the [registry] named
+    /// in lumos.toml, as the version in its [package] section. Published
+    /// versions are never overwritten.
+    Publish {
+        /// Entry schema of the package (default: [package] entry in lumos.toml)
+        schema: Option<PathBuf>,
+
+        /// Show what would be published without copying files
+        #[arg(short = 'n', long)]
+        dry_run: bool,
+    },
+
     /// Initialize a new LUMOS project
     Init {
         /// Project name (optional, defaults to current directory)
@@ -114,6 +143,24 @@ pub enum Commands {
         command: FuzzCommands,
     },
 
+    /// Print the resolved schema model (IR) for other tools
+    Ir {
+        /// Path to .lumos schema file
+        schema: PathBuf,
+
+        /// Output format (json)
+        #[arg(short, long, default_value = "json")]
+        format: String,
+
+        /// Print the parsed AST of the schema file instead of the IR
+        #[arg(long)]
+        ast: bool,
+
+        /// Output file path (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
     /// Compare two schema files and show differences
     Diff {
         /// Path to first .lumos schema file (v1)
@@ -6,8 +6,8 @@
 use anyhow::Result;
 use colored::Colorize;
 use lumos_core::anchor::{
-    generate_accounts_context, parse_anchor_attrs, IdlGenerator, IdlGeneratorConfig,
-    InstructionAccount, InstructionContext,
+    generate_accounts_context, parse_anchor_attrs, zero_copy_type_names, IdlGenerator,
+    IdlGeneratorConfig, InstructionAccount, InstructionArg, InstructionContext,
 };
 use lumos_core::generators::typescript;
 use lumos_core::ir::TypeDefinition;
@@ -93,8 +93,15 @@ pub fn run_idl(
             println!("  Address: {}", addr);
         }
     }
+    println!("  Instructions: {}", idl.instructions.len());
     println!("  Accounts: {}", account_count);
     println!("  Types: {}", type_count);
+    if !idl.events.is_empty() {
+        println!("  Events: {}", idl.events.len());
+    }
+    if !idl.errors.is_empty() {
+        println!("  Errors: {}", idl.errors.len());
+    }
 
     Ok(())
 }
@@ -261,6 +268,8 @@ pub fn run_generate(
     // Collect accounts, instructions, and types
     let mut accounts = Vec::new();
     let mut instructions = Vec::new();
+    let mut events = Vec::new();
+    let mut errors = Vec::new();
     let mut other_types = Vec::new();
 
     for type_def in &type_defs {
@@ -268,16 +277,22 @@ pub fn run_generate(
             TypeDefinition::Struct(s) => {
                 let is_account = s.metadata.attributes.iter().any(|a| a == "account");
                 let is_instruction = s.metadata.is_instruction;
+                let is_event = s.is_event();
 
                 if is_instruction {
                     instructions.push(s);
+                } else if is_event {
+                    events.push(s);
                 } else if is_account {
                     accounts.push(s);
                 } else {
                     other_types.push(type_def);
                 }
             }
-            TypeDefinition::Enum(_) | TypeDefinition::TypeAlias(_) => {
+            TypeDefinition::Enum(e) if e.is_error_code() => {
+                errors.push(e);
+            }
+            TypeDefinition::Enum(_) | TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {
                 other_types.push(type_def);
             }
         }
@@ -310,7 +325,11 @@ pub fn run_generate(
 
         for account in &accounts {
             // Account struct
-            rust_output.push_str("#[account]\n");
+            if account.is_zero_copy() {
+                rust_output.push_str("#[account(zero_copy)]\n");
+            } else {
+                rust_output.push_str("#[account]\n");
+            }
             rust_output.push_str(&format!("pub struct {} {{\n", account.name));
             for field in &account.fields {
                 let rust_type = type_info_to_rust_type(&field.type_info);
@@ -327,6 +346,55 @@ pub fn run_generate(
         }
     }
 
+    // Generate event structs (emitted with `emit!`)
+    if !events.is_empty() {
+        rust_output.push_str(
+            "// ============================================================================\n",
+        );
+        rust_output.push_str("// Events\n");
+        rust_output.push_str(
+            "// ============================================================================\n\n",
+        );
+
+        for event in &events {
+            rust_output.push_str("#[event]\n");
+            rust_output.push_str(&format!("pub struct {} {{\n", event.name));
+            for field in &event.fields {
+                let rust_type = type_info_to_rust_type(&field.type_info);
+                rust_output.push_str(&format!("    pub {}: {},\n", field.name, rust_type));
+            }
+            rust_output.push_str("}\n\n");
+        }
+    }
+
+    // Generate program error enums (returned with `err!`)
+    if !errors.is_empty() {
+        rust_output.push_str(
+            "// ============================================================================\n",
+        );
+        rust_output.push_str("// Errors\n");
+        rust_output.push_str(
+            "// ============================================================================\n\n",
+        );
+
+        for error_enum in &errors {
+            match error_enum.metadata.error_code_offset {
+                Some(offset) => {
+                    rust_output.push_str(&format!("#[error_code(offset = {})]\n", offset))
+                }
+                None => rust_output.push_str("#[error_code]\n"),
+            }
+            rust_output.push_str(&format!("pub enum {} {{\n", error_enum.name));
+            for variant in &error_enum.variants {
+                if let Some(msg) = variant.msg() {
+                    rust_output.push_str(&format!("    #[msg({:?})]\n", msg));
+                }
+                rust_output.push_str(&format!("    {},\n", variant.name()));
+            }
+            rust_output.push_str("}\n\n");
+        }
+    }
+
     // Generate instruction contexts
     if !instructions.is_empty() {
         rust_output.push_str(
@@ -337,6 +405,8 @@ pub fn run_generate(
             "// ============================================================================\n\n",
         );
 
+        let zero_copy_types = zero_copy_type_names(&type_defs);
+
         for instruction in &instructions {
             // Build InstructionContext from the struct
             let mut ctx_accounts = Vec::new();
@@ -347,7 +417,7 @@ pub fn run_generate(
                     attrs.extend(parse_anchor_attrs(attr_str));
                 }
 
-                let account_type = infer_anchor_account_type(&field.type_info);
+                let account_type = infer_anchor_account_type(&field.type_info, &zero_copy_types);
 
                 ctx_accounts.push(InstructionAccount {
                     name: field.name.clone(),
@@ -358,10 +428,20 @@ pub fn run_generate(
                 });
             }
 
+            let args = instruction
+                .metadata
+                .instruction_args
+                .iter()
+                .map(|arg| InstructionArg {
+                    name: arg.name.clone(),
+                    ty: arg.type_info.clone(),
+                })
+                .collect();
+
             let ctx = InstructionContext {
                 name: instruction.name.clone(),
                 accounts: ctx_accounts,
-                args: vec![],
+                args,
             };
 
             // Generate the #[derive(Accounts)] context
@@ -399,10 +479,12 @@ pub fn run_generate(
                     rust_output.push_str(&format!("pub enum {} {{\n", e.name));
                     for variant in &e.variants {
                         match variant {
-                            lumos_core::ir::EnumVariantDefinition::Unit { name } => {
+                            lumos_core::ir::EnumVariantDefinition::Unit { name, .. } => {
                                 rust_output.push_str(&format!("    {},\n", name));
                             }
-                            lumos_core::ir::EnumVariantDefinition::Tuple { name, types } => {
+                            lumos_core::ir::EnumVariantDefinition::Tuple {
+                                name, types, ..
+                            } => {
                                 let type_strs: Vec<String> =
                                     types.iter().map(type_info_to_rust_type).collect();
                                 rust_output.push_str(&format!(
@@ -411,7 +493,9 @@ pub fn run_generate(
                                     type_strs.join(", ")
                                 ));
                             }
-                            lumos_core::ir::EnumVariantDefinition::Struct { name, fields } => {
+                            lumos_core::ir::EnumVariantDefinition::Struct {
+                                name, fields, ..
+                            } => {
                                 rust_output.push_str(&format!("    {} {{\n", name));
                                 for field in fields {
                                     let rust_type = type_info_to_rust_type(&field.type_info);
@@ -429,6 +513,12 @@ pub fn run_generate(
                 TypeDefinition::TypeAlias(_) => {
                     // Type aliases are resolved, skip
                 }
+                TypeDefinition::Const(c) => {
+                    rust_output.push_str(&format!(
+                        "pub const {}: {} = {};\n\n",
+                        c.name, c.type_name, c.value
+                    ));
+                }
             }
         }
     }
@@ -497,6 +587,8 @@ pub fn run_generate(
     println!("  Version: {}", version);
     println!("  Accounts: {}", accounts.len());
     println!("  Instructions: {}", instructions.len());
+    println!("  Events: {}", events.len());
+    println!("  Errors: {}", errors.len());
     println!("  Other types: {}", other_types.len());
 
     if !dry_run {
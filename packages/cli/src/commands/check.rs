@@ -6,12 +6,11 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use lumos_core::generators::{rust, typescript};
-use lumos_core::parser::parse_lumos_file;
 use lumos_core::size_calculator::SizeCalculator;
-use lumos_core::transform::transform_to_ir;
 use std::fs;
 use std::path::Path;
 
+use crate::commands::generate::{resolve_project_cached, resolve_schema};
 use crate::utils::validate_output_path;
 
 /// Verify generated code is up-to-date
@@ -43,14 +42,8 @@ pub fn run(schema_path: &Path, output_dir: Option<&Path>) -> Result<()> {
         std::process::exit(1);
     }
 
-    // Read and parse schema
-    let content = fs::read_to_string(schema_path)
-        .with_context(|| format!("Failed to read schema file: {}", schema_path.display()))?;
-
-    let ast = parse_lumos_file(&content)
-        .with_context(|| format!("Failed to parse schema: {}", schema_path.display()))?;
-
-    let ir = transform_to_ir(ast).with_context(|| "Failed to transform AST to IR")?;
+    // Read and parse schema, reusing unchanged files from the cache
+    let (_graph, ir, _stats) = resolve_project_cached(schema_path)?;
 
     // Generate fresh code
     let fresh_rust = rust::generate_module(&ir);
@@ -93,13 +86,7 @@ pub fn run(schema_path: &Path, output_dir: Option<&Path>) -> Result<()> {
 /// Check account sizes and detect overflow
 pub fn run_size(schema_path: &Path, format: &str) -> Result<()> {
     // Read and parse schema
-    let content = fs::read_to_string(schema_path)
-        .with_context(|| format!("Failed to read schema file: {}", schema_path.display()))?;
-
-    let ast = parse_lumos_file(&content)
-        .with_context(|| format!("Failed to parse schema: {}", schema_path.display()))?;
-
-    let ir = transform_to_ir(ast).with_context(|| "Failed to transform AST to IR")?;
+    let (ir, _file_count) = resolve_schema(schema_path)?;
 
     if ir.is_empty() {
         eprintln!(
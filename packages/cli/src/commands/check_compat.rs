@@ -3,15 +3,15 @@
 
 //! Check compatibility command - verify backward compatibility between schema versions
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use colored::Colorize;
 use lumos_core::compat::{CompatibilityChecker, IssueLevel};
-use lumos_core::parser::parse_lumos_file;
-use lumos_core::transform::transform_to_ir;
+use lumos_core::transform::inline_newtypes;
 use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
 
+use crate::commands::generate::resolve_schema;
+
 /// Check backward compatibility between two schema versions
 pub fn run(
     from_schema_path: &Path,
@@ -20,23 +20,9 @@ pub fn run(
     verbose: bool,
     strict: bool,
 ) -> Result<()> {
-    // Read and parse old schema
-    let from_content = fs::read_to_string(from_schema_path)
-        .with_context(|| format!("Failed to read schema file: {}", from_schema_path.display()))?;
-
-    let from_ast = parse_lumos_file(&from_content)
-        .with_context(|| format!("Failed to parse schema: {}", from_schema_path.display()))?;
-
-    let from_ir = transform_to_ir(from_ast)?;
-
-    // Read and parse new schema
-    let to_content = fs::read_to_string(to_schema_path)
-        .with_context(|| format!("Failed to read schema file: {}", to_schema_path.display()))?;
-
-    let to_ast = parse_lumos_file(&to_content)
-        .with_context(|| format!("Failed to parse schema: {}", to_schema_path.display()))?;
-
-    let to_ir = transform_to_ir(to_ast)?;
+    // Newtypes share their target's wire format, so compare what is on the wire
+    let from_ir = inline_newtypes(&resolve_schema(from_schema_path)?.0);
+    let to_ir = inline_newtypes(&resolve_schema(to_schema_path)?.0);
 
     // Build maps of types
     let from_map: HashMap<&str, &lumos_core::ir::TypeDefinition> =
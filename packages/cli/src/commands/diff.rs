@@ -3,17 +3,15 @@
 
 //! Diff command - compare schema files and show differences
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use colored::Colorize;
 use lumos_core::ir::{
     EnumDefinition, EnumVariantDefinition, FieldDefinition, StructDefinition, TypeDefinition,
 };
-use lumos_core::parser::parse_lumos_file;
-use lumos_core::transform::transform_to_ir;
 use std::collections::{HashMap, HashSet};
-use std::fs;
 use std::path::Path;
 
+use crate::commands::generate::resolve_schema;
 use crate::utils::format_type;
 
 /// Compare two schema files and show differences
@@ -24,22 +22,10 @@ pub fn run(schema1_path: &Path, schema2_path: &Path, _format: &str) -> Result<()
     println!();
 
     // Read and parse first schema
-    let content1 = fs::read_to_string(schema1_path)
-        .with_context(|| format!("Failed to read schema file: {}", schema1_path.display()))?;
-
-    let ast1 = parse_lumos_file(&content1)
-        .with_context(|| format!("Failed to parse schema: {}", schema1_path.display()))?;
-
-    let ir1 = transform_to_ir(ast1)?;
+    let (ir1, _) = resolve_schema(schema1_path)?;
 
     // Read and parse second schema
-    let content2 = fs::read_to_string(schema2_path)
-        .with_context(|| format!("Failed to read schema file: {}", schema2_path.display()))?;
-
-    let ast2 = parse_lumos_file(&content2)
-        .with_context(|| format!("Failed to parse schema: {}", schema2_path.display()))?;
-
-    let ir2 = transform_to_ir(ast2)?;
+    let (ir2, _) = resolve_schema(schema2_path)?;
 
     // Build maps for efficient lookup
     let map1: HashMap<&str, &TypeDefinition> = ir1.iter().map(|t| (t.name(), t)).collect();
@@ -108,6 +94,26 @@ fn compare_types(type1: &TypeDefinition, type2: &TypeDefinition) -> Vec<String>
         (TypeDefinition::TypeAlias(_), TypeDefinition::Enum(_)) => {
             changes.push("Type changed from type alias to enum".to_string());
         }
+        (TypeDefinition::Const(c1), TypeDefinition::Const(c2)) => {
+            if c1.type_name != c2.type_name {
+                changes.push(format!(
+                    "~ Constant type changed: {} → {}",
+                    c1.type_name, c2.type_name
+                ));
+            }
+            if c1.value != c2.value {
+                changes.push(format!(
+                    "~ Constant value changed: {} → {}",
+                    c1.value, c2.value
+                ));
+            }
+        }
+        (TypeDefinition::Const(_), _) => {
+            changes.push("Constant changed to a type definition".to_string());
+        }
+        (_, TypeDefinition::Const(_)) => {
+            changes.push("Type definition changed to a constant".to_string());
+        }
     }
 
     changes
@@ -7,12 +7,11 @@ use anyhow::{Context, Result};
 use colored::Colorize;
 use lumos_core::corpus_generator::CorpusGenerator;
 use lumos_core::fuzz_generator::FuzzGenerator;
-use lumos_core::parser::parse_lumos_file;
-use lumos_core::transform::transform_to_ir;
 use std::fs;
 use std::path::Path;
 use std::process::Command;
 
+use crate::commands::generate::resolve_schema;
 use crate::utils::to_snake_case;
 
 /// Generate fuzz targets from schema
@@ -26,11 +25,7 @@ pub fn run_generate(
     println!("{:>12} fuzz targets...", "Generating".cyan().bold());
 
     // Read and parse schema
-    let source = fs::read_to_string(schema_path)
-        .with_context(|| format!("Failed to read schema file: {}", schema_path.display()))?;
-
-    let ast = parse_lumos_file(&source)?;
-    let ir = transform_to_ir(ast)?;
+    let (ir, _file_count) = resolve_schema(schema_path)?;
 
     let generator = FuzzGenerator::new(&ir);
 
@@ -133,11 +128,7 @@ pub fn run_fuzz(
     );
 
     // Read and parse schema to verify type exists
-    let source = fs::read_to_string(schema_path)
-        .with_context(|| format!("Failed to read schema file: {}", schema_path.display()))?;
-
-    let ast = parse_lumos_file(&source)?;
-    let ir = transform_to_ir(ast)?;
+    let (ir, _file_count) = resolve_schema(schema_path)?;
 
     let generator = FuzzGenerator::new(&ir);
 
@@ -202,11 +193,7 @@ pub fn run_corpus(
     println!("{:>12} corpus files...", "Generating".cyan().bold());
 
     // Read and parse schema
-    let source = fs::read_to_string(schema_path)
-        .with_context(|| format!("Failed to read schema file: {}", schema_path.display()))?;
-
-    let ast = parse_lumos_file(&source)?;
-    let ir = transform_to_ir(ast)?;
+    let (ir, _file_count) = resolve_schema(schema_path)?;
 
     let generator = CorpusGenerator::new(&ir);
 
@@ -5,13 +5,11 @@
 
 use anyhow::{Context, Result};
 use colored::Colorize;
-use lumos_core::ast::Item;
-use lumos_core::file_resolver::FileResolver;
+use lumos_core::cache::{BuildCache, CacheStats};
 use lumos_core::generators::{get_generators, Language};
 use lumos_core::ir::TypeDefinition;
-use lumos_core::module_resolver::ModuleResolver;
-use lumos_core::parser::parse_lumos_file;
-use lumos_core::transform::{collect_deprecation_warnings, transform_to_ir};
+use lumos_core::project::ProjectGraph;
+use lumos_core::transform::{collect_deprecation_warnings, collect_generic_warnings};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -30,46 +28,85 @@ enum TargetMode {
     Anchor,
 }
 
-/// Resolve schema with auto-detection of module system
+/// Load a schema with every file it imports or declares as a module
+///
+/// Returns the IR of the whole project and the number of files loaded.
 pub fn resolve_schema(schema_path: &Path) -> Result<(Vec<TypeDefinition>, usize)> {
-    // Read the file to detect which resolution strategy to use
-    let content = fs::read_to_string(schema_path)
-        .with_context(|| format!("Failed to read schema: {}", schema_path.display()))?;
+    let (graph, ir) = resolve_project(schema_path)?;
+    Ok((ir, graph.files().len()))
+}
 
-    let ast = parse_lumos_file(&content)
-        .with_context(|| format!("Failed to parse schema: {}", schema_path.display()))?;
+/// Load and resolve a schema, keeping the project graph
+pub fn resolve_project(schema_path: &Path) -> Result<(ProjectGraph, Vec<TypeDefinition>)> {
+    let graph = ProjectGraph::load(schema_path)
+        .with_context(|| format!("Failed to load schema: {}", schema_path.display()))?;
 
-    // Check if file has module declarations
-    let has_mod_declarations = ast.items.iter().any(|item| matches!(item, Item::Module(_)));
+    let ir = graph
+        .resolve()
+        .with_context(|| format!("Failed to resolve schema: {}", schema_path.display()))?;
 
-    // Check if file has JS-style imports
-    let has_imports = !ast.imports.is_empty();
+    Ok((graph, ir))
+}
 
-    if has_mod_declarations {
-        // Use ModuleResolver for hierarchical module system
-        let mut resolver = ModuleResolver::new();
-        let ir = resolver.resolve_modules(schema_path).with_context(|| {
-            format!("Failed to resolve modules from: {}", schema_path.display())
-        })?;
-        let file_count = resolver.loaded_modules().len();
-        Ok((ir, file_count))
-    } else if has_imports {
-        // Use FileResolver for JS-style imports
-        let mut resolver = FileResolver::new();
-        let ir = resolver.resolve_imports(schema_path).with_context(|| {
-            format!("Failed to resolve imports from: {}", schema_path.display())
-        })?;
-        let file_count = resolver.loaded_files().len();
-        Ok((ir, file_count))
-    } else {
-        // Single file, no imports or modules
-        let ir = transform_to_ir(ast)
-            .with_context(|| format!("Failed to transform schema: {}", schema_path.display()))?;
-        Ok((ir, 1))
+/// Like [`resolve_project`], reusing the ASTs and IR of unchanged files
+/// from the project's `.lumos/cache`
+///
+/// Returns how many files were read from the cache along with the project.
+pub fn resolve_project_cached(
+    schema_path: &Path,
+) -> Result<(ProjectGraph, Vec<TypeDefinition>, CacheStats)> {
+    let mut cache = BuildCache::for_schema(schema_path);
+    let graph = ProjectGraph::load_cached(schema_path, &mut cache)
+        .with_context(|| format!("Failed to load schema: {}", schema_path.display()))?;
+
+    let ir = graph
+        .resolve_cached(&mut cache)
+        .with_context(|| format!("Failed to resolve schema: {}", schema_path.display()))?;
+
+    Ok((graph, ir, cache.stats()))
+}
+
+/// Record the package versions a project resolved in its `lumos.lock`
+///
+/// With `locked`, the lockfile must already record them, so that a build
+/// uses exactly the packages it was locked to.
+pub fn update_lockfile(graph: &ProjectGraph, locked: bool, dry_run: bool) -> Result<()> {
+    let Some(packages) = graph.packages() else {
+        if locked {
+            anyhow::bail!(
+                "--locked needs a lumos.toml next to the schema or in a parent directory"
+            );
+        }
+        return Ok(());
+    };
+
+    let lockfile = packages.lockfile();
+    let path = packages.lockfile_path();
+    match packages.locked() {
+        Some(existing) if *existing == lockfile => return Ok(()),
+        None if lockfile.packages.is_empty() && !locked => return Ok(()),
+        existing if locked => anyhow::bail!(
+            "{} {} but --locked was given",
+            path.display(),
+            if existing.is_some() {
+                "is out of date"
+            } else {
+                "does not exist"
+            }
+        ),
+        _ => {}
+    }
+
+    if !dry_run {
+        fs::write(&path, lockfile.to_toml())
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("{:>12} {}", "Locked".green().bold(), path.display());
     }
+    Ok(())
 }
 
 /// Generate code from schema
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     schema_path: &Path,
     output_dir: Option<&Path>,
@@ -78,6 +115,8 @@ pub fn run(
     dry_run: bool,
     backup: bool,
     show_diff: bool,
+    module_tree: bool,
+    locked: bool,
 ) -> Result<()> {
     let output_dir = output_dir.unwrap_or_else(|| Path::new("."));
 
@@ -139,15 +178,35 @@ pub fn run(
         println!("{:>12} schema and dependencies", "Resolving".cyan().bold());
     }
 
-    let (mut ir, file_count) = resolve_schema(schema_path)?;
+    // Unchanged files come from the cache, which a dry run leaves untouched
+    let (graph, mut ir, stats) = if dry_run {
+        let (graph, ir) = resolve_project(schema_path)?;
+        (graph, ir, CacheStats::default())
+    } else {
+        resolve_project_cached(schema_path)?
+    };
+    let file_count = graph.files().len();
 
     // Report loaded files if multiple
     if file_count > 1 && !dry_run {
-        println!("{:>12} {} files", "Loaded".green().bold(), file_count);
+        if stats.ir_hits > 0 {
+            println!(
+                "{:>12} {} files ({} unchanged)",
+                "Loaded".green().bold(),
+                file_count,
+                stats.ir_hits
+            );
+        } else {
+            println!("{:>12} {} files", "Loaded".green().bold(), file_count);
+        }
     }
 
-    // Collect and print deprecation warnings
-    let warnings = collect_deprecation_warnings(&ir);
+    // Pin the package versions used, so the next build resolves the same ones
+    update_lockfile(&graph, locked, dry_run)?;
+
+    // Collect and print deprecation and uninstantiated-generic warnings
+    let mut warnings = collect_deprecation_warnings(&ir);
+    warnings.extend(collect_generic_warnings(&ir));
     for warning in &warnings {
         eprintln!("{}: {}", "warning".yellow().bold(), warning);
     }
@@ -160,11 +219,26 @@ pub fn run(
         return Ok(());
     }
 
+    // Types sharing a name across modules can only be written to separate files
+    if !module_tree {
+        let shared: Vec<&str> = ir
+            .iter()
+            .map(|t| t.name())
+            .filter(|name| name.contains("::"))
+            .collect();
+        if !shared.is_empty() {
+            anyhow::bail!(
+                "Types in different modules share a name ({}). Use --module-tree to write one file per module",
+                shared.join(", ")
+            );
+        }
+    }
+
     // Handle target mode
     let has_account_attrs = ir.iter().any(|t| match t {
         TypeDefinition::Struct(s) => s.metadata.attributes.contains(&"account".to_string()),
         TypeDefinition::Enum(e) => e.metadata.attributes.contains(&"account".to_string()),
-        TypeDefinition::TypeAlias(_) => false,
+        TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => false,
     });
 
     match target_mode {
@@ -184,7 +258,7 @@ pub fn run(
                         TypeDefinition::Enum(e) => {
                             e.metadata.attributes.retain(|a| a != "account");
                         }
-                        TypeDefinition::TypeAlias(_) => {}
+                        TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {}
                     }
                 }
             }
@@ -220,14 +294,26 @@ pub fn run(
     }
 
     // Collect generated code for each language
-    let generated: Vec<(Language, String, PathBuf)> = generators
-        .iter()
-        .map(|gen| {
+    let mut generated: Vec<(Language, String, PathBuf)> = Vec::new();
+    for gen in &generators {
+        if !module_tree {
             let code = gen.generate_module(&ir);
             let output_file = output_dir.join(format!("generated.{}", gen.file_extension()));
-            (gen.language(), code, output_file)
-        })
-        .collect();
+            generated.push((gen.language(), code, output_file));
+            continue;
+        }
+
+        let files = gen.generate_module_tree(&ir).with_context(|| {
+            format!(
+                "--module-tree is not supported for {} (supported: rust, typescript)",
+                gen.language().name()
+            )
+        })?;
+        let tree_root = output_dir.join("generated");
+        for file in files {
+            generated.push((gen.language(), file.content, tree_root.join(file.path)));
+        }
+    }
 
     // Dry-run mode: preview only
     if dry_run {
@@ -253,6 +339,11 @@ pub fn run(
     let mut backup_paths: Vec<PathBuf> = Vec::new();
 
     for (lang, code, output_path) in &generated {
+        // Module trees write into one directory per module
+        if let Some(parent) = output_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+        }
         let written = write_with_diff_check(output_path, code, show_diff, lang.name())?;
 
         if written {
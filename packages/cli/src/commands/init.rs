@@ -56,6 +56,13 @@ rust = "generated.rs"
 
 # TypeScript output file name
 typescript = "generated.ts"
+
+# Schema packages imported with `import { .. } from "@scope/name"`
+# [registry]
+# path = "../schema-registry"
+#
+# [dependencies]
+# "@acme/oracle" = "^1.0"
 "#;
 
     let config_path = project_dir.join("lumos.toml");
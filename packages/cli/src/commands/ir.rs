@@ -0,0 +1,45 @@
+// Licensed under either of Apache License, Version 2.0 or MIT license at your option.
+// Copyright 2025 RECTOR-LABS
+
+//! IR command - dump the resolved schema model for other tools
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use lumos_core::ir_json::IrDocument;
+use lumos_core::parser::parse_lumos_file;
+use std::fs;
+use std::path::Path;
+
+use crate::commands::generate::resolve_schema;
+use crate::utils::validate_output_path;
+
+/// Print the IR (or with `ast`, the parsed AST of the schema file) as JSON
+pub fn run(schema_path: &Path, format: &str, ast: bool, output_path: Option<&Path>) -> Result<()> {
+    if format != "json" {
+        anyhow::bail!("Unsupported format '{}' (supported: json)", format);
+    }
+
+    let json = if ast {
+        // The AST is per file, before imports and modules are resolved
+        let content = fs::read_to_string(schema_path)
+            .with_context(|| format!("Failed to read schema: {}", schema_path.display()))?;
+        let file = parse_lumos_file(&content)
+            .with_context(|| format!("Failed to parse schema: {}", schema_path.display()))?;
+        serde_json::to_string_pretty(&file)?
+    } else {
+        let (type_defs, _file_count) = resolve_schema(schema_path)?;
+        IrDocument::new(type_defs).to_json()?
+    };
+
+    match output_path {
+        Some(out_path) => {
+            validate_output_path(out_path)?;
+            fs::write(out_path, format!("{}\n", json))
+                .with_context(|| format!("Failed to write {}", out_path.display()))?;
+            println!("{:>12} {}", "Generated".green().bold(), out_path.display());
+        }
+        None => println!("{}", json),
+    }
+
+    Ok(())
+}
@@ -6,12 +6,12 @@
 use anyhow::{Context, Result};
 use colored::Colorize;
 use lumos_core::migration::{generate_rust_migration, generate_typescript_migration, SchemaDiff};
-use lumos_core::parser::parse_lumos_file;
-use lumos_core::transform::transform_to_ir;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
+use crate::commands::generate::resolve_schema;
+
 /// Generate migration code from one schema version to another
 pub fn run(
     from_schema_path: &Path,
@@ -30,22 +30,10 @@ pub fn run(
     println!();
 
     // Read and parse old schema
-    let from_content = fs::read_to_string(from_schema_path)
-        .with_context(|| format!("Failed to read schema file: {}", from_schema_path.display()))?;
-
-    let from_ast = parse_lumos_file(&from_content)
-        .with_context(|| format!("Failed to parse schema: {}", from_schema_path.display()))?;
-
-    let from_ir = transform_to_ir(from_ast)?;
+    let (from_ir, _) = resolve_schema(from_schema_path)?;
 
     // Read and parse new schema
-    let to_content = fs::read_to_string(to_schema_path)
-        .with_context(|| format!("Failed to read schema file: {}", to_schema_path.display()))?;
-
-    let to_ast = parse_lumos_file(&to_content)
-        .with_context(|| format!("Failed to parse schema: {}", to_schema_path.display()))?;
-
-    let to_ir = transform_to_ir(to_ast)?;
+    let (to_ir, _) = resolve_schema(to_schema_path)?;
 
     // Find types with same name across both schemas
     let from_map: HashMap<&str, &lumos_core::ir::TypeDefinition> =
@@ -10,8 +10,10 @@ pub mod diff;
 pub mod fuzz;
 pub mod generate;
 pub mod init;
+pub mod ir;
 pub mod metaplex;
 pub mod migrate;
+pub mod publish;
 pub mod security;
 pub mod validate;
 pub mod watch;
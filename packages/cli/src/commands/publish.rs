@@ -0,0 +1,97 @@
+// Licensed under either of Apache License, Version 2.0 or MIT license at your option.
+// Copyright 2025 RECTOR-LABS
+
+//! Publish command - copy a schema package into the local registry
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use lumos_core::registry::{Manifest, MANIFEST_FILE};
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::commands::generate::resolve_project;
+
+/// Publish the project of `schema_path` (default: the `entry` of the
+/// `[package]` in lumos.toml) as the version named in its lumos.toml
+pub fn run(schema_path: Option<&Path>, dry_run: bool) -> Result<()> {
+    let start_dir = match schema_path {
+        Some(schema) => schema
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from(".")),
+        None => env::current_dir()?,
+    };
+    let (manifest_path, manifest) = Manifest::find(&start_dir)?.with_context(|| {
+        format!(
+            "No {} found in {} or its parent directories",
+            MANIFEST_FILE,
+            start_dir.display()
+        )
+    })?;
+    let manifest_dir = manifest_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .canonicalize()?;
+
+    let package = manifest.package.as_ref().with_context(|| {
+        format!(
+            "{} has no [package] section with the name and version to publish",
+            manifest_path.display()
+        )
+    })?;
+    let registry = manifest.registry(&manifest_dir).with_context(|| {
+        format!(
+            "{} has no [registry] path to publish to",
+            manifest_path.display()
+        )
+    })?;
+    let entry = match (schema_path, &package.entry) {
+        (Some(schema), _) => schema.to_path_buf(),
+        (None, Some(entry)) => manifest_dir.join(entry),
+        (None, None) => anyhow::bail!(
+            "No schema given and no entry in the [package] section of {}",
+            manifest_path.display()
+        ),
+    };
+
+    println!(
+        "{:>12} {} {}",
+        "Packaging".cyan().bold(),
+        package.name,
+        package.version
+    );
+
+    // Only schemas that resolve are published
+    let (graph, _ir) = resolve_project(&entry)?;
+    let files: Vec<PathBuf> = graph
+        .files()
+        .iter()
+        .map(|file| file.path.clone())
+        .filter(|path| !graph.is_package_file(path))
+        .collect();
+
+    if dry_run {
+        for file in &files {
+            let relative = file.strip_prefix(&manifest_dir).unwrap_or(file);
+            println!("{:>12} {}", "Including".cyan().bold(), relative.display());
+        }
+        println!("\n{}", "Nothing published (dry-run mode).".yellow());
+        return Ok(());
+    }
+
+    let published = registry
+        .publish(&manifest_dir, &manifest, graph.root(), &files)
+        .with_context(|| format!("Failed to publish {}", package.name))?;
+
+    println!(
+        "{:>12} {} {} to {}",
+        "Published".green().bold(),
+        published.name,
+        published.version,
+        published.dir.display()
+    );
+    println!("{:>12} {}", "Hash".cyan().bold(), published.hash);
+
+    Ok(())
+}
@@ -3,25 +3,18 @@
 
 //! Security analysis and audit commands
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use colored::Colorize;
 use lumos_core::audit_generator::AuditGenerator;
-use lumos_core::parser::parse_lumos_file;
 use lumos_core::security_analyzer::SecurityAnalyzer;
-use lumos_core::transform::transform_to_ir;
-use std::fs;
 use std::path::Path;
 
+use crate::commands::generate::resolve_schema;
+
 /// Run security analysis on schema
 pub fn run_analyze(schema_path: &Path, _format: &str, _strict: bool) -> Result<()> {
     // Read and parse schema
-    let content = fs::read_to_string(schema_path)
-        .with_context(|| format!("Failed to read schema file: {}", schema_path.display()))?;
-
-    let ast = parse_lumos_file(&content)
-        .with_context(|| format!("Failed to parse schema: {}", schema_path.display()))?;
-
-    let ir = transform_to_ir(ast).with_context(|| "Failed to transform AST to IR")?;
+    let (ir, _file_count) = resolve_schema(schema_path)?;
 
     if ir.is_empty() {
         eprintln!(
@@ -43,13 +36,7 @@ pub fn run_analyze(schema_path: &Path, _format: &str, _strict: bool) -> Result<(
 /// Run audit checklist generation
 pub fn run_audit(schema_path: &Path, output_path: Option<&Path>, format: &str) -> Result<()> {
     // Read and parse schema
-    let content = fs::read_to_string(schema_path)
-        .with_context(|| format!("Failed to read schema file: {}", schema_path.display()))?;
-
-    let ast = parse_lumos_file(&content)
-        .with_context(|| format!("Failed to parse schema: {}", schema_path.display()))?;
-
-    let ir = transform_to_ir(ast).with_context(|| "Failed to transform AST to IR")?;
+    let (ir, _file_count) = resolve_schema(schema_path)?;
 
     if ir.is_empty() {
         eprintln!(
@@ -3,14 +3,18 @@
 
 //! Validate command - syntax validation without code generation
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use lumos_core::diagnostics::{check_project_file, check_schema, SchemaCheck, Severity};
 use lumos_core::parser::parse_lumos_file;
-use lumos_core::transform::{collect_deprecation_warnings, transform_to_ir};
+use lumos_core::project::{has_dependencies, ProjectGraph};
 use std::fs;
 use std::path::Path;
 
 /// Validate schema syntax without generating code
+///
+/// Every error and warning in the schema is printed with its location, so a
+/// broken schema can be fixed in one pass.
 pub fn run(schema_path: &Path) -> Result<()> {
     println!(
         "{:>12} {}",
@@ -21,33 +25,73 @@ pub fn run(schema_path: &Path) -> Result<()> {
     let content = fs::read_to_string(schema_path)
         .with_context(|| format!("Failed to read schema file: {}", schema_path.display()))?;
 
-    let ast = parse_lumos_file(&content)
-        .with_context(|| format!("Failed to parse schema: {}", schema_path.display()))?;
+    // A file with imports or modules is checked with the rest of its project
+    let check = if parse_lumos_file(&content).is_ok_and(|ast| has_dependencies(&ast)) {
+        let graph = ProjectGraph::load(schema_path)
+            .with_context(|| format!("Failed to load schema: {}", schema_path.display()))?;
 
-    let ir = transform_to_ir(ast).with_context(|| "Failed to transform AST to IR")?;
+        // The entry file's check holds the errors of every file, and each
+        // file's own check its warnings
+        let mut check = check_project_file(&graph, graph.root());
+        print_diagnostics(&check, schema_path);
+        for file in graph.files().iter().filter(|f| f.path != graph.root()) {
+            let mut warnings = check_project_file(&graph, &file.path);
+            warnings
+                .diagnostics
+                .retain(|d| d.severity == Severity::Warning);
+            print_diagnostics(&warnings, &file.path);
+            check.diagnostics.extend(warnings.diagnostics);
+        }
+        check
+    } else {
+        let check = check_schema(&content);
+        print_diagnostics(&check, schema_path);
+        check
+    };
 
-    // Collect and print deprecation warnings
-    let warnings = collect_deprecation_warnings(&ir);
-    for warning in &warnings {
-        eprintln!("{}: {}", "warning".yellow().bold(), warning);
+    if check.has_errors() {
+        bail!(
+            "Schema {} has {} error(s)",
+            schema_path.display(),
+            check.error_count()
+        );
     }
 
-    if ir.is_empty() {
+    if check.type_defs.is_empty() {
         println!("{}: No type definitions found", "warning".yellow().bold());
     } else {
         println!(
             "{:>12} Found {} valid type definitions",
             "Success".green().bold(),
-            ir.len()
+            check.type_defs.len()
         );
-        if !warnings.is_empty() {
+        if check.warning_count() > 0 {
             println!(
-                "{:>12} {} deprecation warnings",
+                "{:>12} {} warnings",
                 "Warnings".yellow().bold(),
-                warnings.len()
+                check.warning_count()
             );
         }
     }
 
     Ok(())
 }
+
+/// Print every diagnostic of `check`, located in `path`
+fn print_diagnostics(check: &SchemaCheck, path: &Path) {
+    for diagnostic in &check.diagnostics {
+        let label = match diagnostic.severity {
+            Severity::Error => "error".red().bold(),
+            Severity::Warning => "warning".yellow().bold(),
+        };
+        eprintln!("{}: {}", label, diagnostic.message);
+        if let Some(location) = &diagnostic.location {
+            eprintln!(
+                "  {} {}:{}",
+                "-->".blue().bold(),
+                path.display(),
+                location.format()
+            );
+        }
+    }
+}
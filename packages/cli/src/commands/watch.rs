@@ -5,10 +5,19 @@
 
 use anyhow::Result;
 use colored::Colorize;
-use std::path::Path;
+use lumos_core::cache::BuildCache;
+use lumos_core::project::ProjectGraph;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 
 /// Watch mode: regenerate on file changes
-pub fn run(schema_path: &Path, output_dir: Option<&Path>, lang: &str, target: &str) -> Result<()> {
+pub fn run(
+    schema_path: &Path,
+    output_dir: Option<&Path>,
+    lang: &str,
+    target: &str,
+    module_tree: bool,
+) -> Result<()> {
     use notify::{RecursiveMode, Watcher};
     use std::sync::mpsc::channel;
     use std::time::Duration;
@@ -35,6 +44,8 @@ pub fn run(schema_path: &Path, output_dir: Option<&Path>, lang: &str, target: &s
         false,
         false,
         false,
+        module_tree,
+        false,
     ) {
         eprintln!("{}: {}", "error".red().bold(), e);
     }
@@ -49,6 +60,8 @@ pub fn run(schema_path: &Path, output_dir: Option<&Path>, lang: &str, target: &s
     })?;
 
     watcher.watch(&schema_path, RecursiveMode::NonRecursive)?;
+    let mut watched = HashSet::new();
+    watch_project(&mut watcher, &schema_path, &mut watched);
 
     // Get configurable debounce duration (default: 100ms)
     let debounce_ms = std::env::var("LUMOS_WATCH_DEBOUNCE")
@@ -78,10 +91,15 @@ pub fn run(schema_path: &Path, output_dir: Option<&Path>, lang: &str, target: &s
                     false,
                     false,
                     false,
+                    module_tree,
+                    false,
                 ) {
                     eprintln!("{}: {}", "error".red().bold(), e);
                 }
 
+                // Imports and modules may have been added
+                watch_project(&mut watcher, &schema_path, &mut watched);
+
                 println!();
                 println!("{:>12} for changes...", "Watching".cyan().bold());
             }
@@ -96,3 +114,29 @@ pub fn run(schema_path: &Path, output_dir: Option<&Path>, lang: &str, target: &s
 
     Ok(())
 }
+
+/// Watch every file of the schema's project that is not watched yet
+///
+/// A project that fails to load keeps the files watched so far; the error
+/// has already been reported by the generation that preceded this.
+fn watch_project(
+    watcher: &mut impl notify::Watcher,
+    schema_path: &Path,
+    watched: &mut HashSet<PathBuf>,
+) {
+    let mut cache = BuildCache::for_schema(schema_path);
+    let Ok(graph) = ProjectGraph::load_cached(schema_path, &mut cache) else {
+        return;
+    };
+
+    for file in graph.files() {
+        if file.path != graph.root()
+            && !watched.contains(&file.path)
+            && watcher
+                .watch(&file.path, notify::RecursiveMode::NonRecursive)
+                .is_ok()
+        {
+            watched.insert(file.path.clone());
+        }
+    }
+}
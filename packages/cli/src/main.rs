@@ -25,9 +25,11 @@ fn main() -> Result<()> {
             dry_run,
             backup,
             show_diff,
+            module_tree,
+            locked,
         } => {
             if watch {
-                commands::watch::run(&schema, output.as_deref(), &lang, &target)
+                commands::watch::run(&schema, output.as_deref(), &lang, &target, module_tree)
             } else {
                 commands::generate::run(
                     &schema,
@@ -37,11 +39,14 @@ fn main() -> Result<()> {
                     dry_run,
                     backup,
                     show_diff,
+                    module_tree,
+                    locked,
                 )
             }
         }
         Commands::Validate { schema } => commands::validate::run(&schema),
         Commands::Init { name } => commands::init::run(name.as_deref()),
+        Commands::Publish { schema, dry_run } => commands::publish::run(schema.as_deref(), dry_run),
         Commands::Check { schema, output } => commands::check::run(&schema, output.as_deref()),
         Commands::CheckSize { schema, format } => commands::check::run_size(&schema, &format),
         Commands::Security { command } => match command {
@@ -76,6 +81,12 @@ fn main() -> Result<()> {
                 type_name,
             } => commands::fuzz::run_corpus(&schema, output.as_deref(), type_name.as_deref()),
         },
+        Commands::Ir {
+            schema,
+            format,
+            ast,
+            output,
+        } => commands::ir::run(&schema, &format, ast, output.as_deref()),
         Commands::Diff {
             schema1,
             schema2,
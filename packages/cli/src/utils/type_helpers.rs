@@ -5,6 +5,7 @@
 
 use lumos_core::anchor::AnchorAccountType;
 use lumos_core::ir::TypeInfo;
+use std::collections::HashSet;
 
 /// Format a TypeInfo as a string representation
 pub fn format_type(type_info: &TypeInfo) -> String {
@@ -17,9 +18,34 @@ pub fn format_type(type_info: &TypeInfo) -> String {
             format!("[{}; {}]", format_type(element), size)
         }
         TypeInfo::Option(inner) => format!("Option<{}>", format_type(inner)),
+        TypeInfo::Map {
+            key,
+            value,
+            ordered,
+        } => format!(
+            "{}<{}, {}>",
+            if *ordered { "BTreeMap" } else { "HashMap" },
+            format_type(key),
+            format_type(value)
+        ),
+        TypeInfo::Set { element, ordered } => format!(
+            "{}<{}>",
+            if *ordered { "BTreeSet" } else { "HashSet" },
+            format_type(element)
+        ),
+        TypeInfo::Tuple(elements) => TypeInfo::rust_tuple(elements, format_type),
+        TypeInfo::Boxed(inner) => format!("Box<{}>", format_type(inner)),
+        TypeInfo::FixedString(size) => format!("FixedString<{}>", size),
+        TypeInfo::Instance { name, args } => format_instance(name, args, format_type),
     }
 }
 
+/// Format a generic instance as `Name<A, B>`
+fn format_instance(name: &str, args: &[TypeInfo], format_arg: fn(&TypeInfo) -> String) -> String {
+    let parts: Vec<String> = args.iter().map(format_arg).collect();
+    format!("{}<{}>", name, parts.join(", "))
+}
+
 /// Convert PascalCase to snake_case
 pub fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
@@ -59,11 +85,35 @@ pub fn type_info_to_rust_type(ty: &TypeInfo) -> String {
         TypeInfo::Option(inner) => {
             format!("Option<{}>", type_info_to_rust_type(inner))
         }
+        TypeInfo::Map {
+            key,
+            value,
+            ordered,
+        } => format!(
+            "{}<{}, {}>",
+            if *ordered { "BTreeMap" } else { "HashMap" },
+            type_info_to_rust_type(key),
+            type_info_to_rust_type(value)
+        ),
+        TypeInfo::Set { element, ordered } => format!(
+            "{}<{}>",
+            if *ordered { "BTreeSet" } else { "HashSet" },
+            type_info_to_rust_type(element)
+        ),
+        TypeInfo::Tuple(elements) => TypeInfo::rust_tuple(elements, type_info_to_rust_type),
+        TypeInfo::Boxed(inner) => format!("Box<{}>", type_info_to_rust_type(inner)),
+        TypeInfo::FixedString(size) => format!("[u8; {}]", size),
+        TypeInfo::Instance { name, args } => format_instance(name, args, type_info_to_rust_type),
     }
 }
 
 /// Infer Anchor account type from LUMOS type
-pub fn infer_anchor_account_type(ty: &TypeInfo) -> AnchorAccountType {
+///
+/// `#[zero_copy]` accounts (listed in `zero_copy_types`) use `AccountLoader`.
+pub fn infer_anchor_account_type(
+    ty: &TypeInfo,
+    zero_copy_types: &HashSet<String>,
+) -> AnchorAccountType {
     match ty {
         TypeInfo::Primitive(name) if name == "Signer" => AnchorAccountType::Signer,
         TypeInfo::UserDefined(name) => match name.as_str() {
@@ -86,6 +136,7 @@ pub fn infer_anchor_account_type(ty: &TypeInfo) -> AnchorAccountType {
                 AnchorAccountType::Sysvar(inner.to_string())
             }
             _ if name == "SystemProgram" => AnchorAccountType::Program("System".to_string()),
+            _ if zero_copy_types.contains(name) => AnchorAccountType::AccountLoader(name.clone()),
             _ => AnchorAccountType::Account(name.clone()),
         },
         _ => AnchorAccountType::AccountInfo,
@@ -8,8 +8,8 @@
 //! - Parsing `#[instruction]` structs for Accounts context generation
 //! - Generating `#[derive(Accounts)]` Rust code
 
-use crate::ir::{StructDefinition, TypeInfo};
-use std::collections::HashMap;
+use crate::ir::{FieldDefinition, StructDefinition, TypeDefinition, TypeInfo};
+use std::collections::{HashMap, HashSet};
 
 /// Anchor account attribute types
 #[derive(Debug, Clone, PartialEq)]
@@ -122,6 +122,9 @@ pub enum AnchorAccountType {
     /// `Box<Account<'info, T>>` - Boxed account (for large accounts)
     BoxedAccount(String),
 
+    /// `AccountLoader<'info, T>` - Zero-copy account
+    AccountLoader(String),
+
     /// `Sysvar<'info, T>` - Sysvar
     Sysvar(String),
 }
@@ -168,6 +171,60 @@ pub struct InstructionArg {
     pub ty: TypeInfo,
 }
 
+/// Flags a client sets on an instruction account (`AccountMeta`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AccountMetaFlags {
+    /// Account is written by the instruction
+    pub is_writable: bool,
+
+    /// Account must sign the transaction
+    pub is_signer: bool,
+}
+
+/// Derive the `AccountMeta` flags of an `#[instruction]` field
+///
+/// An account is writable when it is `mut` or its constraints imply it
+/// (`init`, `zero`, `close`, `realloc`). It signs when its type is `Signer`,
+/// when marked `signer`, or when `init` creates it without PDA seeds
+/// (a fresh keypair must sign its own creation).
+pub fn account_meta_flags(field: &FieldDefinition) -> AccountMetaFlags {
+    let attrs: Vec<AnchorAccountAttr> = field
+        .anchor_attrs
+        .iter()
+        .flat_map(|attr| parse_anchor_attrs(attr))
+        .collect();
+
+    let is_init = attrs.iter().any(|attr| {
+        matches!(
+            attr,
+            AnchorAccountAttr::Init | AnchorAccountAttr::InitIfNeeded
+        )
+    });
+    let has_seeds = attrs
+        .iter()
+        .any(|attr| matches!(attr, AnchorAccountAttr::Seeds(_)));
+
+    let is_writable = is_init
+        || attrs.iter().any(|attr| {
+            matches!(
+                attr,
+                AnchorAccountAttr::Mut
+                    | AnchorAccountAttr::Zero
+                    | AnchorAccountAttr::Close(_)
+                    | AnchorAccountAttr::Realloc(_)
+            )
+        });
+    let is_signer = infer_account_type(&field.type_info, &HashSet::new())
+        == AnchorAccountType::Signer
+        || attrs.contains(&AnchorAccountAttr::Signer)
+        || (is_init && !has_seeds);
+
+    AccountMetaFlags {
+        is_writable,
+        is_signer,
+    }
+}
+
 /// Parse anchor attributes from a string
 ///
 /// Handles formats like:
@@ -459,6 +516,7 @@ fn generate_account_type(ty: &AnchorAccountType) -> String {
         AnchorAccountType::UncheckedAccount => "UncheckedAccount<'info>".to_string(),
         AnchorAccountType::AccountInfo => "AccountInfo<'info>".to_string(),
         AnchorAccountType::BoxedAccount(inner) => format!("Box<Account<'info, {}>>", inner),
+        AnchorAccountType::AccountLoader(inner) => format!("AccountLoader<'info, {}>", inner),
         AnchorAccountType::Sysvar(inner) => format!("Sysvar<'info, {}>", inner),
     }
 }
@@ -476,7 +534,29 @@ fn type_info_to_rust(ty: &TypeInfo) -> String {
         TypeInfo::FixedArray { element, size } => {
             format!("[{}; {}]", type_info_to_rust(element), size)
         }
+        TypeInfo::FixedString(size) => format!("[u8; {}]", size),
         TypeInfo::Option(inner) => format!("Option<{}>", type_info_to_rust(inner)),
+        TypeInfo::Map {
+            key,
+            value,
+            ordered,
+        } => format!(
+            "{}<{}, {}>",
+            if *ordered { "BTreeMap" } else { "HashMap" },
+            type_info_to_rust(key),
+            type_info_to_rust(value)
+        ),
+        TypeInfo::Set { element, ordered } => format!(
+            "{}<{}>",
+            if *ordered { "BTreeSet" } else { "HashSet" },
+            type_info_to_rust(element)
+        ),
+        TypeInfo::Boxed(inner) => format!("Box<{}>", type_info_to_rust(inner)),
+        TypeInfo::Tuple(elements) => TypeInfo::rust_tuple(elements, type_info_to_rust),
+        TypeInfo::Instance { name, args } => {
+            let parts: Vec<String> = args.iter().map(type_info_to_rust).collect();
+            format!("{}<{}>", name, parts.join(", "))
+        }
     }
 }
 
@@ -495,9 +575,12 @@ fn type_info_to_rust(ty: &TypeInfo) -> String {
 ///     system_program: Program<System>,
 /// }
 /// ```
+///
+/// Fields whose type is in `zero_copy_types` are loaded with `AccountLoader`.
 pub fn parse_instruction_context(
     struct_def: &StructDefinition,
     account_attrs: &HashMap<String, Vec<String>>,
+    zero_copy_types: &HashSet<String>,
 ) -> Option<InstructionContext> {
     // Check if struct has #[instruction] attribute
     let is_instruction = struct_def
@@ -522,7 +605,7 @@ pub fn parse_instruction_context(
         }
 
         // Determine account type from field type
-        let account_type = infer_account_type(&field.type_info);
+        let account_type = infer_account_type(&field.type_info, zero_copy_types);
 
         accounts.push(InstructionAccount {
             name: field.name.clone(),
@@ -533,15 +616,39 @@ pub fn parse_instruction_context(
         });
     }
 
+    let args = struct_def
+        .metadata
+        .instruction_args
+        .iter()
+        .map(|arg| InstructionArg {
+            name: arg.name.clone(),
+            ty: arg.type_info.clone(),
+        })
+        .collect();
+
     Some(InstructionContext {
         name: struct_def.name.clone(),
         accounts,
-        args: Vec::new(),
+        args,
     })
 }
 
+/// Names of the `#[zero_copy]` structs in a schema
+pub fn zero_copy_type_names(type_defs: &[TypeDefinition]) -> HashSet<String> {
+    type_defs
+        .iter()
+        .filter_map(|t| match t {
+            TypeDefinition::Struct(s) if s.is_zero_copy() => Some(s.name.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Infer Anchor account type from LUMOS type
-fn infer_account_type(ty: &TypeInfo) -> AnchorAccountType {
+///
+/// `zero_copy_types` are the schema's `#[zero_copy]` structs, which Anchor
+/// loads with `AccountLoader` instead of deserializing into `Account`.
+fn infer_account_type(ty: &TypeInfo, zero_copy_types: &HashSet<String>) -> AnchorAccountType {
     match ty {
         TypeInfo::Primitive(name) if name == "Signer" => AnchorAccountType::Signer,
         TypeInfo::UserDefined(name) => {
@@ -565,6 +672,9 @@ fn infer_account_type(ty: &TypeInfo) -> AnchorAccountType {
                         .unwrap_or("Rent");
                     AnchorAccountType::Sysvar(inner.to_string())
                 }
+                _ if zero_copy_types.contains(name) => {
+                    AnchorAccountType::AccountLoader(name.clone())
+                }
                 _ => AnchorAccountType::Account(name.clone()),
             }
         }
@@ -688,6 +798,63 @@ mod tests {
             generate_account_type(&AnchorAccountType::Program("System".to_string())),
             "Program<'info, System>"
         );
+        assert_eq!(
+            generate_account_type(&AnchorAccountType::AccountLoader("Book".to_string())),
+            "AccountLoader<'info, Book>"
+        );
+    }
+
+    #[test]
+    fn test_zero_copy_accounts_use_account_loader() {
+        use crate::parser::parse_lumos_file;
+        use crate::transform::transform_to_ir;
+
+        let source = r#"
+            #[solana]
+            #[account]
+            #[zero_copy]
+            struct OrderBook {
+                authority: PublicKey,
+                best_bid: u64,
+            }
+
+            #[solana]
+            #[account]
+            struct Market {
+                book: PublicKey,
+            }
+
+            #[solana]
+            #[instruction]
+            struct PlaceOrder {
+                #[anchor(mut)]
+                order_book: OrderBook,
+
+                market: Market,
+            }
+        "#;
+
+        let type_defs = transform_to_ir(parse_lumos_file(source).unwrap()).unwrap();
+        let zero_copy_types = zero_copy_type_names(&type_defs);
+        assert_eq!(zero_copy_types.len(), 1);
+
+        let crate::ir::TypeDefinition::Struct(place_order) = &type_defs[2] else {
+            panic!("Expected struct");
+        };
+        let ctx = parse_instruction_context(place_order, &HashMap::new(), &zero_copy_types)
+            .expect("PlaceOrder is an instruction");
+        assert_eq!(
+            ctx.accounts[0].account_type,
+            AnchorAccountType::AccountLoader("OrderBook".to_string())
+        );
+        assert_eq!(
+            ctx.accounts[1].account_type,
+            AnchorAccountType::Account("Market".to_string())
+        );
+
+        let code = generate_accounts_context(&ctx);
+        assert!(code.contains("pub order_book: AccountLoader<'info, OrderBook>,"));
+        assert!(code.contains("pub market: Account<'info, Market>,"));
     }
 
     #[test]
@@ -828,7 +995,7 @@ mod tests {
                     attrs.extend(parse_anchor_attrs(attr_str));
                 }
 
-                let account_type = infer_account_type(&field.type_info);
+                let account_type = infer_account_type(&field.type_info, &HashSet::new());
 
                 accounts.push(InstructionAccount {
                     name: field.name.clone(),
@@ -6,11 +6,15 @@
 //! Generates Anchor IDL JSON from LUMOS type definitions.
 //! The IDL format follows the Anchor framework specification.
 
+use super::attributes::account_meta_flags;
 use crate::ir::{
     EnumDefinition, EnumVariantDefinition, FieldDefinition, StructDefinition, TypeDefinition,
     TypeInfo,
 };
+use crate::naming::{apply_renames, NameTarget};
+use crate::transform::{inline_newtypes, monomorphize};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 /// Anchor IDL root structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,6 +56,14 @@ pub struct IdlInstruction {
     /// Instruction name (snake_case)
     pub name: String,
 
+    /// Instruction documentation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+
+    /// 8-byte sighash prefixed to the instruction data
+    #[serde(default)]
+    pub discriminator: [u8; 8],
+
     /// Instruction accounts
     #[serde(default)]
     pub accounts: Vec<IdlAccountItem>,
@@ -151,11 +163,28 @@ pub struct IdlTypeDef {
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub docs: Vec<String>,
 
+    /// Serialization format (`"bytemuck"` for zero-copy types, omitted for Borsh)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serialization: Option<String>,
+
+    /// Memory layout of zero-copy types
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repr: Option<IdlRepr>,
+
     /// Type definition (struct or enum)
     #[serde(rename = "type")]
     pub ty: IdlTypeDefTy,
 }
 
+/// Memory layout of a type definition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum IdlRepr {
+    /// `#[repr(C)]`
+    #[serde(rename = "c")]
+    C,
+}
+
 /// Type definition body
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "kind")]
@@ -228,6 +257,18 @@ pub enum IdlType {
     /// Vec type
     Vec(IdlTypeVec),
 
+    /// HashMap type
+    HashMap(IdlTypeHashMap),
+
+    /// BTreeMap type
+    BTreeMap(IdlTypeBTreeMap),
+
+    /// HashSet type
+    HashSet(IdlTypeHashSet),
+
+    /// BTreeSet type
+    BTreeSet(IdlTypeBTreeSet),
+
     /// Defined type (reference to another type)
     Defined(IdlTypeDefined),
 }
@@ -253,6 +294,38 @@ pub struct IdlTypeVec {
     pub vec: Box<IdlType>,
 }
 
+/// HashMap type wrapper (Shank-style `{"hashMap": [K, V]}`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdlTypeHashMap {
+    /// Key and value types
+    #[serde(rename = "hashMap")]
+    pub hash_map: (Box<IdlType>, Box<IdlType>),
+}
+
+/// BTreeMap type wrapper (Shank-style `{"bTreeMap": [K, V]}`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdlTypeBTreeMap {
+    /// Key and value types
+    #[serde(rename = "bTreeMap")]
+    pub btree_map: (Box<IdlType>, Box<IdlType>),
+}
+
+/// HashSet type wrapper (Shank-style `{"hashSet": T}`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdlTypeHashSet {
+    /// Element type
+    #[serde(rename = "hashSet")]
+    pub hash_set: Box<IdlType>,
+}
+
+/// BTreeSet type wrapper (Shank-style `{"bTreeSet": T}`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdlTypeBTreeSet {
+    /// Element type
+    #[serde(rename = "bTreeSet")]
+    pub btree_set: Box<IdlType>,
+}
+
 /// Defined type reference
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IdlTypeDefined {
@@ -266,6 +339,14 @@ pub struct IdlEvent {
     /// Event name
     pub name: String,
 
+    /// 8-byte discriminator prefixed to the event data (`sha256("event:<Name>")[..8]`)
+    #[serde(default)]
+    pub discriminator: [u8; 8],
+
+    /// Event documentation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub docs: Vec<String>,
+
     /// Event fields
     pub fields: Vec<IdlEventField>,
 }
@@ -341,12 +422,26 @@ impl IdlGenerator {
     }
 
     /// Generate an Anchor IDL from type definitions
+    ///
+    /// The IDL has no generics: each instantiation such as `Wrapper<u64>`
+    /// becomes a concrete `WrapperU64` type in place of `Wrapper<T>`, and
+    /// `#[newtype]` fields show the type they wrap.
     pub fn generate(&self, type_defs: &[TypeDefinition]) -> Idl {
+        let type_defs = &monomorphize(&apply_renames(&inline_newtypes(type_defs), NameTarget::Idl));
+        let mut instructions = Vec::new();
         let mut accounts = Vec::new();
         let mut types = Vec::new();
+        let mut events = Vec::new();
+        let mut errors = Vec::new();
 
         for type_def in type_defs {
             match type_def {
+                TypeDefinition::Struct(struct_def) if struct_def.metadata.is_instruction => {
+                    instructions.push(self.convert_instruction(struct_def));
+                }
+                TypeDefinition::Struct(struct_def) if struct_def.is_event() => {
+                    events.push(self.convert_event(struct_def));
+                }
                 TypeDefinition::Struct(struct_def) => {
                     let idl_type = self.convert_struct(struct_def);
                     if self.is_account_type(struct_def) {
@@ -355,24 +450,28 @@ impl IdlGenerator {
                         types.push(idl_type);
                     }
                 }
+                TypeDefinition::Enum(enum_def) if enum_def.is_error_code() => {
+                    errors.extend(self.convert_errors(enum_def));
+                }
                 TypeDefinition::Enum(enum_def) => {
                     types.push(self.convert_enum(enum_def));
                 }
-                TypeDefinition::TypeAlias(_) => {
-                    // Type aliases are resolved during transformation,
+                TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {
+                    // Type aliases and constants are resolved during transformation,
                     // they don't appear in the IDL
                 }
             }
         }
+        types.extend(tuple_type_defs(type_defs));
 
         Idl {
             version: self.config.version.clone(),
             name: to_snake_case(&self.config.program_name),
-            instructions: Vec::new(), // Instructions are parsed separately
+            instructions,
             accounts,
             types,
-            events: Vec::new(),
-            errors: Vec::new(),
+            events,
+            errors,
             metadata: self.config.address.as_ref().map(|addr| IdlMetadata {
                 address: Some(addr.clone()),
             }),
@@ -387,15 +486,96 @@ impl IdlGenerator {
             .map(|f| self.convert_field(f))
             .collect();
 
+        let zero_copy = struct_def.is_zero_copy();
+
         IdlTypeDef {
             name: struct_def.name.clone(),
-            docs: Vec::new(),
+            docs: struct_def.metadata.docs.clone(),
+            serialization: zero_copy.then(|| "bytemuck".to_string()),
+            repr: zero_copy.then_some(IdlRepr::C),
             ty: IdlTypeDefTy::Struct { fields },
         }
     }
 
+    /// Convert an `#[instruction]` struct to an IDL instruction
+    ///
+    /// The struct's fields are the accounts; its `#[instruction(...)]`
+    /// arguments become the instruction args.
+    fn convert_instruction(&self, struct_def: &StructDefinition) -> IdlInstruction {
+        let accounts = struct_def
+            .fields
+            .iter()
+            .map(|field| {
+                let flags = account_meta_flags(field);
+                IdlAccountItem::Single(IdlAccount {
+                    name: field.name.clone(),
+                    is_mut: flags.is_writable,
+                    is_signer: flags.is_signer,
+                    is_optional: field.optional.then_some(true),
+                    docs: field.docs.clone(),
+                    pda: None,
+                })
+            })
+            .collect();
+
+        let args = struct_def
+            .metadata
+            .instruction_args
+            .iter()
+            .map(|arg| self.convert_field(arg))
+            .collect();
+
+        IdlInstruction {
+            name: instruction_name(&struct_def.name),
+            docs: struct_def.metadata.docs.clone(),
+            discriminator: instruction_discriminator(&struct_def.name),
+            accounts,
+            args,
+        }
+    }
+
+    /// Convert an `#[error_code]` enum to IDL errors, one per variant
+    fn convert_errors(&self, enum_def: &EnumDefinition) -> Vec<IdlError> {
+        enum_def
+            .error_codes()
+            .into_iter()
+            .map(|(code, variant)| IdlError {
+                code,
+                name: variant.name().to_string(),
+                msg: variant.msg().map(str::to_string),
+            })
+            .collect()
+    }
+
+    /// Convert an `#[event]` struct to an IDL event
+    fn convert_event(&self, struct_def: &StructDefinition) -> IdlEvent {
+        let fields = struct_def
+            .fields
+            .iter()
+            .map(|f| {
+                let field = self.convert_field(f);
+                IdlEventField {
+                    name: field.name,
+                    ty: field.ty,
+                    index: false,
+                }
+            })
+            .collect();
+
+        IdlEvent {
+            name: struct_def.name.clone(),
+            discriminator: event_discriminator(&struct_def.name),
+            docs: struct_def.metadata.docs.clone(),
+            fields,
+        }
+    }
+
     /// Convert an enum definition to IDL type definition
     fn convert_enum(&self, enum_def: &EnumDefinition) -> IdlTypeDef {
+        if enum_def.is_flags() {
+            return self.convert_flags(enum_def);
+        }
+
         let variants = enum_def
             .variants
             .iter()
@@ -404,8 +584,39 @@ impl IdlGenerator {
 
         IdlTypeDef {
             name: enum_def.name.clone(),
-            docs: Vec::new(),
+            docs: enum_def.metadata.docs.clone(),
             ty: IdlTypeDefTy::Enum { variants },
+            serialization: None,
+            repr: None,
+        }
+    }
+
+    /// Convert a `#[flags]` enum to a struct wrapping its packed integer
+    ///
+    /// This is the newtype's Borsh layout; the IDL has no bitflags type, so
+    /// the flag values are listed in the docs.
+    fn convert_flags(&self, enum_def: &EnumDefinition) -> IdlTypeDef {
+        let flags: Vec<String> = enum_def
+            .variants
+            .iter()
+            .zip(enum_def.discriminants())
+            .map(|(variant, bit)| format!("{} = {}", variant.name(), bit))
+            .collect();
+        let mut docs = enum_def.metadata.docs.clone();
+        docs.push(format!("Flags: {}", flags.join(", ")));
+
+        IdlTypeDef {
+            name: enum_def.name.clone(),
+            docs,
+            ty: IdlTypeDefTy::Struct {
+                fields: vec![IdlField {
+                    name: "bits".to_string(),
+                    ty: IdlType::Primitive(enum_def.repr().name().to_string()),
+                    docs: Vec::new(),
+                }],
+            },
+            serialization: None,
+            repr: None,
         }
     }
 
@@ -420,31 +631,35 @@ impl IdlGenerator {
             self.convert_type(&field.type_info)
         };
 
-        IdlField {
-            name: to_snake_case(&field.name),
-            ty,
-            docs: field
-                .deprecated
-                .as_ref()
-                .map(|msg| vec![format!("@deprecated {}", msg)])
-                .unwrap_or_default(),
+        let mut docs = field.docs.clone();
+        if let Some(msg) = &field.deprecated {
+            docs.push(format!("@deprecated {}", msg));
         }
+
+        // An explicit IDL name is used as is, the rest follow Anchor's snake_case
+        let name = if field.renames.contains_key(&NameTarget::Idl) {
+            field.name.clone()
+        } else {
+            to_snake_case(&field.name)
+        };
+
+        IdlField { name, ty, docs }
     }
 
     /// Convert an enum variant to IDL variant
     fn convert_variant(&self, variant: &EnumVariantDefinition) -> IdlEnumVariant {
         match variant {
-            EnumVariantDefinition::Unit { name } => IdlEnumVariant {
+            EnumVariantDefinition::Unit { name, .. } => IdlEnumVariant {
                 name: name.clone(),
                 fields: None,
             },
-            EnumVariantDefinition::Tuple { name, types } => IdlEnumVariant {
+            EnumVariantDefinition::Tuple { name, types, .. } => IdlEnumVariant {
                 name: name.clone(),
                 fields: Some(IdlEnumFields::Tuple(
                     types.iter().map(|t| self.convert_type(t)).collect(),
                 )),
             },
-            EnumVariantDefinition::Struct { name, fields } => IdlEnumVariant {
+            EnumVariantDefinition::Struct { name, fields, .. } => IdlEnumVariant {
                 name: name.clone(),
                 fields: Some(IdlEnumFields::Named(
                     fields.iter().map(|f| self.convert_field(f)).collect(),
@@ -471,6 +686,11 @@ impl IdlGenerator {
     pub fn calculate_account_space(&self, struct_def: &StructDefinition) -> usize {
         let mut size = 8; // Anchor discriminator
 
+        // Zero-copy accounts are exactly their C layout
+        if let Some(layout) = &struct_def.metadata.c_layout {
+            return size + layout.size;
+        }
+
         for field in &struct_def.fields {
             size += calculate_type_size(&field.type_info, field.optional);
         }
@@ -522,16 +742,92 @@ fn convert_type_to_idl(type_info: &TypeInfo) -> IdlType {
         TypeInfo::UserDefined(name) => IdlType::Defined(IdlTypeDefined {
             defined: name.clone(),
         }),
+        // Generic instances are emitted as concrete types (`WrapperU64`)
+        TypeInfo::Instance { .. } => IdlType::Defined(IdlTypeDefined {
+            defined: type_info.pascal_name(),
+        }),
         TypeInfo::Array(inner) => IdlType::Vec(IdlTypeVec {
             vec: Box::new(convert_type_to_idl(inner)),
         }),
         TypeInfo::FixedArray { element, size } => IdlType::Array(IdlTypeArray {
             array: (Box::new(convert_type_to_idl(element)), *size),
         }),
+        // The IDL has no fixed string type; clients see the raw bytes
+        TypeInfo::FixedString(size) => IdlType::Array(IdlTypeArray {
+            array: (Box::new(IdlType::Primitive("u8".to_string())), *size),
+        }),
         TypeInfo::Option(inner) => IdlType::Option(IdlTypeOption {
             option: Box::new(convert_type_to_idl(inner)),
         }),
+        TypeInfo::Map {
+            key,
+            value,
+            ordered,
+        } => {
+            let entry = (
+                Box::new(convert_type_to_idl(key)),
+                Box::new(convert_type_to_idl(value)),
+            );
+            if *ordered {
+                IdlType::BTreeMap(IdlTypeBTreeMap { btree_map: entry })
+            } else {
+                IdlType::HashMap(IdlTypeHashMap { hash_map: entry })
+            }
+        }
+        TypeInfo::Set { element, ordered } => {
+            let element = Box::new(convert_type_to_idl(element));
+            if *ordered {
+                IdlType::BTreeSet(IdlTypeBTreeSet { btree_set: element })
+            } else {
+                IdlType::HashSet(IdlTypeHashSet { hash_set: element })
+            }
+        }
+        // The IDL has no tuple type; tuples refer to a generated struct (see `tuple_type_defs`)
+        // Box<T> is serialized like T, so the IDL describes T
+        TypeInfo::Boxed(inner) => convert_type_to_idl(inner),
+        TypeInfo::Tuple(_) => IdlType::Defined(IdlTypeDefined {
+            defined: type_info.pascal_name(),
+        }),
+    }
+}
+
+/// Struct definitions standing in for every tuple type used in the schema
+///
+/// A tuple is Borsh-encoded exactly like a struct of its elements, so
+/// `(u64, PublicKey)` becomes a `TupleU64PublicKey` struct with fields `0` and `1`.
+fn tuple_type_defs(type_defs: &[TypeDefinition]) -> Vec<IdlTypeDef> {
+    let mut tuples = Vec::new();
+    for type_info in type_defs.iter().flat_map(|t| t.field_types()) {
+        type_info.walk(&mut |ty| {
+            if matches!(ty, TypeInfo::Tuple(_)) && !tuples.contains(ty) {
+                tuples.push(ty.clone());
+            }
+        });
     }
+
+    tuples
+        .iter()
+        .filter_map(|tuple| match tuple {
+            TypeInfo::Tuple(elements) => Some(IdlTypeDef {
+                name: tuple.pascal_name(),
+                docs: Vec::new(),
+                serialization: None,
+                repr: None,
+                ty: IdlTypeDefTy::Struct {
+                    fields: elements
+                        .iter()
+                        .enumerate()
+                        .map(|(i, element)| IdlField {
+                            name: i.to_string(),
+                            ty: convert_type_to_idl(element),
+                            docs: Vec::new(),
+                        })
+                        .collect(),
+                },
+            }),
+            _ => None,
+        })
+        .collect()
 }
 
 /// Calculate the size of a type in bytes (standalone function for recursion)
@@ -548,11 +844,18 @@ fn calculate_type_size(type_info: &TypeInfo, optional: bool) -> usize {
             "String" | "string" => 4, // Only the length prefix, actual content is variable
             _ => 0,
         },
-        TypeInfo::Generic(_) => 0,     // Generic types have unknown size
-        TypeInfo::UserDefined(_) => 0, // User-defined types need separate calculation
+        TypeInfo::Generic(_) => 0, // Generic types have unknown size
+        TypeInfo::UserDefined(_) | TypeInfo::Instance { .. } => 0, // User-defined types need separate calculation
         TypeInfo::Array(inner) => 4 + calculate_type_size(inner, false), // Vec prefix + content
         TypeInfo::FixedArray { element, size } => calculate_type_size(element, false) * size,
+        TypeInfo::FixedString(size) => *size,
         TypeInfo::Option(inner) => 1 + calculate_type_size(inner, false),
+        TypeInfo::Map { key, value, .. } => {
+            4 + calculate_type_size(key, false) + calculate_type_size(value, false)
+        }
+        TypeInfo::Set { element, .. } => 4 + calculate_type_size(element, false),
+        TypeInfo::Boxed(inner) => calculate_type_size(inner, false),
+        TypeInfo::Tuple(elements) => elements.iter().map(|e| calculate_type_size(e, false)).sum(),
     };
 
     if optional && !matches!(type_info, TypeInfo::Option(_)) {
@@ -562,6 +865,29 @@ fn calculate_type_size(type_info: &TypeInfo, optional: bool) -> usize {
     }
 }
 
+/// Compute an Anchor discriminator: the first 8 bytes of `sha256("<namespace>:<name>")`
+pub fn anchor_discriminator(namespace: &str, name: &str) -> [u8; 8] {
+    let hash = Sha256::digest(format!("{}:{}", namespace, name).as_bytes());
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash[..8]);
+    discriminator
+}
+
+/// Compute the discriminator Anchor's `emit!` prefixes to an event's data
+pub fn event_discriminator(event_name: &str) -> [u8; 8] {
+    anchor_discriminator("event", event_name)
+}
+
+/// Name of the handler an `#[instruction]` struct describes (`InitializeVault` -> `initialize_vault`)
+pub fn instruction_name(struct_name: &str) -> String {
+    to_snake_case(struct_name)
+}
+
+/// Compute the sighash Anchor prefixes to an instruction's data: `sha256("global:<name>")[..8]`
+pub fn instruction_discriminator(struct_name: &str) -> [u8; 8] {
+    anchor_discriminator("global", &instruction_name(struct_name))
+}
+
 /// Convert a string to snake_case
 fn to_snake_case(s: &str) -> String {
     let mut result = String::new();
@@ -610,38 +936,28 @@ mod tests {
                 FieldDefinition {
                     name: "authority".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "level".to_string(),
                     type_info: TypeInfo::Primitive("u16".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "experience".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         };
 
         let config = IdlGeneratorConfig {
@@ -660,6 +976,314 @@ mod tests {
         assert_eq!(idl.accounts[0].name, "PlayerAccount");
     }
 
+    #[test]
+    fn test_docs_carried_into_idl() {
+        let struct_def = StructDefinition {
+            name: "Vault".to_string(),
+            generic_params: vec![],
+            fields: vec![FieldDefinition {
+                name: "balance".to_string(),
+                type_info: TypeInfo::Primitive("u64".to_string()),
+                deprecated: Some("Use lamports instead".to_string()),
+                docs: vec!["Balance in base units".to_string()],
+                ..Default::default()
+            }],
+            metadata: Metadata {
+                solana: true,
+                attributes: vec!["account".to_string()],
+                docs: vec!["Program-owned vault".to_string()],
+                ..Metadata::default()
+            },
+            visibility: Visibility::Public,
+            module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
+        };
+
+        let generator = IdlGenerator::new(IdlGeneratorConfig::default());
+        let idl = generator.generate(&[TypeDefinition::Struct(struct_def)]);
+
+        assert_eq!(idl.accounts[0].docs, vec!["Program-owned vault"]);
+        if let IdlTypeDefTy::Struct { fields } = &idl.accounts[0].ty {
+            assert_eq!(
+                fields[0].docs,
+                vec!["Balance in base units", "@deprecated Use lamports instead"]
+            );
+        } else {
+            panic!("Expected struct type");
+        }
+    }
+
+    #[test]
+    fn test_map_and_set_idl_types() {
+        let map = convert_type_to_idl(&TypeInfo::Map {
+            key: Box::new(TypeInfo::Primitive("PublicKey".to_string())),
+            value: Box::new(TypeInfo::Primitive("u64".to_string())),
+            ordered: false,
+        });
+        assert_eq!(
+            serde_json::to_value(&map).unwrap(),
+            serde_json::json!({ "hashMap": ["publicKey", "u64"] })
+        );
+
+        let set = convert_type_to_idl(&TypeInfo::Set {
+            element: Box::new(TypeInfo::Primitive("u32".to_string())),
+            ordered: true,
+        });
+        assert_eq!(
+            serde_json::to_value(&set).unwrap(),
+            serde_json::json!({ "bTreeSet": "u32" })
+        );
+    }
+
+    #[test]
+    fn test_event_discriminator() {
+        // sha256("event:TradeExecuted")[..8]
+        assert_eq!(
+            event_discriminator("TradeExecuted"),
+            [41, 110, 64, 129, 60, 79, 179, 80]
+        );
+    }
+
+    #[test]
+    fn test_event_structs_populate_idl_events() {
+        let source = r#"
+            #[solana]
+            #[event]
+            struct TradeExecuted {
+                trader: PublicKey,
+                amount: u64,
+            }
+
+            #[solana]
+            struct Quote {
+                price: u64,
+            }
+
+            // Without #[solana] the generators emit no event, so neither does the IDL
+            #[event]
+            struct Fill {
+                amount: u64,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let idl = IdlGenerator::new(IdlGeneratorConfig::default()).generate(&ir);
+
+        assert_eq!(idl.events.len(), 1);
+        assert_eq!(idl.types.len(), 2);
+        assert_eq!(idl.types[1].name, "Fill");
+        let event = &idl.events[0];
+        assert_eq!(event.name, "TradeExecuted");
+        assert_eq!(event.discriminator, event_discriminator("TradeExecuted"));
+        assert_eq!(event.fields.len(), 2);
+        assert_eq!(event.fields[0].name, "trader");
+
+        let json = serde_json::to_value(&idl).unwrap();
+        assert_eq!(
+            json["events"][0]["discriminator"],
+            serde_json::json!([41, 110, 64, 129, 60, 79, 179, 80])
+        );
+    }
+
+    #[test]
+    fn test_error_code_enums_populate_idl_errors() {
+        let source = r#"
+            #[error_code]
+            enum VaultError {
+                #[msg("Insufficient funds")]
+                InsufficientFunds,
+                Unauthorized,
+            }
+
+            #[solana]
+            enum Side {
+                Buy,
+                Sell,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let idl = IdlGenerator::new(IdlGeneratorConfig::default()).generate(&ir);
+
+        assert_eq!(idl.types.len(), 1);
+        assert_eq!(idl.errors.len(), 2);
+        assert_eq!(idl.errors[0].code, 6000);
+        assert_eq!(idl.errors[0].name, "InsufficientFunds");
+        assert_eq!(idl.errors[0].msg.as_deref(), Some("Insufficient funds"));
+        assert_eq!(idl.errors[1].code, 6001);
+        assert_eq!(idl.errors[1].msg, None);
+    }
+
+    #[test]
+    fn test_zero_copy_types_use_bytemuck_c_layout() {
+        let source = r#"
+            #[solana]
+            #[account]
+            #[zero_copy]
+            struct OrderBook {
+                authority: PublicKey,
+                count: u32,
+                best_bid: u64,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let generator = IdlGenerator::new(IdlGeneratorConfig::default());
+        let idl = generator.generate(&ir);
+        let account = &idl.accounts[0];
+        assert_eq!(account.serialization.as_deref(), Some("bytemuck"));
+        assert_eq!(account.repr, Some(IdlRepr::C));
+
+        let json = serde_json::to_value(account).unwrap();
+        assert_eq!(json["serialization"], "bytemuck");
+        assert_eq!(json["repr"]["kind"], "c");
+        // The padding field keeps `best_bid` 8-byte aligned
+        assert_eq!(json["type"]["fields"][2]["name"], "padding0");
+
+        let crate::ir::TypeDefinition::Struct(book) = &ir[0] else {
+            panic!("Expected struct");
+        };
+        assert_eq!(generator.calculate_account_space(book), 8 + 48);
+    }
+
+    #[test]
+    fn test_tuples_become_defined_types() {
+        let source = r#"
+            #[solana]
+            #[account]
+            struct Pool {
+                pair: (u64, PublicKey),
+                shares: Vec<(PublicKey, u16)>,
+                backup: (u64, PublicKey),
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let generator = IdlGenerator::new(IdlGeneratorConfig::default());
+        let idl = generator.generate(&ir);
+        let json = serde_json::to_value(&idl).unwrap();
+
+        let fields = &json["accounts"][0]["type"]["fields"];
+        assert_eq!(fields[0]["type"]["defined"], "TupleU64PublicKey");
+        assert_eq!(fields[1]["type"]["vec"]["defined"], "TuplePublicKeyU16");
+        assert_eq!(fields[2]["type"]["defined"], "TupleU64PublicKey");
+
+        // One defined type per distinct tuple, fields named by position
+        let names: Vec<_> = idl.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["TupleU64PublicKey", "TuplePublicKeyU16"]);
+        let tuple = serde_json::to_value(&idl.types[0]).unwrap();
+        assert_eq!(tuple["type"]["fields"][0]["name"], "0");
+        assert_eq!(tuple["type"]["fields"][1]["type"], "publicKey");
+
+        let crate::ir::TypeDefinition::Struct(pool) = &ir[0] else {
+            panic!("Expected struct");
+        };
+        assert_eq!(
+            generator.calculate_account_space(pool),
+            8 + 40 + (4 + 34) + 40
+        );
+    }
+
+    #[test]
+    fn test_generic_instances_become_concrete_types() {
+        let source = r#"
+            #[solana]
+            struct Wrapper<T> {
+                value: T,
+                items: Vec<T>,
+            }
+
+            #[solana]
+            #[account]
+            struct Holder {
+                a: Wrapper<u64>,
+                b: Option<Wrapper<PublicKey>>,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let idl = IdlGenerator::new(IdlGeneratorConfig::default()).generate(&ir);
+        let json = serde_json::to_value(&idl).unwrap();
+
+        let fields = &json["accounts"][0]["type"]["fields"];
+        assert_eq!(fields[0]["type"]["defined"], "WrapperU64");
+        assert_eq!(fields[1]["type"]["option"]["defined"], "WrapperPublicKey");
+
+        // `Wrapper<T>` itself has no layout; each instance is a concrete type
+        let names: Vec<_> = idl.types.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["WrapperU64", "WrapperPublicKey"]);
+        let wrapper = serde_json::to_value(&idl.types[1]).unwrap();
+        assert_eq!(wrapper["type"]["fields"][0]["type"], "publicKey");
+        assert_eq!(wrapper["type"]["fields"][1]["type"]["vec"], "publicKey");
+    }
+
+    #[test]
+    fn test_instructions_populate_idl_instructions() {
+        let source = r#"
+            /// Move lamports into the vault
+            #[solana]
+            #[instruction(amount: u64)]
+            struct Deposit {
+                #[anchor(mut, has_one = owner)]
+                vault: Vault,
+
+                owner: Signer,
+
+                #[anchor(init, payer = owner, space = 8 + 8)]
+                receipt: Option<Vault>,
+            }
+
+            #[solana]
+            #[account]
+            struct Vault {
+                owner: PublicKey,
+            }
+
+            #[solana]
+            struct Signer {}
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let idl = IdlGenerator::new(IdlGeneratorConfig::default()).generate(&ir);
+
+        assert_eq!(idl.instructions.len(), 1);
+        assert!(idl.types.iter().all(|t| t.name != "Deposit"));
+        let ix = &idl.instructions[0];
+        assert_eq!(ix.name, "deposit");
+        assert_eq!(ix.docs, vec!["Move lamports into the vault"]);
+        // sha256("global:deposit")[..8]
+        assert_eq!(ix.discriminator, [242, 35, 198, 137, 82, 225, 242, 182]);
+        assert_eq!(ix.args.len(), 1);
+        assert_eq!(ix.args[0].name, "amount");
+
+        let flags: Vec<(&str, bool, bool, Option<bool>)> = ix
+            .accounts
+            .iter()
+            .map(|item| match item {
+                IdlAccountItem::Single(a) => {
+                    (a.name.as_str(), a.is_mut, a.is_signer, a.is_optional)
+                }
+                IdlAccountItem::Composite(_) => panic!("Expected single account"),
+            })
+            .collect();
+        assert_eq!(
+            flags,
+            vec![
+                ("vault", true, false, None),
+                ("owner", false, true, None),
+                // init without seeds: a fresh keypair signs its own creation
+                ("receipt", true, true, Some(true)),
+            ]
+        );
+    }
+
     #[test]
     fn test_calculate_account_space() {
         let struct_def = StructDefinition {
@@ -669,32 +1293,25 @@ mod tests {
                 FieldDefinition {
                     name: "authority".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "level".to_string(),
                     type_info: TypeInfo::Primitive("u16".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "experience".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         };
 
         let generator = IdlGenerator::new(IdlGeneratorConfig::default());
@@ -712,26 +1329,33 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Struct {
                     name: "Finished".to_string(),
                     fields: vec![FieldDefinition {
                         name: "winner".to_string(),
                         type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                        optional: false,
-                        deprecated: None,
-                        span: None,
-                        anchor_attrs: vec![],
+                        ..Default::default()
                     }],
+                    docs: vec![],
+                    discriminant: None,
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         };
 
         let config = IdlGeneratorConfig::default();
@@ -769,6 +1393,8 @@ mod tests {
                         docs: vec![],
                     }],
                 },
+                serialization: None,
+                repr: None,
             }],
             types: vec![],
             events: vec![],
@@ -819,24 +1445,20 @@ mod tests {
                 FieldDefinition {
                     name: "owner".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "amount".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         };
 
         let generator = IdlGenerator::new(IdlGeneratorConfig::default());
@@ -845,4 +1467,95 @@ mod tests {
         assert!(len_const.contains("impl MyAccount"));
         assert!(len_const.contains("pub const LEN: usize = 48")); // 8 + 32 + 8
     }
+
+    #[test]
+    fn test_idl_renames() {
+        let source = r#"
+            #![rename_fields(idl = "camelCase")]
+            #![rename_types(idl = "PascalCase")]
+
+            #[solana]
+            #[account]
+            struct vault_state {
+                owner_key: PublicKey,
+            }
+
+            #[solana]
+            struct fee_config {
+                #[rename(idl = "rate")]
+                fee_bps: u16,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let idl = IdlGenerator::new(IdlGeneratorConfig::default()).generate(&ir);
+        // Account names feed the discriminator, so the policy leaves them alone
+        assert_eq!(idl.accounts[0].name, "vault_state");
+        assert_eq!(idl.types[0].name, "FeeConfig");
+        let json = serde_json::to_string(&idl).unwrap();
+        assert!(json.contains("\"ownerKey\""));
+        assert!(json.contains("\"rate\""));
+    }
+
+    #[test]
+    fn test_flags_enum_wraps_integer() {
+        let source = r#"
+            #[solana]
+            #[flags(u16)]
+            enum Permissions {
+                Mint,
+                Burn,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let idl = IdlGenerator::new(IdlGeneratorConfig::default()).generate(&ir);
+        let permissions = &idl.types[0];
+        assert_eq!(
+            permissions.docs,
+            vec!["Flags: Mint = 1, Burn = 2".to_string()]
+        );
+        let IdlTypeDefTy::Struct { fields } = &permissions.ty else {
+            panic!("Expected struct type");
+        };
+        assert_eq!(fields[0].name, "bits");
+        assert!(matches!(&fields[0].ty, IdlType::Primitive(p) if p == "u16"));
+    }
+
+    #[test]
+    fn test_fixed_string_is_byte_array() {
+        let generator = IdlGenerator::new(IdlGeneratorConfig::default());
+        let ty = generator.convert_type(&TypeInfo::FixedString(32));
+        assert_eq!(
+            serde_json::to_value(&ty).unwrap(),
+            serde_json::json!({ "array": ["u8", 32] })
+        );
+    }
+
+    #[test]
+    fn test_newtype_fields_show_underlying_type() {
+        let source = r#"
+            #[newtype]
+            type UserId = PublicKey;
+
+            #[solana]
+            struct Profile {
+                owner: UserId,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let type_defs = crate::transform::transform_to_ir(ast).unwrap();
+
+        let idl = IdlGenerator::new(IdlGeneratorConfig::default()).generate(&type_defs);
+        let json = serde_json::to_value(&idl).unwrap();
+        let profile = json["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|t| t["name"] == "Profile")
+            .unwrap();
+        assert_eq!(profile["type"]["fields"][0]["type"], "publicKey");
+    }
 }
@@ -10,6 +10,7 @@
 //!
 //! - **IDL Generation**: Generate Anchor IDL JSON from LUMOS schemas
 //! - **Account Space Calculation**: Auto-calculate account sizes with discriminator
+//! - **Events**: `#[event]` structs become IDL events with their `emit!` discriminators
 //! - **Type Mapping**: Convert LUMOS types to Anchor IDL types
 //!
 //! # Example
@@ -46,11 +47,12 @@ mod attributes;
 mod idl;
 
 pub use attributes::{
-    generate_accounts_context, parse_anchor_attrs, parse_instruction_context, AnchorAccountAttr,
-    AnchorAccountType, AnchorFieldAttrs, InstructionAccount, InstructionArg, InstructionContext,
-    SeedComponent,
+    account_meta_flags, generate_accounts_context, parse_anchor_attrs, parse_instruction_context,
+    zero_copy_type_names, AccountMetaFlags, AnchorAccountAttr, AnchorAccountType, AnchorFieldAttrs,
+    InstructionAccount, InstructionArg, InstructionContext, SeedComponent,
 };
 pub use idl::{
-    Idl, IdlAccount, IdlAccountItem, IdlEnumVariant, IdlField, IdlGenerator, IdlGeneratorConfig,
-    IdlInstruction, IdlType, IdlTypeDef, IdlTypeDefTy,
+    anchor_discriminator, event_discriminator, instruction_discriminator, instruction_name, Idl,
+    IdlAccount, IdlAccountItem, IdlEnumVariant, IdlEvent, IdlEventField, IdlField, IdlGenerator,
+    IdlGeneratorConfig, IdlInstruction, IdlType, IdlTypeDef, IdlTypeDefTy,
 };
@@ -17,6 +17,10 @@ pub struct LumosFile {
 
     /// All items (structs, enums, type aliases) in this file
     pub items: Vec<Item>,
+
+    /// Inner attributes applying to the whole file (e.g., `#![rename_fields(ts = "camelCase")]`)
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
 }
 
 /// An import statement (JavaScript-style, legacy)
@@ -50,12 +54,18 @@ pub struct Module {
 /// A use statement (Rust-style: `use path::Type;`)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UseStatement {
-    /// Module path (e.g., crate::models::User)
+    /// Module path (e.g., crate::models::User, or crate::models for a glob)
     pub path: ModulePath,
 
     /// Optional alias (e.g., `use path::Type as Alias;`)
     pub alias: Option<String>,
 
+    /// `Public` for `pub use`, which re-exports the item from this module
+    pub visibility: Visibility,
+
+    /// Glob import of every visible item of the module (`use path::*;`)
+    pub glob: bool,
+
     /// Span information for error reporting
     #[serde(skip)]
     pub span: Option<proc_macro2::Span>,
@@ -107,12 +117,57 @@ pub struct TypeAlias {
     /// Target type (e.g., PublicKey)
     pub target: TypeSpec,
 
+    /// Attributes (e.g., `#[newtype]`)
+    pub attributes: Vec<Attribute>,
+
     /// Span information for error reporting
     #[serde(skip)]
     pub span: Option<proc_macro2::Span>,
 }
 
-/// An item in a LUMOS file (struct, enum, type alias, module, or use statement)
+/// A constant definition (e.g., `const MAX_NAME_LEN: usize = 32;`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstDef {
+    /// Constant name (e.g., "MAX_NAME_LEN")
+    pub name: String,
+
+    /// Visibility (pub or private)
+    pub visibility: Visibility,
+
+    /// Declared integer type (e.g., "usize", "u32")
+    pub type_name: String,
+
+    /// Constant value (literal or reference to another constant)
+    pub value: ConstExpr,
+
+    /// Doc comment lines (from `///` comments)
+    pub docs: Vec<String>,
+
+    /// Span information for error reporting
+    #[serde(skip)]
+    pub span: Option<proc_macro2::Span>,
+}
+
+/// An integer expression: either a literal or the name of a schema constant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConstExpr {
+    /// Integer literal (e.g., `32`)
+    Literal(u128),
+
+    /// Reference to a constant (e.g., `MAX_NAME_LEN`)
+    Named(String),
+}
+
+impl std::fmt::Display for ConstExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstExpr::Literal(value) => write!(f, "{}", value),
+            ConstExpr::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// An item in a LUMOS file (struct, enum, type alias, constant, module, or use statement)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Item {
     /// Struct definition
@@ -124,6 +179,9 @@ pub enum Item {
     /// Type alias definition
     TypeAlias(TypeAlias),
 
+    /// Constant definition
+    Const(ConstDef),
+
     /// Module declaration (Rust-style)
     Module(Module),
 
@@ -146,9 +204,16 @@ pub struct StructDef {
     /// Attributes applied to the struct (e.g., @solana, @account)
     pub attributes: Vec<Attribute>,
 
+    /// Doc comment lines (from `///` comments)
+    pub docs: Vec<String>,
+
     /// Fields in this struct
     pub fields: Vec<FieldDef>,
 
+    /// Handler arguments of an instruction (from `#[instruction(amount: u64, ...)]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instruction_args: Vec<FieldDef>,
+
     /// Optional semantic version (e.g., "1.0.0" from #[version = "1.0.0"])
     pub version: Option<String>,
 
@@ -172,6 +237,9 @@ pub struct EnumDef {
     /// Attributes applied to the enum (e.g., @solana)
     pub attributes: Vec<Attribute>,
 
+    /// Doc comment lines (from `///` comments)
+    pub docs: Vec<String>,
+
     /// Variants in this enum
     pub variants: Vec<EnumVariant>,
 
@@ -189,6 +257,13 @@ pub enum EnumVariant {
     /// Unit variant (e.g., `Active`)
     Unit {
         name: String,
+        docs: Vec<String>,
+        /// Error message from `#[msg("...")]`, used by `#[error_code]` enums
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        msg: Option<String>,
+        /// Explicit discriminant (e.g., `Active = 1`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        discriminant: Option<u64>,
         #[serde(skip)]
         span: Option<proc_macro2::Span>,
     },
@@ -197,6 +272,10 @@ pub enum EnumVariant {
     Tuple {
         name: String,
         types: Vec<TypeSpec>,
+        docs: Vec<String>,
+        /// Explicit discriminant (e.g., `Active = 1`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        discriminant: Option<u64>,
         #[serde(skip)]
         span: Option<proc_macro2::Span>,
     },
@@ -205,6 +284,10 @@ pub enum EnumVariant {
     Struct {
         name: String,
         fields: Vec<FieldDef>,
+        docs: Vec<String>,
+        /// Explicit discriminant (e.g., `Active = 1`)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        discriminant: Option<u64>,
         #[serde(skip)]
         span: Option<proc_macro2::Span>,
     },
@@ -225,6 +308,9 @@ pub struct FieldDef {
     /// Attributes applied to this field (e.g., @key, @max(32))
     pub attributes: Vec<Attribute>,
 
+    /// Doc comment lines (from `///` comments)
+    pub docs: Vec<String>,
+
     /// Span information for error reporting
     #[serde(skip)]
     pub span: Option<proc_macro2::Span>,
@@ -239,12 +325,43 @@ pub enum TypeSpec {
     /// Dynamic array type (e.g., `Vec<PublicKey>` in Rust, `[T]` in LUMOS)
     Array(Box<TypeSpec>),
 
-    /// Fixed-size array type (e.g., `[u8; 32]` in Rust and LUMOS)
-    FixedArray { element: Box<TypeSpec>, size: usize },
+    /// Fixed-size array type (e.g., `[u8; 32]` or `[u8; MAX_LEN]` in Rust and LUMOS)
+    FixedArray {
+        element: Box<TypeSpec>,
+        size: ConstExpr,
+    },
+
+    /// Map type (e.g., `HashMap<PublicKey, u64>` or `BTreeMap<PublicKey, u64>`)
+    Map {
+        key: Box<TypeSpec>,
+        value: Box<TypeSpec>,
+        /// `true` for `BTreeMap`, `false` for `HashMap`
+        ordered: bool,
+    },
+
+    /// Set type (e.g., `HashSet<PublicKey>` or `BTreeSet<PublicKey>`)
+    Set {
+        element: Box<TypeSpec>,
+        /// `true` for `BTreeSet`, `false` for `HashSet`
+        ordered: bool,
+    },
+
+    /// Tuple type (e.g., `(u64, PublicKey)`)
+    Tuple(Vec<TypeSpec>),
+
+    /// Boxed type (e.g., `Box<Node>`), needed for directly recursive types
+    Boxed(Box<TypeSpec>),
+
+    /// Fixed-capacity string (e.g., `FixedString<32>` or `FixedString<MAX_NAME>`),
+    /// stored as zero-padded UTF-8 bytes
+    FixedString(ConstExpr),
 
     /// Generic type parameter (e.g., T, U, K, V)
     Generic(String),
 
+    /// Generic user-defined type with type arguments (e.g., `Wrapper<u64>`)
+    Instance { name: String, args: Vec<TypeSpec> },
+
     /// User-defined type (e.g., Address, CustomStruct)
     UserDefined(String),
 }
@@ -263,6 +380,16 @@ pub struct Attribute {
     pub span: Option<proc_macro2::Span>,
 }
 
+impl Attribute {
+    /// Get the `key = "name"` pairs of `#[rename(..)]`-style attributes
+    pub fn names(&self) -> Option<&[(String, String)]> {
+        match &self.value {
+            Some(AttributeValue::Names(names)) => Some(names),
+            _ => None,
+        }
+    }
+}
+
 /// Attribute value
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AttributeValue {
@@ -277,6 +404,91 @@ pub enum AttributeValue {
 
     /// List of values (e.g., derive macros: `#[derive(Debug, Clone)]`)
     List(Vec<String>),
+
+    /// Value range (e.g., `#[range(0..=10000)]`)
+    Range(RangeExpr),
+
+    /// Field default (e.g., `#[default(100)]`)
+    Default(DefaultExpr),
+
+    /// Names per target language (e.g., `#[rename(ts = "feeBps", py = "fee")]`)
+    Names(Vec<(String, String)>),
+}
+
+/// A field default as written in `#[default(..)]`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DefaultExpr {
+    /// Integer literal (e.g., `100` or `-1`)
+    Integer(i128),
+
+    /// Floating-point literal (e.g., `0.5`)
+    Float(f64),
+
+    /// Boolean literal
+    Bool(bool),
+
+    /// String literal (e.g., `"USDC"`)
+    String(String),
+
+    /// Reference to a constant (e.g., `MAX_FEE_BPS`)
+    Named(String),
+}
+
+impl std::fmt::Display for DefaultExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DefaultExpr::Integer(value) => write!(f, "{}", value),
+            DefaultExpr::Float(value) => write!(f, "{:?}", value),
+            DefaultExpr::Bool(value) => write!(f, "{}", value),
+            DefaultExpr::String(value) => write!(f, "{:?}", value),
+            DefaultExpr::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// A value range as written in `#[range(..)]`; either bound may be omitted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RangeExpr {
+    /// Lower bound (inclusive)
+    pub start: Option<RangeBound>,
+
+    /// Upper bound
+    pub end: Option<RangeBound>,
+
+    /// Whether the upper bound is inclusive (`..=`) or exclusive (`..`)
+    pub inclusive: bool,
+}
+
+/// A range bound: a signed integer literal or the name of a schema constant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RangeBound {
+    /// Integer literal (e.g., `-100`)
+    Literal(i128),
+
+    /// Reference to a constant (e.g., `MAX_FEE_BPS`)
+    Named(String),
+}
+
+impl std::fmt::Display for RangeBound {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RangeBound::Literal(value) => write!(f, "{}", value),
+            RangeBound::Named(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl std::fmt::Display for RangeExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(start) = &self.start {
+            write!(f, "{}", start)?;
+        }
+        write!(f, "{}", if self.inclusive { "..=" } else { ".." })?;
+        if let Some(end) = &self.end {
+            write!(f, "{}", end)?;
+        }
+        Ok(())
+    }
 }
 
 impl StructDef {
@@ -326,6 +538,24 @@ impl EnumVariant {
             EnumVariant::Struct { name, .. } => name,
         }
     }
+
+    /// Get the explicitly declared discriminant, if any
+    pub fn discriminant(&self) -> Option<u64> {
+        match self {
+            EnumVariant::Unit { discriminant, .. }
+            | EnumVariant::Tuple { discriminant, .. }
+            | EnumVariant::Struct { discriminant, .. } => *discriminant,
+        }
+    }
+
+    /// Get the source location of the variant name
+    pub fn span(&self) -> Option<proc_macro2::Span> {
+        match self {
+            EnumVariant::Unit { span, .. }
+            | EnumVariant::Tuple { span, .. }
+            | EnumVariant::Struct { span, .. } => *span,
+        }
+    }
 }
 
 impl FieldDef {
@@ -349,9 +579,70 @@ impl FieldDef {
             }
         })
     }
+
+    /// Get the max length constraint as written, which may name a constant (`#[max(MAX_LEN)]`)
+    pub fn max_length_expr(&self) -> Option<ConstExpr> {
+        self.get_attribute("max")
+            .and_then(|attr| match &attr.value {
+                Some(AttributeValue::Integer(n)) => Some(ConstExpr::Literal(u128::from(*n))),
+                Some(AttributeValue::String(name)) => Some(ConstExpr::Named(name.clone())),
+                _ => None,
+            })
+    }
+
+    /// Get the value range from `#[range(..)]`, if present
+    pub fn range(&self) -> Option<&RangeExpr> {
+        self.get_attribute("range")
+            .and_then(|attr| match &attr.value {
+                Some(AttributeValue::Range(range)) => Some(range),
+                _ => None,
+            })
+    }
+
+    /// Get the default value from `#[default(..)]`, if present
+    pub fn default_value(&self) -> Option<&DefaultExpr> {
+        self.get_attribute("default")
+            .and_then(|attr| match &attr.value {
+                Some(AttributeValue::Default(value)) => Some(value),
+                _ => None,
+            })
+    }
+
+    /// Get the regular expression from `#[pattern("..")]`, if present
+    pub fn pattern(&self) -> Option<&str> {
+        self.get_attribute("pattern")
+            .and_then(|attr| match &attr.value {
+                Some(AttributeValue::String(pattern)) => Some(pattern.as_str()),
+                _ => None,
+            })
+    }
 }
 
 impl TypeSpec {
+    /// Visit this type and every type nested inside it, outermost first
+    pub fn walk(&self, visit: &mut dyn FnMut(&TypeSpec)) {
+        visit(self);
+        match self {
+            TypeSpec::Primitive(_)
+            | TypeSpec::Generic(_)
+            | TypeSpec::UserDefined(_)
+            | TypeSpec::FixedString(_) => {}
+            TypeSpec::Array(inner) | TypeSpec::Boxed(inner) => inner.walk(visit),
+            TypeSpec::FixedArray { element, .. } | TypeSpec::Set { element, .. } => {
+                element.walk(visit)
+            }
+            TypeSpec::Map { key, value, .. } => {
+                key.walk(visit);
+                value.walk(visit);
+            }
+            TypeSpec::Tuple(elements) | TypeSpec::Instance { args: elements, .. } => {
+                for element in elements {
+                    element.walk(visit);
+                }
+            }
+        }
+    }
+
     /// Check if this is an array type (dynamic or fixed)
     pub fn is_array(&self) -> bool {
         matches!(self, TypeSpec::Array(_) | TypeSpec::FixedArray { .. })
@@ -374,7 +665,44 @@ impl TypeSpec {
             TypeSpec::FixedArray { element, size } => {
                 format!("[{}; {}]", element.as_string(), size)
             }
+            TypeSpec::Map {
+                key,
+                value,
+                ordered,
+            } => format!(
+                "{}<{}, {}>",
+                if *ordered { "BTreeMap" } else { "HashMap" },
+                key.as_string(),
+                value.as_string()
+            ),
+            TypeSpec::Set { element, ordered } => format!(
+                "{}<{}>",
+                if *ordered { "BTreeSet" } else { "HashSet" },
+                element.as_string()
+            ),
+            // A one-element tuple keeps its trailing comma: `(T,)`
+            TypeSpec::Tuple(elements) if elements.len() == 1 => {
+                format!("({},)", elements[0].as_string())
+            }
+            TypeSpec::Tuple(elements) => format!(
+                "({})",
+                elements
+                    .iter()
+                    .map(|e| e.as_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            TypeSpec::Boxed(inner) => format!("Box<{}>", inner.as_string()),
+            TypeSpec::FixedString(size) => format!("FixedString<{}>", size),
             TypeSpec::Generic(name) => name.clone(),
+            TypeSpec::Instance { name, args } => format!(
+                "{}<{}>",
+                name,
+                args.iter()
+                    .map(|a| a.as_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
             TypeSpec::UserDefined(name) => name.clone(),
         }
     }
@@ -387,6 +715,11 @@ impl std::fmt::Display for TypeSpec {
 }
 
 impl TypeAlias {
+    /// Check if this alias is a `#[newtype]`, a distinct type rather than another name
+    pub fn is_newtype(&self) -> bool {
+        self.attributes.iter().any(|attr| attr.name == "newtype")
+    }
+
     /// Get the alias name
     pub fn alias_name(&self) -> &str {
         &self.name
@@ -410,6 +743,22 @@ impl Import {
     }
 }
 
+impl UseStatement {
+    /// Name the statement brings into scope (`Alias` for `use path::Type as Alias;`),
+    /// `None` for glob imports
+    pub fn local_name(&self) -> Option<&str> {
+        if self.glob {
+            return None;
+        }
+        self.alias.as_deref().or_else(|| self.path.final_ident())
+    }
+
+    /// Check if this is a `pub use` re-export
+    pub fn is_reexport(&self) -> bool {
+        self.visibility.is_public()
+    }
+}
+
 impl ModulePath {
     /// Create a new module path from segments
     pub fn new(segments: Vec<PathSegment>) -> Self {
@@ -520,6 +869,8 @@ mod tests {
             fields: vec![],
             version: None,
             span: None,
+            docs: vec![],
+            instruction_args: Vec::new(),
         };
 
         assert!(struct_def.has_attribute("solana"));
@@ -539,6 +890,7 @@ mod tests {
                 span: None,
             }],
             span: None,
+            docs: vec![],
         };
 
         assert_eq!(field.max_length(), Some(32));
@@ -567,6 +919,7 @@ mod tests {
             variants: vec![],
             version: None,
             span: None,
+            docs: vec![],
         };
 
         assert!(enum_def.has_attribute("solana"));
@@ -584,14 +937,21 @@ mod tests {
                 EnumVariant::Unit {
                     name: "Active".to_string(),
                     span: None,
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariant::Unit {
                     name: "Inactive".to_string(),
                     span: None,
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             version: None,
             span: None,
+            docs: vec![],
         };
 
         assert!(unit_enum.is_unit_only());
@@ -605,15 +965,21 @@ mod tests {
                 EnumVariant::Unit {
                     name: "Start".to_string(),
                     span: None,
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariant::Tuple {
                     name: "PlayerJoined".to_string(),
                     types: vec![TypeSpec::Primitive("PublicKey".to_string())],
                     span: None,
+                    docs: vec![],
+                    discriminant: None,
                 },
             ],
             version: None,
             span: None,
+            docs: vec![],
         };
 
         assert!(!mixed_enum.is_unit_only());
@@ -624,6 +990,9 @@ mod tests {
         let unit = EnumVariant::Unit {
             name: "Active".to_string(),
             span: None,
+            docs: vec![],
+            msg: None,
+            discriminant: None,
         };
         assert_eq!(unit.name(), "Active");
 
@@ -631,6 +1000,8 @@ mod tests {
             name: "PlayerJoined".to_string(),
             types: vec![],
             span: None,
+            docs: vec![],
+            discriminant: None,
         };
         assert_eq!(tuple.name(), "PlayerJoined");
 
@@ -638,6 +1009,8 @@ mod tests {
             name: "Initialize".to_string(),
             fields: vec![],
             span: None,
+            docs: vec![],
+            discriminant: None,
         };
         assert_eq!(struct_variant.name(), "Initialize");
     }
@@ -652,6 +1025,7 @@ mod tests {
             variants: vec![],
             span: None,
             version: None,
+            docs: vec![],
         };
 
         let item = Item::Enum(enum_def.clone());
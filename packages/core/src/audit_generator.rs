@@ -73,8 +73,8 @@ impl<'a> AuditGenerator<'a> {
                 TypeDefinition::Enum(_) => {
                     // Enums have fewer security concerns
                 }
-                TypeDefinition::TypeAlias(_) => {
-                    // Type aliases don't need auditing (they're resolved)
+                TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {
+                    // Type aliases and constants don't need auditing (they're resolved)
                 }
             }
         }
@@ -169,6 +169,20 @@ impl<'a> AuditGenerator<'a> {
                 });
             }
 
+            // Schema-declared constraints are only enforced where validate() is called
+            for constraint in &field.constraints {
+                items.push(ChecklistItem {
+                    category: CheckCategory::DataValidation,
+                    priority: Priority::High,
+                    item: format!(
+                        "Enforce declared constraint on '{}' ({})",
+                        field.name, constraint
+                    ),
+                    context: format!("{}::{}", struct_def.name, field.name),
+                    explanation: format!("The schema declares this constraint and the generated code provides {}::validate(), but nothing calls it automatically. Call it in every instruction that writes this field so invalid values are rejected on-chain.", struct_def.name),
+                });
+            }
+
             // Owner validation
             if field.name == "owner" {
                 items.push(ChecklistItem {
@@ -341,13 +355,12 @@ mod tests {
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = AuditGenerator::new(&type_defs);
@@ -367,21 +380,17 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "authority".to_string(),
                 type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = AuditGenerator::new(&type_defs);
@@ -401,14 +410,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "balance".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = AuditGenerator::new(&type_defs);
@@ -420,6 +428,41 @@ mod tests {
         ) && item.item.contains("checked arithmetic")));
     }
 
+    #[test]
+    fn test_generates_constraint_checks() {
+        let type_defs = vec![TypeDefinition::Struct(StructDefinition {
+            name: "Listing".to_string(),
+            generic_params: vec![],
+            fields: vec![FieldDefinition {
+                name: "fee_bps".to_string(),
+                type_info: TypeInfo::Primitive("u16".to_string()),
+                constraints: vec![crate::ir::Constraint::Range {
+                    min: None,
+                    max: Some(10000),
+                }],
+                ..Default::default()
+            }],
+            metadata: Metadata::default(),
+            visibility: Visibility::Public,
+            module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
+        })];
+
+        let generator = AuditGenerator::new(&type_defs);
+        let checklist = generator.generate();
+
+        let item = checklist
+            .iter()
+            .find(|item| {
+                item.item == "Enforce declared constraint on 'fee_bps' (must be at most 10000)"
+            })
+            .expect("constraint check");
+        assert!(matches!(item.category, CheckCategory::DataValidation));
+        assert!(matches!(item.priority, Priority::High));
+        assert!(item.explanation.contains("Listing::validate()"));
+    }
+
     #[test]
     fn test_sorted_by_priority() {
         let type_defs = vec![TypeDefinition::Struct(StructDefinition {
@@ -429,30 +472,23 @@ mod tests {
                 FieldDefinition {
                     name: "authority".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "balance".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = AuditGenerator::new(&type_defs);
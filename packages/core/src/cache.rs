@@ -0,0 +1,303 @@
+// Licensed under either of Apache License, Version 2.0 or MIT license at your option.
+// Copyright 2025 RECTOR-LABS
+
+//! Incremental compilation cache for multi-file schemas
+//!
+//! Without a cache, every run parses and transforms every file of a project.
+//! A [`BuildCache`] keeps each file's parsed AST and transformed IR in
+//! `.lumos/cache`, next to the project's `lumos.toml` (or next to the entry
+//! schema without one), so that a run only recomputes the files whose inputs
+//! changed:
+//!
+//! - an AST is keyed by a hash of the file's source
+//! - an IR is keyed by the source hash, the IR keys of the files it imports
+//!   or declares as modules, and a hash of the type aliases and constants of
+//!   the whole project, which every file is transformed against
+//!
+//! A change to an imported file thus changes the key of every file that
+//! depends on it, directly or not.
+//!
+//! ```text
+//! .lumos/cache/
+//! ├── ast/<hash>.json
+//! └── ir/<hash>.json
+//! ```
+//!
+//! Entries are named after their key and never modified, so an outdated
+//! entry is never read, only left unused, and the directory can be deleted
+//! at any time. Keys include the LUMOS version. Cached ASTs carry no source
+//! spans, so [`ProjectGraph::resolve_cached`] reloads a project that fails to
+//! resolve from source to report its errors with their locations.
+//!
+//! [`ProjectGraph::resolve_cached`]: crate::project::ProjectGraph::resolve_cached
+
+use crate::ast::LumosFile;
+use crate::ir::TypeDefinition;
+use crate::registry::MANIFEST_FILE;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Cache directory, relative to the project
+pub const CACHE_DIR: &str = ".lumos/cache";
+
+/// Layout version of cache entries, part of every key
+pub const CACHE_VERSION: u32 = 1;
+
+/// How many cached entries a [`BuildCache`] used and computed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Files whose AST was read from the cache
+    pub ast_hits: usize,
+
+    /// Files parsed from source
+    pub ast_misses: usize,
+
+    /// Files whose IR was read from the cache
+    pub ir_hits: usize,
+
+    /// Files transformed to IR
+    pub ir_misses: usize,
+}
+
+/// ASTs and IR of a project's files, stored on disk between runs
+///
+/// The cache is best effort: entries that cannot be read are recomputed
+/// and entries that cannot be written are skipped.
+#[derive(Debug, Clone)]
+pub struct BuildCache {
+    dir: PathBuf,
+    stats: CacheStats,
+}
+
+impl BuildCache {
+    /// A cache stored in `dir`
+    pub fn new(dir: PathBuf) -> Self {
+        BuildCache {
+            dir,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// The cache of the project `schema` belongs to
+    ///
+    /// It is in the directory of the closest `lumos.toml`, or of `schema`
+    /// without one.
+    pub fn for_schema(schema: &Path) -> Self {
+        let schema = schema
+            .canonicalize()
+            .unwrap_or_else(|_| schema.to_path_buf());
+        let schema_dir = schema.parent().unwrap_or(Path::new("."));
+        let project_dir = schema_dir
+            .ancestors()
+            .find(|dir| dir.join(MANIFEST_FILE).is_file())
+            .unwrap_or(schema_dir);
+        Self::new(project_dir.join(CACHE_DIR))
+    }
+
+    /// The cache directory
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Entries used and computed since the cache was opened
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// The AST of a source with this [`source_hash`], if cached
+    pub fn load_ast(&mut self, source_hash: &str) -> Option<LumosFile> {
+        let ast = self.read("ast", source_hash);
+        match ast {
+            Some(_) => self.stats.ast_hits += 1,
+            None => self.stats.ast_misses += 1,
+        }
+        ast
+    }
+
+    /// Store the AST parsed from a source with this [`source_hash`]
+    pub fn store_ast(&self, source_hash: &str, ast: &LumosFile) {
+        self.write("ast", source_hash, ast);
+    }
+
+    /// The IR of a file with this [`ir_key`], if cached
+    pub fn load_ir(&mut self, key: &str) -> Option<Vec<TypeDefinition>> {
+        let type_defs = self.read("ir", key);
+        match type_defs {
+            Some(_) => self.stats.ir_hits += 1,
+            None => self.stats.ir_misses += 1,
+        }
+        type_defs
+    }
+
+    /// Store the IR transformed from a file with this [`ir_key`]
+    pub fn store_ir(&self, key: &str, type_defs: &[TypeDefinition]) {
+        self.write("ir", key, type_defs);
+    }
+
+    fn entry_path(&self, kind: &str, key: &str) -> PathBuf {
+        self.dir.join(kind).join(format!("{}.json", key))
+    }
+
+    fn read<T: DeserializeOwned>(&self, kind: &str, key: &str) -> Option<T> {
+        let content = fs::read(self.entry_path(kind, key)).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    fn write<T: Serialize + ?Sized>(&self, kind: &str, key: &str, value: &T) {
+        let path = self.entry_path(kind, key);
+        let Ok(content) = serde_json::to_vec(value) else {
+            return;
+        };
+        let Some(dir) = path.parent() else {
+            return;
+        };
+
+        // Write next to the entry first, so a concurrent run never reads half of it
+        let partial = path.with_extension(format!("{}.partial", std::process::id()));
+        let written = fs::create_dir_all(dir)
+            .and_then(|_| fs::write(&partial, content))
+            .and_then(|_| fs::rename(&partial, &path));
+        if written.is_err() {
+            let _ = fs::remove_file(&partial);
+        }
+    }
+}
+
+/// Hash of a file's source, the key of its cached AST
+pub fn source_hash(source: &str) -> String {
+    hash(&[b"ast", source.as_bytes()])
+}
+
+/// Key of a file's cached IR
+///
+/// `shared_hash` covers the type aliases and constants of the whole project
+/// and `dependency_keys` are the IR keys of the files it depends on, in order.
+pub fn ir_key(source_hash: &str, shared_hash: &str, dependency_keys: &[&str]) -> String {
+    let mut parts: Vec<&[u8]> = vec![b"ir", source_hash.as_bytes(), shared_hash.as_bytes()];
+    parts.extend(dependency_keys.iter().map(|key| key.as_bytes()));
+    hash(&parts)
+}
+
+/// Hex SHA-256 of `parts` and the cache and LUMOS versions
+pub(crate) fn hash(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(CACHE_VERSION.to_le_bytes());
+    hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+    for part in parts {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir_json::IrDocument;
+    use crate::parser::parse_lumos_file;
+    use crate::transform::transform_to_ir;
+    use tempfile::TempDir;
+
+    const SCHEMA: &str = r#"
+        const MAX_NAME: usize = 16;
+
+        #[solana]
+        #[account]
+        struct Vault {
+            owner: PublicKey,
+            #[range(1..=10000)]
+            fee_bps: u16,
+            name: FixedString<MAX_NAME>,
+            history: Option<Vec<(u64, i128)>>,
+        }
+
+        #[solana]
+        enum Event {
+            Opened,
+            Deposit(u64),
+            Closed { reason: String },
+        }
+    "#;
+
+    #[test]
+    fn test_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let mut cache = BuildCache::new(dir.path().join(CACHE_DIR));
+        let hash = source_hash(SCHEMA);
+        assert!(cache.load_ast(&hash).is_none());
+
+        let ast = parse_lumos_file(SCHEMA).unwrap();
+        cache.store_ast(&hash, &ast);
+        let cached = cache.load_ast(&hash).unwrap();
+
+        // A cached AST transforms to the same IR, spans aside
+        let type_defs = transform_to_ir(ast).unwrap();
+        let key = ir_key(&hash, "", &[]);
+        cache.store_ir(&key, &type_defs);
+        assert_eq!(
+            IrDocument::new(transform_to_ir(cached).unwrap())
+                .to_json()
+                .unwrap(),
+            IrDocument::new(cache.load_ir(&key).unwrap())
+                .to_json()
+                .unwrap()
+        );
+
+        assert_eq!(
+            cache.stats(),
+            CacheStats {
+                ast_hits: 1,
+                ast_misses: 1,
+                ir_hits: 1,
+                ir_misses: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_keys() {
+        let hash = source_hash(SCHEMA);
+        assert_eq!(hash, source_hash(SCHEMA));
+        assert_ne!(hash, source_hash("struct Vault { owner: PublicKey }"));
+
+        let dependency = ir_key(&source_hash("type Id = u64;"), "", &[]);
+        let key = ir_key(&hash, "", &[&dependency]);
+        assert_ne!(key, ir_key(&hash, "", &[]));
+        assert_ne!(key, ir_key(&hash, "aliases", &[&dependency]));
+        assert_eq!(key, ir_key(&hash, "", &[&dependency]));
+    }
+
+    #[test]
+    fn test_corrupt_entries_are_misses() {
+        let dir = TempDir::new().unwrap();
+        let mut cache = BuildCache::new(dir.path().to_path_buf());
+        let hash = source_hash(SCHEMA);
+        fs::create_dir_all(dir.path().join("ast")).unwrap();
+        fs::write(cache.entry_path("ast", &hash), "{ not json").unwrap();
+
+        assert!(cache.load_ast(&hash).is_none());
+        assert_eq!(cache.stats().ast_misses, 1);
+    }
+
+    #[test]
+    fn test_for_schema_uses_project_dir() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        fs::create_dir_all(root.join("schema")).unwrap();
+        fs::write(root.join("schema/main.lumos"), "").unwrap();
+
+        let cache = BuildCache::for_schema(&root.join("schema/main.lumos"));
+        assert_eq!(cache.dir(), root.join("schema").join(CACHE_DIR));
+
+        fs::write(root.join(MANIFEST_FILE), "").unwrap();
+        let cache = BuildCache::for_schema(&root.join("schema/main.lumos"));
+        assert_eq!(cache.dir(), root.join(CACHE_DIR));
+    }
+}
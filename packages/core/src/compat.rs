@@ -8,7 +8,7 @@
 //! deployed Solana programs to fail reading existing account data.
 
 use crate::ir::TypeDefinition;
-use crate::migration::{SchemaChange, SchemaDiff};
+use crate::migration::{is_collection_kind_change, SchemaChange, SchemaDiff};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -198,9 +198,12 @@ impl CompatibilityChecker {
         let mut report =
             CompatibilityReport::new(diff.from_version.clone(), diff.to_version.clone());
 
+        // Versioned accounts are upgraded by the generated migration
+        let versioned = diff.from_version.is_some() && diff.to_version.is_some();
+
         // Analyze each change for compatibility
         for change in diff.changes.iter() {
-            if let Some(issue) = self.analyze_change(&diff.type_name, change) {
+            if let Some(issue) = self.analyze_change(&diff.type_name, change, versioned) {
                 report.add_issue(issue);
             }
         }
@@ -222,6 +225,7 @@ impl CompatibilityChecker {
                         name: String::new(),
                         type_info: crate::ir::TypeInfo::Primitive("bool".to_string()),
                         optional: false,
+                        default: None,
                     },
                 ));
             }
@@ -231,12 +235,37 @@ impl CompatibilityChecker {
     }
 
     /// Analyze a single schema change for compatibility
-    fn analyze_change(&self, type_name: &str, change: &SchemaChange) -> Option<CompatibilityIssue> {
+    ///
+    /// `versioned` is set when both schemas carry a `#[version]`, so old
+    /// accounts go through the generated migration.
+    fn analyze_change(
+        &self,
+        type_name: &str,
+        change: &SchemaChange,
+        versioned: bool,
+    ) -> Option<CompatibilityIssue> {
         match change {
+            SchemaChange::FieldAdded {
+                name,
+                default: Some(default),
+                optional: false,
+                ..
+            } if versioned => {
+                // The migration fills in the declared default
+                Some(CompatibilityIssue::info(
+                    type_name.to_string(),
+                    format!("Added field with default: {} = {}", name, default),
+                    "The generated migration fills in the default for existing accounts"
+                        .to_string(),
+                    change.clone(),
+                ))
+            }
+
             SchemaChange::FieldAdded {
                 name,
                 type_info,
                 optional,
+                default,
             } => {
                 if *optional {
                     // Adding optional fields is safe
@@ -253,7 +282,13 @@ impl CompatibilityChecker {
                         type_name.to_string(),
                         format!("Added required field: {} ({:?})", name, type_info),
                         "Old account data lacks this field, deserialization will fail".to_string(),
-                        Some("Make field optional or provide migration code".to_string()),
+                        Some(if default.is_some() {
+                            "Add #[version] to both schemas so the migration fills in the default"
+                                .to_string()
+                        } else {
+                            "Make field optional, add #[default(..)] or provide migration code"
+                                .to_string()
+                        }),
                         change.clone(),
                     ))
                 }
@@ -270,6 +305,24 @@ impl CompatibilityChecker {
                 ))
             }
 
+            SchemaChange::FieldTypeChanged {
+                name,
+                old_type,
+                new_type,
+            } if is_collection_kind_change(old_type, new_type) => {
+                // HashMap <-> BTreeMap (or sets) keep the same Borsh encoding
+                Some(CompatibilityIssue::warning(
+                    type_name.to_string(),
+                    format!(
+                        "Changed collection kind of '{}': {:?} → {:?}",
+                        name, old_type, new_type
+                    ),
+                    "Borsh writes hash and B-tree collections sorted by key, so existing data still deserializes".to_string(),
+                    Some("Iteration order in client code may change; update any code relying on it".to_string()),
+                    change.clone(),
+                ))
+            }
+
             SchemaChange::FieldTypeChanged {
                 name,
                 old_type,
@@ -327,6 +380,60 @@ impl CompatibilityChecker {
                     change.clone(),
                 ))
             }
+
+            SchemaChange::VariantDiscriminantChanged {
+                name,
+                old_discriminant,
+                new_discriminant,
+            } => Some(CompatibilityIssue::breaking(
+                type_name.to_string(),
+                format!(
+                    "Changed discriminant of variant '{}': {} → {}",
+                    name, old_discriminant, new_discriminant
+                ),
+                "Stored data tagged with the old discriminant will decode as a different variant or fail"
+                    .to_string(),
+                Some(format!(
+                    "Pin the old value with an explicit discriminant ({} = {})",
+                    name, old_discriminant
+                )),
+                change.clone(),
+            )),
+
+            SchemaChange::FlagBitChanged {
+                name,
+                old_bit,
+                new_bit,
+            } => Some(CompatibilityIssue::breaking(
+                type_name.to_string(),
+                format!(
+                    "Moved flag '{}' from bit {} to bit {}",
+                    name,
+                    old_bit.trailing_zeros(),
+                    new_bit.trailing_zeros()
+                ),
+                "Stored bitmasks with the old bit set now read as a different flag".to_string(),
+                Some(format!(
+                    "Pin the old bit with an explicit value ({} = {})",
+                    name, old_bit
+                )),
+                change.clone(),
+            )),
+
+            SchemaChange::EnumReprChanged { old_repr, new_repr } => {
+                Some(CompatibilityIssue::breaking(
+                    type_name.to_string(),
+                    format!(
+                        "Changed discriminant width: {} → {}",
+                        old_repr.name(),
+                        new_repr.name()
+                    ),
+                    "Every stored value starts with a tag of the old width, so the layout no longer matches"
+                        .to_string(),
+                    Some("Keep the old #[repr] or migrate existing accounts".to_string()),
+                    change.clone(),
+                ))
+            }
         }
     }
 }
@@ -50,8 +50,8 @@ impl<'a> CorpusGenerator<'a> {
                 TypeDefinition::Enum(e) => {
                     files.extend(self.generate_enum_corpus(e));
                 }
-                TypeDefinition::TypeAlias(_) => {
-                    // Type aliases don't generate corpus files (they're resolved)
+                TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {
+                    // Type aliases and constants don't generate corpus files (they're resolved)
                 }
             }
         }
@@ -342,8 +342,8 @@ impl<'a> CorpusGenerator<'a> {
                 // Return empty bytes (fuzzer will discover valid structures)
                 vec![]
             }
-            TypeInfo::Array(_) => {
-                // Empty vec (length = 0)
+            TypeInfo::Array(_) | TypeInfo::Map { .. } | TypeInfo::Set { .. } => {
+                // Empty collection (length = 0)
                 vec![0, 0, 0, 0]
             }
             TypeInfo::FixedArray { element, size } => {
@@ -358,7 +358,18 @@ impl<'a> CorpusGenerator<'a> {
                 // None
                 vec![0]
             }
-            TypeInfo::UserDefined(_) => {
+            // Empty string: all padding
+            TypeInfo::FixedString(size) => vec![0; *size],
+            // Box<T> is serialized exactly like T
+            TypeInfo::Boxed(inner) => self.serialize_minimal_value(inner, false),
+            TypeInfo::Tuple(elements) => {
+                // Tuple: each element back to back (no length prefix)
+                elements
+                    .iter()
+                    .flat_map(|element| self.serialize_minimal_value(element, false))
+                    .collect()
+            }
+            TypeInfo::UserDefined(_) | TypeInfo::Instance { .. } => {
                 // For user-defined types, we can't easily generate valid instances
                 // Return empty bytes (fuzzer will discover valid structures)
                 vec![]
@@ -382,6 +393,8 @@ impl<'a> CorpusGenerator<'a> {
                 }
                 data
             }
+            // String filling the whole capacity: no padding
+            TypeInfo::FixedString(size) => vec![b'A'; *size],
             TypeInfo::FixedArray { element, size } => {
                 // Fixed array: serialize size * element maximal values (no length prefix!)
                 let mut data = Vec::new();
@@ -396,7 +409,25 @@ impl<'a> CorpusGenerator<'a> {
                 data.extend(self.serialize_maximal_value(inner, false));
                 data
             }
-            TypeInfo::UserDefined(_) => vec![],
+            TypeInfo::Map { key, value, .. } => {
+                // Single entry (keys must be unique, so repeating one value is invalid)
+                let mut data = vec![1, 0, 0, 0]; // length = 1
+                data.extend(self.serialize_maximal_value(key, false));
+                data.extend(self.serialize_maximal_value(value, false));
+                data
+            }
+            TypeInfo::Set { element, .. } => {
+                // Single element (elements must be unique)
+                let mut data = vec![1, 0, 0, 0]; // length = 1
+                data.extend(self.serialize_maximal_value(element, false));
+                data
+            }
+            TypeInfo::Boxed(inner) => self.serialize_maximal_value(inner, false),
+            TypeInfo::Tuple(elements) => elements
+                .iter()
+                .flat_map(|element| self.serialize_maximal_value(element, false))
+                .collect(),
+            TypeInfo::UserDefined(_) | TypeInfo::Instance { .. } => vec![],
         }
     }
 
@@ -495,15 +526,14 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "value".to_string(),
                 type_info: TypeInfo::Primitive("u32".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = CorpusGenerator::new(&type_defs);
@@ -524,21 +554,17 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "value".to_string(),
                 type_info: TypeInfo::Primitive("u8".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = CorpusGenerator::new(&type_defs);
@@ -560,14 +586,14 @@ mod tests {
                 name: "maybe_value".to_string(),
                 type_info: TypeInfo::Option(Box::new(TypeInfo::Primitive("u32".to_string()))),
                 optional: true,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = CorpusGenerator::new(&type_defs);
@@ -598,15 +624,14 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "items".to_string(),
                 type_info: TypeInfo::Array(Box::new(TypeInfo::Primitive("u8".to_string()))),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = CorpusGenerator::new(&type_defs);
@@ -636,16 +661,23 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Variant1".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Tuple {
                     name: "Variant2".to_string(),
                     types: vec![TypeInfo::Primitive("u32".to_string())],
+                    docs: vec![],
+                    discriminant: None,
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = CorpusGenerator::new(&type_defs);
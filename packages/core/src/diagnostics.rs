@@ -0,0 +1,398 @@
+// Licensed under either of Apache License, Version 2.0 or MIT license at your option.
+// Copyright 2025 RECTOR-LABS
+
+//! Schema diagnostics shared by the CLI and the language server
+//!
+//! [`check_schema`] runs the parser and the AST → IR transformation on a
+//! `.lumos` source and returns every problem found, errors and warnings alike,
+//! as a flat list of [`Diagnostic`]s ordered by position.
+//! [`check_project_file`] does the same for one file of a multi-file project.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use lumos_core::diagnostics::{check_schema, Severity};
+//!
+//! let source = r#"
+//!     struct Account {
+//!         owner: Wallet,
+//!         balance: u64,
+//!         balance: u64,
+//!     }
+//! "#;
+//!
+//! let check = check_schema(source);
+//! assert!(check.has_errors());
+//! assert_eq!(check.diagnostics.len(), 2);
+//! assert!(check.diagnostics.iter().all(|d| d.severity == Severity::Error));
+//! ```
+
+use crate::error::{LumosError, SourceLocation};
+use crate::ir::{EnumVariantDefinition, TypeDefinition, Warning};
+use crate::parser::parse_lumos_file;
+use crate::project::{in_file, FileError, ProjectGraph};
+use crate::transform::{collect_deprecation_warnings, collect_generic_warnings, transform_to_ir};
+use std::fmt;
+use std::path::Path;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The schema is invalid and no code can be generated
+    Error,
+    /// The schema is valid but something deserves attention
+    Warning,
+}
+
+/// A single error or warning about a schema
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    /// Error or warning
+    pub severity: Severity,
+    /// Message without location information
+    pub message: String,
+    /// Where in the source the problem is, if known
+    pub location: Option<SourceLocation>,
+}
+
+impl Diagnostic {
+    /// One error diagnostic per error, flattening [`LumosError::Multiple`]
+    pub fn from_error(error: &LumosError) -> Vec<Diagnostic> {
+        error
+            .errors()
+            .into_iter()
+            .map(|e| Diagnostic {
+                severity: Severity::Error,
+                message: e.message(),
+                location: e.location().cloned(),
+            })
+            .collect()
+    }
+
+    /// A warning diagnostic, located at the field or type it refers to
+    pub fn from_warning(warning: &Warning, type_defs: &[TypeDefinition]) -> Diagnostic {
+        Diagnostic {
+            severity: Severity::Warning,
+            message: warning.to_string(),
+            location: locate_warning(warning, type_defs).map(SourceLocation::from_span),
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        write!(f, "{}: {}", severity, self.message)?;
+        if let Some(location) = &self.location {
+            write!(f, " (at {})", location.format())?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of checking a schema: the IR (if it is valid) and all diagnostics
+#[derive(Debug, Clone)]
+pub struct SchemaCheck {
+    /// Type definitions, empty if the schema has errors
+    pub type_defs: Vec<TypeDefinition>,
+    /// Errors and warnings, ordered by position
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl SchemaCheck {
+    /// Whether any diagnostic is an error
+    pub fn has_errors(&self) -> bool {
+        self.error_count() > 0
+    }
+
+    /// Number of error diagnostics
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    /// Number of warning diagnostics
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count()
+    }
+}
+
+/// Parse and transform `source`, collecting every error and warning
+///
+/// Parse errors stop before transformation, since the IR of a partially
+/// parsed schema would report spurious problems. Warnings are only collected
+/// for valid schemas.
+pub fn check_schema(source: &str) -> SchemaCheck {
+    let result = parse_lumos_file(source).and_then(transform_to_ir);
+
+    let (type_defs, mut diagnostics) = match result {
+        Ok(type_defs) => {
+            let mut warnings = collect_deprecation_warnings(&type_defs);
+            warnings.extend(collect_generic_warnings(&type_defs));
+            let diagnostics = warnings
+                .iter()
+                .map(|w| Diagnostic::from_warning(w, &type_defs))
+                .collect();
+            (type_defs, diagnostics)
+        }
+        Err(error) => (Vec::new(), Diagnostic::from_error(&error)),
+    };
+
+    sort_by_position(&mut diagnostics);
+    SchemaCheck {
+        type_defs,
+        diagnostics,
+    }
+}
+
+/// Check `file` (canonical) as part of the project in `graph`
+///
+/// Types, aliases and constants from every file of the project are in scope.
+/// Problems in `file` keep their locations; problems found in other files
+/// name that file and have no location, since their lines refer to it.
+/// Warnings are only reported for types declared in `file`.
+pub fn check_project_file(graph: &ProjectGraph, file: &Path) -> SchemaCheck {
+    let (type_defs, errors) = graph.resolve_by_file();
+
+    let mut diagnostics = Vec::new();
+    for FileError { path, error } in &errors {
+        match path {
+            Some(path) if path == file => diagnostics.extend(Diagnostic::from_error(error)),
+            Some(path) => {
+                diagnostics.extend(Diagnostic::from_error(&in_file(graph.root(), path, error)))
+            }
+            // Errors spanning files can't be located in any one of them
+            None => {
+                diagnostics.extend(
+                    Diagnostic::from_error(error)
+                        .into_iter()
+                        .map(|d| Diagnostic {
+                            location: None,
+                            ..d
+                        }),
+                )
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        sort_by_position(&mut diagnostics);
+        return SchemaCheck {
+            type_defs: Vec::new(),
+            diagnostics,
+        };
+    }
+
+    let declared = graph
+        .file(file)
+        .map(|f| f.declared_names())
+        .unwrap_or_default();
+    let mut warnings = collect_deprecation_warnings(&type_defs);
+    warnings.extend(collect_generic_warnings(&type_defs));
+    diagnostics.extend(
+        warnings
+            .iter()
+            .filter(|w| declared.contains(&w.type_name.as_str()))
+            .map(|w| Diagnostic::from_warning(w, &type_defs)),
+    );
+
+    sort_by_position(&mut diagnostics);
+    SchemaCheck {
+        type_defs,
+        diagnostics,
+    }
+}
+
+/// Order by position; unlocated diagnostics go last and the sort is stable,
+/// so ties keep discovery order
+fn sort_by_position(diagnostics: &mut [Diagnostic]) {
+    diagnostics.sort_by_key(|d| {
+        d.location
+            .as_ref()
+            .map_or((usize::MAX, 0), |l| (l.line, l.column))
+    });
+}
+
+/// Span of the field or type a warning refers to
+fn locate_warning(warning: &Warning, type_defs: &[TypeDefinition]) -> Option<proc_macro2::Span> {
+    let type_def = type_defs.iter().find(|t| t.name() == warning.type_name)?;
+    let Some(field_name) = &warning.field_name else {
+        return type_def.span();
+    };
+
+    let field = match type_def {
+        TypeDefinition::Struct(s) => s.fields.iter().find(|f| &f.name == field_name),
+        TypeDefinition::Enum(e) => e.variants.iter().find_map(|v| match v {
+            EnumVariantDefinition::Struct { fields, .. } => {
+                fields.iter().find(|f| &f.name == field_name)
+            }
+            _ => None,
+        }),
+        TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => None,
+    };
+    field.and_then(|f| f.span).or_else(|| type_def.span())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(check: &SchemaCheck) -> Vec<usize> {
+        check
+            .diagnostics
+            .iter()
+            .map(|d| d.location.as_ref().expect("diagnostic has a location").line)
+            .collect()
+    }
+
+    #[test]
+    fn test_reports_all_parse_errors() {
+        let source = "struct A {\n    data: [u8; N + 1],\n    #[version = 1]\n    name: String,\n}\n\nenum E {\n    Ok = -1,\n}\n";
+
+        let check = check_schema(source);
+        assert_eq!(check.error_count(), 3, "{:#?}", check.diagnostics);
+        assert_eq!(lines(&check), vec![2, 3, 8]);
+        assert!(check.type_defs.is_empty());
+    }
+
+    #[test]
+    fn test_reports_all_transform_errors() {
+        let source = r#"
+struct Player {
+    wallet: Wallet,
+    score: u64,
+    score: u32,
+}
+
+enum State {
+    Active,
+    Active,
+}
+
+struct Player {
+    level: Level,
+}
+"#;
+
+        let check = check_schema(source);
+        let messages: Vec<_> = check.diagnostics.iter().map(|d| &d.message).collect();
+        assert_eq!(check.error_count(), 5, "{:#?}", messages);
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("Duplicate type definition 'Player'")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("Undefined type 'Wallet'")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("Undefined type 'Level'")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("Duplicate field 'score'")));
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("Duplicate variant 'Active'")));
+        assert_eq!(lines(&check), vec![3, 5, 8, 13, 14]);
+    }
+
+    #[test]
+    fn test_reports_errors_around_failed_fields() {
+        let source = "struct A {\n    x: Missing,\n    #[range(10..=1)]\n    y: u16,\n    data: [u8; 0],\n}\n\nstruct A {\n    z: u8,\n}\n";
+
+        // A is kept without its failed fields, so its other problems are found too
+        let check = check_schema(source);
+        let messages: Vec<_> = check.diagnostics.iter().map(|d| &d.message).collect();
+        assert_eq!(check.error_count(), 4, "{:#?}", messages);
+        assert!(messages[0].contains("Undefined type 'Missing'"));
+        assert!(messages[1].contains("range"));
+        assert!(messages[2].contains("Array size must be greater than 0"));
+        assert!(messages[3].contains("Duplicate type definition 'A'"));
+        assert_eq!(lines(&check), vec![2, 4, 5, 8]);
+    }
+
+    #[test]
+    fn test_failed_items_are_not_reported_as_undefined() {
+        let source = r#"
+#[repr(u8)]
+struct Config {
+    limit: u64,
+}
+
+struct Settings {
+    config: Config,
+}
+"#;
+
+        let check = check_schema(source);
+        assert_eq!(check.error_count(), 1, "{:#?}", check.diagnostics);
+        assert!(check.diagnostics[0].message.contains("#[repr]"));
+    }
+
+    #[test]
+    fn test_locates_warnings() {
+        let source = r#"
+struct Account {
+    balance: u64,
+    #[deprecated("use balance")]
+    old_balance: u64,
+}
+"#;
+
+        let check = check_schema(source);
+        assert!(!check.has_errors());
+        assert_eq!(check.warning_count(), 1);
+        assert_eq!(lines(&check), vec![5]);
+        assert_eq!(check.type_defs.len(), 1);
+    }
+
+    #[test]
+    fn test_checks_file_within_project() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let main = dir.path().join("main.lumos");
+        let models = dir.path().join("models.lumos");
+        std::fs::write(
+            &main,
+            "mod models;\n\nstruct Vault {\n    owner: User,\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            &models,
+            "struct User {\n    #[deprecated]\n    name: String,\n    wallet: Wallet,\n}\n",
+        )
+        .unwrap();
+        let graph = ProjectGraph::load(&main).unwrap();
+        let models = models.canonicalize().unwrap();
+
+        // Errors in the file itself keep their location
+        let check = check_project_file(&graph, &models);
+        assert_eq!(check.error_count(), 1);
+        assert_eq!(lines(&check), vec![4]);
+
+        // The entry file is told which file is broken
+        let check = check_project_file(&graph, graph.root());
+        assert_eq!(check.error_count(), 1);
+        assert!(check.diagnostics[0].location.is_none());
+        assert!(check.diagnostics[0].message.starts_with("models.lumos:4:"));
+
+        // Once fixed, warnings are reported in the file that declares the type
+        std::fs::write(
+            &models,
+            "struct User {\n    #[deprecated]\n    name: String,\n}\n",
+        )
+        .unwrap();
+        let graph = ProjectGraph::load(&main).unwrap();
+        assert_eq!(check_project_file(&graph, &models).warning_count(), 1);
+        assert!(check_project_file(&graph, graph.root())
+            .diagnostics
+            .is_empty());
+    }
+}
@@ -61,7 +61,165 @@ pub enum LumosError {
     /// TOML deserialization error
     #[error("TOML error: {0}")]
     Toml(#[from] toml::de::Error),
+
+    /// Several independent errors, one per line
+    #[error("{}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("\n"))]
+    Multiple(Vec<LumosError>),
+}
+
+impl LumosError {
+    /// Source location of this error, if known
+    pub fn location(&self) -> Option<&SourceLocation> {
+        match self {
+            LumosError::SchemaParse(_, loc)
+            | LumosError::TypeValidation(_, loc)
+            | LumosError::Transform(_, loc) => loc.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Error message without the source location
+    pub fn message(&self) -> String {
+        match self {
+            LumosError::SchemaParse(msg, _)
+            | LumosError::TypeValidation(msg, _)
+            | LumosError::Transform(msg, _) => msg.clone(),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Attach `span` as the location of every error that doesn't have one yet
+    ///
+    /// Lets helpers that only see a type or an attribute report errors, with
+    /// the caller pointing them at the field or item they came from.
+    pub fn with_span(self, span: Option<proc_macro2::Span>) -> Self {
+        let Some(span) = span else {
+            return self;
+        };
+        match self {
+            LumosError::SchemaParse(msg, None) => {
+                LumosError::SchemaParse(msg, Some(SourceLocation::from_span(span)))
+            }
+            LumosError::TypeValidation(msg, None) => {
+                LumosError::TypeValidation(msg, Some(SourceLocation::from_span(span)))
+            }
+            LumosError::Transform(msg, None) => {
+                LumosError::Transform(msg, Some(SourceLocation::from_span(span)))
+            }
+            LumosError::Multiple(errors) => LumosError::Multiple(
+                errors
+                    .into_iter()
+                    .map(|e| e.with_span(Some(span)))
+                    .collect(),
+            ),
+            other => other,
+        }
+    }
+
+    /// The individual errors, flattening [`LumosError::Multiple`]
+    pub fn errors(&self) -> Vec<&LumosError> {
+        match self {
+            LumosError::Multiple(errors) => errors.iter().flat_map(|e| e.errors()).collect(),
+            other => vec![other],
+        }
+    }
+}
+
+/// Collects independent errors so that one mistake doesn't hide the rest
+///
+/// Parsing and transformation keep going after a failed item, field or
+/// check, then report everything found as a single [`LumosError`].
+#[derive(Debug, Default)]
+pub struct ErrorCollector {
+    errors: Vec<LumosError>,
+}
+
+impl ErrorCollector {
+    /// Create an empty collector
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an error
+    pub fn push(&mut self, error: LumosError) {
+        match error {
+            LumosError::Multiple(errors) => self.errors.extend(errors),
+            error => self.errors.push(error),
+        }
+    }
+
+    /// Keep the value of `result`, recording its error instead of returning it
+    pub fn ok<T>(&mut self, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.push(error);
+                None
+            }
+        }
+    }
+
+    /// Whether any error has been recorded
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// `Ok(value)` if nothing was recorded, otherwise all recorded errors
+    pub fn finish<T>(mut self, value: T) -> Result<T> {
+        match self.errors.len() {
+            0 => Ok(value),
+            1 => Err(self.errors.remove(0)),
+            _ => Err(LumosError::Multiple(self.errors)),
+        }
+    }
 }
 
 /// Result type for LUMOS operations
 pub type Result<T> = std::result::Result<T, LumosError>;
+
+/// Combine two independent results, reporting the errors of both
+pub fn join<A, B>(a: Result<A>, b: Result<B>) -> Result<(A, B)> {
+    match (a, b) {
+        (Ok(a), Ok(b)) => Ok((a, b)),
+        (Err(e), Ok(_)) | (Ok(_), Err(e)) => Err(e),
+        (Err(a), Err(b)) => {
+            let mut errors = ErrorCollector::new();
+            errors.push(a);
+            errors.push(b);
+            Err(LumosError::Multiple(errors.errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_collector_reports_every_error() {
+        let mut errors = ErrorCollector::new();
+        assert_eq!(errors.ok(Ok::<_, LumosError>(1)), Some(1));
+        errors.push(LumosError::SchemaParse("first".to_string(), None));
+        errors.push(LumosError::Multiple(vec![
+            LumosError::Transform("second".to_string(), Some(SourceLocation::new(3, 5))),
+            LumosError::CodeGen("third".to_string()),
+        ]));
+
+        let err = errors.finish(()).unwrap_err();
+        assert_eq!(err.errors().len(), 3);
+        assert_eq!(
+            err.to_string(),
+            "first\nsecond (at 3:5)\nCode generation error: third"
+        );
+        assert_eq!(err.errors()[1].message(), "second");
+        assert_eq!(err.errors()[1].location().unwrap().line, 3);
+
+        // A single error is returned as-is
+        let mut errors = ErrorCollector::new();
+        errors.push(LumosError::SchemaParse("only".to_string(), None));
+        assert!(matches!(
+            errors.finish(()),
+            Err(LumosError::SchemaParse(..))
+        ));
+    }
+}
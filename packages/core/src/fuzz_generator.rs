@@ -51,8 +51,8 @@ impl<'a> FuzzGenerator<'a> {
                 TypeDefinition::Enum(e) => {
                     targets.push(self.generate_enum_target(e));
                 }
-                TypeDefinition::TypeAlias(_) => {
-                    // Type aliases don't generate fuzz targets (they're resolved)
+                TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {
+                    // Type aliases and constants don't generate fuzz targets (they're resolved)
                 }
             }
         }
@@ -309,6 +309,7 @@ impl<'a> FuzzGenerator<'a> {
                 TypeDefinition::Struct(s) => Some(s.name.clone()),
                 TypeDefinition::Enum(e) => Some(e.name.clone()),
                 TypeDefinition::TypeAlias(_) => None, // Type aliases are resolved, not fuzzed
+                TypeDefinition::Const(_) => None,
             })
             .collect()
     }
@@ -319,6 +320,7 @@ impl<'a> FuzzGenerator<'a> {
             TypeDefinition::Struct(s) => s.name == type_name,
             TypeDefinition::Enum(e) => e.name == type_name,
             TypeDefinition::TypeAlias(a) => a.name == type_name,
+            TypeDefinition::Const(_) => false,
         })
     }
 }
@@ -356,30 +358,23 @@ mod tests {
                 FieldDefinition {
                     name: "wallet".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "balance".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = FuzzGenerator::new(&type_defs);
@@ -403,6 +398,8 @@ mod tests {
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = FuzzGenerator::new(&type_defs);
@@ -442,6 +439,8 @@ mod tests {
                 visibility: Visibility::Public,
 
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
             TypeDefinition::Enum(EnumDefinition {
                 name: "State1".to_string(),
@@ -451,6 +450,8 @@ mod tests {
                 visibility: Visibility::Public,
 
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
         ];
 
@@ -470,6 +471,8 @@ mod tests {
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let generator = FuzzGenerator::new(&type_defs);
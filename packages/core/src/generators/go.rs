@@ -59,19 +59,53 @@
 //! ```
 
 use crate::ir::{
-    EnumDefinition, EnumVariantDefinition, StructDefinition, TypeAliasDefinition, TypeDefinition,
-    TypeInfo,
+    ConstDefinition, Constraint, DefaultValue, EnumDefinition, EnumVariantDefinition,
+    StructDefinition, TypeAliasDefinition, TypeDefinition, TypeInfo,
 };
+use crate::naming::{apply_renames, NameTarget};
+use crate::transform::inline_newtypes;
 
 /// Generate Go code from a type definition
 pub fn generate(type_def: &TypeDefinition) -> String {
+    let type_def = &apply_renames(
+        &inline_newtypes(std::slice::from_ref(type_def)),
+        NameTarget::Go,
+    )[0];
     match type_def {
         TypeDefinition::Struct(struct_def) => generate_struct(struct_def),
         TypeDefinition::Enum(enum_def) => generate_enum(enum_def),
         TypeDefinition::TypeAlias(alias_def) => generate_type_alias(alias_def),
+        TypeDefinition::Const(const_def) => generate_const(const_def),
     }
 }
 
+/// Generate Go code from a constant definition
+fn generate_const(const_def: &ConstDefinition) -> String {
+    let mut output = String::new();
+
+    // Add file header
+    output.push_str(&generate_header());
+
+    // Add package declaration
+    output.push_str(
+        "package generated
+
+",
+    );
+
+    output.push_str(&generate_const_definition(const_def));
+
+    output
+}
+
+/// Generate an untyped Go constant, usable wherever an integer is expected
+fn generate_const_definition(const_def: &ConstDefinition) -> String {
+    let mut output = String::new();
+    push_doc_comments(&mut output, &const_def.docs, "");
+    output.push_str(&format!("const {} = {}\n", const_def.name, const_def.value));
+    output
+}
+
 /// Generate Go code from a type alias definition
 fn generate_type_alias(alias_def: &TypeAliasDefinition) -> String {
     let mut output = String::new();
@@ -176,7 +210,7 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
         total += match type_def {
             TypeDefinition::Struct(s) => STRUCT_BASE + (s.fields.len() * FIELD_SIZE),
             TypeDefinition::Enum(e) => ENUM_BASE + (e.variants.len() * VARIANT_SIZE),
-            TypeDefinition::TypeAlias(_) => 100,
+            TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => 100,
         };
     }
 
@@ -195,6 +229,7 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
 ///
 /// Complete Go source code as a `String`, ready to write to a `.go` file.
 pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
+    let type_defs = &apply_renames(&inline_newtypes(type_defs), NameTarget::Go);
     let estimated_capacity = estimate_output_size(type_defs);
     let mut output = String::with_capacity(estimated_capacity);
 
@@ -216,7 +251,7 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
                 let imports = collect_enum_imports(e);
                 all_imports.extend(imports);
             }
-            TypeDefinition::TypeAlias(_) => {}
+            TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {}
         }
     }
 
@@ -258,6 +293,9 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
                 let go_type = map_type_to_go(&a.target);
                 output.push_str(&format!("type {} = {}\n", a.name, go_type));
             }
+            TypeDefinition::Const(c) => {
+                output.push_str(&generate_const_definition(c));
+            }
         }
     }
 
@@ -268,6 +306,9 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
 fn generate_struct_definition(struct_def: &StructDefinition) -> String {
     let mut output = String::new();
 
+    // Type-level doc comments
+    push_doc_comments(&mut output, &struct_def.metadata.docs, "");
+
     // Add deprecation comment if any field is deprecated
     let deprecated_fields: Vec<_> = struct_def
         .fields
@@ -325,7 +366,8 @@ fn generate_struct_definition(struct_def: &StructDefinition) -> String {
             // Add borsh tag for serialization
             let borsh_tag = format!("`borsh:\"{}\"`", field.name);
 
-            // Add deprecation comment
+            // Add doc and deprecation comments
+            push_doc_comments(&mut output, &field.docs, "\t");
             if let Some(msg) = &field.deprecated {
                 output.push_str(&format!("\t// Deprecated: {}\n", msg));
             }
@@ -335,6 +377,201 @@ fn generate_struct_definition(struct_def: &StructDefinition) -> String {
         output.push_str("}\n");
     }
 
+    output.push_str(&generate_default_constructor(struct_def));
+    output.push_str(&generate_validate_method(struct_def));
+
+    output
+}
+
+/// Generate `Default{Name}()` for `#[derive(Default)]`, filling in `#[default(..)]` values
+///
+/// Other fields keep Go's zero value. 128-bit integers are raw `[16]byte`
+/// values in Go, so their defaults are left at zero too.
+fn generate_default_constructor(struct_def: &StructDefinition) -> String {
+    if !struct_def.derives_default() {
+        return String::new();
+    }
+
+    let (type_params, type_args) = if struct_def.generic_params.is_empty() {
+        (String::new(), String::new())
+    } else {
+        (
+            format!(
+                "[{}]",
+                struct_def
+                    .generic_params
+                    .iter()
+                    .map(|p| format!("{} any", p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!("[{}]", struct_def.generic_params.join(", ")),
+        )
+    };
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "\n// Default{} creates a {} with the defaults declared in the schema\n",
+        struct_def.name, struct_def.name
+    ));
+    output.push_str(&format!(
+        "func Default{}{}() {}{} {{\n",
+        struct_def.name, type_params, struct_def.name, type_args
+    ));
+    output.push_str(&format!("\treturn {}{}{{\n", struct_def.name, type_args));
+    for field in &struct_def.fields {
+        let value = match &field.default {
+            Some(DefaultValue::Integer(_)) if matches!(&field.type_info, TypeInfo::Primitive(t) if t == "u128" || t == "i128") => {
+                continue
+            }
+            Some(DefaultValue::Integer(value)) => value.to_string(),
+            Some(DefaultValue::Float(value)) => format!("{:?}", value),
+            Some(DefaultValue::Bool(value)) => value.to_string(),
+            Some(DefaultValue::String(value)) => serde_json::to_string(value).unwrap_or_default(),
+            None => continue,
+        };
+        output.push_str(&format!(
+            "\t\t{}: {},\n",
+            to_pascal_case(&field.name),
+            value
+        ));
+    }
+    output.push_str("\t}\n");
+    output.push_str("}\n");
+
+    output
+}
+
+/// Go conditions (and error messages) for the `#[range]`/`#[pattern]` constraints of a struct
+///
+/// 128-bit integers are raw `[16]byte` values in Go, so their ranges are not checked.
+fn validation_checks(struct_def: &StructDefinition) -> Vec<(String, String)> {
+    let mut checks = Vec::new();
+
+    for field in &struct_def.fields {
+        let optional = matches!(field.type_info, TypeInfo::Option(_));
+        let value_type = match &field.type_info {
+            TypeInfo::Option(inner) => inner.as_ref(),
+            other => other,
+        };
+        let is_wide = matches!(value_type, TypeInfo::Primitive(t) if t == "u128" || t == "i128");
+        let field_name = to_pascal_case(&field.name);
+        let value = if optional {
+            format!("*v.{}", field_name)
+        } else {
+            format!("v.{}", field_name)
+        };
+
+        for constraint in &field.constraints {
+            let violated = match constraint {
+                Constraint::Range { .. } if is_wide => continue,
+                Constraint::Range { min, max } => {
+                    let mut bounds = Vec::new();
+                    if let Some(min) = min {
+                        bounds.push(format!("{} < {}", value, min));
+                    }
+                    if let Some(max) = max {
+                        bounds.push(format!("{} > {}", value, max));
+                    }
+                    if bounds.is_empty() {
+                        continue;
+                    }
+                    bounds.join(" || ")
+                }
+                Constraint::Pattern(pattern) => format!(
+                    "!regexp.MustCompile({}).MatchString({})",
+                    serde_json::to_string(pattern).unwrap_or_default(),
+                    value
+                ),
+            };
+            // Optional fields are only checked when present
+            let condition = if optional {
+                format!("v.{} != nil && ({})", field_name, violated)
+            } else {
+                violated
+            };
+            checks.push((condition, format!("{} {}", field.name, constraint)));
+        }
+    }
+
+    checks
+}
+
+/// Generate `Validate()`, which returns an error if a `#[range]` or `#[pattern]` constraint fails
+fn generate_validate_method(struct_def: &StructDefinition) -> String {
+    let checks = validation_checks(struct_def);
+    if checks.is_empty() {
+        return String::new();
+    }
+
+    // The receiver names the type parameters without their constraints
+    let receiver_generics = if struct_def.generic_params.is_empty() {
+        String::new()
+    } else {
+        format!("[{}]", struct_def.generic_params.join(", "))
+    };
+
+    let mut output = String::new();
+    output.push_str("\n// Validate checks the value constraints declared in the schema\n");
+    output.push_str(&format!(
+        "func (v *{}{}) Validate() error {{\n",
+        struct_def.name, receiver_generics
+    ));
+    for (condition, message) in checks {
+        output.push_str(&format!("\tif {} {{\n", condition));
+        output.push_str(&format!(
+            "\t\treturn errors.New({})\n",
+            serde_json::to_string(&message).unwrap_or_default()
+        ));
+        output.push_str("\t}\n");
+    }
+    output.push_str("\treturn nil\n");
+    output.push_str("}\n");
+
+    output
+}
+
+/// Emit `//` doc comment lines at the given indentation
+fn push_doc_comments(output: &mut String, docs: &[String], indent: &str) {
+    for line in docs {
+        if line.is_empty() {
+            output.push_str(&format!("{}//\n", indent));
+        } else {
+            output.push_str(&format!("{}// {}\n", indent, line));
+        }
+    }
+}
+
+/// Go integer type of an enum's Borsh discriminant (`#[repr]`, default uint8)
+fn go_tag_type(enum_def: &EnumDefinition) -> String {
+    format!("uint{}", enum_def.tag_size() * 8)
+}
+
+/// Generate a `#[flags]` enum as an integer type with a constant per flag
+fn generate_flags(enum_def: &EnumDefinition) -> String {
+    let name = &enum_def.name;
+    let mut output = format!("type {} {}\n\n", name, go_tag_type(enum_def));
+
+    output.push_str("const (\n");
+    for (variant, bit) in enum_def.variants.iter().zip(enum_def.discriminants()) {
+        push_doc_comments(&mut output, variant.docs(), "\t");
+        output.push_str(&format!(
+            "\t{}{} {} = 1 << {}\n",
+            name,
+            variant.name(),
+            name,
+            bit.trailing_zeros()
+        ));
+    }
+    output.push_str(")\n");
+
+    output.push_str(&format!(
+        "\n// Has reports whether every flag in flag is set\nfunc (f {}) Has(flag {}) bool {{\n",
+        name, name
+    ));
+    output.push_str("\treturn f&flag == flag\n");
+    output.push_str("}\n");
+
     output
 }
 
@@ -342,14 +579,35 @@ fn generate_struct_definition(struct_def: &StructDefinition) -> String {
 fn generate_enum_definition(enum_def: &EnumDefinition) -> String {
     let mut output = String::new();
 
-    if enum_def.is_unit_only() {
+    // Type-level doc comments
+    push_doc_comments(&mut output, &enum_def.metadata.docs, "");
+
+    if enum_def.is_flags() {
+        output.push_str(&generate_flags(enum_def));
+    } else if enum_def.is_unit_only() {
         // Simple enum - use const iota pattern
-        output.push_str(&format!("type {} uint8\n\n", enum_def.name));
+        output.push_str(&format!(
+            "type {} {}\n\n",
+            enum_def.name,
+            go_tag_type(enum_def)
+        ));
         output.push_str("const (\n");
 
-        for (idx, variant) in enum_def.variants.iter().enumerate() {
-            if let EnumVariantDefinition::Unit { name } = variant {
-                if idx == 0 {
+        let custom = enum_def.has_custom_discriminants();
+        for ((idx, variant), discriminant) in enum_def
+            .variants
+            .iter()
+            .enumerate()
+            .zip(enum_def.discriminants())
+        {
+            if let EnumVariantDefinition::Unit { name, docs, .. } = variant {
+                push_doc_comments(&mut output, docs, "\t");
+                if custom {
+                    output.push_str(&format!(
+                        "\t{}{} {} = {}\n",
+                        enum_def.name, name, enum_def.name, discriminant
+                    ));
+                } else if idx == 0 {
                     output.push_str(&format!(
                         "\t{}{} {} = iota\n",
                         enum_def.name, name, enum_def.name
@@ -368,7 +626,7 @@ fn generate_enum_definition(enum_def: &EnumDefinition) -> String {
         ));
         output.push_str("\tswitch e {\n");
         for variant in &enum_def.variants {
-            if let EnumVariantDefinition::Unit { name } = variant {
+            if let EnumVariantDefinition::Unit { name, .. } = variant {
                 output.push_str(&format!(
                     "\tcase {}{}:\n\t\treturn \"{}\"\n",
                     enum_def.name, name, name
@@ -390,9 +648,23 @@ fn generate_enum_definition(enum_def: &EnumDefinition) -> String {
         // Generate discriminant constants
         output.push_str(&format!("// {} discriminant values\n", enum_def.name));
         output.push_str("const (\n");
-        for (idx, variant) in enum_def.variants.iter().enumerate() {
+        let custom = enum_def.has_custom_discriminants();
+        for ((idx, variant), discriminant) in enum_def
+            .variants
+            .iter()
+            .enumerate()
+            .zip(enum_def.discriminants())
+        {
             let variant_name = variant.name();
-            if idx == 0 {
+            if custom {
+                output.push_str(&format!(
+                    "\t{}{}Discriminant {} = {}\n",
+                    enum_def.name,
+                    variant_name,
+                    go_tag_type(enum_def),
+                    discriminant
+                ));
+            } else if idx == 0 {
                 output.push_str(&format!(
                     "\t{}{}Discriminant uint8 = iota\n",
                     enum_def.name, variant_name
@@ -408,15 +680,16 @@ fn generate_enum_definition(enum_def: &EnumDefinition) -> String {
 
         // Generate variant structs
         for variant in &enum_def.variants {
+            push_doc_comments(&mut output, variant.docs(), "");
             match variant {
-                EnumVariantDefinition::Unit { name } => {
+                EnumVariantDefinition::Unit { name, .. } => {
                     output.push_str(&format!("type {}{} struct{{}}\n", enum_def.name, name));
                     output.push_str(&format!(
                         "func ({}{}) is{}() {{}}\n\n",
                         enum_def.name, name, enum_def.name
                     ));
                 }
-                EnumVariantDefinition::Tuple { name, types } => {
+                EnumVariantDefinition::Tuple { name, types, .. } => {
                     output.push_str(&format!("type {}{} struct {{\n", enum_def.name, name));
                     for (idx, type_info) in types.iter().enumerate() {
                         let go_type = map_type_to_go(type_info);
@@ -431,9 +704,10 @@ fn generate_enum_definition(enum_def: &EnumDefinition) -> String {
                         enum_def.name, name, enum_def.name
                     ));
                 }
-                EnumVariantDefinition::Struct { name, fields } => {
+                EnumVariantDefinition::Struct { name, fields, .. } => {
                     output.push_str(&format!("type {}{} struct {{\n", enum_def.name, name));
                     for field in fields {
+                        push_doc_comments(&mut output, &field.docs, "\t");
                         let go_type = map_type_to_go(&field.type_info);
                         let field_name = to_pascal_case(&field.name);
                         output.push_str(&format!(
@@ -456,11 +730,20 @@ fn generate_enum_definition(enum_def: &EnumDefinition) -> String {
 
 /// Collect required imports based on struct definition
 fn collect_struct_imports(struct_def: &StructDefinition) -> Vec<String> {
-    let imports = Vec::new();
+    let mut imports = Vec::new();
 
-    // Check if we need any imports (currently Go standard library doesn't need special imports for basic types)
-    // borsh-go is typically imported at the application level
-    let _ = struct_def; // suppress unused warning
+    // borsh-go is typically imported at the application level; only the
+    // generated Validate() method needs the standard library
+    let checks = validation_checks(struct_def);
+    if !checks.is_empty() {
+        imports.push("errors".to_string());
+    }
+    if checks
+        .iter()
+        .any(|(condition, _)| condition.contains("regexp."))
+    {
+        imports.push("regexp".to_string());
+    }
 
     imports
 }
@@ -531,12 +814,42 @@ fn map_type_to_go(type_info: &TypeInfo) -> String {
             let element_type = map_type_to_go(element);
             format!("[{}]{}", size, element_type)
         }
+        // Zero-padded UTF-8
+        TypeInfo::FixedString(size) => format!("[{}]byte", size),
+        // An optional box is already a (nilable) pointer
+        TypeInfo::Option(inner) if matches!(**inner, TypeInfo::Boxed(_)) => map_type_to_go(inner),
         TypeInfo::Option(inner) => {
             let inner_type = map_type_to_go(inner);
             format!("*{}", inner_type)
         }
+        // Go maps are unordered; BTree* ordering only matters on the wire
+        TypeInfo::Map { key, value, .. } => {
+            format!("map[{}]{}", map_type_to_go(key), map_type_to_go(value))
+        }
+        TypeInfo::Set { element, .. } => {
+            format!("map[{}]struct{{}}", map_type_to_go(element))
+        }
+        // Go has no tuples; an anonymous struct keeps the elements in wire order
+        TypeInfo::Tuple(elements) => {
+            let fields: Vec<String> = elements
+                .iter()
+                .enumerate()
+                .map(|(i, element)| format!("F{} {}", i, map_type_to_go(element)))
+                .collect();
+            format!("struct {{ {} }}", fields.join("; "))
+        }
+        // A pointer lets a struct refer to itself, like Box<T> in Rust
+        TypeInfo::Boxed(inner) => format!("*{}", map_type_to_go(inner)),
         TypeInfo::Generic(param_name) => param_name.clone(),
         TypeInfo::UserDefined(type_name) => type_name.clone(),
+        TypeInfo::Instance { name, args } => format!(
+            "{}[{}]",
+            name,
+            args.iter()
+                .map(map_type_to_go)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
     }
 }
 
@@ -554,23 +867,19 @@ mod tests {
                 FieldDefinition {
                     name: "id".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "name".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -589,30 +898,23 @@ mod tests {
                 FieldDefinition {
                     name: "wallet".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "balance".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -630,13 +932,13 @@ mod tests {
                 name: "email".to_string(),
                 type_info: TypeInfo::Option(Box::new(TypeInfo::Primitive("String".to_string()))),
                 optional: true,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -651,20 +953,43 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "members".to_string(),
                 type_info: TypeInfo::Array(Box::new(TypeInfo::Primitive("u64".to_string()))),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
         assert!(code.contains("Members []uint64 `borsh:\"members\"`"));
     }
 
+    #[test]
+    fn generates_tuple_fields_as_anonymous_structs() {
+        let type_def = TypeDefinition::Struct(StructDefinition {
+            name: "Pool".to_string(),
+            generic_params: vec![],
+            fields: vec![FieldDefinition {
+                name: "shares".to_string(),
+                type_info: TypeInfo::Array(Box::new(TypeInfo::Tuple(vec![
+                    TypeInfo::Primitive("PublicKey".to_string()),
+                    TypeInfo::Primitive("u16".to_string()),
+                ]))),
+                ..Default::default()
+            }],
+            metadata: Metadata::default(),
+            visibility: Visibility::Public,
+            module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
+        });
+
+        let code = generate(&type_def);
+        assert!(code.contains("Shares []struct { F0 [32]byte; F1 uint16 } `borsh:\"shares\"`"));
+    }
+
     #[test]
     fn generates_fixed_array_fields() {
         let type_def = TypeDefinition::Struct(StructDefinition {
@@ -676,14 +1001,13 @@ mod tests {
                     element: Box::new(TypeInfo::Primitive("u8".to_string())),
                     size: 32,
                 },
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -700,6 +1024,8 @@ mod tests {
                 metadata: Metadata::default(),
                 visibility: Visibility::Public,
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
             TypeDefinition::Struct(StructDefinition {
                 name: "Post".to_string(),
@@ -708,6 +1034,8 @@ mod tests {
                 metadata: Metadata::default(),
                 visibility: Visibility::Public,
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
         ];
 
@@ -726,24 +1054,31 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Finished".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -754,6 +1089,26 @@ mod tests {
         assert!(code.contains("func (e GameState) String() string"));
     }
 
+    #[test]
+    fn generates_enum_with_explicit_discriminants() {
+        let source = r#"
+            #[solana]
+            #[repr(u16)]
+            enum Status {
+                Active = 1,
+                Closed = 500,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate(&ir[0]);
+        assert!(code.contains("type Status uint16"));
+        assert!(code.contains("\tStatusActive Status = 1\n"));
+        assert!(code.contains("\tStatusClosed Status = 500\n"));
+        assert!(!code.contains("iota"));
+    }
+
     #[test]
     fn generates_complex_enum() {
         let type_def = TypeDefinition::Enum(EnumDefinition {
@@ -762,10 +1117,15 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Started".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Tuple {
                     name: "PlayerJoined".to_string(),
                     types: vec![TypeInfo::Primitive("PublicKey".to_string())],
+                    docs: vec![],
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Struct {
                     name: "ScoreUpdate".to_string(),
@@ -773,32 +1133,26 @@ mod tests {
                         FieldDefinition {
                             name: "player".to_string(),
                             type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                         FieldDefinition {
                             name: "score".to_string(),
                             type_info: TypeInfo::Primitive("u64".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                     ],
+                    docs: vec![],
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -861,23 +1215,20 @@ mod tests {
                 FieldDefinition {
                     name: "balance".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "old_field".to_string(),
                     type_info: TypeInfo::Primitive("u32".to_string()),
-                    optional: false,
                     deprecated: Some("Use new_field instead".to_string()),
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -893,4 +1244,92 @@ mod tests {
         assert_eq!(to_pascal_case("simple"), "Simple");
         assert_eq!(to_pascal_case("abc_def_ghi"), "AbcDefGhi");
     }
+
+    #[test]
+    fn generates_validate_method_for_constraints() {
+        let source = r#"
+            #[solana]
+            struct Listing {
+                #[range(0..=10000)]
+                fee_bps: u16,
+                #[range(-5..5)]
+                delta: Option<i32>,
+                #[pattern("^[A-Z]{1,10}$")]
+                symbol: String,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("import (\n\t\"errors\"\n\t\"regexp\"\n)\n"));
+        assert!(code.contains("func (v *Listing) Validate() error {\n"));
+        assert!(code.contains("\tif v.FeeBps > 10000 {\n\t\treturn errors.New(\"fee_bps must be at most 10000\")\n\t}\n"));
+        assert!(code.contains("\tif v.Delta != nil && (*v.Delta < -5 || *v.Delta > 4) {\n"));
+        assert!(
+            code.contains("\tif !regexp.MustCompile(\"^[A-Z]{1,10}$\").MatchString(v.Symbol) {\n")
+        );
+        assert!(code.contains("\treturn nil\n}\n"));
+    }
+
+    #[test]
+    fn generates_default_constructor_with_field_defaults() {
+        let source = r#"
+            #[derive(Default)]
+            struct Config {
+                #[default(100)]
+                fee_bps: u16,
+                #[default("USDC")]
+                symbol: String,
+                #[default(true)]
+                active: bool,
+                #[default(0.5)]
+                ratio: f64,
+                count: u64,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("func DefaultConfig() Config {\n\treturn Config{\n\t\tFeeBps: 100,\n\t\tSymbol: \"USDC\",\n\t\tActive: true,\n\t\tRatio: 0.5,\n\t}\n}\n"));
+    }
+
+    #[test]
+    fn test_go_renames() {
+        let source = r#"
+            #[solana]
+            #[rename(go = "TradeListing")]
+            struct Listing {
+                #[rename(go = "rate")]
+                fee_bps: u16,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        // Go names stay exported
+        let code = generate_module(&ir);
+        assert!(code.contains("type TradeListing struct {\n\tRate uint16"));
+    }
+
+    #[test]
+    fn test_flags_enum_constants() {
+        let source = r#"
+            #[flags(u16)]
+            enum Permissions {
+                Mint,
+                Burn,
+                Admin = 128,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("type Permissions uint16\n\nconst (\n\tPermissionsMint Permissions = 1 << 0\n\tPermissionsBurn Permissions = 1 << 1\n\tPermissionsAdmin Permissions = 1 << 7\n)\n"));
+        assert!(code.contains(
+            "func (f Permissions) Has(flag Permissions) bool {\n\treturn f&flag == flag\n}\n"
+        ));
+    }
 }
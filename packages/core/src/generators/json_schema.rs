@@ -0,0 +1,543 @@
+// Licensed under either of Apache License, Version 2.0 or MIT license at your option.
+// Copyright 2025 RECTOR-LABS
+
+//! JSON Schema Generator
+//!
+//! Generates a [draft 2020-12](https://json-schema.org/draft/2020-12/schema)
+//! JSON Schema from IR, so services that accept account-shaped JSON can
+//! validate it against the same definitions as the on-chain program.
+//!
+//! Every type is a definition under `$defs`; validate a document against
+//! `schema.json#/$defs/Vault`. The JSON shape follows the TypeScript
+//! client:
+//!
+//! | IR Type | JSON Schema |
+//! |---------|-------------|
+//! | `u8`-`u32`, `i8`-`i32` | `integer` with the type's bounds |
+//! | `u64`, `u128`, `i64`, `i128` | decimal `string` (JSON numbers lose precision above 2^53) |
+//! | `f32`, `f64` | `number` |
+//! | `PublicKey` | base58 `string` |
+//! | `Vec<T>`, `HashSet<T>` | `array` (`uniqueItems` for sets) |
+//! | `[T; N]`, tuples | `array` with a fixed number of items |
+//! | `HashMap<K, V>` | `object` keyed by `K`'s string form |
+//! | `Option<T>` | `T` or `null`, and not required |
+//! | enums | `oneOf` objects tagged with `kind`, like the TypeScript unions |
+//!
+//! `#[max(N)]` becomes `maxLength`, `maxItems` or `maxProperties`, and
+//! `#[range]`/`#[pattern]` become `minimum`/`maximum`/`pattern` where the
+//! value is a JSON number or string. Property names are the TypeScript names
+//! (`#[rename(ts = ..)]`). Instruction contexts and `#[error_code]` enums are
+//! not data and are left out.
+//!
+//! Some limits cannot be expressed, so a valid document may still be rejected
+//! on-chain:
+//!
+//! - `#[range]` on a 64- or 128-bit integer: JSON Schema has no numeric bounds
+//!   for decimal strings, so the range is only recorded in a `$comment`
+//! - `FixedString<N>`: `maxLength` counts characters, not UTF-8 bytes, so a
+//!   string of N multi-byte characters passes although it needs up to 4N bytes
+
+use crate::ir::{
+    DefaultValue, EnumDefinition, EnumVariantDefinition, FieldDefinition, StructDefinition,
+    TypeDefinition, TypeInfo,
+};
+use crate::naming::{apply_renames, NameTarget};
+use crate::transform::monomorphize;
+use serde_json::{json, Map, Value};
+
+/// Dialect of the generated schema
+const DRAFT_2020_12: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Base58 alphabet, as used for keys and signatures
+const BASE58: &str = "[1-9A-HJ-NP-Za-km-z]";
+
+/// Generate a JSON Schema document for a single type definition
+pub fn generate(type_def: &TypeDefinition) -> String {
+    generate_module(std::slice::from_ref(type_def))
+}
+
+/// Generate a JSON Schema document with a `$defs` entry per type
+pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
+    // JSON Schema has no generics: each `Wrapper<u64>` becomes `WrapperU64`
+    let type_defs = monomorphize(&apply_renames(type_defs, NameTarget::TypeScript));
+
+    let mut defs = Map::new();
+    for type_def in &type_defs {
+        if let Some(schema) = definition_schema(type_def) {
+            defs.insert(type_def.name().to_string(), schema);
+        }
+    }
+
+    let document = json!({
+        "$schema": DRAFT_2020_12,
+        "$comment": "Auto-generated by LUMOS - DO NOT EDIT",
+        "$defs": defs,
+    });
+    let mut output = serde_json::to_string_pretty(&document).unwrap_or_default();
+    output.push('\n');
+    output
+}
+
+/// Schema of one `$defs` entry, or `None` for definitions that are not data
+fn definition_schema(type_def: &TypeDefinition) -> Option<Value> {
+    match type_def {
+        TypeDefinition::Struct(s) if s.metadata.is_instruction => None,
+        TypeDefinition::Struct(s) => Some(struct_schema(s)),
+        TypeDefinition::Enum(e) if e.is_error_code() => None,
+        TypeDefinition::Enum(e) => Some(enum_schema(e)),
+        TypeDefinition::TypeAlias(a) => Some(type_schema(&a.target)),
+        TypeDefinition::Const(_) => None,
+    }
+}
+
+fn struct_schema(struct_def: &StructDefinition) -> Value {
+    let mut schema = object_schema(&struct_def.fields, None);
+    describe(&mut schema, &struct_def.metadata.docs);
+    schema
+}
+
+fn enum_schema(enum_def: &EnumDefinition) -> Value {
+    // Flags serialize as their packed integer
+    if enum_def.is_flags() {
+        let mut schema = json!({
+            "type": "integer",
+            "minimum": 0,
+            "maximum": enum_def.flags_mask(),
+        });
+        describe(&mut schema, &enum_def.metadata.docs);
+        return schema;
+    }
+
+    let variants: Vec<Value> = enum_def
+        .variants
+        .iter()
+        .map(|variant| {
+            let mut schema = match variant {
+                EnumVariantDefinition::Unit { name, .. } => object_schema(&[], Some(name)),
+                EnumVariantDefinition::Tuple { name, types, .. } => {
+                    // Tuple payloads are `field0`, `field1`, ... as in TypeScript
+                    let fields: Vec<FieldDefinition> = types
+                        .iter()
+                        .enumerate()
+                        .map(|(i, type_info)| tuple_field(i, type_info))
+                        .collect();
+                    object_schema(&fields, Some(name))
+                }
+                EnumVariantDefinition::Struct { name, fields, .. } => {
+                    object_schema(fields, Some(name))
+                }
+            };
+            describe(&mut schema, variant.docs());
+            schema
+        })
+        .collect();
+
+    let mut schema = json!({ "oneOf": variants });
+    describe(&mut schema, &enum_def.metadata.docs);
+    schema
+}
+
+/// Closed object with a property per field, tagged with `kind` for enum variants
+fn object_schema(fields: &[FieldDefinition], kind: Option<&str>) -> Value {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+
+    if let Some(kind) = kind {
+        properties.insert("kind".to_string(), json!({ "const": kind }));
+        required.push(json!("kind"));
+    }
+    for field in fields {
+        properties.insert(field.name.clone(), field_schema(field));
+        if !field.optional {
+            required.push(json!(field.name));
+        }
+    }
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn tuple_field(index: usize, type_info: &TypeInfo) -> FieldDefinition {
+    FieldDefinition {
+        name: format!("field{}", index),
+        type_info: type_info.clone(),
+        ..Default::default()
+    }
+}
+
+/// Schema of a field, including its attributes and docs
+fn field_schema(field: &FieldDefinition) -> Value {
+    let mut schema = field_value_schema(&field.type_info, field);
+    let Value::Object(keywords) = &mut schema else {
+        return schema;
+    };
+
+    if !field.docs.is_empty() {
+        keywords.insert("description".to_string(), json!(field.docs.join("\n")));
+    }
+    if let Some(default) = &field.default {
+        keywords.insert(
+            "default".to_string(),
+            default_value(default, &field.type_info),
+        );
+    }
+    if field.deprecated.is_some() {
+        keywords.insert("deprecated".to_string(), json!(true));
+    }
+    schema
+}
+
+/// Schema of a field's value, with `#[max]`, `#[range]` and `#[pattern]`
+/// applied to the value inside any `Option`
+fn field_value_schema(type_info: &TypeInfo, field: &FieldDefinition) -> Value {
+    match type_info {
+        TypeInfo::Option(inner) => {
+            json!({ "anyOf": [field_value_schema(inner, field), { "type": "null" }] })
+        }
+        TypeInfo::Boxed(inner) => field_value_schema(inner, field),
+        _ => {
+            let mut schema = type_schema(type_info);
+            if let Value::Object(keywords) = &mut schema {
+                apply_field_limits(keywords, field);
+            }
+            schema
+        }
+    }
+}
+
+fn apply_field_limits(keywords: &mut Map<String, Value>, field: &FieldDefinition) {
+    let is_json_type = |name: &str| keywords.get("type").and_then(Value::as_str) == Some(name);
+    let is_string = is_json_type("string");
+    let is_integer = is_json_type("integer");
+    let is_array = is_json_type("array");
+    let is_object = is_json_type("object");
+
+    if let Some(max) = field.max_length {
+        let keyword = if is_string {
+            "maxLength"
+        } else if is_array {
+            "maxItems"
+        } else if is_object {
+            "maxProperties"
+        } else {
+            return;
+        };
+        keywords.insert(keyword.to_string(), json!(max));
+    }
+
+    for constraint in &field.constraints {
+        match constraint {
+            crate::ir::Constraint::Range { min, max } if is_integer => {
+                if let Some(min) = min {
+                    keywords.insert("minimum".to_string(), json!(min));
+                }
+                if let Some(max) = max {
+                    keywords.insert("maximum".to_string(), json!(max));
+                }
+            }
+            // Decimal strings have no numeric bounds in JSON Schema
+            crate::ir::Constraint::Range { .. } if is_string => {
+                keywords.insert(
+                    "$comment".to_string(),
+                    json!(format!("{} (not checked by this schema)", constraint)),
+                );
+            }
+            crate::ir::Constraint::Pattern(pattern) if is_string => {
+                keywords.insert("pattern".to_string(), json!(pattern));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Map an IR type to its JSON Schema
+fn type_schema(type_info: &TypeInfo) -> Value {
+    match type_info {
+        TypeInfo::Primitive(name) => primitive_schema(name),
+        // Anything goes for a parameter that was never instantiated
+        TypeInfo::Generic(_) => json!({}),
+        TypeInfo::UserDefined(name) => json!({ "$ref": format!("#/$defs/{}", name) }),
+        TypeInfo::Instance { .. } => {
+            json!({ "$ref": format!("#/$defs/{}", type_info.pascal_name()) })
+        }
+        TypeInfo::Array(inner) => json!({ "type": "array", "items": type_schema(inner) }),
+        TypeInfo::FixedArray { element, size } => json!({
+            "type": "array",
+            "items": type_schema(element),
+            "minItems": size,
+            "maxItems": size,
+        }),
+        TypeInfo::Map { key, value, .. } => map_schema(key, value),
+        TypeInfo::Set { element, .. } => json!({
+            "type": "array",
+            "items": type_schema(element),
+            "uniqueItems": true,
+        }),
+        TypeInfo::Option(inner) => json!({ "anyOf": [type_schema(inner), { "type": "null" }] }),
+        TypeInfo::Tuple(elements) => tuple_schema(elements),
+        TypeInfo::Boxed(inner) => type_schema(inner),
+        // At most N UTF-8 bytes means at most N characters, but not the other
+        // way around: `maxLength` cannot count bytes, so it only bounds the length
+        TypeInfo::FixedString(size) => json!({ "type": "string", "maxLength": size }),
+    }
+}
+
+fn primitive_schema(name: &str) -> Value {
+    match name {
+        "u8" | "u16" | "u32" | "i8" | "i16" | "i32" => {
+            let (minimum, maximum): (i64, i64) = match name {
+                "u8" => (0, u8::MAX.into()),
+                "u16" => (0, u16::MAX.into()),
+                "u32" => (0, u32::MAX.into()),
+                "i8" => (i8::MIN.into(), i8::MAX.into()),
+                "i16" => (i16::MIN.into(), i16::MAX.into()),
+                _ => (i32::MIN.into(), i32::MAX.into()),
+            };
+            json!({ "type": "integer", "minimum": minimum, "maximum": maximum })
+        }
+        // Up to the digit count of the type's largest value
+        "u64" => decimal_string_schema(false, 20),
+        "u128" => decimal_string_schema(false, 39),
+        "i64" => decimal_string_schema(true, 20),
+        "i128" => decimal_string_schema(true, 40),
+        "f32" | "f64" => json!({ "type": "number" }),
+        "bool" => json!({ "type": "boolean" }),
+        "String" => json!({ "type": "string" }),
+        "Pubkey" | "PublicKey" => json!({
+            "type": "string",
+            "pattern": format!("^{}{{32,44}}$", BASE58),
+        }),
+        "Signature" => json!({
+            "type": "string",
+            "pattern": format!("^{}{{64,88}}$", BASE58),
+        }),
+        _ => json!({}),
+    }
+}
+
+fn decimal_string_schema(signed: bool, max_length: usize) -> Value {
+    let pattern = if signed {
+        "^(0|-?[1-9][0-9]*)$"
+    } else {
+        "^(0|[1-9][0-9]*)$"
+    };
+    json!({ "type": "string", "pattern": pattern, "maxLength": max_length })
+}
+
+/// Maps with string-like keys are objects; any other key needs a list of pairs
+fn map_schema(key: &TypeInfo, value: &TypeInfo) -> Value {
+    let key_schema = type_schema(key);
+    if key_schema.get("type").and_then(Value::as_str) == Some("string") {
+        return json!({
+            "type": "object",
+            "propertyNames": key_schema,
+            "additionalProperties": type_schema(value),
+        });
+    }
+    if key_schema.get("type").and_then(Value::as_str) == Some("integer") {
+        return json!({
+            "type": "object",
+            "propertyNames": { "pattern": "^(0|-?[1-9][0-9]*)$" },
+            "additionalProperties": type_schema(value),
+        });
+    }
+    json!({
+        "type": "array",
+        "items": tuple_schema(&[key.clone(), value.clone()]),
+    })
+}
+
+fn tuple_schema(elements: &[TypeInfo]) -> Value {
+    let items: Vec<Value> = elements.iter().map(type_schema).collect();
+    json!({
+        "type": "array",
+        "prefixItems": items,
+        "items": false,
+        "minItems": elements.len(),
+    })
+}
+
+/// A `#[default]` value in the JSON form of the field's type
+fn default_value(default: &DefaultValue, type_info: &TypeInfo) -> Value {
+    match default {
+        DefaultValue::Integer(value) => {
+            if type_schema(type_info).get("type").and_then(Value::as_str) == Some("string") {
+                json!(value.to_string())
+            } else {
+                json!(value)
+            }
+        }
+        DefaultValue::Float(value) => json!(value),
+        DefaultValue::Bool(value) => json!(value),
+        DefaultValue::String(value) => json!(value),
+    }
+}
+
+fn describe(schema: &mut Value, docs: &[String]) {
+    if let (Value::Object(keywords), false) = (schema, docs.is_empty()) {
+        keywords.insert("description".to_string(), json!(docs.join("\n")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_lumos_file;
+    use crate::transform::transform_to_ir;
+
+    fn schema_for(source: &str) -> Value {
+        let ir = transform_to_ir(parse_lumos_file(source).unwrap()).unwrap();
+        serde_json::from_str(&generate_module(&ir)).unwrap()
+    }
+
+    #[test]
+    fn test_struct_schema() {
+        let schema = schema_for(
+            r#"
+            /// A user's vault
+            #[solana]
+            #[account]
+            struct Vault {
+                owner: PublicKey,
+                balance: u64,
+                #[range(1..=10000)]
+                fee_bps: u16,
+                #[range(..=1000000)]
+                limit: u64,
+                #[max(32)]
+                name: String,
+                #[max(10)]
+                members: Vec<PublicKey>,
+                memo: Option<String>,
+            }
+        "#,
+        );
+
+        assert_eq!(schema["$schema"], DRAFT_2020_12);
+        let vault = &schema["$defs"]["Vault"];
+        assert_eq!(vault["type"], "object");
+        assert_eq!(vault["description"], "A user's vault");
+        assert_eq!(vault["additionalProperties"], false);
+        assert_eq!(
+            vault["required"],
+            json!(["owner", "balance", "fee_bps", "limit", "name", "members"])
+        );
+
+        let properties = &vault["properties"];
+        assert_eq!(
+            properties["owner"]["pattern"],
+            "^[1-9A-HJ-NP-Za-km-z]{32,44}$"
+        );
+        assert_eq!(
+            properties["balance"],
+            json!({ "type": "string", "pattern": "^(0|[1-9][0-9]*)$", "maxLength": 20 })
+        );
+        assert_eq!(
+            properties["fee_bps"],
+            json!({ "type": "integer", "minimum": 1, "maximum": 10000 })
+        );
+        assert_eq!(
+            properties["limit"]["$comment"],
+            "must be at most 1000000 (not checked by this schema)"
+        );
+        assert_eq!(properties["name"]["maxLength"], 32);
+        assert_eq!(properties["members"]["maxItems"], 10);
+        assert_eq!(
+            properties["memo"],
+            json!({ "anyOf": [{ "type": "string" }, { "type": "null" }] })
+        );
+    }
+
+    #[test]
+    fn test_enum_is_tagged_one_of() {
+        let schema = schema_for(
+            r#"
+            #[solana]
+            enum Event {
+                Opened,
+                Deposit(u64),
+                Closed { reason: String },
+            }
+        "#,
+        );
+
+        let variants = schema["$defs"]["Event"]["oneOf"].as_array().unwrap();
+        assert_eq!(variants.len(), 3);
+        assert_eq!(
+            variants[0]["properties"]["kind"],
+            json!({ "const": "Opened" })
+        );
+        assert_eq!(variants[0]["required"], json!(["kind"]));
+        assert_eq!(variants[1]["required"], json!(["kind", "field0"]));
+        assert_eq!(variants[1]["properties"]["field0"]["type"], "string");
+        assert_eq!(
+            variants[2]["properties"]["reason"],
+            json!({ "type": "string" })
+        );
+    }
+
+    #[test]
+    fn test_references_and_collections() {
+        let schema = schema_for(
+            r#"
+            #[newtype]
+            type Lamports = u64;
+
+            struct Position {
+                balance: Lamports,
+                history: [u8; 4],
+                pair: (u32, bool),
+                scores: HashMap<PublicKey, u32>,
+                by_slot: BTreeMap<u64, Lamports>,
+                tags: HashSet<String>,
+            }
+        "#,
+        );
+
+        let defs = &schema["$defs"];
+        assert_eq!(defs["Lamports"]["type"], "string");
+        let properties = &defs["Position"]["properties"];
+        assert_eq!(properties["balance"], json!({ "$ref": "#/$defs/Lamports" }));
+        assert_eq!(properties["history"]["minItems"], 4);
+        assert_eq!(properties["history"]["maxItems"], 4);
+        assert_eq!(properties["pair"]["prefixItems"][1]["type"], "boolean");
+        assert_eq!(properties["pair"]["items"], false);
+        assert_eq!(properties["scores"]["type"], "object");
+        assert_eq!(
+            properties["scores"]["propertyNames"]["pattern"],
+            "^[1-9A-HJ-NP-Za-km-z]{32,44}$"
+        );
+        assert_eq!(
+            properties["by_slot"]["propertyNames"]["pattern"],
+            "^(0|[1-9][0-9]*)$"
+        );
+        assert_eq!(properties["tags"]["uniqueItems"], true);
+    }
+
+    #[test]
+    fn test_skips_non_data_definitions() {
+        let schema = schema_for(
+            r#"
+            const MAX: u8 = 4;
+
+            #[error_code]
+            enum VaultError {
+                #[msg("Too small")]
+                TooSmall,
+            }
+
+            #[solana]
+            struct Vault {
+                amount: u8,
+            }
+        "#,
+        );
+
+        let defs = schema["$defs"].as_object().unwrap();
+        assert_eq!(defs.keys().collect::<Vec<_>>(), vec!["Vault"]);
+    }
+}
@@ -12,6 +12,7 @@
 //! - **Go** - Structs with go-borsh serialization
 //! - **Ruby** - Classes with borsh-rb serialization
 //! - **Seahorse** - Seahorse-compatible Python for Solana programs
+//! - **JSON Schema** - Draft 2020-12 schemas for validating JSON off-chain
 //!
 //! ## Architecture
 //!
@@ -25,6 +26,7 @@
 //!                    │    GoGenerator     │
 //!                    │   RubyGenerator    │
 //!                    │ SeahorseGenerator  │
+//!                    │JsonSchemaGenerator │
 //!                    └────────────────────┘
 //! ```
 //!
@@ -76,6 +78,8 @@ pub enum Language {
     Ruby,
     /// Seahorse Python for Solana programs
     Seahorse,
+    /// JSON Schema (draft 2020-12)
+    JsonSchema,
 }
 
 impl Language {
@@ -88,6 +92,7 @@ impl Language {
             Language::Go,
             Language::Ruby,
             Language::Seahorse,
+            Language::JsonSchema,
         ]
     }
 
@@ -100,6 +105,7 @@ impl Language {
             Language::Go,
             Language::Ruby,
             Language::Seahorse,
+            Language::JsonSchema,
         ]
     }
 
@@ -113,6 +119,7 @@ impl Language {
                 | Language::Go
                 | Language::Ruby
                 | Language::Seahorse
+                | Language::JsonSchema
         )
     }
 
@@ -125,6 +132,7 @@ impl Language {
             Language::Go => "go",
             Language::Ruby => "rb",
             Language::Seahorse => "py",
+            Language::JsonSchema => "json",
         }
     }
 
@@ -137,6 +145,7 @@ impl Language {
             Language::Go => "go",
             Language::Ruby => "ruby",
             Language::Seahorse => "seahorse",
+            Language::JsonSchema => "jsonschema",
         }
     }
 
@@ -149,6 +158,7 @@ impl Language {
             "go" | "golang" => Some(Language::Go),
             "ruby" | "rb" => Some(Language::Ruby),
             "seahorse" => Some(Language::Seahorse),
+            "jsonschema" | "json-schema" | "json_schema" => Some(Language::JsonSchema),
             _ => None,
         }
     }
@@ -227,6 +237,14 @@ pub trait CodeGenerator: Send + Sync {
         self.generate_module(std::slice::from_ref(type_def))
     }
 
+    /// Generate one file per schema module, laid out like the module tree
+    ///
+    /// Types in different modules may share a short name. Returns `None` for
+    /// languages that only write a single file.
+    fn generate_module_tree(&self, _type_defs: &[TypeDefinition]) -> Option<Vec<ModuleFile>> {
+        None
+    }
+
     /// Get the file extension for generated files
     fn file_extension(&self) -> &'static str {
         self.language().file_extension()
@@ -240,12 +258,16 @@ pub trait CodeGenerator: Send + Sync {
 
 // Re-export existing generators
 pub mod go;
+pub mod json_schema;
+pub mod module_tree;
 pub mod python;
 pub mod ruby;
 pub mod rust;
 pub mod seahorse;
 pub mod typescript;
 
+pub use module_tree::ModuleFile;
+
 /// Rust code generator implementing `CodeGenerator` trait
 pub struct RustGenerator;
 
@@ -261,6 +283,10 @@ impl CodeGenerator for RustGenerator {
     fn generate(&self, type_def: &TypeDefinition) -> String {
         rust::generate(type_def)
     }
+
+    fn generate_module_tree(&self, type_defs: &[TypeDefinition]) -> Option<Vec<ModuleFile>> {
+        Some(rust::generate_module_tree(type_defs))
+    }
 }
 
 /// TypeScript code generator implementing `CodeGenerator` trait
@@ -278,6 +304,10 @@ impl CodeGenerator for TypeScriptGenerator {
     fn generate(&self, type_def: &TypeDefinition) -> String {
         typescript::generate(type_def)
     }
+
+    fn generate_module_tree(&self, type_defs: &[TypeDefinition]) -> Option<Vec<ModuleFile>> {
+        Some(typescript::generate_module_tree(type_defs))
+    }
 }
 
 /// Python code generator implementing `CodeGenerator` trait
@@ -348,6 +378,23 @@ impl CodeGenerator for SeahorseGenerator {
     }
 }
 
+/// JSON Schema generator implementing `CodeGenerator` trait
+pub struct JsonSchemaGenerator;
+
+impl CodeGenerator for JsonSchemaGenerator {
+    fn language(&self) -> Language {
+        Language::JsonSchema
+    }
+
+    fn generate_module(&self, type_defs: &[TypeDefinition]) -> String {
+        json_schema::generate_module(type_defs)
+    }
+
+    fn generate(&self, type_def: &TypeDefinition) -> String {
+        json_schema::generate(type_def)
+    }
+}
+
 /// Get a code generator for the specified language
 ///
 /// # Arguments
@@ -380,6 +427,7 @@ pub fn get_generator(language: Language) -> Box<dyn CodeGenerator> {
         Language::Go => Box::new(GoGenerator),
         Language::Ruby => Box::new(RubyGenerator),
         Language::Seahorse => Box::new(SeahorseGenerator),
+        Language::JsonSchema => Box::new(JsonSchemaGenerator),
     }
 }
 
@@ -405,6 +453,7 @@ pub fn try_get_generator(language: Language) -> Option<Box<dyn CodeGenerator>> {
         Language::Go => Some(Box::new(GoGenerator)),
         Language::Ruby => Some(Box::new(RubyGenerator)),
         Language::Seahorse => Some(Box::new(SeahorseGenerator)),
+        Language::JsonSchema => Some(Box::new(JsonSchemaGenerator)),
     }
 }
 
@@ -447,6 +496,8 @@ pub fn get_generators(languages: &[Language]) -> Vec<Box<dyn CodeGenerator>> {
 ///     metadata: Metadata::default(),
 ///     visibility: Visibility::Public,
 ///     module_path: Vec::new(),
+///     reexports: Vec::new(),
+///     span: None,
 /// })];
 ///
 /// let results = generate_for_languages(&type_defs, &[Language::Rust, Language::TypeScript]);
@@ -482,6 +533,14 @@ mod tests {
         assert_eq!(Language::from_name("golang"), Some(Language::Go));
         assert_eq!(Language::from_name("ruby"), Some(Language::Ruby));
         assert_eq!(Language::from_name("rb"), Some(Language::Ruby));
+        assert_eq!(
+            Language::from_name("jsonschema"),
+            Some(Language::JsonSchema)
+        );
+        assert_eq!(
+            Language::from_name("json-schema"),
+            Some(Language::JsonSchema)
+        );
         assert_eq!(Language::from_name("invalid"), None);
     }
 
@@ -509,6 +568,7 @@ mod tests {
         assert_eq!(Language::Python.file_extension(), "py");
         assert_eq!(Language::Go.file_extension(), "go");
         assert_eq!(Language::Ruby.file_extension(), "rb");
+        assert_eq!(Language::JsonSchema.file_extension(), "json");
     }
 
     #[test]
@@ -542,6 +602,7 @@ mod tests {
         assert!(try_get_generator(Language::Go).is_some());
         assert!(try_get_generator(Language::Ruby).is_some());
         assert!(try_get_generator(Language::Seahorse).is_some());
+        assert!(try_get_generator(Language::JsonSchema).is_some());
     }
 
     #[test]
@@ -553,17 +614,19 @@ mod tests {
             Language::Go,
             Language::Ruby,
             Language::Seahorse,
+            Language::JsonSchema,
         ];
         let generators = get_generators(&langs);
 
-        // All 6 languages are implemented
-        assert_eq!(generators.len(), 6);
+        // All 7 languages are implemented
+        assert_eq!(generators.len(), 7);
         assert_eq!(generators[0].language(), Language::Rust);
         assert_eq!(generators[1].language(), Language::TypeScript);
         assert_eq!(generators[2].language(), Language::Python);
         assert_eq!(generators[3].language(), Language::Go);
         assert_eq!(generators[4].language(), Language::Ruby);
         assert_eq!(generators[5].language(), Language::Seahorse);
+        assert_eq!(generators[6].language(), Language::JsonSchema);
     }
 
     #[test]
@@ -574,14 +637,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "value".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let results = generate_for_languages(&type_defs, &[Language::Rust, Language::TypeScript]);
@@ -602,14 +664,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "id".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let code = gen.generate_module(&type_defs);
@@ -627,14 +688,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "id".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let code = gen.generate_module(&type_defs);
@@ -652,14 +712,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "id".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let code = gen.generate_module(&type_defs);
@@ -685,14 +744,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "id".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let code = gen.generate_module(&type_defs);
@@ -718,14 +776,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "id".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let code = gen.generate_module(&type_defs);
@@ -753,19 +810,20 @@ mod tests {
     #[test]
     fn test_language_supported() {
         let supported = Language::supported();
-        assert_eq!(supported.len(), 6);
+        assert_eq!(supported.len(), 7);
         assert!(supported.contains(&Language::Rust));
         assert!(supported.contains(&Language::TypeScript));
         assert!(supported.contains(&Language::Python));
         assert!(supported.contains(&Language::Go));
         assert!(supported.contains(&Language::Ruby));
         assert!(supported.contains(&Language::Seahorse));
+        assert!(supported.contains(&Language::JsonSchema));
     }
 
     #[test]
     fn test_language_all() {
         let all = Language::all();
-        assert_eq!(all.len(), 6);
+        assert_eq!(all.len(), 7);
     }
 
     #[test]
@@ -785,30 +843,23 @@ mod tests {
                 FieldDefinition {
                     name: "wallet".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "level".to_string(),
                     type_info: TypeInfo::Primitive("u16".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let code = gen.generate_module(&type_defs);
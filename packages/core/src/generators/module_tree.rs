@@ -0,0 +1,421 @@
+// Licensed under either of Apache License, Version 2.0 or MIT license at your option.
+// Copyright 2025 RECTOR-LABS
+
+//! Module-tree output
+//!
+//! By default a generator writes the whole schema to one file. A module tree
+//! gives every schema module its own file instead, laid out like the `mod`
+//! hierarchy, so types in different modules can share a short name:
+//!
+//! ```text
+//! generated/
+//! ├── mod.rs            // types of the entry file, `pub mod models;`
+//! └── models/
+//!     └── mod.rs        // types of models.lumos, `use super::Timestamp;`
+//! ```
+//!
+//! [`split_modules`] groups the IR by `module_path` and works out which types
+//! each module refers to in other modules. A type whose short name is already
+//! taken in the module is given a local alias named after its module
+//! (`ModelsConfig` for `models::Config`). Types a module re-exports with
+//! `pub use` are listed with it, so the generated code offers them at the
+//! same paths as the schema. Each generator then writes the files and import
+//! statements of its own language.
+
+use crate::ir::{Reexport, TypeDefinition, TypeInfo};
+use crate::naming::Case;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+
+/// A file of a generated module tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleFile {
+    /// Path relative to the root directory of the tree
+    pub path: PathBuf,
+
+    /// File contents
+    pub content: String,
+}
+
+/// A type that one module refers to from another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ModuleImport {
+    /// Module that declares the type
+    pub module: Vec<String>,
+
+    /// Name of the type in that module
+    pub name: String,
+
+    /// Name the importing module refers to it by
+    pub local: String,
+
+    /// `pub use` to import it through, when one is nearer than its module
+    pub via: Option<Reexport>,
+}
+
+/// One schema module of the tree
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TreeModule {
+    /// Module path, empty for the entry file
+    pub path: Vec<String>,
+
+    /// Types declared in the module, with every type reference spelled the
+    /// way the module's code refers to it
+    pub type_defs: Vec<TypeDefinition>,
+
+    /// Names of the child modules
+    pub children: Vec<String>,
+
+    /// Types the module refers to in other modules
+    pub imports: Vec<ModuleImport>,
+
+    /// Types of other modules it re-exports with `pub use`, `local` being
+    /// the name it re-exports them as
+    pub reexports: Vec<ModuleImport>,
+}
+
+/// Split the IR into one [`TreeModule`] per module, parents before children
+///
+/// Every ancestor of a module that declares types is included, so each
+/// module can be reached from the root.
+pub(crate) fn split_modules(type_defs: &[TypeDefinition]) -> Vec<TreeModule> {
+    let owners: HashMap<&str, &[String]> = type_defs
+        .iter()
+        .map(|t| (t.name(), t.module_path()))
+        .collect();
+    let reexports: HashMap<&str, &[Reexport]> = type_defs
+        .iter()
+        .map(|t| (t.name(), t.reexports()))
+        .collect();
+
+    let mut modules: BTreeMap<Vec<String>, TreeModule> = BTreeMap::new();
+    modules.insert(Vec::new(), TreeModule::default());
+    for type_def in type_defs {
+        insert_module(&mut modules, type_def.module_path())
+            .type_defs
+            .push(type_def.clone());
+        for reexport in type_def.reexports() {
+            insert_module(&mut modules, &reexport.module_path)
+                .reexports
+                .push(ModuleImport {
+                    module: type_def.module_path().to_vec(),
+                    name: short_name(type_def.name()).to_string(),
+                    local: reexport.name.clone(),
+                    via: None,
+                });
+        }
+    }
+
+    let paths: Vec<Vec<String>> = modules.keys().cloned().collect();
+    for path in paths.iter().filter(|p| !p.is_empty()) {
+        let (name, parent) = path.split_last().expect("path is not empty");
+        modules
+            .get_mut(parent)
+            .expect("parents are inserted with their children")
+            .children
+            .push(name.clone());
+    }
+
+    for module in modules.values_mut() {
+        localize(module, &owners, &reexports);
+    }
+    modules.into_values().collect()
+}
+
+/// The module at `path`, inserted along with its ancestors if missing
+fn insert_module<'m>(
+    modules: &'m mut BTreeMap<Vec<String>, TreeModule>,
+    path: &[String],
+) -> &'m mut TreeModule {
+    for depth in 1..path.len() {
+        modules
+            .entry(path[..depth].to_vec())
+            .or_insert_with(|| TreeModule {
+                path: path[..depth].to_vec(),
+                ..Default::default()
+            });
+    }
+    modules.entry(path.to_vec()).or_insert_with(|| TreeModule {
+        path: path.to_vec(),
+        ..Default::default()
+    })
+}
+
+/// Work out the module's imports and spell its type names locally
+///
+/// A type the module re-exports is referred to by its re-exported name. A
+/// type that a `pub use` makes available nearer than its declaration is
+/// imported through it, under the name it is re-exported as.
+fn localize(
+    module: &mut TreeModule,
+    owners: &HashMap<&str, &[String]>,
+    reexports: &HashMap<&str, &[Reexport]>,
+) {
+    let declared: BTreeSet<String> = module
+        .type_defs
+        .iter()
+        .map(|t| short_name(t.name()).to_string())
+        .chain(module.reexports.iter().map(|r| r.local.clone()))
+        .collect();
+
+    // Every type referred to in another module, by the module and name it is
+    // imported through, with its name in the IR
+    let mut referenced: BTreeMap<(Vec<String>, String), String> = BTreeMap::new();
+    for type_def in &module.type_defs {
+        for type_info in type_def.field_types() {
+            type_info.walk(&mut |t| {
+                let (TypeInfo::UserDefined(name) | TypeInfo::Instance { name, .. }) = t else {
+                    return;
+                };
+                match owners.get(name.as_str()) {
+                    Some(owner) if *owner != module.path.as_slice() => {
+                        let via = nearest_path(
+                            &module.path,
+                            owner,
+                            short_name(name),
+                            reexports.get(name.as_str()).copied().unwrap_or_default(),
+                        );
+                        referenced.insert(via, name.clone());
+                    }
+                    _ => {}
+                }
+            });
+        }
+    }
+
+    // A short name taken by a local type, or imported from two modules, is aliased
+    let mut imported_from: HashMap<&str, usize> = HashMap::new();
+    for (_, name) in referenced.keys() {
+        *imported_from.entry(name).or_default() += 1;
+    }
+    let mut locals: HashMap<String, String> = HashMap::new();
+    let mut imports = Vec::new();
+    for ((via_module, via_name), key) in &referenced {
+        let owner = owners[key.as_str()];
+        let name = short_name(key);
+        let reexported = module
+            .reexports
+            .iter()
+            .find(|r| r.module == owner && r.name == name);
+        let local = if let Some(reexport) = reexported {
+            reexport.local.clone()
+        } else if declared.contains(via_name) || imported_from[via_name.as_str()] > 1 {
+            format!("{}{}", module_prefix(via_module), via_name)
+        } else {
+            via_name.clone()
+        };
+        let via = (reexported.is_none() && via_module != owner).then(|| Reexport {
+            module_path: via_module.clone(),
+            name: via_name.clone(),
+        });
+        locals.insert(key.clone(), local.clone());
+        imports.push(ModuleImport {
+            module: owner.to_vec(),
+            name: name.to_string(),
+            local,
+            via,
+        });
+    }
+
+    for type_def in &mut module.type_defs {
+        set_name(type_def, short_name(type_def.name()).to_string());
+        for type_info in type_def.field_types_mut() {
+            type_info.walk_mut(&mut |t| {
+                let (TypeInfo::UserDefined(name) | TypeInfo::Instance { name, .. }) = t else {
+                    return;
+                };
+                if let Some(local) = locals.get(name.as_str()) {
+                    *name = local.clone();
+                } else if owners.contains_key(name.as_str()) {
+                    *name = short_name(name).to_string();
+                }
+            });
+        }
+    }
+    module.imports = imports;
+}
+
+/// The module and name nearest to `from` that a type declared as `name` in
+/// `owner` can be imported through: its declaration or one of its re-exports
+///
+/// On a tie the declaration wins.
+fn nearest_path(
+    from: &[String],
+    owner: &[String],
+    name: &str,
+    reexports: &[Reexport],
+) -> (Vec<String>, String) {
+    let distance = |to: &[String]| {
+        let (up, down) = relative_path(from, to);
+        up + down.len()
+    };
+    let mut nearest = (owner.to_vec(), name.to_string());
+    for reexport in reexports {
+        if reexport.module_path != from && distance(&reexport.module_path) < distance(&nearest.0) {
+            nearest = (reexport.module_path.clone(), reexport.name.clone());
+        }
+    }
+    nearest
+}
+
+/// How to reach module `to` from module `from`: the number of levels to go
+/// up, then the path down from there
+pub(crate) fn relative_path<'a>(from: &[String], to: &'a [String]) -> (usize, &'a [String]) {
+    let common = from.iter().zip(to).take_while(|(a, b)| a == b).count();
+    (from.len() - common, &to[common..])
+}
+
+/// Name of a type without the module path the IR may qualify it with
+pub(crate) fn short_name(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Prefix for aliases of types from `module` (`ModelsUser` for `models::user`)
+fn module_prefix(module: &[String]) -> String {
+    if module.is_empty() {
+        "Crate".to_string()
+    } else {
+        Case::Pascal.apply(&module.join("_"))
+    }
+}
+
+fn set_name(type_def: &mut TypeDefinition, name: String) {
+    match type_def {
+        TypeDefinition::Struct(s) => s.name = name,
+        TypeDefinition::Enum(e) => e.name = name,
+        TypeDefinition::TypeAlias(a) => a.name = name,
+        TypeDefinition::Const(c) => c.name = name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{FieldDefinition, Metadata, StructDefinition, Visibility};
+
+    fn module_struct(module: &[&str], name: &str, field_types: &[&str]) -> TypeDefinition {
+        TypeDefinition::Struct(StructDefinition {
+            name: name.to_string(),
+            generic_params: vec![],
+            fields: field_types
+                .iter()
+                .enumerate()
+                .map(|(i, t)| FieldDefinition {
+                    name: format!("field{}", i),
+                    type_info: TypeInfo::UserDefined(t.to_string()),
+                    ..Default::default()
+                })
+                .collect(),
+            metadata: Metadata::default(),
+            visibility: Visibility::Public,
+            module_path: module.iter().map(|m| m.to_string()).collect(),
+            reexports: Vec::new(),
+            span: None,
+        })
+    }
+
+    fn field_type(type_def: &TypeDefinition, i: usize) -> &TypeInfo {
+        type_def.field_types()[i]
+    }
+
+    #[test]
+    fn test_split_modules() {
+        let modules = split_modules(&[
+            module_struct(&[], "Vault", &["User"]),
+            module_struct(&["models", "user"], "User", &["Timestamp"]),
+            module_struct(&[], "Timestamp", &[]),
+        ]);
+
+        let paths: Vec<&[String]> = modules.iter().map(|m| m.path.as_slice()).collect();
+        assert_eq!(paths.len(), 3);
+        assert!(paths[0].is_empty());
+        assert_eq!(paths[1], ["models"]);
+        assert_eq!(paths[2], ["models", "user"]);
+
+        // Intermediate modules exist to declare their children
+        assert_eq!(modules[0].children, ["models"]);
+        assert_eq!(modules[1].children, ["user"]);
+        assert!(modules[1].type_defs.is_empty());
+
+        assert_eq!(
+            modules[0].imports,
+            vec![ModuleImport {
+                module: vec!["models".to_string(), "user".to_string()],
+                name: "User".to_string(),
+                local: "User".to_string(),
+                via: None,
+            }]
+        );
+        assert_eq!(modules[2].imports[0].module, Vec::<String>::new());
+        assert_eq!(modules[2].imports[0].local, "Timestamp");
+    }
+
+    #[test]
+    fn test_shared_names_are_aliased() {
+        let modules = split_modules(&[
+            module_struct(&[], "Config", &["settings::Config"]),
+            module_struct(&["settings"], "settings::Config", &[]),
+        ]);
+
+        assert_eq!(modules[0].type_defs[0].name(), "Config");
+        assert_eq!(modules[0].imports[0].name, "Config");
+        assert_eq!(modules[0].imports[0].local, "SettingsConfig");
+        assert_eq!(
+            field_type(&modules[0].type_defs[0], 0),
+            &TypeInfo::UserDefined("SettingsConfig".to_string())
+        );
+
+        // The qualified name is only the IR's; the module spells it short
+        assert_eq!(modules[1].type_defs[0].name(), "Config");
+    }
+
+    #[test]
+    fn test_reexports_are_imported_through() {
+        let mut user = module_struct(&["models", "user"], "User", &[]);
+        if let TypeDefinition::Struct(s) = &mut user {
+            s.reexports.push(Reexport {
+                module_path: vec!["models".to_string()],
+                name: "Member".to_string(),
+            });
+        }
+        let modules = split_modules(&[
+            module_struct(&[], "Vault", &["User"]),
+            module_struct(&["models"], "Roster", &["User"]),
+            user,
+        ]);
+
+        // The root goes through the nearer re-export, under its name
+        let import = &modules[0].imports[0];
+        assert_eq!(import.module, ["models", "user"]);
+        assert_eq!(import.local, "Member");
+        assert_eq!(
+            import.via,
+            Some(Reexport {
+                module_path: vec!["models".to_string()],
+                name: "Member".to_string(),
+            })
+        );
+        assert_eq!(
+            field_type(&modules[0].type_defs[0], 0),
+            &TypeInfo::UserDefined("Member".to_string())
+        );
+
+        // The re-exporting module already has the type in scope
+        assert_eq!(modules[1].reexports[0].local, "Member");
+        assert_eq!(modules[1].imports, modules[1].reexports);
+    }
+
+    #[test]
+    fn test_relative_path() {
+        let path = |p: &[&str]| p.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let models_user = path(&["models", "user"]);
+        let models_account = path(&["models", "account"]);
+
+        assert_eq!(
+            relative_path(&models_user, &models_account),
+            (1, &models_account[1..])
+        );
+        assert_eq!(relative_path(&models_user, &[]), (2, &[][..]));
+        assert_eq!(relative_path(&[], &models_user), (0, &models_user[..]));
+    }
+}
@@ -57,20 +57,50 @@
 //! ```
 
 use crate::ir::{
-    EnumDefinition, EnumVariantDefinition, StructDefinition, TypeAliasDefinition, TypeDefinition,
-    TypeInfo,
+    ConstDefinition, Constraint, DefaultValue, EnumDefinition, EnumVariantDefinition,
+    FieldDefinition, StructDefinition, TypeAliasDefinition, TypeDefinition, TypeInfo,
 };
+use crate::naming::{apply_renames, NameTarget};
+use crate::transform::inline_newtypes;
 use std::collections::HashSet;
 
 /// Generate Python code from a type definition
 pub fn generate(type_def: &TypeDefinition) -> String {
+    let type_def = &apply_renames(
+        &inline_newtypes(std::slice::from_ref(type_def)),
+        NameTarget::Python,
+    )[0];
     match type_def {
         TypeDefinition::Struct(struct_def) => generate_struct(struct_def),
         TypeDefinition::Enum(enum_def) => generate_enum(enum_def),
         TypeDefinition::TypeAlias(alias_def) => generate_type_alias(alias_def),
+        TypeDefinition::Const(const_def) => generate_const(const_def),
     }
 }
 
+/// Generate Python code from a constant definition
+fn generate_const(const_def: &ConstDefinition) -> String {
+    let mut output = String::new();
+
+    // Add file header
+    output.push_str("# Auto-generated by LUMOS\n");
+    output.push_str("# DO NOT EDIT - Changes will be overwritten\n\n");
+
+    output.push_str(&generate_const_definition(const_def));
+
+    output
+}
+
+/// Generate a module-level Python constant
+pub(crate) fn generate_const_definition(const_def: &ConstDefinition) -> String {
+    let mut output = String::new();
+    for line in &const_def.docs {
+        output.push_str(&format!("#: {}\n", line));
+    }
+    output.push_str(&format!("{} = {}\n", const_def.name, const_def.value));
+    output
+}
+
 /// Generate Python code from a type alias definition
 fn generate_type_alias(alias_def: &TypeAliasDefinition) -> String {
     let mut output = String::new();
@@ -187,6 +217,7 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
             TypeDefinition::Struct(s) => STRUCT_BASE + (s.fields.len() * FIELD_SIZE),
             TypeDefinition::Enum(e) => ENUM_BASE + (e.variants.len() * VARIANT_SIZE),
             TypeDefinition::TypeAlias(_) => 120,
+            TypeDefinition::Const(_) => 60,
         };
     }
 
@@ -205,6 +236,7 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
 ///
 /// Complete Python source code as a `String`, ready to write to a `.py` file.
 pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
+    let type_defs = &apply_renames(&inline_newtypes(type_defs), NameTarget::Python);
     let estimated_capacity = estimate_output_size(type_defs);
     let mut output = String::with_capacity(estimated_capacity);
 
@@ -232,6 +264,7 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
                     all_imports.insert("from solders.pubkey import Pubkey".to_string());
                 }
             }
+            TypeDefinition::Const(_) => {}
         }
     }
 
@@ -298,12 +331,51 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
                 let py_type = map_type_to_python(&a.target);
                 output.push_str(&format!("{}: TypeAlias = {}\n", a.name, py_type));
             }
+            TypeDefinition::Const(c) => {
+                output.push_str(&generate_const_definition(c));
+            }
         }
     }
 
     output
 }
 
+/// Build docstring lines from type docs plus an `Attributes:` section for documented fields
+pub(crate) fn docstring_lines(docs: &[String], fields: &[FieldDefinition]) -> Vec<String> {
+    let mut lines = docs.to_vec();
+
+    let documented: Vec<_> = fields.iter().filter(|f| !f.docs.is_empty()).collect();
+    if !documented.is_empty() {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push("Attributes:".to_string());
+        for field in documented {
+            lines.push(format!("    {}: {}", field.name, field.docs.join(" ")));
+        }
+    }
+
+    lines
+}
+
+/// Emit a triple-quoted docstring at the given indentation
+pub(crate) fn push_docstring(output: &mut String, lines: &[String], indent: &str) {
+    output.push_str(&format!("{}\"\"\"\n", indent));
+    for line in lines {
+        if line.is_empty() {
+            output.push('\n');
+        } else {
+            // Keep a stray `"""` in the doc text from closing the docstring early
+            output.push_str(&format!(
+                "{}{}\n",
+                indent,
+                line.replace("\"\"\"", "\\\"\"\"")
+            ));
+        }
+    }
+    output.push_str(&format!("{}\"\"\"\n", indent));
+}
+
 /// Generate Python dataclass for struct
 fn generate_struct_dataclass(struct_def: &StructDefinition) -> String {
     let mut output = String::new();
@@ -311,29 +383,37 @@ fn generate_struct_dataclass(struct_def: &StructDefinition) -> String {
     output.push_str("@dataclass\n");
     output.push_str(&format!("class {}:\n", struct_def.name));
 
-    if struct_def.fields.is_empty() {
-        output.push_str("    pass\n");
-    } else {
-        // Add docstring if we have deprecation warnings
-        let deprecated_fields: Vec<_> = struct_def
-            .fields
-            .iter()
-            .filter(|f| f.deprecated.is_some())
-            .collect();
+    // Docstring from schema doc comments
+    let mut doc_lines = docstring_lines(&struct_def.metadata.docs, &struct_def.fields);
 
-        if !deprecated_fields.is_empty() {
-            output.push_str("    \"\"\"\n");
-            output.push_str("    Deprecated fields:\n");
-            for field in &deprecated_fields {
-                if let Some(msg) = &field.deprecated {
-                    output.push_str(&format!("    - {}: {}\n", field.name, msg));
-                } else {
-                    output.push_str(&format!("    - {}: deprecated\n", field.name));
-                }
+    // Append deprecation warnings to the docstring
+    let deprecated_fields: Vec<_> = struct_def
+        .fields
+        .iter()
+        .filter(|f| f.deprecated.is_some())
+        .collect();
+
+    if !deprecated_fields.is_empty() {
+        if !doc_lines.is_empty() {
+            doc_lines.push(String::new());
+        }
+        doc_lines.push("Deprecated fields:".to_string());
+        for field in &deprecated_fields {
+            if let Some(msg) = &field.deprecated {
+                doc_lines.push(format!("- {}: {}", field.name, msg));
+            } else {
+                doc_lines.push(format!("- {}: deprecated", field.name));
             }
-            output.push_str("    \"\"\"\n");
         }
+    }
 
+    if !doc_lines.is_empty() {
+        push_docstring(&mut output, &doc_lines, "    ");
+    }
+
+    if struct_def.fields.is_empty() {
+        output.push_str("    pass\n");
+    } else {
         // Generate fields
         for field in &struct_def.fields {
             let py_type = map_type_to_python(&field.type_info);
@@ -349,6 +429,127 @@ fn generate_struct_dataclass(struct_def: &StructDefinition) -> String {
         }
     }
 
+    if struct_def.derives_default() {
+        output.push('\n');
+        output.push_str(&generate_default_method(struct_def));
+    }
+
+    if struct_def.has_constraints() {
+        output.push('\n');
+        output.push_str(&generate_validate_method(struct_def));
+    }
+
+    output
+}
+
+/// Generate the `default()` class method for `#[derive(Default)]`
+///
+/// Fields with `#[default(..)]` use the declared value, the rest a zero value.
+fn generate_default_method(struct_def: &StructDefinition) -> String {
+    let mut output = String::new();
+    output.push_str("    @classmethod\n");
+    output.push_str(&format!(
+        "    def default(cls) -> \"{}\":\n",
+        struct_def.name
+    ));
+    output.push_str(&format!(
+        "        \"\"\"Create a default {} instance\"\"\"\n",
+        struct_def.name
+    ));
+    output.push_str("        return cls(\n");
+    for field in &struct_def.fields {
+        let value = match &field.default {
+            Some(DefaultValue::Integer(value)) => value.to_string(),
+            Some(DefaultValue::Float(value)) => format!("{:?}", value),
+            Some(DefaultValue::Bool(true)) => "True".to_string(),
+            Some(DefaultValue::Bool(false)) => "False".to_string(),
+            Some(DefaultValue::String(value)) => serde_json::to_string(value).unwrap_or_default(),
+            None => zero_value(&field.type_info),
+        };
+        output.push_str(&format!("            {}={},\n", field.name, value));
+    }
+    output.push_str("        )\n");
+    output
+}
+
+/// Python zero value for a type, used by `default()`
+fn zero_value(type_info: &TypeInfo) -> String {
+    match type_info {
+        TypeInfo::Primitive(type_name) => match type_name.as_str() {
+            "f32" | "f64" => "0.0".to_string(),
+            "bool" => "False".to_string(),
+            "String" | "Signature" => "\"\"".to_string(),
+            "Pubkey" | "PublicKey" => "Pubkey.default()".to_string(),
+            _ => "0".to_string(),
+        },
+        TypeInfo::Array(_) | TypeInfo::FixedArray { .. } => "[]".to_string(),
+        TypeInfo::FixedString(size) => format!("bytes({})", size),
+        TypeInfo::Option(_) | TypeInfo::Generic(_) => "None".to_string(),
+        TypeInfo::Map { .. } => "{}".to_string(),
+        TypeInfo::Set { .. } => "set()".to_string(),
+        TypeInfo::Tuple(elements) => {
+            let values: Vec<String> = elements.iter().map(zero_value).collect();
+            if values.len() == 1 {
+                format!("({},)", values[0])
+            } else {
+                format!("({})", values.join(", "))
+            }
+        }
+        TypeInfo::Boxed(inner) => zero_value(inner),
+        TypeInfo::UserDefined(type_name)
+        | TypeInfo::Instance {
+            name: type_name, ..
+        } => format!("{}.default()", type_name),
+    }
+}
+
+/// Generate `validate()`, which raises `ValueError` if a `#[range]` or `#[pattern]` constraint fails
+fn generate_validate_method(struct_def: &StructDefinition) -> String {
+    let mut output = String::new();
+    output.push_str("    def validate(self) -> None:\n");
+    output.push_str("        \"\"\"Check the value constraints declared in the schema\"\"\"\n");
+
+    for field in &struct_def.fields {
+        let value = format!("self.{}", field.name);
+        for constraint in &field.constraints {
+            let violated = match constraint {
+                Constraint::Range {
+                    min: Some(min),
+                    max: Some(max),
+                } => format!("not {} <= {} <= {}", min, value, max),
+                Constraint::Range {
+                    min: Some(min),
+                    max: None,
+                } => format!("{} < {}", value, min),
+                Constraint::Range {
+                    min: None,
+                    max: Some(max),
+                } => format!("{} > {}", value, max),
+                Constraint::Range {
+                    min: None,
+                    max: None,
+                } => continue,
+                Constraint::Pattern(pattern) => format!(
+                    "re.search({}, {}) is None",
+                    serde_json::to_string(pattern).unwrap_or_default(),
+                    value
+                ),
+            };
+            // Optional fields are only checked when present
+            let condition = if matches!(field.type_info, TypeInfo::Option(_)) {
+                format!("{} is not None and {}", value, violated)
+            } else {
+                violated
+            };
+            let message = format!("{} {}", field.name, constraint);
+            output.push_str(&format!("        if {}:\n", condition));
+            output.push_str(&format!(
+                "            raise ValueError({})\n",
+                serde_json::to_string(&message).unwrap_or_default()
+            ));
+        }
+    }
+
     output
 }
 
@@ -375,38 +576,66 @@ fn generate_struct_borsh_schema(struct_def: &StructDefinition) -> String {
 fn generate_enum_class(enum_def: &EnumDefinition) -> String {
     let mut output = String::new();
 
-    // Check if all variants are unit variants (simple IntEnum)
+    // Check if all variants are unit variants (simple IntEnum, or IntFlag for #[flags])
     if enum_def.is_unit_only() {
-        output.push_str(&format!("class {}(IntEnum):\n", enum_def.name));
-        for (idx, variant) in enum_def.variants.iter().enumerate() {
-            if let EnumVariantDefinition::Unit { name } = variant {
-                output.push_str(&format!("    {} = {}\n", name, idx));
+        let base = if enum_def.is_flags() {
+            "IntFlag"
+        } else {
+            "IntEnum"
+        };
+        output.push_str(&format!("class {}({}):\n", enum_def.name, base));
+        if !enum_def.metadata.docs.is_empty() {
+            push_docstring(&mut output, &enum_def.metadata.docs, "    ");
+        }
+        for (variant, discriminant) in enum_def.variants.iter().zip(enum_def.discriminants()) {
+            if let EnumVariantDefinition::Unit { name, docs, .. } = variant {
+                for line in docs {
+                    output.push_str(&format!("    #: {}\n", line));
+                }
+                output.push_str(&format!("    {} = {}\n", name, discriminant));
             }
         }
     } else {
         // Complex enum with variants - use tagged union pattern
+        for line in &enum_def.metadata.docs {
+            output.push_str(&format!("# {}\n", line));
+        }
         output.push_str("# Variant types\n");
 
         // Generate variant dataclasses
         for variant in &enum_def.variants {
             match variant {
-                EnumVariantDefinition::Unit { name } => {
+                EnumVariantDefinition::Unit { name, docs, .. } => {
                     output.push_str("@dataclass\n");
                     output.push_str(&format!("class {}{}:\n", enum_def.name, name));
+                    if !docs.is_empty() {
+                        push_docstring(&mut output, docs, "    ");
+                    }
                     output.push_str("    pass\n\n");
                 }
-                EnumVariantDefinition::Tuple { name, types } => {
+                EnumVariantDefinition::Tuple {
+                    name, types, docs, ..
+                } => {
                     output.push_str("@dataclass\n");
                     output.push_str(&format!("class {}{}:\n", enum_def.name, name));
+                    if !docs.is_empty() {
+                        push_docstring(&mut output, docs, "    ");
+                    }
                     for (idx, type_info) in types.iter().enumerate() {
                         let py_type = map_type_to_python(type_info);
                         output.push_str(&format!("    field{}: {}\n", idx, py_type));
                     }
                     output.push('\n');
                 }
-                EnumVariantDefinition::Struct { name, fields } => {
+                EnumVariantDefinition::Struct {
+                    name, fields, docs, ..
+                } => {
                     output.push_str("@dataclass\n");
                     output.push_str(&format!("class {}{}:\n", enum_def.name, name));
+                    let doc_lines = docstring_lines(docs, fields);
+                    if !doc_lines.is_empty() {
+                        push_docstring(&mut output, &doc_lines, "    ");
+                    }
                     if fields.is_empty() {
                         output.push_str("    pass\n\n");
                     } else {
@@ -437,15 +666,43 @@ fn generate_enum_class(enum_def: &EnumDefinition) -> String {
 }
 
 /// Generate Borsh schema for enum serialization
+///
+/// `borsh_construct.Enum` tags variants by position with a `U8`, so enums with
+/// explicit discriminants or a wider `#[repr]` switch on the tag instead.
 fn generate_enum_borsh_schema(enum_def: &EnumDefinition) -> String {
     let mut output = String::new();
+    let tag_type = enum_def.repr().name().to_uppercase();
 
-    if enum_def.is_unit_only() {
-        // Simple enum - just use U8 for discriminant
+    if enum_def.is_flags() {
         output.push_str(&format!(
-            "{}_SCHEMA = U8  # Enum discriminant\n",
+            "{}_SCHEMA = {}  # Packed flags\n",
+            enum_def.name.to_uppercase(),
+            tag_type
+        ));
+    } else if enum_def.is_unit_only() {
+        // Simple enum - the discriminant is the whole encoding
+        output.push_str(&format!(
+            "{}_SCHEMA = {}  # Enum discriminant\n",
+            enum_def.name.to_uppercase(),
+            tag_type
+        ));
+    } else if enum_def.has_custom_discriminants() {
+        output.push_str(&format!(
+            "{}_SCHEMA = CStruct(\n",
             enum_def.name.to_uppercase()
         ));
+        output.push_str(&format!("    \"discriminant\" / {},\n", tag_type));
+        output.push_str("    \"value\" / Switch(this.discriminant, {\n");
+        for (variant, discriminant) in enum_def.variants.iter().zip(enum_def.discriminants()) {
+            output.push_str(&format!("        # {}\n", variant.name()));
+            output.push_str(&format!(
+                "        {}: {},\n",
+                discriminant,
+                enum_variant_layout(variant, "        ")
+            ));
+        }
+        output.push_str("    }),\n");
+        output.push_str(")\n");
     } else {
         // Complex enum with variants
         output.push_str(&format!(
@@ -454,33 +711,11 @@ fn generate_enum_borsh_schema(enum_def: &EnumDefinition) -> String {
         ));
 
         for variant in &enum_def.variants {
-            match variant {
-                EnumVariantDefinition::Unit { name } => {
-                    output.push_str(&format!("    \"{}\" / Pass,\n", name));
-                }
-                EnumVariantDefinition::Tuple { name, types } => {
-                    if types.len() == 1 {
-                        let borsh_type = map_type_to_borsh(&types[0]);
-                        output.push_str(&format!("    \"{}\" / {},\n", name, borsh_type));
-                    } else {
-                        output.push_str(&format!("    \"{}\" / CStruct(\n", name));
-                        for (idx, type_info) in types.iter().enumerate() {
-                            let borsh_type = map_type_to_borsh(type_info);
-                            output
-                                .push_str(&format!("        \"field{}\" / {},\n", idx, borsh_type));
-                        }
-                        output.push_str("    ),\n");
-                    }
-                }
-                EnumVariantDefinition::Struct { name, fields } => {
-                    output.push_str(&format!("    \"{}\" / CStruct(\n", name));
-                    for field in fields {
-                        let borsh_type = map_type_to_borsh(&field.type_info);
-                        output.push_str(&format!("        \"{}\" / {},\n", field.name, borsh_type));
-                    }
-                    output.push_str("    ),\n");
-                }
-            }
+            output.push_str(&format!(
+                "    \"{}\" / {},\n",
+                variant.name(),
+                enum_variant_layout(variant, "    ")
+            ));
         }
 
         output.push_str(")\n");
@@ -489,6 +724,40 @@ fn generate_enum_borsh_schema(enum_def: &EnumDefinition) -> String {
     output
 }
 
+/// borsh-construct layout of a single enum variant, with inner lines indented by `indent`
+fn enum_variant_layout(variant: &EnumVariantDefinition, indent: &str) -> String {
+    match variant {
+        EnumVariantDefinition::Unit { .. } => "Pass".to_string(),
+        EnumVariantDefinition::Tuple { types, .. } if types.len() == 1 => {
+            map_type_to_borsh(&types[0])
+        }
+        EnumVariantDefinition::Tuple { types, .. } => {
+            let mut layout = "CStruct(\n".to_string();
+            for (idx, type_info) in types.iter().enumerate() {
+                let borsh_type = map_type_to_borsh(type_info);
+                layout.push_str(&format!(
+                    "{}    \"field{}\" / {},\n",
+                    indent, idx, borsh_type
+                ));
+            }
+            layout.push_str(&format!("{})", indent));
+            layout
+        }
+        EnumVariantDefinition::Struct { fields, .. } => {
+            let mut layout = "CStruct(\n".to_string();
+            for field in fields {
+                let borsh_type = map_type_to_borsh(&field.type_info);
+                layout.push_str(&format!(
+                    "{}    \"{}\" / {},\n",
+                    indent, field.name, borsh_type
+                ));
+            }
+            layout.push_str(&format!("{})", indent));
+            layout
+        }
+    }
+}
+
 /// Collect required imports based on struct definition
 fn collect_struct_imports(struct_def: &StructDefinition) -> HashSet<String> {
     let mut imports = HashSet::new();
@@ -496,6 +765,15 @@ fn collect_struct_imports(struct_def: &StructDefinition) -> HashSet<String> {
     // Always need dataclass
     imports.insert("from dataclasses import dataclass".to_string());
 
+    // Pattern constraints are checked with the standard library regex module
+    if struct_def.fields.iter().any(|f| {
+        f.constraints
+            .iter()
+            .any(|c| matches!(c, Constraint::Pattern(_)))
+    }) {
+        imports.insert("import re".to_string());
+    }
+
     // Check field types for imports
     let mut needs_pubkey = false;
     let needs_borsh = struct_def.metadata.solana;
@@ -509,7 +787,11 @@ fn collect_struct_imports(struct_def: &StructDefinition) -> HashSet<String> {
     }
 
     if needs_borsh {
-        imports.insert("from borsh_construct import Bytes, CStruct, U8, U16, U32, U64, U128, I8, I16, I32, I64, I128, Bool, String, Vec, Option".to_string());
+        imports.insert("from borsh_construct import Bytes, CStruct, U8, U16, U32, U64, U128, I8, I16, I32, I64, I128, Bool, String, Vec, Option, HashMap, HashSet".to_string());
+        collect_schema_helper_imports(
+            struct_def.fields.iter().map(|field| &field.type_info),
+            &mut imports,
+        );
     }
 
     imports
@@ -523,7 +805,9 @@ fn collect_enum_imports(enum_def: &EnumDefinition) -> HashSet<String> {
     let mut needs_pubkey = false;
     let needs_borsh = enum_def.metadata.solana;
 
-    if enum_def.is_unit_only() {
+    if enum_def.is_flags() {
+        imports.insert("from enum import IntFlag".to_string());
+    } else if enum_def.is_unit_only() {
         imports.insert("from enum import IntEnum".to_string());
     } else {
         imports.insert("from dataclasses import dataclass".to_string());
@@ -551,15 +835,52 @@ fn collect_enum_imports(enum_def: &EnumDefinition) -> HashSet<String> {
 
     if needs_borsh {
         if enum_def.is_unit_only() {
-            imports.insert("from borsh_construct import U8".to_string());
+            imports.insert(format!(
+                "from borsh_construct import {}",
+                enum_def.repr().name().to_uppercase()
+            ));
         } else {
-            imports.insert("from borsh_construct import CStruct, Enum, Pass, U8, U16, U32, U64, U128, I8, I16, I32, I64, I128, Bool, String, Vec, Option, Bytes".to_string());
+            if enum_def.has_custom_discriminants() {
+                imports.insert("from construct import Switch, this".to_string());
+            }
+            imports.insert("from borsh_construct import CStruct, Enum, Pass, U8, U16, U32, U64, U128, I8, I16, I32, I64, I128, Bool, String, Vec, Option, Bytes, HashMap, HashSet".to_string());
+            for variant in &enum_def.variants {
+                match variant {
+                    EnumVariantDefinition::Unit { .. } => {}
+                    EnumVariantDefinition::Tuple { types, .. } => {
+                        collect_schema_helper_imports(types, &mut imports)
+                    }
+                    EnumVariantDefinition::Struct { fields, .. } => collect_schema_helper_imports(
+                        fields.iter().map(|field| &field.type_info),
+                        &mut imports,
+                    ),
+                }
+            }
         }
     }
 
     imports
 }
 
+/// Import the construct helpers nested types need: `TupleStruct` for tuples
+/// and `LazyBound` for boxed (possibly recursive) types
+fn collect_schema_helper_imports<'t>(
+    types: impl IntoIterator<Item = &'t TypeInfo>,
+    imports: &mut HashSet<String>,
+) {
+    for type_info in types {
+        type_info.walk(&mut |t| match t {
+            TypeInfo::Tuple(_) => {
+                imports.insert("from borsh_construct import TupleStruct".to_string());
+            }
+            TypeInfo::Boxed(_) => {
+                imports.insert("from construct import LazyBound".to_string());
+            }
+            _ => {}
+        });
+    }
+}
+
 /// Recursively check if type needs specific imports
 fn collect_imports_from_type(type_info: &TypeInfo, needs_pubkey: &mut bool) {
     match type_info {
@@ -568,13 +889,33 @@ fn collect_imports_from_type(type_info: &TypeInfo, needs_pubkey: &mut bool) {
                 *needs_pubkey = true;
             }
         }
-        TypeInfo::Generic(_) => {}
+        TypeInfo::Generic(_) | TypeInfo::FixedString(_) => {}
         TypeInfo::Array(inner) | TypeInfo::FixedArray { element: inner, .. } => {
             collect_imports_from_type(inner, needs_pubkey);
         }
         TypeInfo::Option(inner) => {
             collect_imports_from_type(inner, needs_pubkey);
         }
+        TypeInfo::Map { key, value, .. } => {
+            collect_imports_from_type(key, needs_pubkey);
+            collect_imports_from_type(value, needs_pubkey);
+        }
+        TypeInfo::Set { element, .. } => {
+            collect_imports_from_type(element, needs_pubkey);
+        }
+        TypeInfo::Tuple(elements) => {
+            for element in elements {
+                collect_imports_from_type(element, needs_pubkey);
+            }
+        }
+        TypeInfo::Boxed(inner) => {
+            collect_imports_from_type(inner, needs_pubkey);
+        }
+        TypeInfo::Instance { args, .. } => {
+            for arg in args {
+                collect_imports_from_type(arg, needs_pubkey);
+            }
+        }
         TypeInfo::UserDefined(_) => {}
     }
 }
@@ -614,8 +955,29 @@ fn map_type_to_python(type_info: &TypeInfo) -> String {
             let inner_type = map_type_to_python(inner);
             format!("{} | None", inner_type)
         }
+        TypeInfo::Map { key, value, .. } => {
+            format!(
+                "dict[{}, {}]",
+                map_type_to_python(key),
+                map_type_to_python(value)
+            )
+        }
+        TypeInfo::Set { element, .. } => {
+            format!("set[{}]", map_type_to_python(element))
+        }
+        TypeInfo::Tuple(elements) => {
+            let element_types: Vec<String> = elements.iter().map(map_type_to_python).collect();
+            format!("tuple[{}]", element_types.join(", "))
+        }
+        TypeInfo::Boxed(inner) => map_type_to_python(inner),
+        // Zero-padded UTF-8: `value.rstrip(b"\0").decode()`
+        TypeInfo::FixedString(_) => "bytes".to_string(),
         TypeInfo::Generic(param_name) => param_name.clone(),
-        TypeInfo::UserDefined(type_name) => type_name.clone(),
+        // Generated classes aren't `typing.Generic`, so they can't be subscripted
+        TypeInfo::UserDefined(type_name)
+        | TypeInfo::Instance {
+            name: type_name, ..
+        } => type_name.clone(),
     }
 }
 
@@ -652,10 +1014,31 @@ fn map_type_to_borsh(type_info: &TypeInfo) -> String {
             let inner_borsh = map_type_to_borsh(inner);
             format!("Option({})", inner_borsh)
         }
+        // Borsh writes hash and B-tree collections identically (entries sorted by key)
+        TypeInfo::Map { key, value, .. } => {
+            format!(
+                "HashMap({}, {})",
+                map_type_to_borsh(key),
+                map_type_to_borsh(value)
+            )
+        }
+        TypeInfo::Set { element, .. } => {
+            format!("HashSet({})", map_type_to_borsh(element))
+        }
+        TypeInfo::Tuple(elements) => {
+            let element_borsh: Vec<String> = elements.iter().map(map_type_to_borsh).collect();
+            format!("TupleStruct({})", element_borsh.join(", "))
+        }
+        // Box<T> is serialized like T; resolving it lazily lets a schema refer to itself
+        TypeInfo::Boxed(inner) => format!("LazyBound(lambda: {})", map_type_to_borsh(inner)),
+        TypeInfo::FixedString(size) => format!("Bytes({})", size),
         TypeInfo::Generic(param_name) => {
             format!("/* Generic: {} */", param_name)
         }
-        TypeInfo::UserDefined(type_name) => {
+        TypeInfo::UserDefined(type_name)
+        | TypeInfo::Instance {
+            name: type_name, ..
+        } => {
             format!("{}_SCHEMA", type_name.to_uppercase())
         }
     }
@@ -666,6 +1049,34 @@ mod tests {
     use super::*;
     use crate::ir::{FieldDefinition, Metadata, StructDefinition, TypeDefinition, Visibility};
 
+    #[test]
+    fn generates_enums_with_explicit_discriminants() {
+        let source = r#"
+            #[solana]
+            #[repr(u16)]
+            enum Status {
+                Active = 1,
+                Closed = 500,
+            }
+
+            #[solana]
+            enum Command {
+                Noop = 3,
+                Transfer { amount: u64 },
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("    Active = 1\n    Closed = 500\n"));
+        assert!(code.contains("STATUS_SCHEMA = U16  # Enum discriminant"));
+        assert!(code.contains("from construct import Switch, this"));
+        assert!(code.contains(
+            "COMMAND_SCHEMA = CStruct(\n    \"discriminant\" / U8,\n    \"value\" / Switch(this.discriminant, {\n        # Noop\n        3: Pass,\n        # Transfer\n        4: CStruct(\n"
+        ));
+    }
+
     #[test]
     fn generates_simple_dataclass() {
         let type_def = TypeDefinition::Struct(StructDefinition {
@@ -675,24 +1086,20 @@ mod tests {
                 FieldDefinition {
                     name: "id".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "name".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -711,30 +1118,23 @@ mod tests {
                 FieldDefinition {
                     name: "wallet".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "balance".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -758,14 +1158,14 @@ mod tests {
                 name: "email".to_string(),
                 type_info: TypeInfo::Option(Box::new(TypeInfo::Primitive("String".to_string()))),
                 optional: true,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -782,21 +1182,16 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "members".to_string(),
                 type_info: TypeInfo::Array(Box::new(TypeInfo::Primitive("u64".to_string()))),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -815,6 +1210,8 @@ mod tests {
                 visibility: Visibility::Public,
 
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
             TypeDefinition::Struct(StructDefinition {
                 name: "Post".to_string(),
@@ -824,6 +1221,8 @@ mod tests {
                 visibility: Visibility::Public,
 
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
         ];
 
@@ -840,24 +1239,31 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Finished".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -877,10 +1283,15 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Started".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Tuple {
                     name: "PlayerJoined".to_string(),
                     types: vec![TypeInfo::Primitive("Pubkey".to_string())],
+                    docs: vec![],
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Struct {
                     name: "ScoreUpdate".to_string(),
@@ -888,32 +1299,26 @@ mod tests {
                         FieldDefinition {
                             name: "player".to_string(),
                             type_info: TypeInfo::Primitive("Pubkey".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                         FieldDefinition {
                             name: "score".to_string(),
                             type_info: TypeInfo::Primitive("u64".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                     ],
+                    docs: vec![],
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -998,6 +1403,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn maps_tuple_types() {
+        let tuple = TypeInfo::Tuple(vec![
+            TypeInfo::Primitive("u64".to_string()),
+            TypeInfo::Primitive("PublicKey".to_string()),
+        ]);
+        assert_eq!(map_type_to_python(&tuple), "tuple[int, Pubkey]");
+        assert_eq!(
+            map_type_to_borsh(&TypeInfo::Array(Box::new(tuple))),
+            "Vec(TupleStruct(U64, Bytes(32)))"
+        );
+    }
+
     #[test]
     fn handles_deprecated_fields() {
         let type_def = TypeDefinition::Struct(StructDefinition {
@@ -1007,28 +1425,133 @@ mod tests {
                 FieldDefinition {
                     name: "balance".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "old_field".to_string(),
                     type_info: TypeInfo::Primitive("u32".to_string()),
-                    optional: false,
                     deprecated: Some("Use new_field instead".to_string()),
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
         assert!(code.contains("Deprecated fields:"));
         assert!(code.contains("old_field: Use new_field instead"));
     }
+
+    #[test]
+    fn generates_validate_method_for_constraints() {
+        let source = r#"
+            #[solana]
+            struct Listing {
+                #[range(0..=10000)]
+                fee_bps: u16,
+                #[range(-5..5)]
+                delta: Option<i32>,
+                #[pattern("^[A-Z]{1,10}$")]
+                symbol: String,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("import re\n"));
+        assert!(code.contains("    def validate(self) -> None:\n"));
+        assert!(code.contains("        if self.fee_bps > 10000:\n            raise ValueError(\"fee_bps must be at most 10000\")\n"));
+        assert!(code.contains("        if self.delta is not None and not -5 <= self.delta <= 4:\n"));
+        assert!(code.contains("        if re.search(\"^[A-Z]{1,10}$\", self.symbol) is None:\n"));
+    }
+
+    #[test]
+    fn generates_default_method_with_field_defaults() {
+        let source = r#"
+            #[derive(Default)]
+            struct Config {
+                #[default(100)]
+                fee_bps: u16,
+                #[default("USDC")]
+                symbol: String,
+                #[default(true)]
+                active: bool,
+                #[default(0.5)]
+                ratio: f64,
+                count: u64,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("    @classmethod\n    def default(cls) -> \"Config\":\n"));
+        assert!(code.contains("        return cls(\n            fee_bps=100,\n            symbol=\"USDC\",\n            active=True,\n            ratio=0.5,\n            count=0,\n        )\n"));
+    }
+
+    #[test]
+    fn test_python_renames() {
+        let source = r#"
+            #![rename_fields(ts = "camelCase")]
+
+            #[solana]
+            #[rename(py = "TradeListing")]
+            struct Listing {
+                #[rename(py = "fee")]
+                fee_bps: u16,
+                owner_key: PublicKey,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("class TradeListing:\n    fee: int\n    owner_key: Pubkey\n"));
+        assert!(code.contains("TRADELISTING_SCHEMA = CStruct(\n    \"fee\" / U16,"));
+    }
+
+    #[test]
+    fn test_flags_enum_is_int_flag() {
+        let source = r#"
+            #[solana]
+            #[flags(u16)]
+            enum Permissions {
+                Mint,
+                Burn,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("from enum import IntFlag\n"));
+        assert!(code.contains("class Permissions(IntFlag):\n    Mint = 1\n    Burn = 2\n"));
+        assert!(code.contains("PERMISSIONS_SCHEMA = U16  # Packed flags\n"));
+    }
+
+    #[test]
+    fn test_newtype_is_plain_alias() {
+        let source = r#"
+            #[newtype]
+            type Lamports = u64;
+
+            #[solana]
+            struct Vault {
+                balance: Lamports,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("Lamports: TypeAlias = int\n"));
+        assert!(code.contains("    balance: int\n"));
+        assert!(code.contains("\"balance\" / U64,\n"));
+    }
 }
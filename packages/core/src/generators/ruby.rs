@@ -56,19 +56,46 @@
 //! ```
 
 use crate::ir::{
-    EnumDefinition, EnumVariantDefinition, StructDefinition, TypeAliasDefinition, TypeDefinition,
-    TypeInfo,
+    ConstDefinition, Constraint, DefaultValue, EnumDefinition, EnumVariantDefinition,
+    FieldDefinition, StructDefinition, TypeAliasDefinition, TypeDefinition, TypeInfo,
 };
+use crate::naming::{apply_renames, NameTarget};
+use crate::transform::inline_newtypes;
 
 /// Generate Ruby code from a type definition
 pub fn generate(type_def: &TypeDefinition) -> String {
+    let type_def = &apply_renames(
+        &inline_newtypes(std::slice::from_ref(type_def)),
+        NameTarget::Ruby,
+    )[0];
     match type_def {
         TypeDefinition::Struct(struct_def) => generate_struct(struct_def),
         TypeDefinition::Enum(enum_def) => generate_enum(enum_def),
         TypeDefinition::TypeAlias(alias_def) => generate_type_alias(alias_def),
+        TypeDefinition::Const(const_def) => generate_const(const_def),
     }
 }
 
+/// Generate Ruby code from a constant definition
+fn generate_const(const_def: &ConstDefinition) -> String {
+    let mut output = String::new();
+
+    // Add file header
+    output.push_str(&generate_header());
+
+    output.push_str(&generate_const_definition(const_def));
+
+    output
+}
+
+/// Generate a Ruby constant
+fn generate_const_definition(const_def: &ConstDefinition) -> String {
+    let mut output = String::new();
+    push_doc_comments(&mut output, &const_def.docs, "");
+    output.push_str(&format!("{} = {}\n", const_def.name, const_def.value));
+    output
+}
+
 /// Generate Ruby code from a type alias definition
 fn generate_type_alias(alias_def: &TypeAliasDefinition) -> String {
     let mut output = String::new();
@@ -156,7 +183,7 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
         total += match type_def {
             TypeDefinition::Struct(s) => STRUCT_BASE + (s.fields.len() * FIELD_SIZE),
             TypeDefinition::Enum(e) => ENUM_BASE + (e.variants.len() * VARIANT_SIZE),
-            TypeDefinition::TypeAlias(_) => 100,
+            TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => 100,
         };
     }
 
@@ -175,6 +202,7 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
 ///
 /// Complete Ruby source code as a `String`, ready to write to a `.rb` file.
 pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
+    let type_defs = &apply_renames(&inline_newtypes(type_defs), NameTarget::Ruby);
     let estimated_capacity = estimate_output_size(type_defs);
     let mut output = String::with_capacity(estimated_capacity);
 
@@ -188,7 +216,7 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
     let needs_borsh = type_defs.iter().any(|td| match td {
         TypeDefinition::Struct(s) => s.metadata.solana,
         TypeDefinition::Enum(e) => e.metadata.solana,
-        TypeDefinition::TypeAlias(_) => false,
+        TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => false,
     });
 
     if needs_borsh {
@@ -231,6 +259,9 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
                 output.push_str(&format!("# Type alias: {} = {}\n", a.name, ruby_type));
                 output.push_str(&format!("{} = {}\n", a.name, ruby_type));
             }
+            TypeDefinition::Const(c) => {
+                output.push_str(&generate_const_definition(c));
+            }
         }
     }
 
@@ -241,6 +272,9 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
 fn generate_struct_class(struct_def: &StructDefinition) -> String {
     let mut output = String::new();
 
+    // Type-level doc comments
+    push_doc_comments(&mut output, &struct_def.metadata.docs, "");
+
     // Add deprecation comments if any
     let deprecated_fields: Vec<_> = struct_def
         .fields
@@ -269,7 +303,7 @@ fn generate_struct_class(struct_def: &StructDefinition) -> String {
         let field_names: Vec<String> = struct_def
             .fields
             .iter()
-            .map(|f| format!(":{}", to_snake_case(&f.name)))
+            .map(|f| format!(":{}", attr_name(f)))
             .collect();
         output.push_str(&format!("  attr_accessor {}\n\n", field_names.join(", ")));
 
@@ -288,17 +322,32 @@ fn generate_struct_class(struct_def: &StructDefinition) -> String {
                 output.push_str(&format!("  # @deprecated {}\n", msg));
             }
             output.push_str(&format!(
-                "  # @option opts [{}] :{}\n",
+                "  # @option opts [{}] :{}{}\n",
                 type_doc,
-                to_snake_case(&field.name)
+                attr_name(field),
+                yard_description(&field.docs)
             ));
         }
 
         // Generate initialize method
         output.push_str("  def initialize(opts = {})\n");
         for field in &struct_def.fields {
-            let field_name = to_snake_case(&field.name);
-            output.push_str(&format!("    @{} = opts[:{}]\n", field_name, field_name));
+            let field_name = attr_name(field);
+            let value = match &field.default {
+                Some(DefaultValue::String(value)) => {
+                    format!(
+                        "opts.fetch(:{}, {})",
+                        field_name,
+                        ruby_string_literal(value)
+                    )
+                }
+                Some(DefaultValue::Float(value)) => {
+                    format!("opts.fetch(:{}, {:?})", field_name, value)
+                }
+                Some(value) => format!("opts.fetch(:{}, {})", field_name, value),
+                None => format!("opts[:{}]", field_name),
+            };
+            output.push_str(&format!("    @{} = {}\n", field_name, value));
         }
         output.push_str("  end\n");
 
@@ -307,23 +356,24 @@ fn generate_struct_class(struct_def: &StructDefinition) -> String {
         output.push_str("  def to_h\n");
         output.push_str("    {\n");
         for field in &struct_def.fields {
-            let field_name = to_snake_case(&field.name);
+            let field_name = attr_name(field);
             output.push_str(&format!("      {}: @{},\n", field_name, field_name));
         }
         output.push_str("    }\n");
         output.push_str("  end\n");
 
+        if struct_def.has_constraints() {
+            output.push('\n');
+            output.push_str(&generate_validate_method(struct_def));
+        }
+
         // Generate Borsh schema if Solana type
         if struct_def.metadata.solana {
             output.push_str("\n  # Borsh schema for serialization\n");
             output.push_str("  SCHEMA = {\n");
             for field in &struct_def.fields {
                 let borsh_type = map_type_to_borsh(&field.type_info);
-                output.push_str(&format!(
-                    "    {} => {},\n",
-                    to_snake_case(&field.name),
-                    borsh_type
-                ));
+                output.push_str(&format!("    {} => {},\n", attr_name(field), borsh_type));
             }
             output.push_str("  }.freeze\n");
         }
@@ -334,27 +384,123 @@ fn generate_struct_class(struct_def: &StructDefinition) -> String {
     output
 }
 
+/// Generate `validate`, which raises `ArgumentError` if a `#[range]` or `#[pattern]` constraint fails
+fn generate_validate_method(struct_def: &StructDefinition) -> String {
+    let mut output = String::new();
+    output.push_str("  # Check the value constraints declared in the schema\n");
+    output.push_str("  # @raise [ArgumentError] if a constraint is violated\n");
+    output.push_str("  def validate\n");
+
+    for field in &struct_def.fields {
+        let value = format!("@{}", attr_name(field));
+        for constraint in &field.constraints {
+            let violated = match constraint {
+                Constraint::Range {
+                    min: Some(min),
+                    max: Some(max),
+                } => format!("!({}..{}).cover?({})", min, max, value),
+                Constraint::Range {
+                    min: Some(min),
+                    max: None,
+                } => format!("{} < {}", value, min),
+                Constraint::Range {
+                    min: None,
+                    max: Some(max),
+                } => format!("{} > {}", value, max),
+                Constraint::Range {
+                    min: None,
+                    max: None,
+                } => continue,
+                Constraint::Pattern(pattern) => format!(
+                    "!Regexp.new({}).match?({})",
+                    ruby_string_literal(pattern),
+                    value
+                ),
+            };
+            // Optional fields are only checked when present
+            let condition = if matches!(field.type_info, TypeInfo::Option(_)) {
+                format!("!{}.nil? && {}", value, violated)
+            } else {
+                violated
+            };
+            output.push_str(&format!(
+                "    raise ArgumentError, {} if {}\n",
+                ruby_string_literal(&format!("{} {}", field.name, constraint)),
+                condition
+            ));
+        }
+    }
+
+    output.push_str("  end\n");
+    output
+}
+
+/// Single-quoted Ruby string literal (no interpolation)
+fn ruby_string_literal(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Emit `#` doc comment lines at the given indentation
+fn push_doc_comments(output: &mut String, docs: &[String], indent: &str) {
+    for line in docs {
+        if line.is_empty() {
+            output.push_str(&format!("{}#\n", indent));
+        } else {
+            output.push_str(&format!("{}# {}\n", indent, line));
+        }
+    }
+}
+
+/// Format doc lines as a trailing YARD description (e.g., after `@option opts [T] :name`)
+fn yard_description(docs: &[String]) -> String {
+    if docs.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", docs.join(" "))
+    }
+}
+
 /// Generate Ruby module for enum
 fn generate_enum_module(enum_def: &EnumDefinition) -> String {
     let mut output = String::new();
 
+    // Type-level doc comments
+    push_doc_comments(&mut output, &enum_def.metadata.docs, "");
+
     if enum_def.is_unit_only() {
         // Simple enum - use module with constants
         output.push_str(&format!("module {}\n", enum_def.name));
-        for (idx, variant) in enum_def.variants.iter().enumerate() {
-            if let EnumVariantDefinition::Unit { name } = variant {
-                output.push_str(&format!("  {} = {}\n", to_screaming_snake_case(name), idx));
+        for (variant, discriminant) in enum_def.variants.iter().zip(enum_def.discriminants()) {
+            if let EnumVariantDefinition::Unit { name, docs, .. } = variant {
+                push_doc_comments(&mut output, docs, "  ");
+                output.push_str(&format!(
+                    "  {} = {}\n",
+                    to_screaming_snake_case(name),
+                    discriminant
+                ));
             }
         }
         output.push('\n');
 
+        if enum_def.is_flags() {
+            output.push_str(&format!("  ALL = {}\n\n", enum_def.flags_mask()));
+            output.push_str("  # @param flags [Integer] Packed flags\n");
+            output.push_str("  # @param flag [Integer] Flags to check\n");
+            output.push_str("  # @return [Boolean] Whether every flag in flag is set\n");
+            output.push_str("  def self.has?(flags, flag)\n");
+            output.push_str("    flags & flag == flag\n");
+            output.push_str("  end\n");
+            output.push_str("end\n");
+            return output;
+        }
+
         // Add helper methods
         output.push_str("  # @param value [Integer] Enum value\n");
         output.push_str("  # @return [String] Enum name\n");
         output.push_str("  def self.name_for(value)\n");
         output.push_str("    case value\n");
         for variant in &enum_def.variants {
-            if let EnumVariantDefinition::Unit { name } = variant {
+            if let EnumVariantDefinition::Unit { name, .. } = variant {
                 output.push_str(&format!(
                     "    when {} then '{}'\n",
                     to_screaming_snake_case(name),
@@ -372,19 +518,20 @@ fn generate_enum_module(enum_def: &EnumDefinition) -> String {
 
         // Generate discriminant constants
         output.push_str("  # Discriminant values\n");
-        for (idx, variant) in enum_def.variants.iter().enumerate() {
+        for (variant, discriminant) in enum_def.variants.iter().zip(enum_def.discriminants()) {
             output.push_str(&format!(
                 "  {}_DISCRIMINANT = {}\n",
                 to_screaming_snake_case(variant.name()),
-                idx
+                discriminant
             ));
         }
         output.push('\n');
 
         // Generate variant classes
         for variant in &enum_def.variants {
+            push_doc_comments(&mut output, variant.docs(), "  ");
             match variant {
-                EnumVariantDefinition::Unit { name } => {
+                EnumVariantDefinition::Unit { name, .. } => {
                     output.push_str(&format!("  {} = Struct.new(:discriminant) do\n", name));
                     output.push_str(&format!(
                         "    def initialize\n      super({}_DISCRIMINANT)\n    end\n",
@@ -392,7 +539,7 @@ fn generate_enum_module(enum_def: &EnumDefinition) -> String {
                     ));
                     output.push_str("  end\n\n");
                 }
-                EnumVariantDefinition::Tuple { name, types } => {
+                EnumVariantDefinition::Tuple { name, types, .. } => {
                     let fields: Vec<String> =
                         (0..types.len()).map(|i| format!(":field{}", i)).collect();
                     output.push_str(&format!(
@@ -420,10 +567,10 @@ fn generate_enum_module(enum_def: &EnumDefinition) -> String {
                     ));
                     output.push_str("  end\n\n");
                 }
-                EnumVariantDefinition::Struct { name, fields } => {
+                EnumVariantDefinition::Struct { name, fields, .. } => {
                     let field_symbols: Vec<String> = fields
                         .iter()
-                        .map(|f| format!(":{}", to_snake_case(&f.name)))
+                        .map(|f| format!(":{}", attr_name(f)))
                         .collect();
                     output.push_str(&format!(
                         "  {} = Struct.new(:discriminant, {}) do\n",
@@ -435,17 +582,16 @@ fn generate_enum_module(enum_def: &EnumDefinition) -> String {
                     for field in fields {
                         let ruby_type = map_type_to_ruby(&field.type_info);
                         output.push_str(&format!(
-                            "    # @return [{}] {}\n",
+                            "    # @return [{}] {}{}\n",
                             ruby_type,
-                            to_snake_case(&field.name)
+                            attr_name(field),
+                            yard_description(&field.docs)
                         ));
                     }
 
                     // Initialize with discriminant
-                    let init_params: Vec<String> =
-                        fields.iter().map(|f| to_snake_case(&f.name)).collect();
-                    let init_args: Vec<String> =
-                        fields.iter().map(|f| to_snake_case(&f.name)).collect();
+                    let init_params: Vec<String> = fields.iter().map(attr_name).collect();
+                    let init_args: Vec<String> = fields.iter().map(attr_name).collect();
                     output.push_str(&format!(
                         "    def initialize({})\n      super({}_DISCRIMINANT, {})\n    end\n",
                         init_params.join(", "),
@@ -463,6 +609,15 @@ fn generate_enum_module(enum_def: &EnumDefinition) -> String {
     output
 }
 
+/// Ruby attribute name of a field: snake_case unless `#[rename(rb = ..)]` names it
+fn attr_name(field: &FieldDefinition) -> String {
+    if field.renames.contains_key(&NameTarget::Ruby) {
+        field.name.clone()
+    } else {
+        to_snake_case(&field.name)
+    }
+}
+
 /// Convert PascalCase/camelCase to snake_case
 fn to_snake_case(s: &str) -> String {
     let mut result = String::with_capacity(s.len() + 4);
@@ -517,8 +672,28 @@ fn map_type_to_ruby(type_info: &TypeInfo) -> String {
             let inner_type = map_type_to_ruby(inner);
             format!("{}, nil", inner_type)
         }
+        TypeInfo::Map { key, value, .. } => {
+            format!(
+                "Hash{{{} => {}}}",
+                map_type_to_ruby(key),
+                map_type_to_ruby(value)
+            )
+        }
+        TypeInfo::Set { element, .. } => {
+            format!("Set<{}>", map_type_to_ruby(element))
+        }
+        TypeInfo::Tuple(elements) => {
+            let element_types: Vec<String> = elements.iter().map(map_type_to_ruby).collect();
+            format!("Array({})", element_types.join(", "))
+        }
+        TypeInfo::Boxed(inner) => map_type_to_ruby(inner),
+        // Zero-padded UTF-8 bytes
+        TypeInfo::FixedString(_) => "Array<Integer>".to_string(),
         TypeInfo::Generic(param_name) => param_name.clone(),
-        TypeInfo::UserDefined(type_name) => type_name.clone(),
+        TypeInfo::UserDefined(type_name)
+        | TypeInfo::Instance {
+            name: type_name, ..
+        } => type_name.clone(),
     }
 }
 
@@ -552,14 +727,33 @@ fn map_type_to_borsh(type_info: &TypeInfo) -> String {
             let element_borsh = map_type_to_borsh(element);
             format!("[{}, {}]", element_borsh, size)
         }
+        TypeInfo::FixedString(size) => format!("[:u8, {}]", size),
         TypeInfo::Option(inner) => {
             let inner_borsh = map_type_to_borsh(inner);
             format!("[:option, {}]", inner_borsh)
         }
+        TypeInfo::Map { key, value, .. } => {
+            format!(
+                "[:map, {}, {}]",
+                map_type_to_borsh(key),
+                map_type_to_borsh(value)
+            )
+        }
+        TypeInfo::Set { element, .. } => {
+            format!("[:set, {}]", map_type_to_borsh(element))
+        }
+        TypeInfo::Tuple(elements) => {
+            let element_borsh: Vec<String> = elements.iter().map(map_type_to_borsh).collect();
+            format!("[:tuple, {}]", element_borsh.join(", "))
+        }
+        TypeInfo::Boxed(inner) => map_type_to_borsh(inner),
         TypeInfo::Generic(param_name) => {
             format!("# Generic: {}", param_name)
         }
-        TypeInfo::UserDefined(type_name) => {
+        TypeInfo::UserDefined(type_name)
+        | TypeInfo::Instance {
+            name: type_name, ..
+        } => {
             format!("{}::SCHEMA", type_name)
         }
     }
@@ -579,24 +773,20 @@ mod tests {
                 FieldDefinition {
                     name: "id".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "name".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -616,30 +806,23 @@ mod tests {
                 FieldDefinition {
                     name: "wallet".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "balance".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -659,14 +842,14 @@ mod tests {
                 name: "email".to_string(),
                 type_info: TypeInfo::Option(Box::new(TypeInfo::Primitive("String".to_string()))),
                 optional: true,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -681,21 +864,16 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "members".to_string(),
                 type_info: TypeInfo::Array(Box::new(TypeInfo::Primitive("u64".to_string()))),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -714,6 +892,8 @@ mod tests {
                 visibility: Visibility::Public,
 
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
             TypeDefinition::Struct(StructDefinition {
                 name: "Post".to_string(),
@@ -723,6 +903,8 @@ mod tests {
                 visibility: Visibility::Public,
 
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
         ];
 
@@ -741,24 +923,31 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Finished".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -777,10 +966,15 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Started".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Tuple {
                     name: "PlayerJoined".to_string(),
                     types: vec![TypeInfo::Primitive("PublicKey".to_string())],
+                    docs: vec![],
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Struct {
                     name: "ScoreUpdate".to_string(),
@@ -788,32 +982,26 @@ mod tests {
                         FieldDefinition {
                             name: "player".to_string(),
                             type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                         FieldDefinition {
                             name: "score".to_string(),
                             type_info: TypeInfo::Primitive("u64".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                     ],
+                    docs: vec![],
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -871,24 +1059,21 @@ mod tests {
                 FieldDefinition {
                     name: "balance".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "oldField".to_string(),
                     type_info: TypeInfo::Primitive("u32".to_string()),
-                    optional: false,
                     deprecated: Some("Use new_field instead".to_string()),
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -910,4 +1095,76 @@ mod tests {
         assert_eq!(to_screaming_snake_case("HelloWorld"), "HELLO_WORLD");
         assert_eq!(to_screaming_snake_case("Active"), "ACTIVE");
     }
+
+    #[test]
+    fn generates_validate_method_for_constraints() {
+        let source = r#"
+            #[solana]
+            struct Listing {
+                #[range(0..=10000)]
+                fee_bps: u16,
+                #[range(-5..5)]
+                delta: Option<i32>,
+                #[pattern("^[A-Z]{1,10}$")]
+                symbol: String,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code
+            .contains("  # @raise [ArgumentError] if a constraint is violated\n  def validate\n"));
+        assert!(code.contains(
+            "    raise ArgumentError, 'fee_bps must be at most 10000' if @fee_bps > 10000\n"
+        ));
+        assert!(code.contains("if !@delta.nil? && !(-5..4).cover?(@delta)\n"));
+        assert!(code.contains("if !Regexp.new('^[A-Z]{1,10}$').match?(@symbol)\n"));
+    }
+
+    #[test]
+    fn initializes_missing_options_with_field_defaults() {
+        let source = r#"
+            #[derive(Default)]
+            struct Config {
+                #[default(100)]
+                fee_bps: u16,
+                #[default("USDC")]
+                symbol: String,
+                #[default(true)]
+                active: bool,
+                #[default(0.5)]
+                ratio: f64,
+                count: u64,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("    @fee_bps = opts.fetch(:fee_bps, 100)\n"));
+        assert!(code.contains("    @symbol = opts.fetch(:symbol, 'USDC')\n"));
+        assert!(code.contains("    @active = opts.fetch(:active, true)\n"));
+        assert!(code.contains("    @ratio = opts.fetch(:ratio, 0.5)\n"));
+        assert!(code.contains("    @count = opts[:count]\n"));
+    }
+
+    #[test]
+    fn test_ruby_renames() {
+        let source = r#"
+            #[rename(rb = "TradeListing")]
+            struct Listing {
+                #[rename(rb = "feeBps")]
+                fee_bps: u16,
+                ownerKey: PublicKey,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        // Only an explicit rb name escapes Ruby's snake_case attributes
+        let code = generate_module(&ir);
+        assert!(code.contains("class TradeListing\n"));
+        assert!(code.contains("  attr_accessor :feeBps, :owner_key\n"));
+    }
 }
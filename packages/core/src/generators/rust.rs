@@ -25,6 +25,7 @@
 //! | `#[account]` struct | `anchor_lang::prelude::*` | None | Anchor provides derives |
 //! | Non-account in Anchor module | `anchor_lang::prelude::*` | `AnchorSerialize, AnchorDeserialize` | Module-level Anchor usage |
 //! | Pure Borsh struct | `borsh::{BorshSerialize, BorshDeserialize}` | `BorshSerialize, BorshDeserialize` | Standalone Borsh |
+//! | `#[zero_copy]` struct | Anchor prelude, or none | None in Anchor, else `Clone, Copy, bytemuck::Pod, bytemuck::Zeroable` | `#[account(zero_copy)]` / `#[zero_copy]` / `#[repr(C)]` |
 //!
 //! ## Type Mapping
 //!
@@ -59,11 +60,15 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+use crate::anchor::{account_meta_flags, instruction_discriminator, instruction_name};
+use crate::generators::module_tree::{relative_path, split_modules, ModuleFile, ModuleImport};
 use crate::ir::{
-    EnumDefinition, EnumVariantDefinition, StructDefinition, TypeAliasDefinition, TypeDefinition,
-    TypeInfo, Visibility,
+    ConstDefinition, Constraint, DefaultValue, EnumDefinition, EnumVariantDefinition,
+    FieldDefinition, StructDefinition, TypeAliasDefinition, TypeDefinition, TypeInfo, Visibility,
 };
-use std::collections::HashSet;
+use crate::naming::Case;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
 /// Convert visibility to Rust keyword prefix
 fn visibility_prefix(vis: Visibility) -> &'static str {
@@ -73,15 +78,329 @@ fn visibility_prefix(vis: Visibility) -> &'static str {
     }
 }
 
+/// Render the layout attribute of a `#[zero_copy]` struct
+///
+/// Anchor's `#[account(zero_copy)]` and `#[zero_copy]` add `#[repr(C)]` and the
+/// bytemuck derives themselves; outside Anchor they are written out.
+fn zero_copy_attribute(struct_def: &StructDefinition, use_anchor: bool) -> &'static str {
+    if struct_def.metadata.solana
+        && struct_def
+            .metadata
+            .attributes
+            .contains(&"account".to_string())
+    {
+        "#[account(zero_copy)]\n"
+    } else if use_anchor {
+        "#[zero_copy]\n"
+    } else {
+        "#[repr(C)]\n"
+    }
+}
+
+/// Render Anchor's `#[error_code]` attribute, keeping any custom code offset
+fn error_code_attribute(enum_def: &EnumDefinition) -> String {
+    match enum_def.metadata.error_code_offset {
+        Some(offset) => format!("#[error_code(offset = {})]\n", offset),
+        None => "#[error_code]\n".to_string(),
+    }
+}
+
+/// Render `#[repr(uN)]` for enums with a chosen tag width or explicit discriminants
+fn enum_repr_attribute(enum_def: &EnumDefinition) -> Option<String> {
+    let explicit = enum_def.variants.iter().any(|v| v.discriminant().is_some());
+    if enum_def.metadata.repr.is_none() && !explicit {
+        return None;
+    }
+    Some(format!("#[repr({})]\n", enum_def.repr().name()))
+}
+
+/// Render ` = N` for a variant with an explicit discriminant
+fn discriminant_suffix(variant: &EnumVariantDefinition) -> String {
+    variant
+        .discriminant()
+        .map(|d| format!(" = {}", d))
+        .unwrap_or_default()
+}
+
+/// Check if a Solana enum needs hand-written Borsh impls
+///
+/// Borsh derives always write the variant index as a `u8`, so enums with a
+/// wider tag or non-positional discriminants serialize the tag explicitly.
+fn needs_manual_borsh(enum_def: &EnumDefinition) -> bool {
+    enum_def.metadata.solana && enum_def.has_custom_discriminants()
+}
+
+/// Generate Borsh (or Anchor) serialize/deserialize impls that honor the
+/// enum's discriminants and tag width
+fn generate_enum_borsh_impls(enum_def: &EnumDefinition, use_anchor: bool) -> String {
+    let (ser_trait, de_trait) = if use_anchor {
+        ("AnchorSerialize", "AnchorDeserialize")
+    } else {
+        ("BorshSerialize", "BorshDeserialize")
+    };
+    let tag_type = enum_def.repr().name();
+    let name = &enum_def.name;
+    let impl_generics = |bound: &str| {
+        if enum_def.generic_params.is_empty() {
+            return String::new();
+        }
+        let params: Vec<String> = enum_def
+            .generic_params
+            .iter()
+            .map(|p| format!("{}: {}", p, bound))
+            .collect();
+        format!("<{}>", params.join(", "))
+    };
+    let type_generics = if enum_def.generic_params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", enum_def.generic_params.join(", "))
+    };
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "impl{} {} for {}{} {{\n",
+        impl_generics(ser_trait),
+        ser_trait,
+        name,
+        type_generics
+    ));
+    output.push_str(
+        "    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {\n",
+    );
+    output.push_str("        match self {\n");
+    for (variant, tag) in enum_def.variants.iter().zip(enum_def.discriminants()) {
+        let write_tag = format!("{}{}.serialize(writer)", tag, tag_type);
+        let (pattern, bindings) = match variant {
+            EnumVariantDefinition::Unit { name: v, .. } => (format!("{}::{}", name, v), vec![]),
+            EnumVariantDefinition::Tuple { name: v, types, .. } => {
+                let bindings: Vec<String> = (0..types.len()).map(|i| format!("f{}", i)).collect();
+                (
+                    format!("{}::{}({})", name, v, bindings.join(", ")),
+                    bindings,
+                )
+            }
+            EnumVariantDefinition::Struct {
+                name: v, fields, ..
+            } => {
+                let bindings: Vec<String> = fields.iter().map(|f| f.name.clone()).collect();
+                (
+                    format!("{}::{} {{ {} }}", name, v, bindings.join(", ")),
+                    bindings,
+                )
+            }
+        };
+        if bindings.is_empty() {
+            output.push_str(&format!("            {} => {},\n", pattern, write_tag));
+        } else {
+            output.push_str(&format!("            {} => {{\n", pattern));
+            output.push_str(&format!("                {}?;\n", write_tag));
+            for binding in &bindings[..bindings.len() - 1] {
+                output.push_str(&format!(
+                    "                {}.serialize(writer)?;\n",
+                    binding
+                ));
+            }
+            output.push_str(&format!(
+                "                {}.serialize(writer)\n",
+                bindings[bindings.len() - 1]
+            ));
+            output.push_str("            }\n");
+        }
+    }
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n\n");
+
+    output.push_str(&format!(
+        "impl{} {} for {}{} {{\n",
+        impl_generics(de_trait),
+        de_trait,
+        name,
+        type_generics
+    ));
+    output.push_str(
+        "    fn deserialize_reader<R: std::io::Read>(reader: &mut R) -> std::io::Result<Self> {\n",
+    );
+    output.push_str(&format!(
+        "        let tag: {} = {}::deserialize_reader(reader)?;\n",
+        tag_type, de_trait
+    ));
+    output.push_str("        match tag {\n");
+    let read = format!("{}::deserialize_reader(reader)?", de_trait);
+    for (variant, tag) in enum_def.variants.iter().zip(enum_def.discriminants()) {
+        match variant {
+            EnumVariantDefinition::Unit { name: v, .. } => {
+                output.push_str(&format!("            {} => Ok({}::{}),\n", tag, name, v));
+            }
+            EnumVariantDefinition::Tuple { name: v, types, .. } => {
+                output.push_str(&format!("            {} => Ok({}::{}(\n", tag, name, v));
+                for _ in types {
+                    output.push_str(&format!("                {},\n", read));
+                }
+                output.push_str("            )),\n");
+            }
+            EnumVariantDefinition::Struct {
+                name: v, fields, ..
+            } => {
+                output.push_str(&format!("            {} => Ok({}::{} {{\n", tag, name, v));
+                for field in fields {
+                    output.push_str(&format!("                {}: {},\n", field.name, read));
+                }
+                output.push_str("            }),\n");
+            }
+        }
+    }
+    output.push_str("            _ => Err(std::io::Error::new(\n");
+    output.push_str("                std::io::ErrorKind::InvalidData,\n");
+    output.push_str(&format!(
+        "                format!(\"Invalid {} discriminant: {{}}\", tag),\n",
+        name
+    ));
+    output.push_str("            )),\n");
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
+    output
+}
+
+/// Generate a `#[flags]` enum as a bitflags-style newtype over its integer
+///
+/// Each variant becomes an associated constant, and the newtype serializes
+/// as the packed integer.
+fn generate_flags(enum_def: &EnumDefinition) -> String {
+    let name = &enum_def.name;
+    let bits = enum_def.repr().name();
+    let mut output = String::new();
+
+    output.push_str(&format!(
+        "{}struct {}({});\n\n",
+        visibility_prefix(enum_def.visibility),
+        name,
+        bits
+    ));
+
+    output.push_str(&format!("impl {} {{\n", name));
+    for (variant, bit) in enum_def.variants.iter().zip(enum_def.discriminants()) {
+        push_doc_comments(&mut output, variant.docs(), "    ");
+        output.push_str(&format!(
+            "    pub const {}: Self = Self(1 << {});\n",
+            Case::ScreamingSnake.apply(variant.name()),
+            bit.trailing_zeros()
+        ));
+    }
+    output.push('\n');
+    output.push_str("    /// No flags set\n");
+    output.push_str("    pub const fn empty() -> Self {\n        Self(0)\n    }\n\n");
+    output.push_str("    /// Every flag set\n");
+    output.push_str(&format!(
+        "    pub const fn all() -> Self {{\n        Self({:#x})\n    }}\n\n",
+        enum_def.flags_mask()
+    ));
+    output.push_str("    /// The packed integer\n");
+    output.push_str(&format!(
+        "    pub const fn bits(&self) -> {} {{\n        self.0\n    }}\n\n",
+        bits
+    ));
+    output.push_str("    /// Flags from a packed integer, or `None` if it sets an unknown bit\n");
+    output.push_str(&format!(
+        "    pub const fn from_bits(bits: {}) -> Option<Self> {{\n",
+        bits
+    ));
+    output.push_str("        if bits & !Self::all().0 == 0 {\n");
+    output.push_str("            Some(Self(bits))\n");
+    output.push_str("        } else {\n");
+    output.push_str("            None\n");
+    output.push_str("        }\n");
+    output.push_str("    }\n\n");
+    output.push_str("    /// Check if no flags are set\n");
+    output.push_str("    pub const fn is_empty(&self) -> bool {\n        self.0 == 0\n    }\n\n");
+    output.push_str("    /// Check if every flag in `other` is set\n");
+    output.push_str("    pub const fn contains(&self, other: Self) -> bool {\n");
+    output.push_str("        self.0 & other.0 == other.0\n");
+    output.push_str("    }\n\n");
+    output.push_str("    /// Set the flags in `other`\n");
+    output.push_str(
+        "    pub fn insert(&mut self, other: Self) {\n        self.0 |= other.0;\n    }\n\n",
+    );
+    output.push_str("    /// Clear the flags in `other`\n");
+    output.push_str(
+        "    pub fn remove(&mut self, other: Self) {\n        self.0 &= !other.0;\n    }\n",
+    );
+    output.push_str("}\n");
+
+    for (op, method, expr) in [
+        ("BitOr", "bitor", "self.0 | rhs.0"),
+        ("BitAnd", "bitand", "self.0 & rhs.0"),
+    ] {
+        output.push_str(&format!("\nimpl std::ops::{} for {} {{\n", op, name));
+        output.push_str("    type Output = Self;\n\n");
+        output.push_str(&format!(
+            "    fn {}(self, rhs: Self) -> Self {{\n        Self({})\n    }}\n",
+            method, expr
+        ));
+        output.push_str("}\n");
+    }
+    output.push_str(&format!("\nimpl std::ops::BitOrAssign for {} {{\n", name));
+    output
+        .push_str("    fn bitor_assign(&mut self, rhs: Self) {\n        self.0 |= rhs.0;\n    }\n");
+    output.push_str("}\n");
+
+    output
+}
+
+/// Imports used by generated instruction builders
+const INSTRUCTION_IMPORTS: &str = "solana_program::instruction::{AccountMeta, Instruction}";
+
+/// Emit `///` doc comment lines at the given indentation
+fn push_doc_comments(output: &mut String, docs: &[String], indent: &str) {
+    for line in docs {
+        if line.is_empty() {
+            output.push_str(&format!("{}///\n", indent));
+        } else {
+            output.push_str(&format!("{}/// {}\n", indent, line));
+        }
+    }
+}
+
 /// Generate Rust code from a type definition
 pub fn generate(type_def: &TypeDefinition) -> String {
     match type_def {
         TypeDefinition::Struct(struct_def) => generate_struct(struct_def),
         TypeDefinition::Enum(enum_def) => generate_enum(enum_def),
         TypeDefinition::TypeAlias(alias_def) => generate_type_alias(alias_def),
+        TypeDefinition::Const(const_def) => generate_const(const_def),
     }
 }
 
+/// Generate Rust code from a constant definition
+fn generate_const(const_def: &ConstDefinition) -> String {
+    let mut output = String::new();
+
+    // Add file header
+    output.push_str("// Auto-generated by LUMOS\n");
+    output.push_str("// DO NOT EDIT - Changes will be overwritten\n\n");
+
+    output.push_str(&generate_const_definition(const_def));
+
+    output
+}
+
+/// Generate a Rust `const` item with its declared integer type
+fn generate_const_definition(const_def: &ConstDefinition) -> String {
+    let mut output = String::new();
+    push_doc_comments(&mut output, &const_def.docs, "");
+    output.push_str(&format!(
+        "{}const {}: {} = {};\n",
+        visibility_prefix(const_def.visibility),
+        const_def.name,
+        const_def.type_name,
+        const_def.value
+    ));
+    output
+}
+
 /// Generate Rust code from a type alias definition
 fn generate_type_alias(alias_def: &TypeAliasDefinition) -> String {
     let mut output = String::new();
@@ -91,11 +410,24 @@ fn generate_type_alias(alias_def: &TypeAliasDefinition) -> String {
     output.push_str("// DO NOT EDIT - Changes will be overwritten\n\n");
 
     // Collect required imports for the target type
-    let mut needs_publickey = false;
-    check_needs_solana_types(&alias_def.target, &mut needs_publickey);
+    let mut imports = HashSet::new();
+    collect_imports_from_type(&alias_def.target, &mut imports);
+    if alias_def.newtype {
+        imports.insert("borsh::{BorshSerialize, BorshDeserialize}".to_string());
+    }
+
+    if !imports.is_empty() {
+        let mut sorted_imports: Vec<_> = imports.into_iter().collect();
+        sorted_imports.sort();
+        for import in sorted_imports {
+            output.push_str(&format!("use {};\n", import));
+        }
+        output.push('\n');
+    }
 
-    if needs_publickey {
-        output.push_str("use solana_program::pubkey::Pubkey;\n\n");
+    if alias_def.newtype {
+        output.push_str(&generate_newtype(alias_def, false, false));
+        return output;
     }
 
     // Generate the type alias with visibility
@@ -109,6 +441,87 @@ fn generate_type_alias(alias_def: &TypeAliasDefinition) -> String {
     output
 }
 
+/// Generate a `#[newtype]` alias as a single-field tuple struct
+///
+/// Borsh encodes a one-field tuple struct exactly like the field, so the
+/// newtype keeps the wire format of its target. `From` and `Deref` let
+/// callers move between the two without reaching for `.0`. A newtype
+/// `used_as_key` derives `Eq`, `Hash` and `Ord` whatever its target.
+fn generate_newtype(
+    alias_def: &TypeAliasDefinition,
+    use_anchor: bool,
+    used_as_key: bool,
+) -> String {
+    let mut output = String::new();
+    let name = &alias_def.name;
+    let target = map_type_to_rust(&alias_def.target);
+
+    let mut derives = vec!["Debug", "Clone", "PartialEq"];
+    if use_anchor {
+        derives.extend(["AnchorSerialize", "AnchorDeserialize"]);
+    } else {
+        derives.extend(["BorshSerialize", "BorshDeserialize"]);
+    }
+    if is_ordered_key(&alias_def.target) {
+        if target != "String" {
+            derives.push("Copy");
+        }
+        derives.extend(["Eq", "Hash", "PartialOrd", "Ord"]);
+    } else if used_as_key {
+        derives.extend(["Eq", "Hash", "PartialOrd", "Ord"]);
+    }
+
+    output.push_str(&format!("#[derive({})]\n", derives.join(", ")));
+    output.push_str(&format!(
+        "{}struct {}(pub {});\n\n",
+        visibility_prefix(alias_def.visibility),
+        name,
+        target
+    ));
+    output.push_str(&format!(
+        "impl From<{target}> for {name} {{\n    fn from(value: {target}) -> Self {{\n        Self(value)\n    }}\n}}\n\n"
+    ));
+    output.push_str(&format!(
+        "impl From<{name}> for {target} {{\n    fn from(value: {name}) -> Self {{\n        value.0\n    }}\n}}\n\n"
+    ));
+    output.push_str(&format!(
+        "impl std::ops::Deref for {name} {{\n    type Target = {target};\n\n    fn deref(&self) -> &{target} {{\n        &self.0\n    }}\n}}\n"
+    ));
+
+    output
+}
+
+/// Whether a newtype over this type can also derive `Eq`, `Hash` and `Ord`
+///
+/// Integers, booleans, strings and keys qualify, as do fixed strings and
+/// fixed arrays of integers or keys; floats and collections do not.
+fn is_ordered_key(type_info: &TypeInfo) -> bool {
+    match type_info {
+        TypeInfo::Primitive(p) => matches!(
+            p.as_str(),
+            "u8" | "u16"
+                | "u32"
+                | "u64"
+                | "u128"
+                | "i8"
+                | "i16"
+                | "i32"
+                | "i64"
+                | "i128"
+                | "bool"
+                | "String"
+                | "Pubkey"
+                | "PublicKey"
+                | "Signature"
+        ),
+        TypeInfo::FixedString(_) => true,
+        TypeInfo::FixedArray { element, .. } => {
+            is_ordered_key(element) && map_type_to_rust(element) != "String"
+        }
+        _ => false,
+    }
+}
+
 /// Generate Rust code from a struct definition
 fn generate_struct(struct_def: &StructDefinition) -> String {
     let mut output = String::new();
@@ -117,12 +530,25 @@ fn generate_struct(struct_def: &StructDefinition) -> String {
     output.push_str("// Auto-generated by LUMOS\n");
     output.push_str("// DO NOT EDIT - Changes will be overwritten\n\n");
 
-    // Determine if this struct uses Anchor (#[account])
-    let use_anchor = struct_def.metadata.solana
+    // Instruction contexts become client-side builders
+    if struct_def.metadata.is_instruction {
+        let mut imports: Vec<String> = collect_struct_imports(struct_def).into_iter().collect();
+        imports.sort();
+        for import in imports {
+            output.push_str(&format!("use {};\n", import));
+        }
+        output.push('\n');
+        output.push_str(&generate_instruction_builder(struct_def, false));
+        return output;
+    }
+
+    // Determine if this struct uses Anchor (#[account] or #[event])
+    let use_anchor = (struct_def.metadata.solana
         && struct_def
             .metadata
             .attributes
-            .contains(&"account".to_string());
+            .contains(&"account".to_string()))
+        || struct_def.is_event();
 
     // Collect required imports
     let imports = collect_struct_imports(struct_def);
@@ -142,6 +568,9 @@ fn generate_struct(struct_def: &StructDefinition) -> String {
         ));
     }
 
+    // Type-level doc comments
+    push_doc_comments(&mut output, &struct_def.metadata.docs, "");
+
     // Generate derives using context-aware function
     let derives = generate_struct_derives_with_context(struct_def, use_anchor);
     if !derives.is_empty() {
@@ -149,7 +578,11 @@ fn generate_struct(struct_def: &StructDefinition) -> String {
     }
 
     // Add Solana-specific attributes
-    if use_anchor {
+    if struct_def.is_zero_copy() {
+        output.push_str(zero_copy_attribute(struct_def, use_anchor));
+    } else if struct_def.is_event() {
+        output.push_str("#[event]\n");
+    } else if use_anchor {
         output.push_str("#[account]\n");
     }
 
@@ -168,11 +601,15 @@ fn generate_struct(struct_def: &StructDefinition) -> String {
 
     // Generate fields
     for field in &struct_def.fields {
+        push_doc_comments(&mut output, &field.docs, "    ");
         let rust_type = map_type_to_rust(&field.type_info);
         output.push_str(&format!("    pub {}: {},\n", field.name, rust_type));
     }
 
     output.push_str("}\n");
+    output.push_str(&generate_default_impl(struct_def));
+    output.push_str(&generate_validate_impl(struct_def));
+    output.push_str(&generate_fixed_string_impl(struct_def));
 
     output
 }
@@ -185,12 +622,13 @@ fn generate_enum(enum_def: &EnumDefinition) -> String {
     output.push_str("// Auto-generated by LUMOS\n");
     output.push_str("// DO NOT EDIT - Changes will be overwritten\n\n");
 
-    // Determine if this enum uses Anchor (#[account])
-    let use_anchor = enum_def.metadata.solana
+    // Determine if this enum uses Anchor (#[account] or #[error_code])
+    let use_anchor = (enum_def.metadata.solana
         && enum_def
             .metadata
             .attributes
-            .contains(&"account".to_string());
+            .contains(&"account".to_string()))
+        || enum_def.is_error_code();
 
     // Collect required imports
     let imports = collect_enum_imports(enum_def);
@@ -210,14 +648,24 @@ fn generate_enum(enum_def: &EnumDefinition) -> String {
         ));
     }
 
+    // Type-level doc comments
+    push_doc_comments(&mut output, &enum_def.metadata.docs, "");
+
     // Generate derives using context-aware function
     let derives = generate_enum_derives_with_context(enum_def, use_anchor);
     if !derives.is_empty() {
         output.push_str(&format!("#[derive({})]\n", derives.join(", ")));
     }
 
+    if enum_def.is_flags() {
+        output.push_str(&generate_flags(enum_def));
+        return output;
+    }
+
     // Add Solana-specific attributes
-    if use_anchor {
+    if enum_def.is_error_code() {
+        output.push_str(&error_code_attribute(enum_def));
+    } else if use_anchor {
         output.push_str("#[account]\n");
     }
 
@@ -227,32 +675,50 @@ fn generate_enum(enum_def: &EnumDefinition) -> String {
     } else {
         format!("{}<{}>", enum_def.name, enum_def.generic_params.join(", "))
     };
+    if let Some(repr) = enum_repr_attribute(enum_def) {
+        output.push_str(&repr);
+    }
     let vis = visibility_prefix(enum_def.visibility);
     output.push_str(&format!("{}enum {} {{\n", vis, enum_name_with_generics));
 
     // Generate variants
     for variant in &enum_def.variants {
+        push_doc_comments(&mut output, variant.docs(), "    ");
         match variant {
-            EnumVariantDefinition::Unit { name } => {
-                output.push_str(&format!("    {},\n", name));
+            EnumVariantDefinition::Unit { name, msg, .. } => {
+                if let Some(msg) = msg {
+                    output.push_str(&format!("    #[msg({:?})]\n", msg));
+                }
+                output.push_str(&format!("    {}{},\n", name, discriminant_suffix(variant)));
             }
-            EnumVariantDefinition::Tuple { name, types } => {
+            EnumVariantDefinition::Tuple { name, types, .. } => {
                 let type_strs: Vec<String> = types.iter().map(map_type_to_rust).collect();
-                output.push_str(&format!("    {}({}),\n", name, type_strs.join(", ")));
+                output.push_str(&format!(
+                    "    {}({}){},\n",
+                    name,
+                    type_strs.join(", "),
+                    discriminant_suffix(variant)
+                ));
             }
-            EnumVariantDefinition::Struct { name, fields } => {
+            EnumVariantDefinition::Struct { name, fields, .. } => {
                 output.push_str(&format!("    {} {{\n", name));
                 for field in fields {
+                    push_doc_comments(&mut output, &field.docs, "        ");
                     let rust_type = map_type_to_rust(&field.type_info);
                     output.push_str(&format!("        {}: {},\n", field.name, rust_type));
                 }
-                output.push_str("    },\n");
+                output.push_str(&format!("    }}{},\n", discriminant_suffix(variant)));
             }
         }
     }
 
     output.push_str("}\n");
 
+    if needs_manual_borsh(enum_def) {
+        output.push('\n');
+        output.push_str(&generate_enum_borsh_impls(enum_def, use_anchor));
+    }
+
     output
 }
 
@@ -372,6 +838,7 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
             TypeDefinition::Struct(s) => STRUCT_BASE + (s.fields.len() * FIELD_SIZE),
             TypeDefinition::Enum(e) => ENUM_BASE + (e.variants.len() * VARIANT_SIZE),
             TypeDefinition::TypeAlias(_) => 100, // Type aliases are small (~50-150 chars)
+            TypeDefinition::Const(_) => 60,
         };
     }
 
@@ -379,6 +846,165 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
 }
 
 pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
+    render_module(
+        type_defs,
+        uses_anchor(type_defs),
+        &collect_key_types(type_defs),
+        &[],
+        &[],
+    )
+}
+
+/// Generate a `mod.rs` file per schema module, mirroring the module tree
+///
+/// Each file declares its child modules with `pub mod` and brings types from
+/// other modules into scope with relative `use` paths
+/// (`use super::models::User;`), or `pub use` for the types the schema
+/// module re-exports. If any module uses Anchor, every module
+/// does, so derives stay consistent across the tree.
+pub fn generate_module_tree(type_defs: &[TypeDefinition]) -> Vec<ModuleFile> {
+    let use_anchor = uses_anchor(type_defs);
+    let key_types = collect_key_types(type_defs);
+
+    split_modules(type_defs)
+        .into_iter()
+        .map(|module| {
+            let children: Vec<String> = module
+                .children
+                .iter()
+                .map(|child| format!("pub mod {};", child))
+                .collect();
+            // A `pub use` also brings the type into scope, so it is not imported twice
+            let uses: Vec<String> = module
+                .reexports
+                .iter()
+                .map(|reexport| format!("pub {}", use_statement(&module.path, reexport)))
+                .chain(
+                    module
+                        .imports
+                        .iter()
+                        .filter(|import| !module.reexports.contains(import))
+                        .map(|import| use_statement(&module.path, import)),
+                )
+                .collect();
+
+            ModuleFile {
+                path: module.path.iter().collect::<PathBuf>().join("mod.rs"),
+                content: render_module(&module.type_defs, use_anchor, &key_types, &children, &uses),
+            }
+        })
+        .collect()
+}
+
+/// `use` of a type from another module, by a path relative to module `from`
+/// (`use super::models::User;`), through a re-export if it has one nearer
+fn use_statement(from: &[String], import: &ModuleImport) -> String {
+    let (module, name) = match &import.via {
+        Some(reexport) => (&reexport.module_path, &reexport.name),
+        None => (&import.module, &import.name),
+    };
+    let (up, down) = relative_path(from, module);
+    let prefix = if up == 0 {
+        vec!["self"]
+    } else {
+        vec!["super"; up]
+    };
+    let path = prefix
+        .into_iter()
+        .chain(down.iter().map(String::as_str))
+        .chain(std::iter::once(name.as_str()))
+        .collect::<Vec<_>>()
+        .join("::");
+    if &import.local == name {
+        format!("use {};", path)
+    } else {
+        format!("use {} as {};", path, import.local)
+    }
+}
+
+/// Whether any struct or enum uses `#[account]` (or an Anchor `#[event]` / `#[error_code]`)
+fn uses_anchor(type_defs: &[TypeDefinition]) -> bool {
+    type_defs.iter().any(|t| match t {
+        TypeDefinition::Struct(s) => {
+            (s.metadata.solana && s.metadata.attributes.contains(&"account".to_string()))
+                || s.is_event()
+        }
+        TypeDefinition::Enum(e) => {
+            (e.metadata.solana && e.metadata.attributes.contains(&"account".to_string()))
+                || e.is_error_code()
+        }
+        TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => false, // No attributes
+    })
+}
+
+/// Derives of the types in [`collect_key_types`], which `HashMap`/`BTreeMap`
+/// keys and Borsh's sorted encoding of maps and sets require
+const KEY_DERIVES: [&str; 5] = ["PartialEq", "Eq", "Hash", "PartialOrd", "Ord"];
+
+/// Names of the structs, enums and newtypes used in map keys or set elements
+///
+/// Types stored inside a key type are included, since deriving `Hash` or
+/// `Ord` requires them of every field. Generic types are named without
+/// their arguments.
+fn collect_key_types(type_defs: &[TypeDefinition]) -> HashSet<String> {
+    let definitions: HashMap<&str, &TypeDefinition> =
+        type_defs.iter().map(|t| (t.name(), t)).collect();
+
+    let mut pending = Vec::new();
+    let named_types = |type_info: &TypeInfo, names: &mut Vec<String>| {
+        type_info.walk(&mut |t| {
+            if let TypeInfo::UserDefined(name) | TypeInfo::Instance { name, .. } = t {
+                names.push(name.clone());
+            }
+        })
+    };
+    for type_def in type_defs {
+        for type_info in type_def.field_types() {
+            type_info.walk(&mut |t| match t {
+                TypeInfo::Map { key, .. } => named_types(key, &mut pending),
+                TypeInfo::Set { element, .. } => named_types(element, &mut pending),
+                _ => {}
+            });
+        }
+    }
+
+    let mut key_types = HashSet::new();
+    while let Some(name) = pending.pop() {
+        if !key_types.insert(name.clone()) {
+            continue;
+        }
+        if let Some(definition) = definitions.get(name.as_str()) {
+            for type_info in definition.field_types() {
+                named_types(type_info, &mut pending);
+            }
+        }
+    }
+    key_types
+}
+
+/// A copy of a struct or enum with [`KEY_DERIVES`] added to its custom derives
+fn with_key_derives(type_def: &TypeDefinition) -> TypeDefinition {
+    let mut type_def = type_def.clone();
+    let custom_derives = match &mut type_def {
+        TypeDefinition::Struct(s) => &mut s.metadata.custom_derives,
+        TypeDefinition::Enum(e) => &mut e.metadata.custom_derives,
+        TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => return type_def,
+    };
+    custom_derives.extend(KEY_DERIVES.iter().map(|d| d.to_string()));
+    type_def
+}
+
+/// Generate a file of `type_defs`, after any `pub mod` declarations in
+/// `children` and with `uses` added to its imports
+///
+/// Types named in `key_types` also derive [`KEY_DERIVES`].
+fn render_module(
+    type_defs: &[TypeDefinition],
+    has_account_attr: bool,
+    key_types: &HashSet<String>,
+    children: &[String],
+    uses: &[String],
+) -> String {
     // Estimate output size to reduce allocations for large schemas
     let estimated_capacity = estimate_output_size(type_defs);
     let mut output = String::with_capacity(estimated_capacity);
@@ -387,22 +1013,20 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
     output.push_str("// Auto-generated by LUMOS\n");
     output.push_str("// DO NOT EDIT - Changes will be overwritten\n\n");
 
-    // Check if ANY struct or enum uses #[account]
-    let has_account_attr = type_defs.iter().any(|t| match t {
-        TypeDefinition::Struct(s) => {
-            s.metadata.solana && s.metadata.attributes.contains(&"account".to_string())
-        }
-        TypeDefinition::Enum(e) => {
-            e.metadata.solana && e.metadata.attributes.contains(&"account".to_string())
+    // Child modules of a module tree
+    if !children.is_empty() {
+        for child in children {
+            output.push_str(child);
+            output.push('\n');
         }
-        TypeDefinition::TypeAlias(_) => false, // Type aliases don't have attributes
-    });
+        output.push('\n');
+    }
 
     // Collect all imports needed
     let mut all_imports = HashSet::new();
 
-    // Determine which import style to use
-    if has_account_attr {
+    // Determine which import style to use (a module with no types needs none)
+    if has_account_attr && !type_defs.is_empty() {
         // If any struct/enum uses #[account], use Anchor imports for everything
         all_imports.insert("anchor_lang::prelude::*".to_string());
     } else {
@@ -420,18 +1044,32 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
                 TypeDefinition::TypeAlias(a) => {
                     // Check if alias target needs imports
                     collect_imports_from_type(&a.target, &mut all_imports);
+                    if a.newtype {
+                        all_imports.insert("borsh::{BorshSerialize, BorshDeserialize}".to_string());
+                    }
                 }
+                TypeDefinition::Const(_) => {}
             }
         }
     }
 
-    // Check for Solana-specific types
+    // Check for Solana-specific types and std collections (Anchor's prelude covers neither)
     let mut needs_pubkey = false;
     for type_def in type_defs {
         match type_def {
+            TypeDefinition::Struct(s) if s.metadata.is_instruction => {
+                // Builders take account keys and serialize their arguments
+                needs_pubkey = true;
+                all_imports.insert(INSTRUCTION_IMPORTS.to_string());
+                for arg in &s.metadata.instruction_args {
+                    check_needs_solana_types(&arg.type_info, &mut needs_pubkey);
+                    collect_collection_imports(&arg.type_info, &mut all_imports);
+                }
+            }
             TypeDefinition::Struct(s) => {
                 for field in &s.fields {
                     check_needs_solana_types(&field.type_info, &mut needs_pubkey);
+                    collect_collection_imports(&field.type_info, &mut all_imports);
                 }
             }
             TypeDefinition::Enum(e) => {
@@ -441,11 +1079,13 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
                         EnumVariantDefinition::Tuple { types, .. } => {
                             for type_info in types {
                                 check_needs_solana_types(type_info, &mut needs_pubkey);
+                                collect_collection_imports(type_info, &mut all_imports);
                             }
                         }
                         EnumVariantDefinition::Struct { fields, .. } => {
                             for field in fields {
                                 check_needs_solana_types(&field.type_info, &mut needs_pubkey);
+                                collect_collection_imports(&field.type_info, &mut all_imports);
                             }
                         }
                     }
@@ -454,7 +1094,9 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
             TypeDefinition::TypeAlias(a) => {
                 // Check if the alias target type needs Solana imports
                 check_needs_solana_types(&a.target, &mut needs_pubkey);
+                collect_collection_imports(&a.target, &mut all_imports);
             }
+            TypeDefinition::Const(_) => {}
         }
     }
 
@@ -462,7 +1104,7 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
         all_imports.insert("solana_program::pubkey::Pubkey".to_string());
     }
 
-    // Write imports
+    // Write imports, then the types used from other modules of a module tree
     if !all_imports.is_empty() {
         let mut sorted_imports: Vec<_> = all_imports.into_iter().collect();
         sorted_imports.sort();
@@ -471,6 +1113,13 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
         }
         output.push('\n');
     }
+    if !uses.is_empty() {
+        for line in uses {
+            output.push_str(line);
+            output.push('\n');
+        }
+        output.push('\n');
+    }
 
     // Generate each type definition
     for (i, type_def) in type_defs.iter().enumerate() {
@@ -478,24 +1127,79 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
             output.push('\n');
         }
 
+        let used_as_key = key_types.contains(type_def.name());
+        let keyed;
+        let type_def = if used_as_key {
+            keyed = with_key_derives(type_def);
+            &keyed
+        } else {
+            type_def
+        };
+
         match type_def {
+            TypeDefinition::Struct(s) if s.metadata.is_instruction => {
+                output.push_str(&generate_instruction_builder(s, has_account_attr));
+            }
             TypeDefinition::Struct(s) => {
                 output.push_str(&generate_struct_with_context(s, has_account_attr));
             }
             TypeDefinition::Enum(e) => {
                 output.push_str(&generate_enum_with_context(e, has_account_attr));
             }
+            TypeDefinition::TypeAlias(a) if a.newtype => {
+                output.push_str(&generate_newtype(a, has_account_attr, used_as_key));
+            }
             TypeDefinition::TypeAlias(a) => {
                 // Generate type alias (header already generated individually)
                 let rust_type = map_type_to_rust(&a.target);
                 output.push_str(&format!("pub type {} = {};\n", a.name, rust_type));
             }
+            TypeDefinition::Const(c) => {
+                output.push_str(&generate_const_definition(c));
+            }
         }
     }
 
     output
 }
 
+/// Collect `std::collections` imports for any map/set types nested in a type
+fn collect_collection_imports(type_info: &TypeInfo, imports: &mut HashSet<String>) {
+    match type_info {
+        TypeInfo::Map {
+            key,
+            value,
+            ordered,
+        } => {
+            let collection = if *ordered { "BTreeMap" } else { "HashMap" };
+            imports.insert(format!("std::collections::{}", collection));
+            collect_collection_imports(key, imports);
+            collect_collection_imports(value, imports);
+        }
+        TypeInfo::Set { element, ordered } => {
+            let collection = if *ordered { "BTreeSet" } else { "HashSet" };
+            imports.insert(format!("std::collections::{}", collection));
+            collect_collection_imports(element, imports);
+        }
+        TypeInfo::Array(inner) | TypeInfo::Option(inner) => {
+            collect_collection_imports(inner, imports);
+        }
+        TypeInfo::FixedArray { element, .. } => {
+            collect_collection_imports(element, imports);
+        }
+        TypeInfo::Tuple(elements) | TypeInfo::Instance { args: elements, .. } => {
+            for element in elements {
+                collect_collection_imports(element, imports);
+            }
+        }
+        TypeInfo::Boxed(inner) => collect_collection_imports(inner, imports),
+        TypeInfo::Primitive(_)
+        | TypeInfo::Generic(_)
+        | TypeInfo::UserDefined(_)
+        | TypeInfo::FixedString(_) => {}
+    }
+}
+
 /// Check if type needs Solana-specific imports
 fn check_needs_solana_types(type_info: &TypeInfo, needs_pubkey: &mut bool) {
     match type_info {
@@ -513,10 +1217,25 @@ fn check_needs_solana_types(type_info: &TypeInfo, needs_pubkey: &mut bool) {
         TypeInfo::FixedArray { element, .. } => {
             check_needs_solana_types(element, needs_pubkey);
         }
+        TypeInfo::Map { key, value, .. } => {
+            check_needs_solana_types(key, needs_pubkey);
+            check_needs_solana_types(value, needs_pubkey);
+        }
+        TypeInfo::Set { element, .. } => {
+            check_needs_solana_types(element, needs_pubkey);
+        }
         TypeInfo::Option(inner) => {
             check_needs_solana_types(inner, needs_pubkey);
         }
-        TypeInfo::UserDefined(_) => {}
+        TypeInfo::Tuple(elements) | TypeInfo::Instance { args: elements, .. } => {
+            for element in elements {
+                check_needs_solana_types(element, needs_pubkey);
+            }
+        }
+        TypeInfo::Boxed(inner) => {
+            check_needs_solana_types(inner, needs_pubkey);
+        }
+        TypeInfo::UserDefined(_) | TypeInfo::FixedString(_) => {}
     }
 }
 
@@ -533,14 +1252,24 @@ fn generate_enum_with_context(enum_def: &EnumDefinition, use_anchor: bool) -> St
         ));
     }
 
+    // Type-level doc comments
+    push_doc_comments(&mut output, &enum_def.metadata.docs, "");
+
     // Generate derives (only if there are any)
     let derives = generate_enum_derives_with_context(enum_def, use_anchor);
     if !derives.is_empty() {
         output.push_str(&format!("#[derive({})]\n", derives.join(", ")));
     }
 
+    if enum_def.is_flags() {
+        output.push_str(&generate_flags(enum_def));
+        return output;
+    }
+
     // Add Solana-specific attributes
-    if enum_def.metadata.solana
+    if enum_def.is_error_code() {
+        output.push_str(&error_code_attribute(enum_def));
+    } else if enum_def.metadata.solana
         && enum_def
             .metadata
             .attributes
@@ -555,32 +1284,154 @@ fn generate_enum_with_context(enum_def: &EnumDefinition, use_anchor: bool) -> St
     } else {
         format!("{}<{}>", enum_def.name, enum_def.generic_params.join(", "))
     };
+    if let Some(repr) = enum_repr_attribute(enum_def) {
+        output.push_str(&repr);
+    }
     let vis = visibility_prefix(enum_def.visibility);
     output.push_str(&format!("{}enum {} {{\n", vis, enum_name_with_generics));
 
     // Generate variants
     for variant in &enum_def.variants {
+        push_doc_comments(&mut output, variant.docs(), "    ");
         match variant {
-            EnumVariantDefinition::Unit { name } => {
-                output.push_str(&format!("    {},\n", name));
+            EnumVariantDefinition::Unit { name, msg, .. } => {
+                if let Some(msg) = msg {
+                    output.push_str(&format!("    #[msg({:?})]\n", msg));
+                }
+                output.push_str(&format!("    {}{},\n", name, discriminant_suffix(variant)));
             }
-            EnumVariantDefinition::Tuple { name, types } => {
+            EnumVariantDefinition::Tuple { name, types, .. } => {
                 let type_strs: Vec<String> = types.iter().map(map_type_to_rust).collect();
-                output.push_str(&format!("    {}({}),\n", name, type_strs.join(", ")));
+                output.push_str(&format!(
+                    "    {}({}){},\n",
+                    name,
+                    type_strs.join(", "),
+                    discriminant_suffix(variant)
+                ));
             }
-            EnumVariantDefinition::Struct { name, fields } => {
+            EnumVariantDefinition::Struct { name, fields, .. } => {
                 output.push_str(&format!("    {} {{\n", name));
                 for field in fields {
+                    push_doc_comments(&mut output, &field.docs, "        ");
                     let rust_type = map_type_to_rust(&field.type_info);
                     output.push_str(&format!("        {}: {},\n", field.name, rust_type));
                 }
-                output.push_str("    },\n");
+                output.push_str(&format!("    }}{},\n", discriminant_suffix(variant)));
             }
         }
     }
 
     output.push_str("}\n");
 
+    if needs_manual_borsh(enum_def) {
+        output.push('\n');
+        output.push_str(&generate_enum_borsh_impls(enum_def, use_anchor));
+    }
+
+    output
+}
+
+/// Generate a client-side builder for an `#[instruction]` struct
+///
+/// Emits the instruction's sighash, a `{Name}Accounts` struct of account keys,
+/// the `{Name}Args` struct (when the instruction takes arguments) and a
+/// `{name}_instruction` function that assembles the account metas in field order.
+fn generate_instruction_builder(ix: &StructDefinition, use_anchor: bool) -> String {
+    let mut output = String::new();
+    let name = instruction_name(&ix.name);
+    let vis = visibility_prefix(ix.visibility);
+    let discriminator_const = format!("{}_DISCRIMINATOR", name.to_uppercase());
+
+    let bytes: Vec<String> = instruction_discriminator(&ix.name)
+        .iter()
+        .map(|b| b.to_string())
+        .collect();
+    output.push_str(&format!(
+        "/// Sighash Anchor prefixes to `{}` instruction data\n",
+        name
+    ));
+    output.push_str(&format!(
+        "{}const {}: [u8; 8] = [{}];\n\n",
+        vis,
+        discriminator_const,
+        bytes.join(", ")
+    ));
+
+    output.push_str(&format!("/// Accounts of the `{}` instruction\n", name));
+    output.push_str("#[derive(Debug, Clone)]\n");
+    output.push_str(&format!("{}struct {}Accounts {{\n", vis, ix.name));
+    for field in &ix.fields {
+        push_doc_comments(&mut output, &field.docs, "    ");
+        let key_type = if field.optional {
+            "Option<Pubkey>"
+        } else {
+            "Pubkey"
+        };
+        output.push_str(&format!("    pub {}: {},\n", field.name, key_type));
+    }
+    output.push_str("}\n\n");
+
+    let args_struct = ix.instruction_args_struct();
+    if let Some(args) = &args_struct {
+        output.push_str(&generate_struct_with_context(args, use_anchor));
+        output.push('\n');
+    }
+
+    if ix.metadata.docs.is_empty() {
+        output.push_str(&format!("/// Build a `{}` instruction\n", name));
+    } else {
+        push_doc_comments(&mut output, &ix.metadata.docs, "");
+    }
+    let args_param = match &args_struct {
+        Some(args) => format!(", args: &{}", args.name),
+        None => String::new(),
+    };
+    output.push_str(&format!(
+        "{}fn {}_instruction(program_id: Pubkey, accounts: &{}Accounts{}) -> Instruction {{\n",
+        vis, name, ix.name, args_param
+    ));
+    if args_struct.is_some() {
+        output.push_str(&format!(
+            "    let mut data = {}.to_vec();\n",
+            discriminator_const
+        ));
+        output.push_str(
+            "    args.serialize(&mut data).expect(\"serializing into a Vec cannot fail\");\n",
+        );
+    } else {
+        output.push_str(&format!(
+            "    let data = {}.to_vec();\n",
+            discriminator_const
+        ));
+    }
+    output.push_str("    Instruction {\n");
+    output.push_str("        program_id,\n");
+    output.push_str("        accounts: vec![\n");
+    for field in &ix.fields {
+        let flags = account_meta_flags(field);
+        let constructor = if flags.is_writable {
+            "AccountMeta::new"
+        } else {
+            "AccountMeta::new_readonly"
+        };
+        if field.optional {
+            // Anchor reads a missing optional account as the program id
+            output.push_str(&format!(
+                "            accounts.{}.map_or(AccountMeta::new_readonly(program_id, false), |key| {}(key, {})),\n",
+                field.name, constructor, flags.is_signer
+            ));
+        } else {
+            output.push_str(&format!(
+                "            {}(accounts.{}, {}),\n",
+                constructor, field.name, flags.is_signer
+            ));
+        }
+    }
+    output.push_str("        ],\n");
+    output.push_str("        data,\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
     output
 }
 
@@ -597,6 +1448,9 @@ fn generate_struct_with_context(struct_def: &StructDefinition, use_anchor: bool)
         ));
     }
 
+    // Type-level doc comments
+    push_doc_comments(&mut output, &struct_def.metadata.docs, "");
+
     // Generate derives (only if there are any)
     let derives = generate_struct_derives_with_context(struct_def, use_anchor);
     if !derives.is_empty() {
@@ -604,13 +1458,17 @@ fn generate_struct_with_context(struct_def: &StructDefinition, use_anchor: bool)
     }
 
     // Add Solana-specific attributes
-    if struct_def.metadata.solana
+    if struct_def.is_zero_copy() {
+        output.push_str(zero_copy_attribute(struct_def, use_anchor));
+    } else if struct_def.metadata.solana
         && struct_def
             .metadata
             .attributes
             .contains(&"account".to_string())
     {
         output.push_str("#[account]\n");
+    } else if struct_def.is_event() {
+        output.push_str("#[event]\n");
     }
 
     // Generate struct definition with optional generic parameters
@@ -628,10 +1486,245 @@ fn generate_struct_with_context(struct_def: &StructDefinition, use_anchor: bool)
 
     // Generate fields
     for field in &struct_def.fields {
+        push_doc_comments(&mut output, &field.docs, "    ");
         let rust_type = map_type_to_rust(&field.type_info);
         output.push_str(&format!("    pub {}: {},\n", field.name, rust_type));
     }
 
+    output.push_str("}\n");
+    output.push_str(&generate_default_impl(struct_def));
+    output.push_str(&generate_validate_impl(struct_def));
+    output.push_str(&generate_fixed_string_impl(struct_def));
+
+    output
+}
+
+/// Whether `#[derive(Default)]` is replaced by a hand-written impl using `#[default(..)]` values
+fn needs_manual_default(struct_def: &StructDefinition) -> bool {
+    struct_def.derives_default() && struct_def.has_field_defaults()
+}
+
+/// Generate `impl Default` filling in `#[default(..)]` values
+///
+/// Returns an empty string unless the struct derives `Default` and declares
+/// field defaults; the remaining fields use `Default::default()`.
+fn generate_default_impl(struct_def: &StructDefinition) -> String {
+    if !needs_manual_default(struct_def) {
+        return String::new();
+    }
+
+    let (impl_generics, type_generics) = if struct_def.generic_params.is_empty() {
+        (String::new(), String::new())
+    } else {
+        (
+            format!(
+                "<{}>",
+                struct_def
+                    .generic_params
+                    .iter()
+                    .map(|p| format!("{}: Default", p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            format!("<{}>", struct_def.generic_params.join(", ")),
+        )
+    };
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "\nimpl{} Default for {}{} {{\n",
+        impl_generics, struct_def.name, type_generics
+    ));
+    output.push_str("    fn default() -> Self {\n");
+    output.push_str("        Self {\n");
+    for field in &struct_def.fields {
+        let value = match &field.default {
+            Some(DefaultValue::String(value)) => format!("{:?}.to_string()", value),
+            Some(value) => value.to_string(),
+            None => "Default::default()".to_string(),
+        };
+        output.push_str(&format!("            {}: {},\n", field.name, value));
+    }
+    output.push_str("        }\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+    output
+}
+
+/// Generate the `validate()` method enforcing `#[range]` and `#[pattern]` constraints
+///
+/// Returns an empty string for structs without constraints. Patterns use the
+/// `regex` crate, which the consuming program must depend on; each is compiled
+/// once, on first use, into a `OnceLock` static.
+pub(crate) fn generate_validate_impl(struct_def: &StructDefinition) -> String {
+    if !struct_def.has_constraints() {
+        return String::new();
+    }
+
+    let generics = if struct_def.generic_params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", struct_def.generic_params.join(", "))
+    };
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "\nimpl{} {}{} {{\n",
+        generics, struct_def.name, generics
+    ));
+    output.push_str("    /// Check the value constraints declared in the schema\n");
+    // Fully qualified: the Anchor prelude shadows `Result`
+    output.push_str("    pub fn validate(&self) -> std::result::Result<(), String> {\n");
+
+    for field in &struct_def.fields {
+        if field.constraints.is_empty() {
+            continue;
+        }
+
+        // Optional fields are only checked when present
+        let (value, value_ref, indent) = if matches!(field.type_info, TypeInfo::Option(_)) {
+            output.push_str(&format!(
+                "        if let Some(value) = &self.{} {{\n",
+                field.name
+            ));
+            ("*value".to_string(), "value".to_string(), "            ")
+        } else {
+            let value = format!("self.{}", field.name);
+            (value.clone(), format!("&{}", value), "        ")
+        };
+
+        for constraint in &field.constraints {
+            let violated = match constraint {
+                Constraint::Range {
+                    min: Some(min),
+                    max: Some(max),
+                } => format!("!({}..={}).contains({})", min, max, value_ref),
+                Constraint::Range {
+                    min: Some(min),
+                    max: None,
+                } => format!("{} < {}", value, min),
+                Constraint::Range {
+                    min: None,
+                    max: Some(max),
+                } => format!("{} > {}", value, max),
+                Constraint::Range {
+                    min: None,
+                    max: None,
+                } => continue,
+                Constraint::Pattern(pattern) => {
+                    // The schema compiler already checked that the pattern is valid
+                    let name = format!("{}_PATTERN", field.name.to_uppercase());
+                    output.push_str(&format!(
+                        "{}static {}: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();\n",
+                        indent, name
+                    ));
+                    output.push_str(&format!(
+                        "{}let {} = {}.get_or_init(|| {{\n",
+                        indent,
+                        name.to_lowercase(),
+                        name
+                    ));
+                    output.push_str(&format!(
+                        "{}    regex::Regex::new({:?}).expect(\"pattern is checked by LUMOS\")\n",
+                        indent, pattern
+                    ));
+                    output.push_str(&format!("{}}});\n", indent));
+                    format!("!{}.is_match({})", name.to_lowercase(), value_ref)
+                }
+            };
+            let message = format!("{} {}", field.name, constraint);
+            output.push_str(&format!("{}if {} {{\n", indent, violated));
+            output.push_str(&format!(
+                "{}    return Err({:?}.to_string());\n",
+                indent, message
+            ));
+            output.push_str(&format!("{}}}\n", indent));
+        }
+
+        if matches!(field.type_info, TypeInfo::Option(_)) {
+            output.push_str("        }\n");
+        }
+    }
+
+    output.push_str("        Ok(())\n");
+    output.push_str("    }\n");
+    output.push_str("}\n");
+
+    output
+}
+
+/// Generate string accessors for `FixedString<N>` fields
+///
+/// Each field gets `<field>_str()`, which reads the bytes up to the zero
+/// padding, and `set_<field>()`, which zero-pads a string and truncates it on a
+/// character boundary. Returns an empty string for structs without such fields.
+fn generate_fixed_string_impl(struct_def: &StructDefinition) -> String {
+    let fields: Vec<(&FieldDefinition, usize)> = struct_def
+        .fields
+        .iter()
+        .filter_map(|field| match field.type_info {
+            TypeInfo::FixedString(size) => Some((field, size)),
+            _ => None,
+        })
+        .collect();
+    if fields.is_empty() {
+        return String::new();
+    }
+
+    let generics = if struct_def.generic_params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", struct_def.generic_params.join(", "))
+    };
+
+    let mut output = String::new();
+    output.push_str(&format!(
+        "\nimpl{} {}{} {{\n",
+        generics, struct_def.name, generics
+    ));
+    for (i, (field, size)) in fields.iter().enumerate() {
+        let name = &field.name;
+        if i > 0 {
+            output.push('\n');
+        }
+        output.push_str(&format!(
+            "    /// `{}` up to its zero padding, or its longest valid UTF-8 prefix\n",
+            name
+        ));
+        output.push_str(&format!("    pub fn {}_str(&self) -> &str {{\n", name));
+        output.push_str(&format!(
+            "        let bytes = &self.{name}[..self.{name}.iter().position(|&b| b == 0).unwrap_or({size})];\n"
+        ));
+        output.push_str("        match std::str::from_utf8(bytes) {\n");
+        output.push_str("            Ok(value) => value,\n");
+        output.push_str(
+            "            Err(e) => std::str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_default(),\n",
+        );
+        output.push_str("        }\n");
+        output.push_str("    }\n\n");
+        output.push_str(&format!(
+            "    /// Store `value` in `{}`, zero-padded; returns `false` if it had to be truncated to {} bytes\n",
+            name, size
+        ));
+        output.push_str(&format!(
+            "    pub fn set_{}(&mut self, value: &str) -> bool {{\n",
+            name
+        ));
+        output.push_str(&format!(
+            "        let mut len = value.len().min({});\n",
+            size
+        ));
+        output.push_str("        while !value.is_char_boundary(len) {\n");
+        output.push_str("            len -= 1;\n");
+        output.push_str("        }\n");
+        output.push_str(&format!("        self.{} = [0; {}];\n", name, size));
+        output.push_str(&format!(
+            "        self.{}[..len].copy_from_slice(&value.as_bytes()[..len]);\n",
+            name
+        ));
+        output.push_str("        len == value.len()\n");
+        output.push_str("    }\n");
+    }
     output.push_str("}\n");
 
     output
@@ -674,17 +1767,48 @@ fn merge_derives(auto_derives: Vec<String>, custom_derives: &[String]) -> Vec<St
 fn generate_enum_derives_with_context(enum_def: &EnumDefinition, use_anchor: bool) -> Vec<String> {
     let mut auto_derives = Vec::new();
 
-    // If using #[account], no auto derives needed (Anchor provides them)
+    // If using #[account] or #[error_code], no auto derives needed (Anchor provides them)
     // But still add custom derives
-    if enum_def.metadata.solana
+    if (enum_def.metadata.solana
         && enum_def
             .metadata
             .attributes
-            .contains(&"account".to_string())
+            .contains(&"account".to_string()))
+        || enum_def.is_error_code()
     {
         return merge_derives(auto_derives, &enum_def.metadata.custom_derives);
     }
 
+    // Flags are a plain integer newtype, so Borsh derives encode them as is
+    if enum_def.is_flags() {
+        if enum_def.metadata.solana && use_anchor {
+            auto_derives.push("AnchorSerialize".to_string());
+            auto_derives.push("AnchorDeserialize".to_string());
+        } else if enum_def.metadata.solana {
+            auto_derives.push("BorshSerialize".to_string());
+            auto_derives.push("BorshDeserialize".to_string());
+        }
+        for derive in [
+            "Debug",
+            "Clone",
+            "Copy",
+            "Default",
+            "PartialEq",
+            "Eq",
+            "Hash",
+        ] {
+            auto_derives.push(derive.to_string());
+        }
+        return merge_derives(auto_derives, &enum_def.metadata.custom_derives);
+    }
+
+    // Custom discriminants are encoded by hand-written impls instead
+    if needs_manual_borsh(enum_def) {
+        auto_derives.push("Debug".to_string());
+        auto_derives.push("Clone".to_string());
+        return merge_derives(auto_derives, &enum_def.metadata.custom_derives);
+    }
+
     // If it's a Solana type but module uses Anchor, use Anchor derives
     if enum_def.metadata.solana && use_anchor {
         auto_derives.push("AnchorSerialize".to_string());
@@ -713,15 +1837,36 @@ fn generate_struct_derives_with_context(
 ) -> Vec<String> {
     let mut auto_derives = Vec::new();
 
-    // If using #[account], no auto derives needed (Anchor provides them)
+    // `#[default(..)]` values need a hand-written impl instead of the derive
+    let custom_derives: Vec<String> = struct_def
+        .metadata
+        .custom_derives
+        .iter()
+        .filter(|d| !(d.as_str() == "Default" && needs_manual_default(struct_def)))
+        .cloned()
+        .collect();
+
+    // Zero-copy types are Pod rather than Borsh; Anchor's zero_copy macros derive them
+    if struct_def.is_zero_copy() {
+        if !use_anchor {
+            auto_derives.push("Clone".to_string());
+            auto_derives.push("Copy".to_string());
+            auto_derives.push("bytemuck::Pod".to_string());
+            auto_derives.push("bytemuck::Zeroable".to_string());
+        }
+        return merge_derives(auto_derives, &custom_derives);
+    }
+
+    // If using #[account] or #[event], no auto derives needed (Anchor provides them)
     // But still add custom derives
-    if struct_def.metadata.solana
+    if (struct_def.metadata.solana
         && struct_def
             .metadata
             .attributes
-            .contains(&"account".to_string())
+            .contains(&"account".to_string()))
+        || struct_def.is_event()
     {
-        return merge_derives(auto_derives, &struct_def.metadata.custom_derives);
+        return merge_derives(auto_derives, &custom_derives);
     }
 
     // If it's a Solana type but module uses Anchor, use Anchor derives
@@ -730,7 +1875,7 @@ fn generate_struct_derives_with_context(
         auto_derives.push("AnchorDeserialize".to_string());
         auto_derives.push("Debug".to_string());
         auto_derives.push("Clone".to_string());
-        return merge_derives(auto_derives, &struct_def.metadata.custom_derives);
+        return merge_derives(auto_derives, &custom_derives);
     }
 
     // Otherwise use Borsh derives
@@ -742,7 +1887,7 @@ fn generate_struct_derives_with_context(
     auto_derives.push("Debug".to_string());
     auto_derives.push("Clone".to_string());
 
-    merge_derives(auto_derives, &struct_def.metadata.custom_derives)
+    merge_derives(auto_derives, &custom_derives)
 }
 
 /// Collect required imports based on enum definition
@@ -750,7 +1895,10 @@ fn collect_enum_imports(enum_def: &EnumDefinition) -> HashSet<String> {
     let mut imports = HashSet::new();
 
     // Check if we need Borsh or Anchor imports
-    if enum_def.metadata.solana {
+    if enum_def.is_error_code() {
+        // #[error_code] comes from the Anchor prelude
+        imports.insert("anchor_lang::prelude::*".to_string());
+    } else if enum_def.metadata.solana {
         // If using #[account], use Anchor imports (includes Borsh)
         if enum_def
             .metadata
@@ -788,16 +1936,27 @@ fn collect_enum_imports(enum_def: &EnumDefinition) -> HashSet<String> {
 fn collect_struct_imports(struct_def: &StructDefinition) -> HashSet<String> {
     let mut imports = HashSet::new();
 
+    // Instruction builders only need account keys plus whatever their arguments use
+    if struct_def.metadata.is_instruction {
+        imports.insert(INSTRUCTION_IMPORTS.to_string());
+        imports.insert("solana_program::pubkey::Pubkey".to_string());
+        if let Some(args) = struct_def.instruction_args_struct() {
+            imports.extend(collect_struct_imports(&args));
+        }
+        return imports;
+    }
+
     // Check if we need Borsh or Anchor imports
     if struct_def.metadata.solana {
-        // If using #[account], use Anchor imports (includes Borsh)
+        // If using #[account] or #[event], use Anchor imports (includes Borsh)
         if struct_def
             .metadata
             .attributes
             .contains(&"account".to_string())
+            || struct_def.is_event()
         {
             imports.insert("anchor_lang::prelude::*".to_string());
-        } else {
+        } else if !struct_def.is_zero_copy() {
             // Otherwise use Borsh directly
             imports.insert("borsh::{BorshSerialize, BorshDeserialize}".to_string());
         }
@@ -833,9 +1992,27 @@ fn collect_imports_from_type(type_info: &TypeInfo, imports: &mut HashSet<String>
         TypeInfo::Option(inner) => {
             collect_imports_from_type(inner, imports);
         }
+        TypeInfo::Map { key, value, .. } => {
+            collect_collection_imports(type_info, imports);
+            collect_imports_from_type(key, imports);
+            collect_imports_from_type(value, imports);
+        }
+        TypeInfo::Set { element, .. } => {
+            collect_collection_imports(type_info, imports);
+            collect_imports_from_type(element, imports);
+        }
+        TypeInfo::Tuple(elements) | TypeInfo::Instance { args: elements, .. } => {
+            for element in elements {
+                collect_imports_from_type(element, imports);
+            }
+        }
+        TypeInfo::Boxed(inner) => {
+            collect_imports_from_type(inner, imports);
+        }
         TypeInfo::UserDefined(_) => {
             // User-defined types are assumed to be in the same module
         }
+        TypeInfo::FixedString(_) => {}
     }
 }
 
@@ -878,7 +2055,33 @@ fn map_type_to_rust(type_info: &TypeInfo) -> String {
             let inner_type = map_type_to_rust(inner);
             format!("Option<{}>", inner_type)
         }
+        TypeInfo::Map {
+            key,
+            value,
+            ordered,
+        } => {
+            let collection = if *ordered { "BTreeMap" } else { "HashMap" };
+            format!(
+                "{}<{}, {}>",
+                collection,
+                map_type_to_rust(key),
+                map_type_to_rust(value)
+            )
+        }
+        TypeInfo::Set { element, ordered } => {
+            let collection = if *ordered { "BTreeSet" } else { "HashSet" };
+            format!("{}<{}>", collection, map_type_to_rust(element))
+        }
+        TypeInfo::Tuple(elements) => TypeInfo::rust_tuple(elements, map_type_to_rust),
+        TypeInfo::Boxed(inner) => format!("Box<{}>", map_type_to_rust(inner)),
+        // Stored as raw bytes so the encoding and account size stay fixed
+        TypeInfo::FixedString(size) => format!("[u8; {}]", size),
         TypeInfo::UserDefined(type_name) => type_name.clone(),
+        // Rust keeps real generics, so instances are written as-is
+        TypeInfo::Instance { name, args } => {
+            let args: Vec<String> = args.iter().map(map_type_to_rust).collect();
+            format!("{}<{}>", name, args.join(", "))
+        }
     }
 }
 
@@ -899,24 +2102,20 @@ mod tests {
                 FieldDefinition {
                     name: "id".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "name".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -935,30 +2134,23 @@ mod tests {
                 FieldDefinition {
                     name: "wallet".to_string(),
                     type_info: TypeInfo::Primitive("Pubkey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "balance".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -979,14 +2171,14 @@ mod tests {
                 name: "email".to_string(),
                 type_info: TypeInfo::Option(Box::new(TypeInfo::Primitive("String".to_string()))),
                 optional: true,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1001,15 +2193,14 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "members".to_string(),
                 type_info: TypeInfo::Array(Box::new(TypeInfo::Primitive("u64".to_string()))),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1027,6 +2218,8 @@ mod tests {
                 visibility: Visibility::Public,
 
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
             TypeDefinition::Struct(StructDefinition {
                 name: "Post".to_string(),
@@ -1036,6 +2229,8 @@ mod tests {
                 visibility: Visibility::Public,
 
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
         ];
 
@@ -1052,21 +2247,16 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "key".to_string(),
                 type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1081,24 +2271,31 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Finished".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1119,6 +2316,8 @@ mod tests {
                 EnumVariantDefinition::Tuple {
                     name: "PlayerJoined".to_string(),
                     types: vec![TypeInfo::Primitive("Pubkey".to_string())],
+                    docs: vec![],
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Tuple {
                     name: "ScoreUpdated".to_string(),
@@ -1126,18 +2325,18 @@ mod tests {
                         TypeInfo::Primitive("Pubkey".to_string()),
                         TypeInfo::Primitive("u64".to_string()),
                     ],
+                    docs: vec![],
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1161,20 +2360,16 @@ mod tests {
                         FieldDefinition {
                             name: "authority".to_string(),
                             type_info: TypeInfo::Primitive("Pubkey".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                         FieldDefinition {
                             name: "max_players".to_string(),
                             type_info: TypeInfo::Primitive("u32".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                     ],
+                    docs: vec![],
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Struct {
                     name: "UpdateScore".to_string(),
@@ -1182,32 +2377,26 @@ mod tests {
                         FieldDefinition {
                             name: "player".to_string(),
                             type_info: TypeInfo::Primitive("Pubkey".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                         FieldDefinition {
                             name: "new_score".to_string(),
                             type_info: TypeInfo::Primitive("u64".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                     ],
+                    docs: vec![],
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1231,25 +2420,21 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "balance".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
                 custom_derives: vec![
                     "PartialEq".to_string(),
                     "Eq".to_string(),
                     "Hash".to_string(),
                 ],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1267,19 +2452,18 @@ mod tests {
             fields: vec![],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
                 // User specifies Debug and Clone, which are auto-generated
                 custom_derives: vec![
                     "Debug".to_string(),
                     "Clone".to_string(),
                     "PartialEq".to_string(),
                 ],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1302,13 +2486,13 @@ mod tests {
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
                 custom_derives: vec!["PartialEq".to_string(), "Eq".to_string()],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1325,21 +2509,26 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
                 custom_derives: vec!["PartialEq".to_string(), "Eq".to_string()],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1372,6 +2561,45 @@ mod tests {
         assert!(code.contains("pub balance: u64"));
     }
 
+    #[test]
+    fn test_end_to_end_doc_comments() {
+        use crate::{parser, transform};
+
+        let input = r#"
+            /// Player account
+            ///
+            /// Stores progression data.
+            #[solana]
+            struct Player {
+                /// Wallet that owns this account
+                wallet: PublicKey,
+            }
+
+            /// Lifecycle state
+            #[solana]
+            enum GameState {
+                /// Waiting for players
+                Lobby,
+                Finished {
+                    /// Winning wallet
+                    winner: PublicKey,
+                },
+            }
+        "#;
+
+        let ast = parser::parse_lumos_file(input).unwrap();
+        let ir = transform::transform_to_ir(ast).unwrap();
+        let code = generate_module(&ir);
+
+        assert!(code.contains(
+            "/// Player account\n///\n/// Stores progression data.\n#[derive(BorshSerialize"
+        ));
+        assert!(code.contains("    /// Wallet that owns this account\n    pub wallet: Pubkey,"));
+        assert!(code.contains("/// Lifecycle state\n#[derive("));
+        assert!(code.contains("    /// Waiting for players\n    Lobby,"));
+        assert!(code.contains("        /// Winning wallet\n        winner: Pubkey,"));
+    }
+
     #[test]
     fn test_visibility_public_struct() {
         let struct_def = StructDefinition {
@@ -1380,14 +2608,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "id".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let code = generate_struct(&struct_def);
@@ -1402,14 +2629,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "id".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Private,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let code = generate_struct(&struct_def);
@@ -1424,10 +2650,15 @@ mod tests {
             generic_params: vec![],
             variants: vec![EnumVariantDefinition::Unit {
                 name: "Active".to_string(),
+                docs: vec![],
+                msg: None,
+                discriminant: None,
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let code = generate_enum(&enum_def);
@@ -1441,14 +2672,67 @@ mod tests {
             generic_params: vec![],
             variants: vec![EnumVariantDefinition::Unit {
                 name: "Active".to_string(),
+                docs: vec![],
+                msg: None,
+                discriminant: None,
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Private,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let code = generate_enum(&enum_def);
         assert!(code.contains("enum PrivateStatus"));
         assert!(!code.contains("pub enum PrivateStatus"));
     }
+
+    #[test]
+    fn generates_validate_method_for_constraints() {
+        let source = r#"
+            #[solana]
+            struct Listing {
+                #[range(0..=10000)]
+                fee_bps: u16,
+                #[range(-5..5)]
+                delta: Option<i32>,
+                #[pattern("^[A-Z]{1,10}$")]
+                symbol: String,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("impl Listing {\n    /// Check the value constraints declared in the schema\n    pub fn validate(&self) -> std::result::Result<(), String> {\n"));
+        assert!(code.contains("        if self.fee_bps > 10000 {\n            return Err(\"fee_bps must be at most 10000\".to_string());\n        }\n"));
+        assert!(code.contains("        if let Some(value) = &self.delta {\n            if !(-5..=4).contains(value) {\n"));
+        assert!(code.contains("        static SYMBOL_PATTERN: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();\n        let symbol_pattern = SYMBOL_PATTERN.get_or_init(|| {\n            regex::Regex::new(\"^[A-Z]{1,10}$\").expect(\"pattern is checked by LUMOS\")\n        });\n        if !symbol_pattern.is_match(&self.symbol) {\n"));
+        assert!(code.contains("        Ok(())\n    }\n}\n"));
+    }
+
+    #[test]
+    fn generates_default_impl_for_field_defaults() {
+        let source = r#"
+            #[derive(Default)]
+            struct Config {
+                #[default(100)]
+                fee_bps: u16,
+                #[default("USDC")]
+                symbol: String,
+                #[default(true)]
+                active: bool,
+                #[default(0.5)]
+                ratio: f64,
+                count: u64,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("#[derive(Debug, Clone)]\npub struct Config"));
+        assert!(code.contains("impl Default for Config {\n    fn default() -> Self {\n        Self {\n            fee_bps: 100,\n            symbol: \"USDC\".to_string(),\n            active: true,\n            ratio: 0.5,\n            count: Default::default(),\n"));
+    }
 }
@@ -18,6 +18,8 @@
 //! - Uses `@account` decorator for account structs
 //! - Uses Seahorse-native types (u8, u16, u32, u64, Pubkey, etc.)
 //! - No explicit Borsh schemas (Seahorse handles serialization)
+//! - `#[range]` constraints become `assert`s in a `validate()` method; Seahorse
+//!   has no regex support, so `#[pattern]` is left as a comment
 //!
 //! ## Type Mapping
 //!
@@ -62,20 +64,41 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+use crate::generators::python::{docstring_lines, generate_const_definition, push_docstring};
 use crate::ir::{
-    EnumDefinition, EnumVariantDefinition, StructDefinition, TypeAliasDefinition, TypeDefinition,
-    TypeInfo,
+    ConstDefinition, Constraint, EnumDefinition, EnumVariantDefinition, StructDefinition,
+    TypeAliasDefinition, TypeDefinition, TypeInfo,
 };
+use crate::naming::{apply_renames, Case, NameTarget};
+use crate::transform::inline_newtypes;
 
 /// Generate Seahorse Python code from a type definition
 pub fn generate(type_def: &TypeDefinition) -> String {
+    let type_def = &apply_renames(
+        &inline_newtypes(std::slice::from_ref(type_def)),
+        NameTarget::Python,
+    )[0];
     match type_def {
         TypeDefinition::Struct(struct_def) => generate_struct(struct_def),
         TypeDefinition::Enum(enum_def) => generate_enum(enum_def),
         TypeDefinition::TypeAlias(alias_def) => generate_type_alias(alias_def),
+        TypeDefinition::Const(const_def) => generate_const(const_def),
     }
 }
 
+/// Generate Seahorse Python code from a constant definition
+fn generate_const(const_def: &ConstDefinition) -> String {
+    let mut output = String::new();
+
+    // Add file header
+    output.push_str("# Auto-generated by LUMOS for Seahorse\n");
+    output.push_str("# DO NOT EDIT - Changes will be overwritten\n\n");
+
+    output.push_str(&generate_const_definition(const_def));
+
+    output
+}
+
 /// Generate Seahorse Python code from a type alias definition
 fn generate_type_alias(alias_def: &TypeAliasDefinition) -> String {
     let mut output = String::new();
@@ -130,7 +153,9 @@ fn generate_enum(enum_def: &EnumDefinition) -> String {
     output.push_str("from seahorse.prelude import *\n");
 
     // Add enum import for unit enums
-    if enum_def.is_unit_only() {
+    if enum_def.is_flags() {
+        output.push_str("from typing import TypeAlias\n");
+    } else if enum_def.is_unit_only() {
         output.push_str("from enum import IntEnum\n");
     } else {
         output.push_str("from dataclasses import dataclass\n");
@@ -166,7 +191,7 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
         total += match type_def {
             TypeDefinition::Struct(s) => STRUCT_BASE + (s.fields.len() * FIELD_SIZE),
             TypeDefinition::Enum(e) => ENUM_BASE + (e.variants.len() * VARIANT_SIZE),
-            TypeDefinition::TypeAlias(_) => 100,
+            TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => 100,
         };
     }
 
@@ -185,6 +210,7 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
 ///
 /// Complete Seahorse Python source code as a `String`, ready to write to a `.py` file.
 pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
+    let type_defs = &apply_renames(&inline_newtypes(type_defs), NameTarget::Python);
     let estimated_capacity = estimate_output_size(type_defs);
     let mut output = String::with_capacity(estimated_capacity);
 
@@ -203,7 +229,9 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
     for type_def in type_defs {
         match type_def {
             TypeDefinition::Enum(e) => {
-                if e.is_unit_only() {
+                if e.is_flags() {
+                    needs_type_alias = true;
+                } else if e.is_unit_only() {
                     needs_int_enum = true;
                 } else {
                     needs_dataclass = true;
@@ -262,6 +290,9 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
                 let py_type = map_type_to_seahorse(&a.target);
                 output.push_str(&format!("{}: TypeAlias = {}\n", a.name, py_type));
             }
+            TypeDefinition::Const(c) => {
+                output.push_str(&generate_const_definition(c));
+            }
         }
     }
 
@@ -283,29 +314,37 @@ fn generate_struct_class(struct_def: &StructDefinition) -> String {
 
     output.push_str(&format!("class {}:\n", struct_def.name));
 
-    if struct_def.fields.is_empty() {
-        output.push_str("    pass\n");
-    } else {
-        // Add docstring if we have deprecation warnings
-        let deprecated_fields: Vec<_> = struct_def
-            .fields
-            .iter()
-            .filter(|f| f.deprecated.is_some())
-            .collect();
+    // Docstring from schema doc comments
+    let mut doc_lines = docstring_lines(&struct_def.metadata.docs, &struct_def.fields);
 
-        if !deprecated_fields.is_empty() {
-            output.push_str("    \"\"\"\n");
-            output.push_str("    Deprecated fields:\n");
-            for field in &deprecated_fields {
-                if let Some(msg) = &field.deprecated {
-                    output.push_str(&format!("    - {}: {}\n", field.name, msg));
-                } else {
-                    output.push_str(&format!("    - {}: deprecated\n", field.name));
-                }
+    // Append deprecation warnings to the docstring
+    let deprecated_fields: Vec<_> = struct_def
+        .fields
+        .iter()
+        .filter(|f| f.deprecated.is_some())
+        .collect();
+
+    if !deprecated_fields.is_empty() {
+        if !doc_lines.is_empty() {
+            doc_lines.push(String::new());
+        }
+        doc_lines.push("Deprecated fields:".to_string());
+        for field in &deprecated_fields {
+            if let Some(msg) = &field.deprecated {
+                doc_lines.push(format!("- {}: {}", field.name, msg));
+            } else {
+                doc_lines.push(format!("- {}: deprecated", field.name));
             }
-            output.push_str("    \"\"\"\n");
         }
+    }
+
+    if !doc_lines.is_empty() {
+        push_docstring(&mut output, &doc_lines, "    ");
+    }
 
+    if struct_def.fields.is_empty() {
+        output.push_str("    pass\n");
+    } else {
         // Generate fields with Seahorse types
         for field in &struct_def.fields {
             let py_type = map_type_to_seahorse(&field.type_info);
@@ -321,6 +360,65 @@ fn generate_struct_class(struct_def: &StructDefinition) -> String {
         }
     }
 
+    if struct_def.has_constraints() {
+        output.push('\n');
+        output.push_str(&generate_validate_method(struct_def));
+    }
+
+    output
+}
+
+/// Generate `validate()`, asserting the `#[range]` constraints of non-optional fields
+///
+/// Constraints Seahorse cannot express are listed as comments.
+fn generate_validate_method(struct_def: &StructDefinition) -> String {
+    let mut output = String::new();
+    output.push_str("    def validate(self):\n");
+    let mut has_check = false;
+
+    for field in &struct_def.fields {
+        let optional = matches!(field.type_info, TypeInfo::Option(_));
+        for constraint in &field.constraints {
+            let message = format!("{} {}", field.name, constraint);
+            let value = format!("self.{}", field.name);
+            let check = match constraint {
+                Constraint::Range { .. } if optional => None,
+                Constraint::Range {
+                    min: Some(min),
+                    max: Some(max),
+                } => Some(format!("{} <= {} <= {}", min, value, max)),
+                Constraint::Range {
+                    min: Some(min),
+                    max: None,
+                } => Some(format!("{} >= {}", value, min)),
+                Constraint::Range {
+                    min: None,
+                    max: Some(max),
+                } => Some(format!("{} <= {}", value, max)),
+                Constraint::Range {
+                    min: None,
+                    max: None,
+                } => continue,
+                Constraint::Pattern(_) => None,
+            };
+            match check {
+                Some(check) => {
+                    has_check = true;
+                    output.push_str(&format!(
+                        "        assert {}, {}\n",
+                        check,
+                        serde_json::to_string(&message).unwrap_or_default()
+                    ));
+                }
+                None => output.push_str(&format!("        # Not checked on-chain: {}\n", message)),
+            }
+        }
+    }
+
+    // Python needs a statement when every constraint was left as a comment
+    if !has_check {
+        output.push_str("        pass\n");
+    }
     output
 }
 
@@ -328,12 +426,40 @@ fn generate_struct_class(struct_def: &StructDefinition) -> String {
 fn generate_enum_class(enum_def: &EnumDefinition) -> String {
     let mut output = String::new();
 
+    // Seahorse enums are tagged unions, so flags stay the packed integer
+    if enum_def.is_flags() {
+        for line in &enum_def.metadata.docs {
+            output.push_str(&format!("#: {}\n", line));
+        }
+        output.push_str(&format!(
+            "{}: TypeAlias = {}\n",
+            enum_def.name,
+            enum_def.repr().name()
+        ));
+        let prefix = Case::ScreamingSnake.apply(&enum_def.name);
+        for (variant, bit) in enum_def.variants.iter().zip(enum_def.discriminants()) {
+            for line in variant.docs() {
+                output.push_str(&format!("#: {}\n", line));
+            }
+            output.push_str(&format!(
+                "{}_{} = {}\n",
+                prefix,
+                Case::ScreamingSnake.apply(variant.name()),
+                bit
+            ));
+        }
+        return output;
+    }
+
     // Check if all variants are unit variants (simple IntEnum)
     if enum_def.is_unit_only() {
         output.push_str(&format!("class {}(IntEnum):\n", enum_def.name));
-        for (idx, variant) in enum_def.variants.iter().enumerate() {
-            if let EnumVariantDefinition::Unit { name } = variant {
-                output.push_str(&format!("    {} = {}\n", name, idx));
+        if !enum_def.metadata.docs.is_empty() {
+            push_docstring(&mut output, &enum_def.metadata.docs, "    ");
+        }
+        for (variant, discriminant) in enum_def.variants.iter().zip(enum_def.discriminants()) {
+            if let EnumVariantDefinition::Unit { name, .. } = variant {
+                output.push_str(&format!("    {} = {}\n", name, discriminant));
             }
         }
     } else {
@@ -343,12 +469,12 @@ fn generate_enum_class(enum_def: &EnumDefinition) -> String {
         // Generate variant dataclasses
         for variant in &enum_def.variants {
             match variant {
-                EnumVariantDefinition::Unit { name } => {
+                EnumVariantDefinition::Unit { name, .. } => {
                     output.push_str("@dataclass\n");
                     output.push_str(&format!("class {}{}:\n", enum_def.name, name));
                     output.push_str("    pass\n\n");
                 }
-                EnumVariantDefinition::Tuple { name, types } => {
+                EnumVariantDefinition::Tuple { name, types, .. } => {
                     output.push_str("@dataclass\n");
                     output.push_str(&format!("class {}{}:\n", enum_def.name, name));
                     for (idx, type_info) in types.iter().enumerate() {
@@ -357,7 +483,7 @@ fn generate_enum_class(enum_def: &EnumDefinition) -> String {
                     }
                     output.push('\n');
                 }
-                EnumVariantDefinition::Struct { name, fields } => {
+                EnumVariantDefinition::Struct { name, fields, .. } => {
                     output.push_str("@dataclass\n");
                     output.push_str(&format!("class {}{}:\n", enum_def.name, name));
                     if fields.is_empty() {
@@ -434,12 +560,31 @@ fn map_type_to_seahorse(type_info: &TypeInfo) -> String {
             let element_type = map_type_to_seahorse(element);
             format!("Array[{}, {}]", element_type, size)
         }
+        TypeInfo::FixedString(size) => format!("Array[u8, {}]", size),
         TypeInfo::Option(inner) => {
             let inner_type = map_type_to_seahorse(inner);
             format!("{} | None", inner_type)
         }
+        TypeInfo::Map { key, value, .. } => {
+            format!(
+                "Dict[{}, {}]",
+                map_type_to_seahorse(key),
+                map_type_to_seahorse(value)
+            )
+        }
+        TypeInfo::Set { element, .. } => {
+            format!("Set[{}]", map_type_to_seahorse(element))
+        }
+        TypeInfo::Tuple(elements) => {
+            let element_types: Vec<String> = elements.iter().map(map_type_to_seahorse).collect();
+            format!("Tuple[{}]", element_types.join(", "))
+        }
+        TypeInfo::Boxed(inner) => map_type_to_seahorse(inner),
         TypeInfo::Generic(param_name) => param_name.clone(),
-        TypeInfo::UserDefined(type_name) => type_name.clone(),
+        TypeInfo::UserDefined(type_name)
+        | TypeInfo::Instance {
+            name: type_name, ..
+        } => type_name.clone(),
     }
 }
 
@@ -457,38 +602,28 @@ mod tests {
                 FieldDefinition {
                     name: "wallet".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "level".to_string(),
                     type_info: TypeInfo::Primitive("u16".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "experience".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -508,21 +643,17 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "max_players".to_string(),
                 type_info: TypeInfo::Primitive("u32".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
                 solana: true,
                 attributes: vec![], // No "account" attribute
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -596,21 +727,17 @@ mod tests {
                 fields: vec![FieldDefinition {
                     name: "wallet".to_string(),
                     type_info: TypeInfo::Primitive("Pubkey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 }],
                 metadata: Metadata {
                     solana: true,
                     attributes: vec!["account".to_string()],
-                    version: None,
-                    custom_derives: vec![],
-                    is_instruction: false,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 visibility: Visibility::Public,
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
             TypeDefinition::Struct(StructDefinition {
                 name: "GameState".to_string(),
@@ -618,21 +745,17 @@ mod tests {
                 fields: vec![FieldDefinition {
                     name: "is_active".to_string(),
                     type_info: TypeInfo::Primitive("bool".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 }],
                 metadata: Metadata {
                     solana: true,
                     attributes: vec!["account".to_string()],
-                    version: None,
-                    custom_derives: vec![],
-                    is_instruction: false,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 visibility: Visibility::Public,
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
         ];
 
@@ -650,24 +773,31 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Ended".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -687,34 +817,55 @@ mod tests {
                 FieldDefinition {
                     name: "balance".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "old_field".to_string(),
                     type_info: TypeInfo::Primitive("u32".to_string()),
-                    optional: false,
                     deprecated: Some("Use new_field instead".to_string()),
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
         assert!(code.contains("Deprecated fields:"));
         assert!(code.contains("old_field: Use new_field instead"));
     }
+
+    #[test]
+    fn generates_validate_method_for_ranges() {
+        let source = r#"
+            #[solana]
+            struct Listing {
+                #[range(0..=10000)]
+                fee_bps: u16,
+                #[range(-5..5)]
+                delta: Option<i32>,
+                #[pattern("^[A-Z]{1,10}$")]
+                symbol: String,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("    def validate(self):\n"));
+        assert!(code
+            .contains("        assert self.fee_bps <= 10000, \"fee_bps must be at most 10000\"\n"));
+        assert!(code.contains("        # Not checked on-chain: delta must be between -5 and 4\n"));
+        assert!(
+            code.contains("        # Not checked on-chain: symbol must match /^[A-Z]{1,10}$/\n")
+        );
+        assert!(!code.contains("        pass\n"));
+    }
 }
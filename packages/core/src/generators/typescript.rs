@@ -78,21 +78,60 @@
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
+use crate::anchor::{
+    account_meta_flags, event_discriminator, instruction_discriminator, instruction_name,
+};
+use crate::generators::module_tree::{
+    relative_path, short_name, split_modules, ModuleFile, ModuleImport, TreeModule,
+};
 use crate::ir::{
-    EnumDefinition, EnumVariantDefinition, StructDefinition, TypeAliasDefinition, TypeDefinition,
-    TypeInfo,
+    ConstDefinition, Constraint, DefaultValue, EnumDefinition, EnumVariantDefinition,
+    StructDefinition, TypeAliasDefinition, TypeDefinition, TypeInfo,
 };
-use std::collections::HashSet;
+use crate::naming::{apply_renames, NameTarget};
+use crate::transform::{collect_generic_instances, GenericInstance};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::PathBuf;
 
 /// Generate TypeScript code from a type definition
 pub fn generate(type_def: &TypeDefinition) -> String {
+    let type_def = &apply_renames(std::slice::from_ref(type_def), NameTarget::TypeScript)[0];
     match type_def {
         TypeDefinition::Struct(struct_def) => generate_struct(struct_def),
         TypeDefinition::Enum(enum_def) => generate_enum(enum_def),
         TypeDefinition::TypeAlias(alias_def) => generate_type_alias(alias_def),
+        TypeDefinition::Const(const_def) => generate_const(const_def),
     }
 }
 
+/// Generate TypeScript code from a constant definition
+fn generate_const(const_def: &ConstDefinition) -> String {
+    let mut output = String::new();
+
+    // Add file header
+    output.push_str("// Auto-generated by LUMOS\n");
+    output.push_str("// DO NOT EDIT - Changes will be overwritten\n\n");
+
+    output.push_str(&generate_const_definition(const_def));
+
+    output
+}
+
+/// Generate an exported TypeScript constant
+///
+/// A `u128` constant is a `BigInt`, like the fields of that type.
+fn generate_const_definition(const_def: &ConstDefinition) -> String {
+    let mut output = String::new();
+    push_tsdoc(&mut output, &const_def.docs, "");
+    let value = if const_def.type_name == "u128" {
+        format!("BigInt(\"{}\")", const_def.value)
+    } else {
+        const_def.value.to_string()
+    };
+    output.push_str(&format!("export const {} = {};\n", const_def.name, value));
+    output
+}
+
 /// Generate TypeScript code from a type alias definition
 fn generate_type_alias(alias_def: &TypeAliasDefinition) -> String {
     let mut output = String::new();
@@ -106,7 +145,18 @@ fn generate_type_alias(alias_def: &TypeAliasDefinition) -> String {
     collect_imports_from_type(&alias_def.target, &mut needs_publickey);
 
     if needs_publickey {
-        output.push_str("import { PublicKey } from '@solana/web3.js';\n\n");
+        output.push_str("import { PublicKey } from '@solana/web3.js';\n");
+    }
+    if alias_def.newtype {
+        output.push_str("import * as borsh from '@coral-xyz/borsh';\n");
+    }
+    if needs_publickey || alias_def.newtype {
+        output.push('\n');
+    }
+
+    if alias_def.newtype {
+        output.push_str(&generate_newtype(alias_def, &HashSet::new()));
+        return output;
     }
 
     // Generate the type alias
@@ -116,6 +166,35 @@ fn generate_type_alias(alias_def: &TypeAliasDefinition) -> String {
     output
 }
 
+/// Generate a `#[newtype]` alias as a branded type
+///
+/// The brand only exists at compile time, so a `UserId` is still a
+/// `PublicKey` at runtime and its schema is the target's schema. The
+/// constructor of the same name is the one place a plain value becomes a
+/// `UserId`.
+fn generate_newtype(alias_def: &TypeAliasDefinition, pending: &HashSet<String>) -> String {
+    let name = &alias_def.name;
+    let ts_type = map_type_to_typescript(&alias_def.target);
+    let mut output = String::new();
+
+    output.push_str(&format!(
+        "export type {} = {} & {{ readonly __brand: {{ {}: true }} }};\n\n",
+        name, ts_type, name
+    ));
+    output.push_str(&format!("/** Brand a {} as `{}` */\n", ts_type, name));
+    output.push_str(&format!(
+        "export const {} = (value: {}): {} => value as {};\n\n",
+        name, ts_type, name, name
+    ));
+    output.push_str(&format!(
+        "export const {}Schema = {};\n",
+        name,
+        map_type_to_borsh(&alias_def.target, pending)
+    ));
+
+    output
+}
+
 /// Generate TypeScript code from a struct definition
 fn generate_struct(struct_def: &StructDefinition) -> String {
     let mut output = String::new();
@@ -142,15 +221,38 @@ fn generate_struct(struct_def: &StructDefinition) -> String {
         ));
     }
 
+    // Instruction contexts become client-side builders
+    if struct_def.metadata.is_instruction {
+        output.push_str(&generate_instruction_builder(struct_def));
+        return output;
+    }
+
     // Generate interface
     output.push_str(&generate_struct_interface(struct_def));
     output.push('\n');
 
-    // Generate Borsh schema if Solana type
-    if struct_def.metadata.solana {
-        output.push_str(&generate_struct_borsh_schema(struct_def));
+    // Zero-copy types are read in their C layout, other Solana types with Borsh
+    if struct_def.is_zero_copy() {
+        output.push_str(&generate_zero_copy_decoder(struct_def));
+    } else if struct_def.metadata.solana {
+        let pending = HashSet::from([struct_def.name.clone()]);
+        output.push_str(&generate_struct_borsh_schema(struct_def, &pending));
+    }
+
+    // Events also get a discriminator and a log decoder
+    if struct_def.is_event() {
+        output.push('\n');
+        output.push_str(&generate_event_discriminator(struct_def));
+        output.push('\n');
+        output.push_str(&generate_event_decoder(&[struct_def]));
     }
 
+    if uses_fixed_strings(struct_def.fields.iter().map(|f| &f.type_info)) {
+        output.push('\n');
+        output.push_str(FIXED_STRING_HELPERS);
+    }
+    push_layout_helpers(&mut output);
+
     output
 }
 
@@ -180,14 +282,27 @@ fn generate_enum(enum_def: &EnumDefinition) -> String {
         ));
     }
 
+    // Program errors are never serialized, so they get a code map instead of a union
+    if enum_def.is_error_code() {
+        output.push_str(&generate_error_code_enum(enum_def));
+        return output;
+    }
+
+    if enum_def.is_flags() {
+        output.push_str(&generate_flags(enum_def));
+        return output;
+    }
+
     // Generate discriminated union type
     output.push_str(&generate_enum_type(enum_def));
     output.push('\n');
 
     // Generate Borsh schema if Solana type
     if enum_def.metadata.solana {
-        output.push_str(&generate_enum_borsh_schema(enum_def));
+        let pending = HashSet::from([enum_def.name.clone()]);
+        output.push_str(&generate_enum_borsh_schema(enum_def, &pending));
     }
+    push_layout_helpers(&mut output);
 
     output
 }
@@ -322,6 +437,7 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
             TypeDefinition::Struct(s) => STRUCT_BASE + (s.fields.len() * FIELD_SIZE),
             TypeDefinition::Enum(e) => ENUM_BASE + (e.variants.len() * VARIANT_SIZE),
             TypeDefinition::TypeAlias(_) => 100, // Type aliases are small (~50-150 chars)
+            TypeDefinition::Const(_) => 60,
         };
     }
 
@@ -329,6 +445,236 @@ fn estimate_output_size(type_defs: &[TypeDefinition]) -> usize {
 }
 
 pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
+    let type_defs = &apply_renames(type_defs, NameTarget::TypeScript);
+
+    // Generic types get a Borsh schema per concrete instantiation
+    let instances = collect_generic_instances(type_defs);
+
+    render_module(type_defs, &instances, &[])
+}
+
+/// Generate a folder per schema module, mirroring the module tree
+///
+/// Each folder has a `_types.ts` with the module's types, importing what it
+/// uses from other modules' `_types.ts` by relative path, and an `index.ts`
+/// barrel that re-exports them and each child module as a namespace
+/// (`export * as user from './user/index';`). Types the schema module
+/// re-exports with `pub use` are exported from its barrel under the
+/// re-exported name.
+///
+/// The underscore and the explicit `/index` keep a child module named
+/// `types` or `index` from resolving to its parent's files.
+pub fn generate_module_tree(type_defs: &[TypeDefinition]) -> Vec<ModuleFile> {
+    let type_defs = apply_renames(type_defs, NameTarget::TypeScript);
+    let owners: HashMap<&str, &[String]> = type_defs
+        .iter()
+        .map(|t| (t.name(), t.module_path()))
+        .collect();
+
+    // Instances of a generic type are written next to it, wherever they are used
+    let instances = collect_generic_instances(&type_defs);
+    let modules: Vec<(TreeModule, Vec<GenericInstance>)> = split_modules(&type_defs)
+        .into_iter()
+        .map(|module| {
+            let module_instances = instances
+                .iter()
+                .filter(|i| owners.get(i.generic.as_str()) == Some(&module.path.as_slice()))
+                .map(|i| {
+                    let mut instance = i.clone();
+                    instance.generic = short_name(&i.generic).to_string();
+                    for type_info in instance.definition.field_types_mut() {
+                        type_info.walk_mut(&mut |t| {
+                            if let TypeInfo::UserDefined(name) | TypeInfo::Instance { name, .. } = t
+                            {
+                                *name = short_name(name).to_string();
+                            }
+                        });
+                    }
+                    instance
+                })
+                .collect();
+            (module, module_instances)
+        })
+        .collect();
+
+    // What each module exports and which names its code uses, before imports
+    let drafts: Vec<(HashSet<String>, HashSet<String>)> = modules
+        .iter()
+        .map(|(module, module_instances)| {
+            let code = render_module(&module.type_defs, module_instances, &[]);
+            (exported_names(&code), identifiers(&code))
+        })
+        .collect();
+    let exports: HashMap<&[String], &HashSet<String>> = modules
+        .iter()
+        .zip(&drafts)
+        .map(|((module, _), (exported, _))| (module.path.as_slice(), exported))
+        .collect();
+
+    let mut files = Vec::new();
+    for ((module, module_instances), (exported, used)) in modules.iter().zip(&drafts) {
+        let dir: PathBuf = module.path.iter().collect();
+        let has_types = !module.type_defs.is_empty() || !module_instances.is_empty();
+
+        if has_types {
+            // Import every name of another module's types this module's code uses,
+            // such as `User` and `UserSchema` for a field of type `User`
+            let mut specifiers: BTreeMap<&[String], BTreeSet<String>> = BTreeMap::new();
+            for import in &module.imports {
+                let Some(names) = exports.get(import.module.as_slice()) else {
+                    continue;
+                };
+                for name in names.iter().filter(|n| n.starts_with(&import.name)) {
+                    let local = format!("{}{}", import.local, &name[import.name.len()..]);
+                    if used.contains(&local) && !exported.contains(&local) {
+                        let specifier = if &local == name {
+                            local
+                        } else {
+                            format!("{} as {}", name, local)
+                        };
+                        specifiers
+                            .entry(import.module.as_slice())
+                            .or_default()
+                            .insert(specifier);
+                    }
+                }
+            }
+            let imports: Vec<String> = specifiers
+                .into_iter()
+                .map(|(target, names)| {
+                    format!(
+                        "import {{ {} }} from '{}';",
+                        names.into_iter().collect::<Vec<_>>().join(", "),
+                        types_path(&module.path, target)
+                    )
+                })
+                .collect();
+
+            files.push(ModuleFile {
+                path: dir.join(TYPES_FILE),
+                content: render_module(&module.type_defs, module_instances, &imports),
+            });
+        }
+
+        let mut barrel = String::from(
+            "// Auto-generated by LUMOS\n// DO NOT EDIT - Changes will be overwritten\n\n",
+        );
+        if has_types {
+            barrel.push_str("export * from './_types';\n");
+        }
+        for child in &module.children {
+            barrel.push_str(&format!(
+                "export * as {} from './{}/index';\n",
+                child, child
+            ));
+        }
+        for reexport in &module.reexports {
+            barrel.push_str(&format!(
+                "export {{ {} }} from '{}';\n",
+                reexported_names(&modules, reexport).join(", "),
+                types_path(&module.path, &reexport.module)
+            ));
+        }
+        files.push(ModuleFile {
+            path: dir.join("index.ts"),
+            content: barrel,
+        });
+    }
+    files
+}
+
+/// File of a module's own types in a module tree
+const TYPES_FILE: &str = "_types.ts";
+
+/// Relative path from module `from` to the `_types.ts` of module `to`
+fn types_path(from: &[String], to: &[String]) -> String {
+    let (up, down) = relative_path(from, to);
+    let mut path = if up == 0 {
+        "./".to_string()
+    } else {
+        "../".repeat(up)
+    };
+    for segment in down {
+        path.push_str(segment);
+        path.push('/');
+    }
+    path.push_str(TYPES_FILE.trim_end_matches(".ts"));
+    path
+}
+
+/// Export specifiers for every name generated for a re-exported type, such
+/// as `User as Member` and `UserSchema as MemberSchema`
+fn reexported_names(
+    modules: &[(TreeModule, Vec<GenericInstance>)],
+    reexport: &ModuleImport,
+) -> Vec<String> {
+    let Some((module, instances)) = modules.iter().find(|(m, _)| m.path == reexport.module) else {
+        return Vec::new();
+    };
+    let Some(type_def) = module.type_defs.iter().find(|t| t.name() == reexport.name) else {
+        return Vec::new();
+    };
+    let instances: Vec<GenericInstance> = instances
+        .iter()
+        .filter(|i| i.generic == reexport.name)
+        .cloned()
+        .collect();
+
+    let code = render_module(std::slice::from_ref(type_def), &instances, &[]);
+    let mut names: Vec<String> = exported_names(&code).into_iter().collect();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| {
+            let local = name.replacen(&reexport.name, &reexport.local, 1);
+            if local == name {
+                name
+            } else {
+                format!("{} as {}", name, local)
+            }
+        })
+        .collect()
+}
+
+/// Names a generated file declares with `export`
+fn exported_names(code: &str) -> HashSet<String> {
+    code.lines()
+        .filter_map(|line| {
+            let mut words = line.strip_prefix("export ")?.split_whitespace();
+            let mut word = words.next()?;
+            while matches!(word, "declare" | "abstract" | "async" | "default") {
+                word = words.next()?;
+            }
+            if !matches!(
+                word,
+                "interface" | "type" | "const" | "let" | "enum" | "function" | "class"
+            ) {
+                return None;
+            }
+            let name: String = words
+                .next()?
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+                .collect();
+            (!name.is_empty()).then_some(name)
+        })
+        .collect()
+}
+
+/// Every identifier-like word in generated code
+fn identifiers(code: &str) -> HashSet<String> {
+    code.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Generate a file of `type_defs`, with `extra_imports` after its own imports
+fn render_module(
+    type_defs: &[TypeDefinition],
+    instances: &[GenericInstance],
+    extra_imports: &[String],
+) -> String {
     // Estimate output size to reduce allocations for large schemas
     let estimated_capacity = estimate_output_size(type_defs);
     let mut output = String::with_capacity(estimated_capacity);
@@ -356,11 +702,15 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
                 if needs_publickey {
                     all_imports.insert("import { PublicKey } from '@solana/web3.js'".to_string());
                 }
+                if a.newtype {
+                    all_imports.insert("import * as borsh from '@coral-xyz/borsh'".to_string());
+                }
             }
+            TypeDefinition::Const(_) => {}
         }
     }
 
-    // Write imports
+    // Write imports, then the types used from other modules of a module tree
     if !all_imports.is_empty() {
         let mut sorted_imports: Vec<_> = all_imports.into_iter().collect();
         sorted_imports.sort();
@@ -369,14 +719,34 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
         }
         output.push('\n');
     }
+    if !extra_imports.is_empty() {
+        for import in extra_imports {
+            output.push_str(import);
+            output.push('\n');
+        }
+        output.push('\n');
+    }
+
+    // Schemas not defined yet, which the schemas before them refer to lazily
+    let mut pending: HashSet<String> = type_defs
+        .iter()
+        .map(|t| t.name().to_string())
+        .chain(instances.iter().map(|i| i.definition.name().to_string()))
+        .collect();
 
     // Generate each type definition
     for (i, type_def) in type_defs.iter().enumerate() {
         if i > 0 {
             output.push('\n');
         }
+        if i > 0 {
+            pending.remove(type_defs[i - 1].name());
+        }
 
         match type_def {
+            TypeDefinition::Struct(s) if s.metadata.is_instruction => {
+                output.push_str(&generate_instruction_builder(s));
+            }
             TypeDefinition::Struct(s) => {
                 // Generate version constant if present
                 if let Some(version) = &s.metadata.version {
@@ -389,10 +759,17 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
 
                 output.push_str(&generate_struct_interface(s));
 
-                // Add Borsh schema for Solana types
-                if s.metadata.solana {
+                // Add a C-layout decoder for zero-copy types, Borsh schemas for other Solana types
+                let layout = if s.is_zero_copy() {
+                    generate_zero_copy_decoder(s)
+                } else if s.metadata.solana {
+                    generate_struct_borsh_schemas(s, instances, &mut pending)
+                } else {
+                    String::new()
+                };
+                if !layout.is_empty() {
                     output.push('\n');
-                    output.push_str(&generate_struct_borsh_schema(s));
+                    output.push_str(&layout);
                     if i < type_defs.len() - 1 {
                         output.push('\n');
                     }
@@ -408,28 +785,157 @@ pub fn generate_module(type_defs: &[TypeDefinition]) -> String {
                     ));
                 }
 
+                if e.is_error_code() {
+                    output.push_str(&generate_error_code_enum(e));
+                    continue;
+                }
+
+                if e.is_flags() {
+                    output.push_str(&generate_flags(e));
+                    continue;
+                }
+
                 output.push_str(&generate_enum_type(e));
 
-                // Add Borsh schema for Solana types
-                if e.metadata.solana {
+                // Add Borsh schemas for Solana types
+                let schemas = if e.metadata.solana {
+                    generate_enum_borsh_schemas(e, instances, &mut pending)
+                } else {
+                    String::new()
+                };
+                if !schemas.is_empty() {
                     output.push('\n');
-                    output.push_str(&generate_enum_borsh_schema(e));
+                    output.push_str(&schemas);
                     if i < type_defs.len() - 1 {
                         output.push('\n');
                     }
                 }
             }
+            TypeDefinition::TypeAlias(a) if a.newtype => {
+                output.push_str(&generate_newtype(a, &pending));
+            }
             TypeDefinition::TypeAlias(a) => {
                 // Generate type alias (simple export)
                 let ts_type = map_type_to_typescript(&a.target);
                 output.push_str(&format!("export type {} = {};\n", a.name, ts_type));
             }
+            TypeDefinition::Const(c) => {
+                output.push_str(&generate_const_definition(c));
+            }
         }
     }
 
+    // Discriminators and a shared decoder for `#[event]` structs
+    let events: Vec<&StructDefinition> = type_defs
+        .iter()
+        .filter_map(|t| match t {
+            TypeDefinition::Struct(s) if s.is_event() => Some(s),
+            _ => None,
+        })
+        .collect();
+    if !events.is_empty() {
+        output.push('\n');
+        for event in &events {
+            output.push_str(&generate_event_discriminator(event));
+        }
+        output.push('\n');
+        output.push_str(&generate_event_decoder(&events));
+    }
+
+    if uses_fixed_strings(type_defs.iter().flat_map(|t| t.field_types())) {
+        output.push('\n');
+        output.push_str(FIXED_STRING_HELPERS);
+    }
+    push_layout_helpers(&mut output);
+
     output
 }
 
+/// Borsh layout that looks up its schema when used rather than when defined
+///
+/// A recursive type's schema refers to itself, and a schema may refer to one
+/// defined after it; reading either `const` in the initializer would throw.
+const LAZY_LAYOUT_HELPER: &str = "\
+/** Borsh layout of a schema that is looked up when it is first used */
+function lazyLayout<T>(get: () => borsh.Layout<T>): (property?: string) => borsh.Layout<T> {
+  return (property?: string) =>
+    ({
+      span: -1,
+      property,
+      decode: (b: Buffer, offset?: number) => get().decode(b, offset),
+      encode: (src: T, b: Buffer, offset?: number) => get().encode(src, b, offset),
+      getSpan: (b: Buffer, offset?: number) => get().getSpan(b, offset),
+      replicate: (name: string) => lazyLayout(get)(name),
+    }) as borsh.Layout<T>;
+}
+";
+
+/// Borsh layout of a tuple that decodes to an array
+///
+/// Tuples are laid out as a struct keyed by element index, which would
+/// otherwise decode to `{ '0': a, '1': b }` rather than the `[a, b]` of the
+/// tuple's TypeScript type.
+const TUPLE_LAYOUT_HELPER: &str = "\
+/** Borsh layout of a tuple, decoded as an array rather than an object keyed by index */
+function tupleLayout<T extends unknown[]>(
+  layout: borsh.Layout<Record<string, unknown>>,
+): (property?: string) => borsh.Layout<T> {
+  return (property?: string) =>
+    ({
+      span: layout.span,
+      property,
+      decode: (b: Buffer, offset?: number) => Object.values(layout.decode(b, offset)) as T,
+      encode: (src: T, b: Buffer, offset?: number) => layout.encode({ ...src }, b, offset),
+      getSpan: (b: Buffer, offset?: number) => layout.getSpan(b, offset),
+      replicate: (name: string) => tupleLayout<T>(layout)(name),
+    }) as borsh.Layout<T>;
+}
+";
+
+/// Append [`LAZY_LAYOUT_HELPER`] and [`TUPLE_LAYOUT_HELPER`] if the generated
+/// code uses them
+fn push_layout_helpers(output: &mut String) {
+    if output.contains("lazyLayout(() =>") {
+        output.push('\n');
+        output.push_str(LAZY_LAYOUT_HELPER);
+    }
+    if output.contains("tupleLayout(borsh.struct(") {
+        output.push('\n');
+        output.push_str(TUPLE_LAYOUT_HELPER);
+    }
+}
+
+/// Conversions between strings and the zero-padded bytes of `FixedString<N>` fields
+///
+/// Decoding stops at the first zero byte and replaces invalid UTF-8 with
+/// U+FFFD; encoding truncates on a character boundary.
+const FIXED_STRING_HELPERS: &str = "\
+/** Read a zero-padded FixedString field */
+export function fromFixedString(bytes: ArrayLike<number>): string {
+  const data = Uint8Array.from(bytes);
+  const end = data.indexOf(0);
+  return new TextDecoder().decode(end === -1 ? data : data.subarray(0, end));
+}
+
+/** Encode a string as a FixedString of `size` bytes, truncating it if needed */
+export function toFixedString(value: string, size: number): number[] {
+  const encoded = new TextEncoder().encode(value);
+  let len = Math.min(encoded.length, size);
+  // Never split a multi-byte character
+  while (len < encoded.length && (encoded[len] & 0xc0) === 0x80) len--;
+  return Array.from(encoded.subarray(0, len)).concat(new Array<number>(size - len).fill(0));
+}
+";
+
+/// Check if any of `types` is or contains a `FixedString<N>`
+fn uses_fixed_strings<'t>(types: impl IntoIterator<Item = &'t TypeInfo>) -> bool {
+    types.into_iter().any(|type_info| {
+        let mut found = false;
+        type_info.walk(&mut |t| found |= matches!(t, TypeInfo::FixedString(_)));
+        found
+    })
+}
+
 /// Check if a TypeInfo contains u64 or i64 types (which map to TypeScript 'number' with precision limits)
 fn contains_u64_or_i64(type_info: &TypeInfo) -> bool {
     match type_info {
@@ -440,14 +946,42 @@ fn contains_u64_or_i64(type_info: &TypeInfo) -> bool {
         TypeInfo::Array(inner)
         | TypeInfo::FixedArray { element: inner, .. }
         | TypeInfo::Option(inner) => contains_u64_or_i64(inner),
+        TypeInfo::Map { key, value, .. } => contains_u64_or_i64(key) || contains_u64_or_i64(value),
+        TypeInfo::Set { element, .. } => contains_u64_or_i64(element),
+        TypeInfo::Tuple(elements) | TypeInfo::Instance { args: elements, .. } => {
+            elements.iter().any(contains_u64_or_i64)
+        }
+        TypeInfo::Boxed(inner) => contains_u64_or_i64(inner),
         TypeInfo::UserDefined(_) => false, // User-defined types are checked separately
+        TypeInfo::FixedString(_) => false,
     }
 }
 
+/// Emit a `/** ... */` TSDoc block at the given indentation
+fn push_tsdoc(output: &mut String, lines: &[String], indent: &str) {
+    if lines.is_empty() {
+        return;
+    }
+
+    output.push_str(&format!("{}/**\n", indent));
+    for line in lines {
+        if line.is_empty() {
+            output.push_str(&format!("{} *\n", indent));
+        } else {
+            // Keep a stray `*/` in the doc text from closing the comment early
+            output.push_str(&format!("{} * {}\n", indent, line.replace("*/", "*\\/")));
+        }
+    }
+    output.push_str(&format!("{} */\n", indent));
+}
+
 /// Generate TypeScript interface for struct
 fn generate_struct_interface(struct_def: &StructDefinition) -> String {
     let mut output = String::new();
 
+    // Type-level doc comments
+    push_tsdoc(&mut output, &struct_def.metadata.docs, "");
+
     // Generate interface with optional generic parameters
     let interface_name_with_generics = if struct_def.generic_params.is_empty() {
         struct_def.name.clone()
@@ -468,18 +1002,22 @@ fn generate_struct_interface(struct_def: &StructDefinition) -> String {
         let ts_type = map_type_to_typescript(&field.type_info);
         let optional_marker = if field.optional { "?" } else { "" };
 
-        // Add JSDoc warning for u64/i64 types (precision limitation)
+        // Field docs, followed by a JSDoc warning for u64/i64 types (precision limitation)
+        let mut doc_lines = field.docs.clone();
         if contains_u64_or_i64(&field.type_info) {
-            output.push_str("  /**\n");
-            output.push_str("   * WARNING: TypeScript 'number' has precision limit of 2^53-1 (9,007,199,254,740,991).\n");
-            output.push_str(
-                "   * For Solana lamports or large values, ensure they stay within safe range.\n",
+            if !doc_lines.is_empty() {
+                doc_lines.push(String::new());
+            }
+            doc_lines.push("WARNING: TypeScript 'number' has precision limit of 2^53-1 (9,007,199,254,740,991).".to_string());
+            doc_lines.push(
+                "For Solana lamports or large values, ensure they stay within safe range."
+                    .to_string(),
             );
-            output.push_str(
-                "   * Values exceeding this limit will lose precision during serialization.\n",
+            doc_lines.push(
+                "Values exceeding this limit will lose precision during serialization.".to_string(),
             );
-            output.push_str("   */\n");
         }
+        push_tsdoc(&mut output, &doc_lines, "  ");
 
         output.push_str(&format!(
             "  {}{}: {};\n",
@@ -495,6 +1033,84 @@ fn generate_struct_interface(struct_def: &StructDefinition) -> String {
         output.push_str(&generate_derive_helpers(struct_def));
     }
 
+    if struct_def.has_constraints() {
+        output.push('\n');
+        output.push_str(&generate_validate_function(struct_def, &["u128", "i128"]));
+    }
+
+    output
+}
+
+/// Generate `validate{Name}()`, which throws if a `#[range]` or `#[pattern]` constraint fails
+///
+/// `bigint_types` lists the integer primitives represented as `bigint`, whose
+/// bounds need the `n` suffix.
+pub(crate) fn generate_validate_function(
+    struct_def: &StructDefinition,
+    bigint_types: &[&str],
+) -> String {
+    let generics = if struct_def.generic_params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", struct_def.generic_params.join(", "))
+    };
+
+    let mut output = String::new();
+    output.push_str("/** Check the value constraints declared in the schema */\n");
+    output.push_str(&format!(
+        "export function validate{}{}(value: {}{}): void {{\n",
+        struct_def.name, generics, struct_def.name, generics
+    ));
+
+    for field in &struct_def.fields {
+        let value = format!("value.{}", field.name);
+        let value_type = match &field.type_info {
+            TypeInfo::Option(inner) => inner.as_ref(),
+            other => other,
+        };
+        let suffix = match value_type {
+            TypeInfo::Primitive(name) if bigint_types.contains(&name.as_str()) => "n",
+            _ => "",
+        };
+
+        for constraint in &field.constraints {
+            let violated = match constraint {
+                Constraint::Range { min, max } => {
+                    let mut checks = Vec::new();
+                    if let Some(min) = min {
+                        checks.push(format!("{} < {}{}", value, min, suffix));
+                    }
+                    if let Some(max) = max {
+                        checks.push(format!("{} > {}{}", value, max, suffix));
+                    }
+                    if checks.is_empty() {
+                        continue;
+                    }
+                    checks.join(" || ")
+                }
+                Constraint::Pattern(pattern) => format!(
+                    "!new RegExp({}).test({})",
+                    serde_json::to_string(pattern).unwrap_or_default(),
+                    value
+                ),
+            };
+            // Optional fields are only checked when present
+            let condition = if matches!(field.type_info, TypeInfo::Option(_)) {
+                format!("{} != null && ({})", value, violated)
+            } else {
+                violated
+            };
+            let message = format!("{} {}", field.name, constraint);
+            output.push_str(&format!("  if ({}) {{\n", condition));
+            output.push_str(&format!(
+                "    throw new Error({});\n",
+                serde_json::to_string(&message).unwrap_or_default()
+            ));
+            output.push_str("  }\n");
+        }
+    }
+
+    output.push_str("}\n");
     output
 }
 
@@ -573,7 +1189,7 @@ fn generate_field_equality(type_info: &TypeInfo, field_name: &str) -> String {
         TypeInfo::Primitive(t) if t == "PublicKey" || t == "Pubkey" => {
             format!("a.{}.equals(b.{})", field_name, field_name)
         }
-        TypeInfo::Array(_) => {
+        TypeInfo::Array(_) | TypeInfo::FixedString(_) => {
             format!(
                 "a.{}.length === b.{}.length && a.{}.every((v, i) => v === b.{}[i])",
                 field_name, field_name, field_name, field_name
@@ -617,6 +1233,8 @@ fn generate_hash_function(name: &str) -> String {
 }
 
 /// Generate default factory function for Default derive
+///
+/// Fields with `#[default(..)]` use the declared value, the rest a zero value.
 fn generate_default_function(struct_def: &StructDefinition) -> String {
     let name = &struct_def.name;
     let mut output = String::new();
@@ -633,7 +1251,10 @@ fn generate_default_function(struct_def: &StructDefinition) -> String {
     output.push_str("  return {\n");
 
     for field in &struct_def.fields {
-        let default_value = get_default_value(&field.type_info);
+        let default_value = match &field.default {
+            Some(value) => default_value_literal(value, &field.type_info),
+            None => get_default_value(&field.type_info),
+        };
         output.push_str(&format!("    {}: {},\n", field.name, default_value));
     }
 
@@ -643,6 +1264,23 @@ fn generate_default_function(struct_def: &StructDefinition) -> String {
     output
 }
 
+/// TypeScript literal for a `#[default(..)]` value
+///
+/// 128-bit integers become `BigInt("..")`, which, unlike a `..n` literal,
+/// needs no ES2020 compilation target.
+pub(crate) fn default_value_literal(value: &DefaultValue, type_info: &TypeInfo) -> String {
+    match value {
+        DefaultValue::Integer(value) if matches!(type_info, TypeInfo::Primitive(t) if t == "u128" || t == "i128") =>
+        {
+            format!("BigInt(\"{}\")", value)
+        }
+        DefaultValue::Integer(value) => value.to_string(),
+        DefaultValue::Float(value) => value.to_string(),
+        DefaultValue::Bool(value) => value.to_string(),
+        DefaultValue::String(value) => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
 /// Get default value for a type
 fn get_default_value(type_info: &TypeInfo) -> String {
     match type_info {
@@ -657,9 +1295,23 @@ fn get_default_value(type_info: &TypeInfo) -> String {
             _ => "undefined as any".to_string(),
         },
         TypeInfo::Array(_) | TypeInfo::FixedArray { .. } => "[]".to_string(),
+        TypeInfo::FixedString(size) => format!("new Array<number>({}).fill(0)", size),
         TypeInfo::Option(_) => "undefined".to_string(),
+        TypeInfo::Map { .. } => "new Map()".to_string(),
+        TypeInfo::Set { .. } => "[]".to_string(),
+        TypeInfo::Tuple(elements) => format!(
+            "[{}]",
+            elements
+                .iter()
+                .map(get_default_value)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        TypeInfo::Boxed(inner) => get_default_value(inner),
         TypeInfo::Generic(_) => "undefined as any".to_string(),
-        TypeInfo::UserDefined(name) => format!("{}Default()", to_camel_case(name)),
+        TypeInfo::UserDefined(name) | TypeInfo::Instance { name, .. } => {
+            format!("{}Default()", to_camel_case(name))
+        }
     }
 }
 
@@ -738,7 +1390,12 @@ fn to_camel_case(s: &str) -> String {
 }
 
 /// Generate Borsh schema for struct serialization
-fn generate_struct_borsh_schema(struct_def: &StructDefinition) -> String {
+///
+/// Schemas in `pending`, not defined yet, are referred to lazily.
+fn generate_struct_borsh_schema(
+    struct_def: &StructDefinition,
+    pending: &HashSet<String>,
+) -> String {
     let mut output = String::new();
 
     output.push_str(&format!(
@@ -748,7 +1405,7 @@ fn generate_struct_borsh_schema(struct_def: &StructDefinition) -> String {
 
     // Generate Borsh field definitions
     for field in &struct_def.fields {
-        let borsh_type = map_type_to_borsh(&field.type_info);
+        let borsh_type = map_type_to_borsh(&field.type_info, pending);
         output.push_str(&format!("  {}('{}'),\n", borsh_type, field.name));
     }
 
@@ -757,6 +1414,161 @@ fn generate_struct_borsh_schema(struct_def: &StructDefinition) -> String {
     output
 }
 
+/// Borsh schemas for a struct, one per concrete instance if it is generic
+///
+/// A generic parameter has no Borsh layout of its own, so `Wrapper<T>` gets
+/// `WrapperU64Schema` for each `Wrapper<u64>` used in the schema instead.
+///
+/// Each schema is removed from `pending` once it is defined.
+fn generate_struct_borsh_schemas(
+    struct_def: &StructDefinition,
+    instances: &[GenericInstance],
+    pending: &mut HashSet<String>,
+) -> String {
+    if struct_def.generic_params.is_empty() {
+        return generate_struct_borsh_schema(struct_def, pending);
+    }
+    let mut schemas = Vec::new();
+    for instance in instances.iter().filter(|i| i.generic == struct_def.name) {
+        if let TypeDefinition::Struct(s) = &instance.definition {
+            schemas.push(generate_struct_borsh_schema(s, pending));
+            pending.remove(&s.name);
+        }
+    }
+    schemas.join("\n")
+}
+
+/// Generate a size constant and a C-layout decoder for a `#[zero_copy]` struct
+///
+/// Zero-copy accounts are stored in their `#[repr(C)]` layout rather than as Borsh,
+/// so fields are read in place at the offsets computed during transformation.
+/// Account decoders skip the 8-byte discriminator by default.
+fn generate_zero_copy_decoder(struct_def: &StructDefinition) -> String {
+    let mut output = String::new();
+    let Some(layout) = &struct_def.metadata.c_layout else {
+        return output;
+    };
+    let is_account = struct_def
+        .metadata
+        .attributes
+        .contains(&"account".to_string());
+
+    output.push_str(&format!(
+        "/** Size of `{}` in bytes (C layout) */\n",
+        struct_def.name
+    ));
+    output.push_str(&format!(
+        "export const {}Size = {};\n\n",
+        struct_def.name, layout.size
+    ));
+
+    output.push_str(&format!(
+        "/** Decode `{}` from its C (`#[repr(C)]`) layout starting at `offset` */\n",
+        struct_def.name
+    ));
+    output.push_str(&format!(
+        "export function decode{}(data: Uint8Array, offset = {}): {} {{\n",
+        struct_def.name,
+        if is_account { 8 } else { 0 },
+        struct_def.name
+    ));
+    output
+        .push_str("  const view = new DataView(data.buffer, data.byteOffset, data.byteLength);\n");
+    output.push_str("  return {\n");
+    for (i, field) in struct_def.fields.iter().enumerate() {
+        let start = layout.offsets[i];
+        let end = layout.offsets.get(i + 1).copied().unwrap_or(layout.size);
+        output.push_str(&format!(
+            "    {}: {},\n",
+            field.name,
+            c_layout_reader(&field.type_info, "offset", start, end - start, 0)
+        ));
+    }
+    output.push_str("  };\n");
+    output.push_str("}\n");
+
+    output
+}
+
+/// Render `base + bytes`, omitting a zero offset
+fn offset_expr(base: &str, bytes: usize) -> String {
+    if bytes == 0 {
+        base.to_string()
+    } else {
+        format!("{} + {}", base, bytes)
+    }
+}
+
+/// Expression reading a Pod value of `size` bytes at byte offset `base + at`
+///
+/// `depth` names the index variable of nested fixed-size arrays (`i`, `j`, ...).
+fn c_layout_reader(
+    type_info: &TypeInfo,
+    base: &str,
+    at: usize,
+    size: usize,
+    depth: usize,
+) -> String {
+    let pos = offset_expr(base, at);
+    match type_info {
+        TypeInfo::Primitive(name) => match name.as_str() {
+            "u8" => format!("view.getUint8({})", pos),
+            "i8" => format!("view.getInt8({})", pos),
+            "u16" => format!("view.getUint16({}, true)", pos),
+            "i16" => format!("view.getInt16({}, true)", pos),
+            "u32" => format!("view.getUint32({}, true)", pos),
+            "i32" => format!("view.getInt32({}, true)", pos),
+            "f32" => format!("view.getFloat32({}, true)", pos),
+            "f64" => format!("view.getFloat64({}, true)", pos),
+            "u64" => format!("Number(view.getBigUint64({}, true))", pos),
+            "i64" => format!("Number(view.getBigInt64({}, true))", pos),
+            // Little-endian: the high 64 bits come second
+            "u128" => format!(
+                "(view.getBigUint64({}, true) << 64n) | view.getBigUint64({}, true)",
+                offset_expr(base, at + 8),
+                pos
+            ),
+            "i128" => format!(
+                "(view.getBigInt64({}, true) << 64n) | view.getBigUint64({}, true)",
+                offset_expr(base, at + 8),
+                pos
+            ),
+            "Pubkey" | "PublicKey" => format!(
+                "new PublicKey(data.subarray({}, {}))",
+                pos,
+                offset_expr(base, at + 32)
+            ),
+            _ => format!("undefined as never /* unsupported type {} */", name),
+        },
+        TypeInfo::FixedArray { element, size: len } if matches!(element.as_ref(), TypeInfo::Primitive(name) if name == "u8") =>
+        {
+            format!(
+                "Array.from(data.subarray({}, {}))",
+                pos,
+                offset_expr(base, at + len)
+            )
+        }
+        TypeInfo::FixedString(len) => format!(
+            "Array.from(data.subarray({}, {}))",
+            pos,
+            offset_expr(base, at + len)
+        ),
+        TypeInfo::FixedArray { element, size: len } => {
+            let index = ["i", "j", "k", "l"][depth.min(3)];
+            let stride = if *len == 0 { 0 } else { size / len };
+            let element_base = format!("{} + {} * {}", pos, index, stride);
+            format!(
+                "Array.from({{ length: {} }}, (_, {}) => {})",
+                len,
+                index,
+                c_layout_reader(element, &element_base, 0, stride, depth + 1)
+            )
+        }
+        TypeInfo::UserDefined(name) => format!("decode{}(data, {})", name, pos),
+        _ => "undefined as never /* not Pod */".to_string(),
+    }
+}
+
 /// Generate TypeScript discriminated union type for enum
 fn generate_enum_type(enum_def: &EnumDefinition) -> String {
     let mut output = String::new();
@@ -770,15 +1582,19 @@ fn generate_enum_type(enum_def: &EnumDefinition) -> String {
         }
     });
 
-    // Add JSDoc warning if enum contains u64/i64
+    // Type-level docs, followed by a JSDoc warning if enum contains u64/i64
+    let mut doc_lines = enum_def.metadata.docs.clone();
     if has_u64_i64 {
-        output.push_str("/**\n");
-        output.push_str(" * WARNING: Some variants contain TypeScript 'number' types with precision limit of 2^53-1.\n");
-        output.push_str(
-            " * Large values (e.g., Solana lamports) may lose precision during serialization.\n",
+        if !doc_lines.is_empty() {
+            doc_lines.push(String::new());
+        }
+        doc_lines.push("WARNING: Some variants contain TypeScript 'number' types with precision limit of 2^53-1.".to_string());
+        doc_lines.push(
+            "Large values (e.g., Solana lamports) may lose precision during serialization."
+                .to_string(),
         );
-        output.push_str(" */\n");
     }
+    push_tsdoc(&mut output, &doc_lines, "");
 
     // Generate discriminated union type with optional generic parameters
     let type_name_with_generics = if enum_def.generic_params.is_empty() {
@@ -791,11 +1607,13 @@ fn generate_enum_type(enum_def: &EnumDefinition) -> String {
     for variant in &enum_def.variants {
         let prefix = "  | ";
 
+        push_tsdoc(&mut output, variant.docs(), "  ");
+
         match variant {
-            EnumVariantDefinition::Unit { name } => {
+            EnumVariantDefinition::Unit { name, .. } => {
                 output.push_str(&format!("{}{{ kind: '{}' }}\n", prefix, name));
             }
-            EnumVariantDefinition::Tuple { name, types } => {
+            EnumVariantDefinition::Tuple { name, types, .. } => {
                 output.push_str(&format!("{}{{ kind: '{}'", prefix, name));
                 for (idx, type_info) in types.iter().enumerate() {
                     let ts_type = map_type_to_typescript(type_info);
@@ -803,11 +1621,18 @@ fn generate_enum_type(enum_def: &EnumDefinition) -> String {
                 }
                 output.push_str(" }\n");
             }
-            EnumVariantDefinition::Struct { name, fields } => {
+            EnumVariantDefinition::Struct { name, fields, .. } => {
                 output.push_str(&format!("{}{{ kind: '{}'", prefix, name));
                 for field in fields {
                     let ts_type = map_type_to_typescript(&field.type_info);
-                    output.push_str(&format!("; {}: {}", field.name, ts_type));
+                    output.push_str("; ");
+                    if !field.docs.is_empty() {
+                        output.push_str(&format!(
+                            "/** {} */ ",
+                            field.docs.join(" ").replace("*/", "*\\/")
+                        ));
+                    }
+                    output.push_str(&format!("{}: {}", field.name, ts_type));
                 }
                 output.push_str(" }\n");
             }
@@ -819,52 +1644,230 @@ fn generate_enum_type(enum_def: &EnumDefinition) -> String {
     output
 }
 
-/// Generate Borsh schema for enum serialization
-fn generate_enum_borsh_schema(enum_def: &EnumDefinition) -> String {
+/// Generate the error map and `fromCode()` lookup for an `#[error_code]` enum
+fn generate_error_code_enum(enum_def: &EnumDefinition) -> String {
+    let mut output = String::new();
+    let name = &enum_def.name;
+    let codes = enum_def.error_codes();
+
+    let variant_names: Vec<String> = enum_def
+        .variants
+        .iter()
+        .map(|v| format!("'{}'", v.name()))
+        .collect();
+    output.push_str(&format!(
+        "export type {}Name = {};\n\n",
+        name,
+        variant_names.join(" | ")
+    ));
+
+    output.push_str(&format!("export interface {}Info {{\n", name));
+    output.push_str("  code: number;\n");
+    output.push_str(&format!("  name: {}Name;\n", name));
+    output.push_str("  msg: string | null;\n");
+    output.push_str("}\n\n");
+
+    output.push_str(&format!(
+        "/** {} entries keyed by on-chain error code */\n",
+        name
+    ));
+    output.push_str(&format!(
+        "export const {}Map: Record<number, {}Info> = {{\n",
+        name, name
+    ));
+    for (code, variant) in &codes {
+        let msg = variant
+            .msg()
+            .map(|m| format!("{:?}", m))
+            .unwrap_or_else(|| "null".to_string());
+        output.push_str(&format!(
+            "  {}: {{ code: {}, name: '{}', msg: {} }},\n",
+            code,
+            code,
+            variant.name(),
+            msg
+        ));
+    }
+    output.push_str("};\n\n");
+
+    push_tsdoc(&mut output, &enum_def.metadata.docs, "");
+    output.push_str(&format!("export const {} = {{\n", name));
+    for (code, variant) in &codes {
+        push_tsdoc(&mut output, variant.docs(), "  ");
+        output.push_str(&format!("  {}: {},\n", variant.name(), code));
+    }
+    output
+        .push_str("  /** Look up an error by code; null for codes this enum does not define */\n");
+    output.push_str(&format!(
+        "  fromCode(code: number): {}Info | null {{\n",
+        name
+    ));
+    output.push_str(&format!("    return {}Map[code] ?? null;\n", name));
+    output.push_str("  },\n");
+    output.push_str("} as const;\n");
+
+    output
+}
+
+/// Generate a `#[flags]` enum as a number type with a helper object of its flags
+///
+/// The helpers keep results unsigned (`>>> 0`), since JavaScript bitwise
+/// operators work on signed 32-bit integers.
+fn generate_flags(enum_def: &EnumDefinition) -> String {
+    let name = &enum_def.name;
     let mut output = String::new();
 
+    push_tsdoc(&mut output, &enum_def.metadata.docs, "");
+    output.push_str(&format!("export type {} = number;\n\n", name));
+
+    output.push_str(&format!("export const {} = {{\n", name));
+    for (variant, bit) in enum_def.variants.iter().zip(enum_def.discriminants()) {
+        push_tsdoc(&mut output, variant.docs(), "  ");
+        output.push_str(&format!("  {}: {},\n", variant.name(), bit));
+    }
+    output.push_str("  /** Every flag set */\n");
+    output.push_str(&format!("  all: {},\n", enum_def.flags_mask()));
+    output.push_str("  /** Check if every flag in `flag` is set */\n");
+    output.push_str(&format!(
+        "  has(flags: {}, flag: {}): boolean {{\n",
+        name, name
+    ));
+    output.push_str("    return ((flags & flag) >>> 0) === flag;\n");
+    output.push_str("  },\n");
+    output.push_str("  /** Set the flags in `flag` */\n");
+    output.push_str(&format!(
+        "  add(flags: {}, flag: {}): {} {{\n",
+        name, name, name
+    ));
+    output.push_str("    return (flags | flag) >>> 0;\n");
+    output.push_str("  },\n");
+    output.push_str("  /** Clear the flags in `flag` */\n");
     output.push_str(&format!(
-        "export const {}Schema = borsh.rustEnum([\n",
-        enum_def.name
+        "  remove(flags: {}, flag: {}): {} {{\n",
+        name, name, name
     ));
+    output.push_str("    return (flags & ~flag) >>> 0;\n");
+    output.push_str("  },\n");
+    output.push_str("} as const;\n");
+
+    // Serialized as the packed integer
+    if enum_def.metadata.solana {
+        output.push_str(&format!(
+            "\nexport const {}Schema = borsh.{};\n",
+            name,
+            enum_def.repr().name()
+        ));
+    }
+
+    output
+}
+
+/// Generate Borsh schema for enum serialization
+///
+/// Enums with explicit discriminants or a `#[repr]` wider than `u8` pass the
+/// tag layout to `rustEnum`; sparse discriminants register each variant
+/// under its own tag with `addVariant`.
+fn generate_enum_borsh_schema(enum_def: &EnumDefinition, pending: &HashSet<String>) -> String {
+    let mut output = String::new();
+    let name = &enum_def.name;
+    let tag_layout = format!("borsh.{}()", enum_def.repr().name());
+    let discriminants = enum_def.discriminants();
+    let positional = discriminants
+        .iter()
+        .enumerate()
+        .all(|(i, d)| *d == i as u64);
+
+    if !positional {
+        output.push_str(&format!(
+            "export const {}Schema = borsh.rustEnum([], undefined, {});\n",
+            name, tag_layout
+        ));
+        for (variant, tag) in enum_def.variants.iter().zip(discriminants) {
+            output.push_str(&format!(
+                "({}Schema as any).addVariant({}, {}, '{}');\n",
+                name,
+                tag,
+                enum_variant_layout(variant, "", pending),
+                variant.name()
+            ));
+        }
+        return output;
+    }
+
+    output.push_str(&format!("export const {}Schema = borsh.rustEnum([\n", name));
 
     // Generate Borsh variant definitions
     for variant in &enum_def.variants {
-        match variant {
-            EnumVariantDefinition::Unit { name } => {
-                output.push_str(&format!("  borsh.unit('{}'),\n", name));
-            }
-            EnumVariantDefinition::Tuple { name, types } => {
-                output.push_str("  borsh.tuple([\n");
-                for type_info in types {
-                    let borsh_type = map_type_to_borsh(type_info);
-                    output.push_str(&format!("    {},\n", borsh_type));
-                }
-                output.push_str(&format!("  ], '{}'),\n", name));
-            }
-            EnumVariantDefinition::Struct { name, fields } => {
-                output.push_str("  borsh.struct([\n");
-                for field in fields {
-                    let borsh_type = map_type_to_borsh(&field.type_info);
-                    output.push_str(&format!("    {}('{}'),\n", borsh_type, field.name));
-                }
-                output.push_str(&format!("  ], '{}'),\n", name));
-            }
-        }
+        output.push_str(&format!(
+            "  {},\n",
+            enum_variant_layout(variant, "  ", pending)
+        ));
     }
 
-    output.push_str("]);\n");
+    if enum_def.has_custom_discriminants() {
+        output.push_str(&format!("], undefined, {});\n", tag_layout));
+    } else {
+        output.push_str("]);\n");
+    }
 
     output
 }
 
+/// Borsh schemas for an enum, one per concrete instance if it is generic
+fn generate_enum_borsh_schemas(
+    enum_def: &EnumDefinition,
+    instances: &[GenericInstance],
+    pending: &mut HashSet<String>,
+) -> String {
+    if enum_def.generic_params.is_empty() {
+        return generate_enum_borsh_schema(enum_def, pending);
+    }
+    let mut schemas = Vec::new();
+    for instance in instances.iter().filter(|i| i.generic == enum_def.name) {
+        if let TypeDefinition::Enum(e) = &instance.definition {
+            schemas.push(generate_enum_borsh_schema(e, pending));
+            pending.remove(&e.name);
+        }
+    }
+    schemas.join("\n")
+}
+
+/// Borsh layout of a single enum variant, with inner lines indented by `indent`
+fn enum_variant_layout(
+    variant: &EnumVariantDefinition,
+    indent: &str,
+    pending: &HashSet<String>,
+) -> String {
+    match variant {
+        EnumVariantDefinition::Unit { name, .. } => format!("borsh.unit('{}')", name),
+        EnumVariantDefinition::Tuple { name, types, .. } => {
+            let mut layout = "borsh.tuple([\n".to_string();
+            for type_info in types {
+                let borsh_type = map_type_to_borsh(type_info, pending);
+                layout.push_str(&format!("{}  {},\n", indent, borsh_type));
+            }
+            layout.push_str(&format!("{}], '{}')", indent, name));
+            layout
+        }
+        EnumVariantDefinition::Struct { name, fields, .. } => {
+            let mut layout = "borsh.struct([\n".to_string();
+            for field in fields {
+                let borsh_type = map_type_to_borsh(&field.type_info, pending);
+                layout.push_str(&format!("{}  {}('{}'),\n", indent, borsh_type, field.name));
+            }
+            layout.push_str(&format!("{}], '{}')", indent, name));
+            layout
+        }
+    }
+}
+
 /// Collect required imports based on enum definition
 fn collect_enum_imports(enum_def: &EnumDefinition) -> HashSet<String> {
     let mut imports = HashSet::new();
 
     // Check variant types for imports
     let mut needs_publickey = false;
-    let needs_borsh = enum_def.metadata.solana;
+    let needs_borsh = enum_def.metadata.solana && !enum_def.is_error_code();
 
     for variant in &enum_def.variants {
         match variant {
@@ -897,9 +1900,20 @@ fn collect_enum_imports(enum_def: &EnumDefinition) -> HashSet<String> {
 fn collect_struct_imports(struct_def: &StructDefinition) -> HashSet<String> {
     let mut imports = HashSet::new();
 
+    // Instruction builders only need account keys plus whatever their arguments use
+    if struct_def.metadata.is_instruction {
+        imports.insert("import { PublicKey } from '@solana/web3.js'".to_string());
+        imports.insert("import { TransactionInstruction } from '@solana/web3.js'".to_string());
+        imports.insert("import { Buffer } from 'buffer'".to_string());
+        if let Some(args) = struct_def.instruction_args_struct() {
+            imports.extend(collect_struct_imports(&args));
+        }
+        return imports;
+    }
+
     // Check field types for imports
     let mut needs_publickey = false;
-    let needs_borsh = struct_def.metadata.solana;
+    let needs_borsh = struct_def.metadata.solana && !struct_def.is_zero_copy();
 
     for field in &struct_def.fields {
         collect_imports_from_type(&field.type_info, &mut needs_publickey);
@@ -913,9 +1927,183 @@ fn collect_struct_imports(struct_def: &StructDefinition) -> HashSet<String> {
         imports.insert("import * as borsh from '@coral-xyz/borsh'".to_string());
     }
 
+    if struct_def.is_event() {
+        imports.insert("import { Buffer } from 'buffer'".to_string());
+    }
+
     imports
 }
 
+/// Generate a client-side builder for an `#[instruction]` struct
+///
+/// Emits the instruction's sighash, a `{Name}Accounts` interface of account keys,
+/// the `{Name}Args` interface and schema (when the instruction takes arguments)
+/// and a `create{Name}Instruction` function that assembles the keys in field order.
+fn generate_instruction_builder(ix: &StructDefinition) -> String {
+    let mut output = String::new();
+    let name = instruction_name(&ix.name);
+
+    let bytes: Vec<String> = instruction_discriminator(&ix.name)
+        .iter()
+        .map(|b| b.to_string())
+        .collect();
+    output.push_str(&format!(
+        "/** Sighash Anchor prefixes to `{}` instruction data */\n",
+        name
+    ));
+    output.push_str(&format!(
+        "export const {}Discriminator = Buffer.from([{}]);\n\n",
+        ix.name,
+        bytes.join(", ")
+    ));
+
+    output.push_str(&format!("/** Accounts of the `{}` instruction */\n", name));
+    output.push_str(&format!("export interface {}Accounts {{\n", ix.name));
+    for field in &ix.fields {
+        push_tsdoc(&mut output, &field.docs, "  ");
+        let optional = if field.optional { "?" } else { "" };
+        output.push_str(&format!("  {}{}: PublicKey;\n", field.name, optional));
+    }
+    output.push_str("}\n\n");
+
+    let args_struct = ix.instruction_args_struct();
+    if let Some(args) = &args_struct {
+        output.push_str(&generate_struct_interface(args));
+        output.push('\n');
+        output.push_str(&generate_struct_borsh_schema(args, &HashSet::new()));
+        output.push('\n');
+    }
+
+    if ix.metadata.docs.is_empty() {
+        output.push_str(&format!("/** Build a `{}` instruction */\n", name));
+    } else {
+        push_tsdoc(&mut output, &ix.metadata.docs, "");
+    }
+    output.push_str(&format!("export function create{}Instruction(\n", ix.name));
+    output.push_str("  programId: PublicKey,\n");
+    output.push_str(&format!("  accounts: {}Accounts,\n", ix.name));
+    if let Some(args) = &args_struct {
+        output.push_str(&format!("  args: {},\n", args.name));
+    }
+    output.push_str("): TransactionInstruction {\n");
+
+    let data = match &args_struct {
+        Some(args) => {
+            // Same scratch size Anchor's instruction coder uses
+            output.push_str("  const buffer = Buffer.alloc(1000);\n");
+            output.push_str(&format!(
+                "  const length = {}Schema.encode(args, buffer);\n",
+                args.name
+            ));
+            format!(
+                "Buffer.concat([{}Discriminator, buffer.subarray(0, length)])",
+                ix.name
+            )
+        }
+        None => format!("Buffer.from({}Discriminator)", ix.name),
+    };
+
+    output.push_str("  return new TransactionInstruction({\n");
+    output.push_str("    programId,\n");
+    output.push_str("    keys: [\n");
+    for field in &ix.fields {
+        let flags = account_meta_flags(field);
+        let meta = |pubkey: &str| {
+            format!(
+                "{{ pubkey: {}, isSigner: {}, isWritable: {} }}",
+                pubkey, flags.is_signer, flags.is_writable
+            )
+        };
+        let account = format!("accounts.{}", field.name);
+        if field.optional {
+            // Anchor reads a missing optional account as the program id
+            output.push_str(&format!(
+                "      {} ? {} : {{ pubkey: programId, isSigner: false, isWritable: false }},\n",
+                account,
+                meta(&account)
+            ));
+        } else {
+            output.push_str(&format!("      {},\n", meta(&account)));
+        }
+    }
+    output.push_str("    ],\n");
+    output.push_str(&format!("    data: {},\n", data));
+    output.push_str("  });\n");
+    output.push_str("}\n");
+
+    output
+}
+
+/// Generate the 8-byte discriminator Anchor prefixes to an event's data
+fn generate_event_discriminator(struct_def: &StructDefinition) -> String {
+    let bytes: Vec<String> = event_discriminator(&struct_def.name)
+        .iter()
+        .map(|b| b.to_string())
+        .collect();
+    format!(
+        "export const {}Discriminator = Buffer.from([{}]);\n",
+        struct_def.name,
+        bytes.join(", ")
+    )
+}
+
+/// Generate `decodeEvent`/`decodeEvents` for Anchor `Program data:` log lines
+fn generate_event_decoder(events: &[&StructDefinition]) -> String {
+    let mut output = String::new();
+
+    output.push_str("/** Events that can appear in `Program data:` log lines */\n");
+    output.push_str("export type ProgramEvent =\n");
+    for (i, event) in events.iter().enumerate() {
+        let terminator = if i == events.len() - 1 { ";" } else { "" };
+        output.push_str(&format!(
+            "  | {{ name: '{}'; data: {} }}{}\n",
+            event.name, event.name, terminator
+        ));
+    }
+    output.push('\n');
+
+    output.push_str("const PROGRAM_DATA_PREFIX = 'Program data: ';\n\n");
+
+    output.push_str("/**\n");
+    output.push_str(" * Decode a `Program data:` log line written by Anchor's `emit!`.\n");
+    output.push_str(" * Returns null for other log lines and unknown discriminators.\n");
+    output.push_str(" */\n");
+    output.push_str("export function decodeEvent(logLine: string): ProgramEvent | null {\n");
+    output.push_str("  if (!logLine.startsWith(PROGRAM_DATA_PREFIX)) {\n");
+    output.push_str("    return null;\n");
+    output.push_str("  }\n");
+    output.push_str(
+        "  const bytes = Buffer.from(logLine.slice(PROGRAM_DATA_PREFIX.length), 'base64');\n",
+    );
+    output.push_str("  if (bytes.length < 8) {\n");
+    output.push_str("    return null;\n");
+    output.push_str("  }\n");
+    output.push_str("  const discriminator = bytes.subarray(0, 8);\n");
+    output.push_str("  const payload = bytes.subarray(8);\n");
+    for event in events {
+        output.push_str(&format!(
+            "  if (discriminator.equals({}Discriminator)) {{\n",
+            event.name
+        ));
+        output.push_str(&format!(
+            "    return {{ name: '{}', data: {}Schema.decode(payload) }};\n",
+            event.name, event.name
+        ));
+        output.push_str("  }\n");
+    }
+    output.push_str("  return null;\n");
+    output.push_str("}\n\n");
+
+    output.push_str("/** Decode every event in a transaction's log messages */\n");
+    output.push_str("export function decodeEvents(logs: string[]): ProgramEvent[] {\n");
+    output.push_str("  return logs\n");
+    output.push_str("    .map((line) => decodeEvent(line))\n");
+    output.push_str("    .filter((event): event is ProgramEvent => event !== null);\n");
+    output.push_str("}\n");
+
+    output
+}
+
 /// Recursively check if type needs specific imports
 fn collect_imports_from_type(type_info: &TypeInfo, needs_publickey: &mut bool) {
     match type_info {
@@ -936,9 +2124,25 @@ fn collect_imports_from_type(type_info: &TypeInfo, needs_publickey: &mut bool) {
         TypeInfo::Option(inner) => {
             collect_imports_from_type(inner, needs_publickey);
         }
+        TypeInfo::Map { key, value, .. } => {
+            collect_imports_from_type(key, needs_publickey);
+            collect_imports_from_type(value, needs_publickey);
+        }
+        TypeInfo::Set { element, .. } => {
+            collect_imports_from_type(element, needs_publickey);
+        }
+        TypeInfo::Tuple(elements) | TypeInfo::Instance { args: elements, .. } => {
+            for element in elements {
+                collect_imports_from_type(element, needs_publickey);
+            }
+        }
+        TypeInfo::Boxed(inner) => {
+            collect_imports_from_type(inner, needs_publickey);
+        }
         TypeInfo::UserDefined(_) => {
             // User-defined types are in the same module
         }
+        TypeInfo::FixedString(_) => {}
     }
 }
 
@@ -982,16 +2186,51 @@ fn map_type_to_typescript(type_info: &TypeInfo) -> String {
             let inner_type = map_type_to_typescript(inner);
             format!("{} | undefined", inner_type)
         }
+        TypeInfo::Map { key, value, .. } => {
+            format!(
+                "Map<{}, {}>",
+                map_type_to_typescript(key),
+                map_type_to_typescript(value)
+            )
+        }
+        // A set decodes like the Vec it is encoded as: an array of distinct
+        // elements, which must be in ascending order to encode canonically
+        TypeInfo::Set { element, .. } => {
+            format!("{}[]", map_type_to_typescript(element))
+        }
+        TypeInfo::Tuple(elements) => {
+            let element_types: Vec<String> = elements.iter().map(map_type_to_typescript).collect();
+            format!("[{}]", element_types.join(", "))
+        }
+        // Boxing only matters to Rust's memory layout
+        TypeInfo::Boxed(inner) => map_type_to_typescript(inner),
+        // Raw zero-padded bytes; see `fromFixedString` / `toFixedString`
+        TypeInfo::FixedString(_) => "number[]".to_string(),
         TypeInfo::Generic(param_name) => {
             // Generic type parameter - output as-is
             param_name.clone()
         }
         TypeInfo::UserDefined(type_name) => type_name.clone(),
+        TypeInfo::Instance { name, args } => {
+            let args: Vec<String> = args.iter().map(map_type_to_typescript).collect();
+            format!("{}<{}>", name, args.join(", "))
+        }
     }
 }
 
 /// Map IR type to Borsh type
-fn map_type_to_borsh(type_info: &TypeInfo) -> String {
+///
+/// Schemas in `pending` are not defined yet where the layout is used (the
+/// type's own schema, for a recursive type), so they are looked up lazily.
+fn map_type_to_borsh(type_info: &TypeInfo, pending: &HashSet<String>) -> String {
+    let map = |inner: &TypeInfo| map_type_to_borsh(inner, pending);
+    let schema = |name: String| {
+        if pending.contains(&name) {
+            format!("lazyLayout(() => {}Schema)", name)
+        } else {
+            format!("{}Schema", name)
+        }
+    };
     match type_info {
         TypeInfo::Primitive(type_name) => match type_name.as_str() {
             "u8" => "borsh.u8".to_string(),
@@ -1012,18 +2251,42 @@ fn map_type_to_borsh(type_info: &TypeInfo) -> String {
             _ => format!("borsh.{}", type_name.to_lowercase()),
         },
         TypeInfo::Array(inner) => {
-            let inner_borsh = map_type_to_borsh(inner);
+            let inner_borsh = map(inner);
             format!("borsh.vec({})", inner_borsh)
         }
         TypeInfo::FixedArray { element, size } => {
             // Fixed-size arrays use borsh.array(element, size) - no length prefix!
-            let element_borsh = map_type_to_borsh(element);
+            let element_borsh = map(element);
             format!("borsh.array({}, {})", element_borsh, size)
         }
+        TypeInfo::FixedString(size) => format!("borsh.array(borsh.u8, {})", size),
         TypeInfo::Option(inner) => {
-            let inner_borsh = map_type_to_borsh(inner);
+            let inner_borsh = map(inner);
             format!("borsh.option({})", inner_borsh)
         }
+        TypeInfo::Map { key, value, .. } => {
+            // Hash and B-tree maps share one wire format: u32 length + entries sorted by key
+            format!("borsh.map({}, {})", map(key), map(value))
+        }
+        TypeInfo::Set { element, .. } => {
+            // A Borsh set is encoded exactly like a Vec of its (sorted) elements
+            format!("borsh.vec({})", map(element))
+        }
+        TypeInfo::Tuple(elements) => {
+            // Tuples carry no length or tag: lay them out as a struct keyed by element
+            // index, decoded back to an array
+            let element_layouts: Vec<String> = elements
+                .iter()
+                .enumerate()
+                .map(|(i, element)| format!("{}('{}')", map(element), i))
+                .collect();
+            format!(
+                "tupleLayout(borsh.struct([{}]))",
+                element_layouts.join(", ")
+            )
+        }
+        // Box<T> is serialized exactly like T
+        TypeInfo::Boxed(inner) => map(inner),
         TypeInfo::Generic(param_name) => {
             // Generic parameters cannot be serialized directly with Borsh
             // They need concrete types at instantiation time
@@ -1034,8 +2297,10 @@ fn map_type_to_borsh(type_info: &TypeInfo) -> String {
         }
         TypeInfo::UserDefined(type_name) => {
             // User-defined types need their schema
-            format!("{}Schema", type_name)
+            schema(type_name.clone())
         }
+        // Each instance of a generic type gets its own concrete schema (`WrapperU64Schema`)
+        TypeInfo::Instance { .. } => schema(type_info.pascal_name()),
     }
 }
 
@@ -1056,24 +2321,20 @@ mod tests {
                 FieldDefinition {
                     name: "id".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "name".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1091,30 +2352,23 @@ mod tests {
                 FieldDefinition {
                     name: "wallet".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "balance".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1137,14 +2391,14 @@ mod tests {
                 name: "email".to_string(),
                 type_info: TypeInfo::Option(Box::new(TypeInfo::Primitive("String".to_string()))),
                 optional: true,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1159,21 +2413,16 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "members".to_string(),
                 type_info: TypeInfo::Array(Box::new(TypeInfo::Primitive("u64".to_string()))),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1192,6 +2441,8 @@ mod tests {
                 visibility: Visibility::Public,
 
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
             TypeDefinition::Struct(StructDefinition {
                 name: "Post".to_string(),
@@ -1201,6 +2452,8 @@ mod tests {
                 visibility: Visibility::Public,
 
                 module_path: Vec::new(),
+                reexports: Vec::new(),
+                span: None,
             }),
         ];
 
@@ -1218,24 +2471,20 @@ mod tests {
                 FieldDefinition {
                     name: "big_unsigned".to_string(),
                     type_info: TypeInfo::Primitive("u128".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "big_signed".to_string(),
                     type_info: TypeInfo::Primitive("i128".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
 
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1251,24 +2500,31 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Finished".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1292,6 +2548,8 @@ mod tests {
                 EnumVariantDefinition::Tuple {
                     name: "PlayerJoined".to_string(),
                     types: vec![TypeInfo::Primitive("Pubkey".to_string())],
+                    docs: vec![],
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Tuple {
                     name: "ScoreUpdated".to_string(),
@@ -1299,18 +2557,18 @@ mod tests {
                         TypeInfo::Primitive("Pubkey".to_string()),
                         TypeInfo::Primitive("u64".to_string()),
                     ],
+                    docs: vec![],
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1336,20 +2594,16 @@ mod tests {
                         FieldDefinition {
                             name: "authority".to_string(),
                             type_info: TypeInfo::Primitive("Pubkey".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                         FieldDefinition {
                             name: "max_players".to_string(),
                             type_info: TypeInfo::Primitive("u32".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                     ],
+                    docs: vec![],
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Struct {
                     name: "UpdateScore".to_string(),
@@ -1357,32 +2611,26 @@ mod tests {
                         FieldDefinition {
                             name: "player".to_string(),
                             type_info: TypeInfo::Primitive("Pubkey".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                         FieldDefinition {
                             name: "new_score".to_string(),
                             type_info: TypeInfo::Primitive("u64".to_string()),
-                            optional: false,
-                            deprecated: None,
-                            span: None,
-                            anchor_attrs: vec![],
+                            ..Default::default()
                         },
                     ],
+                    docs: vec![],
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
                 solana: true,
-                attributes: vec![],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1407,30 +2655,22 @@ mod tests {
                 FieldDefinition {
                     name: "id".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "name".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
-                solana: false,
-                attributes: vec![],
-                version: None,
                 custom_derives: vec!["PartialEq".to_string()],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1447,21 +2687,16 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "mint".to_string(),
                 type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
-                solana: false,
-                attributes: vec![],
-                version: None,
                 custom_derives: vec!["Hash".to_string()],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1479,38 +2714,27 @@ mod tests {
                 FieldDefinition {
                     name: "count".to_string(),
                     type_info: TypeInfo::Primitive("u32".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "enabled".to_string(),
                     type_info: TypeInfo::Primitive("bool".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "label".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
-                solana: false,
-                attributes: vec![],
-                version: None,
                 custom_derives: vec!["Default".to_string()],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1529,30 +2753,22 @@ mod tests {
                 FieldDefinition {
                     name: "points".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "rank".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
-                solana: false,
-                attributes: vec![],
-                version: None,
                 custom_derives: vec!["Ord".to_string()],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1569,26 +2785,21 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "value".to_string(),
                 type_info: TypeInfo::Primitive("u32".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
-                solana: false,
-                attributes: vec![],
-                version: None,
                 custom_derives: vec![
                     "PartialEq".to_string(),
                     "Hash".to_string(),
                     "Default".to_string(),
                     "Ord".to_string(),
                 ],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         });
 
         let code = generate(&type_def);
@@ -1598,4 +2809,82 @@ mod tests {
         assert!(code.contains("export function itemDefault"));
         assert!(code.contains("export function itemCompare"));
     }
+
+    #[test]
+    fn generates_validate_function_for_constraints() {
+        let source = r#"
+            #[solana]
+            struct Listing {
+                #[range(0..=10000)]
+                fee_bps: u16,
+                #[range(-5..5)]
+                delta: Option<i32>,
+                #[pattern("^[A-Z]{1,10}$")]
+                symbol: String,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("export function validateListing(value: Listing): void {\n"));
+        assert!(code.contains("  if (value.fee_bps > 10000) {\n    throw new Error(\"fee_bps must be at most 10000\");\n  }\n"));
+        assert!(code
+            .contains("  if (value.delta != null && (value.delta < -5 || value.delta > 4)) {\n"));
+        assert!(code.contains("  if (!new RegExp(\"^[A-Z]{1,10}$\").test(value.symbol)) {\n"));
+    }
+
+    #[test]
+    fn generates_default_helper_with_field_defaults() {
+        let source = r#"
+            #[derive(Default)]
+            struct Config {
+                #[default(100)]
+                fee_bps: u16,
+                #[default("USDC")]
+                symbol: String,
+                #[default(true)]
+                active: bool,
+                #[default(0.5)]
+                ratio: f64,
+                count: u64,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains("export function configDefault(): Config {\n  return {\n    fee_bps: 100,\n    symbol: \"USDC\",\n    active: true,\n    ratio: 0.5,\n    count: 0,\n  };\n}\n"));
+    }
+
+    #[test]
+    fn renames_properties_borsh_keys_and_type_references() {
+        let source = r#"
+            #![rename_fields(ts = "camelCase")]
+
+            #[solana]
+            #[rename(ts = "TradeListing")]
+            struct Listing {
+                #[rename(ts = "feeRate")]
+                fee_bps: u16,
+                owner_key: PublicKey,
+            }
+
+            #[solana]
+            struct Market {
+                best_listing: Option<Listing>,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(source).unwrap();
+        let ir = crate::transform::transform_to_ir(ast).unwrap();
+
+        let code = generate_module(&ir);
+        assert!(code.contains(
+            "export interface TradeListing {\n  feeRate: number;\n  ownerKey: PublicKey;\n}"
+        ));
+        assert!(code.contains("borsh.u16('feeRate'),\n  borsh.publicKey('ownerKey'),"));
+        assert!(code.contains("  bestListing?: TradeListing | undefined;"));
+        assert!(code.contains("borsh.option(TradeListingSchema)('bestListing'),"));
+        assert!(!code.contains("fee_bps"));
+    }
 }
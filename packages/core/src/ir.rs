@@ -6,6 +6,9 @@
 //! The IR is a language-agnostic representation of type definitions
 //! that can be transformed into various target languages.
 
+use crate::naming::Renames;
+use serde::{Deserialize, Serialize};
+
 /// A warning generated during transformation
 ///
 /// Warnings are non-fatal issues that the user should be aware of,
@@ -29,6 +32,8 @@ pub enum WarningKind {
     Deprecated,
     /// Potential compatibility issue
     Compatibility,
+    /// A generic type is never used with concrete type arguments
+    UninstantiatedGeneric,
 }
 
 impl std::fmt::Display for Warning {
@@ -41,7 +46,7 @@ impl std::fmt::Display for Warning {
 }
 
 /// Visibility of a type definition
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum Visibility {
     /// Public visibility (accessible from other modules)
     #[default]
@@ -62,8 +67,8 @@ impl Visibility {
     }
 }
 
-/// Intermediate representation of a type definition (struct, enum, or type alias)
-#[derive(Debug, Clone)]
+/// Intermediate representation of a type definition (struct, enum, type alias, or constant)
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum TypeDefinition {
     /// Struct definition
     Struct(StructDefinition),
@@ -73,10 +78,13 @@ pub enum TypeDefinition {
 
     /// Type alias definition
     TypeAlias(TypeAliasDefinition),
+
+    /// Constant definition
+    Const(ConstDefinition),
 }
 
 /// Type alias definition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TypeAliasDefinition {
     /// Alias name (e.g., "UserId")
     pub name: String,
@@ -89,10 +97,56 @@ pub struct TypeAliasDefinition {
 
     /// Module path (e.g., ["models", "user"] for crate::models::user)
     pub module_path: Vec<String>,
+
+    /// Modules that re-export the definition with `pub use`
+    #[serde(default)]
+    pub reexports: Vec<Reexport>,
+
+    /// `#[newtype]`: a distinct type with the target's wire format
+    ///
+    /// Fields refer to a newtype by name (`TypeInfo::UserDefined`), while
+    /// plain aliases are replaced by their target during the transform.
+    pub newtype: bool,
+}
+
+/// A `pub use` that makes a definition available from another module
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reexport {
+    /// Module of the `pub use` (empty for the entry file)
+    pub module_path: Vec<String>,
+
+    /// Name the definition is re-exported as (differs with `pub use .. as Name`)
+    pub name: String,
+}
+
+/// Constant definition (e.g., `const MAX_NAME_LEN: usize = 32;`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstDefinition {
+    /// Constant name (e.g., "MAX_NAME_LEN")
+    pub name: String,
+
+    /// Declared integer type (e.g., "usize", "u32")
+    pub type_name: String,
+
+    /// Resolved value
+    pub value: u128,
+
+    /// Visibility (pub or private)
+    pub visibility: Visibility,
+
+    /// Module path (e.g., ["models", "user"] for crate::models::user)
+    pub module_path: Vec<String>,
+
+    /// Modules that re-export the definition with `pub use`
+    #[serde(default)]
+    pub reexports: Vec<Reexport>,
+
+    /// Doc comment lines from `///` comments
+    pub docs: Vec<String>,
 }
 
 /// Struct type definition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StructDefinition {
     /// Struct name
     pub name: String,
@@ -111,10 +165,18 @@ pub struct StructDefinition {
 
     /// Module path (e.g., ["models", "user"] for crate::models::user)
     pub module_path: Vec<String>,
+
+    /// Modules that re-export the definition with `pub use`
+    #[serde(default)]
+    pub reexports: Vec<Reexport>,
+
+    /// Source location of the type name for error reporting
+    #[serde(skip)]
+    pub span: Option<proc_macro2::Span>,
 }
 
 /// Enum type definition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnumDefinition {
     /// Enum name
     pub name: String,
@@ -133,26 +195,50 @@ pub struct EnumDefinition {
 
     /// Module path (e.g., ["models", "state"] for crate::models::state)
     pub module_path: Vec<String>,
+
+    /// Modules that re-export the definition with `pub use`
+    #[serde(default)]
+    pub reexports: Vec<Reexport>,
+
+    /// Source location of the type name for error reporting
+    #[serde(skip)]
+    pub span: Option<proc_macro2::Span>,
 }
 
 /// Enum variant definition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum EnumVariantDefinition {
     /// Unit variant (e.g., `Active`)
-    Unit { name: String },
+    Unit {
+        name: String,
+        docs: Vec<String>,
+        /// Error message from `#[msg("...")]`, used by `#[error_code]` enums
+        msg: Option<String>,
+        /// Explicit discriminant (`None` = previous discriminant + 1, starting at 0)
+        discriminant: Option<u64>,
+    },
 
     /// Tuple variant (e.g., `PlayerJoined(PublicKey, u64)`)
-    Tuple { name: String, types: Vec<TypeInfo> },
+    Tuple {
+        name: String,
+        types: Vec<TypeInfo>,
+        docs: Vec<String>,
+        /// Explicit discriminant (`None` = previous discriminant + 1, starting at 0)
+        discriminant: Option<u64>,
+    },
 
     /// Struct variant (e.g., `Initialize { authority: PublicKey }`)
     Struct {
         name: String,
         fields: Vec<FieldDefinition>,
+        docs: Vec<String>,
+        /// Explicit discriminant (`None` = previous discriminant + 1, starting at 0)
+        discriminant: Option<u64>,
     },
 }
 
 /// A field in a type definition
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldDefinition {
     /// Field name
     pub name: String,
@@ -170,12 +256,114 @@ pub struct FieldDefinition {
     /// These are parsed by the anchor module during code generation
     pub anchor_attrs: Vec<String>,
 
+    /// Doc comment lines from `///` comments on the field
+    pub docs: Vec<String>,
+
+    /// Upper bound from `#[max(N)]` (string length, or entry count for collections)
+    pub max_length: Option<usize>,
+
+    /// Value constraints from `#[range(..)]` and `#[pattern("..")]`
+    pub constraints: Vec<Constraint>,
+
+    /// Default from `#[default(..)]`, used by generated Default helpers and migrations
+    pub default: Option<DefaultValue>,
+
+    /// Names in other targets, from `#[rename(..)]` or the file's `#![rename_fields]` policy
+    pub renames: Renames,
+
     /// Source location for error reporting
+    #[serde(skip)]
     pub span: Option<proc_macro2::Span>,
 }
 
+impl Default for FieldDefinition {
+    /// A required, unnamed field of the unit type `()`, to be given a name
+    /// and type with struct update syntax
+    fn default() -> Self {
+        FieldDefinition {
+            name: String::new(),
+            type_info: TypeInfo::Tuple(Vec::new()),
+            optional: false,
+            deprecated: None,
+            anchor_attrs: Vec::new(),
+            docs: Vec::new(),
+            max_length: None,
+            constraints: Vec::new(),
+            default: None,
+            renames: Renames::default(),
+            span: None,
+        }
+    }
+}
+
+/// A value constraint on a field, enforced by the generated `validate` code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Constraint {
+    /// Integer range; both bounds are inclusive and `None` means unbounded
+    Range {
+        min: Option<i128>,
+        max: Option<i128>,
+    },
+
+    /// Regular expression the string value must match
+    Pattern(String),
+}
+
+/// A field default, already checked against the field type and constraints
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DefaultValue {
+    /// Value of an integer field
+    Integer(i128),
+
+    /// Value of an `f32`/`f64` field
+    Float(f64),
+
+    /// Value of a `bool` field
+    Bool(bool),
+
+    /// Value of a `String` field
+    String(String),
+}
+
+impl std::fmt::Display for DefaultValue {
+    /// The value as a Rust literal (`100`, `0.5`, `true`, `"USDC"`)
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DefaultValue::Integer(value) => write!(f, "{}", value),
+            DefaultValue::Float(value) => write!(f, "{:?}", value),
+            DefaultValue::Bool(value) => write!(f, "{}", value),
+            DefaultValue::String(value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
+impl std::fmt::Display for Constraint {
+    /// Requirement wording used in validation error messages (e.g., "must be at most 10000")
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Constraint::Range {
+                min: Some(min),
+                max: Some(max),
+            } => write!(f, "must be between {} and {}", min, max),
+            Constraint::Range {
+                min: Some(min),
+                max: None,
+            } => write!(f, "must be at least {}", min),
+            Constraint::Range {
+                min: None,
+                max: Some(max),
+            } => write!(f, "must be at most {}", max),
+            Constraint::Range {
+                min: None,
+                max: None,
+            } => write!(f, "may be any value"),
+            Constraint::Pattern(pattern) => write!(f, "must match /{}/", pattern),
+        }
+    }
+}
+
 /// Type information
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TypeInfo {
     /// Primitive types (u64, string, etc.)
     Primitive(String),
@@ -186,18 +374,237 @@ pub enum TypeInfo {
     /// User-defined types
     UserDefined(String),
 
+    /// Generic user-defined types with concrete arguments (e.g., `Wrapper<u64>`)
+    Instance { name: String, args: Vec<TypeInfo> },
+
     /// Dynamic array types (Vec<T>)
     Array(Box<TypeInfo>),
 
     /// Fixed-size array types ([T; N])
     FixedArray { element: Box<TypeInfo>, size: usize },
 
+    /// Map types (HashMap<K, V> / BTreeMap<K, V>)
+    Map {
+        key: Box<TypeInfo>,
+        value: Box<TypeInfo>,
+        /// `true` for `BTreeMap`, `false` for `HashMap`
+        ordered: bool,
+    },
+
+    /// Set types (HashSet<T> / BTreeSet<T>)
+    Set {
+        element: Box<TypeInfo>,
+        /// `true` for `BTreeSet`, `false` for `HashSet`
+        ordered: bool,
+    },
+
     /// Option types
     Option(Box<TypeInfo>),
+
+    /// Tuple types ((A, B, ...)), serialized as their elements in order
+    Tuple(Vec<TypeInfo>),
+
+    /// Boxed types (Box<T>), serialized exactly like `T`
+    Boxed(Box<TypeInfo>),
+
+    /// Fixed-capacity string (`FixedString<N>`), serialized as `[u8; N]`
+    ///
+    /// Holds UTF-8 padded with trailing zeros; a string of exactly `N` bytes
+    /// has no padding.
+    FixedString(usize),
+}
+
+impl TypeInfo {
+    /// Visit this type and every type nested inside it, outermost first
+    pub fn walk(&self, visit: &mut dyn FnMut(&TypeInfo)) {
+        visit(self);
+        match self {
+            TypeInfo::Primitive(_)
+            | TypeInfo::Generic(_)
+            | TypeInfo::UserDefined(_)
+            | TypeInfo::FixedString(_) => {}
+            TypeInfo::Array(inner) | TypeInfo::Option(inner) | TypeInfo::Boxed(inner) => {
+                inner.walk(visit)
+            }
+            TypeInfo::FixedArray { element, .. } | TypeInfo::Set { element, .. } => {
+                element.walk(visit)
+            }
+            TypeInfo::Map { key, value, .. } => {
+                key.walk(visit);
+                value.walk(visit);
+            }
+            TypeInfo::Tuple(elements) | TypeInfo::Instance { args: elements, .. } => {
+                for element in elements {
+                    element.walk(visit);
+                }
+            }
+        }
+    }
+
+    /// Visit this type and every type nested inside it mutably, outermost first
+    ///
+    /// Nested types are visited after `visit` returns, so a replaced type is
+    /// walked in its new form.
+    pub fn walk_mut(&mut self, visit: &mut dyn FnMut(&mut TypeInfo)) {
+        visit(self);
+        match self {
+            TypeInfo::Primitive(_)
+            | TypeInfo::Generic(_)
+            | TypeInfo::UserDefined(_)
+            | TypeInfo::FixedString(_) => {}
+            TypeInfo::Array(inner) | TypeInfo::Option(inner) | TypeInfo::Boxed(inner) => {
+                inner.walk_mut(visit)
+            }
+            TypeInfo::FixedArray { element, .. } | TypeInfo::Set { element, .. } => {
+                element.walk_mut(visit)
+            }
+            TypeInfo::Map { key, value, .. } => {
+                key.walk_mut(visit);
+                value.walk_mut(visit);
+            }
+            TypeInfo::Tuple(elements) | TypeInfo::Instance { args: elements, .. } => {
+                for element in elements {
+                    element.walk_mut(visit);
+                }
+            }
+        }
+    }
+
+    /// Render a Rust tuple, type or value, from its elements
+    ///
+    /// A one-element tuple keeps the trailing comma Rust needs: `(T,)`.
+    pub fn rust_tuple(elements: &[TypeInfo], render: impl FnMut(&TypeInfo) -> String) -> String {
+        let parts: Vec<String> = elements.iter().map(render).collect();
+        if parts.len() == 1 {
+            format!("({},)", parts[0])
+        } else {
+            format!("({})", parts.join(", "))
+        }
+    }
+
+    /// PascalCase name spelling out the type (e.g., `TupleU64PublicKey` for `(u64, PublicKey)`)
+    ///
+    /// Used to name the defined types generated for tuples and generic
+    /// instances (`WrapperU64` for `Wrapper<u64>`) where a target format has
+    /// no anonymous tuple type or generics.
+    pub fn pascal_name(&self) -> String {
+        match self {
+            TypeInfo::Primitive(name) => {
+                let mut chars = name.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            }
+            TypeInfo::Generic(name) | TypeInfo::UserDefined(name) => name.clone(),
+            TypeInfo::Instance { name, args } => {
+                let args: String = args.iter().map(|a| a.pascal_name()).collect();
+                format!("{}{}", name, args)
+            }
+            TypeInfo::Array(inner) => format!("Vec{}", inner.pascal_name()),
+            TypeInfo::FixedArray { element, size } => {
+                format!("Array{}{}", size, element.pascal_name())
+            }
+            TypeInfo::Option(inner) => format!("Option{}", inner.pascal_name()),
+            TypeInfo::Map {
+                key,
+                value,
+                ordered,
+            } => format!(
+                "{}{}{}",
+                if *ordered { "BTreeMap" } else { "HashMap" },
+                key.pascal_name(),
+                value.pascal_name()
+            ),
+            TypeInfo::Set { element, ordered } => format!(
+                "{}{}",
+                if *ordered { "BTreeSet" } else { "HashSet" },
+                element.pascal_name()
+            ),
+            TypeInfo::Tuple(elements) => {
+                let names: String = elements.iter().map(|e| e.pascal_name()).collect();
+                format!("Tuple{}", names)
+            }
+            // Boxing doesn't change the wire format, so it doesn't change the name
+            TypeInfo::Boxed(inner) => inner.pascal_name(),
+            TypeInfo::FixedString(size) => format!("FixedString{}", size),
+        }
+    }
+}
+
+/// First custom error code Anchor assigns to an `#[error_code]` enum
+pub const ANCHOR_ERROR_CODE_OFFSET: u32 = 6000;
+
+/// Width of an enum's Borsh discriminant, chosen with `#[repr(u8 | u16 | u32)]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EnumRepr {
+    /// One-byte tag (Borsh default)
+    #[default]
+    U8,
+    /// Two-byte little-endian tag
+    U16,
+    /// Four-byte little-endian tag
+    U32,
+}
+
+impl EnumRepr {
+    /// Parse a `#[repr(...)]` argument (`"u8"`, `"u16"` or `"u32"`)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "u8" => Some(EnumRepr::U8),
+            "u16" => Some(EnumRepr::U16),
+            "u32" => Some(EnumRepr::U32),
+            _ => None,
+        }
+    }
+
+    /// Rust name of the tag type
+    pub fn name(&self) -> &'static str {
+        match self {
+            EnumRepr::U8 => "u8",
+            EnumRepr::U16 => "u16",
+            EnumRepr::U32 => "u32",
+        }
+    }
+
+    /// Size of the tag in bytes
+    pub fn size(&self) -> usize {
+        match self {
+            EnumRepr::U8 => 1,
+            EnumRepr::U16 => 2,
+            EnumRepr::U32 => 4,
+        }
+    }
+
+    /// Largest discriminant that fits in the tag
+    pub fn max_discriminant(&self) -> u64 {
+        match self {
+            EnumRepr::U8 => u8::MAX as u64,
+            EnumRepr::U16 => u16::MAX as u64,
+            EnumRepr::U32 => u32::MAX as u64,
+        }
+    }
+}
+
+/// C (`#[repr(C)]`) layout of a `#[zero_copy]` struct
+///
+/// Computed after explicit padding fields have been inserted, so `offsets`
+/// has one entry per field (padding included) and the fields tile `size` exactly.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct CLayout {
+    /// Total size in bytes, including tail padding
+    pub size: usize,
+
+    /// Alignment of the struct in bytes
+    pub align: usize,
+
+    /// Byte offset of each field from the start of the struct
+    pub offsets: Vec<usize>,
 }
 
 /// Metadata about a type
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Metadata {
     /// Whether this is Solana-specific
     pub solana: bool,
@@ -217,6 +624,24 @@ pub struct Metadata {
 
     /// Anchor-specific struct attributes (raw strings for later parsing)
     pub anchor_attrs: Vec<String>,
+
+    /// Handler arguments of an `#[instruction]` struct, serialized after the discriminator
+    pub instruction_args: Vec<FieldDefinition>,
+
+    /// Doc comment lines from `///` comments on the type
+    pub docs: Vec<String>,
+
+    /// First error code of an `#[error_code(offset = N)]` enum (`None` = Anchor's 6000)
+    pub error_code_offset: Option<u32>,
+
+    /// Discriminant width from `#[repr(...)]` on enums (`None` = u8)
+    pub repr: Option<EnumRepr>,
+
+    /// C layout of a `#[zero_copy]` struct (`None` = Borsh-serialized)
+    pub c_layout: Option<CLayout>,
+
+    /// Names in other targets, from `#[rename(..)]` or the file's `#![rename_types]` policy
+    pub renames: Renames,
 }
 
 impl TypeDefinition {
@@ -226,15 +651,16 @@ impl TypeDefinition {
             TypeDefinition::Struct(s) => &s.name,
             TypeDefinition::Enum(e) => &e.name,
             TypeDefinition::TypeAlias(a) => &a.name,
+            TypeDefinition::Const(c) => &c.name,
         }
     }
 
-    /// Get the metadata for this type definition (not applicable to type aliases)
+    /// Get the metadata for this type definition (not applicable to type aliases or constants)
     pub fn metadata(&self) -> Option<&Metadata> {
         match self {
             TypeDefinition::Struct(s) => Some(&s.metadata),
             TypeDefinition::Enum(e) => Some(&e.metadata),
-            TypeDefinition::TypeAlias(_) => None, // Type aliases don't have metadata
+            TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => None, // No metadata
         }
     }
 
@@ -244,6 +670,75 @@ impl TypeDefinition {
             TypeDefinition::Struct(s) => s.metadata.solana,
             TypeDefinition::Enum(e) => e.metadata.solana,
             TypeDefinition::TypeAlias(_) => false, // Will be resolved based on target type
+            TypeDefinition::Const(_) => false,
+        }
+    }
+
+    /// Types of every field, variant payload, instruction argument or alias target
+    pub fn field_types(&self) -> Vec<&TypeInfo> {
+        match self {
+            TypeDefinition::Struct(s) => s
+                .fields
+                .iter()
+                .chain(&s.metadata.instruction_args)
+                .map(|f| &f.type_info)
+                .collect(),
+            TypeDefinition::Enum(e) => e
+                .variants
+                .iter()
+                .flat_map(|v| match v {
+                    EnumVariantDefinition::Unit { .. } => Vec::new(),
+                    EnumVariantDefinition::Tuple { types, .. } => types.iter().collect(),
+                    EnumVariantDefinition::Struct { fields, .. } => {
+                        fields.iter().map(|f| &f.type_info).collect()
+                    }
+                })
+                .collect(),
+            TypeDefinition::TypeAlias(a) => vec![&a.target],
+            TypeDefinition::Const(_) => Vec::new(),
+        }
+    }
+
+    /// Mutable access to the same types as [`TypeDefinition::field_types`]
+    pub fn field_types_mut(&mut self) -> Vec<&mut TypeInfo> {
+        match self {
+            TypeDefinition::Struct(s) => s
+                .fields
+                .iter_mut()
+                .chain(&mut s.metadata.instruction_args)
+                .map(|f| &mut f.type_info)
+                .collect(),
+            TypeDefinition::Enum(e) => e
+                .variants
+                .iter_mut()
+                .flat_map(|v| match v {
+                    EnumVariantDefinition::Unit { .. } => Vec::new(),
+                    EnumVariantDefinition::Tuple { types, .. } => types.iter_mut().collect(),
+                    EnumVariantDefinition::Struct { fields, .. } => {
+                        fields.iter_mut().map(|f| &mut f.type_info).collect()
+                    }
+                })
+                .collect(),
+            TypeDefinition::TypeAlias(a) => vec![&mut a.target],
+            TypeDefinition::Const(_) => Vec::new(),
+        }
+    }
+
+    /// Generic type parameters (empty for non-generic types, aliases and constants)
+    pub fn generic_params(&self) -> &[String] {
+        match self {
+            TypeDefinition::Struct(s) => &s.generic_params,
+            TypeDefinition::Enum(e) => &e.generic_params,
+            TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => &[],
+        }
+    }
+
+    /// Source location of the type name (only known for structs and enums)
+    pub fn span(&self) -> Option<proc_macro2::Span> {
+        match self {
+            TypeDefinition::Struct(s) => s.span,
+            TypeDefinition::Enum(e) => e.span,
+            TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => None,
         }
     }
 
@@ -252,12 +747,18 @@ impl TypeDefinition {
         matches!(self, TypeDefinition::TypeAlias(_))
     }
 
+    /// Check if this is a constant
+    pub fn is_const(&self) -> bool {
+        matches!(self, TypeDefinition::Const(_))
+    }
+
     /// Get the visibility of this type definition
     pub fn visibility(&self) -> Visibility {
         match self {
             TypeDefinition::Struct(s) => s.visibility,
             TypeDefinition::Enum(e) => e.visibility,
             TypeDefinition::TypeAlias(a) => a.visibility,
+            TypeDefinition::Const(c) => c.visibility,
         }
     }
 
@@ -267,6 +768,17 @@ impl TypeDefinition {
             TypeDefinition::Struct(s) => &s.module_path,
             TypeDefinition::Enum(e) => &e.module_path,
             TypeDefinition::TypeAlias(a) => &a.module_path,
+            TypeDefinition::Const(c) => &c.module_path,
+        }
+    }
+
+    /// Get the modules that re-export this type definition
+    pub fn reexports(&self) -> &[Reexport] {
+        match self {
+            TypeDefinition::Struct(s) => &s.reexports,
+            TypeDefinition::Enum(e) => &e.reexports,
+            TypeDefinition::TypeAlias(a) => &a.reexports,
+            TypeDefinition::Const(c) => &c.reexports,
         }
     }
 
@@ -288,6 +800,60 @@ impl TypeAliasDefinition {
     }
 }
 
+impl StructDefinition {
+    /// Check if this struct is a `#[zero_copy]` (C layout, bytemuck Pod) type
+    pub fn is_zero_copy(&self) -> bool {
+        self.metadata.attributes.iter().any(|a| a == "zero_copy")
+    }
+
+    /// Check if this struct is a Solana `#[event]` (emitted with Anchor's `emit!`)
+    ///
+    /// `#[event]` only takes effect alongside `#[solana]`, in every generator
+    /// and in the IDL.
+    pub fn is_event(&self) -> bool {
+        self.metadata.solana && self.metadata.attributes.iter().any(|a| a == "event")
+    }
+
+    /// Check if any field declares `#[range]` or `#[pattern]`, so a validator is generated
+    pub fn has_constraints(&self) -> bool {
+        self.fields.iter().any(|f| !f.constraints.is_empty())
+    }
+
+    /// Check if any field declares a `#[default(..)]` value
+    pub fn has_field_defaults(&self) -> bool {
+        self.fields.iter().any(|f| f.default.is_some())
+    }
+
+    /// Check if the schema asks for `#[derive(Default)]`
+    pub fn derives_default(&self) -> bool {
+        self.metadata.custom_derives.iter().any(|d| d == "Default")
+    }
+
+    /// Build the `{Name}Args` struct clients serialize for an `#[instruction]`
+    ///
+    /// Returns `None` when the instruction takes no arguments.
+    pub fn instruction_args_struct(&self) -> Option<StructDefinition> {
+        if self.metadata.instruction_args.is_empty() {
+            return None;
+        }
+
+        Some(StructDefinition {
+            name: format!("{}Args", self.name),
+            generic_params: Vec::new(),
+            fields: self.metadata.instruction_args.clone(),
+            metadata: Metadata {
+                solana: true,
+                docs: vec![format!("Arguments of the `{}` instruction", self.name)],
+                ..Default::default()
+            },
+            visibility: self.visibility,
+            module_path: self.module_path.clone(),
+            reexports: Vec::new(),
+            span: self.span,
+        })
+    }
+}
+
 impl EnumDefinition {
     /// Check if this enum has only unit variants
     pub fn is_unit_only(&self) -> bool {
@@ -309,15 +875,123 @@ impl EnumDefinition {
             .iter()
             .any(|v| matches!(v, EnumVariantDefinition::Tuple { .. }))
     }
+
+    /// Check if this enum declares Anchor program errors (`#[error_code]`)
+    pub fn is_error_code(&self) -> bool {
+        self.metadata.attributes.iter().any(|a| a == "error_code")
+    }
+
+    /// Check if this enum is a `#[flags]` bitmask of its variants
+    pub fn is_flags(&self) -> bool {
+        self.metadata.attributes.iter().any(|a| a == "flags")
+    }
+
+    /// Bitmask with every flag of a `#[flags]` enum set
+    pub fn flags_mask(&self) -> u64 {
+        self.discriminants()
+            .into_iter()
+            .fold(0, |mask, bit| mask | bit)
+    }
+
+    /// Discriminant width of this enum (u8 unless set with `#[repr]`)
+    ///
+    /// For `#[flags]` enums this is the width of the packed integer.
+    pub fn repr(&self) -> EnumRepr {
+        self.metadata.repr.unwrap_or_default()
+    }
+
+    /// Size of the Borsh discriminant in bytes
+    pub fn tag_size(&self) -> usize {
+        self.repr().size()
+    }
+
+    /// Discriminant of each variant, in declaration order
+    ///
+    /// Follows Rust's rules: a variant without an explicit value takes the
+    /// previous discriminant plus one, and the first defaults to 0.
+    ///
+    /// Variants of a `#[flags]` enum are bits instead: an implicit flag takes
+    /// the bit after the previous one, and the first defaults to `1`.
+    pub fn discriminants(&self) -> Vec<u64> {
+        let flags = self.is_flags();
+        let mut next = if flags { 1 } else { 0 };
+        self.variants
+            .iter()
+            .map(|v| {
+                let value = v.discriminant().unwrap_or(next);
+                next = if flags {
+                    value.saturating_mul(2)
+                } else {
+                    value.wrapping_add(1)
+                };
+                value
+            })
+            .collect()
+    }
+
+    /// Check if the wire format differs from Borsh's default `u8` variant index
+    ///
+    /// True when the enum has a wider tag or any variant's discriminant is not
+    /// its position. Generators then emit explicit tag handling.
+    pub fn has_custom_discriminants(&self) -> bool {
+        self.repr() != EnumRepr::U8
+            || self
+                .discriminants()
+                .into_iter()
+                .enumerate()
+                .any(|(i, d)| d != i as u64)
+    }
+
+    /// Numeric codes of an `#[error_code]` enum as `(code, variant)` pairs
+    ///
+    /// Codes follow variant order, starting at the enum's offset
+    /// ([`ANCHOR_ERROR_CODE_OFFSET`] unless overridden).
+    pub fn error_codes(&self) -> Vec<(u32, &EnumVariantDefinition)> {
+        let offset = self
+            .metadata
+            .error_code_offset
+            .unwrap_or(ANCHOR_ERROR_CODE_OFFSET);
+        self.variants
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (offset + i as u32, v))
+            .collect()
+    }
 }
 
 impl EnumVariantDefinition {
     /// Get the variant name
     pub fn name(&self) -> &str {
         match self {
-            EnumVariantDefinition::Unit { name } => name,
+            EnumVariantDefinition::Unit { name, .. } => name,
             EnumVariantDefinition::Tuple { name, .. } => name,
             EnumVariantDefinition::Struct { name, .. } => name,
         }
     }
+
+    /// Get the doc comment lines attached to this variant
+    pub fn docs(&self) -> &[String] {
+        match self {
+            EnumVariantDefinition::Unit { docs, .. } => docs,
+            EnumVariantDefinition::Tuple { docs, .. } => docs,
+            EnumVariantDefinition::Struct { docs, .. } => docs,
+        }
+    }
+
+    /// Get the explicitly declared discriminant, if any
+    pub fn discriminant(&self) -> Option<u64> {
+        match self {
+            EnumVariantDefinition::Unit { discriminant, .. }
+            | EnumVariantDefinition::Tuple { discriminant, .. }
+            | EnumVariantDefinition::Struct { discriminant, .. } => *discriminant,
+        }
+    }
+
+    /// Get the `#[msg("...")]` error message, if any (unit variants only)
+    pub fn msg(&self) -> Option<&str> {
+        match self {
+            EnumVariantDefinition::Unit { msg, .. } => msg.as_deref(),
+            _ => None,
+        }
+    }
 }
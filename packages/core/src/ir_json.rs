@@ -0,0 +1,215 @@
+// Licensed under either of Apache License, Version 2.0 or MIT license at your option.
+// Copyright 2025 RECTOR-LABS
+
+//! Versioned JSON format of the IR
+//!
+//! Dashboards, linters and other tools can work from the resolved model
+//! instead of re-implementing the parser: `lumos ir schema.lumos --format json`
+//! writes an [`IrDocument`] after aliases, modules and imports are resolved,
+//! and [`IrDocument::from_json`] loads it back into the same
+//! [`TypeDefinition`]s the generators use.
+//!
+//! ```json
+//! {
+//!   "format_version": 1,
+//!   "lumos_version": "0.3.0",
+//!   "types": [
+//!     { "Struct": { "name": "Vault", "fields": [...], "metadata": {...}, ... } }
+//!   ]
+//! }
+//! ```
+//!
+//! Definitions and types use serde's default (externally tagged) enum
+//! encoding, so a `u64` field is `{ "Primitive": "u64" }` and a `Vec<u64>` is
+//! `{ "Array": { "Primitive": "u64" } }`. Source spans are not included.
+//! The full format is documented in `docs/ir-format.md`; any change that an
+//! existing reader would misinterpret bumps [`IR_FORMAT_VERSION`].
+
+use crate::error::{LumosError, Result};
+use crate::ir::TypeDefinition;
+use serde::{Deserialize, Serialize};
+
+/// Version of the JSON layout written by [`IrDocument::to_json`]
+pub const IR_FORMAT_VERSION: u32 = 1;
+
+/// The resolved type definitions of a schema, as written to JSON
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IrDocument {
+    /// Layout version of this document (see [`IR_FORMAT_VERSION`])
+    pub format_version: u32,
+
+    /// Version of LUMOS that wrote the document
+    pub lumos_version: String,
+
+    /// Type definitions in schema order
+    pub types: Vec<TypeDefinition>,
+}
+
+impl IrDocument {
+    /// Wrap type definitions in a document of the current format version
+    pub fn new(types: Vec<TypeDefinition>) -> Self {
+        Self {
+            format_version: IR_FORMAT_VERSION,
+            lumos_version: env!("CARGO_PKG_VERSION").to_string(),
+            types,
+        }
+    }
+
+    /// Serialize to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| LumosError::CodeGen(format!("Failed to serialize IR: {}", e)))
+    }
+
+    /// Load a document written by [`IrDocument::to_json`]
+    ///
+    /// Documents of another format version are rejected rather than read
+    /// with fields silently missing or misread.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let invalid =
+            |e: serde_json::Error| LumosError::SchemaParse(format!("Invalid IR JSON: {}", e), None);
+
+        // Read the version alone first, then the document straight from the
+        // text: going through `serde_json::Value` would turn 128-bit integers
+        // into floats
+        let version = serde_json::from_str::<FormatVersion>(json)
+            .map_err(invalid)?
+            .format_version
+            .ok_or_else(|| {
+                LumosError::SchemaParse("IR JSON has no 'format_version'".to_string(), None)
+            })?;
+        if version != IR_FORMAT_VERSION {
+            return Err(LumosError::SchemaParse(
+                format!(
+                    "Unsupported IR format version {} (this version of LUMOS reads version {})",
+                    version, IR_FORMAT_VERSION
+                ),
+                None,
+            ));
+        }
+
+        serde_json::from_str(json).map_err(invalid)
+    }
+}
+
+/// The version of a document, read before the rest of it
+#[derive(Deserialize)]
+struct FormatVersion {
+    format_version: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::DefaultValue;
+    use crate::parser::parse_lumos_file;
+    use crate::transform::transform_to_ir;
+
+    fn load(source: &str) -> Vec<TypeDefinition> {
+        transform_to_ir(parse_lumos_file(source).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let types = load(
+            r#"
+            #![rename_fields(ts = "camelCase")]
+
+            const MAX_NAME: usize = 16;
+
+            #[newtype]
+            type Lamports = u64;
+
+            /// A vault
+            #[solana]
+            #[account]
+            struct Vault {
+                owner: PublicKey,
+                #[range(1..=10000)]
+                #[default(30)]
+                fee_bps: u16,
+                balance: Lamports,
+                name: FixedString<MAX_NAME>,
+                history: Option<Vec<(u64, i128)>>,
+            }
+
+            #[solana]
+            #[repr(u16)]
+            enum Event {
+                Opened,
+                Deposit(u64),
+                Closed { reason: String } = 10,
+            }
+        "#,
+        );
+
+        let json = IrDocument::new(types.clone()).to_json().unwrap();
+        let loaded = IrDocument::from_json(&json).unwrap();
+
+        assert_eq!(loaded.format_version, IR_FORMAT_VERSION);
+        assert_eq!(loaded.types.len(), types.len());
+        // Everything but spans survives, so a second dump is identical
+        assert_eq!(IrDocument::new(loaded.types).to_json().unwrap(), json);
+    }
+
+    #[test]
+    fn test_round_trip_128_bit_values() {
+        let types = load(
+            r#"
+            const MAX_SUPPLY: u128 = 340282366920938463463374607431768211455;
+
+            struct Limits {
+                #[default(170141183460469231731687303715884105727)]
+                max: i128,
+                #[default(-170141183460469231731687303715884105727)]
+                min: i128,
+            }
+        "#,
+        );
+
+        let json = IrDocument::new(types).to_json().unwrap();
+        let loaded = IrDocument::from_json(&json).unwrap();
+
+        let TypeDefinition::Const(c) = &loaded.types[0] else {
+            panic!("Expected const");
+        };
+        assert_eq!(c.value, u128::MAX);
+        let TypeDefinition::Struct(s) = &loaded.types[1] else {
+            panic!("Expected struct");
+        };
+        assert_eq!(s.fields[0].default, Some(DefaultValue::Integer(i128::MAX)));
+        assert_eq!(s.fields[1].default, Some(DefaultValue::Integer(-i128::MAX)));
+    }
+
+    #[test]
+    fn test_json_layout() {
+        let types = load("#[solana] struct Vault { #[rename(py = \"amount\")] balance: u64 }");
+        let json: serde_json::Value =
+            serde_json::from_str(&IrDocument::new(types).to_json().unwrap()).unwrap();
+
+        assert_eq!(json["format_version"], 1);
+        let field = &json["types"][0]["Struct"]["fields"][0];
+        assert_eq!(field["name"], "balance");
+        assert_eq!(
+            field["type_info"],
+            serde_json::json!({ "Primitive": "u64" })
+        );
+        assert_eq!(field["renames"], serde_json::json!({ "py": "amount" }));
+        assert!(field.get("span").is_none());
+    }
+
+    #[test]
+    fn test_rejects_other_versions() {
+        let err = IrDocument::from_json(r#"{ "format_version": 2, "types": [] }"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unsupported IR format version 2"), "{}", err);
+
+        let err = IrDocument::from_json(r#"{ "types": [] }"#)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no 'format_version'"), "{}", err);
+
+        assert!(IrDocument::from_json("not json").is_err());
+    }
+}
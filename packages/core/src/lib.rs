@@ -74,6 +74,12 @@ pub mod ir;
 /// Transform AST into IR
 pub mod transform;
 
+/// Per-language names from `#[rename]` and file naming policies
+pub mod naming;
+
+/// Versioned JSON format of the IR for third-party tooling
+pub mod ir_json;
+
 /// Multi-language code generators
 ///
 /// Provides a unified interface for generating code in multiple languages:
@@ -90,6 +96,9 @@ pub mod generators;
 /// Error types for LUMOS core
 pub mod error;
 
+/// Schema diagnostics (all errors and warnings) for the CLI and LSP
+pub mod diagnostics;
+
 /// Account size calculator for Solana programs
 pub mod size_calculator;
 
@@ -117,6 +126,15 @@ pub mod file_resolver;
 /// Module resolver for hierarchical module structures
 pub mod module_resolver;
 
+/// Project graph resolving imports and modules of multi-file schemas together
+pub mod project;
+
+/// Local package registry for sharing schemas between programs
+pub mod registry;
+
+/// Incremental compilation cache of parsed ASTs and transformed IR
+pub mod cache;
+
 /// Anchor Framework integration (IDL generation, constraints, instructions)
 pub mod anchor;
 
@@ -5,9 +5,10 @@
 //!
 //! Generates Rust and TypeScript code that is compatible with Metaplex SDKs.
 
+use crate::generators::{rust, typescript};
 #[cfg(test)]
 use crate::ir::EnumVariantDefinition;
-use crate::ir::{EnumDefinition, StructDefinition, TypeDefinition, TypeInfo};
+use crate::ir::{Constraint, EnumDefinition, StructDefinition, TypeDefinition, TypeInfo};
 
 use super::types::constraints;
 
@@ -84,7 +85,7 @@ impl MetaplexGenerator {
                     output.push_str(&self.generate_rust_enum(e));
                     output.push('\n');
                 }
-                TypeDefinition::TypeAlias(_) => {}
+                TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {}
             }
         }
 
@@ -178,9 +179,44 @@ pub mod metaplex_constraints {{
         }
 
         output.push_str("}\n");
+
+        if self.config.generate_validators {
+            output.push_str(&rust::generate_validate_impl(
+                &self.with_metaplex_constraints(struct_def),
+            ));
+        }
+
         output
     }
 
+    /// Add the Token Metadata limits a schema may leave implicit
+    ///
+    /// The limits then reach the generated `validate` code the same way as a
+    /// declared `#[range]`, so metadata structs are checked field by field.
+    fn with_metaplex_constraints(&self, struct_def: &StructDefinition) -> StructDefinition {
+        let mut struct_def = struct_def.clone();
+        if !struct_def.name.contains("Metadata") {
+            return struct_def;
+        }
+
+        for field in &mut struct_def.fields {
+            let is_seller_fee = field.name == "seller_fee_basis_points"
+                && field.type_info == TypeInfo::Primitive("u16".to_string());
+            let has_range = field
+                .constraints
+                .iter()
+                .any(|c| matches!(c, Constraint::Range { .. }));
+            if is_seller_fee && !has_range {
+                field.constraints.push(Constraint::Range {
+                    min: None,
+                    max: Some(constraints::MAX_SELLER_FEE_BASIS_POINTS as i128),
+                });
+            }
+        }
+
+        struct_def
+    }
+
     /// Generate a Rust enum
     fn generate_rust_enum(&self, enum_def: &EnumDefinition) -> String {
         let mut output = String::new();
@@ -276,11 +312,33 @@ pub mod metaplex_validation {
             },
             TypeInfo::Generic(name) => name.clone(),
             TypeInfo::UserDefined(name) => name.clone(),
+            TypeInfo::Instance { name, args } => {
+                let parts: Vec<String> = args.iter().map(|a| self.rust_type(a)).collect();
+                format!("{}<{}>", name, parts.join(", "))
+            }
             TypeInfo::Array(inner) => format!("Vec<{}>", self.rust_type(inner)),
             TypeInfo::FixedArray { element, size } => {
                 format!("[{}; {}]", self.rust_type(element), size)
             }
+            TypeInfo::FixedString(size) => format!("[u8; {}]", size),
             TypeInfo::Option(inner) => format!("Option<{}>", self.rust_type(inner)),
+            TypeInfo::Map {
+                key,
+                value,
+                ordered,
+            } => format!(
+                "{}<{}, {}>",
+                if *ordered { "BTreeMap" } else { "HashMap" },
+                self.rust_type(key),
+                self.rust_type(value)
+            ),
+            TypeInfo::Set { element, ordered } => format!(
+                "{}<{}>",
+                if *ordered { "BTreeSet" } else { "HashSet" },
+                self.rust_type(element)
+            ),
+            TypeInfo::Boxed(inner) => format!("Box<{}>", self.rust_type(inner)),
+            TypeInfo::Tuple(elements) => TypeInfo::rust_tuple(elements, |e| self.rust_type(e)),
         }
     }
 
@@ -318,7 +376,7 @@ pub mod metaplex_validation {
                     output.push_str(&self.generate_typescript_enum(e));
                     output.push('\n');
                 }
-                TypeDefinition::TypeAlias(_) => {}
+                TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {}
             }
         }
 
@@ -400,6 +458,16 @@ export const METAPLEX_CONSTRAINTS = {{
         // Also generate Borsh schema
         output.push_str(&self.generate_typescript_borsh_schema(struct_def));
 
+        // 64-bit and wider integers are bigints in the Metaplex SDK types
+        let constrained = self.with_metaplex_constraints(struct_def);
+        if self.config.generate_validators && constrained.has_constraints() {
+            output.push('\n');
+            output.push_str(&typescript::generate_validate_function(
+                &constrained,
+                &["u64", "i64", "u128", "i128"],
+            ));
+        }
+
         output
     }
 
@@ -503,9 +571,31 @@ export const MetaplexValidation = {
             },
             TypeInfo::Generic(name) => name.clone(),
             TypeInfo::UserDefined(name) => name.clone(),
+            TypeInfo::Instance { name, args } => {
+                let parts: Vec<String> = args.iter().map(|a| self.typescript_type(a)).collect();
+                format!("{}<{}>", name, parts.join(", "))
+            }
             TypeInfo::Array(inner) => format!("{}[]", self.typescript_type(inner)),
             TypeInfo::Option(inner) => format!("{} | null", self.typescript_type(inner)),
             TypeInfo::FixedArray { element, .. } => format!("{}[]", self.typescript_type(element)),
+            TypeInfo::FixedString(_) => "number[]".to_string(),
+            TypeInfo::Map { key, value, .. } => format!(
+                "Map<{}, {}>",
+                self.typescript_type(key),
+                self.typescript_type(value)
+            ),
+            // Sets decode like the Vec they are encoded as
+            TypeInfo::Set { element, .. } => format!("{}[]", self.typescript_type(element)),
+            TypeInfo::Boxed(inner) => self.typescript_type(inner),
+            // The Borsh layout decodes a tuple to an object keyed by element index
+            TypeInfo::Tuple(elements) => {
+                let parts: Vec<String> = elements
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| format!("{}: {}", i, self.typescript_type(e)))
+                    .collect();
+                format!("{{ {} }}", parts.join("; "))
+            }
         }
     }
 
@@ -532,11 +622,32 @@ export const MetaplexValidation = {
             },
             TypeInfo::Generic(name) => name.clone(),
             TypeInfo::UserDefined(name) => format!("{}Schema", name),
+            TypeInfo::Instance { .. } => format!("{}Schema", type_info.pascal_name()),
             TypeInfo::Array(inner) => format!("vec({})", self.typescript_borsh_type(inner)),
             TypeInfo::Option(inner) => format!("option({})", self.typescript_borsh_type(inner)),
             TypeInfo::FixedArray { element, size } => {
                 format!("array({}, {})", self.typescript_borsh_type(element), size)
             }
+            TypeInfo::FixedString(size) => format!("array(u8, {})", size),
+            TypeInfo::Map { key, value, .. } => format!(
+                "map({}, {})",
+                self.typescript_borsh_type(key),
+                self.typescript_borsh_type(value)
+            ),
+            // Borsh sets share the Vec encoding
+            TypeInfo::Set { element, .. } => {
+                format!("vec({})", self.typescript_borsh_type(element))
+            }
+            TypeInfo::Boxed(inner) => self.typescript_borsh_type(inner),
+            // Tuples are their elements back to back, keyed by index
+            TypeInfo::Tuple(elements) => {
+                let parts: Vec<String> = elements
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| format!("{}('{}')", self.typescript_borsh_type(e), i))
+                    .collect();
+                format!("struct([{}])", parts.join(", "))
+            }
         }
     }
 }
@@ -560,39 +671,29 @@ mod tests {
                 FieldDefinition {
                     name: "name".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "symbol".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "uri".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "seller_fee_basis_points".to_string(),
                     type_info: TypeInfo::Primitive("u16".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         }
     }
 
@@ -610,6 +711,25 @@ mod tests {
         assert!(rust.contains("validate_name"));
     }
 
+    #[test]
+    fn test_metadata_validate_enforces_seller_fee_and_declared_constraints() {
+        let generator = MetaplexGenerator::new();
+        let mut metadata = create_test_metadata();
+        metadata.fields[1]
+            .constraints
+            .push(Constraint::Pattern("^[A-Z]+$".to_string()));
+        let types = vec![TypeDefinition::Struct(metadata)];
+
+        let rust = generator.generate_rust(&types);
+        assert!(rust.contains("impl TokenMetadata {"));
+        assert!(rust.contains("if self.seller_fee_basis_points > 10000 {"));
+        assert!(rust.contains("regex::Regex::new(\"^[A-Z]+$\")"));
+
+        let ts = generator.generate_typescript(&types);
+        assert!(ts.contains("export function validateTokenMetadata(value: TokenMetadata): void {"));
+        assert!(ts.contains("if (value.seller_fee_basis_points > 10000) {"));
+    }
+
     #[test]
     fn test_generate_typescript() {
         let generator = MetaplexGenerator::new();
@@ -633,14 +753,22 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "NonFungible".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Fungible".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
         let types = vec![TypeDefinition::Enum(enum_def)];
 
@@ -699,5 +827,19 @@ mod tests {
             )))),
             "string[]"
         );
+
+        // Tuples match the shape their layout decodes to
+        let pair = TypeInfo::Tuple(vec![
+            TypeInfo::Primitive("u16".to_string()),
+            TypeInfo::Primitive("PublicKey".to_string()),
+        ]);
+        assert_eq!(
+            generator.typescript_type(&pair),
+            "{ 0: number; 1: PublicKey }"
+        );
+        assert_eq!(
+            generator.typescript_borsh_type(&pair),
+            "struct([u16('0'), publicKey('1')])"
+        );
     }
 }
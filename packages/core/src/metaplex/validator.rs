@@ -477,39 +477,29 @@ mod tests {
                 FieldDefinition {
                     name: "name".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "symbol".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "uri".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "seller_fee_basis_points".to_string(),
                     type_info: TypeInfo::Primitive("u16".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         }
     }
 
@@ -521,31 +511,24 @@ mod tests {
                 FieldDefinition {
                     name: "address".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "verified".to_string(),
                     type_info: TypeInfo::Primitive("bool".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "share".to_string(),
                     type_info: TypeInfo::Primitive("u8".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         }
     }
 
@@ -583,14 +566,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "address".to_string(),
                 type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
         let types = vec![TypeDefinition::Struct(struct_def)];
 
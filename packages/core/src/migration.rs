@@ -6,7 +6,11 @@
 //! This module provides functionality to compare two schema versions
 //! and generate migration code to transform data from one version to another.
 
-use crate::ir::{EnumDefinition, FieldDefinition, StructDefinition, TypeDefinition, TypeInfo};
+use crate::generators::typescript::default_value_literal;
+use crate::ir::{
+    DefaultValue, EnumDefinition, EnumRepr, FieldDefinition, StructDefinition, TypeDefinition,
+    TypeInfo,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
@@ -18,6 +22,9 @@ pub enum SchemaChange {
         name: String,
         type_info: TypeInfo,
         optional: bool,
+        /// Value from `#[default(..)]`, filled in by generated migrations
+        #[serde(default)]
+        default: Option<DefaultValue>,
     },
 
     /// A field was removed
@@ -42,6 +49,26 @@ pub enum SchemaChange {
 
     /// An enum variant was removed
     VariantRemoved { name: String },
+
+    /// An enum variant's discriminant was changed (explicitly or by a shift)
+    VariantDiscriminantChanged {
+        name: String,
+        old_discriminant: u64,
+        new_discriminant: u64,
+    },
+
+    /// A flag of a `#[flags]` enum moved to another bit
+    FlagBitChanged {
+        name: String,
+        old_bit: u64,
+        new_bit: u64,
+    },
+
+    /// An enum's discriminant width (`#[repr]`) was changed
+    EnumReprChanged {
+        old_repr: EnumRepr,
+        new_repr: EnumRepr,
+    },
 }
 
 /// Represents the difference between two schema versions
@@ -147,10 +174,11 @@ impl SchemaDiff {
                 name: added_name.to_string(),
                 type_info: field.type_info.clone(),
                 optional: field.optional,
+                default: field.default.clone(),
             });
 
-            // Adding required fields is unsafe
-            if !field.optional {
+            // Adding required fields is unsafe unless the migration can fill in a default
+            if !field.optional && field.default.is_none() {
                 diff.is_safe = false;
             }
         }
@@ -166,7 +194,11 @@ impl SchemaDiff {
                     old_type: old_field.type_info.clone(),
                     new_type: new_field.type_info.clone(),
                 });
-                diff.is_safe = false; // Type changes are generally unsafe
+                // Type changes are generally unsafe, except switching between
+                // hash and B-tree collections (Borsh writes both sorted by key)
+                if !is_collection_kind_change(&old_field.type_info, &new_field.type_info) {
+                    diff.is_safe = false;
+                }
             }
         }
 
@@ -227,6 +259,46 @@ impl SchemaDiff {
             // Adding variants is generally safe
         }
 
+        // Detect a changed tag width
+        if old.repr() != new.repr() {
+            diff.changes.push(SchemaChange::EnumReprChanged {
+                old_repr: old.repr(),
+                new_repr: new.repr(),
+            });
+            diff.is_safe = false; // Old tags no longer decode
+        }
+
+        // Detect changed discriminants of surviving variants
+        let new_discriminants: HashMap<&str, u64> = new
+            .variants
+            .iter()
+            .map(|v| v.name())
+            .zip(new.discriminants())
+            .collect();
+        for (variant, old_discriminant) in old.variants.iter().zip(old.discriminants()) {
+            let Some(&new_discriminant) = new_discriminants.get(variant.name()) else {
+                continue;
+            };
+            if old_discriminant == new_discriminant {
+                continue;
+            }
+            let name = variant.name().to_string();
+            diff.changes.push(if old.is_flags() && new.is_flags() {
+                SchemaChange::FlagBitChanged {
+                    name,
+                    old_bit: old_discriminant,
+                    new_bit: new_discriminant,
+                }
+            } else {
+                SchemaChange::VariantDiscriminantChanged {
+                    name,
+                    old_discriminant,
+                    new_discriminant,
+                }
+            });
+            diff.is_safe = false; // Stored values now decode as another variant or flag
+        }
+
         Ok(diff)
     }
 
@@ -282,6 +354,93 @@ fn type_info_equal(a: &TypeInfo, b: &TypeInfo) -> bool {
         (TypeInfo::UserDefined(a_name), TypeInfo::UserDefined(b_name)) => a_name == b_name,
         (TypeInfo::Array(a_inner), TypeInfo::Array(b_inner)) => type_info_equal(a_inner, b_inner),
         (TypeInfo::Option(a_inner), TypeInfo::Option(b_inner)) => type_info_equal(a_inner, b_inner),
+        (
+            TypeInfo::Map {
+                key: a_key,
+                value: a_value,
+                ordered: a_ordered,
+            },
+            TypeInfo::Map {
+                key: b_key,
+                value: b_value,
+                ordered: b_ordered,
+            },
+        ) => {
+            a_ordered == b_ordered
+                && type_info_equal(a_key, b_key)
+                && type_info_equal(a_value, b_value)
+        }
+        (
+            TypeInfo::Set {
+                element: a_element,
+                ordered: a_ordered,
+            },
+            TypeInfo::Set {
+                element: b_element,
+                ordered: b_ordered,
+            },
+        ) => a_ordered == b_ordered && type_info_equal(a_element, b_element),
+        (TypeInfo::Boxed(a_inner), TypeInfo::Boxed(b_inner)) => type_info_equal(a_inner, b_inner),
+        (
+            TypeInfo::Instance {
+                name: a_name,
+                args: a_elements,
+            },
+            TypeInfo::Instance {
+                name: b_name,
+                args: b_elements,
+            },
+        ) if a_name == b_name => {
+            a_elements.len() == b_elements.len()
+                && a_elements
+                    .iter()
+                    .zip(b_elements)
+                    .all(|(a_element, b_element)| type_info_equal(a_element, b_element))
+        }
+        (TypeInfo::Tuple(a_elements), TypeInfo::Tuple(b_elements)) => {
+            a_elements.len() == b_elements.len()
+                && a_elements
+                    .iter()
+                    .zip(b_elements)
+                    .all(|(a_element, b_element)| type_info_equal(a_element, b_element))
+        }
+        _ => false,
+    }
+}
+
+/// Check whether a type change only swaps a hash collection for its B-tree
+/// counterpart (or vice versa), e.g. `HashMap<K, V>` -> `BTreeMap<K, V>`
+///
+/// Borsh serializes hash collections with entries sorted by key, so both
+/// kinds produce identical bytes and existing data stays readable.
+pub fn is_collection_kind_change(old: &TypeInfo, new: &TypeInfo) -> bool {
+    match (old, new) {
+        (
+            TypeInfo::Map {
+                key: old_key,
+                value: old_value,
+                ordered: old_ordered,
+            },
+            TypeInfo::Map {
+                key: new_key,
+                value: new_value,
+                ordered: new_ordered,
+            },
+        ) => {
+            old_ordered != new_ordered
+                && type_info_equal(old_key, new_key)
+                && type_info_equal(old_value, new_value)
+        }
+        (
+            TypeInfo::Set {
+                element: old_element,
+                ordered: old_ordered,
+            },
+            TypeInfo::Set {
+                element: new_element,
+                ordered: new_ordered,
+            },
+        ) => old_ordered != new_ordered && type_info_equal(old_element, new_element),
         _ => false,
     }
 }
@@ -293,13 +452,19 @@ fn describe_change(change: &SchemaChange) -> String {
             name,
             type_info,
             optional,
+            default,
         } => {
             let opt_str = if *optional { " (optional)" } else { "" };
+            let default_str = default
+                .as_ref()
+                .map(|value| format!(" = {}", value))
+                .unwrap_or_default();
             format!(
-                "✓ Added field: {} ({}){}",
+                "✓ Added field: {} ({}){}{}",
                 name,
                 type_info_display(type_info),
-                opt_str
+                opt_str,
+                default_str
             )
         }
         SchemaChange::FieldRemoved { name, type_info } => {
@@ -337,6 +502,35 @@ fn describe_change(change: &SchemaChange) -> String {
         SchemaChange::VariantRemoved { name } => {
             format!("⚠ Removed enum variant: {}", name)
         }
+        SchemaChange::VariantDiscriminantChanged {
+            name,
+            old_discriminant,
+            new_discriminant,
+        } => {
+            format!(
+                "⚠ Changed discriminant of variant: {} ({} -> {})",
+                name, old_discriminant, new_discriminant
+            )
+        }
+        SchemaChange::FlagBitChanged {
+            name,
+            old_bit,
+            new_bit,
+        } => {
+            format!(
+                "⚠ Moved flag: {} (bit {} -> bit {})",
+                name,
+                old_bit.trailing_zeros(),
+                new_bit.trailing_zeros()
+            )
+        }
+        SchemaChange::EnumReprChanged { old_repr, new_repr } => {
+            format!(
+                "⚠ Changed enum discriminant width: {} -> {}",
+                old_repr.name(),
+                new_repr.name()
+            )
+        }
     }
 }
 
@@ -350,7 +544,26 @@ fn type_info_display(type_info: &TypeInfo) -> String {
         TypeInfo::FixedArray { element, size } => {
             format!("[{}; {}]", type_info_display(element), size)
         }
+        TypeInfo::FixedString(size) => format!("FixedString<{}>", size),
         TypeInfo::Option(inner) => format!("Option<{}>", type_info_display(inner)),
+        TypeInfo::Map {
+            key,
+            value,
+            ordered,
+        } => format!(
+            "{}<{}, {}>",
+            if *ordered { "BTreeMap" } else { "HashMap" },
+            type_info_display(key),
+            type_info_display(value)
+        ),
+        TypeInfo::Set { element, ordered } => format!(
+            "{}<{}>",
+            if *ordered { "BTreeSet" } else { "HashSet" },
+            type_info_display(element)
+        ),
+        TypeInfo::Boxed(inner) => format!("Box<{}>", type_info_display(inner)),
+        TypeInfo::Tuple(elements) => TypeInfo::rust_tuple(elements, type_info_display),
+        TypeInfo::Instance { name, args } => instance_string(name, args, type_info_display),
     }
 }
 
@@ -366,6 +579,12 @@ pub fn generate_rust_migration(diff: &SchemaDiff, old_def: &TypeDefinition) -> S
                 diff.type_name
             )
         }
+        TypeDefinition::Const(_) => {
+            format!(
+                "// Constant '{}' - no migration needed (not stored on-chain)\n",
+                diff.type_name
+            )
+        }
     }
 }
 
@@ -461,7 +680,18 @@ fn generate_rust_struct_migration(diff: &SchemaDiff, old_struct: &StructDefiniti
     }
 
     for field_name in &new_field_names {
-        if old_fields.contains_key(field_name.as_str()) {
+        let collection_kind_changed = diff.changes.iter().any(|change| {
+            matches!(change, SchemaChange::FieldTypeChanged { name, old_type, new_type }
+                if name == field_name && is_collection_kind_change(old_type, new_type))
+        });
+
+        if collection_kind_changed {
+            // Same Borsh bytes, different Rust type - rebuild the collection
+            code.push(format!(
+                "            {}: old.{}.into_iter().collect(),\n",
+                field_name, field_name
+            ));
+        } else if old_fields.contains_key(field_name.as_str()) {
             // Field exists in old version - copy it
             code.push(format!("            {}: old.{},\n", field_name, field_name));
         } else {
@@ -512,15 +742,15 @@ fn generate_rust_enum_migration(diff: &SchemaDiff, old_enum: &EnumDefinition) ->
     // Generate old variants
     for variant in &old_enum.variants {
         match variant {
-            crate::ir::EnumVariantDefinition::Unit { name } => {
+            crate::ir::EnumVariantDefinition::Unit { name, .. } => {
                 code.push(format!("    {},\n", name));
             }
-            crate::ir::EnumVariantDefinition::Tuple { name, types } => {
+            crate::ir::EnumVariantDefinition::Tuple { name, types, .. } => {
                 let type_strs: Vec<String> =
                     types.iter().map(|t| map_type_to_rust(t, false)).collect();
                 code.push(format!("    {}({}),\n", name, type_strs.join(", ")));
             }
-            crate::ir::EnumVariantDefinition::Struct { name, fields } => {
+            crate::ir::EnumVariantDefinition::Struct { name, fields, .. } => {
                 code.push(format!("    {} {{\n", name));
                 for field in fields {
                     let rust_type = map_type_to_rust(&field.type_info, field.optional);
@@ -686,6 +916,12 @@ fn generate_rust_enum_migration(diff: &SchemaDiff, old_enum: &EnumDefinition) ->
     code.join("")
 }
 
+/// Render a generic instance such as `Wrapper<u64>` from its arguments
+fn instance_string(name: &str, args: &[TypeInfo], render: impl Fn(&TypeInfo) -> String) -> String {
+    let parts: Vec<String> = args.iter().map(render).collect();
+    format!("{}<{}>", name, parts.join(", "))
+}
+
 /// Map TypeInfo to Rust type string
 fn map_type_to_rust(type_info: &TypeInfo, optional: bool) -> String {
     let base_type = match type_info {
@@ -701,7 +937,30 @@ fn map_type_to_rust(type_info: &TypeInfo, optional: bool) -> String {
         TypeInfo::FixedArray { element, size } => {
             format!("[{}; {}]", map_type_to_rust(element, false), size)
         }
+        TypeInfo::FixedString(size) => format!("[u8; {}]", size),
         TypeInfo::Option(inner) => return format!("Option<{}>", map_type_to_rust(inner, false)),
+        TypeInfo::Map {
+            key,
+            value,
+            ordered,
+        } => format!(
+            "{}<{}, {}>",
+            if *ordered { "BTreeMap" } else { "HashMap" },
+            map_type_to_rust(key, false),
+            map_type_to_rust(value, false)
+        ),
+        TypeInfo::Set { element, ordered } => format!(
+            "{}<{}>",
+            if *ordered { "BTreeSet" } else { "HashSet" },
+            map_type_to_rust(element, false)
+        ),
+        TypeInfo::Boxed(inner) => format!("Box<{}>", map_type_to_rust(inner, false)),
+        TypeInfo::Tuple(elements) => {
+            TypeInfo::rust_tuple(elements, |element| map_type_to_rust(element, false))
+        }
+        TypeInfo::Instance { name, args } => {
+            instance_string(name, args, |arg| map_type_to_rust(arg, false))
+        }
     };
 
     if optional {
@@ -718,13 +977,18 @@ fn get_default_value_for_added_field(field_name: &str, changes: &[SchemaChange])
             name,
             type_info,
             optional,
+            default,
         } = change
         {
             if name == field_name {
                 if *optional {
                     return "None".to_string();
                 }
-                return get_default_value_for_type(type_info);
+                return match default {
+                    Some(DefaultValue::String(value)) => format!("{:?}.to_string()", value),
+                    Some(value) => value.to_string(),
+                    None => get_default_value_for_type(type_info),
+                };
             }
         }
     }
@@ -744,14 +1008,29 @@ fn get_default_value_for_type(type_info: &TypeInfo) -> String {
         }
         .to_string(),
         TypeInfo::Generic(_) => "Default::default()".to_string(),
-        TypeInfo::UserDefined(_) => "Default::default()".to_string(),
+        TypeInfo::UserDefined(_) | TypeInfo::Instance { .. } => "Default::default()".to_string(),
         TypeInfo::Array(_) => "Vec::new()".to_string(),
         TypeInfo::FixedArray { element, size } => {
             // For fixed arrays, generate [default(); size]
             let elem_default = get_default_value_for_type(element);
             format!("[{}; {}]", elem_default, size)
         }
+        TypeInfo::FixedString(size) => format!("[0; {}]", size),
         TypeInfo::Option(_) => "None".to_string(),
+        TypeInfo::Map { ordered, .. } => if *ordered {
+            "BTreeMap::new()"
+        } else {
+            "HashMap::new()"
+        }
+        .to_string(),
+        TypeInfo::Set { ordered, .. } => if *ordered {
+            "BTreeSet::new()"
+        } else {
+            "HashSet::new()"
+        }
+        .to_string(),
+        TypeInfo::Boxed(inner) => format!("Box::new({})", get_default_value_for_type(inner)),
+        TypeInfo::Tuple(elements) => TypeInfo::rust_tuple(elements, get_default_value_for_type),
     }
 }
 
@@ -769,6 +1048,12 @@ pub fn generate_typescript_migration(diff: &SchemaDiff, old_def: &TypeDefinition
                 diff.type_name
             )
         }
+        TypeDefinition::Const(_) => {
+            format!(
+                "// Constant '{}' - no migration needed (not stored on-chain)\n",
+                diff.type_name
+            )
+        }
     }
 }
 
@@ -921,13 +1206,13 @@ fn generate_typescript_enum_migration(diff: &SchemaDiff, old_enum: &EnumDefiniti
     // Generate type for each variant
     for variant in &old_enum.variants {
         match variant {
-            crate::ir::EnumVariantDefinition::Unit { name } => {
+            crate::ir::EnumVariantDefinition::Unit { name, .. } => {
                 code.push(format!(
                     "type {}V{}{} = {{ kind: '{}' }};\n",
                     diff.type_name, from_version, name, name
                 ));
             }
-            crate::ir::EnumVariantDefinition::Tuple { name, types } => {
+            crate::ir::EnumVariantDefinition::Tuple { name, types, .. } => {
                 code.push(format!(
                     "type {}V{}{} = {{ kind: '{}'; fields: [{}] }};\n",
                     diff.type_name,
@@ -941,7 +1226,7 @@ fn generate_typescript_enum_migration(diff: &SchemaDiff, old_enum: &EnumDefiniti
                         .join(", ")
                 ));
             }
-            crate::ir::EnumVariantDefinition::Struct { name, fields } => {
+            crate::ir::EnumVariantDefinition::Struct { name, fields, .. } => {
                 let field_types: Vec<String> = fields
                     .iter()
                     .map(|f| {
@@ -1108,9 +1393,29 @@ fn map_type_to_typescript(type_info: &TypeInfo, optional: bool) -> String {
         TypeInfo::FixedArray { element, .. } => {
             format!("{}[]", map_type_to_typescript(element, false))
         }
+        TypeInfo::FixedString(_) => "number[]".to_string(),
         TypeInfo::Option(inner) => {
             return format!("{} | undefined", map_type_to_typescript(inner, false))
         }
+        TypeInfo::Map { key, value, .. } => format!(
+            "Map<{}, {}>",
+            map_type_to_typescript(key, false),
+            map_type_to_typescript(value, false)
+        ),
+        TypeInfo::Set { element, .. } => {
+            format!("{}[]", map_type_to_typescript(element, false))
+        }
+        TypeInfo::Boxed(inner) => map_type_to_typescript(inner, false),
+        TypeInfo::Tuple(elements) => {
+            let element_types: Vec<String> = elements
+                .iter()
+                .map(|element| map_type_to_typescript(element, false))
+                .collect();
+            format!("[{}]", element_types.join(", "))
+        }
+        TypeInfo::Instance { name, args } => {
+            instance_string(name, args, |arg| map_type_to_typescript(arg, false))
+        }
     };
 
     if optional {
@@ -1130,13 +1435,17 @@ fn get_typescript_default_value_for_added_field(
             name,
             type_info,
             optional,
+            default,
         } = change
         {
             if name == field_name {
                 if *optional {
                     return "undefined".to_string();
                 }
-                return get_typescript_default_value_for_type(type_info);
+                return match default {
+                    Some(value) => default_value_literal(value, type_info),
+                    None => get_typescript_default_value_for_type(type_info),
+                };
             }
         }
     }
@@ -1148,7 +1457,7 @@ fn get_typescript_default_value_for_type(type_info: &TypeInfo) -> String {
     match type_info {
         TypeInfo::Primitive(name) => match name.as_str() {
             "u8" | "u16" | "u32" | "u64" | "i8" | "i16" | "i32" | "i64" | "f32" | "f64" => "0",
-            "u128" | "i128" => "0n",
+            "u128" | "i128" => "BigInt(0)",
             "bool" => "false",
             "string" | "String" => "\"\"",
             "PublicKey" => "PublicKey.default",
@@ -1156,14 +1465,25 @@ fn get_typescript_default_value_for_type(type_info: &TypeInfo) -> String {
         }
         .to_string(),
         TypeInfo::Generic(_) => "undefined".to_string(),
-        TypeInfo::UserDefined(_) => "undefined".to_string(),
+        TypeInfo::UserDefined(_) | TypeInfo::Instance { .. } => "undefined".to_string(),
         TypeInfo::Array(_) => "[]".to_string(),
         TypeInfo::FixedArray { element, size } => {
             // For fixed arrays, generate an array of size filled with defaults
             let elem_default = get_typescript_default_value_for_type(element);
             format!("new Array({}).fill({})", size, elem_default)
         }
+        TypeInfo::FixedString(size) => format!("new Array({}).fill(0)", size),
         TypeInfo::Option(_) => "undefined".to_string(),
+        TypeInfo::Map { .. } => "new Map()".to_string(),
+        TypeInfo::Set { .. } => "[]".to_string(),
+        TypeInfo::Boxed(inner) => get_typescript_default_value_for_type(inner),
+        TypeInfo::Tuple(elements) => {
+            let element_defaults: Vec<String> = elements
+                .iter()
+                .map(get_typescript_default_value_for_type)
+                .collect();
+            format!("[{}]", element_defaults.join(", "))
+        }
     }
 }
 
@@ -1182,14 +1502,14 @@ mod tests {
                     name: field_name.to_string(),
                     type_info,
                     optional,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 })
                 .collect(),
             metadata: Default::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         }
     }
 
@@ -1389,6 +1709,81 @@ mod tests {
         assert_eq!(diff.safety(), MigrationSafety::Unsafe);
     }
 
+    #[test]
+    fn test_collection_kind_change_is_safe() {
+        let set = |ordered| TypeInfo::Set {
+            element: Box::new(TypeInfo::Primitive("u64".to_string())),
+            ordered,
+        };
+        let v1 = create_test_struct("Registry", vec![("ids", set(false), false)]);
+        let v2 = create_test_struct("Registry", vec![("ids", set(true), false)]);
+
+        let diff = SchemaDiff::compute(
+            &TypeDefinition::Struct(v1.clone()),
+            &TypeDefinition::Struct(v2),
+        )
+        .unwrap();
+
+        assert_eq!(diff.changes.len(), 1);
+        assert!(matches!(
+            diff.changes[0],
+            SchemaChange::FieldTypeChanged { .. }
+        ));
+        // HashSet and BTreeSet share the same Borsh encoding
+        assert_eq!(diff.safety(), MigrationSafety::Safe);
+
+        let migration = generate_rust_migration(&diff, &TypeDefinition::Struct(v1));
+        assert!(migration.contains("pub ids: HashSet<u64>,"));
+        assert!(migration.contains("ids: old.ids.into_iter().collect(),"));
+    }
+
+    #[test]
+    fn test_field_added_with_default_is_filled_in() {
+        let v1 = create_test_struct(
+            "Market",
+            vec![(
+                "authority",
+                TypeInfo::Primitive("PublicKey".to_string()),
+                false,
+            )],
+        );
+        let mut v2 = create_test_struct(
+            "Market",
+            vec![
+                (
+                    "authority",
+                    TypeInfo::Primitive("PublicKey".to_string()),
+                    false,
+                ),
+                ("fee_bps", TypeInfo::Primitive("u16".to_string()), false),
+                ("symbol", TypeInfo::Primitive("String".to_string()), false),
+                ("supply", TypeInfo::Primitive("u128".to_string()), false),
+            ],
+        );
+        v2.fields[1].default = Some(DefaultValue::Integer(100));
+        v2.fields[2].default = Some(DefaultValue::String("USDC".to_string()));
+        v2.fields[3].default = Some(DefaultValue::Integer(5));
+
+        let diff = SchemaDiff::compute(
+            &TypeDefinition::Struct(v1.clone()),
+            &TypeDefinition::Struct(v2),
+        )
+        .unwrap();
+        // The migration fills in the defaults, so old data can be upgraded
+        assert_eq!(diff.safety(), MigrationSafety::Safe);
+        assert!(diff.describe().contains("Added field: fee_bps (u16) = 100"));
+
+        let rust = generate_rust_migration(&diff, &TypeDefinition::Struct(v1.clone()));
+        assert!(rust.contains("fee_bps: 100, // Default: Added in"));
+        assert!(rust.contains("symbol: \"USDC\".to_string(), // Default: Added in"));
+
+        let typescript = generate_typescript_migration(&diff, &TypeDefinition::Struct(v1));
+        assert!(typescript.contains("fee_bps: 100, // Default: Added in"));
+        assert!(typescript.contains("symbol: \"USDC\", // Default: Added in"));
+        // Same literal as the generated TypeScript types use
+        assert!(typescript.contains("supply: BigInt(\"5\"), // Default: Added in"));
+    }
+
     #[test]
     fn test_enum_variant_added() {
         let v1 = EnumDefinition {
@@ -1397,9 +1792,15 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
@@ -1409,6 +1810,8 @@ mod tests {
             },
             visibility: crate::ir::Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let v2 = EnumDefinition {
@@ -1417,12 +1820,21 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Finished".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
@@ -1432,6 +1844,8 @@ mod tests {
             },
             visibility: crate::ir::Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let diff =
@@ -1453,12 +1867,21 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Deprecated".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
@@ -1468,6 +1891,8 @@ mod tests {
             },
             visibility: crate::ir::Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let v2 = EnumDefinition {
@@ -1476,9 +1901,15 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
@@ -1488,6 +1919,8 @@ mod tests {
             },
             visibility: crate::ir::Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let diff =
@@ -1509,12 +1942,21 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Deprecated".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
@@ -1524,6 +1966,8 @@ mod tests {
             },
             visibility: crate::ir::Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let new_enum = EnumDefinition {
@@ -1532,12 +1976,21 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Finished".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
@@ -1547,6 +2000,8 @@ mod tests {
             },
             visibility: crate::ir::Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let diff = SchemaDiff::compute(
@@ -1573,12 +2028,21 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Deprecated".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
@@ -1588,6 +2052,8 @@ mod tests {
             },
             visibility: crate::ir::Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let new_enum = EnumDefinition {
@@ -1596,12 +2062,21 @@ mod tests {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Active".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Paused".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Unit {
                     name: "Finished".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
             ],
             metadata: Metadata {
@@ -1611,6 +2086,8 @@ mod tests {
             },
             visibility: crate::ir::Visibility::Public,
             module_path: vec![],
+            reexports: vec![],
+            span: None,
         };
 
         let diff = SchemaDiff::compute(
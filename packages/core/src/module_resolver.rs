@@ -3,22 +3,15 @@
 
 //! Module resolver for handling hierarchical LUMOS module structures
 //!
-//! This module provides functionality to:
-//! - Resolve `mod name;` declarations to files (name.lumos or name/mod.lumos)
-//! - Build a module tree structure
-//! - Resolve `use` statements with path resolution (crate::, super::, self::)
-//! - Detect circular module dependencies
-//! - Cache loaded modules to avoid duplicate parsing
-
-use crate::ast::{Item as AstItem, LumosFile};
-use crate::error::{LumosError, Result};
+//! Kept for API compatibility: resolution is done by [`ProjectGraph`], which
+//! follows `mod` declarations and `import` statements together. New code
+//! should use [`ProjectGraph`] directly.
+
+use crate::ast::LumosFile;
+use crate::error::Result;
 use crate::ir::TypeDefinition;
-use crate::parser::parse_lumos_file;
-use crate::transform::{
-    transform_to_ir_with_resolver_no_validation, validate_user_defined_types, TypeAliasResolver,
-};
+use crate::project::ProjectGraph;
 use std::collections::HashMap;
-use std::fs;
 use std::path::{Path, PathBuf};
 
 /// Represents a node in the module tree
@@ -41,200 +34,40 @@ pub struct ModuleNode {
 }
 
 /// Module resolver that handles hierarchical module loading
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ModuleResolver {
+    /// Graph of the last resolved entry file
+    graph: Option<ProjectGraph>,
+
     /// Map of absolute path -> module node
     modules: HashMap<PathBuf, ModuleNode>,
-
-    /// Root module path
-    root_path: PathBuf,
-
-    /// Stack of modules currently being loaded (for circular dependency detection)
-    loading_stack: Vec<PathBuf>,
 }
 
 impl ModuleResolver {
     /// Create a new module resolver
     pub fn new() -> Self {
-        Self {
-            modules: HashMap::new(),
-            root_path: PathBuf::new(),
-            loading_stack: Vec::new(),
-        }
+        Self::default()
     }
 
     /// Resolve all modules starting from an entry file and return combined type definitions
-    ///
-    /// This is the main entry point for the module resolver. It:
-    /// 1. Loads the entry file as the root module
-    /// 2. Recursively loads all child modules declared with `mod name;`
-    /// 3. Resolves all type aliases across all modules
-    /// 4. Transforms all modules to IR
-    /// 5. Validates all user-defined types
     pub fn resolve_modules(&mut self, entry_file: &Path) -> Result<Vec<TypeDefinition>> {
-        // Canonicalize the entry file path
-        let entry_path = self.canonicalize_path(entry_file)?;
-        self.root_path = entry_path.clone();
-
-        // Load the root module and all its dependencies
-        self.load_module_recursive(&entry_path, None)?;
-
-        // Validate all use statements
-        self.validate_use_statements()?;
-
-        // First pass: Collect all type aliases from all modules
-        let mut resolver = TypeAliasResolver::new();
-        for module_node in self.modules.values() {
-            for item in &module_node.ast.items {
-                if let AstItem::TypeAlias(alias_def) = item {
-                    resolver.add_alias(alias_def.name.clone(), alias_def.target.clone())?;
-                }
-            }
-        }
-
-        // Resolve all aliases recursively
-        resolver.resolve_all_aliases()?;
-
-        // Second pass: Transform all modules with shared resolver (skip per-file validation)
-        let mut all_type_defs = Vec::new();
-        for module_node in self.modules.values() {
-            let type_defs =
-                transform_to_ir_with_resolver_no_validation(module_node.ast.clone(), &resolver)?;
-            all_type_defs.extend(type_defs);
-        }
-
-        // Third pass: Validate all user-defined types across all modules
-        validate_user_defined_types(&all_type_defs)?;
-
-        Ok(all_type_defs)
-    }
-
-    /// Recursively load a module and all its child modules
-    fn load_module_recursive(&mut self, file_path: &Path, parent: Option<PathBuf>) -> Result<()> {
-        // Check if already loaded (cache hit)
-        if self.modules.contains_key(file_path) {
-            return Ok(());
-        }
-
-        // Check for circular dependencies
-        if self.loading_stack.contains(&file_path.to_path_buf()) {
-            return Err(LumosError::SchemaParse(
-                format!(
-                    "Circular module dependency detected: {} -> {}",
-                    self.format_loading_chain(),
-                    file_path.display()
-                ),
-                None,
-            ));
-        }
-
-        // Add to loading stack
-        self.loading_stack.push(file_path.to_path_buf());
-
-        // Read and parse the file
-        let content = fs::read_to_string(file_path).map_err(|e| {
-            LumosError::SchemaParse(
-                format!("Failed to read file '{}': {}", file_path.display(), e),
-                None,
-            )
-        })?;
-
-        let ast = parse_lumos_file(&content)?;
-
-        // Get the directory of the current file for resolving relative modules
-        let current_dir = file_path.parent().ok_or_else(|| {
-            LumosError::SchemaParse(format!("Invalid file path: {}", file_path.display()), None)
-        })?;
-
-        // Extract child module declarations
-        let mut children = HashMap::new();
-        for item in &ast.items {
-            if let AstItem::Module(module) = item {
-                let child_path = self.resolve_module_path(current_dir, &module.name)?;
-                children.insert(module.name.clone(), child_path.clone());
-
-                // Recursively load the child module
-                self.load_module_recursive(&child_path, Some(file_path.to_path_buf()))?;
-            }
-        }
-
-        // Create module node
-        let module_name = file_path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .unwrap_or("")
-            .to_string();
-
-        let module_node = ModuleNode {
-            name: if file_path == self.root_path {
-                String::new() // Root module has empty name
-            } else {
-                module_name
-            },
-            file_path: file_path.to_path_buf(),
-            ast,
-            children,
-            parent,
-        };
-
-        // Remove from loading stack
-        self.loading_stack.pop();
-
-        // Add to modules map
-        self.modules.insert(file_path.to_path_buf(), module_node);
-
-        Ok(())
-    }
-
-    /// Resolve a module declaration to a file path
-    ///
-    /// Tries two strategies:
-    /// 1. Sibling file: `current_dir/name.lumos`
-    /// 2. Directory module: `current_dir/name/mod.lumos`
-    ///
-    /// Prefers sibling file if both exist.
-    fn resolve_module_path(&self, current_dir: &Path, module_name: &str) -> Result<PathBuf> {
-        // Strategy 1: Sibling file (name.lumos)
-        let sibling_path = current_dir.join(format!("{}.lumos", module_name));
-        if sibling_path.exists() {
-            return self.canonicalize_path(&sibling_path);
-        }
-
-        // Strategy 2: Directory module (name/mod.lumos)
-        let dir_module_path = current_dir.join(module_name).join("mod.lumos");
-        if dir_module_path.exists() {
-            return self.canonicalize_path(&dir_module_path);
-        }
-
-        // Neither exists - error
-        Err(LumosError::SchemaParse(
-            format!(
-                "Module '{}' not found. Tried:\n  - {}\n  - {}",
-                module_name,
-                sibling_path.display(),
-                dir_module_path.display()
-            ),
-            None,
-        ))
-    }
-
-    /// Canonicalize a path to an absolute path
-    fn canonicalize_path(&self, path: &Path) -> Result<PathBuf> {
-        path.canonicalize().map_err(|e| {
-            LumosError::SchemaParse(
-                format!("Failed to resolve path '{}': {}", path.display(), e),
-                None,
-            )
-        })
-    }
-
-    /// Format the current loading chain for error messages
-    fn format_loading_chain(&self) -> String {
-        self.loading_stack
+        let graph = ProjectGraph::load(entry_file)?;
+        self.modules = graph
+            .files()
             .iter()
-            .map(|p| p.file_name().and_then(|n| n.to_str()).unwrap_or("unknown"))
-            .collect::<Vec<_>>()
-            .join(" -> ")
+            .map(|file| {
+                let node = ModuleNode {
+                    name: file.module_path.last().cloned().unwrap_or_default(),
+                    file_path: file.path.clone(),
+                    ast: file.ast.clone(),
+                    children: file.children.clone().into_iter().collect(),
+                    parent: file.parent.clone(),
+                };
+                (file.path.clone(), node)
+            })
+            .collect();
+
+        self.graph.insert(graph).resolve()
     }
 
     /// Get all loaded module paths
@@ -244,230 +77,23 @@ impl ModuleResolver {
 
     /// Get the module tree structure (for debugging/visualization)
     pub fn module_tree(&self) -> Option<&ModuleNode> {
-        self.modules.get(&self.root_path)
+        let root = self.graph.as_ref()?.root();
+        self.modules.get(root)
     }
 
     /// Validate all use statements in all modules
-    ///
-    /// Checks that:
-    /// 1. The path can be resolved to a module
-    /// 2. The referenced type exists
-    /// 3. The type is accessible (visibility check)
     pub fn validate_use_statements(&self) -> Result<()> {
-        for (module_path, module_node) in &self.modules {
-            for item in &module_node.ast.items {
-                if let AstItem::Use(use_stmt) = item {
-                    self.validate_use_statement(module_path, use_stmt)?;
-                }
-            }
-        }
-        Ok(())
-    }
-
-    /// Validate a single use statement
-    fn validate_use_statement(
-        &self,
-        current_module_path: &Path,
-        use_stmt: &crate::ast::UseStatement,
-    ) -> Result<()> {
-        use crate::ast::Visibility;
-
-        // Get the final identifier (the type being imported)
-        let type_name = use_stmt.path.final_ident().ok_or_else(|| {
-            LumosError::SchemaParse(format!("Invalid use path: {}", use_stmt.path), None)
-        })?;
-
-        // Resolve the module path
-        let target_module_path =
-            self.resolve_use_path(current_module_path, &use_stmt.path.segments)?;
-
-        // Find the type in the target module
-        let target_module = self.modules.get(&target_module_path).ok_or_else(|| {
-            LumosError::SchemaParse(
-                format!("Module not found for path: {}", use_stmt.path),
-                None,
-            )
-        })?;
-
-        // Check if the type exists in the target module
-        let mut type_found = false;
-        let mut type_visibility = Visibility::Private;
-
-        for item in &target_module.ast.items {
-            let (name, visibility) = match item {
-                AstItem::Struct(s) => (Some(&s.name), &s.visibility),
-                AstItem::Enum(e) => (Some(&e.name), &e.visibility),
-                AstItem::TypeAlias(t) => (Some(&t.name), &t.visibility),
-                AstItem::Module(_) | AstItem::Use(_) => (None, &Visibility::Private),
-            };
-
-            if let Some(name) = name {
-                if name == type_name {
-                    type_found = true;
-                    type_visibility = visibility.clone();
-                    break;
-                }
-            }
-        }
-
-        if !type_found {
-            return Err(LumosError::SchemaParse(
-                format!(
-                    "Type '{}' not found in module '{}'",
-                    type_name,
-                    target_module_path.display()
-                ),
-                None,
-            ));
-        }
-
-        // Check visibility (type must be public if importing from different module)
-        if target_module_path != current_module_path && type_visibility == Visibility::Private {
-            return Err(LumosError::SchemaParse(
-                format!(
-                    "Type '{}' is private and cannot be imported from '{}'",
-                    type_name,
-                    target_module_path.display()
-                ),
-                None,
-            ));
-        }
-
-        Ok(())
-    }
-
-    /// Resolve a use path to an absolute module path
-    ///
-    /// Handles:
-    /// - `crate::models::User` → resolve from root
-    /// - `super::User` → resolve from parent
-    /// - `self::types::UserId` → resolve from current
-    /// - `models::User` → resolve from current (implicit self)
-    fn resolve_use_path(
-        &self,
-        current_module_path: &Path,
-        segments: &[crate::ast::PathSegment],
-    ) -> Result<PathBuf> {
-        use crate::ast::PathSegment;
-
-        if segments.is_empty() {
-            return Ok(current_module_path.to_path_buf());
+        match &self.graph {
+            Some(graph) => graph.validate(),
+            None => Ok(()),
         }
-
-        let mut target_path = match &segments[0] {
-            PathSegment::Crate => {
-                // Absolute path from root
-                self.root_path.clone()
-            }
-            PathSegment::Super => {
-                // Relative to parent module
-                let current_module = self.modules.get(current_module_path).ok_or_else(|| {
-                    LumosError::SchemaParse(
-                        format!(
-                            "Current module not found: {}",
-                            current_module_path.display()
-                        ),
-                        None,
-                    )
-                })?;
-
-                current_module.parent.clone().ok_or_else(|| {
-                    LumosError::SchemaParse("Cannot use 'super' in root module".to_string(), None)
-                })?
-            }
-            PathSegment::SelfPath => {
-                // Relative to current module
-                current_module_path.to_path_buf()
-            }
-            PathSegment::Ident(name) => {
-                // Implicit self - relative to current module
-                let current_module = self.modules.get(current_module_path).ok_or_else(|| {
-                    LumosError::SchemaParse(
-                        format!(
-                            "Current module not found: {}",
-                            current_module_path.display()
-                        ),
-                        None,
-                    )
-                })?;
-
-                // Check if this is a child module
-                if let Some(child_path) = current_module.children.get(name) {
-                    return self.resolve_use_path(child_path, &segments[1..]);
-                } else {
-                    // If not a child module, the type should be in the current module
-                    return Ok(current_module_path.to_path_buf());
-                }
-            }
-        };
-
-        // Traverse the remaining path segments
-        for segment in &segments[1..] {
-            match segment {
-                PathSegment::Ident(name) => {
-                    let current_module = self.modules.get(&target_path).ok_or_else(|| {
-                        LumosError::SchemaParse(
-                            format!("Module not found: {}", target_path.display()),
-                            None,
-                        )
-                    })?;
-
-                    // Check if this is a child module
-                    if let Some(child_path) = current_module.children.get(name) {
-                        target_path = child_path.clone();
-                    } else {
-                        // Not a child module, so this must be the final type name
-                        // Return the current module path
-                        return Ok(target_path);
-                    }
-                }
-                PathSegment::Super => {
-                    let current_module = self.modules.get(&target_path).ok_or_else(|| {
-                        LumosError::SchemaParse(
-                            format!("Module not found: {}", target_path.display()),
-                            None,
-                        )
-                    })?;
-
-                    target_path = current_module.parent.clone().ok_or_else(|| {
-                        LumosError::SchemaParse(
-                            format!(
-                                "Cannot use 'super' - module '{}' has no parent",
-                                target_path.display()
-                            ),
-                            None,
-                        )
-                    })?;
-                }
-                PathSegment::Crate | PathSegment::SelfPath => {
-                    return Err(LumosError::SchemaParse(
-                        format!(
-                            "'{}' can only appear at the start of a path",
-                            if matches!(segment, PathSegment::Crate) {
-                                "crate"
-                            } else {
-                                "self"
-                            }
-                        ),
-                        None,
-                    ));
-                }
-            }
-        }
-
-        Ok(target_path)
-    }
-}
-
-impl Default for ModuleResolver {
-    fn default() -> Self {
-        Self::new()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ir::TypeInfo;
     use std::fs;
     use tempfile::TempDir;
 
@@ -692,6 +318,49 @@ pub struct User {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_use_statement_constant() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let limits_path = temp_dir.path().join("limits.lumos");
+        fs::write(
+            &limits_path,
+            r#"
+pub const MAX_SEATS: usize = 8;
+"#,
+        )
+        .unwrap();
+
+        let main_path = temp_dir.path().join("main.lumos");
+        fs::write(
+            &main_path,
+            r#"
+mod limits;
+use limits::MAX_SEATS;
+
+pub struct Table {
+    seats: [u64; MAX_SEATS],
+}
+"#,
+        )
+        .unwrap();
+
+        let mut resolver = ModuleResolver::new();
+        let type_defs = resolver.resolve_modules(&main_path).unwrap();
+
+        let table = type_defs
+            .iter()
+            .find_map(|t| match t {
+                TypeDefinition::Struct(s) if s.name == "Table" => Some(s),
+                _ => None,
+            })
+            .unwrap();
+        assert!(matches!(
+            table.fields[0].type_info,
+            TypeInfo::FixedArray { size: 8, .. }
+        ));
+    }
+
     #[test]
     fn test_use_statement_with_crate() {
         let temp_dir = TempDir::new().unwrap();
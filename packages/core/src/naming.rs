@@ -0,0 +1,257 @@
+// Licensed under either of Apache License, Version 2.0 or MIT license at your option.
+// Copyright 2025 RECTOR-LABS
+
+//! Per-language names for types and fields
+//!
+//! A schema can give a field or type a different name in each target with
+//! `#[rename(ts = "feeBps", py = "fee")]`, and set a naming policy for a whole
+//! file with inner attributes:
+//!
+//! ```text
+//! #![rename_fields(ts = "camelCase", idl = "camelCase")]
+//! #![rename_types(py = "PascalCase")]
+//! ```
+//!
+//! The transform resolves both into [`Renames`] on each field and type, so the
+//! IR always carries the final name per target. Generators call
+//! [`apply_renames`] on entry, which renames the definitions and every
+//! reference to them; Borsh schemas are built from the same renamed fields,
+//! so their keys always match the generated properties.
+
+use crate::ir::{EnumVariantDefinition, FieldDefinition, TypeDefinition, TypeInfo};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+
+/// A generated output whose names `#[rename]` can change
+///
+/// Rust always uses the schema names: they are the program's identifiers and
+/// Anchor derives account, event and instruction discriminators from them.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum NameTarget {
+    /// TypeScript (`ts`)
+    #[serde(rename = "ts")]
+    TypeScript,
+    /// Python and Seahorse (`py`)
+    #[serde(rename = "py")]
+    Python,
+    /// Go (`go`)
+    Go,
+    /// Ruby (`rb`)
+    #[serde(rename = "rb")]
+    Ruby,
+    /// Anchor IDL (`idl`)
+    Idl,
+}
+
+impl NameTarget {
+    /// Every target, in the order their keys are documented
+    pub const ALL: [NameTarget; 5] = [
+        NameTarget::TypeScript,
+        NameTarget::Python,
+        NameTarget::Go,
+        NameTarget::Ruby,
+        NameTarget::Idl,
+    ];
+
+    /// Key used in `#[rename(..)]` and the naming policies
+    pub fn key(self) -> &'static str {
+        match self {
+            NameTarget::TypeScript => "ts",
+            NameTarget::Python => "py",
+            NameTarget::Go => "go",
+            NameTarget::Ruby => "rb",
+            NameTarget::Idl => "idl",
+        }
+    }
+
+    /// Parse a key such as `ts`
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|target| target.key() == key)
+    }
+}
+
+impl fmt::Display for NameTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.key())
+    }
+}
+
+/// Names of a field or type that differ from the schema name, per target
+pub type Renames = BTreeMap<NameTarget, String>;
+
+/// Naming convention of a `#![rename_fields]`/`#![rename_types]` policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+    /// `feeBps`
+    Camel,
+    /// `fee_bps`
+    Snake,
+    /// `FeeBps`
+    Pascal,
+    /// `FEE_BPS`
+    ScreamingSnake,
+}
+
+impl Case {
+    /// Every convention, in the order they are documented
+    pub const ALL: [Case; 4] = [Case::Camel, Case::Snake, Case::Pascal, Case::ScreamingSnake];
+
+    /// Name used in policies (e.g., `"camelCase"`)
+    pub fn name(self) -> &'static str {
+        match self {
+            Case::Camel => "camelCase",
+            Case::Snake => "snake_case",
+            Case::Pascal => "PascalCase",
+            Case::ScreamingSnake => "SCREAMING_SNAKE_CASE",
+        }
+    }
+
+    /// Parse a convention name such as `"camelCase"`
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|case| case.name() == name)
+    }
+
+    /// Convert an identifier written in any convention
+    pub fn apply(self, name: &str) -> String {
+        let words = split_words(name);
+        match self {
+            Case::Snake => words.join("_"),
+            Case::ScreamingSnake => words.join("_").to_uppercase(),
+            Case::Camel | Case::Pascal => words
+                .iter()
+                .enumerate()
+                .map(|(i, word)| {
+                    if i == 0 && self == Case::Camel {
+                        word.clone()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Split an identifier into lowercase words at underscores and case changes
+///
+/// Acronyms stay together: `HTTPServer` is `http` + `server`.
+fn split_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in name.split('_').filter(|p| !p.is_empty()) {
+        let chars: Vec<char> = part.chars().collect();
+        let mut word = String::new();
+        for (i, &c) in chars.iter().enumerate() {
+            let boundary = i > 0
+                && c.is_uppercase()
+                && (chars[i - 1].is_lowercase()
+                    || chars[i - 1].is_ascii_digit()
+                    || chars.get(i + 1).is_some_and(|next| next.is_lowercase()));
+            if boundary && !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            word.extend(c.to_lowercase());
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Rename types and fields for one target, including every reference to a renamed type
+///
+/// Definitions keep their [`Renames`], so a generator can tell an explicit
+/// name from one it should still adapt to its own conventions.
+pub fn apply_renames(type_defs: &[TypeDefinition], target: NameTarget) -> Vec<TypeDefinition> {
+    let type_names: HashMap<String, String> = type_defs
+        .iter()
+        .filter_map(|type_def| {
+            let renamed = type_def.metadata()?.renames.get(&target)?;
+            Some((type_def.name().to_string(), renamed.clone()))
+        })
+        .collect();
+
+    let mut renamed = type_defs.to_vec();
+    for type_def in &mut renamed {
+        match type_def {
+            TypeDefinition::Struct(s) => {
+                if let Some(name) = type_names.get(&s.name) {
+                    s.name = name.clone();
+                }
+                rename_fields(&mut s.fields, target);
+                rename_fields(&mut s.metadata.instruction_args, target);
+            }
+            TypeDefinition::Enum(e) => {
+                if let Some(name) = type_names.get(&e.name) {
+                    e.name = name.clone();
+                }
+                for variant in &mut e.variants {
+                    if let EnumVariantDefinition::Struct { fields, .. } = variant {
+                        rename_fields(fields, target);
+                    }
+                }
+            }
+            TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {}
+        }
+
+        if !type_names.is_empty() {
+            for type_info in type_def.field_types_mut() {
+                type_info.walk_mut(&mut |t| match t {
+                    TypeInfo::UserDefined(name) | TypeInfo::Instance { name, .. } => {
+                        if let Some(renamed) = type_names.get(name.as_str()) {
+                            *name = renamed.clone();
+                        }
+                    }
+                    _ => {}
+                });
+            }
+        }
+    }
+    renamed
+}
+
+fn rename_fields(fields: &mut [FieldDefinition], target: NameTarget) {
+    for field in fields {
+        if let Some(name) = field.renames.get(&target) {
+            field.name = name.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_case_conversion() {
+        assert_eq!(Case::Camel.apply("fee_bps"), "feeBps");
+        assert_eq!(Case::Pascal.apply("fee_bps"), "FeeBps");
+        assert_eq!(Case::Snake.apply("feeBps"), "fee_bps");
+        assert_eq!(Case::Snake.apply("HTTPServer"), "http_server");
+        assert_eq!(Case::ScreamingSnake.apply("maxFee2x"), "MAX_FEE2X");
+        assert_eq!(Case::Camel.apply("UserAccount"), "userAccount");
+        assert_eq!(Case::Pascal.apply("token_v2_mint"), "TokenV2Mint");
+    }
+
+    #[test]
+    fn test_keys_round_trip() {
+        for target in NameTarget::ALL {
+            assert_eq!(NameTarget::from_key(target.key()), Some(target));
+        }
+        for case in Case::ALL {
+            assert_eq!(Case::from_name(case.name()), Some(case));
+        }
+        assert_eq!(NameTarget::from_key("typescript"), None);
+    }
+}
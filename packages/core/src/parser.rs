@@ -34,11 +34,13 @@
 //! ```
 
 use crate::ast::{
-    Attribute, AttributeValue, EnumDef, EnumVariant, FieldDef, Import, Item as AstItem, LumosFile,
-    Module, ModulePath, PathSegment, StructDef, TypeAlias, TypeSpec, UseStatement, Visibility,
+    Attribute, AttributeValue, ConstDef, ConstExpr, DefaultExpr, EnumDef, EnumVariant, FieldDef,
+    Import, Item as AstItem, LumosFile, Module, ModulePath, PathSegment, RangeBound, RangeExpr,
+    StructDef, TypeAlias, TypeSpec, UseStatement, Visibility,
 };
-use crate::error::{LumosError, Result};
+use crate::error::{join, ErrorCollector, LumosError, Result, SourceLocation};
 use regex::Regex;
+use syn::spanned::Spanned;
 use syn::{Item, Meta, Type, UseTree};
 
 /// Extract JavaScript-style import statements from source code
@@ -119,8 +121,13 @@ fn extract_imports(input: &str) -> Result<(Vec<Import>, String)> {
         });
     }
 
-    // Remove all import statements from the input
-    remaining = import_regex.replace_all(&remaining, "").to_string();
+    // Remove all import statements from the input, keeping their line breaks so
+    // that spans in the remaining code still point at the original lines
+    remaining = import_regex
+        .replace_all(&remaining, |caps: &regex::Captures| {
+            "\n".repeat(caps[0].matches('\n').count())
+        })
+        .to_string();
 
     Ok((imports, remaining))
 }
@@ -246,6 +253,7 @@ fn parse_type_alias(item: syn::ItemType) -> Result<TypeAlias> {
     let name = item.ident.to_string();
     let span = Some(item.ident.span());
     let visibility = parse_visibility(&item.vis);
+    let attributes = parse_attributes(&item.attrs)?;
 
     // Parse the target type
     let (target, _optional) = parse_type(&item.ty)?;
@@ -254,10 +262,98 @@ fn parse_type_alias(item: syn::ItemType) -> Result<TypeAlias> {
         name,
         visibility,
         target,
+        attributes,
+        span,
+    })
+}
+
+/// Parse a constant definition
+///
+/// Converts `const MAX_NAME_LEN: usize = 32;` into a ConstDef AST node. Constants must
+/// have an unsigned integer type, and their value must be an integer literal or the
+/// name of another constant.
+///
+/// # Arguments
+///
+/// * `item` - syn ItemConst node representing a constant definition
+///
+/// # Returns
+///
+/// * `Ok(ConstDef)` - Successfully parsed constant
+/// * `Err(LumosError)` - Unsupported type or value expression
+fn parse_const(item: syn::ItemConst) -> Result<ConstDef> {
+    let name = item.ident.to_string();
+    let span = Some(item.ident.span());
+    let location = Some(SourceLocation::from_span(item.ident.span()));
+    let visibility = parse_visibility(&item.vis);
+    let docs = parse_doc_comments(&item.attrs);
+
+    let type_name = match item.ty.as_ref() {
+        Type::Path(type_path) if type_path.path.segments.len() == 1 => {
+            type_path.path.segments[0].ident.to_string()
+        }
+        _ => String::new(),
+    };
+
+    if !matches!(
+        type_name.as_str(),
+        "u8" | "u16" | "u32" | "u64" | "u128" | "usize"
+    ) {
+        return Err(LumosError::SchemaParse(
+            format!(
+                "Constant '{}' must have an unsigned integer type (u8, u16, u32, u64, u128 or usize)",
+                name
+            ),
+            location,
+        ));
+    }
+
+    let value = parse_const_expr(&item.expr).map_err(|e| {
+        LumosError::SchemaParse(
+            format!("Invalid value for constant '{}': {}", name, e.message()),
+            location,
+        )
+    })?;
+
+    Ok(ConstDef {
+        name,
+        visibility,
+        type_name,
+        value,
+        docs,
         span,
     })
 }
 
+/// Parse an integer literal or a constant name (e.g., `32` or `MAX_NAME_LEN`)
+fn parse_const_expr(expr: &syn::Expr) -> Result<ConstExpr> {
+    match expr {
+        syn::Expr::Lit(expr_lit) => match &expr_lit.lit {
+            syn::Lit::Int(lit_int) => lit_int
+                .base10_parse::<u128>()
+                .map(ConstExpr::Literal)
+                .map_err(|e| {
+                    LumosError::SchemaParse(format!("Invalid integer literal: {}", e), None)
+                }),
+            _ => Err(LumosError::SchemaParse(
+                "Expected an integer literal".to_string(),
+                None,
+            )),
+        },
+        syn::Expr::Path(expr_path) => match expr_path.path.get_ident() {
+            Some(ident) => Ok(ConstExpr::Named(ident.to_string())),
+            None => Err(LumosError::SchemaParse(
+                "Constant references must be a single identifier".to_string(),
+                None,
+            )),
+        },
+        _ => Err(LumosError::SchemaParse(
+            "Expected an integer literal or constant name".to_string(),
+            None,
+        )),
+    }
+}
+
 /// Parse a module declaration
 ///
 /// Converts `mod foo;` into a Module AST node. Only handles external module declarations,
@@ -296,7 +392,9 @@ fn parse_mod(item: syn::ItemMod) -> Result<Module> {
 
 /// Parse a use statement
 ///
-/// Converts `use path::Type;` or `use path::Type as Alias;` into a UseStatement AST node.
+/// Converts `use path::Type;`, `use path::Type as Alias;` or `use path::*;`
+/// into a UseStatement AST node. `pub use` re-exports the item; any other
+/// visibility keeps the import private to the module.
 ///
 /// # Arguments
 ///
@@ -305,84 +403,86 @@ fn parse_mod(item: syn::ItemMod) -> Result<Module> {
 /// # Returns
 ///
 /// * `Ok(UseStatement)` - Successfully parsed use statement
-/// * `Err(LumosError)` - Unsupported use syntax (glob imports, nested groups)
+/// * `Err(LumosError)` - Unsupported use syntax (nested groups)
 fn parse_use(item: syn::ItemUse) -> Result<UseStatement> {
-    // Parse the use tree (supports simple paths and rename)
-    let (path, alias) = parse_use_tree(&item.tree)?;
+    // Parse the use tree (supports simple paths, rename and glob)
+    let tree = parse_use_tree(&item.tree)?;
+    let visibility = match item.vis {
+        syn::Visibility::Public(_) => Visibility::Public,
+        _ => Visibility::Private,
+    };
 
     Ok(UseStatement {
-        path,
-        alias,
-        span: None,
+        path: tree.path,
+        alias: tree.alias,
+        visibility,
+        glob: tree.glob,
+        span: Some(tree.span),
     })
 }
 
-/// Parse a use tree into a module path and optional alias
+/// A parsed use tree
+struct ParsedUseTree {
+    path: ModulePath,
+    alias: Option<String>,
+    glob: bool,
+    /// Span of the imported name, or of `*`
+    span: proc_macro2::Span,
+}
+
+/// Parse a use tree into a module path, optional alias and glob flag
 ///
 /// Supports:
 /// - `use crate::models::User;` → (crate::models::User, None)
 /// - `use super::User;` → (super::User, None)
 /// - `use models::User as UserModel;` → (models::User, Some("UserModel"))
+/// - `use crate::models::*;` → (crate::models, None), glob
 ///
 /// Does NOT support:
-/// - Glob imports: `use models::*;`
 /// - Nested groups: `use models::{User, Post};`
-fn parse_use_tree(tree: &UseTree) -> Result<(ModulePath, Option<String>)> {
+fn parse_use_tree(tree: &UseTree) -> Result<ParsedUseTree> {
+    let segment = |ident: &syn::Ident| match ident.to_string().as_str() {
+        "crate" => PathSegment::Crate,
+        "super" => PathSegment::Super,
+        "self" => PathSegment::SelfPath,
+        other => PathSegment::Ident(other.to_string()),
+    };
+
     match tree {
         UseTree::Path(use_path) => {
             // Recursively parse path segments
-            let ident = use_path.ident.to_string();
-            let segment = match ident.as_str() {
-                "crate" => PathSegment::Crate,
-                "super" => PathSegment::Super,
-                "self" => PathSegment::SelfPath,
-                _ => PathSegment::Ident(ident),
-            };
-
-            let (rest_path, alias) = parse_use_tree(&use_path.tree)?;
-            let mut segments = vec![segment];
-            segments.extend(rest_path.segments);
-
-            Ok((ModulePath { segments }, alias))
+            let mut rest = parse_use_tree(&use_path.tree)?;
+            rest.path.segments.insert(0, segment(&use_path.ident));
+            Ok(rest)
         }
         UseTree::Name(use_name) => {
             // Final identifier in the path
-            let ident = use_name.ident.to_string();
-            let segment = match ident.as_str() {
-                "crate" => PathSegment::Crate,
-                "super" => PathSegment::Super,
-                "self" => PathSegment::SelfPath,
-                _ => PathSegment::Ident(ident),
-            };
-
-            Ok((
-                ModulePath {
-                    segments: vec![segment],
+            Ok(ParsedUseTree {
+                path: ModulePath {
+                    segments: vec![segment(&use_name.ident)],
                 },
-                None,
-            ))
+                alias: None,
+                glob: false,
+                span: use_name.ident.span(),
+            })
         }
         UseTree::Rename(use_rename) => {
             // Type with alias (e.g., `User as UserModel`)
-            let ident = use_rename.ident.to_string();
-            let segment = match ident.as_str() {
-                "crate" => PathSegment::Crate,
-                "super" => PathSegment::Super,
-                "self" => PathSegment::SelfPath,
-                _ => PathSegment::Ident(ident),
-            };
-
-            Ok((
-                ModulePath {
-                    segments: vec![segment],
+            Ok(ParsedUseTree {
+                path: ModulePath {
+                    segments: vec![segment(&use_rename.ident)],
                 },
-                Some(use_rename.rename.to_string()),
-            ))
+                alias: Some(use_rename.rename.to_string()),
+                glob: false,
+                span: use_rename.ident.span(),
+            })
         }
-        UseTree::Glob(_) => Err(LumosError::SchemaParse(
-            "Glob imports (use path::*) are not supported. Import types explicitly.".to_string(),
-            None,
-        )),
+        UseTree::Glob(use_glob) => Ok(ParsedUseTree {
+            path: ModulePath { segments: vec![] },
+            alias: None,
+            glob: true,
+            span: use_glob.star_token.spans[0],
+        }),
         UseTree::Group(_) => Err(LumosError::SchemaParse(
             "Grouped imports (use path::{A, B}) are not yet supported. Use separate import statements.".to_string(),
             None,
@@ -444,45 +544,55 @@ fn parse_use_tree(tree: &UseTree) -> Result<(ModulePath, Option<String>)> {
 /// - Syntax is invalid (not valid Rust-style code)
 /// - Invalid import syntax
 /// - Unsupported type syntax encountered
+///
+/// Once the file is syntactically valid, every item is parsed even if an
+/// earlier one fails, and all problems are returned together as
+/// [`LumosError::Multiple`], each with the location it refers to.
 pub fn parse_lumos_file(input: &str) -> Result<LumosFile> {
     let mut items = Vec::new();
+    let mut errors = ErrorCollector::new();
 
     // Extract imports first (JavaScript-style imports are not valid Rust syntax)
     let (imports, remaining_input) = extract_imports(input)?;
 
     // Parse the remaining file as Rust code using syn
     let file = syn::parse_file(&remaining_input).map_err(|e| {
-        LumosError::SchemaParse(format!("Failed to parse .lumos file: {}", e), None)
+        LumosError::SchemaParse(
+            format!("Failed to parse .lumos file: {}", e),
+            Some(SourceLocation::from_span(e.span())),
+        )
     })?;
 
-    // Extract struct, enum, type alias, module, and use definitions
+    // File-level inner attributes such as `#![rename_fields(..)]`
+    let attributes = errors.ok(parse_attributes(&file.attrs)).unwrap_or_default();
+
+    // Extract struct, enum, type alias, constant, module, and use definitions
     for item in file.items {
-        match item {
-            Item::Struct(item_struct) => {
-                let struct_def = parse_struct(item_struct)?;
-                items.push(AstItem::Struct(struct_def));
-            }
-            Item::Enum(item_enum) => {
-                let enum_def = parse_enum(item_enum)?;
-                items.push(AstItem::Enum(enum_def));
-            }
-            Item::Type(item_type) => {
-                let type_alias = parse_type_alias(item_type)?;
-                items.push(AstItem::TypeAlias(type_alias));
-            }
-            Item::Mod(item_mod) => {
-                let module = parse_mod(item_mod)?;
-                items.push(AstItem::Module(module));
-            }
-            Item::Use(item_use) => {
-                let use_stmt = parse_use(item_use)?;
-                items.push(AstItem::Use(use_stmt));
-            }
+        let span = match &item {
+            Item::Struct(item) => item.ident.span(),
+            Item::Enum(item) => item.ident.span(),
+            Item::Type(item) => item.ident.span(),
+            Item::Const(item) => item.ident.span(),
+            Item::Mod(item) => item.ident.span(),
+            other => other.span(),
+        };
+        let parsed = match item {
+            Item::Struct(item_struct) => parse_struct(item_struct).map(AstItem::Struct),
+            Item::Enum(item_enum) => parse_enum(item_enum).map(AstItem::Enum),
+            Item::Type(item_type) => parse_type_alias(item_type).map(AstItem::TypeAlias),
+            Item::Const(item_const) => parse_const(item_const).map(AstItem::Const),
+            Item::Mod(item_mod) => parse_mod(item_mod).map(AstItem::Module),
+            Item::Use(item_use) => parse_use(item_use).map(AstItem::Use),
             _ => {
                 // Ignore other items (functions, impls, etc.)
+                continue;
             }
+        };
+        if let Some(item) = errors.ok(parsed.map_err(|e| e.with_span(Some(span)))) {
+            items.push(item);
         }
     }
+    errors.finish(())?;
 
     if items.is_empty() && imports.is_empty() {
         return Err(LumosError::SchemaParse(
@@ -491,7 +601,11 @@ pub fn parse_lumos_file(input: &str) -> Result<LumosFile> {
         ));
     }
 
-    Ok(LumosFile { imports, items })
+    Ok(LumosFile {
+        imports,
+        items,
+        attributes,
+    })
 }
 
 /// Parse a struct definition
@@ -500,39 +614,52 @@ fn parse_struct(item: syn::ItemStruct) -> Result<StructDef> {
     let span = Some(item.ident.span());
     let visibility = parse_visibility(&item.vis);
 
+    let mut errors = ErrorCollector::new();
+
     // Extract generic type parameters
-    let type_params = parse_generic_params(&item.generics)?;
+    let type_params = errors
+        .ok(parse_generic_params(&item.generics))
+        .unwrap_or_default();
 
-    // Extract attributes
-    let attributes = parse_attributes(&item.attrs)?;
+    // Extract attributes and doc comments
+    let attributes = errors.ok(parse_attributes(&item.attrs)).unwrap_or_default();
+    let docs = parse_doc_comments(&item.attrs);
 
     // Extract version from attributes
-    let version = extract_version_attribute(&attributes)?.map(|v| v.to_string());
+    let version = errors
+        .ok(extract_version_attribute(&attributes))
+        .flatten()
+        .map(|v| v.to_string());
 
-    // Extract fields
+    // Extract handler arguments from #[instruction(...)]
+    let instruction_args = errors
+        .ok(parse_instruction_args(&item.attrs, &type_params))
+        .unwrap_or_default();
+
+    // Extract fields, reporting every bad field rather than just the first
     let fields = match item.fields {
-        syn::Fields::Named(fields_named) => {
-            let mut field_defs = Vec::new();
-            for field in fields_named.named {
-                let field_def = parse_field(field, &type_params)?;
-                field_defs.push(field_def);
-            }
-            field_defs
-        }
+        syn::Fields::Named(fields_named) => fields_named
+            .named
+            .into_iter()
+            .filter_map(|field| errors.ok(parse_field(field, &type_params)))
+            .collect(),
         _ => {
-            return Err(LumosError::SchemaParse(
+            errors.push(LumosError::SchemaParse(
                 format!("Struct '{}' must have named fields", name),
                 None,
-            ))
+            ));
+            Vec::new()
         }
     };
 
-    Ok(StructDef {
+    errors.finish(StructDef {
         name,
         visibility,
         type_params,
         attributes,
+        docs,
         fields,
+        instruction_args,
         version,
         span,
     })
@@ -544,34 +671,47 @@ fn parse_enum(item: syn::ItemEnum) -> Result<EnumDef> {
     let span = Some(item.ident.span());
     let visibility = parse_visibility(&item.vis);
 
+    let mut errors = ErrorCollector::new();
+
     // Extract generic type parameters
-    let type_params = parse_generic_params(&item.generics)?;
+    let type_params = errors
+        .ok(parse_generic_params(&item.generics))
+        .unwrap_or_default();
 
-    // Extract attributes
-    let attributes = parse_attributes(&item.attrs)?;
+    // Extract attributes and doc comments
+    let attributes = errors.ok(parse_attributes(&item.attrs)).unwrap_or_default();
+    let docs = parse_doc_comments(&item.attrs);
 
     // Extract version from attributes
-    let version = extract_version_attribute(&attributes)?.map(|v| v.to_string());
-
-    // Extract variants
-    let mut variants = Vec::new();
-    for variant in item.variants {
-        let variant_def = parse_enum_variant(variant, &type_params)?;
-        variants.push(variant_def);
-    }
+    let version = errors
+        .ok(extract_version_attribute(&attributes))
+        .flatten()
+        .map(|v| v.to_string());
 
-    if variants.is_empty() {
-        return Err(LumosError::SchemaParse(
+    if item.variants.is_empty() {
+        errors.push(LumosError::SchemaParse(
             format!("Enum '{}' must have at least one variant", name),
             None,
         ));
     }
 
-    Ok(EnumDef {
+    // Extract variants, reporting every bad variant rather than just the first
+    let variants = item
+        .variants
+        .into_iter()
+        .filter_map(|variant| {
+            let span = variant.ident.span();
+            errors
+                .ok(parse_enum_variant(variant, &type_params).map_err(|e| e.with_span(Some(span))))
+        })
+        .collect();
+
+    errors.finish(EnumDef {
         name,
         visibility,
         type_params,
         attributes,
+        docs,
         variants,
         version,
         span,
@@ -582,33 +722,156 @@ fn parse_enum(item: syn::ItemEnum) -> Result<EnumDef> {
 fn parse_enum_variant(variant: syn::Variant, generic_params: &[String]) -> Result<EnumVariant> {
     let name = variant.ident.to_string();
     let span = Some(variant.ident.span());
+    let docs = parse_doc_comments(&variant.attrs);
+    let (msg, discriminant) = join(
+        parse_variant_message(&variant.attrs),
+        parse_variant_discriminant(&variant),
+    )?;
+
+    if msg.is_some() && !matches!(variant.fields, syn::Fields::Unit) {
+        return Err(LumosError::SchemaParse(
+            format!(
+                "#[msg] is only supported on unit variants, found it on '{}'",
+                name
+            ),
+            None,
+        ));
+    }
 
     match variant.fields {
         // Unit variant: `Active`
-        syn::Fields::Unit => Ok(EnumVariant::Unit { name, span }),
+        syn::Fields::Unit => Ok(EnumVariant::Unit {
+            name,
+            docs,
+            msg,
+            span,
+            discriminant,
+        }),
 
         // Tuple variant: `PlayerJoined(PublicKey, u64)`
         syn::Fields::Unnamed(fields_unnamed) => {
-            let mut types = Vec::new();
-            for field in fields_unnamed.unnamed {
-                let (type_spec, _optional) = parse_type_with_generics(&field.ty, generic_params)?;
-                types.push(type_spec);
-            }
-            Ok(EnumVariant::Tuple { name, types, span })
+            let mut errors = ErrorCollector::new();
+            let types = fields_unnamed
+                .unnamed
+                .iter()
+                .filter_map(|field| {
+                    errors.ok(parse_type_with_generics(&field.ty, generic_params)
+                        .map(|(type_spec, _optional)| type_spec)
+                        .map_err(|e| e.with_span(Some(field.ty.span()))))
+                })
+                .collect();
+            errors.finish(EnumVariant::Tuple {
+                name,
+                types,
+                docs,
+                span,
+                discriminant,
+            })
         }
 
         // Struct variant: `Initialize { authority: PublicKey }`
         syn::Fields::Named(fields_named) => {
-            let mut fields = Vec::new();
-            for field in fields_named.named {
-                let field_def = parse_field(field, generic_params)?;
-                fields.push(field_def);
-            }
-            Ok(EnumVariant::Struct { name, fields, span })
+            let mut errors = ErrorCollector::new();
+            let fields = fields_named
+                .named
+                .into_iter()
+                .filter_map(|field| errors.ok(parse_field(field, generic_params)))
+                .collect();
+            errors.finish(EnumVariant::Struct {
+                name,
+                fields,
+                docs,
+                span,
+                discriminant,
+            })
         }
     }
 }
 
+/// Parse handler arguments from `#[instruction(amount: u64, memo: String)]`
+///
+/// Arguments use the same syntax as struct fields, so they may carry
+/// attributes such as `#[max(32)]`. A bare `#[instruction]` has no arguments.
+fn parse_instruction_args(
+    attrs: &[syn::Attribute],
+    generic_params: &[String],
+) -> Result<Vec<FieldDef>> {
+    let Some(attr) = attrs.iter().find(|a| a.path().is_ident("instruction")) else {
+        return Ok(Vec::new());
+    };
+    if !matches!(attr.meta, Meta::List(_)) {
+        return Ok(Vec::new());
+    }
+
+    let fields = attr
+        .parse_args_with(|input: syn::parse::ParseStream| {
+            syn::punctuated::Punctuated::<syn::Field, syn::Token![,]>::parse_terminated_with(
+                input,
+                syn::Field::parse_named,
+            )
+        })
+        .map_err(|e| {
+            LumosError::SchemaParse(
+                format!(
+                    "Invalid #[instruction(...)] arguments, expected `name: Type, ...`: {}",
+                    e
+                ),
+                None,
+            )
+        })?;
+
+    let mut errors = ErrorCollector::new();
+    let args = fields
+        .into_iter()
+        .filter_map(|field| errors.ok(parse_field(field, generic_params)))
+        .collect();
+    errors.finish(args)
+}
+
+/// Parse an explicit variant discriminant (`Active = 1`)
+///
+/// Only non-negative integer literals are accepted; the tag width is chosen
+/// separately with `#[repr(u8 | u16 | u32)]` on the enum.
+fn parse_variant_discriminant(variant: &syn::Variant) -> Result<Option<u64>> {
+    let Some((_, expr)) = &variant.discriminant else {
+        return Ok(None);
+    };
+
+    let invalid = || {
+        LumosError::SchemaParse(
+            format!(
+                "Invalid discriminant for variant '{}': expected a non-negative integer literal",
+                variant.ident
+            ),
+            None,
+        )
+    };
+
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit.base10_parse::<u64>().map(Some).map_err(|_| invalid()),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parse the `#[msg("...")]` error message attached to an enum variant
+fn parse_variant_message(attrs: &[syn::Attribute]) -> Result<Option<String>> {
+    let Some(attr) = attrs.iter().find(|a| a.path().is_ident("msg")) else {
+        return Ok(None);
+    };
+
+    attr.parse_args::<syn::LitStr>()
+        .map(|lit| Some(lit.value()))
+        .map_err(|_| {
+            LumosError::SchemaParse(
+                "#[msg] expects a string literal, e.g. #[msg(\"Insufficient funds\")]".to_string(),
+                None,
+            )
+        })
+}
+
 /// Parse a field definition
 fn parse_field(field: syn::Field, generic_params: &[String]) -> Result<FieldDef> {
     let name = field
@@ -619,99 +882,172 @@ fn parse_field(field: syn::Field, generic_params: &[String]) -> Result<FieldDef>
 
     let span = field.ident.as_ref().map(|i| i.span());
 
-    // Extract field attributes
-    let attributes = parse_attributes(&field.attrs)?;
-
-    // Parse field type (with generic context)
-    let (type_spec, optional) = parse_type_with_generics(&field.ty, generic_params)?;
+    // Extract field attributes and parse the field type (with generic
+    // context), reporting problems in both
+    let ((type_spec, optional), attributes) = join(
+        parse_type_with_generics(&field.ty, generic_params)
+            .map_err(|e| e.with_span(Some(field.ty.span()))),
+        parse_attributes(&field.attrs),
+    )
+    .map_err(|e| e.with_span(span))?;
+    let docs = parse_doc_comments(&field.attrs);
 
     Ok(FieldDef {
         name,
         type_spec,
         optional,
         attributes,
+        docs,
         span,
     })
 }
 
 /// Parse attributes (e.g., #[solana], #[account], #[key], #[max(100)])
+///
+/// Every malformed attribute is reported, each at its own location.
 fn parse_attributes(attrs: &[syn::Attribute]) -> Result<Vec<Attribute>> {
-    let mut attributes = Vec::new();
+    let mut errors = ErrorCollector::new();
+    let attributes = attrs
+        .iter()
+        .filter_map(|attr| {
+            errors
+                .ok(parse_attribute(attr).map_err(|e| e.with_span(Some(attr.span()))))
+                .flatten()
+        })
+        .collect();
+    errors.finish(attributes)
+}
 
-    for attr in attrs {
-        // Parse meta (attribute content)
-        let meta = &attr.meta;
-
-        match meta {
-            // Simple path attribute: #[solana]
-            Meta::Path(path) => {
-                if let Some(ident) = path.get_ident() {
-                    attributes.push(Attribute {
-                        name: ident.to_string(),
-                        value: None,
-                        span: Some(ident.span()),
-                    });
-                }
-            }
+/// Parse a single attribute, returning `None` for ones LUMOS doesn't track
+fn parse_attribute(attr: &syn::Attribute) -> Result<Option<Attribute>> {
+    // Parse meta (attribute content)
+    let meta = &attr.meta;
+
+    match meta {
+        // Simple path attribute: #[solana]
+        Meta::Path(path) => Ok(path.get_ident().map(|ident| Attribute {
+            name: ident.to_string(),
+            value: None,
+            span: Some(ident.span()),
+        })),
+
+        // List attribute: #[max(100)] or #[derive(Debug, Clone)]
+        Meta::List(meta_list) => {
+            let ident = meta_list
+                .path
+                .get_ident()
+                .ok_or_else(|| LumosError::SchemaParse("Invalid attribute".to_string(), None))?;
+            let name = ident.to_string();
+
+            // Special handling for #[derive(...)] - contains comma-separated list of macros
+            let value = if name == "derive" {
+                parse_derive_list(&meta_list.tokens.to_string())?
+            } else if name == "range" {
+                parse_range(meta_list)?
+            } else if name == "pattern" {
+                parse_pattern(meta_list)?
+            } else if name == "default" {
+                parse_default(meta_list)?
+            } else if matches!(name.as_str(), "rename" | "rename_fields" | "rename_types") {
+                parse_names(&name, meta_list)?
+            } else {
+                // Parse the value inside parentheses for other list attributes
+                parse_attribute_value(&meta_list.tokens.to_string())?
+            };
 
-            // List attribute: #[max(100)] or #[derive(Debug, Clone)]
-            Meta::List(meta_list) => {
-                let ident = meta_list.path.get_ident().ok_or_else(|| {
-                    LumosError::SchemaParse("Invalid attribute".to_string(), None)
-                })?;
-                let name = ident.to_string();
+            Ok(Some(Attribute {
+                name,
+                value: Some(value),
+                span: Some(ident.span()),
+            }))
+        }
 
-                // Special handling for #[derive(...)] - contains comma-separated list of macros
-                let value = if name == "derive" {
-                    parse_derive_list(&meta_list.tokens.to_string())?
-                } else {
-                    // Parse the value inside parentheses for other list attributes
-                    parse_attribute_value(&meta_list.tokens.to_string())?
-                };
+        // Name-value attribute: #[version = "1.0.0"]
+        Meta::NameValue(meta_name_value) => {
+            let ident = meta_name_value
+                .path
+                .get_ident()
+                .ok_or_else(|| LumosError::SchemaParse("Invalid attribute".to_string(), None))?;
+            let name = ident.to_string();
 
-                attributes.push(Attribute {
-                    name,
-                    value: Some(value),
-                    span: Some(ident.span()),
-                });
+            // Doc comments (`/// ...`) are collected separately by parse_doc_comments
+            if name == "doc" {
+                return Ok(None);
             }
 
-            // Name-value attribute: #[version = "1.0.0"]
-            Meta::NameValue(meta_name_value) => {
-                let ident = meta_name_value.path.get_ident().ok_or_else(|| {
-                    LumosError::SchemaParse("Invalid attribute".to_string(), None)
-                })?;
-                let name = ident.to_string();
-
-                // Extract the value (e.g., "1.0.0" from #[version = "1.0.0"])
-                let value_str = match &meta_name_value.value {
-                    syn::Expr::Lit(expr_lit) => match &expr_lit.lit {
-                        syn::Lit::Str(lit_str) => lit_str.value(),
-                        _ => {
-                            return Err(LumosError::SchemaParse(
-                                format!("Attribute '{}' must have a string value", name),
-                                None,
-                            ))
-                        }
-                    },
+            // Extract the value (e.g., "1.0.0" from #[version = "1.0.0"])
+            let value_str = match &meta_name_value.value {
+                syn::Expr::Lit(expr_lit) => match &expr_lit.lit {
+                    syn::Lit::Str(lit_str) => lit_str.value(),
                     _ => {
                         return Err(LumosError::SchemaParse(
-                            format!("Attribute '{}' must have a literal value", name),
+                            format!("Attribute '{}' must have a string value", name),
                             None,
                         ))
                     }
-                };
+                },
+                _ => {
+                    return Err(LumosError::SchemaParse(
+                        format!("Attribute '{}' must have a literal value", name),
+                        None,
+                    ))
+                }
+            };
+
+            Ok(Some(Attribute {
+                name,
+                value: Some(AttributeValue::String(value_str)),
+                span: Some(ident.span()),
+            }))
+        }
+    }
+}
 
-                attributes.push(Attribute {
-                    name,
-                    value: Some(AttributeValue::String(value_str)),
-                    span: Some(ident.span()),
-                });
+/// Collect doc comment lines from `///` and `/** */` comments
+///
+/// Doc comments reach syn as `#[doc = "..."]` attributes, one per line. The single
+/// leading space that follows `///` is stripped; blank lines are kept so that
+/// paragraph breaks survive into the generated code.
+fn parse_doc_comments(attrs: &[syn::Attribute]) -> Vec<String> {
+    let mut docs = Vec::new();
+
+    for attr in attrs {
+        if let Meta::NameValue(meta_name_value) = &attr.meta {
+            if !meta_name_value.path.is_ident("doc") {
+                continue;
+            }
+
+            if let syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit_str),
+                ..
+            }) = &meta_name_value.value
+            {
+                let value = lit_str.value();
+                // Block comments carry all their lines in one attribute
+                let is_block = value.contains('\n');
+                for line in value.split('\n') {
+                    let line = if is_block {
+                        let line = line.trim_start();
+                        line.strip_prefix('*').unwrap_or(line)
+                    } else {
+                        line
+                    };
+                    let line = line.strip_prefix(' ').unwrap_or(line);
+                    docs.push(line.trim_end().to_string());
+                }
             }
         }
     }
 
-    Ok(attributes)
+    // Drop leading/trailing blank lines (common with `/** ... */` blocks)
+    while docs.first().is_some_and(|l| l.trim().is_empty()) {
+        docs.remove(0);
+    }
+    while docs.last().is_some_and(|l| l.trim().is_empty()) {
+        docs.pop();
+    }
+
+    docs
 }
 
 /// Parse attribute value from token stream
@@ -741,6 +1077,169 @@ fn parse_attribute_value(tokens: &str) -> Result<AttributeValue> {
     Ok(AttributeValue::String(tokens_trimmed.to_string()))
 }
 
+/// Parse `#[range(..)]`: `a..=b`, `a..b`, `a..`, `..=b` or `..b`
+///
+/// Bounds are integer literals (optionally negative) or constant names.
+fn parse_range(meta_list: &syn::MetaList) -> Result<AttributeValue> {
+    let invalid = || {
+        LumosError::SchemaParse(
+            format!(
+                "Invalid #[range({})]: expected a range such as 0..=100",
+                meta_list.tokens
+            ),
+            None,
+        )
+    };
+
+    let range: syn::ExprRange = meta_list.parse_args().map_err(|_| invalid())?;
+    let start = range.start.as_deref().map(parse_range_bound).transpose()?;
+    let end = range.end.as_deref().map(parse_range_bound).transpose()?;
+    let inclusive = matches!(range.limits, syn::RangeLimits::Closed(_));
+    if start.is_none() && end.is_none() {
+        return Err(invalid());
+    }
+
+    Ok(AttributeValue::Range(RangeExpr {
+        start,
+        end,
+        inclusive,
+    }))
+}
+
+/// Parse one bound of a `#[range(..)]`
+fn parse_range_bound(expr: &syn::Expr) -> Result<RangeBound> {
+    let invalid = || {
+        LumosError::SchemaParse(
+            format!(
+                "Invalid range bound '{}': expected an integer or a constant name",
+                quote::quote!(#expr)
+            ),
+            None,
+        )
+    };
+
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(lit),
+            ..
+        }) => lit
+            .base10_parse::<i128>()
+            .map(RangeBound::Literal)
+            .map_err(|_| invalid()),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => match parse_range_bound(expr)? {
+            RangeBound::Literal(value) => Ok(RangeBound::Literal(-value)),
+            RangeBound::Named(_) => Err(invalid()),
+        },
+        syn::Expr::Path(path) => path
+            .path
+            .get_ident()
+            .map(|ident| RangeBound::Named(ident.to_string()))
+            .ok_or_else(invalid),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parse `#[pattern("..")]`, unescaping the string literal
+fn parse_pattern(meta_list: &syn::MetaList) -> Result<AttributeValue> {
+    let pattern: syn::LitStr = meta_list.parse_args().map_err(|_| {
+        LumosError::SchemaParse(
+            format!(
+                "Invalid #[pattern({})]: expected a string literal",
+                meta_list.tokens
+            ),
+            None,
+        )
+    })?;
+
+    Ok(AttributeValue::String(pattern.value()))
+}
+
+/// Parse `key = "value"` pairs, as in `#[rename(ts = "feeBps", py = "fee")]`
+fn parse_names(name: &str, meta_list: &syn::MetaList) -> Result<AttributeValue> {
+    let invalid = || {
+        LumosError::SchemaParse(
+            format!(
+                "Invalid #[{}({})]: expected key = \"value\" pairs such as ts = \"feeBps\"",
+                name, meta_list.tokens
+            ),
+            None,
+        )
+    };
+
+    let pairs = meta_list
+        .parse_args_with(
+            syn::punctuated::Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated,
+        )
+        .map_err(|_| invalid())?;
+
+    pairs
+        .into_iter()
+        .map(|pair| {
+            let key = pair.path.get_ident().ok_or_else(invalid)?.to_string();
+            match pair.value {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }) => Ok((key, value.value())),
+                _ => Err(invalid()),
+            }
+        })
+        .collect::<Result<Vec<_>>>()
+        .map(AttributeValue::Names)
+}
+
+/// Parse `#[default(..)]`: an integer, float, boolean or string literal, or a constant name
+fn parse_default(meta_list: &syn::MetaList) -> Result<AttributeValue> {
+    let tokens = &meta_list.tokens;
+    let invalid = || {
+        LumosError::SchemaParse(
+            format!(
+                "Invalid #[default({})]: expected a number, bool, string or constant name",
+                tokens
+            ),
+            None,
+        )
+    };
+
+    let expr: syn::Expr = meta_list.parse_args().map_err(|_| invalid())?;
+    let (negative, expr) = match &expr {
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => (true, expr.as_ref()),
+        other => (false, other),
+    };
+
+    let value = match expr {
+        syn::Expr::Lit(syn::ExprLit { lit, .. }) => match lit {
+            syn::Lit::Int(lit) => lit
+                .base10_parse::<i128>()
+                .map(|n| DefaultExpr::Integer(if negative { -n } else { n }))
+                .map_err(|_| invalid())?,
+            syn::Lit::Float(lit) => lit
+                .base10_parse::<f64>()
+                .map(|n| DefaultExpr::Float(if negative { -n } else { n }))
+                .map_err(|_| invalid())?,
+            syn::Lit::Bool(lit) if !negative => DefaultExpr::Bool(lit.value),
+            syn::Lit::Str(lit) if !negative => DefaultExpr::String(lit.value()),
+            _ => return Err(invalid()),
+        },
+        syn::Expr::Path(path) if !negative => path
+            .path
+            .get_ident()
+            .map(|ident| DefaultExpr::Named(ident.to_string()))
+            .ok_or_else(invalid)?,
+        _ => return Err(invalid()),
+    };
+
+    Ok(AttributeValue::Default(value))
+}
+
 /// Parse derive list from token stream
 ///
 /// Parses comma-separated derive macro names from `#[derive(Debug, Clone, PartialEq)]`.
@@ -780,6 +1279,9 @@ fn parse_derive_list(tokens: &str) -> Result<AttributeValue> {
     Ok(AttributeValue::List(derives))
 }
 
+/// Largest tuple Borsh (and Rust's standard trait impls) support
+const MAX_TUPLE_LEN: usize = 12;
+
 /// Parse a type specification
 fn parse_type(ty: &Type) -> Result<(TypeSpec, bool)> {
     parse_type_with_generics(ty, &[])
@@ -825,11 +1327,110 @@ fn parse_type_with_generics(ty: &Type, generic_params: &[String]) -> Result<(Typ
                 }
             }
 
+            // Check if it's Box<T> (heap indirection for recursive types)
+            if type_name == "Box" {
+                if let Some(segment) = type_path.path.segments.last() {
+                    if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                        if let Some(syn::GenericArgument::Type(inner_ty)) = args.args.first() {
+                            return match parse_type_with_generics(inner_ty, generic_params)? {
+                                (_, true) => Err(LumosError::SchemaParse(
+                                    "Option is not supported inside Box; use Option<Box<T>>"
+                                        .to_string(),
+                                    None,
+                                )),
+                                (inner_type_spec, false) => {
+                                    Ok((TypeSpec::Boxed(Box::new(inner_type_spec)), false))
+                                }
+                            };
+                        }
+                    }
+                }
+            }
+
+            // Check if it's a fixed-capacity string: FixedString<N>
+            if type_name == "FixedString" {
+                return Ok((
+                    TypeSpec::FixedString(parse_fixed_string_size(type_path)?),
+                    false,
+                ));
+            }
+
+            // Check if it's a map: HashMap<K, V> or BTreeMap<K, V>
+            if type_name == "HashMap" || type_name == "BTreeMap" {
+                let args = parse_type_arguments(type_path, generic_params)?;
+                let [key, value]: [TypeSpec; 2] = args.try_into().map_err(|_| {
+                    LumosError::SchemaParse(
+                        format!("{} requires exactly two type arguments", type_name),
+                        None,
+                    )
+                })?;
+                return Ok((
+                    TypeSpec::Map {
+                        key: Box::new(key),
+                        value: Box::new(value),
+                        ordered: type_name == "BTreeMap",
+                    },
+                    false,
+                ));
+            }
+
+            // Check if it's a set: HashSet<T> or BTreeSet<T>
+            if type_name == "HashSet" || type_name == "BTreeSet" {
+                let args = parse_type_arguments(type_path, generic_params)?;
+                let [element]: [TypeSpec; 1] = args.try_into().map_err(|_| {
+                    LumosError::SchemaParse(
+                        format!("{} requires exactly one type argument", type_name),
+                        None,
+                    )
+                })?;
+                return Ok((
+                    TypeSpec::Set {
+                        element: Box::new(element),
+                        ordered: type_name == "BTreeSet",
+                    },
+                    false,
+                ));
+            }
+
             // Check if it's a generic parameter
             if generic_params.contains(&type_name) {
                 return Ok((TypeSpec::Generic(type_name), false));
             }
 
+            // Generic user-defined type with arguments: Wrapper<u64>
+            if let Some(syn::PathArguments::AngleBracketed(args)) =
+                type_path.path.segments.last().map(|s| &s.arguments)
+            {
+                let mut type_args = Vec::new();
+                for arg in &args.args {
+                    let syn::GenericArgument::Type(inner_ty) = arg else {
+                        return Err(LumosError::SchemaParse(
+                            format!("Only type arguments are supported in '{}<..>'", type_name),
+                            None,
+                        ));
+                    };
+                    match parse_type_with_generics(inner_ty, generic_params)? {
+                        (_, true) => {
+                            return Err(LumosError::SchemaParse(
+                                format!(
+                                    "Option is not supported as a type argument of '{}'",
+                                    type_name
+                                ),
+                                None,
+                            ))
+                        }
+                        (spec, false) => type_args.push(spec),
+                    }
+                }
+                return Ok((
+                    TypeSpec::Instance {
+                        name: type_name,
+                        args: type_args,
+                    },
+                    false,
+                ));
+            }
+
             // Regular type (primitive or user-defined)
             Ok((TypeSpec::Primitive(type_name), false))
         }
@@ -838,12 +1439,10 @@ fn parse_type_with_generics(ty: &Type, generic_params: &[String]) -> Result<(Typ
         Type::Array(type_array) => {
             let (inner_type_spec, _) = parse_type_with_generics(&type_array.elem, generic_params)?;
 
-            // Extract array size from length expression
+            // Extract array size from length expression; its bounds are checked
+            // during transformation, once constant sizes are resolved
             let size = parse_array_size(&type_array.len)?;
 
-            // Validate size constraints
-            validate_array_size(size)?;
-
             Ok((
                 TypeSpec::FixedArray {
                     element: Box::new(inner_type_spec),
@@ -859,6 +1458,41 @@ fn parse_type_with_generics(ty: &Type, generic_params: &[String]) -> Result<(Typ
             Ok((TypeSpec::Array(Box::new(inner_type_spec)), false))
         }
 
+        // Tuple type: (A, B, ...)
+        Type::Tuple(type_tuple) => {
+            if type_tuple.elems.is_empty() {
+                return Err(LumosError::SchemaParse(
+                    "The unit type () is not supported".to_string(),
+                    None,
+                ));
+            }
+            if type_tuple.elems.len() > MAX_TUPLE_LEN {
+                return Err(LumosError::SchemaParse(
+                    format!(
+                        "Tuples support at most {} elements, found {}",
+                        MAX_TUPLE_LEN,
+                        type_tuple.elems.len()
+                    ),
+                    None,
+                ));
+            }
+
+            let elements = type_tuple
+                .elems
+                .iter()
+                .map(
+                    |elem| match parse_type_with_generics(elem, generic_params)? {
+                        (_, true) => Err(LumosError::SchemaParse(
+                            "Option is not supported inside tuples".to_string(),
+                            None,
+                        )),
+                        (spec, false) => Ok(spec),
+                    },
+                )
+                .collect::<Result<Vec<_>>>()?;
+            Ok((TypeSpec::Tuple(elements), false))
+        }
+
         _ => Err(LumosError::SchemaParse(
             format!("Unsupported type: {:?}", ty),
             None,
@@ -866,83 +1500,99 @@ fn parse_type_with_generics(ty: &Type, generic_params: &[String]) -> Result<(Typ
     }
 }
 
-/// Parse array size from expression (must be literal integer)
+/// Parse the angle-bracketed type arguments of a path type (e.g., `K, V` in `BTreeMap<K, V>`)
+fn parse_type_arguments(
+    type_path: &syn::TypePath,
+    generic_params: &[String],
+) -> Result<Vec<TypeSpec>> {
+    let mut types = Vec::new();
+
+    if let Some(segment) = type_path.path.segments.last() {
+        if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+            for arg in &args.args {
+                if let syn::GenericArgument::Type(inner_ty) = arg {
+                    let (inner_type_spec, _) = parse_type_with_generics(inner_ty, generic_params)?;
+                    types.push(inner_type_spec);
+                }
+            }
+        }
+    }
+
+    Ok(types)
+}
+
+/// Parse the capacity of `FixedString<N>`, a literal byte count or a constant name
+fn parse_fixed_string_size(type_path: &syn::TypePath) -> Result<ConstExpr> {
+    let args = match type_path.path.segments.last().map(|s| &s.arguments) {
+        Some(syn::PathArguments::AngleBracketed(args)) if args.args.len() == 1 => &args.args,
+        _ => {
+            return Err(LumosError::SchemaParse(
+                "FixedString requires a capacity in bytes, e.g. FixedString<32>".to_string(),
+                None,
+            ))
+        }
+    };
+
+    let size = match &args[0] {
+        syn::GenericArgument::Const(expr) => parse_array_size(expr)?,
+        // A constant name parses as a type: FixedString<MAX_NAME>
+        syn::GenericArgument::Type(Type::Path(path)) if path.path.get_ident().is_some() => {
+            ConstExpr::Named(path.path.segments[0].ident.to_string())
+        }
+        _ => {
+            return Err(LumosError::SchemaParse(
+                "FixedString capacity must be an integer literal or a constant name".to_string(),
+                None,
+            ))
+        }
+    };
+    Ok(size)
+}
+
+/// Parse array size from expression (literal integer or constant name)
 ///
 /// # Arguments
 ///
-/// * `expr` - Expression representing array size (e.g., `32` in `[u8; 32]`)
+/// * `expr` - Expression representing array size (e.g., `32` or `MAX_LEN` in `[u8; 32]`)
 ///
 /// # Returns
 ///
-/// * `Ok(usize)` - Successfully parsed array size
-/// * `Err(LumosError)` - Non-literal or invalid size expression
+/// * `Ok(ConstExpr)` - Successfully parsed array size
+/// * `Err(LumosError)` - Unsupported or invalid size expression
 ///
 /// # Examples
 ///
 /// ```ignore
 /// // Valid: [u8; 32]
 /// let size = parse_array_size(&lit_expr)?;
-/// assert_eq!(size, 32);
+/// assert_eq!(size, ConstExpr::Literal(32));
 /// ```
-fn parse_array_size(expr: &syn::Expr) -> Result<usize> {
+fn parse_array_size(expr: &syn::Expr) -> Result<ConstExpr> {
     match expr {
         syn::Expr::Lit(expr_lit) => match &expr_lit.lit {
-            syn::Lit::Int(lit_int) => lit_int.base10_parse::<usize>().map_err(|e| {
-                LumosError::SchemaParse(
-                    format!("Invalid array size (must be valid usize): {}", e),
-                    None,
-                )
-            }),
+            syn::Lit::Int(lit_int) => lit_int
+                .base10_parse::<u128>()
+                .map(ConstExpr::Literal)
+                .map_err(|e| LumosError::SchemaParse(format!("Invalid array size: {}", e), None)),
             _ => Err(LumosError::SchemaParse(
                 "Array size must be an integer literal".to_string(),
                 None,
             )),
         },
+        syn::Expr::Path(expr_path) => match expr_path.path.get_ident() {
+            Some(ident) => Ok(ConstExpr::Named(ident.to_string())),
+            None => Err(LumosError::SchemaParse(
+                "Constant references must be a single identifier".to_string(),
+                None,
+            )),
+        },
         _ => Err(LumosError::SchemaParse(
-            "Array size must be a literal integer (const generics not yet supported)".to_string(),
+            "Array size must be an integer literal or a constant name".to_string(),
             None,
         )),
     }
 }
 
-/// Validate array size constraints
-///
-/// Ensures array size is within reasonable bounds for Solana programs.
-///
-/// # Constraints
-///
-/// * Size must be > 0 (zero-sized arrays are invalid)
-/// * Size must be ≤ 1024 (practical limit for most use cases)
-///
-/// # Arguments
-///
-/// * `size` - Array size to validate
-///
-/// # Returns
-///
-/// * `Ok(())` - Size is valid
-/// * `Err(LumosError)` - Size is out of bounds
-fn validate_array_size(size: usize) -> Result<()> {
-    if size == 0 {
-        return Err(LumosError::SchemaParse(
-            "Array size must be greater than 0".to_string(),
-            None,
-        ));
-    }
-
-    if size > 1024 {
-        return Err(LumosError::SchemaParse(
-            format!(
-                "Array size {} exceeds maximum of 1024 elements (consider using Vec for dynamic arrays)",
-                size
-            ),
-            None,
-        ));
-    }
-
-    Ok(())
-}
-
 /// Extract and validate version attribute from a list of attributes
 ///
 /// Searches for `#[version = "X.Y.Z"]` attribute and validates it as semantic version.
@@ -1002,113 +1652,504 @@ pub fn extract_version_attribute(attributes: &[Attribute]) -> Result<Option<semv
                 None,
             )),
         }
-    } else {
-        // No version attribute found
-        Ok(None)
+    } else {
+        // No version attribute found
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_struct() {
+        let input = r#"
+            struct User {
+                id: u64,
+                name: String,
+            }
+        "#;
+
+        let result = parse_lumos_file(input);
+        assert!(result.is_ok());
+
+        let file = result.unwrap();
+        assert_eq!(file.items.len(), 1);
+
+        match &file.items[0] {
+            AstItem::Struct(struct_def) => {
+                assert_eq!(struct_def.name, "User");
+                assert_eq!(struct_def.fields.len(), 2);
+                assert_eq!(struct_def.fields[0].name, "id");
+                assert_eq!(struct_def.fields[1].name, "name");
+            }
+            _ => panic!("Expected struct item"),
+        }
+    }
+
+    #[test]
+    fn test_parse_with_attributes() {
+        let input = r#"
+            #[solana]
+            #[account]
+            struct UserAccount {
+                #[key]
+                wallet: PublicKey,
+
+                #[max(32)]
+                username: String,
+            }
+        "#;
+
+        let result = parse_lumos_file(input);
+        assert!(result.is_ok());
+
+        let file = result.unwrap();
+        match &file.items[0] {
+            AstItem::Struct(struct_def) => {
+                assert!(struct_def.has_attribute("solana"));
+                assert!(struct_def.has_attribute("account"));
+                assert_eq!(struct_def.fields[0].name, "wallet");
+                assert!(struct_def.fields[0].has_attribute("key"));
+            }
+            _ => panic!("Expected struct item"),
+        }
+    }
+
+    #[test]
+    fn test_parse_optional_type() {
+        let input = r#"
+            struct User {
+                email: Option<String>,
+            }
+        "#;
+
+        let result = parse_lumos_file(input);
+        assert!(result.is_ok());
+
+        let file = result.unwrap();
+        match &file.items[0] {
+            AstItem::Struct(struct_def) => {
+                let field = &struct_def.fields[0];
+                assert!(field.optional);
+            }
+            _ => panic!("Expected struct item"),
+        }
+    }
+
+    #[test]
+    fn test_parse_array_type() {
+        let input = r#"
+            struct Team {
+                members: [u64],
+            }
+        "#;
+
+        let result = parse_lumos_file(input);
+        assert!(result.is_ok());
+
+        let file = result.unwrap();
+        match &file.items[0] {
+            AstItem::Struct(struct_def) => {
+                let field = &struct_def.fields[0];
+                assert!(field.type_spec.is_array());
+            }
+            _ => panic!("Expected struct item"),
+        }
+    }
+
+    #[test]
+    fn test_parse_map_and_set_types() {
+        let input = r#"
+            struct Registry {
+                balances: HashMap<PublicKey, u64>,
+                scores: BTreeMap<u32, Vec<u8>>,
+                members: HashSet<PublicKey>,
+                tags: BTreeSet<String>,
+            }
+        "#;
+
+        let file = parse_lumos_file(input).unwrap();
+        match &file.items[0] {
+            AstItem::Struct(struct_def) => {
+                match &struct_def.fields[0].type_spec {
+                    TypeSpec::Map {
+                        key,
+                        value,
+                        ordered,
+                    } => {
+                        assert!(!ordered);
+                        assert_eq!(key.as_string(), "PublicKey");
+                        assert_eq!(value.as_string(), "u64");
+                    }
+                    _ => panic!("Expected Map type"),
+                }
+                match &struct_def.fields[1].type_spec {
+                    TypeSpec::Map { value, ordered, .. } => {
+                        assert!(ordered);
+                        assert!(value.is_array());
+                    }
+                    _ => panic!("Expected Map type"),
+                }
+                assert!(matches!(
+                    struct_def.fields[2].type_spec,
+                    TypeSpec::Set { ordered: false, .. }
+                ));
+                assert_eq!(
+                    struct_def.fields[3].type_spec.as_string(),
+                    "BTreeSet<String>"
+                );
+            }
+            _ => panic!("Expected struct item"),
+        }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
 
     #[test]
-    fn test_parse_simple_struct() {
+    fn test_parse_map_with_wrong_arity_fails() {
         let input = r#"
-            struct User {
-                id: u64,
-                name: String,
+            struct Registry {
+                balances: HashMap<PublicKey>,
             }
         "#;
 
         let result = parse_lumos_file(input);
-        assert!(result.is_ok());
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("exactly two type arguments"));
+    }
 
-        let file = result.unwrap();
-        assert_eq!(file.items.len(), 1);
+    #[test]
+    fn test_parse_tuple_types() {
+        let input = r#"
+            struct Pool {
+                pair: (u64, PublicKey),
+                shares: Vec<(PublicKey, u16)>,
+                single: (u8,),
+            }
+        "#;
 
+        let file = parse_lumos_file(input).unwrap();
         match &file.items[0] {
             AstItem::Struct(struct_def) => {
-                assert_eq!(struct_def.name, "User");
-                assert_eq!(struct_def.fields.len(), 2);
-                assert_eq!(struct_def.fields[0].name, "id");
-                assert_eq!(struct_def.fields[1].name, "name");
+                match &struct_def.fields[0].type_spec {
+                    TypeSpec::Tuple(elements) => {
+                        assert_eq!(elements.len(), 2);
+                        assert_eq!(elements[1].as_string(), "PublicKey");
+                    }
+                    _ => panic!("Expected Tuple type"),
+                }
+                assert_eq!(
+                    struct_def.fields[1].type_spec.as_string(),
+                    "[(PublicKey, u16)]"
+                );
+                assert_eq!(struct_def.fields[2].type_spec.as_string(), "(u8,)");
             }
             _ => panic!("Expected struct item"),
         }
     }
 
     #[test]
-    fn test_parse_with_attributes() {
+    fn test_parse_box_types() {
         let input = r#"
-            #[solana]
-            #[account]
-            struct UserAccount {
-                #[key]
-                wallet: PublicKey,
-
-                #[max(32)]
-                username: String,
+            struct Node {
+                next: Option<Box<Node>>,
+                children: Vec<Box<Node>>,
             }
         "#;
 
-        let result = parse_lumos_file(input);
-        assert!(result.is_ok());
-
-        let file = result.unwrap();
+        let file = parse_lumos_file(input).unwrap();
         match &file.items[0] {
             AstItem::Struct(struct_def) => {
-                assert!(struct_def.has_attribute("solana"));
-                assert!(struct_def.has_attribute("account"));
-                assert_eq!(struct_def.fields[0].name, "wallet");
-                assert!(struct_def.fields[0].has_attribute("key"));
+                assert!(struct_def.fields[0].optional);
+                assert_eq!(struct_def.fields[0].type_spec.as_string(), "Box<Node>");
+                assert_eq!(struct_def.fields[1].type_spec.as_string(), "[Box<Node>]");
             }
             _ => panic!("Expected struct item"),
         }
+
+        let err = parse_lumos_file("struct Node { next: Box<Option<Node>> }")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("use Option<Box<T>>"));
     }
 
     #[test]
-    fn test_parse_optional_type() {
+    fn test_parse_fixed_string() {
         let input = r#"
-            struct User {
-                email: Option<String>,
+            struct Profile {
+                name: FixedString<32>,
+                bio: FixedString<MAX_BIO>,
+                aliases: Vec<FixedString<8>>,
             }
         "#;
 
-        let result = parse_lumos_file(input);
-        assert!(result.is_ok());
-
-        let file = result.unwrap();
+        let file = parse_lumos_file(input).unwrap();
         match &file.items[0] {
             AstItem::Struct(struct_def) => {
-                let field = &struct_def.fields[0];
-                assert!(field.optional);
+                assert!(matches!(
+                    struct_def.fields[0].type_spec,
+                    TypeSpec::FixedString(ConstExpr::Literal(32))
+                ));
+                assert_eq!(
+                    struct_def.fields[1].type_spec.as_string(),
+                    "FixedString<MAX_BIO>"
+                );
+                assert_eq!(
+                    struct_def.fields[2].type_spec.as_string(),
+                    "[FixedString<8>]"
+                );
             }
             _ => panic!("Expected struct item"),
         }
+
+        for (field_type, message) in [
+            ("FixedString", "requires a capacity in bytes"),
+            (
+                "FixedString<Vec<u8>>",
+                "must be an integer literal or a constant name",
+            ),
+        ] {
+            let input = format!("struct Profile {{ name: {} }}", field_type);
+            let err = parse_lumos_file(&input).unwrap_err().to_string();
+            assert!(err.contains(message), "{}: {}", field_type, err);
+        }
     }
 
     #[test]
-    fn test_parse_array_type() {
+    fn test_parse_generic_instances() {
         let input = r#"
-            struct Team {
-                members: [u64],
+            struct Holder {
+                a: Wrapper<u64>,
+                b: Option<Pair<PublicKey, Vec<Wrapper<u8>>>>,
             }
         "#;
 
-        let result = parse_lumos_file(input);
-        assert!(result.is_ok());
+        let file = parse_lumos_file(input).unwrap();
+        match &file.items[0] {
+            AstItem::Struct(struct_def) => {
+                assert_eq!(struct_def.fields[0].type_spec.as_string(), "Wrapper<u64>");
+                assert!(struct_def.fields[1].optional);
+                assert_eq!(
+                    struct_def.fields[1].type_spec.as_string(),
+                    "Pair<PublicKey, [Wrapper<u8>]>"
+                );
+            }
+            _ => panic!("Expected struct item"),
+        }
 
-        let file = result.unwrap();
+        let err = parse_lumos_file("struct Holder { a: Wrapper<Option<u64>> }")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Option is not supported as a type argument of 'Wrapper'"));
+    }
+
+    #[test]
+    fn test_parse_invalid_tuples_fail() {
+        for (field_type, message) in [
+            ("()", "unit type () is not supported"),
+            ("(u8, Option<u8>)", "Option is not supported inside tuples"),
+            (
+                "(u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8)",
+                "at most 12 elements, found 13",
+            ),
+        ] {
+            let input = format!("struct Pool {{ value: {} }}", field_type);
+            let err = parse_lumos_file(&input).unwrap_err().to_string();
+            assert!(err.contains(message), "{}: {}", field_type, err);
+        }
+    }
+
+    #[test]
+    fn test_parse_const_definitions() {
+        let input = r#"
+            /// Longest allowed name
+            pub const MAX_NAME_LEN: usize = 32;
+            const NAME_BUF: u32 = MAX_NAME_LEN;
+
+            struct Profile {
+                name: [u8; MAX_NAME_LEN],
+                #[max(NAME_BUF)]
+                bio: String,
+            }
+        "#;
+
+        let file = parse_lumos_file(input).unwrap();
         match &file.items[0] {
+            AstItem::Const(const_def) => {
+                assert_eq!(const_def.name, "MAX_NAME_LEN");
+                assert_eq!(const_def.type_name, "usize");
+                assert_eq!(const_def.value, ConstExpr::Literal(32));
+                assert_eq!(const_def.visibility, Visibility::Public);
+                assert_eq!(const_def.docs, vec!["Longest allowed name"]);
+            }
+            _ => panic!("Expected const item"),
+        }
+        match &file.items[1] {
+            AstItem::Const(const_def) => {
+                assert_eq!(
+                    const_def.value,
+                    ConstExpr::Named("MAX_NAME_LEN".to_string())
+                );
+            }
+            _ => panic!("Expected const item"),
+        }
+        match &file.items[2] {
             AstItem::Struct(struct_def) => {
-                let field = &struct_def.fields[0];
-                assert!(field.type_spec.is_array());
+                assert_eq!(
+                    struct_def.fields[0].type_spec.as_string(),
+                    "[u8; MAX_NAME_LEN]"
+                );
+                assert_eq!(
+                    struct_def.fields[1].max_length_expr(),
+                    Some(ConstExpr::Named("NAME_BUF".to_string()))
+                );
             }
             _ => panic!("Expected struct item"),
         }
     }
 
+    #[test]
+    fn test_parse_const_with_non_integer_type_fails() {
+        let input = r#"
+            const LABEL: String = "hello";
+        "#;
+
+        let result = parse_lumos_file(input);
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("must have an unsigned integer type"));
+    }
+
+    #[test]
+    fn test_parse_const_value_errors() {
+        let input = "\nconst LABEL: usize = \"hello\";";
+        let err = parse_lumos_file(input).unwrap_err();
+        assert_eq!(
+            err.message(),
+            "Invalid value for constant 'LABEL': Expected an integer literal"
+        );
+        assert_eq!(err.location().map(|l| (l.line, l.column)), Some((2, 7)));
+
+        let file =
+            parse_lumos_file("const MAX: u128 = 340282366920938463463374607431768211455;").unwrap();
+        let AstItem::Const(const_def) = &file.items[0] else {
+            panic!("Expected const item");
+        };
+        assert_eq!(const_def.value, ConstExpr::Literal(u128::MAX));
+    }
+
+    #[test]
+    fn test_parse_instruction_args() {
+        let input = r#"
+            #[solana]
+            #[instruction(amount: u64, #[max(32)] memo: String)]
+            struct Deposit {
+                vault: Vault,
+            }
+        "#;
+
+        let file = parse_lumos_file(input).unwrap();
+        let crate::ast::Item::Struct(s) = &file.items[0] else {
+            panic!("Expected struct");
+        };
+        assert!(s.has_attribute("instruction"));
+        assert_eq!(s.fields.len(), 1);
+        assert_eq!(s.instruction_args.len(), 2);
+        assert_eq!(s.instruction_args[0].name, "amount");
+        assert_eq!(s.instruction_args[1].name, "memo");
+        assert_eq!(
+            s.instruction_args[1].max_length_expr(),
+            Some(ConstExpr::Literal(32))
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_instruction_args_fails() {
+        let input = r#"
+            #[instruction(u64)]
+            struct Deposit {
+                vault: Vault,
+            }
+        "#;
+
+        let err = parse_lumos_file(input).unwrap_err().to_string();
+        assert!(err.contains("Invalid #[instruction(...)] arguments"));
+    }
+
+    #[test]
+    fn test_parse_msg_on_unit_variant() {
+        let input = r#"
+            #[error_code]
+            enum VaultError {
+                #[msg("Insufficient \"vault\" funds")]
+                InsufficientFunds,
+            }
+        "#;
+
+        let file = parse_lumos_file(input).unwrap();
+        let crate::ast::Item::Enum(e) = &file.items[0] else {
+            panic!("Expected enum");
+        };
+        match &e.variants[0] {
+            EnumVariant::Unit { msg, .. } => {
+                assert_eq!(msg.as_deref(), Some("Insufficient \"vault\" funds"))
+            }
+            _ => panic!("Expected unit variant"),
+        }
+    }
+
+    #[test]
+    fn test_parse_msg_on_tuple_variant_fails() {
+        let input = r#"
+            #[error_code]
+            enum VaultError {
+                #[msg("Bad")]
+                Custom(u32),
+            }
+        "#;
+
+        let err = parse_lumos_file(input).unwrap_err().to_string();
+        assert!(err.contains("#[msg] is only supported on unit variants"));
+    }
+
+    #[test]
+    fn test_parse_explicit_discriminants() {
+        let input = r#"
+            #[solana]
+            #[repr(u16)]
+            enum Status {
+                Active = 1,
+                Frozen,
+                Closed = 0x100,
+                Transfer { amount: u64 } = 7,
+            }
+        "#;
+
+        let file = parse_lumos_file(input).unwrap();
+        let crate::ast::Item::Enum(e) = &file.items[0] else {
+            panic!("Expected enum");
+        };
+        let discriminants: Vec<Option<u64>> = e.variants.iter().map(|v| v.discriminant()).collect();
+        assert_eq!(discriminants, vec![Some(1), None, Some(256), Some(7)]);
+    }
+
+    #[test]
+    fn test_parse_negative_discriminant_fails() {
+        let input = r#"
+            enum Status {
+                Active = -1,
+            }
+        "#;
+
+        let err = parse_lumos_file(input).unwrap_err().to_string();
+        assert!(err.contains("Invalid discriminant for variant 'Active'"));
+    }
+
     #[test]
     fn test_parse_struct_with_version() {
         let input = r#"
@@ -1535,15 +2576,35 @@ mod tests {
     }
 
     #[test]
-    fn test_parse_use_glob_fails() {
-        let input = "use models::*;";
+    fn test_parse_use_glob() {
+        let input = "use crate::models::*;";
 
-        let result = parse_lumos_file(input);
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("Glob imports (use path::*) are not supported"));
+        let file = parse_lumos_file(input).unwrap();
+        match &file.items[0] {
+            AstItem::Use(use_stmt) => {
+                assert_eq!(use_stmt.path.to_string(), "crate::models");
+                assert!(use_stmt.glob);
+                assert_eq!(use_stmt.visibility, Visibility::Private);
+            }
+            _ => panic!("Expected use item"),
+        }
+    }
+
+    #[test]
+    fn test_parse_pub_use() {
+        let input = "pub use self::user::User as Member;";
+
+        let file = parse_lumos_file(input).unwrap();
+        match &file.items[0] {
+            AstItem::Use(use_stmt) => {
+                assert_eq!(use_stmt.path.to_string(), "self::user::User");
+                assert_eq!(use_stmt.alias.as_deref(), Some("Member"));
+                assert_eq!(use_stmt.visibility, Visibility::Public);
+                assert!(!use_stmt.glob);
+                assert!(use_stmt.span.is_some());
+            }
+            _ => panic!("Expected use item"),
+        }
     }
 
     #[test]
@@ -1803,4 +2864,229 @@ mod tests {
             .to_string()
             .contains("Const generic parameters are not yet supported"));
     }
+
+    #[test]
+    fn test_parse_doc_comments() {
+        let input = r#"
+            /// Player account
+            ///
+            /// Stores progression data.
+            #[solana]
+            struct Player {
+                /// Wallet that owns this account
+                wallet: PublicKey,
+                level: u16,
+            }
+
+            /** Lifecycle state */
+            enum GameState {
+                /// Waiting for players
+                Lobby,
+                Finished { /// Winning wallet
+                    winner: PublicKey },
+            }
+        "#;
+
+        let file = parse_lumos_file(input).unwrap();
+
+        match &file.items[0] {
+            AstItem::Struct(struct_def) => {
+                assert_eq!(
+                    struct_def.docs,
+                    vec!["Player account", "", "Stores progression data."]
+                );
+                assert!(!struct_def.has_attribute("doc"));
+                assert!(struct_def.has_attribute("solana"));
+                assert_eq!(
+                    struct_def.fields[0].docs,
+                    vec!["Wallet that owns this account"]
+                );
+                assert!(struct_def.fields[1].docs.is_empty());
+            }
+            _ => panic!("Expected struct item"),
+        }
+
+        match &file.items[1] {
+            AstItem::Enum(enum_def) => {
+                assert_eq!(enum_def.docs, vec!["Lifecycle state"]);
+                match &enum_def.variants[0] {
+                    EnumVariant::Unit { docs, .. } => {
+                        assert_eq!(docs, &vec!["Waiting for players".to_string()])
+                    }
+                    _ => panic!("Expected unit variant"),
+                }
+                match &enum_def.variants[1] {
+                    EnumVariant::Struct { fields, docs, .. } => {
+                        assert!(docs.is_empty());
+                        assert_eq!(fields[0].docs, vec!["Winning wallet"]);
+                    }
+                    _ => panic!("Expected struct variant"),
+                }
+            }
+            _ => panic!("Expected enum item"),
+        }
+    }
+
+    #[test]
+    fn test_parse_range_and_pattern_attributes() {
+        let input = r#"
+            struct Listing {
+                #[range(0..=MAX_FEE)]
+                fee_bps: u16,
+                #[range(-5..5)]
+                delta: i32,
+                #[range(1..)]
+                quantity: u64,
+                #[pattern(r"^\d+$")]
+                code: String,
+            }
+        "#;
+
+        let file = parse_lumos_file(input).unwrap();
+        let AstItem::Struct(struct_def) = &file.items[0] else {
+            panic!("Expected struct item");
+        };
+        assert_eq!(
+            struct_def.fields[0].range(),
+            Some(&RangeExpr {
+                start: Some(RangeBound::Literal(0)),
+                end: Some(RangeBound::Named("MAX_FEE".to_string())),
+                inclusive: true,
+            })
+        );
+        assert_eq!(
+            struct_def.fields[1].range(),
+            Some(&RangeExpr {
+                start: Some(RangeBound::Literal(-5)),
+                end: Some(RangeBound::Literal(5)),
+                inclusive: false,
+            })
+        );
+        assert_eq!(struct_def.fields[2].range().unwrap().to_string(), "1..");
+        assert_eq!(struct_def.fields[3].pattern(), Some("^\\d+$"));
+    }
+
+    #[test]
+    fn test_parse_invalid_range_and_pattern() {
+        let err = parse_lumos_file("struct A { #[range(10)] x: u8 }")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("expected a range such as 0..=100"), "{}", err);
+
+        let err = parse_lumos_file("struct A { #[range(0..=x + 1)] x: u8 }")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Invalid range bound"), "{}", err);
+
+        let err = parse_lumos_file("struct A { #[pattern(abc)] x: String }")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("expected a string literal"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_default_attribute() {
+        let input = r#"
+            struct Config {
+                #[default(100)]
+                fee_bps: u16,
+                #[default(-1)]
+                offset: i32,
+                #[default(0.5)]
+                ratio: f64,
+                #[default(true)]
+                active: bool,
+                #[default("USDC")]
+                symbol: String,
+                #[default(MAX_FEE)]
+                max_fee: u16,
+            }
+        "#;
+
+        let file = parse_lumos_file(input).unwrap();
+        let AstItem::Struct(struct_def) = &file.items[0] else {
+            panic!("Expected struct item");
+        };
+        let defaults: Vec<_> = struct_def
+            .fields
+            .iter()
+            .map(|f| f.default_value().cloned())
+            .collect();
+        assert_eq!(
+            defaults,
+            vec![
+                Some(DefaultExpr::Integer(100)),
+                Some(DefaultExpr::Integer(-1)),
+                Some(DefaultExpr::Float(0.5)),
+                Some(DefaultExpr::Bool(true)),
+                Some(DefaultExpr::String("USDC".to_string())),
+                Some(DefaultExpr::Named("MAX_FEE".to_string())),
+            ]
+        );
+
+        let err = parse_lumos_file("struct A { #[default([1])] x: u8 }")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("expected a number, bool, string or constant name"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_parse_rename_and_naming_policy() {
+        let input = r#"
+            #![rename_fields(ts = "camelCase")]
+
+            #[rename(ts = "TradeListing", py = "Listing")]
+            struct Listing {
+                #[rename(ts = "feeRate")]
+                fee_bps: u16,
+            }
+        "#;
+
+        let file = parse_lumos_file(input).unwrap();
+        assert_eq!(file.attributes.len(), 1);
+        assert_eq!(file.attributes[0].name, "rename_fields");
+        assert_eq!(
+            file.attributes[0].names(),
+            Some(&[("ts".to_string(), "camelCase".to_string())][..])
+        );
+
+        let AstItem::Struct(struct_def) = &file.items[0] else {
+            panic!("Expected struct item");
+        };
+        assert_eq!(
+            struct_def.attributes[0].names(),
+            Some(
+                &[
+                    ("ts".to_string(), "TradeListing".to_string()),
+                    ("py".to_string(), "Listing".to_string()),
+                ][..]
+            )
+        );
+        assert_eq!(
+            struct_def.fields[0].attributes[0].names(),
+            Some(&[("ts".to_string(), "feeRate".to_string())][..])
+        );
+
+        let err = parse_lumos_file("#[rename(ts)]\nstruct A { a: u8 }").unwrap_err();
+        assert!(err.to_string().contains("expected key = \"value\" pairs"));
+    }
+
+    #[test]
+    fn test_parse_newtype_alias() {
+        let file =
+            parse_lumos_file("#[newtype] type UserId = PublicKey; type Name = String;").unwrap();
+
+        let AstItem::TypeAlias(user_id) = &file.items[0] else {
+            panic!("Expected type alias");
+        };
+        assert!(user_id.is_newtype());
+        let AstItem::TypeAlias(name) = &file.items[1] else {
+            panic!("Expected type alias");
+        };
+        assert!(!name.is_newtype());
+    }
 }
@@ -0,0 +1,2111 @@
+// Licensed under either of Apache License, Version 2.0 or MIT license at your option.
+// Copyright 2025 RECTOR-LABS
+
+//! Project graph for multi-file LUMOS schemas
+//!
+//! A schema pulls in other files in two ways: JS-style
+//! `import { UserId } from "./types.lumos";` and Rust-style `mod models;`
+//! declarations with `use` paths. [`ProjectGraph`] follows both from an entry
+//! file in a single pass, so a project can mix them freely, and keeps:
+//!
+//! - every file's parsed AST and direct [`Dependency`]s
+//! - the module each file defines, used for qualified names like
+//!   `models::user::User` and set as `module_path` on the IR
+//! - the module tree (`mod` parents and children) that `use` paths resolve against
+//!
+//! ## Module paths
+//!
+//! `mod name;` in the entry file or in a `mod.lumos` looks for `name.lumos`
+//! or `name/mod.lumos` next to it. In any other file, `models.lumos`, it looks
+//! in the `models/` directory first, as Rust does, then next to the file.
+//! A file's module path follows the `mod` declarations from the entry file; a
+//! file that is only imported is named after its location (`common/types.lumos`
+//! is `common::types`).
+//!
+//! ## Packages
+//!
+//! `import { Price } from "@acme/oracle";` imports from a package published
+//! to the registry named in the project's `lumos.toml` (see
+//! [`crate::registry`]). A package's entry file is the module `acme::oracle`.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use lumos_core::project::ProjectGraph;
+//! use std::path::Path;
+//!
+//! let graph = ProjectGraph::load(Path::new("schema/main.lumos"))?;
+//! for file in graph.files() {
+//!     println!("{} ({} dependencies)", file.path.display(), file.dependencies.len());
+//! }
+//! let type_defs = graph.resolve()?;
+//! # Ok::<(), lumos_core::error::LumosError>(())
+//! ```
+
+use crate::ast::{
+    EnumVariant, Import, Item as AstItem, LumosFile, ModulePath, PathSegment, TypeSpec,
+    UseStatement, Visibility,
+};
+use crate::cache::{self, BuildCache};
+use crate::error::{ErrorCollector, LumosError, Result};
+use crate::ir::{Reexport, TypeDefinition, TypeInfo};
+use crate::parser::parse_lumos_file;
+use crate::registry::{is_package_import, package_module_path, PackageResolver};
+use crate::transform::{
+    apply_zero_copy_layouts, transform_to_ir, transform_to_ir_with_resolver_no_validation,
+    validate_type_references, validate_user_defined_types, TypeAliasResolver,
+};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How one file pulls in another
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyKind {
+    /// `import { .. } from "./file.lumos";` or `from "@scope/package"`
+    Import,
+    /// `mod name;`
+    Module,
+}
+
+/// A file that another file depends on directly
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    /// Canonical path of the file depended on
+    pub path: PathBuf,
+    /// Whether it is imported or declared as a module
+    pub kind: DependencyKind,
+}
+
+/// A loaded file of a project
+#[derive(Debug, Clone)]
+pub struct ProjectFile {
+    /// Canonical file path
+    pub path: PathBuf,
+
+    /// Parsed AST
+    pub ast: LumosFile,
+
+    /// Hash of the file's source (see [`cache::source_hash`])
+    pub source_hash: String,
+
+    /// Module the file defines (empty for the entry file)
+    pub module_path: Vec<String>,
+
+    /// File that declares this one with `mod`, if any
+    pub parent: Option<PathBuf>,
+
+    /// Child modules declared with `mod` (name -> file)
+    pub children: BTreeMap<String, PathBuf>,
+
+    /// Files this one imports or declares as modules, in source order
+    pub dependencies: Vec<Dependency>,
+}
+
+impl ProjectFile {
+    /// Qualified name of an item declared in this file (`models::user::User`)
+    pub fn qualified_name(&self, name: &str) -> String {
+        qualify(&self.module_path, name)
+    }
+
+    /// Names of the types and constants declared in this file
+    pub fn declared_names(&self) -> Vec<&str> {
+        self.ast
+            .items
+            .iter()
+            .filter_map(item_name)
+            .map(|(name, _)| name)
+            .collect()
+    }
+}
+
+/// All files of a schema project and how they depend on each other
+#[derive(Debug, Clone)]
+pub struct ProjectGraph {
+    /// Canonical path of the entry file
+    root: PathBuf,
+
+    /// Loaded files, each after the files it depends on
+    files: Vec<ProjectFile>,
+
+    /// Canonical path -> index into `files`
+    index: HashMap<PathBuf, usize>,
+
+    /// Packages the project imports, if it has a `lumos.toml`
+    packages: Option<PackageResolver>,
+
+    /// Whether some ASTs were read from a [`BuildCache`], without source spans
+    cached_asts: bool,
+}
+
+/// An error found while resolving a project, with the file it comes from
+#[derive(Debug)]
+pub(crate) struct FileError {
+    /// File the error's location refers to, `None` for errors spanning files
+    pub path: Option<PathBuf>,
+    pub error: LumosError,
+}
+
+impl ProjectGraph {
+    /// Load `entry` and every file it imports or declares as a module
+    pub fn load(entry: &Path) -> Result<Self> {
+        Self::load_with_sources(entry, &HashMap::new())
+    }
+
+    /// Load a project, reading files in `sources` (keyed by canonical path)
+    /// from memory instead of disk
+    ///
+    /// Lets an editor check unsaved buffers against the rest of the project.
+    pub fn load_with_sources(entry: &Path, sources: &HashMap<PathBuf, String>) -> Result<Self> {
+        Self::load_from(entry, sources, None)
+    }
+
+    /// Load a project, reading the AST of each file whose source is
+    /// unchanged from `cache` instead of parsing it
+    ///
+    /// Resolve it with [`ProjectGraph::resolve_cached`], which reports errors
+    /// with their source locations even though cached ASTs have none.
+    pub fn load_cached(entry: &Path, cache: &mut BuildCache) -> Result<Self> {
+        Self::load_from(entry, &HashMap::new(), Some(cache))
+    }
+
+    fn load_from(
+        entry: &Path,
+        sources: &HashMap<PathBuf, String>,
+        cache: Option<&mut BuildCache>,
+    ) -> Result<Self> {
+        let root = canonicalize(entry)?;
+        let mut loader = Loader {
+            packages: PackageResolver::discover(parent_dir(&root)?)?,
+            root: root.clone(),
+            sources,
+            cache,
+            cached_asts: false,
+            files: Vec::new(),
+            index: HashMap::new(),
+            loading_stack: Vec::new(),
+        };
+        loader.load(&root, None, DependencyKind::Import)?;
+
+        let mut graph = ProjectGraph {
+            root,
+            files: loader.files,
+            index: loader.index,
+            packages: loader.packages,
+            cached_asts: loader.cached_asts,
+        };
+        graph.assign_module_paths();
+        Ok(graph)
+    }
+
+    /// Canonical path of the entry file
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Every file of the project, each after the files it depends on
+    pub fn files(&self) -> &[ProjectFile] {
+        &self.files
+    }
+
+    /// The file at `path` (canonical), if it is part of the project
+    pub fn file(&self, path: &Path) -> Option<&ProjectFile> {
+        self.index.get(path).map(|&i| &self.files[i])
+    }
+
+    /// Packages the project imports, `None` without a `lumos.toml`
+    ///
+    /// Its [`PackageResolver::lockfile`] records the versions in use.
+    pub fn packages(&self) -> Option<&PackageResolver> {
+        self.packages.as_ref()
+    }
+
+    /// Whether `path` belongs to an imported package rather than the project
+    pub fn is_package_file(&self, path: &Path) -> bool {
+        self.packages
+            .as_ref()
+            .is_some_and(|packages| packages.package_of(path).is_some())
+    }
+
+    /// The `import` statements of a file with the files they import
+    fn imports<'a>(
+        &'a self,
+        file: &'a ProjectFile,
+    ) -> impl Iterator<Item = (&'a Import, &'a ProjectFile)> + 'a {
+        let imported = file
+            .dependencies
+            .iter()
+            .filter(|d| d.kind == DependencyKind::Import);
+        file.ast
+            .imports
+            .iter()
+            .zip(imported)
+            .filter_map(move |(import, dependency)| Some((import, self.file(&dependency.path)?)))
+    }
+
+    /// Whether `path` (canonical) is part of the project
+    pub fn contains(&self, path: &Path) -> bool {
+        self.index.contains_key(path)
+    }
+
+    /// Files that import `path` or declare it as a module
+    pub fn dependents(&self, path: &Path) -> Vec<&Path> {
+        self.files
+            .iter()
+            .filter(|f| f.dependencies.iter().any(|d| d.path == path))
+            .map(|f| f.path.as_path())
+            .collect()
+    }
+
+    /// Qualified names of every type and constant, in file order
+    pub fn qualified_names(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .flat_map(|file| {
+                file.declared_names()
+                    .into_iter()
+                    .map(|name| file.qualified_name(name))
+            })
+            .collect()
+    }
+
+    /// Check that every import and `use` names a declared item it can see,
+    /// and that no file refers to another module's private types
+    pub fn validate(&self) -> Result<()> {
+        let mut errors = Vec::new();
+        for file in &self.files {
+            record(&mut errors, Some(&file.path), self.validate_imports(file));
+            record(
+                &mut errors,
+                Some(&file.path),
+                self.validate_use_statements(file),
+            );
+            record(
+                &mut errors,
+                Some(&file.path),
+                self.validate_references(file),
+            );
+        }
+        self.collect_errors(errors).finish(())
+    }
+
+    /// Check every import and `use`, then transform all files to IR
+    ///
+    /// Type aliases, constants and type references resolve across the whole
+    /// project. Each definition's `module_path` is set to its file's module.
+    /// Errors in files other than the entry file name the file they are in.
+    pub fn resolve(&self) -> Result<Vec<TypeDefinition>> {
+        let (type_defs, errors) = self.resolve_by_file();
+        self.collect_errors(errors).finish(type_defs)
+    }
+
+    /// Like [`ProjectGraph::resolve`], reading the IR of each file whose
+    /// source, dependencies and the project's aliases and constants are
+    /// unchanged from `cache` instead of transforming it
+    ///
+    /// A project loaded with cached ASTs that fails to resolve is loaded
+    /// again from source, so that its errors point at their locations.
+    pub fn resolve_cached(&self, cache: &mut BuildCache) -> Result<Vec<TypeDefinition>> {
+        let (type_defs, errors) = self.resolve_with(Some(cache));
+        if !errors.is_empty() && self.cached_asts {
+            return ProjectGraph::load(&self.root)?.resolve();
+        }
+        self.collect_errors(errors).finish(type_defs)
+    }
+
+    /// Errors from files other than the entry file name the file they are in
+    fn collect_errors(&self, errors: Vec<FileError>) -> ErrorCollector {
+        let mut collector = ErrorCollector::new();
+        for FileError { path, error } in errors {
+            match path {
+                Some(path) if path != self.root => {
+                    collector.push(in_file(&self.root, &path, &error))
+                }
+                _ => collector.push(error),
+            }
+        }
+        collector
+    }
+
+    /// Like [`ProjectGraph::resolve`], keeping each error with its file
+    pub(crate) fn resolve_by_file(&self) -> (Vec<TypeDefinition>, Vec<FileError>) {
+        self.resolve_with(None)
+    }
+
+    fn resolve_with(
+        &self,
+        mut cache: Option<&mut BuildCache>,
+    ) -> (Vec<TypeDefinition>, Vec<FileError>) {
+        let mut errors = Vec::new();
+        for file in &self.files {
+            record(&mut errors, Some(&file.path), self.validate_imports(file));
+            record(
+                &mut errors,
+                Some(&file.path),
+                self.validate_use_statements(file),
+            );
+            record(
+                &mut errors,
+                Some(&file.path),
+                self.validate_references(file),
+            );
+        }
+
+        // A lone file keeps the single-file transform and its error reporting
+        if let [file] = self.files.as_slice() {
+            let key = cache::ir_key(&file.source_hash, "single-file", &[]);
+            let transformed = cached_ir(&mut cache, &key, || transform_to_ir(file.ast.clone()));
+            let type_defs = match transformed {
+                Ok(type_defs) => type_defs,
+                Err(error) => {
+                    record(&mut errors, Some(&file.path), Err(error));
+                    Vec::new()
+                }
+            };
+            return (type_defs, errors);
+        }
+
+        // First pass: aliases and constants from every file share one resolver
+        let mut resolver = TypeAliasResolver::new();
+        for file in &self.files {
+            let mut collector = ErrorCollector::new();
+            for item in &file.ast.items {
+                let added = match item {
+                    AstItem::TypeAlias(alias_def) if alias_def.is_newtype() => resolver
+                        .add_newtype(alias_def.name.clone(), alias_def.target.clone())
+                        .map_err(|e| e.with_span(alias_def.span)),
+                    AstItem::TypeAlias(alias_def) => resolver
+                        .add_alias(alias_def.name.clone(), alias_def.target.clone())
+                        .map_err(|e| e.with_span(alias_def.span)),
+                    AstItem::Const(const_def) => resolver
+                        .add_constant(const_def.name.clone(), const_def.value.clone())
+                        .map_err(|e| e.with_span(const_def.span)),
+                    _ => Ok(()),
+                };
+                collector.ok(added);
+            }
+            record(&mut errors, Some(&file.path), collector.finish(()));
+        }
+        record(&mut errors, None, resolver.resolve_all_aliases());
+        if !errors.is_empty() {
+            return (Vec::new(), errors);
+        }
+
+        // Second pass: transform each file with the shared resolver
+        let ir_keys = if cache.is_some() {
+            self.ir_keys()
+        } else {
+            HashMap::new()
+        };
+        let mut per_file = Vec::new();
+        for file in &self.files {
+            let transform =
+                || transform_to_ir_with_resolver_no_validation(file.ast.clone(), &resolver);
+            let transformed = match ir_keys.get(file.path.as_path()) {
+                Some(key) => cached_ir(&mut cache, key, transform),
+                None => transform(),
+            };
+            match transformed {
+                Ok(mut type_defs) => {
+                    for type_def in &mut type_defs {
+                        set_module_path(type_def, &file.module_path);
+                    }
+                    per_file.push((file, type_defs));
+                }
+                Err(error) => record(&mut errors, Some(&file.path), Err(error)),
+            }
+        }
+        if !errors.is_empty() {
+            return (Vec::new(), errors);
+        }
+
+        // Record where `pub use` makes each definition available
+        self.record_reexports(&mut per_file);
+
+        // Types that share a short name with a type in another module are
+        // told apart by their qualified names
+        errors.extend(self.qualify_shared_names(&mut per_file));
+        if !errors.is_empty() {
+            return (Vec::new(), errors);
+        }
+
+        // Third pass: type references resolve against the whole project
+        let defined_types: HashMap<String, usize> = per_file
+            .iter()
+            .flat_map(|(_, type_defs)| type_defs)
+            .filter(|t| !t.is_const())
+            .map(|t| (t.name().to_string(), t.generic_params().len()))
+            .collect();
+        for (file, type_defs) in &per_file {
+            record(
+                &mut errors,
+                Some(&file.path),
+                validate_type_references(type_defs, &defined_types),
+            );
+        }
+        if !errors.is_empty() {
+            return (Vec::new(), errors);
+        }
+
+        // Then the checks that span files: duplicate names and recursive types
+        let mut type_defs: Vec<TypeDefinition> = per_file
+            .into_iter()
+            .flat_map(|(_, type_defs)| type_defs)
+            .collect();
+        record(&mut errors, None, validate_user_defined_types(&type_defs));
+        if errors.is_empty() {
+            record(&mut errors, None, apply_zero_copy_layouts(&mut type_defs));
+        }
+
+        (type_defs, errors)
+    }
+
+    /// Key of each file's cached IR (see [`cache::ir_key`])
+    ///
+    /// Every file is transformed against the aliases and constants of the
+    /// whole project, so all keys cover them. Files come after their
+    /// dependencies, whose keys are then known.
+    fn ir_keys(&self) -> HashMap<&Path, String> {
+        let shared: Vec<Vec<u8>> = self
+            .files
+            .iter()
+            .flat_map(|file| &file.ast.items)
+            .filter(|item| matches!(item, AstItem::TypeAlias(_) | AstItem::Const(_)))
+            .map(|item| serde_json::to_vec(item).unwrap_or_default())
+            .collect();
+        let shared_hash = cache::hash(&shared.iter().map(Vec::as_slice).collect::<Vec<_>>());
+
+        let mut keys: HashMap<&Path, String> = HashMap::new();
+        for file in &self.files {
+            let dependency_keys: Vec<&str> = file
+                .dependencies
+                .iter()
+                .filter_map(|dependency| {
+                    let dependency = self.file(&dependency.path)?;
+                    Some(
+                        keys.get(dependency.path.as_path())
+                            .map(String::as_str)
+                            .unwrap_or(&dependency.source_hash),
+                    )
+                })
+                .collect();
+            let key = cache::ir_key(&file.source_hash, &shared_hash, &dependency_keys);
+            keys.insert(&file.path, key);
+        }
+        keys
+    }
+
+    /// Rename types whose short name is declared in more than one module to
+    /// their qualified name (`models::Config`), and point every reference at
+    /// the type it means
+    ///
+    /// A reference means the type declared in its own file, else the one it
+    /// imports or `use`s, following globs and `pub use` re-exports to the
+    /// declaration. `use .. as Alias` references are resolved the same way.
+    /// Types in the entry file keep their short name.
+    fn qualify_shared_names(
+        &self,
+        per_file: &mut [(&ProjectFile, Vec<TypeDefinition>)],
+    ) -> Vec<FileError> {
+        let mut modules_by_name: HashMap<&str, Vec<&[String]>> = HashMap::new();
+        for (file, type_defs) in per_file.iter() {
+            for type_def in type_defs.iter().filter(|t| !t.is_const()) {
+                let modules = modules_by_name.entry(type_def.name()).or_default();
+                if !modules.contains(&file.module_path.as_slice()) {
+                    modules.push(&file.module_path);
+                }
+            }
+        }
+        let shared: HashMap<String, String> = modules_by_name
+            .into_iter()
+            .filter(|(_, modules)| modules.len() > 1)
+            .map(|(name, modules)| {
+                let qualified: Vec<String> = modules
+                    .iter()
+                    .map(|m| format!("crate::{}", qualify(m, name)))
+                    .collect();
+                (name.to_string(), qualified.join(", "))
+            })
+            .collect();
+        let key = |module_path: &[String], name: &str| {
+            if shared.contains_key(name) {
+                qualify(module_path, name)
+            } else {
+                name.to_string()
+            }
+        };
+
+        let mut errors = Vec::new();
+        for (file, type_defs) in per_file.iter_mut() {
+            // What each name used in this file refers to
+            let mut scope: HashMap<String, String> = HashMap::new();
+            for (import, target) in self.imports(file) {
+                for name in &import.items {
+                    scope.insert(name.clone(), key(&target.module_path, name));
+                }
+            }
+            // Globs first, so that names imported one by one win over them
+            let uses = use_statements(&file.ast);
+            for use_stmt in uses
+                .iter()
+                .filter(|u| u.glob)
+                .chain(uses.iter().filter(|u| !u.glob))
+            {
+                let Ok(bindings) = self.use_bindings(file, use_stmt) else {
+                    continue;
+                };
+                for (local, binding) in bindings {
+                    scope.insert(local, key(&binding.file.module_path, binding.name));
+                }
+            }
+            for type_def in type_defs.iter().filter(|t| !t.is_const()) {
+                let name = type_def.name().to_string();
+                scope.insert(name.clone(), key(&file.module_path, &name));
+            }
+
+            let mut collector = ErrorCollector::new();
+            for type_def in type_defs.iter_mut() {
+                let span = type_def.span();
+                for type_info in type_def.field_types_mut() {
+                    type_info.walk_mut(&mut |t| {
+                        let (TypeInfo::UserDefined(name) | TypeInfo::Instance { name, .. }) = t
+                        else {
+                            return;
+                        };
+                        if let Some(key) = scope.get(name.as_str()) {
+                            if key != name {
+                                *name = key.clone();
+                            }
+                        } else if let Some(modules) = shared.get(name.as_str()) {
+                            collector.push(
+                                LumosError::TypeValidation(
+                                    format!(
+                                        "Type '{}' is declared in more than one module ({}); bring the one you mean into scope with `use`",
+                                        name, modules
+                                    ),
+                                    None,
+                                )
+                                .with_span(span),
+                            );
+                        }
+                    });
+                }
+                if !type_def.is_const() && shared.contains_key(type_def.name()) {
+                    let qualified = qualify(&file.module_path, type_def.name());
+                    set_name(type_def, qualified);
+                }
+            }
+            record(&mut errors, Some(&file.path), collector.finish(()));
+        }
+        errors
+    }
+
+    /// Add a [`Reexport`] to every definition a `pub use` makes available
+    /// from another module
+    fn record_reexports(&self, per_file: &mut [(&ProjectFile, Vec<TypeDefinition>)]) {
+        let mut found = Vec::new();
+        for (file, _) in per_file.iter() {
+            for use_stmt in use_statements(&file.ast) {
+                if !use_stmt.is_reexport() {
+                    continue;
+                }
+                let Ok(bindings) = self.use_bindings(file, use_stmt) else {
+                    continue;
+                };
+                // A glob re-exports only the public items it brings in
+                for (name, binding) in bindings.into_iter().filter(|(_, b)| b.is_public()) {
+                    let reexport = Reexport {
+                        module_path: file.module_path.clone(),
+                        name,
+                    };
+                    found.push((
+                        binding.file.path.clone(),
+                        binding.name.to_string(),
+                        reexport,
+                    ));
+                }
+            }
+        }
+
+        for (path, name, reexport) in found {
+            let type_def = per_file
+                .iter_mut()
+                .filter(|(file, _)| file.path == path)
+                .flat_map(|(_, type_defs)| type_defs.iter_mut())
+                .find(|t| t.name() == name);
+            if let Some(type_def) = type_def {
+                let reexports = reexports_mut(type_def);
+                if !reexports.contains(&reexport) {
+                    reexports.push(reexport);
+                }
+            }
+        }
+    }
+
+    /// Check that every imported name is declared, and visible, in the imported file
+    fn validate_imports(&self, file: &ProjectFile) -> Result<()> {
+        let mut errors = ErrorCollector::new();
+
+        for (import, target) in self.imports(file) {
+            for name in &import.items {
+                let error = match find_item(&target.ast, name) {
+                    None => format!("Type '{}' not found in '{}'", name, import.path),
+                    Some(Visibility::Private) => format!(
+                        "Type '{}' is private and cannot be imported from '{}'",
+                        name, import.path
+                    ),
+                    Some(Visibility::Public) => continue,
+                };
+                errors.push(LumosError::SchemaParse(error, None).with_span(import.span));
+            }
+        }
+
+        errors.finish(())
+    }
+
+    /// Check that every `use` path names a declared item it can see, and
+    /// that `pub use` only re-exports public items
+    fn validate_use_statements(&self, file: &ProjectFile) -> Result<()> {
+        let mut errors = ErrorCollector::new();
+        for use_stmt in use_statements(&file.ast) {
+            errors.ok(self
+                .validate_use_statement(file, use_stmt)
+                .map_err(|e| e.with_span(use_stmt.span)));
+        }
+        errors.finish(())
+    }
+
+    fn validate_use_statement(&self, file: &ProjectFile, use_stmt: &UseStatement) -> Result<()> {
+        self.use_bindings(file, use_stmt)?;
+
+        match use_stmt.local_name() {
+            Some(name) if find_item(&file.ast, name).is_some() => {
+                Err(LumosError::SchemaParse(
+                    format!(
+                        "Type '{}' is already declared in this module; import it under another name with `as`",
+                        name
+                    ),
+                    None,
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The names a `use` brings into scope in `file`, and what each refers to
+    ///
+    /// A glob brings in every item of the module that `file` can see.
+    fn use_bindings<'a>(
+        &'a self,
+        file: &ProjectFile,
+        use_stmt: &UseStatement,
+    ) -> Result<Vec<(String, Binding<'a>)>> {
+        let (target, item) = self.use_target(file, use_stmt)?;
+
+        let Some(item) = item else {
+            return Ok(self
+                .item_names(target, &mut Vec::new())
+                .into_iter()
+                .filter_map(|name| {
+                    let binding = self.lookup(target, &name, &mut Vec::new())?;
+                    binding
+                        .is_visible_from(self, file)
+                        .then_some((name, binding))
+                })
+                .collect());
+        };
+
+        let module = self.module_display(target);
+        let binding = self.lookup(target, item, &mut Vec::new()).ok_or_else(|| {
+            LumosError::SchemaParse(
+                format!("Type '{}' not found in module '{}'", item, module),
+                None,
+            )
+        })?;
+        // Private items are only visible inside their module and its children
+        if !binding.is_visible_from(self, file) {
+            return Err(LumosError::SchemaParse(
+                format!(
+                    "Type '{}' is private and cannot be imported from '{}'",
+                    item, module
+                ),
+                None,
+            ));
+        }
+        if use_stmt.is_reexport() && binding.visibility.is_private() {
+            return Err(LumosError::SchemaParse(
+                format!(
+                    "Type '{}' is private in '{}' and cannot be re-exported with `pub use`",
+                    item, module
+                ),
+                None,
+            ));
+        }
+
+        let local = use_stmt.alias.clone().unwrap_or_else(|| item.to_string());
+        Ok(vec![(local, binding)])
+    }
+
+    /// The module a `use` path points into, and the item it names there
+    /// (`None` for a glob)
+    fn use_target<'a, 'u>(
+        &'a self,
+        file: &ProjectFile,
+        use_stmt: &'u UseStatement,
+    ) -> Result<(&'a ProjectFile, Option<&'u str>)> {
+        let (target, rest) = self.resolve_use_path(&file.path, &use_stmt.path.segments)?;
+        match (rest, use_stmt.glob) {
+            ([], true) => Ok((target, None)),
+            ([PathSegment::Ident(name)], false) => Ok((target, Some(name))),
+            ([], false) => Err(LumosError::SchemaParse(
+                format!(
+                    "'{}' is a module; name a type in it, or import all of them with `{}::*`",
+                    use_stmt.path, use_stmt.path
+                ),
+                None,
+            )),
+            (rest, _) => Err(LumosError::SchemaParse(
+                format!(
+                    "Module '{}' not found in '{}'",
+                    ModulePath::new(rest[..1].to_vec()),
+                    self.module_display(target)
+                ),
+                None,
+            )),
+        }
+    }
+
+    /// What `name` refers to as an item of `module` (`crate::models::name`)
+    ///
+    /// The module's own items come first, then names it brings in with `use`,
+    /// then those of its glob imports. `stack` guards against `use` cycles.
+    fn lookup<'a>(
+        &'a self,
+        module: &'a ProjectFile,
+        name: &str,
+        stack: &mut Vec<PathBuf>,
+    ) -> Option<Binding<'a>> {
+        if let Some((declared, visibility)) = module
+            .ast
+            .items
+            .iter()
+            .filter_map(item_name)
+            .find(|(declared, _)| *declared == name)
+        {
+            return Some(Binding {
+                file: module,
+                name: declared,
+                module,
+                visibility,
+            });
+        }
+        if stack.contains(&module.path) {
+            return None;
+        }
+
+        stack.push(module.path.clone());
+        let found = self.lookup_use(module, name, stack);
+        stack.pop();
+        found
+    }
+
+    fn lookup_use<'a>(
+        &'a self,
+        module: &'a ProjectFile,
+        name: &str,
+        stack: &mut Vec<PathBuf>,
+    ) -> Option<Binding<'a>> {
+        let uses = use_statements(&module.ast);
+
+        for use_stmt in uses.iter().filter(|u| u.local_name() == Some(name)) {
+            let Ok((target, Some(item))) = self.use_target(module, use_stmt) else {
+                continue;
+            };
+            if let Some(binding) = self.lookup(target, item, stack) {
+                return Some(Binding {
+                    module,
+                    visibility: use_stmt.visibility.clone(),
+                    ..binding
+                });
+            }
+        }
+
+        for use_stmt in uses.iter().filter(|u| u.glob) {
+            let Ok((target, None)) = self.use_target(module, use_stmt) else {
+                continue;
+            };
+            let Some(binding) = self
+                .lookup(target, name, stack)
+                .filter(|b| b.is_visible_from(self, module))
+            else {
+                continue;
+            };
+            // A glob re-exports only what was public to begin with
+            let visibility = if binding.is_public() {
+                use_stmt.visibility.clone()
+            } else {
+                Visibility::Private
+            };
+            return Some(Binding {
+                module,
+                visibility,
+                ..binding
+            });
+        }
+
+        None
+    }
+
+    /// Every name `module` declares or brings in with `use`
+    fn item_names(&self, module: &ProjectFile, stack: &mut Vec<PathBuf>) -> BTreeSet<String> {
+        let mut names: BTreeSet<String> = module
+            .declared_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        if stack.contains(&module.path) {
+            return names;
+        }
+
+        stack.push(module.path.clone());
+        for use_stmt in use_statements(&module.ast) {
+            match use_stmt.local_name() {
+                Some(name) => {
+                    names.insert(name.to_string());
+                }
+                None => {
+                    if let Ok((target, None)) = self.use_target(module, use_stmt) {
+                        names.extend(self.item_names(target, stack));
+                    }
+                }
+            }
+        }
+        stack.pop();
+        names
+    }
+
+    /// Check that types a file refers to without importing them are visible
+    /// from it
+    ///
+    /// A name nothing declares is left to the type checks.
+    fn validate_references(&self, file: &ProjectFile) -> Result<()> {
+        let mut errors = ErrorCollector::new();
+        let imported: HashSet<&str> = file
+            .ast
+            .imports
+            .iter()
+            .flat_map(|import| import.items.iter().map(String::as_str))
+            .collect();
+
+        for (name, span) in references(&file.ast) {
+            if imported.contains(name.as_str())
+                || self.lookup(file, &name, &mut Vec::new()).is_some()
+            {
+                continue;
+            }
+            let declarations: Vec<Binding> = self
+                .files
+                .iter()
+                .filter_map(|other| self.lookup(other, &name, &mut Vec::new()))
+                .filter(|b| std::ptr::eq(b.file, b.module))
+                .collect();
+            if let Some(private) = declarations.first() {
+                if declarations.iter().all(|b| !b.is_visible_from(self, file)) {
+                    errors.push(
+                        LumosError::TypeValidation(
+                            format!(
+                                "Type '{}' is private to module '{}'",
+                                name,
+                                self.module_display(private.module)
+                            ),
+                            None,
+                        )
+                        .with_span(span),
+                    );
+                }
+            }
+        }
+
+        errors.finish(())
+    }
+
+    /// Resolve the module part of a `use` path to the file that defines it,
+    /// returning the segments after it
+    ///
+    /// Handles `crate::models::User` (from the entry file), `super::User`
+    /// (from the parent module), `self::types::UserId` and `models::User`
+    /// (from the current module).
+    fn resolve_use_path<'s>(
+        &self,
+        current: &Path,
+        segments: &'s [PathSegment],
+    ) -> Result<(&ProjectFile, &'s [PathSegment])> {
+        let mut target = self.module(current)?;
+        let mut rest = segments;
+
+        match segments.first() {
+            Some(PathSegment::Crate) => {
+                target = self.module(&self.root)?;
+                rest = &segments[1..];
+            }
+            Some(PathSegment::SelfPath) => rest = &segments[1..],
+            _ => {}
+        }
+
+        for (i, segment) in rest.iter().enumerate() {
+            match segment {
+                PathSegment::Ident(name) => match target.children.get(name) {
+                    Some(child) => target = self.module(child)?,
+                    // Not a child module, so the item path starts here
+                    None => return Ok((target, &rest[i..])),
+                },
+                PathSegment::Super => {
+                    let parent = target.parent.as_ref().ok_or_else(|| {
+                        LumosError::SchemaParse(
+                            format!(
+                                "Cannot use 'super' - module '{}' has no parent",
+                                self.module_display(target)
+                            ),
+                            None,
+                        )
+                    })?;
+                    target = self.module(parent)?;
+                }
+                PathSegment::Crate | PathSegment::SelfPath => {
+                    return Err(LumosError::SchemaParse(
+                        format!(
+                            "'{}' can only appear at the start of a path",
+                            if matches!(segment, PathSegment::Crate) {
+                                "crate"
+                            } else {
+                                "self"
+                            }
+                        ),
+                        None,
+                    ));
+                }
+            }
+        }
+
+        Ok((target, &[]))
+    }
+
+    fn module(&self, path: &Path) -> Result<&ProjectFile> {
+        self.file(path).ok_or_else(|| {
+            LumosError::SchemaParse(format!("Module not found: {}", path.display()), None)
+        })
+    }
+
+    /// `crate::models::user` style name of a file's module
+    fn module_display(&self, file: &ProjectFile) -> String {
+        std::iter::once("crate")
+            .chain(file.module_path.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join("::")
+    }
+
+    /// Name each file's module after the `mod` declarations that reach it,
+    /// or after its location for files that are only imported
+    fn assign_module_paths(&mut self) {
+        let mut assigned: HashMap<PathBuf, Vec<String>> = HashMap::new();
+        let mut starts = vec![(self.root.clone(), Vec::new())];
+        starts.extend(
+            self.files
+                .iter()
+                .filter(|f| f.parent.is_none() && f.path != self.root)
+                .map(|f| (f.path.clone(), self.location_module_path(&f.path))),
+        );
+
+        for (start, module_path) in starts {
+            let mut queue = vec![(start, module_path)];
+            while let Some((path, module_path)) = queue.pop() {
+                if assigned.contains_key(&path) {
+                    continue;
+                }
+                if let Some(file) = self.file(&path) {
+                    for (name, child) in &file.children {
+                        let mut child_path = module_path.clone();
+                        child_path.push(name.clone());
+                        queue.push((child.clone(), child_path));
+                    }
+                }
+                assigned.insert(path, module_path);
+            }
+        }
+
+        for file in &mut self.files {
+            if let Some(module_path) = assigned.remove(&file.path) {
+                file.module_path = module_path;
+            }
+        }
+    }
+
+    /// `common/types.lumos` -> `["common", "types"]`, relative to the entry
+    /// file, or to the package for files of a package
+    fn location_module_path(&self, path: &Path) -> Vec<String> {
+        let package = self.packages.as_ref().and_then(|p| p.package_of(path));
+        if let Some(package) = package {
+            let mut segments = package_module_path(&package.name);
+            if path != package.entry {
+                if let Ok(relative) = path.strip_prefix(&package.dir) {
+                    segments.extend(relative_module_path(relative));
+                }
+            }
+            return segments;
+        }
+
+        let relative = self
+            .root
+            .parent()
+            .and_then(|dir| path.strip_prefix(dir).ok());
+        let Some(relative) = relative else {
+            // Outside the project directory: just the file name
+            return path
+                .file_stem()
+                .map(|s| vec![s.to_string_lossy().to_string()])
+                .unwrap_or_default();
+        };
+
+        relative_module_path(relative)
+    }
+}
+
+/// `common/types.lumos` -> `["common", "types"]`, `models/mod.lumos` -> `["models"]`
+fn relative_module_path(relative: &Path) -> Vec<String> {
+    let mut segments: Vec<String> = relative
+        .with_extension("")
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    if segments.last().map(String::as_str) == Some("mod") {
+        segments.pop();
+    }
+    segments
+}
+
+/// What a name refers to as an item of a module
+#[derive(Debug, Clone)]
+struct Binding<'a> {
+    /// File that declares the item
+    file: &'a ProjectFile,
+
+    /// Name the item is declared with
+    name: &'a str,
+
+    /// Module the name is an item of: the declaring file, or one that
+    /// brings it in with `use`
+    module: &'a ProjectFile,
+
+    /// Visibility of the name in `module` (`pub use` makes it public there)
+    visibility: Visibility,
+}
+
+impl Binding<'_> {
+    fn is_public(&self) -> bool {
+        self.visibility.is_public()
+    }
+
+    /// Public names are visible everywhere; private ones inside their
+    /// module and its child modules, as in Rust
+    fn is_visible_from(&self, graph: &ProjectGraph, file: &ProjectFile) -> bool {
+        if self.is_public() {
+            return true;
+        }
+        let mut current = Some(file);
+        while let Some(module) = current {
+            if module.path == self.module.path {
+                return true;
+            }
+            current = module.parent.as_deref().and_then(|p| graph.file(p));
+        }
+        false
+    }
+}
+
+/// Depth-first loader that builds the file list
+struct Loader<'s> {
+    root: PathBuf,
+    sources: &'s HashMap<PathBuf, String>,
+    files: Vec<ProjectFile>,
+    index: HashMap<PathBuf, usize>,
+    /// Files currently being loaded (for cycle detection)
+    loading_stack: Vec<PathBuf>,
+    /// Resolves package imports, `None` without a `lumos.toml`
+    packages: Option<PackageResolver>,
+    /// Where to read and store ASTs, if anywhere
+    cache: Option<&'s mut BuildCache>,
+    /// Whether an AST was read from `cache`
+    cached_asts: bool,
+}
+
+impl Loader<'_> {
+    fn load(&mut self, path: &Path, parent: Option<&Path>, kind: DependencyKind) -> Result<()> {
+        // Already loaded through another file
+        if let Some(&i) = self.index.get(path) {
+            if let (Some(parent), None) = (parent, &self.files[i].parent) {
+                self.files[i].parent = Some(parent.to_path_buf());
+            }
+            return Ok(());
+        }
+
+        if self.loading_stack.iter().any(|p| p == path) {
+            let what = match kind {
+                DependencyKind::Import => "Circular import detected",
+                DependencyKind::Module => "Circular module dependency detected",
+            };
+            return Err(LumosError::SchemaParse(
+                format!(
+                    "{}: {} -> {}",
+                    what,
+                    self.format_loading_chain(),
+                    path.display()
+                ),
+                None,
+            ));
+        }
+        self.loading_stack.push(path.to_path_buf());
+
+        let (ast, source_hash) = self.parse(path)?;
+        let current_dir = parent_dir(path)?;
+
+        // Child modules, then imports, each loaded before this file
+        let mut children = BTreeMap::new();
+        let mut dependencies = Vec::new();
+        for item in &ast.items {
+            if let AstItem::Module(module) = item {
+                let child = self.resolve_module_path(path, &module.name)?;
+                self.load(&child, Some(path), DependencyKind::Module)?;
+                children.insert(module.name.clone(), child.clone());
+                dependencies.push(Dependency {
+                    path: child,
+                    kind: DependencyKind::Module,
+                });
+            }
+        }
+        for import in &ast.imports {
+            let imported = if is_package_import(&import.path) {
+                self.resolve_package(path, &import.path)?
+            } else {
+                resolve_import_path(current_dir, &import.path)?
+            };
+            self.load(&imported, None, DependencyKind::Import)?;
+            dependencies.push(Dependency {
+                path: imported,
+                kind: DependencyKind::Import,
+            });
+        }
+
+        self.loading_stack.pop();
+        self.index.insert(path.to_path_buf(), self.files.len());
+        self.files.push(ProjectFile {
+            path: path.to_path_buf(),
+            ast,
+            source_hash,
+            module_path: Vec::new(),
+            parent: parent.map(Path::to_path_buf),
+            children,
+            dependencies,
+        });
+
+        Ok(())
+    }
+
+    /// Parse a file, naming it in errors unless it is the entry file
+    ///
+    /// Returns the AST with the hash of the source it was parsed from.
+    fn parse(&mut self, path: &Path) -> Result<(LumosFile, String)> {
+        let read;
+        let source = match self.sources.get(path) {
+            Some(source) => source,
+            None => {
+                read = fs::read_to_string(path).map_err(|e| {
+                    LumosError::SchemaParse(
+                        format!("Failed to read file '{}': {}", path.display(), e),
+                        None,
+                    )
+                })?;
+                &read
+            }
+        };
+
+        let source_hash = cache::source_hash(source);
+        if let Some(ast) = self.cache.as_mut().and_then(|c| c.load_ast(&source_hash)) {
+            self.cached_asts = true;
+            return Ok((ast, source_hash));
+        }
+
+        let ast = parse_lumos_file(source).map_err(|error| {
+            if path == self.root {
+                error
+            } else {
+                in_file(&self.root, path, &error)
+            }
+        })?;
+        if let Some(cache) = &self.cache {
+            cache.store_ast(&source_hash, &ast);
+        }
+        Ok((ast, source_hash))
+    }
+
+    /// Find the file of `mod name;` declared in `declaring_file`
+    ///
+    /// The entry file and `mod.lumos` files own their directory, so children
+    /// are `name.lumos` or `name/mod.lumos` next to them. Any other file,
+    /// `models.lumos`, keeps its children in `models/`, with files next to it
+    /// as a fallback.
+    fn resolve_module_path(&self, declaring_file: &Path, name: &str) -> Result<PathBuf> {
+        let dir = parent_dir(declaring_file)?;
+        let owns_dir = declaring_file == self.root
+            || declaring_file.file_name().and_then(|n| n.to_str()) == Some("mod.lumos");
+
+        let mut candidates = Vec::new();
+        if !owns_dir {
+            if let Some(stem) = declaring_file.file_stem() {
+                let nested = dir.join(stem);
+                candidates.push(nested.join(format!("{}.lumos", name)));
+                candidates.push(nested.join(name).join("mod.lumos"));
+            }
+        }
+        candidates.push(dir.join(format!("{}.lumos", name)));
+        candidates.push(dir.join(name).join("mod.lumos"));
+
+        match candidates.iter().find(|c| c.exists()) {
+            Some(found) => canonicalize(found),
+            None => Err(LumosError::SchemaParse(
+                format!(
+                    "Module '{}' not found. Tried:\n{}",
+                    name,
+                    candidates
+                        .iter()
+                        .map(|c| format!("  - {}", c.display()))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                ),
+                None,
+            )),
+        }
+    }
+
+    /// Entry file of the package imported as `name` in `importer`
+    fn resolve_package(&mut self, importer: &Path, name: &str) -> Result<PathBuf> {
+        let packages = self.packages.as_mut().ok_or_else(|| {
+            LumosError::SchemaParse(
+                format!(
+                    "Cannot import package '{}': no {} found for the project",
+                    name,
+                    crate::registry::MANIFEST_FILE
+                ),
+                None,
+            )
+        })?;
+        Ok(packages.resolve(importer, name)?.entry.clone())
+    }
+
+    fn format_loading_chain(&self) -> String {
+        self.loading_stack
+            .iter()
+            .map(|p| p.file_name().and_then(|n| n.to_str()).unwrap_or("unknown"))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
+}
+
+/// Whether a file pulls in other files with `import`, `mod` or `use`
+///
+/// Files without any can be checked on their own, without loading a project.
+pub fn has_dependencies(ast: &LumosFile) -> bool {
+    !ast.imports.is_empty()
+        || ast
+            .items
+            .iter()
+            .any(|item| matches!(item, AstItem::Module(_) | AstItem::Use(_)))
+}
+
+/// The IR cached under `key`, else `transform`'s, stored if it succeeds
+fn cached_ir(
+    cache: &mut Option<&mut BuildCache>,
+    key: &str,
+    transform: impl FnOnce() -> Result<Vec<TypeDefinition>>,
+) -> Result<Vec<TypeDefinition>> {
+    let Some(cache) = cache else {
+        return transform();
+    };
+    if let Some(type_defs) = cache.load_ir(key) {
+        return Ok(type_defs);
+    }
+    let type_defs = transform()?;
+    cache.store_ir(key, &type_defs);
+    Ok(type_defs)
+}
+
+fn record(errors: &mut Vec<FileError>, path: Option<&Path>, result: Result<()>) {
+    if let Err(error) = result {
+        errors.push(FileError {
+            path: path.map(Path::to_path_buf),
+            error,
+        });
+    }
+}
+
+/// Fold the file name and location of errors from a file other than the
+/// entry file into their messages, since their lines refer to that file
+pub(crate) fn in_file(root: &Path, path: &Path, error: &LumosError) -> LumosError {
+    let file = root
+        .parent()
+        .and_then(|dir| path.strip_prefix(dir).ok())
+        .unwrap_or(path)
+        .display()
+        .to_string();
+
+    let mut errors: Vec<LumosError> = error
+        .errors()
+        .into_iter()
+        .map(|error| {
+            let message = match error.location() {
+                Some(location) => format!("{}:{}: {}", file, location.format(), error.message()),
+                None => format!("{}: {}", file, error.message()),
+            };
+            LumosError::SchemaParse(message, None)
+        })
+        .collect();
+
+    if errors.len() == 1 {
+        errors.remove(0)
+    } else {
+        LumosError::Multiple(errors)
+    }
+}
+
+/// Resolve an import path like `./types` or `../common/types.lumos`
+fn resolve_import_path(current_dir: &Path, import_path: &str) -> Result<PathBuf> {
+    let mut resolved = current_dir.join(import_path);
+    if resolved.extension().is_none() {
+        resolved.set_extension("lumos");
+    }
+    canonicalize(&resolved)
+}
+
+fn canonicalize(path: &Path) -> Result<PathBuf> {
+    path.canonicalize().map_err(|e| {
+        LumosError::SchemaParse(
+            format!("Failed to resolve path '{}': {}", path.display(), e),
+            None,
+        )
+    })
+}
+
+fn parent_dir(path: &Path) -> Result<&Path> {
+    path.parent().ok_or_else(|| {
+        LumosError::SchemaParse(format!("Invalid file path: {}", path.display()), None)
+    })
+}
+
+/// Name and visibility of an item that declares a type or constant
+fn item_name(item: &AstItem) -> Option<(&str, Visibility)> {
+    match item {
+        AstItem::Struct(s) => Some((&s.name, s.visibility.clone())),
+        AstItem::Enum(e) => Some((&e.name, e.visibility.clone())),
+        AstItem::TypeAlias(a) => Some((&a.name, a.visibility.clone())),
+        AstItem::Const(c) => Some((&c.name, c.visibility.clone())),
+        AstItem::Module(_) | AstItem::Use(_) => None,
+    }
+}
+
+/// The `use` statements of a file, in source order
+fn use_statements(ast: &LumosFile) -> Vec<&UseStatement> {
+    ast.items
+        .iter()
+        .filter_map(|item| match item {
+            AstItem::Use(use_stmt) => Some(use_stmt),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Every type name a file's structs, enums and aliases refer to,
+/// with the span of the item that refers to it
+fn references(ast: &LumosFile) -> Vec<(String, Option<proc_macro2::Span>)> {
+    let mut found = Vec::new();
+    for item in &ast.items {
+        let (type_specs, span): (Vec<&TypeSpec>, _) = match item {
+            AstItem::Struct(s) => (
+                s.fields
+                    .iter()
+                    .chain(&s.instruction_args)
+                    .map(|f| &f.type_spec)
+                    .collect(),
+                s.span,
+            ),
+            AstItem::Enum(e) => (
+                e.variants
+                    .iter()
+                    .flat_map(|variant| match variant {
+                        EnumVariant::Unit { .. } => Vec::new(),
+                        EnumVariant::Tuple { types, .. } => types.iter().collect(),
+                        EnumVariant::Struct { fields, .. } => {
+                            fields.iter().map(|f| &f.type_spec).collect()
+                        }
+                    })
+                    .collect(),
+                e.span,
+            ),
+            AstItem::TypeAlias(a) => (vec![&a.target], a.span),
+            AstItem::Const(_) | AstItem::Module(_) | AstItem::Use(_) => continue,
+        };
+        for type_spec in type_specs {
+            type_spec.walk(&mut |t| {
+                // The parser leaves user-defined names as `Primitive`
+                if let TypeSpec::Primitive(name)
+                | TypeSpec::UserDefined(name)
+                | TypeSpec::Instance { name, .. } = t
+                {
+                    found.push((name.clone(), span));
+                }
+            });
+        }
+    }
+    found
+}
+
+/// Visibility of the item called `name` in `ast`, if it declares one
+fn find_item(ast: &LumosFile, name: &str) -> Option<Visibility> {
+    ast.items
+        .iter()
+        .filter_map(item_name)
+        .find(|(item, _)| *item == name)
+        .map(|(_, visibility)| visibility)
+}
+
+fn qualify(module_path: &[String], name: &str) -> String {
+    module_path
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(name))
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+fn set_name(type_def: &mut TypeDefinition, name: String) {
+    match type_def {
+        TypeDefinition::Struct(s) => s.name = name,
+        TypeDefinition::Enum(e) => e.name = name,
+        TypeDefinition::TypeAlias(a) => a.name = name,
+        TypeDefinition::Const(c) => c.name = name,
+    }
+}
+
+fn reexports_mut(type_def: &mut TypeDefinition) -> &mut Vec<Reexport> {
+    match type_def {
+        TypeDefinition::Struct(s) => &mut s.reexports,
+        TypeDefinition::Enum(e) => &mut e.reexports,
+        TypeDefinition::TypeAlias(a) => &mut a.reexports,
+        TypeDefinition::Const(c) => &mut c.reexports,
+    }
+}
+
+fn set_module_path(type_def: &mut TypeDefinition, module_path: &[String]) {
+    let target = match type_def {
+        TypeDefinition::Struct(s) => &mut s.module_path,
+        TypeDefinition::Enum(e) => &mut e.module_path,
+        TypeDefinition::TypeAlias(a) => &mut a.module_path,
+        TypeDefinition::Const(c) => &mut c.module_path,
+    };
+    *target = module_path.to_vec();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::TypeInfo;
+    use tempfile::TempDir;
+
+    fn write(dir: &TempDir, name: &str, content: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path.canonicalize().unwrap()
+    }
+
+    fn module_of<'a>(type_defs: &'a [TypeDefinition], name: &str) -> &'a [String] {
+        type_defs
+            .iter()
+            .find(|t| t.name() == name)
+            .map(|t| t.module_path())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_mixed_imports_and_modules() {
+        let dir = TempDir::new().unwrap();
+        let main = write(
+            &dir,
+            "main.lumos",
+            r#"
+import { Amount } from "./types.lumos";
+mod models;
+
+struct Vault {
+    owner: User,
+    balance: Amount,
+}
+"#,
+        );
+        let models = write(&dir, "models.lumos", "struct User { id: u64 }");
+        let types = write(&dir, "types.lumos", "type Amount = u64;");
+
+        let graph = ProjectGraph::load(&main).unwrap();
+        assert_eq!(graph.root(), main);
+        let paths: Vec<&Path> = graph.files().iter().map(|f| f.path.as_path()).collect();
+        assert_eq!(
+            paths,
+            vec![models.as_path(), types.as_path(), main.as_path()]
+        );
+        assert_eq!(
+            graph.file(&main).unwrap().dependencies,
+            vec![
+                Dependency {
+                    path: models.clone(),
+                    kind: DependencyKind::Module,
+                },
+                Dependency {
+                    path: types.clone(),
+                    kind: DependencyKind::Import,
+                },
+            ]
+        );
+
+        let type_defs = graph.resolve().unwrap();
+        let Some(TypeDefinition::Struct(vault)) = type_defs.iter().find(|t| t.name() == "Vault")
+        else {
+            panic!("expected Vault");
+        };
+        assert_eq!(
+            vault.fields[1].type_info,
+            TypeInfo::Primitive("u64".to_string())
+        );
+        assert_eq!(module_of(&type_defs, "User"), ["models"]);
+        assert!(module_of(&type_defs, "Vault").is_empty());
+    }
+
+    #[test]
+    fn test_nested_module_directory() {
+        let dir = TempDir::new().unwrap();
+        let main = write(&dir, "main.lumos", "mod models;");
+        let models = write(&dir, "models.lumos", "mod user;\n\ntype Timestamp = i64;");
+        let user = write(
+            &dir,
+            "models/user.lumos",
+            "struct User { created_at: Timestamp }",
+        );
+
+        let graph = ProjectGraph::load(&main).unwrap();
+        let file = graph.file(&user).unwrap();
+        assert_eq!(file.module_path, ["models", "user"]);
+        assert_eq!(file.parent.as_deref(), Some(models.as_path()));
+        assert_eq!(file.qualified_name("User"), "models::user::User");
+        assert_eq!(
+            graph.file(&models).unwrap().children.get("user"),
+            Some(&user)
+        );
+        assert_eq!(
+            graph.qualified_names(),
+            vec!["models::user::User", "models::Timestamp"]
+        );
+
+        let type_defs = graph.resolve().unwrap();
+        assert_eq!(module_of(&type_defs, "User"), ["models", "user"]);
+    }
+
+    #[test]
+    fn test_shared_names_are_qualified() {
+        let dir = TempDir::new().unwrap();
+        let main = write(
+            &dir,
+            "main.lumos",
+            r#"
+mod settings;
+
+use crate::settings::Config as Settings;
+
+struct Config { admin: PublicKey }
+
+struct Vault {
+    config: Config,
+    settings: Settings,
+}
+"#,
+        );
+        write(&dir, "settings.lumos", "pub struct Config { fee_bps: u16 }");
+
+        let type_defs = ProjectGraph::load(&main).unwrap().resolve().unwrap();
+        assert_eq!(module_of(&type_defs, "settings::Config"), ["settings"]);
+        assert!(module_of(&type_defs, "Config").is_empty());
+
+        let Some(TypeDefinition::Struct(vault)) = type_defs.iter().find(|t| t.name() == "Vault")
+        else {
+            panic!("expected Vault");
+        };
+        assert_eq!(
+            vault.fields[0].type_info,
+            TypeInfo::UserDefined("Config".to_string())
+        );
+        assert_eq!(
+            vault.fields[1].type_info,
+            TypeInfo::UserDefined("settings::Config".to_string())
+        );
+    }
+
+    #[test]
+    fn test_shared_name_must_be_in_scope() {
+        let dir = TempDir::new().unwrap();
+        let main = write(
+            &dir,
+            "main.lumos",
+            "mod a;\nmod b;\n\nstruct Vault { config: Config }",
+        );
+        write(&dir, "a.lumos", "pub struct Config { x: u8 }");
+        write(&dir, "b.lumos", "pub struct Config { y: u8 }");
+
+        let err = ProjectGraph::load(&main)
+            .unwrap()
+            .resolve()
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Type 'Config' is declared in more than one module"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_private_use_points_at_use_site() {
+        let dir = TempDir::new().unwrap();
+        let main = write(
+            &dir,
+            "main.lumos",
+            "mod models;\n\nuse crate::models::Secret;\n\nstruct Vault { secret: Secret }",
+        );
+        write(
+            &dir,
+            "models.lumos",
+            "pub(crate) struct Secret { key: u64 }",
+        );
+
+        let err = ProjectGraph::load(&main).unwrap().validate().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Type 'Secret' is private and cannot be imported from 'crate::models'"),
+            "{}",
+            err
+        );
+        let location = err.errors()[0].location().unwrap();
+        assert_eq!((location.line, location.column), (3, 20));
+    }
+
+    #[test]
+    fn test_private_type_used_without_import() {
+        let dir = TempDir::new().unwrap();
+        let main = write(&dir, "main.lumos", "mod a;\nmod b;");
+        write(&dir, "a.lumos", "pub(crate) struct Secret { key: u64 }");
+        write(&dir, "b.lumos", "struct Vault { secret: Secret }");
+
+        let err = ProjectGraph::load(&main)
+            .unwrap()
+            .resolve()
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("b.lumos:1:")
+                && err.contains("Type 'Secret' is private to module 'crate::a'"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_private_type_visible_to_child_modules() {
+        let dir = TempDir::new().unwrap();
+        let main = write(&dir, "main.lumos", "mod models;");
+        write(
+            &dir,
+            "models.lumos",
+            "mod user;\n\npub(crate) struct Stamp { at: i64 }",
+        );
+        write(
+            &dir,
+            "models/user.lumos",
+            "use super::Stamp;\n\nstruct User { created: Stamp }",
+        );
+
+        assert!(ProjectGraph::load(&main).unwrap().resolve().is_ok());
+    }
+
+    #[test]
+    fn test_pub_use_reexports() {
+        let dir = TempDir::new().unwrap();
+        let main = write(
+            &dir,
+            "main.lumos",
+            r#"
+mod models;
+
+use crate::models::Member;
+
+struct Vault { owner: Member }
+"#,
+        );
+        write(
+            &dir,
+            "models.lumos",
+            "mod user;\n\npub use self::user::User as Member;",
+        );
+        write(&dir, "models/user.lumos", "pub struct User { id: u64 }");
+
+        let type_defs = ProjectGraph::load(&main).unwrap().resolve().unwrap();
+        let user = type_defs.iter().find(|t| t.name() == "User").unwrap();
+        assert_eq!(
+            user.reexports(),
+            [Reexport {
+                module_path: vec!["models".to_string()],
+                name: "Member".to_string(),
+            }]
+        );
+
+        let Some(TypeDefinition::Struct(vault)) = type_defs.iter().find(|t| t.name() == "Vault")
+        else {
+            panic!("expected Vault");
+        };
+        assert_eq!(
+            vault.fields[0].type_info,
+            TypeInfo::UserDefined("User".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pub_use_of_private_type() {
+        let dir = TempDir::new().unwrap();
+        let main = write(&dir, "main.lumos", "mod models;");
+        write(
+            &dir,
+            "models.lumos",
+            "mod user;\n\npub use self::user::Secret;",
+        );
+        write(
+            &dir,
+            "models/user.lumos",
+            "pub(crate) struct Secret { key: u64 }",
+        );
+
+        let err = ProjectGraph::load(&main)
+            .unwrap()
+            .validate()
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains(
+                "Type 'Secret' is private and cannot be imported from 'crate::models::user'"
+            ),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_glob_use() {
+        let dir = TempDir::new().unwrap();
+        let main = write(
+            &dir,
+            "main.lumos",
+            r#"
+mod a;
+mod b;
+
+use crate::a::*;
+
+struct Vault { config: Config }
+"#,
+        );
+        write(
+            &dir,
+            "a.lumos",
+            "pub struct Config { x: u8 }\n\npub(crate) struct Secret { key: u64 }",
+        );
+        write(&dir, "b.lumos", "pub struct Config { y: u8 }");
+
+        let graph = ProjectGraph::load(&main).unwrap();
+        let type_defs = graph.resolve().unwrap();
+        let Some(TypeDefinition::Struct(vault)) = type_defs.iter().find(|t| t.name() == "Vault")
+        else {
+            panic!("expected Vault");
+        };
+        assert_eq!(
+            vault.fields[0].type_info,
+            TypeInfo::UserDefined("a::Config".to_string())
+        );
+
+        // A glob brings in only what the importing module can see
+        let file = graph.file(&main).unwrap();
+        let AstItem::Use(glob) = &file.ast.items[2] else {
+            panic!("expected use");
+        };
+        let names: Vec<String> = graph
+            .use_bindings(file, glob)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, ["Config"]);
+    }
+
+    #[test]
+    fn test_imported_files_are_named_by_location() {
+        let dir = TempDir::new().unwrap();
+        let main = write(
+            &dir,
+            "main.lumos",
+            r#"import { Id } from "./common/types.lumos";"#,
+        );
+        let types = write(&dir, "common/types.lumos", "type Id = u64;");
+
+        let graph = ProjectGraph::load(&main).unwrap();
+        assert_eq!(graph.file(&types).unwrap().module_path, ["common", "types"]);
+        assert_eq!(graph.dependents(&types), vec![main.as_path()]);
+        assert!(graph.dependents(&main).is_empty());
+    }
+
+    #[test]
+    fn test_package_imports() {
+        use crate::registry::{Manifest, PackageInfo, Registry};
+
+        let dir = TempDir::new().unwrap();
+        let oracle = write(
+            &dir,
+            "oracle/oracle.lumos",
+            "import { Expo } from \"./types\";\n\npub struct Price { value: u64, expo: Expo }",
+        );
+        let types = write(&dir, "oracle/types.lumos", "pub type Expo = i32;");
+        let manifest = Manifest {
+            package: Some(PackageInfo {
+                name: "@acme/oracle".to_string(),
+                version: "1.2.0".to_string(),
+                entry: None,
+            }),
+            ..Default::default()
+        };
+        Registry::new(dir.path().join("registry"))
+            .publish(
+                oracle.parent().unwrap(),
+                &manifest,
+                &oracle,
+                &[oracle.clone(), types],
+            )
+            .unwrap();
+
+        write(
+            &dir,
+            "app/lumos.toml",
+            "[registry]\npath = \"../registry\"\n\n[dependencies]\n\"@acme/oracle\" = \"^1\"\n",
+        );
+        let main = write(
+            &dir,
+            "app/main.lumos",
+            r#"import { Price } from "@acme/oracle";
+
+struct Feed { price: Price }"#,
+        );
+
+        let graph = ProjectGraph::load(&main).unwrap();
+        let type_defs = graph.resolve().unwrap();
+        assert_eq!(module_of(&type_defs, "Price"), ["acme", "oracle"]);
+        assert_eq!(module_of(&type_defs, "Expo"), ["acme", "oracle", "types"]);
+        assert!(graph.files().iter().any(|f| graph.is_package_file(&f.path)));
+        assert!(!graph.is_package_file(&main));
+
+        let lockfile = graph.packages().unwrap().lockfile();
+        assert_eq!(lockfile.packages[0].name, "@acme/oracle");
+        assert_eq!(lockfile.packages[0].version, "1.2.0");
+
+        // Without a lumos.toml, package imports have nothing to resolve against
+        let orphan = write(
+            &dir,
+            "orphan.lumos",
+            r#"import { Price } from "@acme/oracle";"#,
+        );
+        let err = ProjectGraph::load(&orphan).unwrap_err().to_string();
+        assert!(err.contains("no lumos.toml found"), "{}", err);
+    }
+
+    #[test]
+    fn test_import_validation() {
+        let dir = TempDir::new().unwrap();
+        let main = write(
+            &dir,
+            "main.lumos",
+            r#"import { Missing, Secret } from "./types.lumos";"#,
+        );
+        write(&dir, "types.lumos", "pub(crate) struct Secret { key: u64 }");
+
+        let err = ProjectGraph::load(&main)
+            .unwrap()
+            .validate()
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Type 'Missing' not found in './types.lumos'"),
+            "{}",
+            err
+        );
+        assert!(
+            err.contains("Type 'Secret' is private and cannot be imported from './types.lumos'"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_errors_name_their_file() {
+        let dir = TempDir::new().unwrap();
+        let main = write(&dir, "main.lumos", "mod models;");
+        write(
+            &dir,
+            "models.lumos",
+            "struct User {\n    wallet: Wallet,\n}",
+        );
+
+        let err = ProjectGraph::load(&main).unwrap().resolve().unwrap_err();
+        assert!(err.location().is_none());
+        assert!(err.to_string().starts_with("models.lumos:2:"), "{}", err);
+        assert!(err.to_string().contains("Wallet"), "{}", err);
+    }
+
+    #[test]
+    fn test_cache_recomputes_changed_files() {
+        use crate::cache::{BuildCache, CacheStats};
+        use crate::ir_json::IrDocument;
+
+        let dir = TempDir::new().unwrap();
+        let main = write(
+            &dir,
+            "main.lumos",
+            r#"
+import { Amount } from "./types.lumos";
+import { User } from "./user.lumos";
+
+struct Vault {
+    owner: User,
+    balance: Amount,
+}
+"#,
+        );
+        write(&dir, "types.lumos", "pub type Amount = u64;");
+        write(&dir, "user.lumos", "pub struct User { id: u64 }");
+
+        let run = || {
+            let mut cache = BuildCache::for_schema(&main);
+            let graph = ProjectGraph::load_cached(&main, &mut cache).unwrap();
+            let type_defs = graph.resolve_cached(&mut cache).unwrap();
+            (type_defs, cache.stats())
+        };
+        let stats = |ast_hits, ir_hits| CacheStats {
+            ast_hits,
+            ast_misses: 3 - ast_hits,
+            ir_hits,
+            ir_misses: 3 - ir_hits,
+        };
+        let json = |type_defs: Vec<TypeDefinition>| IrDocument::new(type_defs).to_json().unwrap();
+
+        let (first, first_stats) = run();
+        assert_eq!(first_stats, stats(0, 0));
+        let (second, second_stats) = run();
+        assert_eq!(second_stats, stats(3, 3));
+        let fresh = ProjectGraph::load(&main).unwrap().resolve().unwrap();
+        assert_eq!(json(second), json(fresh.clone()));
+        assert_eq!(json(first), json(fresh));
+
+        // A changed file is recomputed along with the files that import it
+        write(
+            &dir,
+            "user.lumos",
+            "pub struct User { id: u64, name: String }",
+        );
+        let (_, changed_stats) = run();
+        assert_eq!(changed_stats, stats(2, 1));
+
+        // Every file is transformed against the project's aliases
+        write(&dir, "types.lumos", "pub type Amount = u128;");
+        let (type_defs, alias_stats) = run();
+        assert_eq!(alias_stats, stats(2, 0));
+        let Some(TypeDefinition::Struct(vault)) = type_defs.iter().find(|t| t.name() == "Vault")
+        else {
+            panic!("expected Vault");
+        };
+        assert_eq!(
+            vault.fields[1].type_info,
+            TypeInfo::Primitive("u128".to_string())
+        );
+    }
+
+    #[test]
+    fn test_cached_errors_keep_their_location() {
+        use crate::cache::BuildCache;
+
+        let dir = TempDir::new().unwrap();
+        let main = write(&dir, "main.lumos", "mod models;");
+        write(
+            &dir,
+            "models.lumos",
+            "struct User {\n    wallet: Wallet,\n}",
+        );
+
+        // The second run reads both ASTs, without spans, from the cache
+        for _ in 0..2 {
+            let mut cache = BuildCache::for_schema(&main);
+            let err = ProjectGraph::load_cached(&main, &mut cache)
+                .unwrap()
+                .resolve_cached(&mut cache)
+                .unwrap_err();
+            assert!(err.to_string().starts_with("models.lumos:2:"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_circular_import() {
+        let dir = TempDir::new().unwrap();
+        let a = write(
+            &dir,
+            "a.lumos",
+            r#"import { B } from "./b.lumos"; struct A { b: B }"#,
+        );
+        write(
+            &dir,
+            "b.lumos",
+            r#"import { A } from "./a.lumos"; struct B { x: u64 }"#,
+        );
+
+        let err = ProjectGraph::load(&a).unwrap_err().to_string();
+        assert!(err.contains("Circular import detected"), "{}", err);
+    }
+
+    #[test]
+    fn test_sources_replace_files_on_disk() {
+        let dir = TempDir::new().unwrap();
+        let main = write(
+            &dir,
+            "main.lumos",
+            r#"import { Amount } from "./types.lumos";"#,
+        );
+        let types = write(&dir, "types.lumos", "type Amount = u64;");
+
+        let sources = HashMap::from([(types, "type Total = u64;".to_string())]);
+        let err = ProjectGraph::load_with_sources(&main, &sources)
+            .unwrap()
+            .validate()
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("Type 'Amount' not found"),
+            "{}",
+            err
+        );
+    }
+}
@@ -0,0 +1,874 @@
+// Licensed under either of Apache License, Version 2.0 or MIT license at your option.
+// Copyright 2025 RECTOR-LABS
+
+//! Local package registry for sharing schemas between programs
+//!
+//! A schema can import from a package instead of a file:
+//!
+//! ```text
+//! import { Price } from "@acme/oracle";
+//! ```
+//!
+//! Packages live in a registry directory named in the project's `lumos.toml`
+//! (next to the schema or in a parent directory), which also lists the
+//! version ranges the project depends on:
+//!
+//! ```toml
+//! [registry]
+//! path = "../schema-registry"   # relative to lumos.toml
+//!
+//! [dependencies]
+//! "@acme/oracle" = "^1.2"
+//! ```
+//!
+//! Each published version is a directory holding the package's schema files
+//! and a `lumos-package.toml` with its name, version, entry file and own
+//! dependencies:
+//!
+//! ```text
+//! schema-registry/
+//! └── @acme/
+//!     └── oracle/
+//!         ├── 1.2.0/
+//!         └── 1.3.1/
+//!             ├── lumos-package.toml
+//!             └── oracle.lumos
+//! ```
+//!
+//! An import resolves to the highest version matching its semver range. The
+//! versions picked are written to `lumos.lock` with a hash of each package's
+//! files; while a locked version still matches, it is used again, and a
+//! package whose files changed since it was locked is an error. Every
+//! package is used in one version across the project.
+//!
+//! `lumos publish` copies a project into the registry as the version in the
+//! `[package]` section of its `lumos.toml`. Published versions are never
+//! overwritten.
+
+use crate::error::{LumosError, Result};
+use semver::{Version, VersionReq};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Project configuration file
+pub const MANIFEST_FILE: &str = "lumos.toml";
+
+/// Versions and hashes of the packages a project resolved
+pub const LOCKFILE: &str = "lumos.lock";
+
+/// Manifest of a published package version
+pub const PACKAGE_MANIFEST: &str = "lumos-package.toml";
+
+/// Layout version of `lumos.lock`
+pub const LOCKFILE_VERSION: u32 = 1;
+
+/// Whether an import path names a registry package (`@acme/oracle`) rather
+/// than a file
+pub fn is_package_import(path: &str) -> bool {
+    path.starts_with('@')
+}
+
+/// Module path of a package's entry file (`@acme/oracle-feeds` is
+/// `["acme", "oracle_feeds"]`)
+pub fn package_module_path(name: &str) -> Vec<String> {
+    name.trim_start_matches('@')
+        .split('/')
+        .map(|segment| segment.replace(['-', '.'], "_"))
+        .collect()
+}
+
+/// Contents of a `lumos.toml` (or of a published `lumos-package.toml`)
+///
+/// Sections used by other tools, such as `[output]`, are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Name and version to publish the project as
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<PackageInfo>,
+
+    /// Where packages are published and looked up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<RegistryConfig>,
+
+    /// Package name -> semver range (`"^1.2"`)
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, String>,
+}
+
+/// `[package]` section of a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageInfo {
+    /// Scoped package name (`@acme/oracle`)
+    pub name: String,
+
+    /// Semver version (`1.2.0`)
+    pub version: String,
+
+    /// Entry schema, relative to the manifest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<String>,
+}
+
+/// `[registry]` section of a manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegistryConfig {
+    /// Registry directory, relative to the manifest
+    pub path: PathBuf,
+}
+
+impl Manifest {
+    /// Read a manifest file
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            LumosError::SchemaParse(format!("Failed to read '{}': {}", path.display(), e), None)
+        })?;
+        toml::from_str(&content).map_err(|e| {
+            LumosError::SchemaParse(format!("Invalid '{}': {}", path.display(), e), None)
+        })
+    }
+
+    /// Find the `lumos.toml` in `dir` or the closest parent directory
+    ///
+    /// Returns the manifest's path along with it.
+    pub fn find(dir: &Path) -> Result<Option<(PathBuf, Self)>> {
+        for dir in dir.ancestors() {
+            let path = dir.join(MANIFEST_FILE);
+            if path.is_file() {
+                let manifest = Self::load(&path)?;
+                return Ok(Some((path, manifest)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The registry this manifest configures, relative to `manifest_dir`
+    pub fn registry(&self, manifest_dir: &Path) -> Option<Registry> {
+        self.registry
+            .as_ref()
+            .map(|config| Registry::new(manifest_dir.join(&config.path)))
+    }
+
+    /// The semver range of the dependency on `name`
+    fn dependency(&self, name: &str) -> Option<Result<VersionReq>> {
+        self.dependencies.get(name).map(|range| {
+            VersionReq::parse(range).map_err(|e| {
+                LumosError::SchemaParse(
+                    format!("Invalid version range '{}' for '{}': {}", range, name, e),
+                    None,
+                )
+            })
+        })
+    }
+}
+
+/// A published package version found in the registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolvedPackage {
+    /// Scoped package name (`@acme/oracle`)
+    pub name: String,
+
+    /// Version used
+    pub version: Version,
+
+    /// Directory of the version in the registry (canonical)
+    pub dir: PathBuf,
+
+    /// Entry schema of the package (canonical)
+    pub entry: PathBuf,
+
+    /// Hash of the package's files (`sha256:...`)
+    pub hash: String,
+
+    /// The package's own dependencies (name -> semver range)
+    pub dependencies: BTreeMap<String, String>,
+}
+
+/// A directory of published packages
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Registry {
+    root: PathBuf,
+}
+
+impl Registry {
+    /// A registry rooted at `root`
+    pub fn new(root: PathBuf) -> Self {
+        Registry { root }
+    }
+
+    /// The registry directory
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Directory of a published version (`<root>/@acme/oracle/1.2.0`)
+    pub fn package_dir(&self, name: &str, version: &Version) -> PathBuf {
+        self.root.join(name).join(version.to_string())
+    }
+
+    /// Published versions of a package, lowest first
+    pub fn versions(&self, name: &str) -> Result<Vec<Version>> {
+        let dir = self.root.join(name);
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Ok(Vec::new());
+        };
+
+        let mut versions: Vec<Version> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join(PACKAGE_MANIFEST).is_file())
+            .filter_map(|entry| Version::parse(&entry.file_name().to_string_lossy()).ok())
+            .collect();
+        versions.sort();
+        Ok(versions)
+    }
+
+    /// Read a published version
+    pub fn package(&self, name: &str, version: &Version) -> Result<ResolvedPackage> {
+        let dir = canonicalize(&self.package_dir(name, version))?;
+        let manifest = Manifest::load(&dir.join(PACKAGE_MANIFEST))?;
+        let entry = manifest
+            .package
+            .as_ref()
+            .and_then(|p| p.entry.as_deref())
+            .ok_or_else(|| {
+                LumosError::SchemaParse(
+                    format!(
+                        "Package '{}' {} has no entry in its {}",
+                        name, version, PACKAGE_MANIFEST
+                    ),
+                    None,
+                )
+            })?;
+
+        Ok(ResolvedPackage {
+            name: name.to_string(),
+            version: version.clone(),
+            entry: canonicalize(&dir.join(entry))?,
+            hash: hash_dir(&dir)?,
+            dependencies: manifest.dependencies,
+            dir,
+        })
+    }
+
+    /// Copy a project into the registry as the version in its `[package]`
+    ///
+    /// `files` are the project's schema files, all inside `manifest_dir`,
+    /// and `entry` is the one a package import resolves to. The package's
+    /// `[dependencies]` are published with it.
+    pub fn publish(
+        &self,
+        manifest_dir: &Path,
+        manifest: &Manifest,
+        entry: &Path,
+        files: &[PathBuf],
+    ) -> Result<ResolvedPackage> {
+        let info = manifest.package.as_ref().ok_or_else(|| {
+            LumosError::SchemaParse(
+                format!(
+                    "{} has no [package] section with the name and version to publish",
+                    MANIFEST_FILE
+                ),
+                None,
+            )
+        })?;
+        validate_package_name(&info.name)?;
+        let version = Version::parse(&info.version).map_err(|e| {
+            LumosError::SchemaParse(
+                format!("Invalid package version '{}': {}", info.version, e),
+                None,
+            )
+        })?;
+
+        let dir = self.package_dir(&info.name, &version);
+        if dir.exists() {
+            return Err(LumosError::SchemaParse(
+                format!(
+                    "Package '{}' {} is already published; bump the version in {}",
+                    info.name, version, MANIFEST_FILE
+                ),
+                None,
+            ));
+        }
+
+        let relative = |path: &Path| {
+            path.strip_prefix(manifest_dir)
+                .map(Path::to_path_buf)
+                .map_err(|_| {
+                    LumosError::SchemaParse(
+                        format!(
+                            "Cannot publish '{}': it is outside the package directory '{}'",
+                            path.display(),
+                            manifest_dir.display()
+                        ),
+                        None,
+                    )
+                })
+        };
+        let entry = relative(entry)?;
+        let files = files
+            .iter()
+            .map(|file| Ok((file, relative(file)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Stage next to the final directory, so a failed copy leaves no version behind
+        let staging = dir.with_file_name(format!(".{}.partial", version));
+        if staging.exists() {
+            fs::remove_dir_all(&staging)?;
+        }
+        let published = (|| {
+            for (file, relative) in &files {
+                let target = staging.join(relative);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(file, target)?;
+            }
+
+            let package_manifest = Manifest {
+                package: Some(PackageInfo {
+                    name: info.name.clone(),
+                    version: version.to_string(),
+                    entry: Some(entry.to_string_lossy().replace('\\', "/")),
+                }),
+                registry: None,
+                dependencies: manifest.dependencies.clone(),
+            };
+            let content = toml::to_string(&package_manifest).map_err(|e| {
+                LumosError::SchemaParse(format!("Failed to write package manifest: {}", e), None)
+            })?;
+            fs::write(staging.join(PACKAGE_MANIFEST), content)?;
+            fs::rename(&staging, &dir)?;
+            Ok(())
+        })();
+        if let Err(error) = published {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(error);
+        }
+
+        self.package(&info.name, &version)
+    }
+}
+
+/// `lumos.lock`: the package versions a project resolved
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lockfile {
+    /// Layout version (see [`LOCKFILE_VERSION`])
+    pub version: u32,
+
+    /// Locked packages, sorted by name
+    #[serde(default, rename = "package")]
+    pub packages: Vec<LockedPackage>,
+}
+
+/// A package version recorded in `lumos.lock`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPackage {
+    /// Scoped package name
+    pub name: String,
+
+    /// Version used
+    pub version: String,
+
+    /// Hash of the package's files (`sha256:...`)
+    pub hash: String,
+}
+
+impl Lockfile {
+    /// Read a lockfile, `None` if there is none
+    pub fn load(path: &Path) -> Result<Option<Self>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)?;
+        let lockfile: Lockfile = toml::from_str(&content).map_err(|e| {
+            LumosError::SchemaParse(format!("Invalid '{}': {}", path.display(), e), None)
+        })?;
+        if lockfile.version != LOCKFILE_VERSION {
+            return Err(LumosError::SchemaParse(
+                format!(
+                    "Unsupported {} version {} (expected {})",
+                    LOCKFILE, lockfile.version, LOCKFILE_VERSION
+                ),
+                None,
+            ));
+        }
+        Ok(Some(lockfile))
+    }
+
+    /// The lockfile's contents
+    pub fn to_toml(&self) -> String {
+        let body = toml::to_string(self).expect("lockfile serializes to TOML");
+        format!(
+            "# Auto-generated by LUMOS\n# DO NOT EDIT - Changes will be overwritten\n\n{}",
+            body
+        )
+    }
+
+    fn get(&self, name: &str) -> Option<&LockedPackage> {
+        self.packages.iter().find(|p| p.name == name)
+    }
+}
+
+/// Resolves package imports of a project against its registry
+///
+/// Built from the project's `lumos.toml`; a project without one can only
+/// import files.
+#[derive(Debug, Clone)]
+pub struct PackageResolver {
+    manifest_path: PathBuf,
+    manifest: Manifest,
+    registry: Option<Registry>,
+    locked: Option<Lockfile>,
+    resolved: BTreeMap<String, ResolvedPackage>,
+}
+
+impl PackageResolver {
+    /// The resolver of the project whose `lumos.toml` is in `dir` or a parent
+    pub fn discover(dir: &Path) -> Result<Option<Self>> {
+        Manifest::find(dir)?
+            .map(|(path, manifest)| Self::new(path, manifest))
+            .transpose()
+    }
+
+    /// A resolver for the project of `manifest`, reading its `lumos.lock`
+    pub fn new(manifest_path: PathBuf, manifest: Manifest) -> Result<Self> {
+        let dir = parent(&manifest_path);
+        Ok(PackageResolver {
+            registry: manifest.registry(dir),
+            locked: Lockfile::load(&dir.join(LOCKFILE))?,
+            manifest_path,
+            manifest,
+            resolved: BTreeMap::new(),
+        })
+    }
+
+    /// Path of the project's `lumos.toml`
+    pub fn manifest_path(&self) -> &Path {
+        &self.manifest_path
+    }
+
+    /// The project's manifest
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Path of the project's `lumos.lock`
+    pub fn lockfile_path(&self) -> PathBuf {
+        parent(&self.manifest_path).join(LOCKFILE)
+    }
+
+    /// The lockfile read when the resolver was created
+    pub fn locked(&self) -> Option<&Lockfile> {
+        self.locked.as_ref()
+    }
+
+    /// Lockfile recording every package resolved so far
+    pub fn lockfile(&self) -> Lockfile {
+        Lockfile {
+            version: LOCKFILE_VERSION,
+            packages: self
+                .resolved
+                .values()
+                .map(|package| LockedPackage {
+                    name: package.name.clone(),
+                    version: package.version.to_string(),
+                    hash: package.hash.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Packages resolved so far, by name
+    pub fn packages(&self) -> impl Iterator<Item = &ResolvedPackage> {
+        self.resolved.values()
+    }
+
+    /// The package whose files include `path`, if any
+    pub fn package_of(&self, path: &Path) -> Option<&ResolvedPackage> {
+        self.resolved.values().find(|p| path.starts_with(&p.dir))
+    }
+
+    /// Resolve `import .. from "<name>"` in `importer`
+    ///
+    /// The version range comes from the dependencies of the package
+    /// `importer` belongs to, or of the project. A package resolved before
+    /// must match the range too, since each package is used in one version.
+    pub fn resolve(&mut self, importer: &Path, name: &str) -> Result<&ResolvedPackage> {
+        let (range, required_by) = match self.package_of(importer) {
+            Some(package) => {
+                let required_by = format!("{} {}", package.name, package.version);
+                let range = package.dependencies.get(name).map(|range| {
+                    VersionReq::parse(range).map_err(|e| {
+                        LumosError::SchemaParse(
+                            format!(
+                                "Invalid version range '{}' for '{}' in {}: {}",
+                                range, name, required_by, e
+                            ),
+                            None,
+                        )
+                    })
+                });
+                (range, required_by)
+            }
+            None => (
+                self.manifest.dependency(name),
+                self.manifest_path.display().to_string(),
+            ),
+        };
+        let range = range.ok_or_else(|| {
+            LumosError::SchemaParse(
+                format!(
+                    "Package '{}' is not listed in the [dependencies] of {}",
+                    name, required_by
+                ),
+                None,
+            )
+        })??;
+
+        if let Some(package) = self.resolved.get(name) {
+            if !range.matches(&package.version) {
+                return Err(LumosError::SchemaParse(
+                    format!(
+                        "{} requires '{}' {}, but {} is already in use",
+                        required_by, name, range, package.version
+                    ),
+                    None,
+                ));
+            }
+            return Ok(&self.resolved[name]);
+        }
+
+        let registry = self.registry.as_ref().ok_or_else(|| {
+            LumosError::SchemaParse(
+                format!(
+                    "Cannot import package '{}': {} has no [registry] path",
+                    name,
+                    self.manifest_path.display()
+                ),
+                None,
+            )
+        })?;
+
+        // A locked version is used again while it still matches
+        let locked = self
+            .locked
+            .as_ref()
+            .and_then(|lock| lock.get(name))
+            .and_then(|locked| Some((Version::parse(&locked.version).ok()?, locked)))
+            .filter(|(version, _)| range.matches(version));
+        let package = match locked {
+            Some((version, locked)) => {
+                let package = registry.package(name, &version).map_err(|_| {
+                    LumosError::SchemaParse(
+                        format!(
+                            "Package '{}' {} from {} is not in the registry '{}'",
+                            name,
+                            version,
+                            LOCKFILE,
+                            registry.root().display()
+                        ),
+                        None,
+                    )
+                })?;
+                if package.hash != locked.hash {
+                    return Err(LumosError::SchemaParse(
+                        format!(
+                            "Package '{}' {} has changed since it was locked: {} in {}, {} in the registry",
+                            name, version, locked.hash, LOCKFILE, package.hash
+                        ),
+                        None,
+                    ));
+                }
+                package
+            }
+            None => {
+                let version = registry
+                    .versions(name)?
+                    .into_iter()
+                    .rev()
+                    .find(|v| range.matches(v))
+                    .ok_or_else(|| {
+                        LumosError::SchemaParse(
+                            format!(
+                                "No version of package '{}' matching '{}' in the registry '{}'",
+                                name,
+                                range,
+                                registry.root().display()
+                            ),
+                            None,
+                        )
+                    })?;
+                registry.package(name, &version)?
+            }
+        };
+
+        Ok(self.resolved.entry(name.to_string()).or_insert(package))
+    }
+}
+
+/// Hash of every file in a directory, by relative path (`sha256:...`)
+pub fn hash_dir(dir: &Path) -> Result<String> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for relative in files {
+        let path = relative.to_string_lossy().replace('\\', "/");
+        let content = fs::read(dir.join(&relative))?;
+        hasher.update((path.len() as u64).to_le_bytes());
+        hasher.update(path.as_bytes());
+        hasher.update((content.len() as u64).to_le_bytes());
+        hasher.update(&content);
+    }
+
+    let hex: String = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    Ok(format!("sha256:{}", hex))
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            files.push(relative.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+/// Package names are scoped (`@acme/oracle`), lowercase, with `-`, `_` and `.`
+fn validate_package_name(name: &str) -> Result<()> {
+    let valid_part = |part: &str| {
+        !part.is_empty()
+            && part.chars().all(|c| {
+                c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_' | '.')
+            })
+            && !part.starts_with('.')
+    };
+    let valid = name
+        .strip_prefix('@')
+        .and_then(|rest| rest.split_once('/'))
+        .is_some_and(|(scope, package)| valid_part(scope) && valid_part(package));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(LumosError::SchemaParse(
+            format!(
+                "Invalid package name '{}': expected '@scope/name' in lowercase letters, digits, '-', '_' and '.'",
+                name
+            ),
+            None,
+        ))
+    }
+}
+
+fn canonicalize(path: &Path) -> Result<PathBuf> {
+    path.canonicalize().map_err(|e| {
+        LumosError::SchemaParse(
+            format!("Failed to resolve path '{}': {}", path.display(), e),
+            None,
+        )
+    })
+}
+
+fn parent(path: &Path) -> &Path {
+    path.parent().unwrap_or(Path::new("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path.canonicalize().unwrap()
+    }
+
+    /// Publish `schema` as `version` of `@acme/oracle` into `registry`
+    fn publish(registry: &Registry, version: &str, schema: &str) -> ResolvedPackage {
+        let dir = TempDir::new().unwrap();
+        let entry = write(dir.path(), "oracle.lumos", schema);
+        let manifest = Manifest {
+            package: Some(PackageInfo {
+                name: "@acme/oracle".to_string(),
+                version: version.to_string(),
+                entry: None,
+            }),
+            ..Default::default()
+        };
+        let manifest_dir = dir.path().canonicalize().unwrap();
+        registry
+            .publish(
+                &manifest_dir,
+                &manifest,
+                &entry,
+                std::slice::from_ref(&entry),
+            )
+            .unwrap()
+    }
+
+    fn project_resolver(dir: &Path, range: &str) -> PackageResolver {
+        let manifest = write(
+            dir,
+            MANIFEST_FILE,
+            &format!(
+                "[registry]\npath = \"registry\"\n\n[dependencies]\n\"@acme/oracle\" = \"{}\"\n",
+                range
+            ),
+        );
+        PackageResolver::discover(parent(&manifest))
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_publish_and_resolve_highest_match() {
+        let dir = TempDir::new().unwrap();
+        let registry = Registry::new(dir.path().join("registry"));
+        publish(&registry, "1.2.0", "pub struct Price { value: u64 }");
+        publish(
+            &registry,
+            "1.4.1",
+            "pub struct Price { value: u64, expo: i32 }",
+        );
+        publish(&registry, "2.0.0", "pub struct Price { value: u128 }");
+
+        let versions = registry.versions("@acme/oracle").unwrap();
+        assert_eq!(versions.len(), 3);
+
+        let importer = write(dir.path(), "main.lumos", "");
+        let mut resolver = project_resolver(dir.path(), "^1.2");
+        let package = resolver.resolve(&importer, "@acme/oracle").unwrap();
+        assert_eq!(package.version, Version::new(1, 4, 1));
+        assert!(package.entry.ends_with("oracle.lumos"));
+        assert!(package.hash.starts_with("sha256:"));
+
+        let lockfile = resolver.lockfile();
+        assert_eq!(lockfile.packages[0].version, "1.4.1");
+        assert!(lockfile
+            .to_toml()
+            .contains("[[package]]\nname = \"@acme/oracle\""));
+    }
+
+    #[test]
+    fn test_locked_version_is_reused_and_verified() {
+        let dir = TempDir::new().unwrap();
+        let registry = Registry::new(dir.path().join("registry"));
+        let old = publish(&registry, "1.2.0", "pub struct Price { value: u64 }");
+        publish(
+            &registry,
+            "1.3.0",
+            "pub struct Price { value: u64, expo: i32 }",
+        );
+
+        let lockfile = Lockfile {
+            version: LOCKFILE_VERSION,
+            packages: vec![LockedPackage {
+                name: "@acme/oracle".to_string(),
+                version: "1.2.0".to_string(),
+                hash: old.hash.clone(),
+            }],
+        };
+        fs::write(dir.path().join(LOCKFILE), lockfile.to_toml()).unwrap();
+
+        let importer = write(dir.path(), "main.lumos", "");
+        let mut resolver = project_resolver(dir.path(), "^1.2");
+        assert_eq!(resolver.locked(), Some(&lockfile));
+        let package = resolver.resolve(&importer, "@acme/oracle").unwrap();
+        assert_eq!(package.version, Version::new(1, 2, 0));
+        assert_eq!(resolver.lockfile(), lockfile);
+
+        // Changing a published version breaks the lock
+        fs::write(old.entry, "pub struct Price { value: i64 }").unwrap();
+        let err = project_resolver(dir.path(), "^1.2")
+            .resolve(&importer, "@acme/oracle")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("has changed since it was locked"), "{}", err);
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        let dir = TempDir::new().unwrap();
+        let registry = Registry::new(dir.path().join("registry"));
+        publish(&registry, "1.2.0", "pub struct Price { value: u64 }");
+        let importer = write(dir.path(), "main.lumos", "");
+
+        let err = project_resolver(dir.path(), "^2")
+            .resolve(&importer, "@acme/oracle")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("No version of package '@acme/oracle' matching '^2'"),
+            "{}",
+            err
+        );
+
+        let err = project_resolver(dir.path(), "^1")
+            .resolve(&importer, "@acme/fees")
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Package '@acme/fees' is not listed in the [dependencies]"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_publish_errors() {
+        let dir = TempDir::new().unwrap();
+        let registry = Registry::new(dir.path().join("registry"));
+        publish(&registry, "1.0.0", "pub struct Price { value: u64 }");
+
+        let project = dir.path().join("project");
+        let entry = write(&project, "oracle.lumos", "pub struct Price { value: u64 }");
+        let mut manifest = Manifest {
+            package: Some(PackageInfo {
+                name: "@acme/oracle".to_string(),
+                version: "1.0.0".to_string(),
+                entry: None,
+            }),
+            ..Default::default()
+        };
+        let project = project.canonicalize().unwrap();
+        let err = registry
+            .publish(&project, &manifest, &entry, std::slice::from_ref(&entry))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("is already published"), "{}", err);
+
+        manifest.package.as_mut().unwrap().name = "oracle".to_string();
+        let err = registry
+            .publish(&project, &manifest, &entry, std::slice::from_ref(&entry))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Invalid package name 'oracle'"), "{}", err);
+    }
+
+    #[test]
+    fn test_manifest_ignores_other_sections() {
+        let manifest: Manifest = toml::from_str(
+            "[output]\ndirectory = \".\"\n\n[dependencies]\n\"@acme/oracle\" = \"^1\"\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.dependencies["@acme/oracle"], "^1");
+        assert!(manifest.registry.is_none());
+        assert_eq!(
+            package_module_path("@acme/oracle-feeds"),
+            ["acme", "oracle_feeds"]
+        );
+    }
+}
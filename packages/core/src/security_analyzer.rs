@@ -115,8 +115,8 @@ impl<'a> SecurityAnalyzer<'a> {
                     // Enums have fewer security concerns
                     // Future: Could check for sensitive data in variants
                 }
-                TypeDefinition::TypeAlias(_) => {
-                    // Type aliases don't introduce security concerns (they're just name mappings)
+                TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {
+                    // Type aliases and constants don't introduce security concerns
                 }
             }
         }
@@ -349,14 +349,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "authority".to_string(),
                 type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let analyzer = SecurityAnalyzer::new(&type_defs);
@@ -376,14 +375,13 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "balance".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let analyzer = SecurityAnalyzer::new(&type_defs);
@@ -403,13 +401,12 @@ mod tests {
             metadata: Metadata {
                 solana: true,
                 attributes: vec![], // Missing #[account]
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let analyzer = SecurityAnalyzer::new(&type_defs);
@@ -428,21 +425,17 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "owner".to_string(),
                 type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         // Normal mode
@@ -465,30 +458,23 @@ mod tests {
                 FieldDefinition {
                     name: "id".to_string(),
                     type_info: TypeInfo::Primitive("u32".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "name".to_string(),
                     type_info: TypeInfo::Primitive("String".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let analyzer = SecurityAnalyzer::new(&type_defs);
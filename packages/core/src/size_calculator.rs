@@ -7,8 +7,11 @@
 //! Borsh serialization format.
 
 use crate::ir::{
-    EnumDefinition, EnumVariantDefinition, StructDefinition, TypeDefinition, TypeInfo,
+    CLayout, EnumDefinition, EnumVariantDefinition, FieldDefinition, StructDefinition,
+    TypeDefinition, TypeInfo,
 };
+use crate::transform::instantiate_generic;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 /// Result of size calculation for an account
@@ -39,8 +42,12 @@ pub enum SizeInfo {
     /// Fixed size in bytes
     Fixed(usize),
 
-    /// Variable size with minimum bytes
-    Variable { min: usize, reason: String },
+    /// Variable size with minimum bytes, and maximum bytes when bounded by `#[max(N)]`
+    Variable {
+        min: usize,
+        max: Option<usize>,
+        reason: String,
+    },
 }
 
 /// Field size breakdown
@@ -63,6 +70,16 @@ pub struct SizeCalculator<'a> {
 
     /// Cache of calculated sizes for user-defined types
     size_cache: HashMap<String, SizeInfo>,
+
+    /// User-defined types currently being sized, outermost first
+    in_progress: Vec<String>,
+
+    /// Remaining nesting allowed by a `#[max(N)]` on a `Box` field, if any
+    depth_budget: Option<usize>,
+
+    /// Number of recursive references met so far; sizes computed while this
+    /// changes depend on where the cycle was entered and aren't cached
+    recursion_hits: usize,
 }
 
 impl<'a> SizeCalculator<'a> {
@@ -71,6 +88,9 @@ impl<'a> SizeCalculator<'a> {
         Self {
             type_defs,
             size_cache: HashMap::new(),
+            in_progress: Vec::new(),
+            depth_budget: None,
+            recursion_hits: 0,
         }
     }
 
@@ -82,14 +102,17 @@ impl<'a> SizeCalculator<'a> {
                 TypeDefinition::Struct(s) => Some(self.calculate_struct_size(s)),
                 TypeDefinition::Enum(e) => Some(self.calculate_enum_size(e)),
                 TypeDefinition::TypeAlias(_) => None, // Type aliases don't have sizes (they're resolved)
+                TypeDefinition::Const(_) => None,     // Constants aren't serialized
             })
             .collect()
     }
 
     /// Calculate size for a struct
     fn calculate_struct_size(&mut self, struct_def: &StructDefinition) -> AccountSize {
+        self.in_progress.push(struct_def.name.clone());
         let mut field_breakdown = Vec::new();
         let mut total_size = 0;
+        let mut total_max = Some(0);
         let mut is_variable = false;
         let mut variable_reason = String::new();
         let mut warnings = Vec::new();
@@ -110,14 +133,15 @@ impl<'a> SizeCalculator<'a> {
 
         // Calculate size for each field
         for field in &struct_def.fields {
-            let size = self.calculate_type_size(&field.type_info);
+            let size = self.calculate_field_size(field);
             let description = self.describe_type(&field.type_info);
 
+            total_max = total_max.zip(size.max_bytes()).map(|(a, b)| a + b);
             match &size {
                 SizeInfo::Fixed(bytes) => {
                     total_size += bytes;
                 }
-                SizeInfo::Variable { min, reason } => {
+                SizeInfo::Variable { min, reason, .. } => {
                     total_size += min;
                     is_variable = true;
                     if !variable_reason.is_empty() {
@@ -158,11 +182,13 @@ impl<'a> SizeCalculator<'a> {
         let total_bytes = if is_variable {
             SizeInfo::Variable {
                 min: total_size,
+                max: total_max.map(|max| max + if is_account { 8 } else { 0 }),
                 reason: variable_reason,
             }
         } else {
             SizeInfo::Fixed(total_size)
         };
+        self.in_progress.pop();
 
         AccountSize {
             name: struct_def.name.clone(),
@@ -176,63 +202,93 @@ impl<'a> SizeCalculator<'a> {
 
     /// Calculate size for an enum
     fn calculate_enum_size(&mut self, enum_def: &EnumDefinition) -> AccountSize {
+        self.in_progress.push(enum_def.name.clone());
         let mut field_breakdown = Vec::new();
         let mut max_variant_size = 0;
+        let mut min_variant_size = None;
+        let mut max_variant_bytes = Some(0);
+        let mut variable_reasons = Vec::new();
         let mut warnings = Vec::new();
 
-        // Borsh enum discriminant (1 byte for < 256 variants, 4 bytes for >= 256)
-        let discriminant_size = if enum_def.variants.len() < 256 { 1 } else { 4 };
+        // Borsh enum discriminant (u8 unless widened with #[repr])
+        let discriminant_size = enum_def.tag_size();
 
-        field_breakdown.push(FieldSize {
-            name: "discriminant".to_string(),
-            size: SizeInfo::Fixed(discriminant_size),
-            description: "Enum variant discriminant".to_string(),
-        });
+        // Flags are only the packed integer; their variants are bits, not payloads
+        let variants = if enum_def.is_flags() {
+            field_breakdown.push(FieldSize {
+                name: "bits".to_string(),
+                size: SizeInfo::Fixed(discriminant_size),
+                description: format!("Packed flags ({})", enum_def.repr().name()),
+            });
+            &[][..]
+        } else {
+            field_breakdown.push(FieldSize {
+                name: "discriminant".to_string(),
+                size: SizeInfo::Fixed(discriminant_size),
+                description: format!("Enum variant discriminant ({})", enum_def.repr().name()),
+            });
+            &enum_def.variants[..]
+        };
 
         // Calculate size for each variant
-        for variant in &enum_def.variants {
-            let variant_size = match variant {
-                EnumVariantDefinition::Unit { name } => {
+        for variant in variants {
+            let payload: Vec<(String, SizeInfo, String)> = match variant {
+                EnumVariantDefinition::Unit { name, .. } => {
                     field_breakdown.push(FieldSize {
                         name: format!("  └─ {}", name),
                         size: SizeInfo::Fixed(0),
                         description: "Unit variant (no data)".to_string(),
                     });
-                    0
-                }
-                EnumVariantDefinition::Tuple { name, types } => {
-                    let mut tuple_size = 0;
-                    for (i, type_info) in types.iter().enumerate() {
-                        let size = self.calculate_type_size(type_info);
-                        if let SizeInfo::Fixed(bytes) = size {
-                            tuple_size += bytes;
-                        }
-                        field_breakdown.push(FieldSize {
-                            name: format!("  └─ {}.{}", name, i),
-                            size,
-                            description: self.describe_type(type_info),
-                        });
-                    }
-                    tuple_size
+                    Vec::new()
                 }
-                EnumVariantDefinition::Struct { name, fields } => {
-                    let mut struct_size = 0;
-                    for field in fields {
-                        let size = self.calculate_type_size(&field.type_info);
-                        if let SizeInfo::Fixed(bytes) = size {
-                            struct_size += bytes;
-                        }
-                        field_breakdown.push(FieldSize {
-                            name: format!("  └─ {}.{}", name, field.name),
-                            size,
-                            description: self.describe_type(&field.type_info),
-                        });
+                EnumVariantDefinition::Tuple { name, types, .. } => types
+                    .iter()
+                    .enumerate()
+                    .map(|(i, type_info)| {
+                        (
+                            format!("{}.{}", name, i),
+                            self.calculate_type_size(type_info),
+                            self.describe_type(type_info),
+                        )
+                    })
+                    .collect(),
+                EnumVariantDefinition::Struct { name, fields, .. } => fields
+                    .iter()
+                    .map(|field| {
+                        (
+                            format!("{}.{}", name, field.name),
+                            self.calculate_field_size(field),
+                            self.describe_type(&field.type_info),
+                        )
+                    })
+                    .collect(),
+            };
+
+            let mut variant_size = 0;
+            let mut variant_min = 0;
+            let mut variant_max = Some(0);
+            for (name, size, description) in payload {
+                variant_min += size.min_bytes();
+                variant_max = variant_max.zip(size.max_bytes()).map(|(a, b)| a + b);
+                match &size {
+                    SizeInfo::Fixed(bytes) => variant_size += bytes,
+                    SizeInfo::Variable { reason, .. } => {
+                        variable_reasons.push(format!("{}: {}", name, reason))
                     }
-                    struct_size
                 }
-            };
+                field_breakdown.push(FieldSize {
+                    name: format!("  └─ {}", name),
+                    size,
+                    description,
+                });
+            }
 
             max_variant_size = max_variant_size.max(variant_size);
+            min_variant_size =
+                Some(min_variant_size.map_or(variant_min, |m: usize| m.min(variant_min)));
+            max_variant_bytes = max_variant_bytes
+                .zip(variant_max)
+                .map(|(a, b): (usize, usize)| a.max(b));
         }
 
         let total_size = discriminant_size + max_variant_size;
@@ -249,9 +305,21 @@ impl<'a> SizeCalculator<'a> {
             ));
         }
 
+        // Variable-sized payloads (e.g. recursive variants) make the whole enum variable
+        let total_bytes = if variable_reasons.is_empty() {
+            SizeInfo::Fixed(total_size)
+        } else {
+            SizeInfo::Variable {
+                min: discriminant_size + min_variant_size.unwrap_or(0),
+                max: max_variant_bytes.map(|max| discriminant_size + max),
+                reason: variable_reasons.join(", "),
+            }
+        };
+        self.in_progress.pop();
+
         AccountSize {
             name: enum_def.name.clone(),
-            total_bytes: SizeInfo::Fixed(total_size),
+            total_bytes,
             field_breakdown,
             is_account: false,
             rent_sol,
@@ -259,6 +327,152 @@ impl<'a> SizeCalculator<'a> {
         }
     }
 
+    /// Calculate size for a field, applying its `#[max(N)]` bound if present
+    fn calculate_field_size(&mut self, field: &FieldDefinition) -> SizeInfo {
+        match field.max_length {
+            Some(max_length) => self.calculate_bounded_type_size(&field.type_info, max_length),
+            None => self.calculate_type_size(&field.type_info),
+        }
+    }
+
+    /// Calculate size for a variable-length type bounded by `#[max(N)]`
+    ///
+    /// `N` is the byte length for strings and the element/entry count for
+    /// vectors, maps and sets.
+    fn calculate_bounded_type_size(&mut self, type_info: &TypeInfo, max_length: usize) -> SizeInfo {
+        // Per-entry upper bound in bytes (None if an entry is itself unbounded)
+        let (entry_max, reason) = match type_info {
+            TypeInfo::Primitive(name) if name == "String" => (
+                Some(1),
+                format!("String length prefix + up to {} UTF-8 bytes", max_length),
+            ),
+            TypeInfo::Array(inner) => (
+                self.calculate_type_size(inner).max_bytes(),
+                format!(
+                    "Vec length prefix + up to {} elements ({})",
+                    max_length,
+                    self.describe_type(inner)
+                ),
+            ),
+            TypeInfo::Map { key, value, .. } => {
+                let key_max = self.calculate_type_size(key).max_bytes();
+                let value_max = self.calculate_type_size(value).max_bytes();
+                (
+                    key_max.zip(value_max).map(|(k, v)| k + v),
+                    format!(
+                        "Map length prefix + up to {} entries ({} => {})",
+                        max_length,
+                        self.describe_type(key),
+                        self.describe_type(value)
+                    ),
+                )
+            }
+            TypeInfo::Set { element, .. } => (
+                self.calculate_type_size(element).max_bytes(),
+                format!(
+                    "Set length prefix + up to {} elements ({})",
+                    max_length,
+                    self.describe_type(element)
+                ),
+            ),
+            TypeInfo::Option(inner) => {
+                // Option<T> = 1 byte (discriminant) + bounded T
+                return match self.calculate_bounded_type_size(inner, max_length) {
+                    SizeInfo::Fixed(bytes) => SizeInfo::Fixed(1 + bytes),
+                    SizeInfo::Variable { min, max, reason } => SizeInfo::Variable {
+                        min: 1 + min,
+                        max: max.map(|max| 1 + max),
+                        reason,
+                    },
+                };
+            }
+            TypeInfo::Boxed(inner) => {
+                // On a box, #[max(N)] bounds how deeply a recursive type nests
+                // through it; the outermost bound applies
+                let previous = self.depth_budget;
+                if previous.is_none() {
+                    self.depth_budget = Some(max_length);
+                }
+                let size = self.calculate_type_size(inner);
+                self.depth_budget = previous;
+                return size;
+            }
+            // #[max] has no effect on fixed-size types
+            _ => return self.calculate_type_size(type_info),
+        };
+
+        SizeInfo::Variable {
+            min: 4,
+            max: entry_max.map(|bytes| 4 + bytes * max_length),
+            reason,
+        }
+    }
+
+    /// Calculate size of a user-defined type or generic instance
+    ///
+    /// Generic instances are sized with their arguments substituted and
+    /// cached under their concrete name (e.g., `WrapperU64`).
+    fn calculate_user_type_size(&mut self, type_info: &TypeInfo) -> SizeInfo {
+        let type_name = type_info.pascal_name();
+
+        // A type referring back to itself (e.g. `children: Vec<Node>`)
+        if self.in_progress.contains(&type_name) {
+            return self.calculate_recursive_size(&type_name);
+        }
+
+        // Check cache first
+        if let Some(cached) = self.size_cache.get(&type_name) {
+            return cached.clone();
+        }
+        let recursion_hits = self.recursion_hits;
+
+        // Find type definition, substituting the arguments of a generic instance
+        let type_defs = self.type_defs;
+        let type_def = match type_info {
+            TypeInfo::Instance { name, args } => type_defs
+                .iter()
+                .find(|t| t.name() == name)
+                .map(|generic| Cow::Owned(instantiate_generic(generic, args))),
+            _ => type_defs
+                .iter()
+                .find(|t| t.name() == type_name)
+                .map(Cow::Borrowed),
+        };
+
+        let Some(type_def) = type_def else {
+            // Unknown user-defined type, assume reasonable size
+            return SizeInfo::Variable {
+                min: 0,
+                max: None,
+                reason: format!("Unknown type '{}'", type_name),
+            };
+        };
+
+        let size = match type_def.as_ref() {
+            TypeDefinition::Struct(s) => {
+                let account_size = self.calculate_struct_size(s);
+                account_size.total_bytes
+            }
+            TypeDefinition::Enum(e) => {
+                let account_size = self.calculate_enum_size(e);
+                account_size.total_bytes
+            }
+            TypeDefinition::TypeAlias(a) => {
+                // Type aliases are resolved - calculate the target type size
+                self.calculate_type_size(&a.target)
+            }
+            TypeDefinition::Const(c) => SizeInfo::Variable {
+                min: 0,
+                max: None,
+                reason: format!("'{}' is a constant, not a type", c.name),
+            },
+        };
+        if self.recursion_hits == recursion_hits {
+            self.size_cache.insert(type_name, size.clone());
+        }
+        size
+    }
+
     /// Calculate size for a type
     fn calculate_type_size(&mut self, type_info: &TypeInfo) -> SizeInfo {
         match type_info {
@@ -267,61 +481,37 @@ impl<'a> SizeCalculator<'a> {
                 // Generic parameters have unknown size until concrete type is provided
                 SizeInfo::Variable {
                     min: 0,
+                    max: None,
                     reason: format!(
                         "Generic parameter '{}' (size depends on concrete type)",
                         param_name
                     ),
                 }
             }
-            TypeInfo::UserDefined(type_name) => {
-                // Check cache first
-                if let Some(cached) = self.size_cache.get(type_name) {
-                    return cached.clone();
-                }
-
-                // Find type definition and calculate
-                if let Some(type_def) = self.type_defs.iter().find(|t| t.name() == type_name) {
-                    let size = match type_def {
-                        TypeDefinition::Struct(s) => {
-                            let account_size = self.calculate_struct_size(s);
-                            account_size.total_bytes
-                        }
-                        TypeDefinition::Enum(e) => {
-                            let account_size = self.calculate_enum_size(e);
-                            account_size.total_bytes
-                        }
-                        TypeDefinition::TypeAlias(a) => {
-                            // Type aliases are resolved - calculate the target type size
-                            self.calculate_type_size(&a.target)
-                        }
-                    };
-                    self.size_cache.insert(type_name.clone(), size.clone());
-                    size
-                } else {
-                    // Unknown user-defined type, assume reasonable size
-                    SizeInfo::Variable {
-                        min: 0,
-                        reason: format!("Unknown type '{}'", type_name),
-                    }
-                }
+            TypeInfo::UserDefined(_) | TypeInfo::Instance { .. } => {
+                self.calculate_user_type_size(type_info)
             }
             TypeInfo::Array(inner) => {
                 // Vec<T> = 4 bytes (length) + variable data
                 SizeInfo::Variable {
                     min: 4,
+                    max: None,
                     reason: format!(
                         "Vec length prefix + elements ({})",
                         self.describe_type(inner)
                     ),
                 }
             }
+            // Raw bytes: the capacity, however long the string is
+            TypeInfo::FixedString(size) => SizeInfo::Fixed(*size),
             TypeInfo::FixedArray { element, size } => {
                 // Fixed array [T; N] = element_size * N (no length prefix!)
                 let element_size = self.calculate_type_size(element);
                 match element_size {
                     SizeInfo::Fixed(bytes) => SizeInfo::Fixed(bytes * size),
-                    SizeInfo::Variable { min, reason } => SizeInfo::Variable {
+                    SizeInfo::Variable { min, max, reason } => SizeInfo::Variable {
                         min: min * size,
+                        max: max.map(|max| max * size),
                         reason: format!(
                             "Fixed array[{}] with variable-sized elements ({})",
                             size, reason
@@ -334,12 +524,229 @@ impl<'a> SizeCalculator<'a> {
                 let inner_size = self.calculate_type_size(inner);
                 match inner_size {
                     SizeInfo::Fixed(bytes) => SizeInfo::Fixed(1 + bytes),
-                    SizeInfo::Variable { min, reason } => SizeInfo::Variable {
+                    SizeInfo::Variable { min, max, reason } => SizeInfo::Variable {
                         min: 1 + min,
+                        max: max.map(|max| 1 + max),
                         reason,
                     },
                 }
             }
+            TypeInfo::Map { key, value, .. } => {
+                // Map<K, V> = 4 bytes (length) + entries; bound with #[max(N)]
+                SizeInfo::Variable {
+                    min: 4,
+                    max: None,
+                    reason: format!(
+                        "Map length prefix + entries ({} => {})",
+                        self.describe_type(key),
+                        self.describe_type(value)
+                    ),
+                }
+            }
+            TypeInfo::Set { element, .. } => {
+                // Set<T> = 4 bytes (length) + elements; bound with #[max(N)]
+                SizeInfo::Variable {
+                    min: 4,
+                    max: None,
+                    reason: format!(
+                        "Set length prefix + elements ({})",
+                        self.describe_type(element)
+                    ),
+                }
+            }
+            // Box<T> is serialized exactly like T
+            TypeInfo::Boxed(inner) => self.calculate_type_size(inner),
+            TypeInfo::Tuple(elements) => {
+                // (A, B, ...) = elements back to back, no prefix
+                let mut min = 0;
+                let mut max = Some(0);
+                let mut reasons = Vec::new();
+                for element in elements {
+                    let size = self.calculate_type_size(element);
+                    min += size.min_bytes();
+                    max = max.zip(size.max_bytes()).map(|(a, b)| a + b);
+                    if let SizeInfo::Variable { reason, .. } = size {
+                        reasons.push(reason);
+                    }
+                }
+                if reasons.is_empty() {
+                    SizeInfo::Fixed(min)
+                } else {
+                    SizeInfo::Variable {
+                        min,
+                        max,
+                        reason: format!(
+                            "Tuple with variable-sized elements ({})",
+                            reasons.join(", ")
+                        ),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Compute the C (`#[repr(C)]`) layout of a `#[zero_copy]` struct
+    ///
+    /// Returns the struct's fields with an explicit `_paddingN: [u8; N]` field
+    /// wherever `#[repr(C)]` would leave a gap (including tail padding), along
+    /// with the resulting layout. Fails if any field is not Pod-compatible.
+    pub fn zero_copy_layout(
+        &self,
+        struct_def: &StructDefinition,
+    ) -> Result<(Vec<FieldDefinition>, CLayout), String> {
+        self.zero_copy_layout_inner(struct_def, &mut Vec::new())
+    }
+
+    fn zero_copy_layout_inner(
+        &self,
+        struct_def: &StructDefinition,
+        visiting: &mut Vec<String>,
+    ) -> Result<(Vec<FieldDefinition>, CLayout), String> {
+        if visiting.contains(&struct_def.name) {
+            return Err(format!(
+                "#[zero_copy] struct '{}' contains itself",
+                struct_def.name
+            ));
+        }
+        visiting.push(struct_def.name.clone());
+
+        let mut fields = Vec::new();
+        let mut offsets = Vec::new();
+        let mut offset = 0;
+        let mut align = 1;
+        let mut padding_count = 0;
+
+        // Pad `offset` up to a multiple of `to`, recording the gap as a field
+        let mut pad = |fields: &mut Vec<FieldDefinition>,
+                       offsets: &mut Vec<usize>,
+                       offset: &mut usize,
+                       to: usize| {
+            let gap = (to - *offset % to) % to;
+            if gap == 0 {
+                return;
+            }
+            let name = loop {
+                let name = format!("_padding{}", padding_count);
+                padding_count += 1;
+                if !struct_def.fields.iter().any(|f| f.name == name) {
+                    break name;
+                }
+            };
+            fields.push(padding_field(name, gap));
+            offsets.push(*offset);
+            *offset += gap;
+        };
+
+        for field in &struct_def.fields {
+            let (size, field_align) = if field.optional {
+                Err(not_pod_error(struct_def, field, "an Option"))
+            } else {
+                self.pod_layout(&field.type_info, struct_def, field, visiting)
+            }?;
+
+            pad(&mut fields, &mut offsets, &mut offset, field_align);
+            fields.push(field.clone());
+            offsets.push(offset);
+            offset += size;
+            align = align.max(field_align);
+        }
+
+        // Tail padding rounds the size up to the struct's alignment
+        pad(&mut fields, &mut offsets, &mut offset, align);
+
+        visiting.pop();
+        Ok((
+            fields,
+            CLayout {
+                size: offset,
+                align,
+                offsets,
+            },
+        ))
+    }
+
+    /// Size and alignment of a Pod field type under `#[repr(C)]`
+    fn pod_layout(
+        &self,
+        type_info: &TypeInfo,
+        owner: &StructDefinition,
+        field: &FieldDefinition,
+        visiting: &mut Vec<String>,
+    ) -> Result<(usize, usize), String> {
+        match type_info {
+            TypeInfo::Primitive(name) => match name.as_str() {
+                "u8" | "i8" => Ok((1, 1)),
+                "u16" | "i16" => Ok((2, 2)),
+                "u32" | "i32" | "f32" => Ok((4, 4)),
+                "u64" | "i64" | "f64" => Ok((8, 8)),
+                // Aligned to 16 so the layout is the same on the host and on SBF
+                "u128" | "i128" => Ok((16, 16)),
+                // Pubkey is a [u8; 32] wrapper
+                "PublicKey" | "Pubkey" => Ok((32, 1)),
+                "bool" => Err(not_pod_error(owner, field, "bool (use u8 instead)")),
+                _ => Err(not_pod_error(owner, field, &self.describe_type(type_info))),
+            },
+            TypeInfo::FixedArray { element, size } => {
+                let (element_size, element_align) =
+                    self.pod_layout(element, owner, field, visiting)?;
+                Ok((element_size * size, element_align))
+            }
+            TypeInfo::FixedString(size) => Ok((*size, 1)),
+            TypeInfo::UserDefined(name) => {
+                match self.type_defs.iter().find(|t| t.name() == name) {
+                    Some(TypeDefinition::Struct(s)) if s.is_zero_copy() => {
+                        let (_, layout) = self.zero_copy_layout_inner(s, visiting)?;
+                        Ok((layout.size, layout.align))
+                    }
+                    // Newtypes are Borsh wrappers, not Pod
+                    Some(TypeDefinition::TypeAlias(a)) if a.newtype => Err(format!(
+                        "Field '{}' of #[zero_copy] struct '{}' has #[newtype] '{}', which is not Pod; use its underlying type instead",
+                        field.name, owner.name, name
+                    )),
+                    Some(TypeDefinition::TypeAlias(a)) => {
+                        self.pod_layout(&a.target, owner, field, visiting)
+                    }
+                    Some(TypeDefinition::Struct(_)) => Err(format!(
+                        "Field '{}' of #[zero_copy] struct '{}' has type '{}', which must also be #[zero_copy]",
+                        field.name, owner.name, name
+                    )),
+                    _ => Err(not_pod_error(owner, field, &format!("'{}'", name))),
+                }
+            }
+            _ => Err(not_pod_error(owner, field, &self.describe_type(type_info))),
+        }
+    }
+
+    /// Calculate size for a reference to a type that is already being sized
+    ///
+    /// Recursion nests without limit, so the size is unbounded unless a
+    /// `#[max(N)]` on the `Box` it passes through limits the depth; then the
+    /// type is unrolled that many more levels to find its maximum size.
+    fn calculate_recursive_size(&mut self, type_name: &str) -> SizeInfo {
+        self.recursion_hits += 1;
+        let max = match self.depth_budget {
+            None => None,
+            // No further nesting: the value can't be present at this depth
+            Some(0) => Some(0),
+            Some(depth) => {
+                self.depth_budget = Some(depth - 1);
+                let unrolled = match self.type_defs.iter().find(|t| t.name() == type_name) {
+                    Some(TypeDefinition::Struct(s)) => self.calculate_struct_size(s).total_bytes,
+                    Some(TypeDefinition::Enum(e)) => self.calculate_enum_size(e).total_bytes,
+                    _ => SizeInfo::Fixed(0),
+                };
+                self.depth_budget = Some(depth);
+                unrolled.max_bytes()
+            }
+        };
+
+        SizeInfo::Variable {
+            min: 0,
+            max,
+            reason: match max {
+                Some(_) => format!("Recursive type '{}' (nesting bounded by #[max])", type_name),
+                None => format!("Recursive type '{}' (unbounded nesting)", type_name),
+            },
         }
     }
 
@@ -360,12 +767,14 @@ impl<'a> SizeCalculator<'a> {
             // String is variable length
             "String" => SizeInfo::Variable {
                 min: 4,
+                max: None,
                 reason: "String length prefix + UTF-8 bytes".to_string(),
             },
 
             // Unknown
             _ => SizeInfo::Variable {
                 min: 0,
+                max: None,
                 reason: format!("Unknown primitive type '{}'", type_name),
             },
         }
@@ -383,15 +792,71 @@ impl<'a> SizeCalculator<'a> {
             },
             TypeInfo::Generic(param_name) => param_name.clone(),
             TypeInfo::UserDefined(name) => name.clone(),
+            TypeInfo::Instance { name, args } => format!(
+                "{}<{}>",
+                name,
+                args.iter()
+                    .map(|a| self.describe_type(a))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
             TypeInfo::Array(inner) => format!("Vec<{}>", self.describe_type(inner)),
             TypeInfo::FixedArray { element, size } => {
                 format!("[{}; {}]", self.describe_type(element), size)
             }
             TypeInfo::Option(inner) => format!("Option<{}>", self.describe_type(inner)),
+            TypeInfo::Boxed(inner) => format!("Box<{}>", self.describe_type(inner)),
+            TypeInfo::FixedString(size) => format!("FixedString<{}>", size),
+            TypeInfo::Tuple(elements) => format!(
+                "({})",
+                elements
+                    .iter()
+                    .map(|e| self.describe_type(e))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            TypeInfo::Map {
+                key,
+                value,
+                ordered,
+            } => format!(
+                "{}<{}, {}>",
+                if *ordered { "BTreeMap" } else { "HashMap" },
+                self.describe_type(key),
+                self.describe_type(value)
+            ),
+            TypeInfo::Set { element, ordered } => format!(
+                "{}<{}>",
+                if *ordered { "BTreeSet" } else { "HashSet" },
+                self.describe_type(element)
+            ),
         }
     }
 }
 
+/// Error for a field of a `#[zero_copy]` struct that is not Pod-compatible
+fn not_pod_error(owner: &StructDefinition, field: &FieldDefinition, ty: &str) -> String {
+    format!(
+        "Field '{}' of #[zero_copy] struct '{}' has type {}, which is not Pod. \
+         Zero-copy fields must be fixed-size integers, floats, PublicKey, \
+         fixed-size arrays or other #[zero_copy] structs",
+        field.name, owner.name, ty
+    )
+}
+
+/// An explicit `[u8; N]` padding field of a `#[zero_copy]` struct
+fn padding_field(name: String, size: usize) -> FieldDefinition {
+    FieldDefinition {
+        name,
+        type_info: TypeInfo::FixedArray {
+            element: Box::new(TypeInfo::Primitive("u8".to_string())),
+            size,
+        },
+        docs: vec!["Alignment padding".to_string()],
+        ..Default::default()
+    }
+}
+
 impl SizeInfo {
     /// Get the minimum size in bytes
     pub fn min_bytes(&self) -> usize {
@@ -401,6 +866,14 @@ impl SizeInfo {
         }
     }
 
+    /// Get the maximum size in bytes, if bounded
+    pub fn max_bytes(&self) -> Option<usize> {
+        match self {
+            SizeInfo::Fixed(bytes) => Some(*bytes),
+            SizeInfo::Variable { max, .. } => *max,
+        }
+    }
+
     /// Check if this is a fixed size
     pub fn is_fixed(&self) -> bool {
         matches!(self, SizeInfo::Fixed(_))
@@ -435,23 +908,19 @@ mod tests {
                 FieldDefinition {
                     name: "wallet".to_string(),
                     type_info: TypeInfo::Primitive("PublicKey".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
                 FieldDefinition {
                     name: "score".to_string(),
                     type_info: TypeInfo::Primitive("u64".to_string()),
-                    optional: false,
-                    deprecated: None,
-                    span: None,
-                    anchor_attrs: vec![],
+                    ..Default::default()
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let mut calc = SizeCalculator::new(&type_defs);
@@ -470,21 +939,17 @@ mod tests {
             fields: vec![FieldDefinition {
                 name: "score".to_string(),
                 type_info: TypeInfo::Primitive("u64".to_string()),
-                optional: false,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata {
                 solana: true,
                 attributes: vec!["account".to_string()],
-                version: None,
-                custom_derives: vec![],
-                is_instruction: false,
-                anchor_attrs: vec![],
+                ..Default::default()
             },
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let mut calc = SizeCalculator::new(&type_defs);
@@ -504,13 +969,13 @@ mod tests {
                 name: "maybe_value".to_string(),
                 type_info: TypeInfo::Option(Box::new(TypeInfo::Primitive("u64".to_string()))),
                 optional: true,
-                deprecated: None,
-                span: None,
-                anchor_attrs: vec![],
+                ..Default::default()
             }],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         })];
 
         let mut calc = SizeCalculator::new(&type_defs);
@@ -519,4 +984,236 @@ mod tests {
         assert_eq!(sizes.len(), 1);
         assert_eq!(sizes[0].total_bytes.min_bytes(), 1 + 8); // discriminant + u64
     }
+
+    #[test]
+    fn test_enum_repr_widens_discriminant() {
+        let input = r#"
+            #[solana]
+            #[repr(u32)]
+            enum Status {
+                Active = 1,
+                Locked(u64) = 70000,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(input).unwrap();
+        let type_defs = crate::transform::transform_to_ir(ast).unwrap();
+
+        let mut calc = SizeCalculator::new(&type_defs);
+        let sizes = calc.calculate_all();
+
+        assert_eq!(sizes[0].field_breakdown[0].size.max_bytes(), Some(4));
+        assert_eq!(sizes[0].total_bytes.min_bytes(), 4 + 8); // u32 tag + largest variant
+    }
+
+    #[test]
+    fn test_zero_copy_layout_pads_nested_structs() {
+        let input = r#"
+            #[zero_copy]
+            struct Order {
+                side: u8,
+                price: u64,
+            }
+
+            #[solana]
+            #[account]
+            #[zero_copy]
+            struct Book {
+                count: u16,
+                volume: u128,
+                orders: [Order; 2],
+                flags: u8,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(input).unwrap();
+        let type_defs = crate::transform::transform_to_ir(ast).unwrap();
+        let TypeDefinition::Struct(book) = &type_defs[1] else {
+            panic!("Expected struct");
+        };
+
+        // count @0, volume @16, orders @32 (2 x 16 bytes), flags @64, tail padding to 80
+        let layout = book.metadata.c_layout.as_ref().unwrap();
+        assert_eq!(layout.offsets, vec![0, 2, 16, 32, 64, 65]);
+        assert_eq!((layout.size, layout.align), (80, 16));
+
+        // Padding is explicit, so the field sizes add up to the C layout
+        let mut calc = SizeCalculator::new(&type_defs);
+        let sizes = calc.calculate_all();
+        assert_eq!(sizes[1].total_bytes.min_bytes(), 8 + 80);
+
+        // Laying out an already padded struct adds nothing
+        let (fields, relaid) = calc.zero_copy_layout(book).unwrap();
+        assert_eq!(fields.len(), book.fields.len());
+        assert_eq!(&relaid, layout);
+    }
+
+    #[test]
+    fn test_map_and_set_sizes_with_max() {
+        let input = r#"
+            #[solana]
+            #[account]
+            struct Registry {
+                #[max(10)]
+                balances: HashMap<PublicKey, u64>,
+                #[max(4)]
+                members: BTreeSet<PublicKey>,
+                #[max(32)]
+                name: String,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(input).unwrap();
+        let type_defs = crate::transform::transform_to_ir(ast).unwrap();
+
+        let mut calc = SizeCalculator::new(&type_defs);
+        let sizes = calc.calculate_all();
+
+        let fields = &sizes[0].field_breakdown;
+        assert_eq!(fields[1].size.max_bytes(), Some(4 + 10 * (32 + 8)));
+        assert_eq!(fields[2].size.max_bytes(), Some(4 + 4 * 32));
+        assert_eq!(fields[3].size.max_bytes(), Some(4 + 32));
+
+        // discriminator + three length prefixes
+        assert_eq!(sizes[0].total_bytes.min_bytes(), 8 + 4 + 4 + 4);
+        assert_eq!(
+            sizes[0].total_bytes.max_bytes(),
+            Some(8 + (4 + 400) + (4 + 128) + (4 + 32))
+        );
+    }
+
+    #[test]
+    fn test_unbounded_map_has_no_max() {
+        let input = r#"
+            struct Registry {
+                balances: BTreeMap<u32, u64>,
+                #[max(8)]
+                tags: HashSet<String>,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(input).unwrap();
+        let type_defs = crate::transform::transform_to_ir(ast).unwrap();
+
+        let mut calc = SizeCalculator::new(&type_defs);
+        let sizes = calc.calculate_all();
+
+        let fields = &sizes[0].field_breakdown;
+        assert_eq!(fields[0].size.min_bytes(), 4);
+        assert_eq!(fields[0].size.max_bytes(), None);
+        // Bounded entry count, but each String entry is itself unbounded
+        assert_eq!(fields[1].size.max_bytes(), None);
+        assert_eq!(sizes[0].total_bytes.max_bytes(), None);
+    }
+
+    #[test]
+    fn test_tuple_sizes() {
+        let input = r#"
+            struct Pool {
+                pair: (u64, PublicKey),
+                shares: Vec<(PublicKey, u16)>,
+                label: (u8, String),
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(input).unwrap();
+        let type_defs = crate::transform::transform_to_ir(ast).unwrap();
+
+        let mut calc = SizeCalculator::new(&type_defs);
+        let sizes = calc.calculate_all();
+
+        let fields = &sizes[0].field_breakdown;
+        assert!(matches!(fields[0].size, SizeInfo::Fixed(40)));
+        assert_eq!(fields[1].size.min_bytes(), 4);
+        // A String element makes the whole tuple variable
+        assert_eq!(fields[2].size.min_bytes(), 1 + 4);
+        assert_eq!(fields[2].size.max_bytes(), None);
+    }
+
+    #[test]
+    fn test_recursive_types_are_unbounded_unless_depth_limited() {
+        let input = r#"
+            struct Tree {
+                value: u64,
+                children: Vec<Tree>,
+            }
+
+            struct Chain {
+                value: u64,
+                #[max(2)]
+                next: Option<Box<Chain>>,
+            }
+
+            enum Expr {
+                Literal(u64),
+                Negate(Box<Expr>),
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(input).unwrap();
+        let type_defs = crate::transform::transform_to_ir(ast).unwrap();
+
+        let mut calc = SizeCalculator::new(&type_defs);
+        let sizes = calc.calculate_all();
+
+        assert_eq!(sizes[0].total_bytes.min_bytes(), 8 + 4);
+        assert_eq!(sizes[0].total_bytes.max_bytes(), None);
+
+        // At most three links (value + Option tag each); the last `next` is None
+        let chain = &sizes[1];
+        assert_eq!(chain.total_bytes.max_bytes(), Some(3 * (8 + 1)));
+        match &chain.field_breakdown[1].size {
+            SizeInfo::Variable { reason, .. } => {
+                assert!(reason.contains("Recursive type 'Chain' (nesting bounded by #[max])"))
+            }
+            other => panic!("Expected variable size, got {:?}", other),
+        }
+
+        let expr = &sizes[2].total_bytes;
+        assert_eq!(expr.max_bytes(), None);
+        assert!(matches!(expr, SizeInfo::Variable { reason, .. }
+            if reason.contains("Recursive type 'Expr' (unbounded nesting)")));
+    }
+
+    #[test]
+    fn test_flags_take_their_integer_width() {
+        let input = r#"
+            #[solana]
+            #[flags(u16)]
+            enum Permissions {
+                Mint,
+                Burn,
+                Freeze,
+            }
+
+            #[solana]
+            #[account]
+            struct Authority {
+                permissions: Permissions,
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(input).unwrap();
+        let type_defs = crate::transform::transform_to_ir(ast).unwrap();
+
+        let mut calc = SizeCalculator::new(&type_defs);
+        let sizes = calc.calculate_all();
+
+        assert_eq!(sizes[0].total_bytes.min_bytes(), 2);
+        assert_eq!(sizes[0].field_breakdown.len(), 1);
+        assert_eq!(sizes[1].total_bytes.min_bytes(), 8 + 2); // discriminator + u16
+    }
+
+    #[test]
+    fn test_fixed_string_has_fixed_size() {
+        let input = r#"
+            #[solana]
+            #[account]
+            struct Profile {
+                name: FixedString<32>,
+                aliases: [FixedString<8>; 4],
+            }
+        "#;
+        let ast = crate::parser::parse_lumos_file(input).unwrap();
+        let type_defs = crate::transform::transform_to_ir(ast).unwrap();
+
+        let mut calc = SizeCalculator::new(&type_defs);
+        let sizes = calc.calculate_all();
+
+        assert!(sizes[0].total_bytes.is_fixed());
+        assert_eq!(sizes[0].total_bytes.min_bytes(), 8 + 32 + 4 * 8);
+    }
 }
@@ -64,21 +64,26 @@
 //! ```
 
 use crate::ast::{
-    Attribute, AttributeValue, EnumDef as AstEnum, EnumVariant as AstEnumVariant,
-    FieldDef as AstField, Item as AstItem, LumosFile, StructDef as AstStruct,
-    TypeAlias as AstTypeAlias, TypeSpec as AstType, Visibility as AstVisibility,
+    Attribute, AttributeValue, ConstDef as AstConst, ConstExpr, DefaultExpr, EnumDef as AstEnum,
+    EnumVariant as AstEnumVariant, FieldDef as AstField, Item as AstItem, LumosFile, RangeBound,
+    RangeExpr, StructDef as AstStruct, TypeAlias as AstTypeAlias, TypeSpec as AstType,
+    Visibility as AstVisibility,
 };
-use crate::error::{LumosError, Result};
+use crate::error::{join, ErrorCollector, LumosError, Result, SourceLocation};
 use crate::ir::{
-    EnumDefinition, EnumVariantDefinition, FieldDefinition, Metadata, StructDefinition,
-    TypeAliasDefinition, TypeDefinition, TypeInfo, Visibility,
+    ConstDefinition, Constraint, DefaultValue, EnumDefinition, EnumRepr, EnumVariantDefinition,
+    FieldDefinition, Metadata, StructDefinition, TypeAliasDefinition, TypeDefinition, TypeInfo,
+    Visibility, ANCHOR_ERROR_CODE_OFFSET,
 };
-use std::collections::{HashMap, HashSet};
+use crate::naming::{Case, NameTarget, Renames};
+use crate::size_calculator::SizeCalculator;
+use std::collections::{HashMap, HashSet, VecDeque};
 
 /// Type alias resolver
 ///
 /// Resolves type aliases recursively and detects circular references.
-/// Maintains a map of alias names to their resolved target types.
+/// Maintains a map of alias names to their resolved target types, along with
+/// the schema constants that array sizes and `#[max]` bounds may refer to.
 #[derive(Debug, Clone)]
 pub struct TypeAliasResolver {
     /// Map of alias names to their AST type specs (unresolved)
@@ -86,6 +91,15 @@ pub struct TypeAliasResolver {
 
     /// Map of alias names to their resolved TypeInfo (after resolution)
     resolved: HashMap<String, TypeInfo>,
+
+    /// Aliases marked `#[newtype]`, which are referred to by name instead of resolved
+    newtypes: HashSet<String>,
+
+    /// Map of constant names to their value expressions (unresolved)
+    constants: HashMap<String, ConstExpr>,
+
+    /// Map of constant names to their integer values (after resolution)
+    resolved_constants: HashMap<String, u128>,
 }
 
 impl Default for TypeAliasResolver {
@@ -100,6 +114,9 @@ impl TypeAliasResolver {
         Self {
             aliases: HashMap::new(),
             resolved: HashMap::new(),
+            newtypes: HashSet::new(),
+            constants: HashMap::new(),
+            resolved_constants: HashMap::new(),
         }
     }
 
@@ -115,12 +132,103 @@ impl TypeAliasResolver {
         Ok(())
     }
 
+    /// Add a `#[newtype]` alias to the resolver
+    ///
+    /// Its target is resolved like any alias, but references to it stay
+    /// `TypeInfo::UserDefined` so generators can emit a distinct type.
+    pub fn add_newtype(&mut self, name: String, target: AstType) -> Result<()> {
+        self.add_alias(name.clone(), target)?;
+        self.newtypes.insert(name);
+        Ok(())
+    }
+
+    /// Check if an alias was added with [`TypeAliasResolver::add_newtype`]
+    pub fn is_newtype(&self, name: &str) -> bool {
+        self.newtypes.contains(name)
+    }
+
+    /// Add a schema constant to the resolver
+    pub fn add_constant(&mut self, name: String, value: ConstExpr) -> Result<()> {
+        if self.constants.contains_key(&name) {
+            return Err(LumosError::Transform(
+                format!("Duplicate constant definition: {}", name),
+                None,
+            ));
+        }
+        self.constants.insert(name, value);
+        Ok(())
+    }
+
     /// Resolve all aliases and detect circular references
+    ///
+    /// Constants are resolved first, since alias targets may use them as array sizes.
+    /// Every alias and constant that fails to resolve is reported.
     pub fn resolve_all_aliases(&mut self) -> Result<()> {
-        for alias_name in self.aliases.keys().cloned().collect::<Vec<_>>() {
+        self.resolve_all(&HashMap::new())
+    }
+
+    /// Resolve all constants and aliases, locating each failure at its definition
+    fn resolve_all(&mut self, spans: &HashMap<&str, proc_macro2::Span>) -> Result<()> {
+        let mut errors = ErrorCollector::new();
+
+        let mut const_names = self.constants.keys().cloned().collect::<Vec<_>>();
+        const_names.sort();
+        for const_name in const_names {
+            let mut visited = HashSet::new();
+            let resolved = self.resolve_constant(&const_name, &mut visited);
+            errors.ok(resolved.map_err(|e| e.with_span(spans.get(const_name.as_str()).copied())));
+        }
+
+        let mut alias_names = self.aliases.keys().cloned().collect::<Vec<_>>();
+        alias_names.sort();
+        for alias_name in &alias_names {
             let mut visited = HashSet::new();
-            self.resolve_alias(&alias_name, &mut visited)?;
+            let resolved = self.resolve_alias(alias_name, &mut visited);
+            errors.ok(resolved.map_err(|e| e.with_span(spans.get(alias_name.as_str()).copied())));
+        }
+
+        for alias_name in alias_names
+            .iter()
+            .filter(|name| self.newtypes.contains(*name))
+        {
+            let checked = self.check_newtype_cycle(alias_name, &mut Vec::new());
+            errors.ok(checked.map_err(|e| e.with_span(spans.get(alias_name.as_str()).copied())));
+        }
+
+        errors.finish(())
+    }
+
+    /// Reject a newtype whose target contains itself through other newtypes
+    ///
+    /// Such a type could never be written out as its underlying type.
+    fn check_newtype_cycle(&self, name: &str, path: &mut Vec<String>) -> Result<()> {
+        if path.first().is_some_and(|first| first == name) {
+            path.push(name.to_string());
+            return Err(LumosError::Transform(
+                format!("Newtype '{}' contains itself: {}", name, path.join(" → ")),
+                None,
+            ));
+        }
+        if path.iter().any(|seen| seen == name) {
+            // A cycle not through the starting newtype is reported from its own start
+            return Ok(());
         }
+
+        path.push(name.to_string());
+        let mut nested = Vec::new();
+        if let Some(target) = self.resolved.get(name) {
+            target.walk(&mut |t| {
+                if let TypeInfo::UserDefined(inner) = t {
+                    if self.newtypes.contains(inner) {
+                        nested.push(inner.clone());
+                    }
+                }
+            });
+        }
+        for inner in nested {
+            self.check_newtype_cycle(&inner, path)?;
+        }
+        path.pop();
         Ok(())
     }
 
@@ -159,6 +267,61 @@ impl TypeAliasResolver {
         Ok(resolved)
     }
 
+    /// Resolve a single constant recursively
+    fn resolve_constant(&mut self, name: &str, visited: &mut HashSet<String>) -> Result<u128> {
+        if let Some(value) = self.resolved_constants.get(name) {
+            return Ok(*value);
+        }
+
+        if visited.contains(name) {
+            return Err(LumosError::Transform(
+                format!("Circular constant reference detected: {}", name),
+                None,
+            ));
+        }
+
+        visited.insert(name.to_string());
+
+        let value = match self.constants.get(name).cloned() {
+            Some(ConstExpr::Literal(value)) => value,
+            Some(ConstExpr::Named(target)) => self.resolve_constant(&target, visited)?,
+            None => {
+                return Err(LumosError::Transform(
+                    format!("Unknown constant: {}", name),
+                    None,
+                ))
+            }
+        };
+
+        self.resolved_constants.insert(name.to_string(), value);
+
+        visited.remove(name);
+
+        Ok(value)
+    }
+
+    /// Get the value of a constant (after resolve_all_aliases)
+    pub fn get_constant(&self, name: &str) -> Option<u128> {
+        self.resolved_constants.get(name).copied()
+    }
+
+    /// Evaluate an integer expression, looking up constant names
+    pub fn resolve_const_expr(&self, expr: &ConstExpr) -> Result<u128> {
+        match expr {
+            ConstExpr::Literal(value) => Ok(*value),
+            ConstExpr::Named(name) => self
+                .get_constant(name)
+                .ok_or_else(|| LumosError::Transform(format!("Unknown constant: {}", name), None)),
+        }
+    }
+
+    /// Evaluate a fixed array size and check it against the array size limits
+    fn resolve_array_size(&self, size: &ConstExpr) -> Result<usize> {
+        let value = usize::try_from(self.resolve_const_expr(size)?).unwrap_or(usize::MAX);
+        validate_array_size(value)?;
+        Ok(value)
+    }
+
     /// Transform a type spec, resolving any aliases it contains
     fn transform_type_with_resolver(
         &mut self,
@@ -173,6 +336,9 @@ impl TypeAliasResolver {
                     // Map TypeScript-friendly aliases to Rust types
                     let rust_type = map_type_alias(&name);
                     TypeInfo::Primitive(rust_type)
+                } else if self.newtypes.contains(&name) {
+                    // Newtypes are distinct types, referred to by name
+                    TypeInfo::UserDefined(name)
                 } else if self.aliases.contains_key(&name) {
                     // It's a type alias, resolve it
                     self.resolve_alias(&name, visited)?
@@ -196,10 +362,53 @@ impl TypeAliasResolver {
                 let element_type = self.transform_type_with_resolver(*element, false, visited)?;
                 TypeInfo::FixedArray {
                     element: Box::new(element_type),
-                    size,
+                    size: self.resolve_array_size(&size)?,
+                }
+            }
+
+            AstType::Map {
+                key,
+                value,
+                ordered,
+            } => {
+                let key_type = self.transform_type_with_resolver(*key, false, visited)?;
+                let value_type = self.transform_type_with_resolver(*value, false, visited)?;
+                TypeInfo::Map {
+                    key: Box::new(key_type),
+                    value: Box::new(value_type),
+                    ordered,
+                }
+            }
+
+            AstType::Set { element, ordered } => {
+                let element_type = self.transform_type_with_resolver(*element, false, visited)?;
+                TypeInfo::Set {
+                    element: Box::new(element_type),
+                    ordered,
                 }
             }
 
+            AstType::Tuple(elements) => TypeInfo::Tuple(
+                elements
+                    .into_iter()
+                    .map(|e| self.transform_type_with_resolver(e, false, visited))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+
+            AstType::Boxed(inner) => TypeInfo::Boxed(Box::new(
+                self.transform_type_with_resolver(*inner, false, visited)?,
+            )),
+
+            AstType::FixedString(size) => TypeInfo::FixedString(self.resolve_array_size(&size)?),
+
+            AstType::Instance { name, args } => TypeInfo::Instance {
+                name,
+                args: args
+                    .into_iter()
+                    .map(|a| self.transform_type_with_resolver(a, false, visited))
+                    .collect::<Result<Vec<_>>>()?,
+            },
+
             AstType::UserDefined(name) => {
                 // Check if it's an alias
                 if self.aliases.contains_key(&name) {
@@ -223,6 +432,16 @@ impl TypeAliasResolver {
     fn get_resolved(&self, name: &str) -> Option<&TypeInfo> {
         self.resolved.get(name)
     }
+
+    /// Type a reference to `name` stands for, if `name` is an alias
+    ///
+    /// Plain aliases are replaced by their target; newtypes keep their name.
+    fn alias_reference(&self, name: &str) -> Option<TypeInfo> {
+        if self.newtypes.contains(name) {
+            return Some(TypeInfo::UserDefined(name.to_string()));
+        }
+        self.resolved.get(name).cloned()
+    }
 }
 
 /// Transform a parsed LUMOS file (AST) into Intermediate Representation (IR).
@@ -272,52 +491,161 @@ impl TypeAliasResolver {
 ///
 /// # Errors
 ///
-/// Returns [`crate::error::LumosError`] if transformation fails (rare, most validation happens in parser).
+/// Returns [`crate::error::LumosError`] if transformation or validation fails.
+/// Independent problems (e.g. an unknown type in one struct and a duplicate
+/// field in another) are all reported together as [`LumosError::Multiple`].
 pub fn transform_to_ir(file: LumosFile) -> Result<Vec<TypeDefinition>> {
-    let mut type_defs = Vec::new();
+    let mut errors = ErrorCollector::new();
 
     // First pass: Build type alias resolver
     let mut alias_resolver = TypeAliasResolver::new();
+    let mut spans = HashMap::new();
 
     for item in &file.items {
-        if let AstItem::TypeAlias(alias_def) = item {
-            alias_resolver.add_alias(alias_def.name.clone(), alias_def.target.clone())?;
-        }
+        let added = match item {
+            AstItem::TypeAlias(alias_def) => {
+                spans.extend(alias_def.span.map(|span| (alias_def.name.as_str(), span)));
+                let added = if alias_def.is_newtype() {
+                    alias_resolver.add_newtype(alias_def.name.clone(), alias_def.target.clone())
+                } else {
+                    alias_resolver.add_alias(alias_def.name.clone(), alias_def.target.clone())
+                };
+                added.map_err(|e| e.with_span(alias_def.span))
+            }
+            AstItem::Const(const_def) => {
+                spans.extend(const_def.span.map(|span| (const_def.name.as_str(), span)));
+                alias_resolver
+                    .add_constant(const_def.name.clone(), const_def.value.clone())
+                    .map_err(|e| e.with_span(const_def.span))
+            }
+            _ => Ok(()),
+        };
+        errors.ok(added);
+    }
+
+    // Resolve all constants and aliases recursively and check for cycles
+    errors.ok(alias_resolver.resolve_all(&spans));
+
+    // Every named item counts as defined, so an item that fails to transform
+    // is not reported again as an undefined type wherever it's used
+    let defined_types: HashMap<String, usize> = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            AstItem::Struct(s) => Some((s.name.clone(), s.type_params.len())),
+            AstItem::Enum(e) => Some((e.name.clone(), e.type_params.len())),
+            AstItem::TypeAlias(a) => Some((a.name.clone(), 0)),
+            _ => None,
+        })
+        .collect();
+
+    // Second pass: Transform all items (structs, enums, type aliases). Aliases
+    // and constants that failed to resolve have already been reported.
+    let attributes = file.attributes;
+    let items = file
+        .items
+        .into_iter()
+        .filter(|item| match item {
+            AstItem::TypeAlias(a) => alias_resolver.get_resolved(&a.name).is_some(),
+            AstItem::Const(c) => alias_resolver.get_constant(&c.name).is_some(),
+            _ => true,
+        })
+        .collect();
+    let mut type_defs = transform_items(items, &alias_resolver, &mut errors);
+    errors.ok(resolve_names(&attributes, &mut type_defs));
+
+    // Validate user-defined type references
+    errors.ok(validate_type_references(&type_defs, &defined_types));
+
+    // Layouts are only meaningful once every type is valid
+    if !errors.has_errors() {
+        errors.ok(apply_zero_copy_layouts(&mut type_defs));
     }
 
-    // Resolve all aliases recursively and check for cycles
-    alias_resolver.resolve_all_aliases()?;
+    // Note: Deprecation warnings are now collected via collect_deprecation_warnings()
+    // The caller (CLI) is responsible for displaying them
+
+    errors.finish(type_defs)
+}
+
+/// Transform every struct, enum, type alias and constant, collecting all errors
+fn transform_items(
+    items: Vec<AstItem>,
+    resolver: &TypeAliasResolver,
+    errors: &mut ErrorCollector,
+) -> Vec<TypeDefinition> {
+    let mut type_defs = Vec::new();
 
-    // Second pass: Transform all items (structs, enums, type aliases)
-    for item in file.items {
-        match item {
+    for item in items {
+        let (span, type_def) = match item {
             AstItem::Struct(struct_def) => {
-                let type_def = transform_struct(struct_def, &alias_resolver)?;
-                type_defs.push(TypeDefinition::Struct(type_def));
+                type_defs.push(TypeDefinition::Struct(transform_struct(
+                    struct_def, resolver, errors,
+                )));
+                continue;
             }
             AstItem::Enum(enum_def) => {
-                let type_def = transform_enum(enum_def, &alias_resolver)?;
-                type_defs.push(TypeDefinition::Enum(type_def));
-            }
-            AstItem::TypeAlias(alias_def) => {
-                let type_def = transform_type_alias(alias_def, &alias_resolver)?;
-                type_defs.push(TypeDefinition::TypeAlias(type_def));
+                type_defs.push(TypeDefinition::Enum(transform_enum(
+                    enum_def, resolver, errors,
+                )));
+                continue;
             }
-            // Module and Use statements are not yet transformed in this pass
+            AstItem::TypeAlias(alias_def) => (
+                alias_def.span,
+                transform_type_alias(alias_def, resolver).map(TypeDefinition::TypeAlias),
+            ),
+            AstItem::Const(const_def) => (
+                const_def.span,
+                transform_const(const_def, resolver).map(TypeDefinition::Const),
+            ),
+            // Module and Use statements are not yet transformed
             // They will be handled by ModuleResolver in #53b
-            AstItem::Module(_) | AstItem::Use(_) => {
-                // Skip for now - module resolution is not yet implemented
-            }
+            AstItem::Module(_) | AstItem::Use(_) => continue,
+        };
+        if let Some(type_def) = errors.ok(type_def.map_err(|e| e.with_span(span))) {
+            type_defs.push(type_def);
         }
     }
 
-    // Validate user-defined type references
-    validate_user_defined_types(&type_defs)?;
+    type_defs
+}
 
-    // Note: Deprecation warnings are now collected via collect_deprecation_warnings()
-    // The caller (CLI) is responsible for displaying them
+/// Validate array size constraints
+///
+/// Ensures array size is within reasonable bounds for Solana programs.
+///
+/// # Constraints
+///
+/// * Size must be > 0 (zero-sized arrays are invalid)
+/// * Size must be ≤ 1024 (practical limit for most use cases)
+///
+/// # Arguments
+///
+/// * `size` - Array size to validate
+///
+/// # Returns
+///
+/// * `Ok(())` - Size is valid
+/// * `Err(LumosError)` - Size is out of bounds
+fn validate_array_size(size: usize) -> Result<()> {
+    if size == 0 {
+        return Err(LumosError::Transform(
+            "Array size must be greater than 0".to_string(),
+            None,
+        ));
+    }
+
+    if size > 1024 {
+        return Err(LumosError::Transform(
+            format!(
+                "Array size {} exceeds maximum of 1024 elements (consider using Vec for dynamic arrays)",
+                size
+            ),
+            None,
+        ));
+    }
 
-    Ok(type_defs)
+    Ok(())
 }
 
 /// Transform AST to IR with a pre-populated type alias resolver
@@ -370,40 +698,22 @@ fn transform_to_ir_with_resolver_impl(
     resolver: &TypeAliasResolver,
     validate: bool,
 ) -> Result<Vec<TypeDefinition>> {
-    let mut type_defs = Vec::new();
+    let mut errors = ErrorCollector::new();
 
     // Transform all items using the provided resolver
-    for item in file.items {
-        match item {
-            AstItem::Struct(struct_def) => {
-                let type_def = transform_struct(struct_def, resolver)?;
-                type_defs.push(TypeDefinition::Struct(type_def));
-            }
-            AstItem::Enum(enum_def) => {
-                let type_def = transform_enum(enum_def, resolver)?;
-                type_defs.push(TypeDefinition::Enum(type_def));
-            }
-            AstItem::TypeAlias(alias_def) => {
-                let type_def = transform_type_alias(alias_def, resolver)?;
-                type_defs.push(TypeDefinition::TypeAlias(type_def));
-            }
-            // Module and Use statements are not yet transformed
-            // They will be handled by ModuleResolver in #53b
-            AstItem::Module(_) | AstItem::Use(_) => {
-                // Skip for now - module resolution is not yet implemented
-            }
-        }
-    }
+    let mut type_defs = transform_items(file.items, resolver, &mut errors);
+    errors.ok(resolve_names(&file.attributes, &mut type_defs));
 
     // Validate user-defined type references (skip for multi-file scenarios)
-    if validate {
+    if validate && !errors.has_errors() {
         validate_user_defined_types(&type_defs)?;
+        apply_zero_copy_layouts(&mut type_defs)?;
     }
 
     // Note: Deprecation warnings are now collected via collect_deprecation_warnings()
     // The caller (CLI) is responsible for displaying them
 
-    Ok(type_defs)
+    errors.finish(type_defs)
 }
 
 /// Transform a type alias definition
@@ -422,10 +732,48 @@ fn transform_type_alias(
         .clone();
 
     Ok(TypeAliasDefinition {
+        newtype: resolver.is_newtype(&name),
         name,
         target,
         visibility: Visibility::Public, // Type aliases are always public by default
         module_path: Vec::new(),        // Will be set by module resolver if needed
+        reexports: Vec::new(),
+    })
+}
+
+/// Transform a constant definition
+fn transform_const(const_def: AstConst, resolver: &TypeAliasResolver) -> Result<ConstDefinition> {
+    let name = const_def.name;
+
+    let value = resolver.get_constant(&name).ok_or_else(|| {
+        LumosError::Transform(format!("Constant '{}' was not resolved", name), None)
+    })?;
+
+    let max = match const_def.type_name.as_str() {
+        "u8" => u8::MAX as u128,
+        "u16" => u16::MAX as u128,
+        "u32" => u32::MAX as u128,
+        "u64" | "usize" => u64::MAX as u128,
+        _ => u128::MAX,
+    };
+    if value > max {
+        return Err(LumosError::Transform(
+            format!(
+                "Constant '{}' value {} does not fit in {}",
+                name, value, const_def.type_name
+            ),
+            None,
+        ));
+    }
+
+    Ok(ConstDefinition {
+        name,
+        type_name: const_def.type_name,
+        value,
+        visibility: convert_visibility(&const_def.visibility),
+        module_path: Vec::new(), // Will be set by module resolver if needed
+        reexports: Vec::new(),
+        docs: const_def.docs,
     })
 }
 
@@ -438,139 +786,885 @@ fn convert_visibility(ast_vis: &AstVisibility) -> Visibility {
 }
 
 /// Transform a single struct definition
+///
+/// Errors go to `reported`. The struct is kept without the fields that
+/// failed, so that problems elsewhere, such as a type it names that does not
+/// exist or a second struct of the same name, are still found.
 fn transform_struct(
     struct_def: AstStruct,
     resolver: &TypeAliasResolver,
-) -> Result<StructDefinition> {
+    reported: &mut ErrorCollector,
+) -> StructDefinition {
     // Extract metadata from attributes BEFORE consuming struct
-    let metadata = extract_struct_metadata(&struct_def);
+    let mut metadata = extract_struct_metadata(&struct_def);
+    let mut errors = ErrorCollector::new();
+
+    if struct_def.has_attribute("error_code") {
+        errors.push(LumosError::Transform(
+            format!(
+                "#[error_code] is only supported on enums, found it on struct '{}'",
+                struct_def.name
+            ),
+            None,
+        ));
+    }
+
+    for attr in ["repr", "flags"] {
+        if struct_def.has_attribute(attr) {
+            errors.push(LumosError::Transform(
+                format!(
+                    "#[{}] is only supported on enums, found it on struct '{}'",
+                    attr, struct_def.name
+                ),
+                None,
+            ));
+        }
+    }
+
+    // Events are emitted into logs, never stored in an account
+    if struct_def.has_attribute("event") && struct_def.has_attribute("account") {
+        errors.push(LumosError::Transform(
+            format!(
+                "Struct '{}' cannot be both #[account] and #[event]",
+                struct_def.name
+            ),
+            None,
+        ));
+    }
+
+    if struct_def.has_attribute("zero_copy") {
+        // Pod types have one fixed layout and are never Borsh-encoded
+        let conflict = if !struct_def.type_params.is_empty() {
+            Some("cannot be generic")
+        } else if struct_def.has_attribute("event") {
+            Some("cannot be an #[event]")
+        } else if struct_def.has_attribute("instruction") {
+            Some("cannot be an #[instruction]")
+        } else {
+            None
+        };
+        if let Some(conflict) = conflict {
+            errors.push(LumosError::Transform(
+                format!("#[zero_copy] struct '{}' {}", struct_def.name, conflict),
+                None,
+            ));
+        }
+    }
+
+    metadata.renames = errors
+        .ok(extract_renames(&struct_def.attributes, &struct_def.name))
+        .unwrap_or_default();
+    if is_anchor_named(&metadata) && metadata.renames.contains_key(&NameTarget::Idl) {
+        errors.push(LumosError::Transform(
+            format!(
+                "Cannot rename '{}' in the IDL: Anchor derives its discriminator from the type name",
+                struct_def.name
+            ),
+            None,
+        ));
+    }
 
     // Extract visibility before consuming
     let visibility = convert_visibility(&struct_def.visibility);
 
     let name = struct_def.name;
     let generic_params = struct_def.type_params;
+    let span = struct_def.span;
+
+    // Instruction arguments are ordinary fields serialized after the discriminator
+    metadata.instruction_args = struct_def
+        .instruction_args
+        .into_iter()
+        .filter_map(|f| errors.ok(transform_field(f, resolver)))
+        .collect();
 
     // Transform fields
     let fields = struct_def
         .fields
         .into_iter()
-        .map(|f| transform_field(f, resolver))
-        .collect::<Result<Vec<_>>>()?;
+        .filter_map(|f| errors.ok(transform_field(f, resolver)))
+        .collect();
 
-    Ok(StructDefinition {
+    reported.ok(errors.finish(()).map_err(|e| e.with_span(span)));
+    StructDefinition {
         name,
         generic_params,
         fields,
         metadata,
         visibility,
         module_path: Vec::new(), // Will be set by module resolver if needed
-    })
+        reexports: Vec::new(),
+        span,
+    }
 }
 
 /// Transform a single enum definition
-fn transform_enum(enum_def: AstEnum, resolver: &TypeAliasResolver) -> Result<EnumDefinition> {
+///
+/// Like [`transform_struct`], reports errors to `reported` and keeps the
+/// enum without the variants that failed.
+fn transform_enum(
+    enum_def: AstEnum,
+    resolver: &TypeAliasResolver,
+    reported: &mut ErrorCollector,
+) -> EnumDefinition {
     // Extract metadata from attributes BEFORE consuming enum
-    let metadata = extract_enum_metadata(&enum_def);
+    let mut metadata = extract_enum_metadata(&enum_def);
+    let mut errors = ErrorCollector::new();
+
+    if enum_def.has_attribute("event") {
+        errors.push(LumosError::Transform(
+            format!(
+                "#[event] is only supported on structs, found it on enum '{}'",
+                enum_def.name
+            ),
+            None,
+        ));
+    }
+
+    if enum_def.has_attribute("zero_copy") {
+        errors.push(LumosError::Transform(
+            format!(
+                "#[zero_copy] is only supported on structs, found it on enum '{}'",
+                enum_def.name
+            ),
+            None,
+        ));
+    }
+
+    errors.ok(validate_error_code_enum(&enum_def));
+    metadata.error_code_offset = errors.ok(extract_error_code_offset(&enum_def)).flatten();
+    if enum_def.has_attribute("flags") {
+        metadata.repr = errors.ok(extract_flags_width(&enum_def));
+        errors.ok(validate_flags(&enum_def, metadata.repr.unwrap_or_default()));
+    } else {
+        metadata.repr = errors.ok(extract_enum_repr(&enum_def)).flatten();
+        errors.ok(validate_discriminants(
+            &enum_def,
+            metadata.repr.unwrap_or_default(),
+        ));
+    }
+
+    metadata.renames = errors
+        .ok(extract_renames(&enum_def.attributes, &enum_def.name))
+        .unwrap_or_default();
 
     // Extract visibility before consuming
     let visibility = convert_visibility(&enum_def.visibility);
 
     let name = enum_def.name;
     let generic_params = enum_def.type_params;
+    let span = enum_def.span;
 
     // Transform variants
     let variants = enum_def
         .variants
         .into_iter()
-        .map(|v| transform_enum_variant(v, resolver))
-        .collect::<Result<Vec<_>>>()?;
+        .filter_map(|v| {
+            let span = v.span();
+            errors.ok(transform_enum_variant(v, resolver).map_err(|e| e.with_span(span)))
+        })
+        .collect();
 
-    Ok(EnumDefinition {
+    reported.ok(errors.finish(()).map_err(|e| e.with_span(span)));
+    EnumDefinition {
         name,
         generic_params,
         variants,
         metadata,
         visibility,
         module_path: Vec::new(), // Will be set by module resolver if needed
-    })
+        reexports: Vec::new(),
+        span,
+    }
 }
 
-/// Transform an enum variant
-fn transform_enum_variant(
-    variant: AstEnumVariant,
-    resolver: &TypeAliasResolver,
-) -> Result<EnumVariantDefinition> {
-    match variant {
-        AstEnumVariant::Unit { name, .. } => Ok(EnumVariantDefinition::Unit { name }),
-
-        AstEnumVariant::Tuple { name, types, .. } => {
-            let transformed_types = types
-                .into_iter()
-                .map(|t| transform_type(t, false, resolver))
-                .collect::<Result<Vec<_>>>()?;
-
-            Ok(EnumVariantDefinition::Tuple {
-                name,
-                types: transformed_types,
-            })
-        }
-
-        AstEnumVariant::Struct { name, fields, .. } => {
-            let transformed_fields = fields
-                .into_iter()
-                .map(|f| transform_field(f, resolver))
-                .collect::<Result<Vec<_>>>()?;
-
-            Ok(EnumVariantDefinition::Struct {
-                name,
-                fields: transformed_fields,
-            })
+/// Check the shape of `#[error_code]` enums and the placement of `#[msg]`
+///
+/// Anchor error codes are assigned by variant position, so error enums must be
+/// plain, non-generic lists of unit variants.
+fn validate_error_code_enum(enum_def: &AstEnum) -> Result<()> {
+    let is_error_code = enum_def.has_attribute("error_code");
+
+    if !is_error_code {
+        let with_msg = enum_def.variants.iter().find_map(|v| match v {
+            AstEnumVariant::Unit {
+                name, msg: Some(_), ..
+            } => Some(name),
+            _ => None,
+        });
+        if let Some(variant) = with_msg {
+            return Err(LumosError::Transform(
+                format!(
+                    "#[msg] on variant '{}::{}' requires the enum to be #[error_code]",
+                    enum_def.name, variant
+                ),
+                None,
+            ));
         }
+        return Ok(());
     }
-}
 
-/// Transform a field definition
-fn transform_field(field: AstField, resolver: &TypeAliasResolver) -> Result<FieldDefinition> {
-    let name = field.name;
-    let optional = field.optional;
-    let span = field.span;
+    if !enum_def.type_params.is_empty() {
+        return Err(LumosError::Transform(
+            format!("#[error_code] enum '{}' cannot be generic", enum_def.name),
+            None,
+        ));
+    }
 
-    // Extract deprecation info from attributes
-    let deprecated = extract_deprecation(&field.attributes);
+    if let Some(variant) = enum_def
+        .variants
+        .iter()
+        .find(|v| !matches!(v, AstEnumVariant::Unit { .. }))
+    {
+        return Err(LumosError::Transform(
+            format!(
+                "#[error_code] enum '{}' can only contain unit variants, found '{}'",
+                enum_def.name,
+                variant.name()
+            ),
+            None,
+        ));
+    }
 
-    // Extract anchor attributes
-    let anchor_attrs = extract_anchor_attrs(&field.attributes);
+    Ok(())
+}
 
-    // Transform type using the alias resolver
-    let type_info = transform_type(field.type_spec, optional, resolver)?;
+/// Extract the tag width from `#[repr(u8 | u16 | u32)]`
+fn extract_enum_repr(enum_def: &AstEnum) -> Result<Option<EnumRepr>> {
+    let Some(attr) = enum_def.attributes.iter().find(|attr| attr.name == "repr") else {
+        return Ok(None);
+    };
 
-    Ok(FieldDefinition {
-        name,
-        type_info,
-        optional,
-        deprecated,
-        anchor_attrs,
-        span,
+    let repr = match &attr.value {
+        Some(AttributeValue::String(name)) => EnumRepr::from_name(name.trim()),
+        _ => None,
+    };
+    repr.map(Some).ok_or_else(|| {
+        LumosError::Transform(
+            format!(
+                "Invalid #[repr] on enum '{}': expected #[repr(u8)], #[repr(u16)] or #[repr(u32)]",
+                enum_def.name
+            ),
+            None,
+        )
     })
 }
 
-/// Transform type specification with alias resolution
-fn transform_type(
-    type_spec: AstType,
-    optional: bool,
-    resolver: &TypeAliasResolver,
-) -> Result<TypeInfo> {
-    let base_type = match type_spec {
-        AstType::Primitive(name) => {
-            // Check if it's a known primitive type
+/// Check that discriminants are unique and fit the enum's tag width
+///
+/// Discriminants follow Rust's rules: an implicit value is the previous one
+/// plus one, starting at 0. `#[error_code]` and `#[account]` enums keep
+/// positional tags because Anchor derives their encoding.
+fn validate_discriminants(enum_def: &AstEnum, repr: EnumRepr) -> Result<()> {
+    let explicit = enum_def.variants.iter().any(|v| v.discriminant().is_some());
+    let customized = explicit || repr != EnumRepr::U8;
+
+    if customized && enum_def.has_attribute("error_code") {
+        return Err(LumosError::Transform(
+            format!(
+                "#[error_code] enum '{}' cannot declare discriminants or #[repr]; error codes follow variant order",
+                enum_def.name
+            ),
+            None,
+        ));
+    }
+
+    if customized && enum_def.has_attribute("account") {
+        return Err(LumosError::Transform(
+            format!(
+                "#[account] enum '{}' cannot declare discriminants or #[repr]; Anchor derives its encoding",
+                enum_def.name
+            ),
+            None,
+        ));
+    }
+
+    let mut errors = ErrorCollector::new();
+    let mut seen: HashMap<u64, &str> = HashMap::new();
+    let mut next = 0u64;
+    for variant in &enum_def.variants {
+        let value = variant.discriminant().unwrap_or(next);
+        if value > repr.max_discriminant() {
+            errors.push(
+                LumosError::Transform(
+                    format!(
+                        "Discriminant {} of '{}::{}' does not fit in {}",
+                        value,
+                        enum_def.name,
+                        variant.name(),
+                        repr.name()
+                    ),
+                    None,
+                )
+                .with_span(variant.span()),
+            );
+        }
+        if let Some(previous) = seen.insert(value, variant.name()) {
+            errors.push(
+                LumosError::Transform(
+                    format!(
+                        "Duplicate discriminant {} in enum '{}': '{}' and '{}'",
+                        value,
+                        enum_def.name,
+                        previous,
+                        variant.name()
+                    ),
+                    None,
+                )
+                .with_span(variant.span()),
+            );
+        }
+        next = value.saturating_add(1);
+    }
+
+    errors.finish(())
+}
+
+/// Width of the packed integer of a `#[flags(u8 | u16 | u32)]` enum (u8 when omitted)
+fn extract_flags_width(enum_def: &AstEnum) -> Result<EnumRepr> {
+    let attr = enum_def.attributes.iter().find(|attr| attr.name == "flags");
+    let width = match attr.and_then(|attr| attr.value.as_ref()) {
+        None => Some(EnumRepr::U8),
+        Some(AttributeValue::String(name)) => EnumRepr::from_name(name.trim()),
+        Some(_) => None,
+    };
+    width.ok_or_else(|| {
+        LumosError::Transform(
+            format!(
+                "Invalid #[flags] on enum '{}': expected #[flags(u8)], #[flags(u16)] or #[flags(u32)]",
+                enum_def.name
+            ),
+            None,
+        )
+    })
+}
+
+/// Check that a `#[flags]` enum is a list of distinct single bits that fit its width
+///
+/// An implicit flag takes the bit after the previous one, starting at `1`.
+fn validate_flags(enum_def: &AstEnum, width: EnumRepr) -> Result<()> {
+    let conflicting = ["repr", "error_code", "account"]
+        .into_iter()
+        .find(|attr| enum_def.has_attribute(attr));
+    if let Some(attr) = conflicting {
+        return Err(LumosError::Transform(
+            format!(
+                "#[flags] enum '{}' cannot also be #[{}]",
+                enum_def.name, attr
+            ),
+            None,
+        ));
+    }
+    if !enum_def.type_params.is_empty() {
+        return Err(LumosError::Transform(
+            format!("#[flags] enum '{}' cannot be generic", enum_def.name),
+            None,
+        ));
+    }
+    if enum_def.variants.is_empty() {
+        return Err(LumosError::Transform(
+            format!("#[flags] enum '{}' needs at least one flag", enum_def.name),
+            None,
+        ));
+    }
+
+    let mut errors = ErrorCollector::new();
+    let mut seen: HashMap<u64, &str> = HashMap::new();
+    let mut next = 1u64;
+    for variant in &enum_def.variants {
+        let located =
+            |message: String| LumosError::Transform(message, None).with_span(variant.span());
+        if !matches!(variant, AstEnumVariant::Unit { .. }) {
+            errors.push(located(format!(
+                "Flag '{}::{}' cannot carry data; #[flags] variants are single bits",
+                enum_def.name,
+                variant.name()
+            )));
+        }
+
+        let value = variant.discriminant().unwrap_or(next);
+        if !value.is_power_of_two() {
+            errors.push(located(format!(
+                "Flag '{}::{}' = {} is not a single bit",
+                enum_def.name,
+                variant.name(),
+                value
+            )));
+        } else if value > width.max_discriminant() {
+            errors.push(located(format!(
+                "Flag '{}::{}' ({}) does not fit in {}",
+                enum_def.name,
+                variant.name(),
+                value,
+                width.name()
+            )));
+        } else if let Some(previous) = seen.insert(value, variant.name()) {
+            errors.push(located(format!(
+                "Duplicate flag bit {} in enum '{}': '{}' and '{}'",
+                value,
+                enum_def.name,
+                previous,
+                variant.name()
+            )));
+        }
+        next = value.saturating_mul(2);
+    }
+
+    errors.finish(())
+}
+
+/// Extract `N` from `#[error_code(offset = N)]`
+fn extract_error_code_offset(enum_def: &AstEnum) -> Result<Option<u32>> {
+    let Some(attr) = enum_def
+        .attributes
+        .iter()
+        .find(|attr| attr.name == "error_code")
+    else {
+        return Ok(None);
+    };
+
+    let offset = match &attr.value {
+        None => None,
+        Some(AttributeValue::String(args)) => Some(
+            args.split_once('=')
+                .filter(|(key, _)| key.trim() == "offset")
+                .and_then(|(_, value)| value.trim().parse::<u32>().ok())
+                .ok_or_else(|| {
+                    LumosError::Transform(
+                        format!(
+                            "Invalid #[error_code({})] on enum '{}': expected #[error_code(offset = N)]",
+                            args, enum_def.name
+                        ),
+                        None,
+                    )
+                })?,
+        ),
+        Some(_) => {
+            return Err(LumosError::Transform(
+                format!(
+                    "Invalid #[error_code] on enum '{}': expected #[error_code(offset = N)]",
+                    enum_def.name
+                ),
+                None,
+            ))
+        }
+    };
+
+    // Codes run from the offset, one per variant, and must all fit in a u32
+    let first = offset.unwrap_or(ANCHOR_ERROR_CODE_OFFSET);
+    let last = u64::from(first) + enum_def.variants.len().saturating_sub(1) as u64;
+    if last > u64::from(u32::MAX) {
+        return Err(LumosError::Transform(
+            format!(
+                "Error codes of enum '{}' overflow u32: {} variants starting at offset {} end at {}, above {}",
+                enum_def.name,
+                enum_def.variants.len(),
+                first,
+                last,
+                u32::MAX
+            ),
+            None,
+        ));
+    }
+
+    Ok(offset)
+}
+
+/// Transform an enum variant
+fn transform_enum_variant(
+    variant: AstEnumVariant,
+    resolver: &TypeAliasResolver,
+) -> Result<EnumVariantDefinition> {
+    match variant {
+        AstEnumVariant::Unit {
+            name,
+            docs,
+            msg,
+            discriminant,
+            ..
+        } => Ok(EnumVariantDefinition::Unit {
+            name,
+            docs,
+            msg,
+            discriminant,
+        }),
+
+        AstEnumVariant::Tuple {
+            name,
+            types,
+            docs,
+            discriminant,
+            ..
+        } => {
+            let mut errors = ErrorCollector::new();
+            let transformed_types = types
+                .into_iter()
+                .filter_map(|t| errors.ok(transform_type(t, false, resolver)))
+                .collect();
+
+            errors.finish(EnumVariantDefinition::Tuple {
+                name,
+                types: transformed_types,
+                docs,
+                discriminant,
+            })
+        }
+
+        AstEnumVariant::Struct {
+            name,
+            fields,
+            docs,
+            discriminant,
+            ..
+        } => {
+            let mut errors = ErrorCollector::new();
+            // Validators and Default helpers are generated per struct; enum
+            // variants have nowhere to put them
+            for field in &fields {
+                for attr in ["range", "pattern", "default"] {
+                    if field.has_attribute(attr) {
+                        errors.push(LumosError::Transform(
+                            format!(
+                                "#[{}] is only supported on struct fields, found it on '{}::{}'",
+                                attr, name, field.name
+                            ),
+                            field.span.map(SourceLocation::from_span),
+                        ));
+                    }
+                }
+            }
+            let transformed_fields = fields
+                .into_iter()
+                .filter_map(|f| errors.ok(transform_field(f, resolver)))
+                .collect();
+
+            errors.finish(EnumVariantDefinition::Struct {
+                name,
+                fields: transformed_fields,
+                docs,
+                discriminant,
+            })
+        }
+    }
+}
+
+/// Transform a field definition
+fn transform_field(field: AstField, resolver: &TypeAliasResolver) -> Result<FieldDefinition> {
+    let max_length = field
+        .max_length_expr()
+        .map(|expr| resolver.resolve_const_expr(&expr))
+        .transpose()
+        .map_err(|e| {
+            LumosError::Transform(
+                format!("Invalid #[max] bound on field '{}': {}", field.name, e),
+                None,
+            )
+        })
+        .map(|max| max.map(|n| usize::try_from(n).unwrap_or(usize::MAX)));
+    let range = field.range().cloned();
+    let pattern = field.pattern().map(str::to_string);
+    let default = match field.default_value() {
+        Some(value) => Ok(Some(value.clone())),
+        None if field.has_attribute("default") => Err(LumosError::Transform(
+            format!(
+                "#[default] on field '{}' needs a value, e.g. #[default(0)]",
+                field.name
+            ),
+            None,
+        )),
+        None => Ok(None),
+    };
+    let renames = extract_renames(&field.attributes, &field.name);
+    let name = field.name;
+    let optional = field.optional;
+    let span = field.span;
+    let docs = field.docs;
+
+    // Extract deprecation info from attributes
+    let deprecated = extract_deprecation(&field.attributes);
+
+    // Extract anchor attributes
+    let anchor_attrs = extract_anchor_attrs(&field.attributes);
+
+    // Transform type using the alias resolver
+    let ((max_length, renames), type_info) = join(
+        join(max_length, renames),
+        transform_type(field.type_spec, optional, resolver),
+    )
+    .map_err(|e| e.with_span(span))?;
+
+    let constraints = transform_constraints(&name, range, pattern, &type_info, resolver)
+        .map_err(|e| e.with_span(span))?;
+    let default = default
+        .and_then(|default| {
+            default
+                .map(|d| {
+                    transform_default(&name, d, &type_info, &constraints, max_length, resolver)
+                })
+                .transpose()
+        })
+        .map_err(|e| e.with_span(span))?;
+
+    Ok(FieldDefinition {
+        name,
+        type_info,
+        optional,
+        deprecated,
+        anchor_attrs,
+        docs,
+        span,
+        max_length,
+        constraints,
+        default,
+        renames,
+    })
+}
+
+/// Check `#[range]` and `#[pattern]` against the field type and resolve their bounds
+fn transform_constraints(
+    field_name: &str,
+    range: Option<RangeExpr>,
+    pattern: Option<String>,
+    type_info: &TypeInfo,
+    resolver: &TypeAliasResolver,
+) -> Result<Vec<Constraint>> {
+    // Optional fields are validated only when present
+    let value_type = match type_info {
+        TypeInfo::Option(inner) => inner.as_ref(),
+        other => other,
+    };
+    let mut errors = ErrorCollector::new();
+    let mut constraints = Vec::new();
+
+    if let Some(range) = range {
+        let constraint = integer_limits(value_type)
+            .ok_or_else(|| {
+                LumosError::Transform(
+                    format!(
+                        "#[range] on field '{}' requires an integer type",
+                        field_name
+                    ),
+                    None,
+                )
+            })
+            .and_then(|limits| resolve_range(field_name, &range, limits, resolver));
+        // A range spanning the whole type cannot fail and needs no check
+        constraints.extend(errors.ok(constraint).filter(|c| {
+            *c != Constraint::Range {
+                min: None,
+                max: None,
+            }
+        }));
+    }
+
+    if let Some(pattern) = pattern {
+        if !matches!(value_type, TypeInfo::Primitive(name) if name == "String") {
+            errors.push(LumosError::Transform(
+                format!(
+                    "#[pattern] on field '{}' requires a String type",
+                    field_name
+                ),
+                None,
+            ));
+        } else if let Err(e) = regex::Regex::new(&pattern) {
+            errors.push(LumosError::Transform(
+                format!("Invalid #[pattern] on field '{}': {}", field_name, e),
+                None,
+            ));
+        } else {
+            constraints.push(Constraint::Pattern(pattern));
+        }
+    }
+
+    errors.finish(constraints)
+}
+
+/// Check a `#[default(..)]` value against the field type, constraints and `#[max]`
+///
+/// Optional fields are rejected: they already default to `None`.
+fn transform_default(
+    field_name: &str,
+    default: DefaultExpr,
+    type_info: &TypeInfo,
+    constraints: &[Constraint],
+    max_length: Option<usize>,
+    resolver: &TypeAliasResolver,
+) -> Result<DefaultValue> {
+    let invalid = |reason: String| {
+        LumosError::Transform(
+            format!(
+                "Invalid #[default({})] on field '{}': {}",
+                default, field_name, reason
+            ),
+            None,
+        )
+    };
+
+    let type_name = match type_info {
+        TypeInfo::Primitive(name) => name.as_str(),
+        TypeInfo::Option(_) => {
+            return Err(invalid(
+                "optional fields always default to None".to_string(),
+            ))
+        }
+        _ => {
+            return Err(invalid(
+                "defaults are supported on integer, float, bool and String fields".to_string(),
+            ))
+        }
+    };
+    let resolved = match &default {
+        DefaultExpr::Named(name) => {
+            let value = resolver
+                .resolve_const_expr(&ConstExpr::Named(name.clone()))
+                .map_err(|e| invalid(e.message()))?;
+            let value = i128::try_from(value)
+                .map_err(|_| invalid(format!("{} is out of range for {}", value, type_name)))?;
+            DefaultExpr::Integer(value)
+        }
+        other => other.clone(),
+    };
+
+    let value = match (resolved, integer_limits(type_info)) {
+        (DefaultExpr::Integer(value), Some((min, max))) => {
+            if value < min || value > max {
+                return Err(invalid(format!(
+                    "{} is out of range for {}",
+                    value, type_name
+                )));
+            }
+            DefaultValue::Integer(value)
+        }
+        (DefaultExpr::Integer(value), None) if matches!(type_name, "f32" | "f64") => {
+            DefaultValue::Float(value as f64)
+        }
+        (DefaultExpr::Float(value), None) if matches!(type_name, "f32" | "f64") => {
+            DefaultValue::Float(value)
+        }
+        (DefaultExpr::Bool(value), None) if type_name == "bool" => DefaultValue::Bool(value),
+        (DefaultExpr::String(value), None) if type_name == "String" => {
+            if let Some(max) = max_length.filter(|max| value.len() > *max) {
+                return Err(invalid(format!("longer than #[max({})]", max)));
+            }
+            DefaultValue::String(value)
+        }
+        _ => return Err(invalid(format!("not a valid {} value", type_name))),
+    };
+
+    // The default has to pass the generated validator
+    for constraint in constraints {
+        let satisfied = match (constraint, &value) {
+            (Constraint::Range { min, max }, DefaultValue::Integer(value)) => {
+                min.is_none_or(|min| *value >= min) && max.is_none_or(|max| *value <= max)
+            }
+            (Constraint::Pattern(pattern), DefaultValue::String(value)) => {
+                regex::Regex::new(pattern).is_ok_and(|re| re.is_match(value))
+            }
+            _ => true,
+        };
+        if !satisfied {
+            return Err(invalid(format!("value {}", constraint)));
+        }
+    }
+
+    Ok(value)
+}
+
+/// Smallest and largest value of an integer primitive (u128 is capped at i128::MAX)
+fn integer_limits(type_info: &TypeInfo) -> Option<(i128, i128)> {
+    let TypeInfo::Primitive(name) = type_info else {
+        return None;
+    };
+    let limits = match name.as_str() {
+        "u8" => (0, u8::MAX as i128),
+        "u16" => (0, u16::MAX as i128),
+        "u32" => (0, u32::MAX as i128),
+        "u64" => (0, u64::MAX as i128),
+        "u128" => (0, i128::MAX),
+        "i8" => (i8::MIN as i128, i8::MAX as i128),
+        "i16" => (i16::MIN as i128, i16::MAX as i128),
+        "i32" => (i32::MIN as i128, i32::MAX as i128),
+        "i64" => (i64::MIN as i128, i64::MAX as i128),
+        "i128" => (i128::MIN, i128::MAX),
+        _ => return None,
+    };
+    Some(limits)
+}
+
+/// Resolve a range to inclusive bounds and check them against the field type
+///
+/// Bounds equal to the type's own limits are dropped, since they cannot fail.
+fn resolve_range(
+    field_name: &str,
+    range: &RangeExpr,
+    (type_min, type_max): (i128, i128),
+    resolver: &TypeAliasResolver,
+) -> Result<Constraint> {
+    let invalid = |reason: String| {
+        LumosError::Transform(
+            format!(
+                "Invalid #[range({})] on field '{}': {}",
+                range, field_name, reason
+            ),
+            None,
+        )
+    };
+    let resolve = |bound: &RangeBound| match bound {
+        RangeBound::Literal(value) => Ok(*value),
+        RangeBound::Named(name) => {
+            let value = resolver
+                .resolve_const_expr(&ConstExpr::Named(name.clone()))
+                .map_err(|e| invalid(e.message()))?;
+            i128::try_from(value)
+                .map_err(|_| invalid(format!("{} is out of range for the field type", value)))
+        }
+    };
+
+    let min = range.start.as_ref().map(resolve).transpose()?;
+    let max = match range.end.as_ref().map(resolve).transpose()? {
+        Some(end) if !range.inclusive => Some(
+            end.checked_sub(1)
+                .ok_or_else(|| invalid("empty range".to_string()))?,
+        ),
+        end => end,
+    };
+
+    for bound in min.iter().chain(max.iter()) {
+        if *bound < type_min || *bound > type_max {
+            return Err(invalid(format!(
+                "{} is out of range for the field type",
+                bound
+            )));
+        }
+    }
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            return Err(invalid("empty range".to_string()));
+        }
+    }
+
+    Ok(Constraint::Range {
+        min: min.filter(|&min| min != type_min),
+        max: max.filter(|&max| max != type_max),
+    })
+}
+
+/// Transform type specification with alias resolution
+fn transform_type(
+    type_spec: AstType,
+    optional: bool,
+    resolver: &TypeAliasResolver,
+) -> Result<TypeInfo> {
+    let base_type = match type_spec {
+        AstType::Primitive(name) => {
+            // Check if it's a known primitive type
             if is_valid_primitive_type(&name) {
                 // Map TypeScript-friendly aliases to Rust types
                 let rust_type = map_type_alias(&name);
                 TypeInfo::Primitive(rust_type)
-            } else if let Some(resolved) = resolver.get_resolved(&name) {
+            } else if let Some(resolved) = resolver.alias_reference(&name) {
                 // It's a type alias, use the resolved type
-                resolved.clone()
+                resolved
             } else {
                 // Treat as user-defined type (enum or struct defined in schema)
                 // Validation of whether the type actually exists happens in a later phase
@@ -592,14 +1686,57 @@ fn transform_type(
             let element_type = transform_type(*element, false, resolver)?;
             TypeInfo::FixedArray {
                 element: Box::new(element_type),
-                size,
+                size: resolver.resolve_array_size(&size)?,
+            }
+        }
+
+        AstType::Map {
+            key,
+            value,
+            ordered,
+        } => {
+            let key_type = transform_type(*key, false, resolver)?;
+            let value_type = transform_type(*value, false, resolver)?;
+            TypeInfo::Map {
+                key: Box::new(key_type),
+                value: Box::new(value_type),
+                ordered,
+            }
+        }
+
+        AstType::Set { element, ordered } => {
+            let element_type = transform_type(*element, false, resolver)?;
+            TypeInfo::Set {
+                element: Box::new(element_type),
+                ordered,
             }
         }
 
+        AstType::Tuple(elements) => TypeInfo::Tuple(
+            elements
+                .into_iter()
+                .map(|e| transform_type(e, false, resolver))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+
+        AstType::Boxed(inner) => {
+            TypeInfo::Boxed(Box::new(transform_type(*inner, false, resolver)?))
+        }
+
+        AstType::FixedString(size) => TypeInfo::FixedString(resolver.resolve_array_size(&size)?),
+
+        AstType::Instance { name, args } => TypeInfo::Instance {
+            name,
+            args: args
+                .into_iter()
+                .map(|a| transform_type(a, false, resolver))
+                .collect::<Result<Vec<_>>>()?,
+        },
+
         AstType::UserDefined(name) => {
             // Check if it's a type alias first
-            if let Some(resolved) = resolver.get_resolved(&name) {
-                resolved.clone()
+            if let Some(resolved) = resolver.alias_reference(&name) {
+                resolved
             } else {
                 // User-defined type (struct/enum)
                 // Validated after full transformation via validate_user_defined_types()
@@ -653,6 +1790,227 @@ fn map_type_alias(name: &str) -> String {
     }
 }
 
+/// Extract the per-target names of `#[rename(ts = "..", ..)]`
+fn extract_renames(attributes: &[Attribute], name: &str) -> Result<Renames> {
+    let Some(attr) = attributes.iter().find(|a| a.name == "rename") else {
+        return Ok(Renames::new());
+    };
+    let pairs = attr.names().ok_or_else(|| {
+        LumosError::Transform(
+            format!(
+                "#[rename] on '{}' needs target names, e.g. #[rename(ts = \"{}\")]",
+                name, name
+            ),
+            None,
+        )
+    })?;
+
+    let mut errors = ErrorCollector::new();
+    let mut renames = Renames::new();
+    for (key, renamed) in pairs {
+        let Some(target) = NameTarget::from_key(key) else {
+            errors.push(LumosError::Transform(
+                format!(
+                    "Unknown #[rename] target '{}' on '{}', expected one of: {}",
+                    key,
+                    name,
+                    NameTarget::ALL.map(NameTarget::key).join(", ")
+                ),
+                None,
+            ));
+            continue;
+        };
+        if !is_identifier(renamed) {
+            errors.push(LumosError::Transform(
+                format!(
+                    "Invalid #[rename] of '{}' for {}: '{}' is not an identifier",
+                    name, target, renamed
+                ),
+                None,
+            ));
+            continue;
+        }
+        if renamed != name {
+            renames.insert(target, renamed.clone());
+        }
+    }
+    errors.finish(renames)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Whether Anchor derives a discriminator from this type's name
+fn is_anchor_named(metadata: &Metadata) -> bool {
+    metadata.is_instruction
+        || metadata
+            .attributes
+            .iter()
+            .any(|a| a == "account" || a == "event")
+}
+
+/// Apply the file's `#![rename_fields]`/`#![rename_types]` policy and check for name clashes
+///
+/// Explicit `#[rename]`s take precedence over the policy. Types Anchor
+/// derives discriminators from keep their IDL name.
+fn resolve_names(attributes: &[Attribute], type_defs: &mut [TypeDefinition]) -> Result<()> {
+    let mut errors = ErrorCollector::new();
+    let field_policy = errors
+        .ok(extract_naming_policy(attributes, "rename_fields"))
+        .unwrap_or_default();
+    let type_policy = errors
+        .ok(extract_naming_policy(attributes, "rename_types"))
+        .unwrap_or_default();
+
+    let apply = |policy: &[(NameTarget, Case)], name: &str, renames: &mut Renames, anchor_named| {
+        for &(target, case) in policy {
+            if anchor_named && target == NameTarget::Idl {
+                continue;
+            }
+            let renamed = case.apply(name);
+            if renamed != name {
+                renames.entry(target).or_insert(renamed);
+            }
+        }
+    };
+
+    for type_def in type_defs.iter_mut() {
+        let (name, metadata, fields): (&str, &mut Metadata, Vec<&mut FieldDefinition>) =
+            match type_def {
+                TypeDefinition::Struct(s) => {
+                    (&s.name, &mut s.metadata, s.fields.iter_mut().collect())
+                }
+                TypeDefinition::Enum(e) => (
+                    &e.name,
+                    &mut e.metadata,
+                    e.variants
+                        .iter_mut()
+                        .flat_map(|v| match v {
+                            EnumVariantDefinition::Struct { fields, .. } => {
+                                fields.iter_mut().collect()
+                            }
+                            _ => Vec::new(),
+                        })
+                        .collect(),
+                ),
+                TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => continue,
+            };
+        let anchor_named = is_anchor_named(metadata);
+        apply(&type_policy, name, &mut metadata.renames, anchor_named);
+        for field in fields.into_iter().chain(&mut metadata.instruction_args) {
+            apply(&field_policy, &field.name, &mut field.renames, false);
+        }
+    }
+
+    // Renamed fields and types must still be unique in every target
+    for target in NameTarget::ALL {
+        let mut type_names: HashMap<&str, &str> = HashMap::new();
+        for type_def in type_defs.iter() {
+            let Some(metadata) = type_def.metadata() else {
+                continue;
+            };
+            let renamed = metadata
+                .renames
+                .get(&target)
+                .map_or(type_def.name(), String::as_str);
+            // Same-named definitions are reported as duplicates elsewhere
+            let clash = type_names
+                .insert(renamed, type_def.name())
+                .filter(|&other| other != type_def.name());
+            if let Some(other) = clash {
+                errors.push(LumosError::Transform(
+                    format!(
+                        "Types '{}' and '{}' are both named '{}' in {}",
+                        other,
+                        type_def.name(),
+                        renamed,
+                        target
+                    ),
+                    type_def.span().map(SourceLocation::from_span),
+                ));
+            }
+
+            let field_lists: Vec<&[FieldDefinition]> = match type_def {
+                TypeDefinition::Struct(s) => vec![&s.fields, &s.metadata.instruction_args],
+                TypeDefinition::Enum(e) => e
+                    .variants
+                    .iter()
+                    .filter_map(|v| match v {
+                        EnumVariantDefinition::Struct { fields, .. } => Some(fields.as_slice()),
+                        _ => None,
+                    })
+                    .collect(),
+                TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => Vec::new(),
+            };
+            for fields in field_lists {
+                let mut field_names: HashMap<&str, &str> = HashMap::new();
+                for field in fields {
+                    let renamed = field.renames.get(&target).map_or(&field.name, |n| n);
+                    let clash = field_names
+                        .insert(renamed, &field.name)
+                        .filter(|&other| other != field.name);
+                    if let Some(other) = clash {
+                        errors.push(LumosError::Transform(
+                            format!(
+                                "Fields '{}' and '{}' of '{}' are both named '{}' in {}",
+                                other,
+                                field.name,
+                                type_def.name(),
+                                renamed,
+                                target
+                            ),
+                            field.span.map(SourceLocation::from_span),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    errors.finish(())
+}
+
+/// Parse a `#![rename_fields(ts = "camelCase", ..)]`-style policy
+fn extract_naming_policy(attributes: &[Attribute], name: &str) -> Result<Vec<(NameTarget, Case)>> {
+    let mut errors = ErrorCollector::new();
+    let mut policy = Vec::new();
+    for attr in attributes.iter().filter(|a| a.name == name) {
+        let located = |message: String| {
+            LumosError::Transform(message, attr.span.map(SourceLocation::from_span))
+        };
+        let Some(pairs) = attr.names() else {
+            errors.push(located(format!(
+                "#![{}] needs naming conventions, e.g. #![{}(ts = \"camelCase\")]",
+                name, name
+            )));
+            continue;
+        };
+        for (key, convention) in pairs {
+            match (NameTarget::from_key(key), Case::from_name(convention)) {
+                (Some(target), Some(case)) => policy.push((target, case)),
+                (None, _) => errors.push(located(format!(
+                    "Unknown #![{}] target '{}', expected one of: {}",
+                    name,
+                    key,
+                    NameTarget::ALL.map(NameTarget::key).join(", ")
+                ))),
+                (_, None) => errors.push(located(format!(
+                    "Unknown naming convention '{}' in #![{}], expected one of: {}",
+                    convention,
+                    name,
+                    Case::ALL.map(Case::name).join(", ")
+                ))),
+            }
+        }
+    }
+    errors.finish(policy)
+}
+
 /// Extract metadata from struct attributes
 fn extract_struct_metadata(struct_def: &AstStruct) -> Metadata {
     Metadata {
@@ -666,6 +2024,12 @@ fn extract_struct_metadata(struct_def: &AstStruct) -> Metadata {
         custom_derives: extract_custom_derives(&struct_def.attributes),
         is_instruction: struct_def.has_attribute("instruction"),
         anchor_attrs: extract_anchor_attrs(&struct_def.attributes),
+        docs: struct_def.docs.clone(),
+        error_code_offset: None,
+        instruction_args: Vec::new(),
+        repr: None,
+        c_layout: None,
+        renames: Default::default(),
     }
 }
 
@@ -682,6 +2046,12 @@ fn extract_enum_metadata(enum_def: &AstEnum) -> Metadata {
         custom_derives: extract_custom_derives(&enum_def.attributes),
         is_instruction: false, // Enums don't have instruction attribute
         anchor_attrs: Vec::new(),
+        docs: enum_def.docs.clone(),
+        error_code_offset: None,
+        instruction_args: Vec::new(),
+        repr: None,
+        c_layout: None,
+        renames: Default::default(),
     }
 }
 
@@ -810,7 +2180,7 @@ pub fn collect_deprecation_warnings(type_defs: &[TypeDefinition]) -> Vec<crate::
             TypeDefinition::Enum(e) => {
                 // Check enum struct variant fields
                 for variant in &e.variants {
-                    if let EnumVariantDefinition::Struct { name, fields } = variant {
+                    if let EnumVariantDefinition::Struct { name, fields, .. } = variant {
                         for field in fields {
                             if let Some(msg) = &field.deprecated {
                                 warnings.push(Warning {
@@ -824,8 +2194,8 @@ pub fn collect_deprecation_warnings(type_defs: &[TypeDefinition]) -> Vec<crate::
                     }
                 }
             }
-            TypeDefinition::TypeAlias(_) => {
-                // Type aliases don't have fields that can be deprecated
+            TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {
+                // Type aliases and constants don't have fields that can be deprecated
             }
         }
     }
@@ -833,10 +2203,313 @@ pub fn collect_deprecation_warnings(type_defs: &[TypeDefinition]) -> Vec<crate::
     warnings
 }
 
-/// Validate that all user-defined type references are defined in the schema
+/// A concrete instantiation of a generic type used in the schema
+#[derive(Debug, Clone)]
+pub struct GenericInstance {
+    /// Name of the generic type (e.g., `Wrapper`)
+    pub generic: String,
+    /// Concrete type arguments (e.g., `[u64]` for `Wrapper<u64>`)
+    pub args: Vec<TypeInfo>,
+    /// The generic definition with its parameters substituted, named after
+    /// the instance (e.g., `WrapperU64`)
+    pub definition: TypeDefinition,
+}
+
+/// Collect every concrete instantiation of a generic type used in the schema
 ///
-/// This function ensures type safety by catching references to undefined types
-/// during transformation rather than at Rust/TypeScript compile time.
+/// Starting from the non-generic types, each `Wrapper<u64>` found yields a
+/// copy of `Wrapper` with `T` replaced by `u64`, named by
+/// [`TypeInfo::pascal_name`]. Instances nested in other instances (an
+/// `Outer<T>` field of type `Wrapper<T>`) are found transitively. Within the
+/// returned definitions, instances are referenced by their concrete name.
+///
+/// Instances are returned in the order they are first used. Instances whose
+/// arguments nest deeper than [`MAX_GENERIC_DEPTH`], which only a generic
+/// containing itself with growing arguments produces, are left out; see
+/// [`validate_user_defined_types`].
+pub fn collect_generic_instances(type_defs: &[TypeDefinition]) -> Vec<GenericInstance> {
+    generic_instances(type_defs).0
+}
+
+/// How deep the arguments of a generic instance may nest
+///
+/// A generic that contains itself with other arguments
+/// (`struct Nested<T> { inner: Option<Box<Nested<Vec<T>>>> }`) has an
+/// instance for every depth; the limit ends the search for them.
+pub const MAX_GENERIC_DEPTH: usize = 32;
+
+/// Generic instances, with the generics whose instances exceeded
+/// [`MAX_GENERIC_DEPTH`]
+fn generic_instances(type_defs: &[TypeDefinition]) -> (Vec<GenericInstance>, Vec<String>) {
+    let generics: HashMap<&str, &TypeDefinition> = type_defs
+        .iter()
+        .filter(|t| !t.generic_params().is_empty())
+        .map(|t| (t.name(), t))
+        .collect();
+    if generics.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    let mut pending: VecDeque<(String, Vec<TypeInfo>)> = type_defs
+        .iter()
+        .filter(|t| t.generic_params().is_empty())
+        .flat_map(|t| t.field_types())
+        .flat_map(instances_in)
+        .collect();
+    let mut seen = HashSet::new();
+    let mut instances = Vec::new();
+    let mut unbounded = Vec::new();
+
+    while let Some((name, args)) = pending.pop_front() {
+        let Some(generic) = generics.get(name.as_str()) else {
+            continue;
+        };
+        if args.iter().map(nesting_depth).max().unwrap_or(0) > MAX_GENERIC_DEPTH {
+            if !unbounded.contains(&name) {
+                unbounded.push(name);
+            }
+            continue;
+        }
+        let concrete_name = TypeInfo::Instance {
+            name: name.clone(),
+            args: args.clone(),
+        }
+        .pascal_name();
+        if !seen.insert(concrete_name) {
+            continue;
+        }
+
+        let mut definition = instantiate_generic(generic, &args);
+        for type_info in definition.field_types_mut() {
+            pending.extend(instances_in(type_info));
+            concretize_instances(type_info);
+        }
+
+        instances.push(GenericInstance {
+            generic: name,
+            args,
+            definition,
+        });
+    }
+
+    (instances, unbounded)
+}
+
+/// How many types are nested in `type_info` (1 for `u64`, 2 for `Vec<u64>`)
+fn nesting_depth(type_info: &TypeInfo) -> usize {
+    let inner = match type_info {
+        TypeInfo::Primitive(_)
+        | TypeInfo::Generic(_)
+        | TypeInfo::UserDefined(_)
+        | TypeInfo::FixedString(_) => 0,
+        TypeInfo::Array(inner) | TypeInfo::Option(inner) | TypeInfo::Boxed(inner) => {
+            nesting_depth(inner)
+        }
+        TypeInfo::FixedArray { element, .. } | TypeInfo::Set { element, .. } => {
+            nesting_depth(element)
+        }
+        TypeInfo::Map { key, value, .. } => nesting_depth(key).max(nesting_depth(value)),
+        TypeInfo::Tuple(elements) | TypeInfo::Instance { args: elements, .. } => {
+            elements.iter().map(nesting_depth).max().unwrap_or(0)
+        }
+    };
+    inner + 1
+}
+
+/// Reject generics that contain themselves with other type arguments
+///
+/// `struct Nested<T> { inner: Option<Box<Nested<Vec<T>>>> }` used as
+/// `Nested<u8>` needs `Nested<Vec<u8>>`, which needs `Nested<Vec<Vec<u8>>>`,
+/// and so on: no finite set of concrete types describes it.
+fn validate_generic_recursion(type_defs: &[TypeDefinition]) -> Result<()> {
+    let mut errors = ErrorCollector::new();
+    for name in generic_instances(type_defs).1 {
+        let span = type_defs
+            .iter()
+            .find(|t| t.name() == name)
+            .and_then(|t| t.span());
+        errors.push(
+            LumosError::TypeValidation(
+                format!(
+                    "Generic type '{}' contains itself with different type arguments, \
+                     so it has infinitely many instances. A generic type can only \
+                     contain itself with its own type parameters, e.g. Box<{}<T>>",
+                    name, name
+                ),
+                None,
+            )
+            .with_span(span),
+        );
+    }
+    errors.finish(())
+}
+
+/// Substitute `args` for the type parameters of `generic`
+///
+/// The result is named after the instance (`WrapperU64` for `Wrapper<u64>`)
+/// and has no type parameters. Generic instances inside its fields are kept
+/// as they are.
+pub fn instantiate_generic(generic: &TypeDefinition, args: &[TypeInfo]) -> TypeDefinition {
+    let bindings: HashMap<&str, &TypeInfo> = generic
+        .generic_params()
+        .iter()
+        .map(String::as_str)
+        .zip(args)
+        .collect();
+    let concrete_name = TypeInfo::Instance {
+        name: generic.name().to_string(),
+        args: args.to_vec(),
+    }
+    .pascal_name();
+
+    let mut definition = generic.clone();
+    for type_info in definition.field_types_mut() {
+        type_info.walk_mut(&mut |t| {
+            if let TypeInfo::Generic(param) = t {
+                if let Some(&bound) = bindings.get(param.as_str()) {
+                    *t = bound.clone();
+                }
+            }
+        });
+    }
+    match &mut definition {
+        TypeDefinition::Struct(s) => {
+            s.name = concrete_name;
+            s.generic_params.clear();
+        }
+        TypeDefinition::Enum(e) => {
+            e.name = concrete_name;
+            e.generic_params.clear();
+        }
+        TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {}
+    }
+    definition
+}
+
+/// Replace each generic type by its concrete instances
+///
+/// Formats without generics, such as the Anchor IDL and Borsh schemas, are
+/// generated from the result: `Wrapper<u64>` becomes a `WrapperU64` type
+/// defined where `Wrapper` was, and generic types that are never
+/// instantiated are dropped. Rust output keeps the generic definitions.
+pub fn monomorphize(type_defs: &[TypeDefinition]) -> Vec<TypeDefinition> {
+    let instances = collect_generic_instances(type_defs);
+    let mut result = Vec::with_capacity(type_defs.len() + instances.len());
+
+    for type_def in type_defs {
+        if !type_def.generic_params().is_empty() {
+            result.extend(
+                instances
+                    .iter()
+                    .filter(|i| i.generic == type_def.name())
+                    .map(|i| i.definition.clone()),
+            );
+            continue;
+        }
+
+        let mut type_def = type_def.clone();
+        for type_info in type_def.field_types_mut() {
+            concretize_instances(type_info);
+        }
+        result.push(type_def);
+    }
+
+    result
+}
+
+/// Replace every reference to a `#[newtype]` by its underlying type
+///
+/// Newtypes share their target's wire format, so formats that only describe
+/// bytes (the Anchor IDL) or whose generators have no distinct-type support
+/// are generated from the result. The newtype definitions stay, as plain
+/// aliases.
+pub fn inline_newtypes(type_defs: &[TypeDefinition]) -> Vec<TypeDefinition> {
+    let targets: HashMap<&str, &TypeInfo> = type_defs
+        .iter()
+        .filter_map(|t| match t {
+            TypeDefinition::TypeAlias(a) if a.newtype => Some((a.name.as_str(), &a.target)),
+            _ => None,
+        })
+        .collect();
+    if targets.is_empty() {
+        return type_defs.to_vec();
+    }
+
+    let mut result = type_defs.to_vec();
+    for type_def in &mut result {
+        if let TypeDefinition::TypeAlias(a) = type_def {
+            a.newtype = false;
+        }
+        for type_info in type_def.field_types_mut() {
+            type_info.walk_mut(&mut |t| {
+                // Newtype cycles are rejected by the resolver, so this ends
+                while let Some(target) = match t {
+                    TypeInfo::UserDefined(name) => targets.get(name.as_str()),
+                    _ => None,
+                } {
+                    *t = (*target).clone();
+                }
+            });
+        }
+    }
+    result
+}
+
+/// Warn about generic types that are never used with concrete arguments
+///
+/// Such types still appear in Rust output, but have no concrete layout to
+/// put in the Anchor IDL or Borsh schemas.
+pub fn collect_generic_warnings(type_defs: &[TypeDefinition]) -> Vec<crate::ir::Warning> {
+    use crate::ir::{Warning, WarningKind};
+
+    let instances = collect_generic_instances(type_defs);
+
+    type_defs
+        .iter()
+        .filter(|t| !t.generic_params().is_empty())
+        .filter(|t| !instances.iter().any(|i| i.generic == t.name()))
+        .map(|t| Warning {
+            type_name: t.name().to_string(),
+            field_name: None,
+            message: format!(
+                "generic type is never instantiated (e.g. '{}<{}>'), so it is left out of \
+                 the Anchor IDL and Borsh schemas",
+                t.name(),
+                t.generic_params()
+                    .iter()
+                    .map(|_| "u64")
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            kind: WarningKind::UninstantiatedGeneric,
+        })
+        .collect()
+}
+
+/// Generic instances used anywhere inside `type_info`, outermost first
+fn instances_in(type_info: &TypeInfo) -> Vec<(String, Vec<TypeInfo>)> {
+    let mut found = Vec::new();
+    type_info.walk(&mut |t| {
+        if let TypeInfo::Instance { name, args } = t {
+            found.push((name.clone(), args.clone()));
+        }
+    });
+    found
+}
+
+/// Refer to each generic instance in `type_info` by its concrete type name
+fn concretize_instances(type_info: &mut TypeInfo) {
+    type_info.walk_mut(&mut |t| {
+        if let TypeInfo::Instance { .. } = t {
+            *t = TypeInfo::UserDefined(t.pascal_name());
+        }
+    });
+}
+
+/// Validate that all user-defined type references are defined in the schema
+///
+/// This function ensures type safety by catching references to undefined types
+/// during transformation rather than at Rust/TypeScript compile time.
 ///
 /// # Arguments
 ///
@@ -856,24 +2529,37 @@ pub fn collect_deprecation_warnings(type_defs: &[TypeDefinition]) -> Vec<crate::
 /// }
 /// ```
 pub fn validate_user_defined_types(type_defs: &[TypeDefinition]) -> Result<()> {
-    use std::collections::HashSet;
+    // Collect all defined type names with their number of generic parameters
+    let defined_types: HashMap<String, usize> = type_defs
+        .iter()
+        .filter(|t| !t.is_const())
+        .map(|t| (t.name().to_string(), t.generic_params().len()))
+        .collect();
+
+    validate_type_references(type_defs, &defined_types)
+}
 
-    // Collect all defined type names
-    let defined_types: HashSet<String> = type_defs.iter().map(|t| t.name().to_string()).collect();
+/// Validate names and type references against `defined_types`, reporting every problem
+pub(crate) fn validate_type_references(
+    type_defs: &[TypeDefinition],
+    defined_types: &HashMap<String, usize>,
+) -> Result<()> {
+    let mut errors = ErrorCollector::new();
+    errors.ok(validate_unique_names(type_defs));
 
     // Validate each type definition
     for type_def in type_defs {
         match type_def {
             TypeDefinition::Struct(s) => {
-                // Validate struct fields
-                for field in &s.fields {
-                    validate_type_info(
+                // Validate struct fields and instruction arguments
+                for field in s.fields.iter().chain(&s.metadata.instruction_args) {
+                    errors.ok(validate_type_info(
                         &field.type_info,
-                        &defined_types,
+                        defined_types,
                         &s.name,
                         &field.name,
                         field.span,
-                    )?;
+                    ));
                 }
             }
             TypeDefinition::Enum(e) => {
@@ -883,24 +2569,30 @@ pub fn validate_user_defined_types(type_defs: &[TypeDefinition]) -> Result<()> {
                         EnumVariantDefinition::Unit { .. } => {
                             // Unit variants have no types to validate
                         }
-                        EnumVariantDefinition::Tuple { name, types } => {
+                        EnumVariantDefinition::Tuple { name, types, .. } => {
                             // Validate tuple variant types
                             for (idx, type_info) in types.iter().enumerate() {
                                 let context = format!("{}.{}[{}]", e.name, name, idx);
-                                validate_type_info(type_info, &defined_types, &context, "", None)?;
+                                errors.ok(validate_type_info(
+                                    type_info,
+                                    defined_types,
+                                    &context,
+                                    "",
+                                    e.span,
+                                ));
                             }
                         }
-                        EnumVariantDefinition::Struct { name, fields } => {
+                        EnumVariantDefinition::Struct { name, fields, .. } => {
                             // Validate struct variant fields
                             for field in fields {
                                 let context = format!("{}.{}", e.name, name);
-                                validate_type_info(
+                                errors.ok(validate_type_info(
                                     &field.type_info,
-                                    &defined_types,
+                                    defined_types,
                                     &context,
                                     &field.name,
                                     field.span,
-                                )?;
+                                ));
                             }
                         }
                     }
@@ -908,7 +2600,284 @@ pub fn validate_user_defined_types(type_defs: &[TypeDefinition]) -> Result<()> {
             }
             TypeDefinition::TypeAlias(a) => {
                 // Type aliases are already resolved - validate their target type
-                validate_type_info(&a.target, &defined_types, &a.name, "", None)?;
+                errors.ok(validate_type_info(
+                    &a.target,
+                    defined_types,
+                    &a.name,
+                    "",
+                    None,
+                ));
+            }
+            TypeDefinition::Const(_) => {
+                // Constants are plain integers with nothing to validate
+            }
+        }
+    }
+
+    errors.ok(validate_collection_keys(type_defs));
+    errors.ok(validate_recursive_types(type_defs));
+    errors.ok(validate_generic_recursion(type_defs));
+    errors.finish(())
+}
+
+/// Reject duplicate type names, field names and variant names
+///
+/// Duplicate aliases and constants are already reported by the
+/// [`TypeAliasResolver`], so only clashes involving a struct or enum are
+/// checked between types.
+fn validate_unique_names(type_defs: &[TypeDefinition]) -> Result<()> {
+    let mut errors = ErrorCollector::new();
+
+    let mut seen_types: HashMap<&str, &TypeDefinition> = HashMap::new();
+    for type_def in type_defs.iter().filter(|t| !t.is_const()) {
+        match seen_types.get(type_def.name()) {
+            Some(previous) if !(previous.is_type_alias() && type_def.is_type_alias()) => {
+                errors.push(
+                    LumosError::TypeValidation(
+                        format!("Duplicate type definition '{}'", type_def.name()),
+                        None,
+                    )
+                    .with_span(type_def.span()),
+                );
+            }
+            Some(_) => {}
+            None => {
+                seen_types.insert(type_def.name(), type_def);
+            }
+        }
+    }
+
+    let check_fields = |errors: &mut ErrorCollector, owner: &str, fields: &[FieldDefinition]| {
+        let mut seen = HashSet::new();
+        for field in fields {
+            if !seen.insert(field.name.as_str()) {
+                errors.push(
+                    LumosError::TypeValidation(
+                        format!("Duplicate field '{}' in '{}'", field.name, owner),
+                        None,
+                    )
+                    .with_span(field.span),
+                );
+            }
+        }
+    };
+
+    for type_def in type_defs {
+        match type_def {
+            TypeDefinition::Struct(s) => {
+                check_fields(&mut errors, &s.name, &s.fields);
+                check_fields(
+                    &mut errors,
+                    &format!("{} instruction arguments", s.name),
+                    &s.metadata.instruction_args,
+                );
+            }
+            TypeDefinition::Enum(e) => {
+                let mut seen = HashSet::new();
+                for variant in &e.variants {
+                    if !seen.insert(variant.name()) {
+                        errors.push(
+                            LumosError::TypeValidation(
+                                format!(
+                                    "Duplicate variant '{}' in enum '{}'",
+                                    variant.name(),
+                                    e.name
+                                ),
+                                None,
+                            )
+                            .with_span(e.span),
+                        );
+                    }
+                    if let EnumVariantDefinition::Struct { name, fields, .. } = variant {
+                        check_fields(&mut errors, &format!("{}::{}", e.name, name), fields);
+                    }
+                }
+            }
+            TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => {}
+        }
+    }
+
+    errors.finish(())
+}
+
+/// Reject types that contain themselves without indirection
+///
+/// A type stored inline in itself (directly, or through `Option`, fixed
+/// arrays, tuples or other types) would have infinite size. `Box<T>`, `Vec<T>`,
+/// maps and sets keep their contents behind a pointer, so recursion through
+/// them is allowed. The error names the whole cycle, e.g. `A → B → A`.
+fn validate_recursive_types(type_defs: &[TypeDefinition]) -> Result<()> {
+    // Types each definition stores inline
+    let inline_refs: HashMap<&str, Vec<&str>> = type_defs
+        .iter()
+        .filter(|t| !t.is_const())
+        .map(|t| {
+            let mut refs = Vec::new();
+            for type_info in t.field_types() {
+                collect_inline_references(type_info, &mut refs);
+            }
+            (t.name(), refs)
+        })
+        .collect();
+
+    // Each independent cycle is reported once, at the type it was found from
+    let mut errors = ErrorCollector::new();
+    let mut finished = HashSet::new();
+    for type_def in type_defs.iter().filter(|t| !t.is_const()) {
+        let mut path = Vec::new();
+        if let Some(cycle) =
+            find_inline_cycle(type_def.name(), &inline_refs, &mut path, &mut finished)
+        {
+            errors.push(
+                LumosError::TypeValidation(
+                    format!(
+                        "Recursive type '{}' has infinite size: {}. \
+                         Wrap the recursive field in Box<T> or a collection such as Vec<T>",
+                        cycle[0],
+                        cycle.join(" → ")
+                    ),
+                    None,
+                )
+                .with_span(type_def.span()),
+            );
+            finished.extend(path);
+        }
+    }
+
+    errors.finish(())
+}
+
+/// Depth-first search for a cycle of inline references, returning its path
+fn find_inline_cycle<'t>(
+    name: &'t str,
+    inline_refs: &HashMap<&'t str, Vec<&'t str>>,
+    path: &mut Vec<&'t str>,
+    finished: &mut HashSet<&'t str>,
+) -> Option<Vec<&'t str>> {
+    if let Some(start) = path.iter().position(|&n| n == name) {
+        let mut cycle = path[start..].to_vec();
+        cycle.push(name);
+        return Some(cycle);
+    }
+    if finished.contains(name) {
+        return None;
+    }
+
+    path.push(name);
+    for &next in inline_refs.get(name).into_iter().flatten() {
+        if let Some(cycle) = find_inline_cycle(next, inline_refs, path, finished) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    finished.insert(name);
+    None
+}
+
+/// Collect the user-defined types stored inline in `type_info`
+fn collect_inline_references<'t>(type_info: &'t TypeInfo, refs: &mut Vec<&'t str>) {
+    match type_info {
+        TypeInfo::UserDefined(name) => refs.push(name),
+        TypeInfo::Option(inner) => collect_inline_references(inner, refs),
+        TypeInfo::FixedArray { element, size } if *size > 0 => {
+            collect_inline_references(element, refs)
+        }
+        TypeInfo::Tuple(elements) => {
+            for element in elements {
+                collect_inline_references(element, refs);
+            }
+        }
+        TypeInfo::Instance { name, args } => {
+            refs.push(name);
+            for arg in args {
+                collect_inline_references(arg, refs);
+            }
+        }
+        // Heap-allocated: recursion through these has finite size
+        TypeInfo::Boxed(_) | TypeInfo::Array(_) | TypeInfo::Map { .. } | TypeInfo::Set { .. } => {}
+        TypeInfo::Primitive(_)
+        | TypeInfo::Generic(_)
+        | TypeInfo::FixedArray { .. }
+        | TypeInfo::FixedString(_) => {}
+    }
+}
+
+/// Lay out `#[zero_copy]` structs in C order with explicit padding fields
+///
+/// Runs once every type is known, since nested zero-copy structs are laid out
+/// recursively. Padding becomes ordinary `[u8; N]` fields, so generators and
+/// the IDL see the exact `#[repr(C)]` byte layout. Zero-copy structs have no
+/// Borsh encoding, so Borsh-serialized types may not contain them.
+pub fn apply_zero_copy_layouts(type_defs: &mut [TypeDefinition]) -> Result<()> {
+    let calculator = SizeCalculator::new(type_defs);
+    let mut errors = ErrorCollector::new();
+    let mut layouts = HashMap::new();
+    for type_def in type_defs.iter() {
+        if let TypeDefinition::Struct(s) = type_def {
+            if s.is_zero_copy() {
+                let layout = calculator
+                    .zero_copy_layout(s)
+                    .map_err(|msg| LumosError::Transform(msg, None).with_span(s.span));
+                if let Some(layout) = errors.ok(layout) {
+                    layouts.insert(s.name.clone(), layout);
+                }
+            }
+        }
+    }
+
+    if layouts.is_empty() {
+        return errors.finish(());
+    }
+
+    for type_def in type_defs.iter() {
+        // Fields of an instruction context are accounts, loaded with AccountLoader
+        let borsh_types: Vec<(&str, &TypeInfo)> = match type_def {
+            TypeDefinition::Struct(s) if s.is_zero_copy() => continue,
+            TypeDefinition::Struct(s) => s
+                .metadata
+                .instruction_args
+                .iter()
+                .chain(s.fields.iter().filter(|_| !s.metadata.is_instruction))
+                .map(|f| (s.name.as_str(), &f.type_info))
+                .collect(),
+            TypeDefinition::Enum(e) => e
+                .variants
+                .iter()
+                .flat_map(|v| match v {
+                    EnumVariantDefinition::Unit { .. } => Vec::new(),
+                    EnumVariantDefinition::Tuple { types, .. } => types.iter().collect(),
+                    EnumVariantDefinition::Struct { fields, .. } => {
+                        fields.iter().map(|f| &f.type_info).collect()
+                    }
+                })
+                .map(|ty| (e.name.as_str(), ty))
+                .collect(),
+            TypeDefinition::TypeAlias(_) | TypeDefinition::Const(_) => continue,
+        };
+
+        for (owner, type_info) in borsh_types {
+            if let Some(name) = find_type_reference(type_info, &|name| layouts.contains_key(name)) {
+                errors.push(
+                    LumosError::Transform(
+                        format!(
+                            "#[zero_copy] struct '{}' cannot be used in Borsh-serialized type '{}'; \
+                             mark '{}' #[zero_copy] as well",
+                            name, owner, owner
+                        ),
+                        None,
+                    )
+                    .with_span(type_def.span()),
+                );
+            }
+        }
+    }
+    errors.finish(())?;
+
+    for type_def in type_defs.iter_mut() {
+        if let TypeDefinition::Struct(s) = type_def {
+            if let Some((fields, layout)) = layouts.remove(&s.name) {
+                s.fields = fields;
+                s.metadata.c_layout = Some(layout);
             }
         }
     }
@@ -916,26 +2885,62 @@ pub fn validate_user_defined_types(type_defs: &[TypeDefinition]) -> Result<()> {
     Ok(())
 }
 
+/// Find the first user-defined type name inside `type_info` matching `predicate`
+fn find_type_reference<'t>(
+    type_info: &'t TypeInfo,
+    predicate: &dyn Fn(&str) -> bool,
+) -> Option<&'t str> {
+    match type_info {
+        TypeInfo::UserDefined(name) | TypeInfo::Instance { name, .. } if predicate(name) => {
+            Some(name)
+        }
+        TypeInfo::Primitive(_)
+        | TypeInfo::Generic(_)
+        | TypeInfo::UserDefined(_)
+        | TypeInfo::FixedString(_) => None,
+        TypeInfo::Array(inner) | TypeInfo::Option(inner) | TypeInfo::Boxed(inner) => {
+            find_type_reference(inner, predicate)
+        }
+        TypeInfo::FixedArray { element, .. } | TypeInfo::Set { element, .. } => {
+            find_type_reference(element, predicate)
+        }
+        TypeInfo::Map { key, value, .. } => {
+            find_type_reference(key, predicate).or_else(|| find_type_reference(value, predicate))
+        }
+        TypeInfo::Tuple(elements) | TypeInfo::Instance { args: elements, .. } => elements
+            .iter()
+            .find_map(|e| find_type_reference(e, predicate)),
+    }
+}
+
 /// Recursively validate a TypeInfo against defined types
 ///
 /// # Arguments
 ///
 /// * `type_info` - The type to validate
-/// * `defined_types` - Set of all defined type names
+/// * `defined_types` - All defined type names, with their generic parameter counts
 /// * `parent_context` - Parent type name for error messages (e.g., "Player")
 /// * `field_name` - Field name for error messages (e.g., "inventory")
 /// * `span` - Optional source location for error reporting
 fn validate_type_info(
     type_info: &TypeInfo,
-    defined_types: &std::collections::HashSet<String>,
+    defined_types: &HashMap<String, usize>,
     parent_context: &str,
     field_name: &str,
     span: Option<proc_macro2::Span>,
 ) -> Result<()> {
     use crate::error::{LumosError, SourceLocation};
 
+    let location = || {
+        if field_name.is_empty() {
+            parent_context.to_string()
+        } else {
+            format!("{}.{}", parent_context, field_name)
+        }
+    };
+
     match type_info {
-        TypeInfo::Primitive(_) => {
+        TypeInfo::Primitive(_) | TypeInfo::FixedString(_) => {
             // Primitive types are always valid
             Ok(())
         }
@@ -945,20 +2950,59 @@ fn validate_type_info(
         }
         TypeInfo::UserDefined(type_name) => {
             // Check if the user-defined type exists
-            if !defined_types.contains(type_name) {
-                let location = if field_name.is_empty() {
-                    parent_context.to_string()
-                } else {
-                    format!("{}.{}", parent_context, field_name)
-                };
-                return Err(LumosError::TypeValidation(
+            match defined_types.get(type_name) {
+                None => Err(LumosError::TypeValidation(
                     format!(
                         "Undefined type '{}' referenced in '{}'",
-                        type_name, location
+                        type_name,
+                        location()
+                    ),
+                    span.map(SourceLocation::from_span),
+                )),
+                Some(&arity) if arity > 0 => Err(LumosError::TypeValidation(
+                    format!(
+                        "Generic type '{}' used without type arguments in '{}'; it expects {}",
+                        type_name,
+                        location(),
+                        arity
                     ),
                     span.map(SourceLocation::from_span),
+                )),
+                Some(_) => Ok(()),
+            }
+        }
+        TypeInfo::Instance { name, args } => {
+            // The generic type must exist and take exactly this many arguments
+            let arity = *defined_types.get(name).ok_or_else(|| {
+                LumosError::TypeValidation(
+                    format!("Undefined type '{}' referenced in '{}'", name, location()),
+                    span.map(SourceLocation::from_span),
+                )
+            })?;
+            if arity != args.len() {
+                let message = if arity == 0 {
+                    format!(
+                        "Type '{}' is not generic but is given type arguments in '{}'",
+                        name,
+                        location()
+                    )
+                } else {
+                    format!(
+                        "Generic type '{}' expects {} type argument(s) but {} were given in '{}'",
+                        name,
+                        arity,
+                        args.len(),
+                        location()
+                    )
+                };
+                return Err(LumosError::TypeValidation(
+                    message,
+                    span.map(SourceLocation::from_span),
                 ));
             }
+            for arg in args {
+                validate_type_info(arg, defined_types, parent_context, field_name, span)?;
+            }
             Ok(())
         }
         TypeInfo::Array(inner) => {
@@ -969,10 +3013,178 @@ fn validate_type_info(
             // Recursively validate fixed array element type
             validate_type_info(element, defined_types, parent_context, field_name, span)
         }
-        TypeInfo::Option(inner) => {
-            // Recursively validate optional type
+        TypeInfo::Option(inner) | TypeInfo::Boxed(inner) => {
+            // Recursively validate optional and boxed types
             validate_type_info(inner, defined_types, parent_context, field_name, span)
         }
+        TypeInfo::Map { key, value, .. } => {
+            // Key types are checked by validate_collection_keys, which sees their definitions
+            validate_type_info(key, defined_types, parent_context, field_name, span)?;
+            validate_type_info(value, defined_types, parent_context, field_name, span)
+        }
+        TypeInfo::Set { element, .. } => {
+            validate_type_info(element, defined_types, parent_context, field_name, span)
+        }
+        TypeInfo::Tuple(elements) => {
+            // Recursively validate each tuple element
+            for element in elements {
+                validate_type_info(element, defined_types, parent_context, field_name, span)?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Reject map keys and set elements whose types cannot implement `Hash` and `Ord`
+///
+/// Borsh writes maps and sets sorted by key, so keys must be totally ordered
+/// as well as hashable. That rules out floats, `HashMap`/`HashSet` (which
+/// implement neither) and type parameters (which carry no such bound),
+/// including inside the structs, enums and newtypes a key refers to. Those
+/// types get the derives from the Rust generator.
+///
+/// Types not among `type_defs` are skipped: they are reported as undefined,
+/// or checked once the whole project is validated.
+fn validate_collection_keys(type_defs: &[TypeDefinition]) -> Result<()> {
+    let definitions: HashMap<&str, &TypeDefinition> = type_defs
+        .iter()
+        .filter(|t| !t.is_const())
+        .map(|t| (t.name(), t))
+        .collect();
+
+    let mut errors = ErrorCollector::new();
+    for type_def in type_defs {
+        let fields: Vec<(String, &TypeInfo, Option<proc_macro2::Span>)> = match type_def {
+            TypeDefinition::Struct(s) => s
+                .fields
+                .iter()
+                .chain(&s.metadata.instruction_args)
+                .map(|f| (format!("{}.{}", s.name, f.name), &f.type_info, f.span))
+                .collect(),
+            TypeDefinition::Enum(e) => e
+                .variants
+                .iter()
+                .flat_map(|v| match v {
+                    EnumVariantDefinition::Unit { .. } => Vec::new(),
+                    EnumVariantDefinition::Tuple { name, types, .. } => types
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, t)| (format!("{}.{}[{}]", e.name, name, idx), t, e.span))
+                        .collect(),
+                    EnumVariantDefinition::Struct { name, fields, .. } => fields
+                        .iter()
+                        .map(|f| {
+                            (
+                                format!("{}.{}.{}", e.name, name, f.name),
+                                &f.type_info,
+                                f.span,
+                            )
+                        })
+                        .collect(),
+                })
+                .collect(),
+            TypeDefinition::TypeAlias(a) => vec![(a.name.clone(), &a.target, None)],
+            TypeDefinition::Const(_) => Vec::new(),
+        };
+
+        for (location, type_info, span) in fields {
+            let mut keys = Vec::new();
+            type_info.walk(&mut |t| match t {
+                TypeInfo::Map { key, .. } => keys.push(key.as_ref().clone()),
+                TypeInfo::Set { element, .. } => keys.push(element.as_ref().clone()),
+                _ => {}
+            });
+            // One error per field, for its first offending key
+            let Some(problem) = keys
+                .iter()
+                .find_map(|key| key_problem(key, &[], &definitions, &mut Vec::new()))
+            else {
+                continue;
+            };
+
+            let through = if problem.path.is_empty() {
+                String::new()
+            } else {
+                format!(" (through {})", problem.path.join(" → "))
+            };
+            errors.push(LumosError::TypeValidation(
+                format!(
+                    "{} cannot be used as map keys or set elements in '{}'{}",
+                    problem.reason, location, through
+                ),
+                span.map(SourceLocation::from_span),
+            ));
+        }
+    }
+
+    errors.finish(())
+}
+
+/// Why a type cannot be a map key or set element, and the types it was found through
+struct KeyProblem {
+    reason: &'static str,
+    path: Vec<String>,
+}
+
+/// Find the first part of a key type that cannot implement `Hash` and `Ord`
+///
+/// `params` are the type parameters of the definition being walked, which
+/// stand for arguments already checked. `path` holds the definitions
+/// entered so far, so recursive types are walked once.
+fn key_problem(
+    key: &TypeInfo,
+    params: &[String],
+    definitions: &HashMap<&str, &TypeDefinition>,
+    path: &mut Vec<String>,
+) -> Option<KeyProblem> {
+    let found = |reason| {
+        Some(KeyProblem {
+            reason,
+            path: path.clone(),
+        })
+    };
+    match key {
+        TypeInfo::Primitive(name) if name == "f32" || name == "f64" => {
+            found("Floating-point types")
+        }
+        TypeInfo::Primitive(_) | TypeInfo::FixedString(_) => None,
+        TypeInfo::Generic(name) if params.contains(name) => None,
+        TypeInfo::Generic(_) => found("Type parameters"),
+        TypeInfo::Map { ordered: false, .. } | TypeInfo::Set { ordered: false, .. } => {
+            found("HashMap and HashSet (use BTreeMap or BTreeSet)")
+        }
+        TypeInfo::Map { key, value, .. } => key_problem(key, params, definitions, path)
+            .or_else(|| key_problem(value, params, definitions, path)),
+        TypeInfo::Set { element, .. } | TypeInfo::FixedArray { element, .. } => {
+            key_problem(element, params, definitions, path)
+        }
+        TypeInfo::Array(inner) | TypeInfo::Option(inner) | TypeInfo::Boxed(inner) => {
+            key_problem(inner, params, definitions, path)
+        }
+        TypeInfo::Tuple(elements) => elements
+            .iter()
+            .find_map(|element| key_problem(element, params, definitions, path)),
+        TypeInfo::UserDefined(name) | TypeInfo::Instance { name, .. } => {
+            if let TypeInfo::Instance { args, .. } = key {
+                if let Some(problem) = args
+                    .iter()
+                    .find_map(|arg| key_problem(arg, params, definitions, path))
+                {
+                    return Some(problem);
+                }
+            }
+            let definition = definitions.get(name.as_str())?;
+            if path.contains(name) {
+                return None;
+            }
+
+            path.push(name.clone());
+            let problem = definition.field_types().into_iter().find_map(|field_type| {
+                key_problem(field_type, definition.generic_params(), definitions, path)
+            });
+            path.pop();
+            problem
+        }
     }
 }
 
@@ -1064,186 +3276,1098 @@ mod tests {
                 let field = &s.fields[0];
                 assert!(matches!(field.type_info, TypeInfo::Array(_)));
             }
-            _ => panic!("Expected struct type definition"),
-        }
-    }
+            _ => panic!("Expected struct type definition"),
+        }
+    }
+
+    #[test]
+    fn test_transform_solana_metadata() {
+        let input = r#"
+            #[solana]
+            #[account]
+            struct UserAccount {
+                wallet: PublicKey,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+
+        match &ir[0] {
+            TypeDefinition::Struct(s) => {
+                assert!(s.metadata.solana);
+                assert!(s.metadata.attributes.contains(&"account".to_string()));
+            }
+            _ => panic!("Expected struct type definition"),
+        }
+    }
+
+    #[test]
+    fn test_transform_unit_enum() {
+        let input = r#"
+            #[solana]
+            enum GameState {
+                Inactive,
+                Active,
+                Paused,
+                Finished,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+
+        assert_eq!(ir.len(), 1);
+        match &ir[0] {
+            TypeDefinition::Enum(e) => {
+                assert_eq!(e.name, "GameState");
+                assert_eq!(e.variants.len(), 4);
+                assert!(e.metadata.solana);
+                assert!(e.is_unit_only());
+
+                // Check variant names
+                assert_eq!(e.variants[0].name(), "Inactive");
+                assert_eq!(e.variants[1].name(), "Active");
+                assert_eq!(e.variants[2].name(), "Paused");
+                assert_eq!(e.variants[3].name(), "Finished");
+            }
+            _ => panic!("Expected enum type definition"),
+        }
+    }
+
+    #[test]
+    fn test_transform_tuple_enum() {
+        let input = r#"
+            enum GameEvent {
+                PlayerJoined(PublicKey),
+                ScoreUpdated(PublicKey, u64),
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+
+        assert_eq!(ir.len(), 1);
+        match &ir[0] {
+            TypeDefinition::Enum(e) => {
+                assert_eq!(e.name, "GameEvent");
+                assert_eq!(e.variants.len(), 2);
+                assert!(e.has_tuple_variants());
+
+                // Check tuple variant types
+                match &e.variants[0] {
+                    EnumVariantDefinition::Tuple { name, types, .. } => {
+                        assert_eq!(name, "PlayerJoined");
+                        assert_eq!(types.len(), 1);
+                    }
+                    _ => panic!("Expected tuple variant"),
+                }
+
+                match &e.variants[1] {
+                    EnumVariantDefinition::Tuple { name, types, .. } => {
+                        assert_eq!(name, "ScoreUpdated");
+                        assert_eq!(types.len(), 2);
+                    }
+                    _ => panic!("Expected tuple variant"),
+                }
+            }
+            _ => panic!("Expected enum type definition"),
+        }
+    }
+
+    #[test]
+    fn test_transform_struct_enum() {
+        let input = r#"
+            enum GameInstruction {
+                Initialize {
+                    authority: PublicKey,
+                    max_players: u8,
+                },
+                Terminate,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+
+        assert_eq!(ir.len(), 1);
+        match &ir[0] {
+            TypeDefinition::Enum(e) => {
+                assert_eq!(e.name, "GameInstruction");
+                assert_eq!(e.variants.len(), 2);
+                assert!(e.has_struct_variants());
+
+                // Check struct variant fields
+                match &e.variants[0] {
+                    EnumVariantDefinition::Struct { name, fields, .. } => {
+                        assert_eq!(name, "Initialize");
+                        assert_eq!(fields.len(), 2);
+                        assert_eq!(fields[0].name, "authority");
+                        assert_eq!(fields[1].name, "max_players");
+                    }
+                    _ => panic!("Expected struct variant"),
+                }
+
+                // Check unit variant
+                match &e.variants[1] {
+                    EnumVariantDefinition::Unit { name, .. } => {
+                        assert_eq!(name, "Terminate");
+                    }
+                    _ => panic!("Expected unit variant"),
+                }
+            }
+            _ => panic!("Expected enum type definition"),
+        }
+    }
+
+    // Type validation tests
+    #[test]
+    fn test_validate_undefined_type_in_struct() {
+        let input = r#"
+            struct Player {
+                inventory: UndefinedType,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let result = transform_to_ir(ast);
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::LumosError::TypeValidation(_, _)
+        ));
+        assert!(err.to_string().contains("Undefined type 'UndefinedType'"));
+        assert!(err.to_string().contains("Player.inventory"));
+    }
+
+    #[test]
+    fn test_validate_undefined_type_in_array() {
+        let input = r#"
+            struct Inventory {
+                items: [MissingItem],
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let result = transform_to_ir(ast);
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("Undefined type 'MissingItem'"));
+    }
+
+    #[test]
+    fn test_validate_undefined_type_in_map_value() {
+        let input = r#"
+            struct Registry {
+                entries: HashMap<u64, MissingEntry>,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let result = transform_to_ir(ast);
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert!(err.to_string().contains("Undefined type 'MissingEntry'"));
+    }
+
+    #[test]
+    fn test_validate_float_map_key_rejected() {
+        let input = r#"
+            struct Prices {
+                by_rate: BTreeMap<f64, u64>,
+                seen: HashSet<f32>,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let result = transform_to_ir(ast);
+
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("cannot be used as map keys or set elements in 'Prices.by_rate'"));
+    }
+
+    #[test]
+    fn test_validate_nested_map_keys_rejected() {
+        let input = r#"
+            struct Point {
+                x: f32,
+                y: f32,
+            }
+
+            enum Shape {
+                Dot(Point),
+                Empty,
+            }
+
+            struct Wrapper<T> {
+                value: T,
+            }
+
+            struct Index<T> {
+                by_param: BTreeMap<T, u64>,
+            }
+
+            struct Scene {
+                by_option: HashMap<Option<f32>, u64>,
+                by_array: BTreeSet<[f64; 2]>,
+                by_tuple: HashSet<(u8, Vec<f32>)>,
+                by_point: HashMap<Point, u64>,
+                by_shape: BTreeSet<Shape>,
+                by_wrapper: HashSet<Wrapper<f64>>,
+                by_set: BTreeSet<HashSet<u8>>,
+                nested: Vec<HashMap<Point, u8>>,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err();
+        let messages: Vec<String> = err.errors().iter().map(|e| e.message()).collect();
+
+        let expected = [
+            "Type parameters cannot be used as map keys or set elements in 'Index.by_param'",
+            "Floating-point types cannot be used as map keys or set elements in 'Scene.by_option'",
+            "Floating-point types cannot be used as map keys or set elements in 'Scene.by_array'",
+            "Floating-point types cannot be used as map keys or set elements in 'Scene.by_tuple'",
+            "Floating-point types cannot be used as map keys or set elements in 'Scene.by_point' (through Point)",
+            "Floating-point types cannot be used as map keys or set elements in 'Scene.by_shape' (through Shape → Point)",
+            "Floating-point types cannot be used as map keys or set elements in 'Scene.by_wrapper'",
+            "HashMap and HashSet (use BTreeMap or BTreeSet) cannot be used as map keys or set elements in 'Scene.by_set'",
+            "Floating-point types cannot be used as map keys or set elements in 'Scene.nested' (through Point)",
+        ];
+        assert_eq!(messages, expected);
+        assert!(err.errors().iter().all(|e| e.location().is_some()));
+    }
+
+    #[test]
+    fn test_validate_user_defined_map_keys_accepted() {
+        let input = r#"
+            struct Coord {
+                x: i32,
+                y: i32,
+            }
+
+            enum Side {
+                Buy,
+                Limit { price: u64, at: Coord },
+            }
+
+            struct Wrapper<T> {
+                value: T,
+            }
+
+            struct Node {
+                children: BTreeSet<Node>,
+            }
+
+            struct Board {
+                owners: HashMap<Coord, PublicKey>,
+                sides: BTreeSet<(Side, u8)>,
+                wrapped: HashSet<Wrapper<[u8; 4]>>,
+                tree: BTreeSet<Node>,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        assert!(transform_to_ir(ast).is_ok());
+    }
+
+    #[test]
+    fn test_transform_map_and_set_with_max() {
+        let input = r#"
+            struct Registry {
+                #[max(16)]
+                balances: HashMap<PublicKey, u64>,
+                tags: BTreeSet<String>,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+
+        if let TypeDefinition::Struct(s) = &ir[0] {
+            assert!(matches!(
+                s.fields[0].type_info,
+                TypeInfo::Map { ordered: false, .. }
+            ));
+            assert_eq!(s.fields[0].max_length, Some(16));
+            assert!(matches!(
+                s.fields[1].type_info,
+                TypeInfo::Set { ordered: true, .. }
+            ));
+            assert_eq!(s.fields[1].max_length, None);
+        } else {
+            panic!("Expected struct definition");
+        }
+    }
+
+    #[test]
+    fn test_transform_fixed_string_capacity() {
+        let input = r#"
+            const MAX_BIO: u16 = 64;
+
+            struct Profile {
+                name: FixedString<32>,
+                bio: FixedString<MAX_BIO>,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+
+        let TypeDefinition::Struct(s) = &ir[1] else {
+            panic!("Expected struct definition");
+        };
+        assert_eq!(s.fields[0].type_info, TypeInfo::FixedString(32));
+        assert_eq!(s.fields[1].type_info, TypeInfo::FixedString(64));
+
+        for (field_type, message) in [
+            ("FixedString<0>", "Array size must be greater than 0"),
+            ("FixedString<4096>", "exceeds maximum of 1024"),
+            ("FixedString<HUGE>", "exceeds maximum of 1024"),
+            // Past usize, a literal is still checked here, not by the parser
+            ("[u8; 100000000000000000000000]", "exceeds maximum of 1024"),
+        ] {
+            let input = format!(
+                "const HUGE: u32 = 5000; struct Profile {{ bio: {} }}",
+                field_type
+            );
+            let ast = parse_lumos_file(&input).unwrap();
+            let err = transform_to_ir(ast).unwrap_err().to_string();
+            assert!(err.contains(message), "{}: {}", field_type, err);
+        }
+    }
+
+    #[test]
+    fn test_transform_constants_in_array_size_and_max() {
+        let input = r#"
+            const MAX_NAME_LEN: usize = 32;
+            const MAX_BIO_LEN: u16 = MAX_NAME_LEN;
+            type Name = [u8; MAX_NAME_LEN];
+
+            struct Profile {
+                name: Name,
+                tag: [u8; MAX_NAME_LEN],
+                #[max(MAX_BIO_LEN)]
+                bio: String,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+
+        match &ir[1] {
+            TypeDefinition::Const(c) => {
+                assert_eq!(c.name, "MAX_BIO_LEN");
+                assert_eq!(c.type_name, "u16");
+                assert_eq!(c.value, 32);
+            }
+            _ => panic!("Expected const definition"),
+        }
+
+        if let TypeDefinition::Struct(s) = &ir[3] {
+            assert!(matches!(
+                s.fields[0].type_info,
+                TypeInfo::FixedArray { size: 32, .. }
+            ));
+            assert!(matches!(
+                s.fields[1].type_info,
+                TypeInfo::FixedArray { size: 32, .. }
+            ));
+            assert_eq!(s.fields[2].max_length, Some(32));
+        } else {
+            panic!("Expected struct definition");
+        }
+    }
+
+    #[test]
+    fn test_unknown_constant_in_array_size_fails() {
+        let input = r#"
+            struct Profile {
+                name: [u8; MISSING_LEN],
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let result = transform_to_ir(ast);
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Unknown constant: MISSING_LEN"));
+    }
+
+    #[test]
+    fn test_circular_constants_rejected() {
+        let input = r#"
+            const A: usize = B;
+            const B: usize = A;
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let result = transform_to_ir(ast);
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("Circular constant reference"));
+    }
+
+    #[test]
+    fn test_constant_array_size_limits_enforced() {
+        let input = r#"
+            const HUGE: usize = 4096;
+
+            struct Buffer {
+                data: [u8; HUGE],
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let result = transform_to_ir(ast);
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("exceeds maximum of 1024"));
+    }
+
+    #[test]
+    fn test_constant_value_must_fit_declared_type() {
+        let input = r#"
+            const SMALL: u8 = 300;
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let result = transform_to_ir(ast);
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("does not fit in u8"));
+    }
+
+    #[test]
+    fn test_u128_constants() {
+        let input = r#"
+            const BIG: u128 = 340282366920938463463374607431768211455;
+            const WIDE: u64 = 18446744073709551616;
+
+            struct Vault {
+                #[default(BIG)]
+                cap: u128,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err();
+        let messages: Vec<String> = err.errors().iter().map(|e| e.message()).collect();
+        assert!(messages
+            .iter()
+            .any(|m| m.contains("'WIDE' value 18446744073709551616 does not fit in u64")));
+        assert!(messages.iter().any(
+            |m| m.contains("340282366920938463463374607431768211455 is out of range for u128")
+        ));
+
+        let ast =
+            parse_lumos_file("const BIG: u128 = 340282366920938463463374607431768211455;").unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+        let TypeDefinition::Const(c) = &ir[0] else {
+            panic!("Expected const definition");
+        };
+        assert_eq!(c.value, u128::MAX);
+    }
+
+    #[test]
+    fn test_event_cannot_be_account() {
+        let input = r#"
+            #[solana]
+            #[account]
+            #[event]
+            struct Trade {
+                amount: u64,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let result = transform_to_ir(ast);
+
+        assert!(result.is_err());
+        assert!(result
+            .unwrap_err()
+            .to_string()
+            .contains("cannot be both #[account] and #[event]"));
+    }
+
+    #[test]
+    fn test_error_code_enum_messages_and_offset() {
+        let input = r#"
+            #[error_code(offset = 7000)]
+            enum VaultError {
+                #[msg("Insufficient funds")]
+                InsufficientFunds,
+                Unauthorized,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+
+        let TypeDefinition::Enum(e) = &ir[0] else {
+            panic!("Expected enum");
+        };
+        assert!(e.is_error_code());
+        assert_eq!(e.metadata.error_code_offset, Some(7000));
+        assert_eq!(e.variants[0].msg(), Some("Insufficient funds"));
+        assert_eq!(e.variants[1].msg(), None);
+
+        let codes: Vec<(u32, &str)> = e
+            .error_codes()
+            .into_iter()
+            .map(|(code, v)| (code, v.name()))
+            .collect();
+        assert_eq!(
+            codes,
+            vec![(7000, "InsufficientFunds"), (7001, "Unauthorized")]
+        );
+    }
+
+    #[test]
+    fn test_error_code_offset_overflow() {
+        let input = r#"
+            #[error_code(offset = 4294967295)]
+            enum VaultError {
+                InsufficientFunds,
+                Unauthorized,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("Error codes of enum 'VaultError' overflow u32"));
+
+        // The last code may be u32::MAX itself
+        let input = input.replace("4294967295", "4294967294");
+        let ir = transform_to_ir(parse_lumos_file(&input).unwrap()).unwrap();
+        let TypeDefinition::Enum(e) = &ir[0] else {
+            panic!("Expected enum");
+        };
+        assert_eq!(e.error_codes()[1].0, u32::MAX);
+    }
+
+    #[test]
+    fn test_error_code_enum_rejects_data_variants() {
+        let input = r#"
+            #[error_code]
+            enum VaultError {
+                InsufficientFunds,
+                Custom(u32),
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("can only contain unit variants, found 'Custom'"));
+    }
+
+    #[test]
+    fn test_enum_discriminants_and_repr() {
+        let input = r#"
+            #[solana]
+            #[repr(u16)]
+            enum Status {
+                Active = 1,
+                Frozen,
+                Closed = 500,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+        let TypeDefinition::Enum(e) = &ir[0] else {
+            panic!("Expected enum");
+        };
+        assert_eq!(e.repr(), EnumRepr::U16);
+        assert_eq!(e.tag_size(), 2);
+        assert_eq!(e.discriminants(), vec![1, 2, 500]);
+        assert!(e.has_custom_discriminants());
+    }
+
+    #[test]
+    fn test_flags_enum_bits() {
+        let input = r#"
+            #[solana]
+            #[flags(u16)]
+            enum Permissions {
+                Mint,
+                Burn,
+                Admin = 128,
+                Audit,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+        let TypeDefinition::Enum(e) = &ir[0] else {
+            panic!("Expected enum");
+        };
+        assert!(e.is_flags());
+        assert_eq!(e.repr(), EnumRepr::U16);
+        assert_eq!(e.discriminants(), vec![1, 2, 128, 256]);
+        assert_eq!(e.flags_mask(), 0x183);
+    }
+
+    #[test]
+    fn test_invalid_flags_rejected() {
+        let input = r#"
+            #[flags(u8)]
+            enum Permissions {
+                Mint,
+                Freeze(u8),
+                Burn = 3,
+                Admin = 256,
+                Owner = 1,
+            }
+
+            #[flags(u64)]
+            enum Wide {
+                A,
+            }
+
+            #[flags]
+            #[repr(u16)]
+            enum Both {
+                A,
+            }
+
+            #[flags]
+            struct NotAnEnum {
+                a: u8,
+            }
+        "#;
+
+        let err = transform_to_ir(parse_lumos_file(input).unwrap()).unwrap_err();
+        let messages: Vec<String> = err.errors().iter().map(|e| e.message()).collect();
+        assert_eq!(messages.len(), 7, "{:#?}", messages);
+        assert!(messages[0].contains("Flag 'Permissions::Freeze' cannot carry data"));
+        assert!(messages[1].contains("Flag 'Permissions::Burn' = 3 is not a single bit"));
+        assert!(messages[2].contains("Flag 'Permissions::Admin' (256) does not fit in u8"));
+        assert!(
+            messages[3].contains("Duplicate flag bit 1 in enum 'Permissions': 'Mint' and 'Owner'")
+        );
+        assert!(messages[4].contains("expected #[flags(u8)], #[flags(u16)] or #[flags(u32)]"));
+        assert!(messages[5].contains("#[flags] enum 'Both' cannot also be #[repr]"));
+        assert!(messages[6]
+            .contains("#[flags] is only supported on enums, found it on struct 'NotAnEnum'"));
+    }
+
+    #[test]
+    fn test_enum_discriminant_must_fit_repr() {
+        let input = r#"
+            enum Status {
+                Active = 256,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("Discriminant 256 of 'Status::Active' does not fit in u8"));
+    }
+
+    #[test]
+    fn test_enum_duplicate_discriminants_rejected() {
+        let input = r#"
+            enum Status {
+                Active = 2,
+                Paused = 1,
+                Frozen,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("Duplicate discriminant 2 in enum 'Status': 'Active' and 'Frozen'"));
+    }
+
+    #[test]
+    fn test_enum_invalid_repr_rejected() {
+        let input = r#"
+            #[repr(i64)]
+            enum Status {
+                Active,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("Invalid #[repr] on enum 'Status'"));
+    }
+
+    #[test]
+    fn test_zero_copy_struct_gets_explicit_padding() {
+        let input = r#"
+            #[solana]
+            #[account]
+            #[zero_copy]
+            struct Market {
+                authority: PublicKey,
+                bump: u8,
+                total: u64,
+                fee_bps: u16,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+        let TypeDefinition::Struct(s) = &ir[0] else {
+            panic!("Expected struct");
+        };
+        let names: Vec<&str> = s.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "authority",
+                "bump",
+                "_padding0",
+                "total",
+                "fee_bps",
+                "_padding1"
+            ]
+        );
+        let layout = s.metadata.c_layout.as_ref().unwrap();
+        assert_eq!(layout.size, 56);
+        assert_eq!(layout.align, 8);
+        assert_eq!(layout.offsets, vec![0, 32, 33, 40, 48, 50]);
+    }
+
+    #[test]
+    fn test_zero_copy_rejects_non_pod_fields() {
+        let input = r#"
+            #[zero_copy]
+            struct Market {
+                name: String,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("Field 'name' of #[zero_copy] struct 'Market' has type String"));
+        assert!(err.contains("not Pod"));
+    }
+
+    #[test]
+    fn test_zero_copy_nested_types_must_be_zero_copy() {
+        let input = r#"
+            struct Order {
+                price: u64,
+            }
+
+            #[zero_copy]
+            struct Book {
+                orders: [Order; 8],
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("has type 'Order', which must also be #[zero_copy]"));
+    }
+
+    #[test]
+    fn test_zero_copy_struct_not_allowed_in_borsh_type() {
+        let input = r#"
+            #[zero_copy]
+            struct Order {
+                price: u64,
+            }
+
+            struct Snapshot {
+                orders: Vec<Order>,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains(
+            "#[zero_copy] struct 'Order' cannot be used in Borsh-serialized type 'Snapshot'"
+        ));
+    }
+
+    #[test]
+    fn test_zero_copy_rejected_on_enums() {
+        let input = r#"
+            #[zero_copy]
+            enum Side {
+                Bid,
+                Ask,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("#[zero_copy] is only supported on structs"));
+    }
+
+    #[test]
+    fn test_recursion_through_box_or_collection_is_allowed() {
+        let input = r#"
+            struct TreeNode {
+                value: u64,
+                children: Vec<TreeNode>,
+            }
+
+            struct ListNode {
+                value: u64,
+                next: Option<Box<ListNode>>,
+            }
 
-    #[test]
-    fn test_transform_solana_metadata() {
-        let input = r#"
-            #[solana]
-            #[account]
-            struct UserAccount {
-                wallet: PublicKey,
+            enum Expr {
+                Literal(u64),
+                Add(Box<Expr>, Box<Expr>),
             }
         "#;
 
         let ast = parse_lumos_file(input).unwrap();
         let ir = transform_to_ir(ast).unwrap();
-
-        match &ir[0] {
-            TypeDefinition::Struct(s) => {
-                assert!(s.metadata.solana);
-                assert!(s.metadata.attributes.contains(&"account".to_string()));
-            }
-            _ => panic!("Expected struct type definition"),
-        }
+        let TypeDefinition::Struct(list) = &ir[1] else {
+            panic!("Expected struct definition");
+        };
+        assert_eq!(
+            list.fields[1].type_info,
+            TypeInfo::Option(Box::new(TypeInfo::Boxed(Box::new(TypeInfo::UserDefined(
+                "ListNode".to_string()
+            )))))
+        );
     }
 
     #[test]
-    fn test_transform_unit_enum() {
+    fn test_infinite_size_cycle_reports_path() {
         let input = r#"
-            #[solana]
-            enum GameState {
-                Inactive,
-                Active,
-                Paused,
-                Finished,
+            struct Owner {
+                pet: Option<Pet>,
+            }
+
+            struct Pet {
+                tags: [u8; 4],
+                owner: (u8, Owner),
             }
         "#;
 
         let ast = parse_lumos_file(input).unwrap();
-        let ir = transform_to_ir(ast).unwrap();
-
-        assert_eq!(ir.len(), 1);
-        match &ir[0] {
-            TypeDefinition::Enum(e) => {
-                assert_eq!(e.name, "GameState");
-                assert_eq!(e.variants.len(), 4);
-                assert!(e.metadata.solana);
-                assert!(e.is_unit_only());
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("Recursive type 'Owner' has infinite size: Owner → Pet → Owner"));
+        assert!(err.contains("Box<T>"));
 
-                // Check variant names
-                assert_eq!(e.variants[0].name(), "Inactive");
-                assert_eq!(e.variants[1].name(), "Active");
-                assert_eq!(e.variants[2].name(), "Paused");
-                assert_eq!(e.variants[3].name(), "Finished");
+        let input = r#"
+            struct Node {
+                next: Node,
             }
-            _ => panic!("Expected enum type definition"),
-        }
+        "#;
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("infinite size: Node → Node"));
     }
 
     #[test]
-    fn test_transform_tuple_enum() {
+    fn test_generic_with_growing_arguments_is_rejected() {
         let input = r#"
-            enum GameEvent {
-                PlayerJoined(PublicKey),
-                ScoreUpdated(PublicKey, u64),
+            struct Nested<T> {
+                value: T,
+                inner: Option<Box<Nested<Vec<T>>>>,
             }
-        "#;
-
-        let ast = parse_lumos_file(input).unwrap();
-        let ir = transform_to_ir(ast).unwrap();
 
-        assert_eq!(ir.len(), 1);
-        match &ir[0] {
-            TypeDefinition::Enum(e) => {
-                assert_eq!(e.name, "GameEvent");
-                assert_eq!(e.variants.len(), 2);
-                assert!(e.has_tuple_variants());
+            struct Holder {
+                nested: Nested<u8>,
+            }
+        "#;
 
-                // Check tuple variant types
-                match &e.variants[0] {
-                    EnumVariantDefinition::Tuple { name, types } => {
-                        assert_eq!(name, "PlayerJoined");
-                        assert_eq!(types.len(), 1);
-                    }
-                    _ => panic!("Expected tuple variant"),
-                }
+        let ir = transform_to_ir_with_resolver_no_validation(
+            parse_lumos_file(input).unwrap(),
+            &TypeAliasResolver::new(),
+        )
+        .unwrap();
+        // Instance collection ends instead of growing forever
+        let instances = collect_generic_instances(&ir);
+        assert_eq!(instances.len(), MAX_GENERIC_DEPTH);
+        assert!(collect_generic_warnings(&ir).is_empty());
+
+        let err = transform_to_ir(parse_lumos_file(input).unwrap())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Generic type 'Nested' contains itself with different type arguments"),
+            "{}",
+            err
+        );
+
+        // Containing itself with its own parameters is fine
+        let input = r#"
+            struct Tree<T> {
+                value: T,
+                children: Vec<Tree<T>>,
+            }
 
-                match &e.variants[1] {
-                    EnumVariantDefinition::Tuple { name, types } => {
-                        assert_eq!(name, "ScoreUpdated");
-                        assert_eq!(types.len(), 2);
-                    }
-                    _ => panic!("Expected tuple variant"),
-                }
+            struct Forest {
+                tree: Tree<u8>,
             }
-            _ => panic!("Expected enum type definition"),
-        }
+        "#;
+        assert!(transform_to_ir(parse_lumos_file(input).unwrap()).is_ok());
     }
 
     #[test]
-    fn test_transform_struct_enum() {
+    fn test_generic_instances_are_collected_transitively() {
         let input = r#"
-            enum GameInstruction {
-                Initialize {
-                    authority: PublicKey,
-                    max_players: u8,
-                },
-                Terminate,
+            struct Wrapper<T> {
+                value: T,
+            }
+
+            struct Outer<T> {
+                inner: Wrapper<T>,
+                extra: Option<T>,
+            }
+
+            enum Maybe<T> {
+                Nothing,
+                Just(T),
+            }
+
+            struct Holder {
+                a: Wrapper<u64>,
+                b: Outer<u64>,
+                c: Maybe<Wrapper<PublicKey>>,
             }
         "#;
 
         let ast = parse_lumos_file(input).unwrap();
         let ir = transform_to_ir(ast).unwrap();
 
-        assert_eq!(ir.len(), 1);
-        match &ir[0] {
-            TypeDefinition::Enum(e) => {
-                assert_eq!(e.name, "GameInstruction");
-                assert_eq!(e.variants.len(), 2);
-                assert!(e.has_struct_variants());
-
-                // Check struct variant fields
-                match &e.variants[0] {
-                    EnumVariantDefinition::Struct { name, fields } => {
-                        assert_eq!(name, "Initialize");
-                        assert_eq!(fields.len(), 2);
-                        assert_eq!(fields[0].name, "authority");
-                        assert_eq!(fields[1].name, "max_players");
-                    }
-                    _ => panic!("Expected struct variant"),
-                }
+        let instances = collect_generic_instances(&ir);
+        let names: Vec<_> = instances.iter().map(|i| i.definition.name()).collect();
+        assert_eq!(
+            names,
+            [
+                "WrapperU64",
+                "OuterU64",
+                "MaybeWrapperPublicKey",
+                "WrapperPublicKey"
+            ]
+        );
+
+        // Parameters are substituted and nested instances referenced by concrete name
+        let TypeDefinition::Struct(outer) = &instances[1].definition else {
+            panic!("Expected struct");
+        };
+        assert!(outer.generic_params.is_empty());
+        assert_eq!(
+            outer.fields[0].type_info,
+            TypeInfo::UserDefined("WrapperU64".to_string())
+        );
+        assert_eq!(
+            outer.fields[1].type_info,
+            TypeInfo::Option(Box::new(TypeInfo::Primitive("u64".to_string())))
+        );
+
+        // Generic definitions are replaced by their instances, in place
+        let names: Vec<_> = monomorphize(&ir)
+            .iter()
+            .map(|t| t.name().to_string())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "WrapperU64",
+                "WrapperPublicKey",
+                "OuterU64",
+                "MaybeWrapperPublicKey",
+                "Holder"
+            ]
+        );
+        assert!(collect_generic_warnings(&ir).is_empty());
+    }
 
-                // Check unit variant
-                match &e.variants[1] {
-                    EnumVariantDefinition::Unit { name } => {
-                        assert_eq!(name, "Terminate");
-                    }
-                    _ => panic!("Expected unit variant"),
-                }
-            }
-            _ => panic!("Expected enum type definition"),
+    #[test]
+    fn test_generic_type_arguments_are_validated() {
+        for (field_type, message) in [
+            (
+                "Wrapper<u64, u8>",
+                "Generic type 'Wrapper' expects 1 type argument(s) but 2 were given in 'Holder.a'",
+            ),
+            (
+                "Wrapper",
+                "Generic type 'Wrapper' used without type arguments in 'Holder.a'",
+            ),
+            (
+                "Plain<u64>",
+                "Type 'Plain' is not generic but is given type arguments in 'Holder.a'",
+            ),
+            (
+                "Missing<u64>",
+                "Undefined type 'Missing' referenced in 'Holder.a'",
+            ),
+        ] {
+            let input = format!(
+                "struct Wrapper<T> {{ value: T }} struct Plain {{ value: u8 }} \
+                 struct Holder {{ a: {} }}",
+                field_type
+            );
+            let ast = parse_lumos_file(&input).unwrap();
+            let err = transform_to_ir(ast).unwrap_err().to_string();
+            assert!(err.contains(message), "{}: {}", field_type, err);
         }
     }
 
-    // Type validation tests
     #[test]
-    fn test_validate_undefined_type_in_struct() {
+    fn test_uninstantiated_generic_warns() {
         let input = r#"
-            struct Player {
-                inventory: UndefinedType,
+            struct Wrapper<T> {
+                value: T,
+            }
+
+            struct Unused<K, V> {
+                key: K,
+                value: V,
+            }
+
+            struct Holder {
+                a: Wrapper<u8>,
             }
         "#;
 
         let ast = parse_lumos_file(input).unwrap();
-        let result = transform_to_ir(ast);
+        let ir = transform_to_ir(ast).unwrap();
 
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(matches!(
-            err,
-            crate::error::LumosError::TypeValidation(_, _)
-        ));
-        assert!(err.to_string().contains("Undefined type 'UndefinedType'"));
-        assert!(err.to_string().contains("Player.inventory"));
+        let warnings = collect_generic_warnings(&ir);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].type_name, "Unused");
+        assert_eq!(
+            warnings[0].kind,
+            crate::ir::WarningKind::UninstantiatedGeneric
+        );
+        assert!(warnings[0].message.contains("'Unused<u64, u64>'"));
     }
 
     #[test]
-    fn test_validate_undefined_type_in_array() {
+    fn test_msg_requires_error_code_enum() {
         let input = r#"
-            struct Inventory {
-                items: [MissingItem],
+            enum Status {
+                #[msg("Still running")]
+                Active,
             }
         "#;
 
         let ast = parse_lumos_file(input).unwrap();
-        let result = transform_to_ir(ast);
-
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(err.to_string().contains("Undefined type 'MissingItem'"));
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("requires the enum to be #[error_code]"));
     }
 
     #[test]
@@ -1557,4 +4681,365 @@ mod tests {
             panic!("Expected struct definition");
         }
     }
+
+    #[test]
+    fn test_transform_range_and_pattern_constraints() {
+        let input = r#"
+            const MAX_FEE: u16 = 10000;
+
+            struct Listing {
+                #[range(0..=MAX_FEE)]
+                fee_bps: u16,
+                #[range(-5..5)]
+                delta: Option<i32>,
+                #[range(0..=255)]
+                level: u8,
+                #[pattern("^[A-Z]{1,10}$")]
+                symbol: String,
+            }
+        "#;
+
+        let ir = transform_to_ir(parse_lumos_file(input).unwrap()).unwrap();
+        let TypeDefinition::Struct(s) = &ir[1] else {
+            panic!("Expected struct definition");
+        };
+        // A lower bound equal to the type minimum cannot fail and is dropped
+        assert_eq!(
+            s.fields[0].constraints,
+            vec![Constraint::Range {
+                min: None,
+                max: Some(10000)
+            }]
+        );
+        // Exclusive upper bounds become inclusive
+        assert_eq!(
+            s.fields[1].constraints,
+            vec![Constraint::Range {
+                min: Some(-5),
+                max: Some(4)
+            }]
+        );
+        assert!(s.fields[2].constraints.is_empty());
+        assert_eq!(
+            s.fields[3].constraints,
+            vec![Constraint::Pattern("^[A-Z]{1,10}$".to_string())]
+        );
+        assert!(s.has_constraints());
+    }
+
+    #[test]
+    fn test_invalid_constraints_rejected() {
+        let input = r#"
+            struct Bad {
+                #[range(0..=300)]
+                small: u8,
+                #[range(10..5)]
+                empty: u32,
+                #[range(0..=10)]
+                name: String,
+                #[pattern("^[a-z")]
+                code: String,
+                #[pattern("^x$")]
+                count: u64,
+                #[range(0..=UNKNOWN)]
+                limit: u64,
+            }
+
+            enum Event {
+                Listed {
+                    #[range(0..=100)]
+                    fee: u8,
+                },
+            }
+        "#;
+
+        let err = transform_to_ir(parse_lumos_file(input).unwrap()).unwrap_err();
+        let messages: Vec<String> = err.errors().iter().map(|e| e.message()).collect();
+        assert_eq!(messages.len(), 7, "{:#?}", messages);
+        assert!(messages[0].contains("300 is out of range for the field type"));
+        assert!(messages[1].contains("empty range"));
+        assert!(messages[2].contains("#[range] on field 'name' requires an integer type"));
+        assert!(messages[3].contains("Invalid #[pattern] on field 'code'"));
+        assert!(messages[4].contains("#[pattern] on field 'count' requires a String type"));
+        assert!(messages[5].contains("Unknown constant: UNKNOWN"));
+        assert!(messages[6]
+            .contains("#[range] is only supported on struct fields, found it on 'Listed::fee'"));
+        assert!(err.errors().iter().all(|e| e.location().is_some()));
+    }
+
+    #[test]
+    fn test_transform_field_defaults() {
+        let input = r#"
+            const MAX_FEE: u16 = 500;
+
+            struct Config {
+                #[default(MAX_FEE)]
+                fee_bps: u16,
+                #[default(1)]
+                ratio: f32,
+                #[default(true)]
+                active: bool,
+                #[max(8)]
+                #[default("USDC")]
+                symbol: String,
+                authority: PublicKey,
+            }
+        "#;
+
+        let ir = transform_to_ir(parse_lumos_file(input).unwrap()).unwrap();
+        let TypeDefinition::Struct(s) = &ir[1] else {
+            panic!("Expected struct definition");
+        };
+        let defaults: Vec<_> = s.fields.iter().map(|f| f.default.clone()).collect();
+        assert_eq!(
+            defaults,
+            vec![
+                Some(DefaultValue::Integer(500)),
+                Some(DefaultValue::Float(1.0)),
+                Some(DefaultValue::Bool(true)),
+                Some(DefaultValue::String("USDC".to_string())),
+                None,
+            ]
+        );
+        assert!(s.has_field_defaults());
+    }
+
+    #[test]
+    fn test_invalid_defaults_rejected() {
+        let input = r#"
+            struct Bad {
+                #[default(300)]
+                small: u8,
+                #[default("x")]
+                count: u64,
+                #[default(1)]
+                maybe: Option<u8>,
+                #[range(0..=100)]
+                #[default(200)]
+                fee: u16,
+                #[pattern("^[A-Z]+$")]
+                #[default("usdc")]
+                symbol: String,
+                #[max(2)]
+                #[default("long")]
+                code: String,
+                #[default]
+                bare: u8,
+            }
+
+            enum Event {
+                Listed {
+                    #[default(1)]
+                    fee: u8,
+                },
+            }
+        "#;
+
+        let err = transform_to_ir(parse_lumos_file(input).unwrap()).unwrap_err();
+        let messages: Vec<String> = err.errors().iter().map(|e| e.message()).collect();
+        assert_eq!(messages.len(), 8, "{:#?}", messages);
+        assert!(messages[0].contains("300 is out of range for u8"));
+        assert!(messages[1].contains("not a valid u64 value"));
+        assert!(messages[2].contains("optional fields always default to None"));
+        assert!(messages[3].contains("value must be at most 100"));
+        assert!(messages[4].contains("value must match /^[A-Z]+$/"));
+        assert!(messages[5].contains("longer than #[max(2)]"));
+        assert!(messages[6].contains("#[default] on field 'bare' needs a value"));
+        assert!(messages[7]
+            .contains("#[default] is only supported on struct fields, found it on 'Listed::fee'"));
+    }
+
+    #[test]
+    fn test_naming_policy_and_renames() {
+        let input = r#"
+            #![rename_fields(ts = "camelCase", idl = "camelCase")]
+            #![rename_types(py = "SCREAMING_SNAKE_CASE", idl = "snake_case")]
+
+            #[account]
+            struct MarketState {
+                #[rename(ts = "feeRate", rb = "feeBps")]
+                fee_bps: u16,
+                owner: PublicKey,
+            }
+
+            struct ListingInfo {
+                best_price: u64,
+            }
+        "#;
+
+        let ir = transform_to_ir(parse_lumos_file(input).unwrap()).unwrap();
+        let TypeDefinition::Struct(market) = &ir[0] else {
+            panic!("Expected struct definition");
+        };
+        // Anchor derives the account discriminator from the type name
+        assert_eq!(
+            market.metadata.renames,
+            Renames::from([(NameTarget::Python, "MARKET_STATE".to_string())])
+        );
+        assert_eq!(
+            market.fields[0].renames,
+            Renames::from([
+                (NameTarget::TypeScript, "feeRate".to_string()),
+                (NameTarget::Ruby, "feeBps".to_string()),
+                (NameTarget::Idl, "feeBps".to_string()),
+            ])
+        );
+        assert!(market.fields[1].renames.is_empty());
+
+        let TypeDefinition::Struct(listing) = &ir[1] else {
+            panic!("Expected struct definition");
+        };
+        assert_eq!(
+            listing
+                .metadata
+                .renames
+                .get(&NameTarget::Idl)
+                .map(String::as_str),
+            Some("listing_info")
+        );
+        assert_eq!(
+            listing.fields[0]
+                .renames
+                .get(&NameTarget::TypeScript)
+                .map(String::as_str),
+            Some("bestPrice")
+        );
+    }
+
+    #[test]
+    fn test_invalid_renames_rejected() {
+        let input = r#"
+            #![rename_fields(ts = "kebab-case")]
+            #![rename_types(java = "camelCase")]
+
+            #[rename(swift = "Thing")]
+            struct A {
+                #[rename(ts = "not valid")]
+                a: u8,
+            }
+
+            #[account]
+            #[rename(idl = "VaultV2")]
+            struct Vault {
+                #[rename]
+                b: u8,
+            }
+        "#;
+
+        let err = transform_to_ir(parse_lumos_file(input).unwrap()).unwrap_err();
+        let messages: Vec<String> = err.errors().iter().map(|e| e.message()).collect();
+        assert_eq!(messages.len(), 6, "{:#?}", messages);
+        assert!(messages[0].contains("Unknown #[rename] target 'swift' on 'A'"));
+        assert!(messages[1].contains("'not valid' is not an identifier"));
+        assert!(messages[2].contains("Cannot rename 'Vault' in the IDL"));
+        assert!(messages[3].contains("#[rename] on 'b' needs target names"));
+        assert!(messages[4].contains("Unknown naming convention 'kebab-case' in #![rename_fields]"));
+        assert!(messages[5].contains("Unknown #![rename_types] target 'java'"));
+    }
+
+    #[test]
+    fn test_renamed_name_collisions_rejected() {
+        let input = r#"
+            #![rename_fields(ts = "camelCase")]
+
+            struct Vault {
+                #[rename(py = "owner")]
+                authority: PublicKey,
+                owner: PublicKey,
+                fee_bps: u16,
+                feeBps: u16,
+            }
+
+            #[rename(go = "Vault")]
+            struct Other {
+                b: u8,
+            }
+        "#;
+
+        let err = transform_to_ir(parse_lumos_file(input).unwrap()).unwrap_err();
+        let messages: Vec<String> = err.errors().iter().map(|e| e.message()).collect();
+        assert_eq!(messages.len(), 3, "{:#?}", messages);
+        assert!(messages[0]
+            .contains("Fields 'fee_bps' and 'feeBps' of 'Vault' are both named 'feeBps' in ts"));
+        assert!(messages[1]
+            .contains("Fields 'authority' and 'owner' of 'Vault' are both named 'owner' in py"));
+        assert!(messages[2].contains("Types 'Vault' and 'Other' are both named 'Vault' in go"));
+    }
+
+    #[test]
+    fn test_transform_newtype_alias() {
+        let input = r#"
+            #[newtype]
+            type Lamports = u64;
+
+            type Name = String;
+
+            struct Account {
+                balance: Lamports,
+                history: Vec<Lamports>,
+                name: Name,
+            }
+        "#;
+
+        let ast = parse_lumos_file(input).unwrap();
+        let ir = transform_to_ir(ast).unwrap();
+
+        let TypeDefinition::TypeAlias(lamports) = &ir[0] else {
+            panic!("Expected type alias definition");
+        };
+        assert!(lamports.newtype);
+        let TypeDefinition::TypeAlias(name) = &ir[1] else {
+            panic!("Expected type alias definition");
+        };
+        assert!(!name.newtype);
+
+        // Newtypes are referenced by name, plain aliases are replaced
+        let TypeDefinition::Struct(s) = &ir[2] else {
+            panic!("Expected struct definition");
+        };
+        assert_eq!(
+            s.fields[0].type_info,
+            TypeInfo::UserDefined("Lamports".into())
+        );
+        assert_eq!(
+            s.fields[1].type_info,
+            TypeInfo::Array(Box::new(TypeInfo::UserDefined("Lamports".into())))
+        );
+        assert_eq!(s.fields[2].type_info, TypeInfo::Primitive("String".into()));
+
+        // Inlining restores the wire type everywhere
+        let inlined = inline_newtypes(&ir);
+        let TypeDefinition::Struct(s) = &inlined[2] else {
+            panic!("Expected struct definition");
+        };
+        assert_eq!(s.fields[0].type_info, TypeInfo::Primitive("u64".into()));
+        assert_eq!(
+            s.fields[1].type_info,
+            TypeInfo::Array(Box::new(TypeInfo::Primitive("u64".into())))
+        );
+    }
+
+    #[test]
+    fn test_invalid_newtypes_rejected() {
+        let ast = parse_lumos_file("#[newtype] type Tree = Vec<Tree>;").unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(err.contains("Newtype 'Tree' contains itself"), "{}", err);
+
+        let input = r#"
+            #[newtype]
+            type Lamports = u64;
+
+            #[zero_copy]
+            struct Vault {
+                balance: Lamports,
+            }
+        "#;
+        let ast = parse_lumos_file(input).unwrap();
+        let err = transform_to_ir(ast).unwrap_err().to_string();
+        assert!(
+            err.contains("has #[newtype] 'Lamports', which is not Pod"),
+            "{}",
+            err
+        );
+    }
 }
@@ -20,9 +20,7 @@ fn create_struct(
             name: name.to_string(),
             type_info,
             optional,
-            deprecated: None,
-            span: None,
-            anchor_attrs: vec![],
+            ..Default::default()
         })
         .collect();
 
@@ -34,12 +32,12 @@ fn create_struct(
             solana: true,
             attributes: vec!["account".to_string()],
             version: version.map(|s| s.to_string()),
-            custom_derives: vec![],
-            is_instruction: false,
-            anchor_attrs: vec![],
+            ..Default::default()
         },
         visibility: Visibility::Public,
         module_path: Vec::new(),
+        reexports: Vec::new(),
+        span: None,
     })
 }
 
@@ -477,3 +475,227 @@ fn test_option_type_change() {
     assert!(!report.is_compatible);
     assert_eq!(report.count_by_level(IssueLevel::Breaking), 1);
 }
+
+#[test]
+fn test_hashmap_to_btreemap_is_compatible() {
+    let map = |ordered| TypeInfo::Map {
+        key: Box::new(TypeInfo::Primitive("PublicKey".to_string())),
+        value: Box::new(TypeInfo::Primitive("u64".to_string())),
+        ordered,
+    };
+
+    let old = create_struct(
+        "Registry",
+        vec![("balances", map(false), false)],
+        Some("1.0.0"),
+    );
+    let new = create_struct(
+        "Registry",
+        vec![("balances", map(true), false)],
+        Some("1.1.0"),
+    );
+
+    let checker = CompatibilityChecker::new(old, new);
+    let report = checker.check().unwrap();
+
+    // Borsh writes both map kinds sorted by key, so the bytes are identical
+    assert!(report.is_compatible);
+    assert_eq!(report.count_by_level(IssueLevel::Breaking), 0);
+    assert_eq!(report.count_by_level(IssueLevel::Warning), 1);
+}
+
+#[test]
+fn test_map_value_type_change_is_breaking() {
+    let map = |value: &str| TypeInfo::Map {
+        key: Box::new(TypeInfo::Primitive("PublicKey".to_string())),
+        value: Box::new(TypeInfo::Primitive(value.to_string())),
+        ordered: false,
+    };
+
+    let old = create_struct(
+        "Registry",
+        vec![("balances", map("u64"), false)],
+        Some("1.0.0"),
+    );
+    let new = create_struct(
+        "Registry",
+        vec![("balances", map("u32"), false)],
+        Some("2.0.0"),
+    );
+
+    let checker = CompatibilityChecker::new(old, new);
+    let report = checker.check().unwrap();
+
+    assert!(!report.is_compatible);
+    assert_eq!(report.count_by_level(IssueLevel::Breaking), 1);
+}
+
+/// Helper to build the first type of a `.lumos` source
+fn parse_type(source: &str) -> TypeDefinition {
+    let ast = lumos_core::parser::parse_lumos_file(source).unwrap();
+    lumos_core::transform::transform_to_ir(ast)
+        .unwrap()
+        .remove(0)
+}
+
+#[test]
+fn test_changing_variant_discriminant_is_breaking() {
+    let old = parse_type("#[solana] enum Status { Active = 1, Frozen = 2 }");
+    let new = parse_type("#[solana] enum Status { Active = 1, Frozen = 3 }");
+
+    let checker = CompatibilityChecker::new(old, new);
+    let report = checker.check().unwrap();
+
+    assert!(!report.is_compatible);
+    assert_eq!(report.count_by_level(IssueLevel::Breaking), 1);
+    assert!(report.issues[0]
+        .message
+        .contains("Changed discriminant of variant 'Frozen': 2 → 3"));
+}
+
+#[test]
+fn test_inserting_variant_that_shifts_discriminants_is_breaking() {
+    let old = parse_type("#[solana] enum Status { Active, Frozen }");
+    let new = parse_type("#[solana] enum Status { Active, Paused, Frozen }");
+
+    let checker = CompatibilityChecker::new(old, new);
+    let report = checker.check().unwrap();
+
+    assert!(!report.is_compatible);
+    assert_eq!(report.count_by_level(IssueLevel::Breaking), 1);
+    assert_eq!(report.count_by_level(IssueLevel::Info), 1);
+}
+
+#[test]
+fn test_pinned_discriminants_allow_inserting_variants() {
+    let old = parse_type("#[solana] enum Status { Active, Frozen }");
+    let new = parse_type("#[solana] enum Status { Active = 0, Paused = 2, Frozen = 1 }");
+
+    let checker = CompatibilityChecker::new(old, new);
+    let report = checker.check().unwrap();
+
+    assert!(report.is_compatible);
+}
+
+#[test]
+fn test_changing_enum_repr_is_breaking() {
+    let old = parse_type("#[solana] enum Status { Active, Frozen }");
+    let new = parse_type("#[solana] #[repr(u16)] enum Status { Active, Frozen }");
+
+    let checker = CompatibilityChecker::new(old, new);
+    let report = checker.check().unwrap();
+
+    assert!(!report.is_compatible);
+    assert!(report.issues[0]
+        .message
+        .contains("Changed discriminant width: u8 → u16"));
+}
+
+#[test]
+fn test_changing_tuple_element_is_breaking() {
+    let pair = |second: &str| {
+        TypeInfo::Tuple(vec![
+            TypeInfo::Primitive("u64".to_string()),
+            TypeInfo::Primitive(second.to_string()),
+        ])
+    };
+    let old = create_struct("Pool", vec![("pair", pair("u32"), false)], Some("1.0.0"));
+
+    let unchanged = create_struct("Pool", vec![("pair", pair("u32"), false)], Some("1.0.1"));
+    let report = CompatibilityChecker::new(old.clone(), unchanged)
+        .check()
+        .unwrap();
+    assert!(report.is_compatible);
+    assert!(report.issues.is_empty());
+
+    let changed = create_struct("Pool", vec![("pair", pair("u16"), false)], Some("2.0.0"));
+    let report = CompatibilityChecker::new(old, changed).check().unwrap();
+    assert!(!report.is_compatible);
+    assert_eq!(report.count_by_level(IssueLevel::Breaking), 1);
+    assert!(report.breaking_issues()[0]
+        .message
+        .contains("Changed type of 'pair'"));
+}
+
+#[test]
+fn test_adding_field_with_default_is_compatible_for_versioned_accounts() {
+    let old = parse_type("#[solana] #[version = \"1.0.0\"] struct Market { authority: PublicKey }");
+    let new = parse_type(
+        "#[solana] #[version = \"1.1.0\"] struct Market { authority: PublicKey, #[default(100)] fee_bps: u16 }",
+    );
+
+    let report = CompatibilityChecker::new(old, new).check().unwrap();
+    assert!(report.is_compatible);
+    assert!(report.version_bump_valid);
+    assert_eq!(report.count_by_level(IssueLevel::Info), 1);
+    assert!(report.issues[0]
+        .message
+        .contains("Added field with default: fee_bps = 100"));
+
+    // Without versions there is no migration to fill the default in
+    let old = parse_type("#[solana] struct Market { authority: PublicKey }");
+    let new = parse_type(
+        "#[solana] struct Market { authority: PublicKey, #[default(100)] fee_bps: u16 }",
+    );
+    let report = CompatibilityChecker::new(old, new).check().unwrap();
+    assert!(!report.is_compatible);
+    assert!(report.issues[0]
+        .suggestion
+        .as_deref()
+        .unwrap()
+        .contains("#[version]"));
+}
+
+#[test]
+fn test_reordering_flag_bits_is_breaking() {
+    let old = parse_type("#[solana] #[flags(u16)] enum Permissions { Mint, Burn, Freeze }");
+    let new = parse_type("#[solana] #[flags(u16)] enum Permissions { Burn, Mint, Freeze }");
+
+    let report = CompatibilityChecker::new(old, new).check().unwrap();
+
+    assert!(!report.is_compatible);
+    assert_eq!(report.count_by_level(IssueLevel::Breaking), 2);
+    assert!(report.issues.iter().any(|issue| issue
+        .message
+        .contains("Moved flag 'Mint' from bit 0 to bit 1")));
+}
+
+#[test]
+fn test_appending_flag_is_compatible() {
+    let old = parse_type("#[solana] #[flags(u16)] enum Permissions { Mint, Burn }");
+    let new = parse_type("#[solana] #[flags(u16)] enum Permissions { Mint, Burn, Freeze }");
+
+    let report = CompatibilityChecker::new(old, new).check().unwrap();
+
+    assert!(report.is_compatible);
+    assert_eq!(report.count_by_level(IssueLevel::Info), 1);
+}
+
+#[test]
+fn test_changing_fixed_string_capacity_is_breaking() {
+    let old = parse_type("#[solana] struct Profile { name: FixedString<16> }");
+    let new = parse_type("#[solana] struct Profile { name: FixedString<32> }");
+
+    let report = CompatibilityChecker::new(old, new).check().unwrap();
+
+    assert!(!report.is_compatible);
+    assert_eq!(report.count_by_level(IssueLevel::Breaking), 1);
+}
+
+#[test]
+fn test_alias_to_newtype_is_compatible() {
+    let parse_inlined = |source: &str| {
+        let ast = lumos_core::parser::parse_lumos_file(source).unwrap();
+        let ir = lumos_core::transform::transform_to_ir(ast).unwrap();
+        lumos_core::transform::inline_newtypes(&ir).pop().unwrap()
+    };
+    let old = parse_inlined("type Lamports = u64; #[solana] struct Vault { balance: Lamports }");
+    let new = parse_inlined(
+        "#[newtype] type Lamports = u64; #[solana] struct Vault { balance: Lamports }",
+    );
+
+    let report = CompatibilityChecker::new(old, new).check().unwrap();
+
+    assert!(report.is_compatible);
+    assert_eq!(report.count_by_level(IssueLevel::Breaking), 0);
+}
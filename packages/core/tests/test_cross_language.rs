@@ -9,19 +9,15 @@ use lumos_core::generators::{
     typescript::generate_module as generate_typescript,
 };
 use lumos_core::ir::{
-    EnumDefinition, EnumVariantDefinition, FieldDefinition, Metadata, StructDefinition,
-    TypeDefinition, TypeInfo, Visibility,
+    ConstDefinition, EnumDefinition, EnumVariantDefinition, FieldDefinition, Metadata,
+    StructDefinition, TypeDefinition, TypeInfo, Visibility,
 };
 
 /// Helper to create test metadata
 fn test_metadata() -> Metadata {
     Metadata {
         solana: true,
-        attributes: vec![],
-        version: None,
-        custom_derives: vec![],
-        is_instruction: false,
-        anchor_attrs: vec![],
+        ..Default::default()
     }
 }
 
@@ -30,10 +26,7 @@ fn field(name: &str, type_info: TypeInfo) -> FieldDefinition {
     FieldDefinition {
         name: name.to_string(),
         type_info,
-        optional: false,
-        deprecated: None,
-        span: None,
-        anchor_attrs: vec![],
+        ..Default::default()
     }
 }
 
@@ -46,6 +39,8 @@ fn make_struct(name: &str, fields: Vec<FieldDefinition>) -> StructDefinition {
         generic_params: vec![],
         visibility: Visibility::Public,
         module_path: vec![],
+        reexports: vec![],
+        span: None,
     }
 }
 
@@ -58,6 +53,8 @@ fn make_enum(name: &str, variants: Vec<EnumVariantDefinition>) -> EnumDefinition
         generic_params: vec![],
         visibility: Visibility::Public,
         module_path: vec![],
+        reexports: vec![],
+        span: None,
     }
 }
 
@@ -132,17 +129,27 @@ fn create_test_enum() -> EnumDefinition {
         vec![
             EnumVariantDefinition::Unit {
                 name: "Active".to_string(),
+                docs: vec![],
+                msg: None,
+                discriminant: None,
             },
             EnumVariantDefinition::Unit {
                 name: "Paused".to_string(),
+                docs: vec![],
+                msg: None,
+                discriminant: None,
             },
             EnumVariantDefinition::Tuple {
                 name: "Score".to_string(),
                 types: vec![TypeInfo::Primitive("u64".to_string())],
+                docs: vec![],
+                discriminant: None,
             },
             EnumVariantDefinition::Struct {
                 name: "Data".to_string(),
                 fields: vec![field("value", TypeInfo::Primitive("String".to_string()))],
+                docs: vec![],
+                discriminant: None,
             },
         ],
     )
@@ -487,6 +494,114 @@ fn nested_vec_option_types() {
     assert!(ts_code.contains("option_of_vec"));
 }
 
+#[test]
+fn map_and_set_types_all_languages() {
+    let type_defs = vec![TypeDefinition::Struct(make_struct(
+        "Registry",
+        vec![
+            field(
+                "balances",
+                TypeInfo::Map {
+                    key: Box::new(TypeInfo::Primitive("PublicKey".to_string())),
+                    value: Box::new(TypeInfo::Primitive("u64".to_string())),
+                    ordered: false,
+                },
+            ),
+            field(
+                "tags",
+                TypeInfo::Set {
+                    element: Box::new(TypeInfo::Primitive("String".to_string())),
+                    ordered: true,
+                },
+            ),
+        ],
+    ))];
+
+    let rust_code = generate_rust(&type_defs);
+    let ts_code = generate_typescript(&type_defs);
+    let python_code = generate_python(&type_defs);
+    let go_code = generate_go(&type_defs);
+    let ruby_code = generate_ruby(&type_defs);
+
+    // Rust: std collections with imports
+    assert!(rust_code.contains("use std::collections::HashMap;"));
+    assert!(rust_code.contains("use std::collections::BTreeSet;"));
+    assert!(rust_code.contains("pub balances: HashMap<Pubkey, u64>,"));
+    assert!(rust_code.contains("pub tags: BTreeSet<String>,"));
+
+    // TypeScript: Map with borsh.map, sets are arrays sharing the Vec encoding
+    assert!(ts_code.contains("balances: Map<PublicKey, number>;"));
+    assert!(ts_code.contains("tags: string[];"));
+    assert!(ts_code.contains("borsh.map(borsh.publicKey, borsh.u64)('balances')"));
+    assert!(ts_code.contains("borsh.vec(borsh.string)('tags')"));
+
+    // Python: dict/set with borsh-construct HashMap/HashSet
+    assert!(python_code.contains("balances: dict[Pubkey, int]"));
+    assert!(python_code.contains("tags: set[str]"));
+    assert!(python_code.contains("\"balances\" / HashMap(Bytes(32), U64)"));
+    assert!(python_code.contains("\"tags\" / HashSet(String)"));
+
+    // Go: native maps, sets as map[T]struct{}
+    assert!(go_code.contains("map[[32]byte]uint64"));
+    assert!(go_code.contains("map[string]struct{}"));
+
+    // Ruby: Hash/Set documentation and borsh schema
+    assert!(ruby_code.contains("[:map, [:u8, 32], :u64]"));
+    assert!(ruby_code.contains("[:set, :string]"));
+}
+
+#[test]
+fn constants_all_languages() {
+    let type_defs = vec![
+        TypeDefinition::Const(ConstDefinition {
+            name: "MAX_NAME_LEN".to_string(),
+            type_name: "usize".to_string(),
+            value: 32,
+            visibility: Visibility::Public,
+            module_path: vec![],
+            reexports: vec![],
+            docs: vec!["Longest allowed name".to_string()],
+        }),
+        TypeDefinition::Const(ConstDefinition {
+            name: "MAX_SUPPLY".to_string(),
+            type_name: "u128".to_string(),
+            value: u128::MAX,
+            visibility: Visibility::Public,
+            module_path: vec![],
+            reexports: vec![],
+            docs: vec![],
+        }),
+        TypeDefinition::Struct(make_struct(
+            "Profile",
+            vec![field(
+                "name",
+                TypeInfo::FixedArray {
+                    element: Box::new(TypeInfo::Primitive("u8".to_string())),
+                    size: 32,
+                },
+            )],
+        )),
+    ];
+
+    let rust_code = generate_rust(&type_defs);
+    let ts_code = generate_typescript(&type_defs);
+    let python_code = generate_python(&type_defs);
+    let go_code = generate_go(&type_defs);
+    let ruby_code = generate_ruby(&type_defs);
+
+    assert!(rust_code.contains("/// Longest allowed name\npub const MAX_NAME_LEN: usize = 32;"));
+    assert!(ts_code.contains("export const MAX_NAME_LEN = 32;"));
+    assert!(python_code.contains("#: Longest allowed name\nMAX_NAME_LEN = 32"));
+    assert!(go_code.contains("// Longest allowed name\nconst MAX_NAME_LEN = 32"));
+    assert!(ruby_code.contains("MAX_NAME_LEN = 32"));
+
+    let max = "340282366920938463463374607431768211455";
+    assert!(rust_code.contains(&format!("pub const MAX_SUPPLY: u128 = {};", max)));
+    assert!(ts_code.contains(&format!("export const MAX_SUPPLY = BigInt(\"{}\");", max)));
+    assert!(python_code.contains(&format!("MAX_SUPPLY = {}", max)));
+    assert!(go_code.contains(&format!("const MAX_SUPPLY = {}", max)));
+}
+
 #[test]
 fn multiple_structs_all_languages() {
     let type_defs = vec![
@@ -527,3 +642,72 @@ fn multiple_structs_all_languages() {
         );
     }
 }
+
+#[test]
+fn doc_comments_in_all_languages() {
+    let mut wallet = field("wallet", TypeInfo::Primitive("PublicKey".to_string()));
+    wallet.docs = vec!["Wallet that owns this account".to_string()];
+
+    let mut account = make_struct(
+        "Player",
+        vec![
+            wallet,
+            field("level", TypeInfo::Primitive("u16".to_string())),
+        ],
+    );
+    account.metadata.docs = vec!["Player account".to_string()];
+
+    let mut state = make_enum(
+        "GameState",
+        vec![
+            EnumVariantDefinition::Unit {
+                name: "Lobby".to_string(),
+                docs: vec!["Waiting for players".to_string()],
+                msg: None,
+                discriminant: None,
+            },
+            EnumVariantDefinition::Unit {
+                name: "Active".to_string(),
+                docs: vec![],
+                msg: None,
+                discriminant: None,
+            },
+        ],
+    );
+    state.metadata.docs = vec!["Lifecycle state".to_string()];
+
+    let type_defs = vec![TypeDefinition::Struct(account), TypeDefinition::Enum(state)];
+
+    let rust_code = generate_rust(&type_defs);
+    assert!(rust_code.contains("/// Player account\n"));
+    assert!(rust_code.contains("    /// Wallet that owns this account\n    pub wallet: Pubkey,"));
+    assert!(rust_code.contains("    /// Waiting for players\n    Lobby,"));
+
+    let ts_code = generate_typescript(&type_defs);
+    assert!(ts_code.contains("/**\n * Player account\n */\nexport interface Player"));
+    assert!(
+        ts_code.contains("  /**\n   * Wallet that owns this account\n   */\n  wallet: PublicKey;")
+    );
+    assert!(ts_code.contains("/**\n * Lifecycle state\n */\nexport type GameState"));
+    assert!(ts_code.contains("  /**\n   * Waiting for players\n   */\n  | { kind: 'Lobby' }"));
+
+    let py_code = generate_python(&type_defs);
+    assert!(py_code.contains(
+        "class Player:\n    \"\"\"\n    Player account\n\n    Attributes:\n        wallet: Wallet that owns this account\n    \"\"\"\n"
+    ));
+    assert!(py_code
+        .contains("class GameState(IntEnum):\n    \"\"\"\n    Lifecycle state\n    \"\"\"\n"));
+    assert!(py_code.contains("    #: Waiting for players\n    Lobby = 0"));
+
+    let go_code = generate_go(&type_defs);
+    assert!(go_code.contains("// Player account\ntype Player struct"));
+    assert!(go_code.contains("\t// Wallet that owns this account\n\tWallet "));
+    assert!(go_code.contains("// Lifecycle state\ntype GameState uint8"));
+    assert!(go_code.contains("\t// Waiting for players\n\tGameStateLobby "));
+
+    let rb_code = generate_ruby(&type_defs);
+    assert!(rb_code.contains("# Player account\nclass Player"));
+    assert!(rb_code.contains(":wallet Wallet that owns this account\n"));
+    assert!(rb_code.contains("# Lifecycle state\nmodule GameState"));
+    assert!(rb_code.contains("  # Waiting for players\n  LOBBY = 0"));
+}
@@ -588,6 +588,84 @@ fn test_e2e_generic_types_compile() {
     println!("✓ E2E generic types test passed (parse → IR → Rust compile + TypeScript syntax)");
 }
 
+#[test]
+fn test_e2e_user_defined_map_keys_compile() {
+    let lumos_code = r#"
+        #[solana]
+        struct Coord {
+            x: i32,
+            y: i32,
+        }
+
+        #[solana]
+        struct Cell {
+            coord: Coord,
+            layer: Option<u8>,
+        }
+
+        #[solana]
+        enum Side {
+            Buy,
+            Sell,
+            Limit { price: u64 },
+        }
+
+        #[solana]
+        struct Wrapper<T> {
+            value: T,
+        }
+
+        #[solana]
+        #[newtype]
+        type CellId = Cell;
+
+        #[solana]
+        struct Board {
+            owners: HashMap<Cell, PublicKey>,
+            sides: BTreeSet<Side>,
+            ids: HashSet<CellId>,
+            wrapped: BTreeMap<Wrapper<u64>, u8>,
+            orders: HashMap<(Side, u8), Vec<u64>>,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse map key schema");
+    let ir = transform_to_ir(ast).expect("Failed to transform map key schema");
+
+    let rust_code = rust::generate_module(&ir);
+    println!("Generated Rust code:\n{}", rust_code);
+
+    // Key types, and the types stored inside them, derive what maps and sets need
+    for name in ["Coord", "Cell", "Side", "Wrapper<T>"] {
+        let definition = rust_code
+            .find(&format!(" {} {{", name))
+            .unwrap_or_else(|| panic!("{} not generated", name));
+        let derive = rust_code[..definition].rfind("#[derive(").unwrap();
+        assert!(
+            rust_code[derive..definition].contains("Eq, Hash, PartialOrd, Ord"),
+            "{} lacks key derives",
+            name
+        );
+    }
+    assert!(rust_code.contains("pub struct CellId(pub Cell);"));
+
+    let (temp_dir, project_dir) = create_temp_rust_project("test_map_keys", &rust_code);
+
+    let output = Command::new("cargo")
+        .arg("check")
+        .current_dir(&project_dir)
+        .output()
+        .expect("Failed to run cargo check");
+
+    if !output.status.success() {
+        eprintln!("STDOUT: {}", String::from_utf8_lossy(&output.stdout));
+        eprintln!("STDERR: {}", String::from_utf8_lossy(&output.stderr));
+        panic!("Rust compilation failed for user-defined map keys");
+    }
+
+    drop(temp_dir);
+}
+
 #[test]
 fn test_e2e_instruction_context_generation() {
     // Test the complete pipeline for Anchor instruction context generation
@@ -330,6 +330,8 @@ mod generator_errors {
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         };
 
         let type_def = TypeDefinition::Struct(empty_struct);
@@ -352,6 +354,8 @@ mod generator_errors {
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         };
 
         let type_def = TypeDefinition::Enum(empty_enum);
@@ -373,9 +377,7 @@ mod generator_errors {
                 Box::new(TypeInfo::Primitive("u64".to_string())),
             ))))),
             optional: true,
-            deprecated: None,
-            span: None,
-            anchor_attrs: vec![],
+            ..Default::default()
         };
 
         let struct_def = StructDefinition {
@@ -385,6 +387,8 @@ mod generator_errors {
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         };
 
         let type_def = TypeDefinition::Struct(struct_def);
@@ -406,26 +410,32 @@ mod generator_errors {
             variants: vec![
                 EnumVariantDefinition::Unit {
                     name: "Empty".to_string(),
+                    docs: vec![],
+                    msg: None,
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Tuple {
                     name: "WithData".to_string(),
                     types: vec![TypeInfo::Primitive("u64".to_string())],
+                    docs: vec![],
+                    discriminant: None,
                 },
                 EnumVariantDefinition::Struct {
                     name: "WithFields".to_string(),
                     fields: vec![FieldDefinition {
                         name: "value".to_string(),
                         type_info: TypeInfo::Primitive("String".to_string()),
-                        optional: false,
-                        deprecated: None,
-                        span: None,
-                        anchor_attrs: vec![],
+                        ..Default::default()
                     }],
+                    docs: vec![],
+                    discriminant: None,
                 },
             ],
             metadata: Metadata::default(),
             visibility: Visibility::Public,
             module_path: Vec::new(),
+            reexports: Vec::new(),
+            span: None,
         };
 
         let type_def = TypeDefinition::Enum(mixed_enum);
@@ -5,8 +5,9 @@
 //!
 //! Tests the complete pipeline: .lumos → AST → IR → Rust code
 
-use lumos_core::generators::rust::{generate, generate_module};
+use lumos_core::generators::rust::{generate, generate_module, generate_module_tree};
 use lumos_core::parser::parse_lumos_file;
+use lumos_core::project::ProjectGraph;
 use lumos_core::transform::transform_to_ir;
 use std::fs;
 use std::path::PathBuf;
@@ -251,3 +252,388 @@ fn test_generate_generic_enum() {
     assert!(rust_code.contains("Ok(T)"));
     assert!(rust_code.contains("Err(E)"));
 }
+
+#[test]
+fn test_generate_event_struct() {
+    let lumos_code = r#"
+        #[solana]
+        #[event]
+        struct TradeExecuted {
+            trader: PublicKey,
+            amount: u64,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let rust_code = generate_module(&ir);
+
+    // Anchor's #[event] provides the serialization derives
+    assert!(rust_code.contains("use anchor_lang::prelude::*;"));
+    assert!(rust_code.contains("#[event]\npub struct TradeExecuted {"));
+    assert!(!rust_code.contains("#[derive(AnchorSerialize"));
+    assert!(!rust_code.contains("#[account]"));
+}
+
+#[test]
+fn test_generate_error_code_enum() {
+    let lumos_code = r#"
+        #[error_code(offset = 7000)]
+        enum VaultError {
+            #[msg("Insufficient funds")]
+            InsufficientFunds,
+            Unauthorized,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let rust_code = generate_module(&ir);
+
+    assert!(rust_code.contains("use anchor_lang::prelude::*;"));
+    assert!(rust_code.contains(
+        "#[error_code(offset = 7000)]\npub enum VaultError {\n    #[msg(\"Insufficient funds\")]\n    InsufficientFunds,\n    Unauthorized,\n}"
+    ));
+    assert!(!rust_code.contains("#[derive("));
+}
+
+#[test]
+fn test_generate_enum_with_explicit_discriminants() {
+    let lumos_code = r#"
+        #[solana]
+        #[repr(u16)]
+        enum Command {
+            Noop = 3,
+            Transfer { amount: u64 } = 300,
+            Pair(u8, u8),
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let rust_code = generate_module(&ir);
+
+    assert!(rust_code
+        .contains("#[derive(Debug, Clone)]\n#[repr(u16)]\npub enum Command {\n    Noop = 3,\n"));
+    assert!(rust_code.contains("    } = 300,\n    Pair(u8, u8),\n}"));
+    assert!(rust_code.contains("impl BorshSerialize for Command {"));
+    assert!(rust_code.contains("            Command::Noop => 3u16.serialize(writer),"));
+    assert!(rust_code.contains(
+        "            Command::Pair(f0, f1) => {\n                301u16.serialize(writer)?;"
+    ));
+    assert!(rust_code.contains("impl BorshDeserialize for Command {"));
+    assert!(
+        rust_code.contains("        let tag: u16 = BorshDeserialize::deserialize_reader(reader)?;")
+    );
+    assert!(rust_code.contains("            300 => Ok(Command::Transfer {\n"));
+    assert!(rust_code.contains("\"Invalid Command discriminant: {}\""));
+}
+
+#[test]
+fn test_generate_enum_with_positional_discriminants_keeps_derives() {
+    let lumos_code = r#"
+        #[solana]
+        enum Status {
+            Active = 0,
+            Frozen = 1,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let rust_code = generate_module(&ir);
+
+    assert!(rust_code.contains(
+        "#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]\n#[repr(u8)]\npub enum Status {"
+    ));
+    assert!(!rust_code.contains("impl BorshSerialize"));
+}
+
+#[test]
+fn test_generate_instruction_builder() {
+    let lumos_code = r#"
+        #[solana]
+        #[instruction(amount: u64)]
+        struct Deposit {
+            #[anchor(mut)]
+            vault: Vault,
+            owner: Signer,
+        }
+
+        #[solana]
+        struct Vault {
+            balance: u64,
+        }
+
+        #[solana]
+        struct Signer {}
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let rust_code = generate_module(&ir);
+
+    assert!(rust_code.contains("use solana_program::instruction::{AccountMeta, Instruction};"));
+    assert!(rust_code.contains("use solana_program::pubkey::Pubkey;"));
+    assert!(rust_code.contains(
+        "pub const DEPOSIT_DISCRIMINATOR: [u8; 8] = [242, 35, 198, 137, 82, 225, 242, 182];"
+    ));
+    assert!(rust_code.contains(
+        "pub struct DepositAccounts {\n    pub vault: Pubkey,\n    pub owner: Pubkey,\n}"
+    ));
+    assert!(rust_code.contains(
+        "#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]\npub struct DepositArgs {\n    pub amount: u64,\n}"
+    ));
+    assert!(rust_code.contains(
+        "pub fn deposit_instruction(program_id: Pubkey, accounts: &DepositAccounts, args: &DepositArgs) -> Instruction {"
+    ));
+    assert!(rust_code.contains("AccountMeta::new(accounts.vault, false),"));
+    assert!(rust_code.contains("AccountMeta::new_readonly(accounts.owner, true),"));
+    assert!(!rust_code.contains("pub struct Deposit {"));
+}
+
+#[test]
+fn test_generate_zero_copy_account() {
+    let lumos_code = r#"
+        #[solana]
+        #[zero_copy]
+        struct Order {
+            side: u8,
+            price: u64,
+        }
+
+        #[solana]
+        #[account]
+        #[zero_copy]
+        struct OrderBook {
+            authority: PublicKey,
+            count: u32,
+            orders: [Order; 16],
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let rust_code = generate_module(&ir);
+
+    // Anchor's zero_copy macros supply repr(C) and the Pod derives
+    assert!(rust_code.contains("#[zero_copy]\npub struct Order {"));
+    assert!(rust_code.contains("#[account(zero_copy)]\npub struct OrderBook {"));
+    assert!(!rust_code.contains("AnchorSerialize"));
+    assert!(rust_code.contains(
+        "    pub side: u8,\n    /// Alignment padding\n    pub _padding0: [u8; 7],\n    pub price: u64,\n"
+    ));
+    assert!(rust_code.contains("    pub count: u32,\n    /// Alignment padding\n    pub _padding0: [u8; 4],\n    pub orders: [Order; 16],\n}"));
+
+    // Outside Anchor the layout and derives are spelled out
+    let order = generate(&ir[0]);
+    assert!(order.contains(
+        "#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]\n#[repr(C)]\npub struct Order {"
+    ));
+    assert!(!order.contains("borsh"));
+}
+
+#[test]
+fn test_generate_tuple_fields() {
+    let lumos_code = r#"
+        #[solana]
+        #[account]
+        struct Pool {
+            pair: (u64, PublicKey),
+            shares: Vec<(PublicKey, u16)>,
+            single: (u8,),
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let rust_code = generate_module(&ir);
+
+    assert!(rust_code.contains("pub pair: (u64, Pubkey),"));
+    assert!(rust_code.contains("pub shares: Vec<(Pubkey, u16)>,"));
+    assert!(rust_code.contains("pub single: (u8,),"));
+}
+
+#[test]
+fn test_generate_recursive_types_with_box() {
+    let lumos_code = r#"
+        #[solana]
+        struct Node {
+            value: u64,
+            children: Vec<Node>,
+            next: Option<Box<Node>>,
+        }
+
+        #[solana]
+        enum Expr {
+            Literal(u64),
+            Negate(Box<Expr>),
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let rust_code = generate_module(&ir);
+
+    assert!(rust_code.contains("pub children: Vec<Node>,"));
+    assert!(rust_code.contains("pub next: Option<Box<Node>>,"));
+    assert!(rust_code.contains("Negate(Box<Expr>),"));
+}
+
+#[test]
+fn test_generate_flags_enum() {
+    let lumos_code = r#"
+        #[solana]
+        #[flags(u16)]
+        enum Permissions {
+            Mint,
+            Burn,
+            Admin = 128,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let rust_code = generate_module(&ir);
+
+    assert!(rust_code.contains(
+        "#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]\npub struct Permissions(u16);\n"
+    ));
+    assert!(rust_code.contains("    pub const MINT: Self = Self(1 << 0);\n"));
+    assert!(rust_code.contains("    pub const ADMIN: Self = Self(1 << 7);\n"));
+    assert!(rust_code.contains("    pub const fn all() -> Self {\n        Self(0x83)\n    }"));
+    assert!(rust_code.contains("impl std::ops::BitOr for Permissions {"));
+    assert!(!rust_code.contains("enum Permissions"));
+}
+
+#[test]
+fn test_generate_fixed_string_accessors() {
+    let lumos_code = r#"
+        #[solana]
+        #[account]
+        struct Profile {
+            name: FixedString<32>,
+            tags: Vec<FixedString<8>>,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let rust_code = generate_module(&ir);
+
+    assert!(rust_code.contains("    pub name: [u8; 32],\n"));
+    assert!(rust_code.contains("    pub tags: Vec<[u8; 8]>,\n"));
+    assert!(rust_code.contains("impl Profile {\n"));
+    assert!(rust_code.contains("    pub fn name_str(&self) -> &str {\n"));
+    assert!(rust_code.contains("    pub fn set_name(&mut self, value: &str) -> bool {\n"));
+    assert!(rust_code.contains("        let mut len = value.len().min(32);\n"));
+    // Only direct fields get accessors
+    assert!(!rust_code.contains("fn tags_str"));
+}
+
+#[test]
+fn test_generate_newtype_alias() {
+    let lumos_code = r#"
+        #[newtype]
+        type UserId = PublicKey;
+
+        #[newtype]
+        type Score = f64;
+
+        #[solana]
+        struct Profile {
+            owner: UserId,
+            score: Score,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let rust_code = generate_module(&ir);
+
+    assert!(rust_code.contains("use borsh::{BorshSerialize, BorshDeserialize};\n"));
+    assert!(rust_code.contains(
+        "#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize, Copy, Eq, Hash, PartialOrd, Ord)]\npub struct UserId(pub Pubkey);\n"
+    ));
+    assert!(rust_code.contains("impl From<Pubkey> for UserId {\n"));
+    assert!(rust_code.contains("impl From<UserId> for Pubkey {\n"));
+    assert!(rust_code.contains("impl std::ops::Deref for UserId {\n    type Target = Pubkey;\n"));
+    // Floats have no total order
+    assert!(rust_code.contains(
+        "#[derive(Debug, Clone, PartialEq, BorshSerialize, BorshDeserialize)]\npub struct Score(pub f64);\n"
+    ));
+    assert!(rust_code.contains("    pub owner: UserId,\n"));
+    assert!(!rust_code.contains("pub type UserId"));
+}
+
+#[test]
+fn test_generate_module_tree() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.pop();
+    path.pop();
+    path.push("examples/modules/shared-names/main.lumos");
+
+    let ir = ProjectGraph::load(&path)
+        .expect("Failed to load project")
+        .resolve()
+        .expect("Failed to resolve project");
+    let files = generate_module_tree(&ir);
+
+    let paths: Vec<String> = files
+        .iter()
+        .map(|f| f.path.to_string_lossy().replace('\\', "/"))
+        .collect();
+    assert_eq!(
+        paths,
+        vec![
+            "mod.rs",
+            "models/mod.rs",
+            "models/user/mod.rs",
+            "settings/mod.rs"
+        ]
+    );
+
+    // The root declares its children and aliases the clashing Config
+    let root = &files[0].content;
+    assert!(root.contains("pub mod models;\npub mod settings;"));
+    assert!(root.contains("use self::settings::Config as SettingsConfig;"));
+    assert!(root.contains("pub struct Config {"));
+    assert!(root.contains("pub settings: SettingsConfig,"));
+
+    let models = &files[1].content;
+    assert!(models.contains("pub mod user;"));
+    assert!(models.contains("pub struct Stamp {"));
+
+    // Nested modules reach their siblings through super
+    let user = &files[2].content;
+    assert!(user.contains("use super::Stamp;"));
+    assert!(user.contains("use super::super::settings::Config;"));
+    assert!(user.contains("pub config: Config,"));
+
+    assert!(files[3].content.contains("pub struct Config {"));
+}
+
+#[test]
+fn test_generate_module_tree_reexports() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.pop();
+    path.pop();
+    path.push("examples/modules/reexports/main.lumos");
+
+    let ir = ProjectGraph::load(&path)
+        .expect("Failed to load project")
+        .resolve()
+        .expect("Failed to resolve project");
+    let files = generate_module_tree(&ir);
+
+    // The root reaches both types through the re-exports in models
+    let root = &files[0].content;
+    assert!(root.contains("use self::models::Balance;\nuse self::models::Member;"));
+    assert!(root.contains("pub owner: Member,"));
+
+    // A glob re-export passes on only the public Balance
+    let models = &files[1].content;
+    assert!(models.contains("pub use self::user::User as Member;"));
+    assert!(models.contains("pub use self::account::Balance;"));
+    assert!(!models.contains("Ledger"));
+}
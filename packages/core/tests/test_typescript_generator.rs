@@ -5,8 +5,9 @@
 //!
 //! Tests the complete pipeline: .lumos → AST → IR → TypeScript code
 
-use lumos_core::generators::typescript::{generate, generate_module};
+use lumos_core::generators::typescript::{generate, generate_module, generate_module_tree};
 use lumos_core::parser::parse_lumos_file;
+use lumos_core::project::ProjectGraph;
 use lumos_core::transform::transform_to_ir;
 use std::fs;
 use std::path::PathBuf;
@@ -273,3 +274,536 @@ fn test_generate_versioned_schema() {
 
     println!("Generated TypeScript code with versions:\n{}", ts_code);
 }
+
+#[test]
+fn test_generate_event_decoder() {
+    let schema = r#"
+        #[solana]
+        #[event]
+        struct TradeExecuted {
+            trader: PublicKey,
+            amount: u64,
+        }
+
+        #[solana]
+        #[event]
+        struct OrderCancelled {
+            order_id: u64,
+        }
+    "#;
+
+    let ast = parse_lumos_file(schema).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let ts_code = generate_module(&ir);
+
+    // sha256("event:TradeExecuted")[..8]
+    assert!(ts_code.contains(
+        "export const TradeExecutedDiscriminator = Buffer.from([41, 110, 64, 129, 60, 79, 179, 80]);"
+    ));
+    assert!(ts_code.contains("import { Buffer } from 'buffer'"));
+    assert!(ts_code.contains("| { name: 'TradeExecuted'; data: TradeExecuted }"));
+    assert!(ts_code.contains("| { name: 'OrderCancelled'; data: OrderCancelled };"));
+    assert!(ts_code.contains("export function decodeEvent(logLine: string): ProgramEvent | null"));
+    assert!(ts_code.contains("const PROGRAM_DATA_PREFIX = 'Program data: ';"));
+    assert!(ts_code.contains(
+        "return { name: 'OrderCancelled', data: OrderCancelledSchema.decode(payload) };"
+    ));
+    assert!(ts_code.contains("export function decodeEvents(logs: string[]): ProgramEvent[]"));
+}
+
+#[test]
+fn test_generate_error_code_map() {
+    let schema = r#"
+        /// Vault program errors
+        #[error_code]
+        enum VaultError {
+            #[msg("Insufficient funds")]
+            InsufficientFunds,
+            Unauthorized,
+        }
+    "#;
+
+    let ast = parse_lumos_file(schema).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let ts_code = generate_module(&ir);
+
+    assert!(ts_code.contains("export type VaultErrorName = 'InsufficientFunds' | 'Unauthorized';"));
+    assert!(ts_code.contains("export const VaultErrorMap: Record<number, VaultErrorInfo> = {"));
+    assert!(ts_code
+        .contains("6000: { code: 6000, name: 'InsufficientFunds', msg: \"Insufficient funds\" },"));
+    assert!(ts_code.contains("6001: { code: 6001, name: 'Unauthorized', msg: null },"));
+    assert!(ts_code.contains("/**\n * Vault program errors\n */\nexport const VaultError = {"));
+    assert!(ts_code.contains("  InsufficientFunds: 6000,\n"));
+    assert!(ts_code.contains("fromCode(code: number): VaultErrorInfo | null {"));
+    // Errors are never Borsh-encoded
+    assert!(!ts_code.contains("VaultErrorSchema"));
+    assert!(!ts_code.contains("kind: 'InsufficientFunds'"));
+}
+
+#[test]
+fn test_generate_enum_with_explicit_discriminants() {
+    let schema = r#"
+        #[solana]
+        #[repr(u16)]
+        enum Wide {
+            First,
+            Second,
+        }
+
+        #[solana]
+        enum Sparse {
+            Active = 1,
+            Locked(u64) = 9,
+        }
+    "#;
+
+    let ast = parse_lumos_file(schema).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let ts_code = generate_module(&ir);
+
+    // Positional tags only need a wider tag layout
+    assert!(ts_code.contains(
+        "export const WideSchema = borsh.rustEnum([\n  borsh.unit('First'),\n  borsh.unit('Second'),\n], undefined, borsh.u16());"
+    ));
+    // Sparse tags register each variant under its discriminant
+    assert!(
+        ts_code.contains("export const SparseSchema = borsh.rustEnum([], undefined, borsh.u8());")
+    );
+    assert!(
+        ts_code.contains("(SparseSchema as any).addVariant(1, borsh.unit('Active'), 'Active');")
+    );
+    assert!(ts_code.contains(
+        "(SparseSchema as any).addVariant(9, borsh.tuple([\n  borsh.u64,\n], 'Locked'), 'Locked');"
+    ));
+}
+
+#[test]
+fn test_generate_instruction_builder() {
+    let schema = r#"
+        #[solana]
+        #[instruction(amount: u64)]
+        struct Deposit {
+            #[anchor(mut)]
+            vault: Vault,
+            owner: Signer,
+            referrer: Option<Vault>,
+        }
+
+        #[solana]
+        #[instruction]
+        struct Close {
+            #[anchor(mut, close = owner)]
+            vault: Vault,
+        }
+
+        #[solana]
+        #[account]
+        struct Vault {
+            balance: u64,
+        }
+
+        #[solana]
+        struct Signer {}
+    "#;
+
+    let ast = parse_lumos_file(schema).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let ts_code = generate_module(&ir);
+
+    // sha256("global:deposit")[..8]
+    assert!(ts_code.contains(
+        "export const DepositDiscriminator = Buffer.from([242, 35, 198, 137, 82, 225, 242, 182]);"
+    ));
+    assert!(ts_code.contains("import { TransactionInstruction } from '@solana/web3.js'"));
+    assert!(ts_code.contains("export interface DepositAccounts {\n  vault: PublicKey;\n  owner: PublicKey;\n  referrer?: PublicKey;\n}"));
+    assert!(ts_code.contains("export interface DepositArgs {"));
+    assert!(ts_code.contains("export const DepositArgsSchema = borsh.struct(["));
+    assert!(ts_code.contains("export function createDepositInstruction(\n  programId: PublicKey,\n  accounts: DepositAccounts,\n  args: DepositArgs,\n): TransactionInstruction {"));
+    assert!(ts_code.contains("{ pubkey: accounts.vault, isSigner: false, isWritable: true },"));
+    assert!(ts_code.contains("{ pubkey: accounts.owner, isSigner: true, isWritable: false },"));
+    assert!(ts_code.contains("accounts.referrer ? { pubkey: accounts.referrer, isSigner: false, isWritable: false } : { pubkey: programId, isSigner: false, isWritable: false },"));
+    assert!(ts_code
+        .contains("data: Buffer.concat([DepositDiscriminator, buffer.subarray(0, length)]),"));
+    // The context struct itself is not emitted as a data type
+    assert!(!ts_code.contains("export interface Deposit {"));
+    assert!(!ts_code.contains("DepositSchema"));
+
+    // Instructions without arguments take no args parameter
+    assert!(ts_code.contains("export function createCloseInstruction(\n  programId: PublicKey,\n  accounts: CloseAccounts,\n): TransactionInstruction {"));
+    assert!(ts_code.contains("data: Buffer.from(CloseDiscriminator),"));
+    assert!(!ts_code.contains("CloseArgs"));
+}
+
+#[test]
+fn test_generate_zero_copy_decoder() {
+    let schema = r#"
+        #[solana]
+        #[zero_copy]
+        struct Order {
+            side: u8,
+            price: u64,
+        }
+
+        #[solana]
+        #[account]
+        #[zero_copy]
+        struct OrderBook {
+            authority: PublicKey,
+            total: u128,
+            orders: [Order; 16],
+        }
+    "#;
+
+    let ast = parse_lumos_file(schema).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let ts_code = generate_module(&ir);
+
+    // C layout decoders replace the Borsh schemas
+    assert!(!ts_code.contains("borsh"));
+    assert!(ts_code.contains("export const OrderSize = 16;"));
+    assert!(ts_code.contains("export const OrderBookSize = 304;"));
+    assert!(ts_code.contains("export function decodeOrder(data: Uint8Array, offset = 0): Order {"));
+    // Account decoders skip the 8-byte discriminator by default
+    assert!(ts_code
+        .contains("export function decodeOrderBook(data: Uint8Array, offset = 8): OrderBook {"));
+    assert!(ts_code.contains("    side: view.getUint8(offset),\n    _padding0: Array.from(data.subarray(offset + 1, offset + 8)),\n    price: Number(view.getBigUint64(offset + 8, true)),\n"));
+    assert!(ts_code.contains(
+        "    total: (view.getBigUint64(offset + 40, true) << 64n) | view.getBigUint64(offset + 32, true),\n"
+    ));
+    assert!(ts_code.contains(
+        "    orders: Array.from({ length: 16 }, (_, i) => decodeOrder(data, offset + 48 + i * 16)),\n"
+    ));
+}
+
+#[test]
+fn test_generate_tuple_fields() {
+    let lumos_code = r#"
+        #[solana]
+        struct Pool {
+            pair: (u64, PublicKey),
+            shares: Vec<(PublicKey, u16)>,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let ts_code = generate_module(&ir);
+
+    assert!(ts_code.contains("pair: [number, PublicKey];"));
+    assert!(ts_code.contains("shares: [PublicKey, number][];"));
+    // Tuples have no prefix: elements are laid out back to back
+    assert!(ts_code
+        .contains("tupleLayout(borsh.struct([borsh.u64('0'), borsh.publicKey('1')]))('pair'),"));
+    assert!(ts_code.contains(
+        "borsh.vec(tupleLayout(borsh.struct([borsh.publicKey('0'), borsh.u16('1')])))('shares'),"
+    ));
+
+    // and decode to the array their type promises, not an object keyed by index
+    assert!(ts_code.contains("function tupleLayout<T extends unknown[]>("));
+    assert!(ts_code.contains(
+        "decode: (b: Buffer, offset?: number) => Object.values(layout.decode(b, offset)) as T,"
+    ));
+    assert!(ts_code.contains(
+        "encode: (src: T, b: Buffer, offset?: number) => layout.encode({ ...src }, b, offset),"
+    ));
+}
+
+#[test]
+fn test_generate_generic_instance_schemas() {
+    let lumos_code = r#"
+        #[solana]
+        struct Wrapper<T> {
+            value: T,
+        }
+
+        #[solana]
+        enum Maybe<T> {
+            Nothing,
+            Just(T),
+        }
+
+        #[solana]
+        struct Holder {
+            a: Wrapper<u128>,
+            b: Maybe<Wrapper<PublicKey>>,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let ts_code = generate_module(&ir);
+
+    // Interfaces stay generic
+    assert!(ts_code.contains("export interface Wrapper<T>"));
+    assert!(ts_code.contains("a: Wrapper<bigint>;"));
+    assert!(ts_code.contains("b: Maybe<Wrapper<PublicKey>>;"));
+
+    // Borsh schemas are concrete, one per instantiation
+    assert!(
+        ts_code.contains("export const WrapperU128Schema = borsh.struct([\n  borsh.u128('value'),")
+    );
+    assert!(ts_code.contains("export const WrapperPublicKeySchema = borsh.struct(["));
+    assert!(ts_code.contains("export const MaybeWrapperPublicKeySchema = borsh.rustEnum(["));
+    assert!(ts_code.contains("WrapperU128Schema('a'),"));
+    assert!(ts_code.contains("MaybeWrapperPublicKeySchema('b'),"));
+    assert!(!ts_code.contains("WrapperSchema"));
+    assert!(!ts_code.contains("Generic parameter"));
+}
+
+#[test]
+fn test_generate_recursive_types_with_box() {
+    let lumos_code = r#"
+        #[solana]
+        struct Node {
+            value: u64,
+            children: Vec<Node>,
+            next: Option<Box<Node>>,
+            expr: Expr,
+        }
+
+        #[solana]
+        enum Expr {
+            Literal(u64),
+            Negate(Box<Expr>),
+            Sum { terms: Vec<Expr> },
+        }
+
+        #[solana]
+        struct Tree<T> {
+            value: T,
+            children: Vec<Tree<T>>,
+        }
+
+        #[solana]
+        struct Forest {
+            trees: Vec<Tree<u8>>,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let ts_code = generate_module(&ir);
+
+    // A schema cannot read its own const, or one defined after it, while it is built
+    assert!(ts_code.contains("borsh.vec(lazyLayout(() => NodeSchema))('children'),"));
+    assert!(ts_code.contains("borsh.option(lazyLayout(() => NodeSchema))('next'),"));
+    assert!(ts_code.contains("lazyLayout(() => ExprSchema)('expr'),"));
+    assert!(ts_code.contains("borsh.tuple([\n    lazyLayout(() => ExprSchema),\n  ], 'Negate'),"));
+    assert!(ts_code.contains("borsh.vec(lazyLayout(() => ExprSchema))('terms'),"));
+    assert!(ts_code.contains("borsh.vec(lazyLayout(() => TreeU8Schema))('children'),"));
+    assert!(ts_code.contains("function lazyLayout<T>("));
+
+    // Schemas already defined are used directly
+    assert!(ts_code.contains("borsh.vec(TreeU8Schema)('trees'),"));
+
+    // Single types refer to themselves lazily too
+    let ts_code = generate(&ir[1]);
+    assert!(ts_code.contains("borsh.tuple([\n    lazyLayout(() => ExprSchema),\n  ], 'Negate'),"));
+    assert!(ts_code.contains("function lazyLayout<T>("));
+}
+
+#[test]
+fn test_generate_flags_enum() {
+    let lumos_code = r#"
+        #[solana]
+        #[flags(u32)]
+        enum Permissions {
+            Mint,
+            Burn,
+            Root = 2147483648,
+        }
+
+        #[solana]
+        struct Authority {
+            permissions: Permissions,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let ts_code = generate_module(&ir);
+
+    assert!(ts_code.contains("export type Permissions = number;\n"));
+    assert!(ts_code.contains("  Mint: 1,\n  Burn: 2,\n  Root: 2147483648,\n"));
+    assert!(ts_code.contains("  all: 2147483651,\n"));
+    assert!(ts_code.contains("    return ((flags & flag) >>> 0) === flag;\n"));
+    assert!(ts_code.contains("    return (flags & ~flag) >>> 0;\n"));
+    assert!(ts_code.contains("export const PermissionsSchema = borsh.u32;\n"));
+    assert!(ts_code.contains("  permissions: Permissions;\n"));
+    assert!(ts_code.contains("  PermissionsSchema('permissions'),\n"));
+}
+
+#[test]
+fn test_generate_fixed_string_helpers() {
+    let lumos_code = r#"
+        #[solana]
+        struct Profile {
+            name: FixedString<32>,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let ts_code = generate_module(&ir);
+
+    assert!(ts_code.contains("  name: number[];\n"));
+    assert!(ts_code.contains("  borsh.array(borsh.u8, 32)('name'),\n"));
+    assert!(
+        ts_code.contains("export function fromFixedString(bytes: ArrayLike<number>): string {\n")
+    );
+    assert!(ts_code
+        .contains("export function toFixedString(value: string, size: number): number[] {\n"));
+
+    // Helpers are only emitted when a type needs them
+    let ast = parse_lumos_file("#[solana] struct Plain { name: String }").unwrap();
+    let ts_code = generate_module(&transform_to_ir(ast).unwrap());
+    assert!(!ts_code.contains("FixedString"));
+}
+
+#[test]
+fn test_generate_newtype_alias() {
+    let lumos_code = r#"
+        #[newtype]
+        type UserId = PublicKey;
+
+        #[solana]
+        struct Profile {
+            owner: UserId,
+        }
+    "#;
+
+    let ast = parse_lumos_file(lumos_code).expect("Failed to parse");
+    let ir = transform_to_ir(ast).expect("Failed to transform");
+    let ts_code = generate_module(&ir);
+
+    assert!(ts_code
+        .contains("export type UserId = PublicKey & { readonly __brand: { UserId: true } };\n"));
+    assert!(
+        ts_code.contains("export const UserId = (value: PublicKey): UserId => value as UserId;\n")
+    );
+    assert!(ts_code.contains("export const UserIdSchema = borsh.publicKey;\n"));
+    assert!(ts_code.contains("  owner: UserId;\n"));
+    assert!(ts_code.contains("  UserIdSchema('owner'),\n"));
+}
+
+#[test]
+fn test_generate_module_tree() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.pop();
+    path.pop();
+    path.push("examples/modules/shared-names/main.lumos");
+
+    let ir = ProjectGraph::load(&path)
+        .expect("Failed to load project")
+        .resolve()
+        .expect("Failed to resolve project");
+    let files = generate_module_tree(&ir);
+
+    let file = |name: &str| {
+        files
+            .iter()
+            .find(|f| f.path.to_string_lossy().replace('\\', "/") == name)
+            .map(|f| f.content.as_str())
+            .unwrap_or_else(|| panic!("missing {}", name))
+    };
+
+    // Each module has a barrel re-exporting its types and children
+    let index = file("index.ts");
+    assert!(index.contains("export * from './_types';"));
+    assert!(index.contains("export * as models from './models/index';"));
+    assert!(index.contains("export * as settings from './settings/index';"));
+    assert!(file("models/index.ts").contains("export * as user from './user/index';"));
+
+    // The clashing Config is imported under an alias, schema included
+    let root = file("_types.ts");
+    assert!(root.contains(
+        "import { Config as SettingsConfig, ConfigSchema as SettingsConfigSchema } from './settings/_types';"
+    ));
+    assert!(root.contains("export interface Config {"));
+    assert!(root.contains("settings: SettingsConfig;"));
+    assert!(root.contains("SettingsConfigSchema('settings')"));
+
+    let user = file("models/user/_types.ts");
+    assert!(user.contains("import { Stamp, StampSchema } from '../_types';"));
+    assert!(user.contains("import { Config, ConfigSchema } from '../../settings/_types';"));
+    assert!(user.contains("config: Config;"));
+}
+
+#[test]
+fn test_generate_module_tree_reexports() {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    path.pop();
+    path.pop();
+    path.push("examples/modules/reexports/main.lumos");
+
+    let ir = ProjectGraph::load(&path)
+        .expect("Failed to load project")
+        .resolve()
+        .expect("Failed to resolve project");
+    let files = generate_module_tree(&ir);
+
+    let file = |name: &str| {
+        files
+            .iter()
+            .find(|f| f.path.to_string_lossy().replace('\\', "/") == name)
+            .map(|f| f.content.as_str())
+            .unwrap_or_else(|| panic!("missing {}", name))
+    };
+
+    // The barrel exports re-exported types under their new names
+    let models = file("models/index.ts");
+    assert!(models
+        .contains("export { User as Member, UserSchema as MemberSchema } from './user/_types';"));
+    assert!(models.contains("export { Balance, BalanceSchema } from './account/_types';"));
+    assert!(!models.contains("Ledger"));
+
+    // Other modules import the declaration under the re-exported name
+    let root = file("_types.ts");
+    assert!(root.contains(
+        "import { User as Member, UserSchema as MemberSchema } from './models/user/_types';"
+    ));
+    assert!(root.contains("owner: Member;"));
+}
+
+#[test]
+fn test_generate_module_tree_reserved_module_names() {
+    let dir = tempfile::TempDir::new().unwrap();
+    fs::write(
+        dir.path().join("main.lumos"),
+        "mod types;\nmod index;\n\n#[solana]\nstruct Root { id: u64 }\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("types.lumos"),
+        "#[solana]\npub struct Amount { value: u64 }\n",
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("index.lumos"),
+        "#[solana]\npub struct Entry { key: u64 }\n",
+    )
+    .unwrap();
+
+    let ir = ProjectGraph::load(&dir.path().join("main.lumos"))
+        .expect("Failed to load project")
+        .resolve()
+        .expect("Failed to resolve project");
+    let files = generate_module_tree(&ir);
+    let paths: Vec<String> = files
+        .iter()
+        .map(|f| f.path.to_string_lossy().replace('\\', "/"))
+        .collect();
+
+    // Modules named `types` and `index` get folders that no import mistakes for the root's files
+    for path in ["_types.ts", "index.ts", "types/_types.ts", "index/index.ts"] {
+        assert!(paths.iter().any(|p| p == path), "missing {}", path);
+    }
+    let index = &files
+        .iter()
+        .find(|f| f.path.to_string_lossy() == "index.ts")
+        .unwrap()
+        .content;
+    assert!(index.contains("export * from './_types';"));
+    assert!(index.contains("export * as types from './types/index';"));
+    assert!(index.contains("export * as index from './index/index';"));
+}
@@ -4,6 +4,8 @@
 //! Core LSP server implementation
 
 use dashmap::DashMap;
+use std::collections::HashMap;
+use std::path::PathBuf;
 use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer};
@@ -27,6 +29,9 @@ pub struct LumosLanguageServer {
     /// Document cache (URI -> content)
     documents: DashMap<String, String>,
 
+    /// Entry file of the project each known schema file belongs to
+    project_roots: DashMap<PathBuf, PathBuf>,
+
     /// Diagnostics handler
     diagnostics: DiagnosticsHandler,
 
@@ -46,6 +51,7 @@ impl LumosLanguageServer {
         Self {
             client,
             documents: DashMap::new(),
+            project_roots: DashMap::new(),
             diagnostics: DiagnosticsHandler::new(),
             completion: CompletionHandler::new(),
             hover: HoverHandler::new(),
@@ -58,13 +64,66 @@ impl LumosLanguageServer {
         // Cache document content
         self.documents.insert(uri.to_string(), text.clone());
 
-        // Run diagnostics
-        let diagnostics = self.diagnostics.analyze(&text);
+        // Documents that are not files on disk are checked on their own
+        let Some(path) = uri.to_file_path().ok().and_then(|p| p.canonicalize().ok()) else {
+            let diagnostics = self.diagnostics.analyze(&text);
+            self.client
+                .publish_diagnostics(uri, diagnostics, None)
+                .await;
+            return;
+        };
+
+        // Run diagnostics against the project the file was last seen in
+        let entry = self
+            .project_roots
+            .get(&path)
+            .map(|entry| entry.clone())
+            .unwrap_or_else(|| path.clone());
+        let report = self
+            .diagnostics
+            .analyze_file(&entry, &path, &text, &self.open_sources());
+
+        match &report.root {
+            Some(root) => {
+                for member in &report.members {
+                    self.project_roots.insert(member.clone(), root.clone());
+                }
+            }
+            None => {
+                self.project_roots.remove(&path);
+            }
+        }
 
-        // Publish diagnostics to client
-        self.client
-            .publish_diagnostics(uri, diagnostics, None)
-            .await;
+        // Publish diagnostics to client, for every open file of the project
+        for (file, diagnostics) in report.files {
+            let file_uri = if file == path {
+                uri.clone()
+            } else {
+                match Url::from_file_path(&file) {
+                    Ok(file_uri) => file_uri,
+                    Err(()) => continue,
+                }
+            };
+            self.client
+                .publish_diagnostics(file_uri, diagnostics, None)
+                .await;
+        }
+    }
+
+    /// Text of every open document that is a file on disk, by canonical path
+    fn open_sources(&self) -> HashMap<PathBuf, String> {
+        self.documents
+            .iter()
+            .filter_map(|document| {
+                let path = Url::parse(document.key())
+                    .ok()?
+                    .to_file_path()
+                    .ok()?
+                    .canonicalize()
+                    .ok()?;
+                Some((path, document.value().clone()))
+            })
+            .collect()
     }
 }
 
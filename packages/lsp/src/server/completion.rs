@@ -263,8 +263,55 @@ impl CompletionHandler {
         vec![
             Self::create_attribute_name_item("solana", "Mark as Solana-compatible type", "solana]"),
             Self::create_attribute_name_item("account", "Mark as Anchor account", "account]"),
+            Self::create_attribute_name_item("event", "Mark as Anchor event", "event]"),
+            Self::create_attribute_name_item(
+                "error_code",
+                "Mark enum as Anchor program errors",
+                "error_code]",
+            ),
+            Self::create_attribute_name_item("msg", "Set error message", "msg(\"$1\")]"),
+            Self::create_attribute_name_item(
+                "repr",
+                "Set enum discriminant width",
+                "repr(${1|u8,u16,u32|})]",
+            ),
+            Self::create_attribute_name_item(
+                "flags",
+                "Encode a unit enum as a bitmask of its variants",
+                "flags(${1|u8,u16,u32|})]",
+            ),
+            Self::create_attribute_name_item(
+                "newtype",
+                "Make a type alias a distinct type",
+                "newtype]",
+            ),
+            Self::create_attribute_name_item(
+                "zero_copy",
+                "Store struct in C layout (bytemuck Pod)",
+                "zero_copy]",
+            ),
             Self::create_attribute_name_item("key", "Mark field as unique key", "key]"),
             Self::create_attribute_name_item("max", "Set maximum array/string length", "max($1)]"),
+            Self::create_attribute_name_item(
+                "range",
+                "Restrict integer field to a range",
+                "range(${1:0}..=${2:100})]",
+            ),
+            Self::create_attribute_name_item(
+                "pattern",
+                "Require string field to match a regex",
+                "pattern(\"$1\")]",
+            ),
+            Self::create_attribute_name_item(
+                "default",
+                "Set the field's default value",
+                "default($1)]",
+            ),
+            Self::create_attribute_name_item(
+                "rename",
+                "Name the field or type differently in some languages",
+                "rename(ts = \"$1\")]",
+            ),
             Self::create_attribute_name_item(
                 "deprecated",
                 "Mark field as deprecated",
@@ -273,7 +320,7 @@ impl CompletionHandler {
         ]
     }
 
-    /// Container type completions (Vec, Option)
+    /// Container type completions (Vec, Option, maps, sets)
     fn container_type_completions() -> Vec<CompletionItem> {
         vec![
             CompletionItem {
@@ -300,6 +347,66 @@ impl CompletionHandler {
                 insert_text_format: Some(InsertTextFormat::SNIPPET),
                 ..Default::default()
             },
+            CompletionItem {
+                label: "HashMap".to_string(),
+                kind: Some(CompletionItemKind::CLASS),
+                detail: Some("Key-value map type".to_string()),
+                documentation: Some(Documentation::MarkupContent(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: "Key-value map type.\n\n**Usage**: `HashMap<Key, Value>`\n**Example**: `balances: HashMap<PublicKey, u64>`".to_string(),
+                })),
+                insert_text: Some("HashMap<$1, $2>".to_string()),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "BTreeMap".to_string(),
+                kind: Some(CompletionItemKind::CLASS),
+                detail: Some("Ordered key-value map type".to_string()),
+                documentation: Some(Documentation::MarkupContent(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: "Key-value map type ordered by key.\n\n**Usage**: `BTreeMap<Key, Value>`\n**Example**: `scores: BTreeMap<u32, u64>`".to_string(),
+                })),
+                insert_text: Some("BTreeMap<$1, $2>".to_string()),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "HashSet".to_string(),
+                kind: Some(CompletionItemKind::CLASS),
+                detail: Some("Unique value set type".to_string()),
+                documentation: Some(Documentation::MarkupContent(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: "Set of unique values.\n\n**Usage**: `HashSet<Type>`\n**Example**: `members: HashSet<PublicKey>`".to_string(),
+                })),
+                insert_text: Some("HashSet<$1>".to_string()),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "BTreeSet".to_string(),
+                kind: Some(CompletionItemKind::CLASS),
+                detail: Some("Ordered unique value set type".to_string()),
+                documentation: Some(Documentation::MarkupContent(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: "Set of unique values in sorted order.\n\n**Usage**: `BTreeSet<Type>`\n**Example**: `ids: BTreeSet<u64>`".to_string(),
+                })),
+                insert_text: Some("BTreeSet<$1>".to_string()),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            },
+            CompletionItem {
+                label: "FixedString".to_string(),
+                kind: Some(CompletionItemKind::CLASS),
+                detail: Some("Fixed-capacity string type".to_string()),
+                documentation: Some(Documentation::MarkupContent(MarkupContent {
+                    kind: MarkupKind::Markdown,
+                    value: "Zero-padded UTF-8 string stored as `[u8; N]`.\n\n**Usage**: `FixedString<N>`\n**Example**: `name: FixedString<32>`".to_string(),
+                })),
+                insert_text: Some("FixedString<${1:32}>".to_string()),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            },
         ]
     }
 
@@ -324,6 +431,18 @@ impl CompletionHandler {
                 insert_text_format: Some(InsertTextFormat::SNIPPET),
                 ..Default::default()
             },
+            CompletionItem {
+                label: "const".to_string(),
+                kind: Some(CompletionItemKind::KEYWORD),
+                detail: Some("Define a constant".to_string()),
+                documentation: Some(Documentation::String(
+                    "Define an integer constant usable in array sizes and #[max] bounds"
+                        .to_string(),
+                )),
+                insert_text: Some("const $1: usize = $2;".to_string()),
+                insert_text_format: Some(InsertTextFormat::SNIPPET),
+                ..Default::default()
+            },
         ]
     }
 
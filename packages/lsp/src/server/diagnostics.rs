@@ -3,10 +3,28 @@
 
 //! Diagnostics handler for LUMOS LSP
 
+use lumos_core::diagnostics::{
+    check_project_file, check_schema, Diagnostic as SchemaDiagnostic, Severity,
+};
 use lumos_core::parser::parse_lumos_file;
-use lumos_core::transform::transform_to_ir;
+use lumos_core::project::{has_dependencies, ProjectGraph};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use tower_lsp::lsp_types::*;
 
+/// Diagnostics of a document checked together with its project
+#[derive(Debug, Default)]
+pub struct ProjectDiagnostics {
+    /// Entry file of the project, `None` when the document was checked on its own
+    pub root: Option<PathBuf>,
+
+    /// Files of the project
+    pub members: Vec<PathBuf>,
+
+    /// Diagnostics of the document and of every other open file in the project
+    pub files: Vec<(PathBuf, Vec<Diagnostic>)>,
+}
+
 /// Diagnostics handler
 #[derive(Debug)]
 pub struct DiagnosticsHandler;
@@ -18,34 +36,91 @@ impl DiagnosticsHandler {
     }
 
     /// Analyze document and return diagnostics
+    ///
+    /// Every independent parse or validation error is reported, along with
+    /// deprecation and generic-type warnings for valid schemas.
     pub fn analyze(&self, text: &str) -> Vec<Diagnostic> {
-        let mut diagnostics = Vec::new();
+        check_schema(text)
+            .diagnostics
+            .iter()
+            .map(Self::to_lsp_diagnostic)
+            .collect()
+    }
+
+    /// Analyze a document as part of the project loaded from `entry`
+    ///
+    /// Imported types and modules are resolved against the other files of
+    /// the project, taking the text of open documents from `sources`. A
+    /// document that does not parse, or an entry file without imports or
+    /// modules, is analyzed on its own.
+    pub fn analyze_file(
+        &self,
+        entry: &Path,
+        file: &Path,
+        text: &str,
+        sources: &HashMap<PathBuf, String>,
+    ) -> ProjectDiagnostics {
+        let standalone = || ProjectDiagnostics {
+            files: vec![(file.to_path_buf(), self.analyze(text))],
+            ..Default::default()
+        };
 
-        // Step 1: Parse LUMOS file
         match parse_lumos_file(text) {
-            Ok(ast) => {
-                // Step 2: Transform to IR (validates types)
-                if let Err(e) = transform_to_ir(ast) {
-                    // Type validation errors
-                    diagnostics.push(Self::error_to_diagnostic(&e));
-                }
-            }
-            Err(e) => {
-                // Parse errors
-                diagnostics.push(Self::error_to_diagnostic(&e));
+            Ok(ast) if entry != file || has_dependencies(&ast) => {}
+            _ => return standalone(),
+        }
+
+        let graph = match ProjectGraph::load_with_sources(entry, sources) {
+            Ok(graph) => graph,
+            Err(e) if entry == file => {
+                let diagnostics = SchemaDiagnostic::from_error(&e)
+                    .iter()
+                    .map(Self::to_lsp_diagnostic)
+                    .collect();
+                return ProjectDiagnostics {
+                    files: vec![(file.to_path_buf(), diagnostics)],
+                    ..Default::default()
+                };
             }
+            // The project this file belonged to no longer loads
+            Err(_) => return self.analyze_file(file, file, text, sources),
+        };
+
+        // The file is no longer part of the project it was last seen in
+        if !graph.contains(file) {
+            return self.analyze_file(file, file, text, sources);
         }
 
-        diagnostics
+        let files = graph
+            .files()
+            .iter()
+            .filter(|f| f.path == file || sources.contains_key(&f.path))
+            .map(|f| {
+                let diagnostics = check_project_file(&graph, &f.path)
+                    .diagnostics
+                    .iter()
+                    .map(Self::to_lsp_diagnostic)
+                    .collect();
+                (f.path.clone(), diagnostics)
+            })
+            .collect();
+
+        ProjectDiagnostics {
+            root: Some(graph.root().to_path_buf()),
+            members: graph.files().iter().map(|f| f.path.clone()).collect(),
+            files,
+        }
     }
 
-    /// Convert LUMOS error to LSP diagnostic
-    fn error_to_diagnostic(error: &lumos_core::error::LumosError) -> Diagnostic {
-        // Extract error message
-        let message = error.to_string();
+    /// Convert a LUMOS schema diagnostic to an LSP diagnostic
+    fn to_lsp_diagnostic(diagnostic: &SchemaDiagnostic) -> Diagnostic {
+        let message = &diagnostic.message;
 
-        // Try to extract line/column from error message
-        let (line, column) = Self::extract_location_from_error(&message);
+        // Prefer the attached location, falling back to one embedded in the message
+        let (line, column) = match &diagnostic.location {
+            Some(location) => (location.line, location.column),
+            None => Self::extract_location_from_error(message),
+        };
 
         // Create LSP range (0-indexed for LSP)
         let range = Range {
@@ -59,19 +134,16 @@ impl DiagnosticsHandler {
             },
         };
 
-        // Determine severity based on error type
-        let severity = match error {
-            lumos_core::error::LumosError::SchemaParse(_, _) => DiagnosticSeverity::ERROR,
-            lumos_core::error::LumosError::TypeValidation(_, _) => DiagnosticSeverity::ERROR,
-            lumos_core::error::LumosError::CodeGen(_) => DiagnosticSeverity::WARNING,
-            _ => DiagnosticSeverity::ERROR,
+        let severity = match diagnostic.severity {
+            Severity::Error => DiagnosticSeverity::ERROR,
+            Severity::Warning => DiagnosticSeverity::WARNING,
         };
 
         Diagnostic {
             range,
             severity: Some(severity),
             source: Some("lumos".to_string()),
-            message: Self::clean_error_message(&message),
+            message: Self::clean_error_message(message),
             ..Default::default()
         }
     }
@@ -181,6 +253,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_diagnostics_report_every_error() {
+        let handler = DiagnosticsHandler::new();
+        let text = "struct Account {\n    owner: Wallet,\n    balance: u64,\n    balance: u64,\n}\n\nstruct Vault {\n    data: [u8; 0],\n}\n";
+
+        let diagnostics = handler.analyze(text);
+        let lines: Vec<u32> = diagnostics.iter().map(|d| d.range.start.line).collect();
+        assert_eq!(lines, vec![1, 3, 7]);
+        assert!(diagnostics[0].message.contains("Wallet"));
+        assert!(diagnostics[1].message.contains("Duplicate field 'balance'"));
+        assert!(diagnostics[2]
+            .message
+            .contains("Array size must be greater than 0"));
+    }
+
+    #[test]
+    fn test_deprecated_field_is_a_warning() {
+        let handler = DiagnosticsHandler::new();
+        let text = "struct Account {\n    #[deprecated]\n    old: u64,\n}\n";
+
+        let diagnostics = handler.analyze(text);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(diagnostics[0].range.start.line, 2);
+    }
+
     #[test]
     fn test_extract_location_from_error() {
         let message = "Failed to parse at line 5, column 12";
@@ -195,4 +293,42 @@ mod tests {
         let cleaned = DiagnosticsHandler::clean_error_message(message);
         assert_eq!(cleaned, "Undefined type 'Foo'");
     }
+
+    #[test]
+    fn test_diagnostics_resolve_imports() {
+        let handler = DiagnosticsHandler::new();
+        let dir = tempfile::TempDir::new().unwrap();
+        let main = dir.path().join("main.lumos");
+        let types = dir.path().join("types.lumos");
+        let main_text =
+            "import { Amount } from \"./types.lumos\";\n\nstruct Vault {\n    balance: Amount,\n}\n";
+        std::fs::write(&main, main_text).unwrap();
+        std::fs::write(&types, "type Amount = u64;\n").unwrap();
+        let main = main.canonicalize().unwrap();
+        let types = types.canonicalize().unwrap();
+
+        let report = handler.analyze_file(&main, &main, main_text, &HashMap::new());
+        assert_eq!(report.root.as_ref(), Some(&main));
+        assert_eq!(report.members, vec![types.clone(), main.clone()]);
+        assert_eq!(report.files.len(), 1);
+        assert!(report.files[0].1.is_empty(), "{:?}", report.files);
+
+        // Unsaved edits of open files are used instead of the file on disk
+        let sources = HashMap::from([(types.clone(), "type Total = u64;\n".to_string())]);
+        let report = handler.analyze_file(&main, &types, &sources[&types], &sources);
+        let (file, diagnostics) = &report.files[0];
+        assert_eq!(file, &types);
+        // The broken import in main.lumos is reported with the file it is in
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "main.lumos: Type 'Amount' not found in './types.lumos'"
+        );
+
+        let report = handler.analyze_file(&main, &main, main_text, &sources);
+        let diagnostics = &report.files[0].1;
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start.line, 0);
+        assert!(diagnostics[0].message.contains("Amount"));
+    }
 }
@@ -57,6 +57,14 @@ impl FormattingHandler {
     fn format_file(&self, file: &LumosFile) -> String {
         let mut output = String::new();
 
+        // File-level attributes come first
+        for attr in &file.attributes {
+            output.push_str(&format!("#![{}]\n", self.format_attribute(attr)));
+        }
+        if !file.attributes.is_empty() {
+            output.push('\n');
+        }
+
         // Format use statements
         for use_stmt in &file.imports {
             output.push_str(&self.format_import(use_stmt));
@@ -103,6 +111,7 @@ impl FormattingHandler {
             Item::Struct(s) => self.format_struct_def(s, indent_level),
             Item::Enum(e) => self.format_enum_def(e, indent_level),
             Item::TypeAlias(t) => self.format_type_alias(t, indent_level),
+            Item::Const(c) => self.format_const(c, indent_level),
             Item::Module(m) => self.format_module(m, indent_level),
             Item::Use(u) => self.format_use(u, indent_level),
         }
@@ -237,6 +246,22 @@ impl FormattingHandler {
         )
     }
 
+    /// Format a constant definition
+    fn format_const(&self, const_def: &ConstDef, indent_level: usize) -> String {
+        let indent = self.indent(indent_level);
+
+        // Format visibility
+        let visibility = match const_def.visibility {
+            Visibility::Public => "pub ",
+            Visibility::Private => "",
+        };
+
+        format!(
+            "{}{}const {}: {} = {};",
+            indent, visibility, const_def.name, const_def.type_name, const_def.value
+        )
+    }
+
     /// Format a module declaration
     fn format_module(&self, module: &Module, indent_level: usize) -> String {
         let indent = self.indent(indent_level);
@@ -254,11 +279,17 @@ impl FormattingHandler {
     fn format_use(&self, use_stmt: &UseStatement, indent_level: usize) -> String {
         let indent = self.indent(indent_level);
         let path = self.format_module_path(&use_stmt.path);
+        let visibility = match use_stmt.visibility {
+            Visibility::Public => "pub ",
+            Visibility::Private => "",
+        };
 
-        if let Some(alias) = &use_stmt.alias {
-            format!("{}use {} as {};", indent, path, alias)
+        if use_stmt.glob {
+            format!("{}{}use {}::*;", indent, visibility, path)
+        } else if let Some(alias) = &use_stmt.alias {
+            format!("{}{}use {} as {};", indent, visibility, path, alias)
         } else {
-            format!("{}use {};", indent, path)
+            format!("{}{}use {};", indent, visibility, path)
         }
     }
 
@@ -287,6 +318,43 @@ impl FormattingHandler {
             TypeSpec::FixedArray { element, size } => {
                 format!("[{}; {}]", self.format_type_spec(element), size)
             }
+            TypeSpec::Map {
+                key,
+                value,
+                ordered,
+            } => format!(
+                "{}<{}, {}>",
+                if *ordered { "BTreeMap" } else { "HashMap" },
+                self.format_type_spec(key),
+                self.format_type_spec(value)
+            ),
+            TypeSpec::Set { element, ordered } => format!(
+                "{}<{}>",
+                if *ordered { "BTreeSet" } else { "HashSet" },
+                self.format_type_spec(element)
+            ),
+            // A one-element tuple keeps its trailing comma: `(T,)`
+            TypeSpec::Tuple(elements) if elements.len() == 1 => {
+                format!("({},)", self.format_type_spec(&elements[0]))
+            }
+            TypeSpec::Tuple(elements) => format!(
+                "({})",
+                elements
+                    .iter()
+                    .map(|e| self.format_type_spec(e))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            TypeSpec::Boxed(inner) => format!("Box<{}>", self.format_type_spec(inner)),
+            TypeSpec::FixedString(size) => format!("FixedString<{}>", size),
+            TypeSpec::Instance { name, args } => format!(
+                "{}<{}>",
+                name,
+                args.iter()
+                    .map(|a| self.format_type_spec(a))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
         }
     }
 
@@ -297,6 +365,17 @@ impl FormattingHandler {
             Some(AttributeValue::Integer(n)) => format!("{} = {}", attr.name, n),
             Some(AttributeValue::Bool(b)) => format!("{} = {}", attr.name, b),
             Some(AttributeValue::List(items)) => format!("{}({})", attr.name, items.join(", ")),
+            Some(AttributeValue::Range(range)) => format!("{}({})", attr.name, range),
+            Some(AttributeValue::Default(value)) => format!("{}({})", attr.name, value),
+            Some(AttributeValue::Names(names)) => format!(
+                "{}({})",
+                attr.name,
+                names
+                    .iter()
+                    .map(|(key, name)| format!("{} = {:?}", key, name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
             None => attr.name.clone(),
         }
     }
@@ -360,6 +439,18 @@ experience:u64,
         assert!(formatted.contains("    Finished,"));
     }
 
+    #[test]
+    fn test_format_pub_and_glob_use() {
+        let input = "pub   use crate::models::User;\nuse  super::* ;";
+
+        let formatter = FormattingHandler::new();
+        let edits = formatter.format(input).unwrap();
+
+        let formatted = &edits[0].new_text;
+        assert!(formatted.contains("pub use crate::models::User;"));
+        assert!(formatted.contains("use super::*;"));
+    }
+
     #[test]
     fn test_already_formatted() {
         // Canonical format: pub visibility (LUMOS default) and no trailing newline
@@ -376,4 +467,21 @@ experience:u64,
         // Should return empty edits if already formatted
         assert_eq!(edits.len(), 0);
     }
+
+    #[test]
+    fn test_format_renames_and_naming_policy() {
+        let input = r#"#![rename_fields(ts="camelCase")]
+#[rename(ts="TradeListing")] struct Listing { #[rename(ts="feeRate", py="fee")] fee_bps: u16 }"#;
+
+        let formatter = FormattingHandler::new();
+        let edits = formatter.format(input).unwrap();
+        assert_eq!(edits.len(), 1);
+
+        let formatted = &edits[0].new_text;
+        assert!(formatted.starts_with("#![rename_fields(ts = \"camelCase\")]\n\n"));
+        assert!(formatted.contains("#[rename(ts = \"TradeListing\")]\npub struct Listing {"));
+        assert!(
+            formatted.contains("    #[rename(ts = \"feeRate\", py = \"fee\")]\n    fee_bps: u16,")
+        );
+    }
 }
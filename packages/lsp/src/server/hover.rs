@@ -140,6 +140,27 @@ impl HoverHandler {
                 **Rust**: `Option<T>` (Some/None)  \n\
                 **TypeScript**: `T | undefined`"
             }
+            "HashMap" | "BTreeMap" => {
+                "**HashMap\\<K, V> / BTreeMap\\<K, V>** - Key-value map\n\n\
+                **Example**: `balances: HashMap<PublicKey, u64>`  \n\
+                **Rust**: `HashMap<K, V>` / `BTreeMap<K, V>`  \n\
+                **TypeScript**: `Map<K, V>`  \n\
+                **Note**: Borsh encodes both with entries sorted by key; use `#[max(n)]` to bound entries"
+            }
+            "HashSet" | "BTreeSet" => {
+                "**HashSet\\<T> / BTreeSet\\<T>** - Set of unique values\n\n\
+                **Example**: `members: HashSet<PublicKey>`  \n\
+                **Rust**: `HashSet<T>` / `BTreeSet<T>`  \n\
+                **TypeScript**: `T[]`  \n\
+                **Note**: Borsh encodes both with elements sorted; use `#[max(n)]` to bound elements"
+            }
+            "FixedString" => {
+                "**FixedString\\<N>** - Fixed-capacity string\n\n\
+                **Example**: `name: FixedString<32>`  \n\
+                **Rust**: `[u8; N]` with `name_str()` / `set_name()`  \n\
+                **TypeScript**: `number[]` with `fromFixedString` / `toFixedString`  \n\
+                **Note**: Always N bytes of zero-padded UTF-8; values are truncated to fit"
+            }
 
             // Keywords
             "struct" => {
@@ -177,6 +198,54 @@ impl HoverHandler {
                 **Applies to**: structs only  \n\
                 **Generates**: Anchor Account trait implementation"
             }
+            "event" => {
+                "**#[event]** - Mark as Anchor event\n\n\
+                Indicates this struct is emitted with Anchor's `emit!`.  \n\
+                **Requires**: `#[solana]` attribute  \n\
+                **Applies to**: structs only  \n\
+                **Generates**: IDL event with discriminator, TypeScript log decoder"
+            }
+            "error_code" => {
+                "**#[error_code]** - Mark enum as Anchor program errors\n\n\
+                Each unit variant becomes an error code, starting at 6000.  \n\
+                **Example**: `#[error_code(offset = 7000)]`  \n\
+                **Applies to**: enums with unit variants  \n\
+                **Generates**: IDL errors, TypeScript error map with `fromCode()`"
+            }
+            "msg" => {
+                "**#[msg(\"...\")]** - Set error message\n\n\
+                Message reported for this error variant.  \n\
+                **Example**: `#[msg(\"Insufficient funds\")]`  \n\
+                **Applies to**: variants of `#[error_code]` enums"
+            }
+            "repr" => {
+                "**#[repr(u8 | u16 | u32)]** - Set enum discriminant width\n\n\
+                Encodes the variant tag as a `u8` (default), `u16` or `u32`.  \n\
+                Variants may pin their tag with `Variant = N`.  \n\
+                **Example**: `#[repr(u16)]`  \n\
+                **Applies to**: enums"
+            }
+            "flags" => {
+                "**#[flags(u8 | u16 | u32)]** - Bitflags enum\n\n\
+                Each unit variant is one bit of a packed integer, in declaration order.  \n\
+                Variants may pin their bit with `Variant = 8`.  \n\
+                **Example**: `#[flags(u16)] enum Permissions { Mint, Burn, Freeze }`  \n\
+                **Applies to**: enums with unit variants only"
+            }
+            "newtype" => {
+                "**#[newtype]** - Distinct type alias\n\n\
+                Generates a wrapper type instead of an alias, with the target's wire format.  \n\
+                **Example**: `#[newtype] type UserId = PublicKey;`  \n\
+                **Applies to**: type aliases  \n\
+                **Generates**: Rust tuple struct with `From`/`Deref`, TypeScript branded type"
+            }
+            "zero_copy" => {
+                "**#[zero_copy]** - Store struct in C layout\n\n\
+                Lays the struct out as `#[repr(C)]` with explicit padding fields.  \n\
+                Fields must be Pod: integers, floats, `PublicKey`, fixed arrays or other `#[zero_copy]` structs.  \n\
+                **Applies to**: structs  \n\
+                **Generates**: `#[account(zero_copy)]`, `AccountLoader` contexts, TypeScript C-layout decoder"
+            }
             "key" => {
                 "**#[key]** - Mark field as unique key\n\n\
                 Indicates this field is a unique identifier.  \n\
@@ -188,6 +257,39 @@ impl HoverHandler {
                 **Example**: `#[max(100)]`  \n\
                 **Applies to**: `Vec<T>` and `String` fields"
             }
+            "range" => {
+                "**#[range(a..=b)]** - Restrict an integer value\n\n\
+                Bounds are integers or constant names; `a..b`, `a..` and `..=b` also work.  \n\
+                **Example**: `#[range(0..=10000)]`  \n\
+                **Applies to**: integer struct fields (optional fields are checked when present)  \n\
+                **Generates**: `validate()` in Rust, Python, Go and Ruby, `validate{Name}()` in TypeScript"
+            }
+            "pattern" => {
+                "**#[pattern(\"regex\")]** - Require a string to match a regular expression\n\n\
+                **Example**: `#[pattern(\"^[A-Z]{1,10}$\")]`  \n\
+                **Applies to**: `String` struct fields (optional fields are checked when present)  \n\
+                **Generates**: `validate()` in Rust (needs the `regex` crate), Python, Go and Ruby, `validate{Name}()` in TypeScript"
+            }
+            "default" => {
+                "**#[default(value)]** - Set a field's default value\n\n\
+                Values are numbers, `true`/`false`, strings or constant names.  \n\
+                **Example**: `#[default(100)]`, `#[default(\"USDC\")]`  \n\
+                **Applies to**: integer, float, `bool` and `String` struct fields  \n\
+                **Used by**: `Default` helpers in every language and migrations adding the field"
+            }
+            "rename" => {
+                "**#[rename(target = \"name\", ...)]** - Use another name in some languages\n\n\
+                Targets are `ts`, `py` (also Seahorse), `go`, `rb` and `idl`; Rust keeps the schema name.  \n\
+                **Example**: `#[rename(ts = \"feeRate\", py = \"fee\")]`  \n\
+                **Applies to**: structs, enums and their fields  \n\
+                **Overrides**: the file's `#![rename_fields]`/`#![rename_types]` naming policy"
+            }
+            "rename_fields" | "rename_types" => {
+                "**#![rename_fields(..)]** / **#![rename_types(..)]** - File naming policy\n\n\
+                Convert every field or type name for a target to `camelCase`, `snake_case`, `PascalCase` or `SCREAMING_SNAKE_CASE`.  \n\
+                **Example**: `#![rename_fields(ts = \"camelCase\", idl = \"camelCase\")]`  \n\
+                **Note**: explicit `#[rename]`s win, and account, event and instruction types keep their IDL name"
+            }
             "deprecated" => {
                 "**#[deprecated]** - Mark field as deprecated\n\n\
                 Marks a field as deprecated with optional migration message.  \n\